@@ -3,52 +3,398 @@ use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::{
     fs,
+    io::Write,
     path::{Path, PathBuf},
 };
 use tauri::AppHandle;
 
+use crate::events;
+
+/// How often `account:not-backed-up` may be sent (it's checked on every start).
+const BACKUP_REMINDER_INTERVAL_SECS: i64 = 24 * 60 * 60;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AccountJson {
     pub address: String, // ss58 (qzo…)
     pub secret_phrase: Option<String>,
     pub seed: Option<String>,
     pub pub_key: Option<String>,
+    /// Set by `mark_account_backed_up` once the user confirmed writing the secret phrase
+    /// down; until then every start may remind them (`remind_backup`).
+    #[serde(default)]
+    pub backed_up: bool,
+    /// The 32-byte account id ("0x…"), decoded from `address` once. Each chain's
+    /// address is derived from it (`address_for`); `address` keeps the string as created.
+    #[serde(default)]
+    pub account_id: Option<String>,
+}
+
+// version 0: the bare object, written before the envelope
+impl crate::persist::Versioned for AccountJson {
+    const VERSION: u32 = 1;
+    const MIGRATIONS: &'static [crate::persist::Migration] = &[crate::persist::unchanged];
 }
 
 impl AccountJson {
-    pub fn load_from_file(path: &Path) -> Result<Self> {
-        let txt = fs::read_to_string(path)?;
-        let acct: AccountJson = serde_json::from_str(&txt)?;
-        Ok(acct)
+    /// The account id: `account_id`, else decoded from `address`.
+    pub fn id(&self) -> Option<[u8; 32]> {
+        self.account_id
+            .as_deref()
+            .and_then(|h| hex::decode(h.trim_start_matches("0x")).ok())
+            .and_then(|b| b.try_into().ok())
+            .or_else(|| crate::authorship::account_id(&self.address))
+    }
+
+    /// The address to use on `chain` (its SS58 prefix); the stored one for custom specs
+    /// or an id that doesn't decode.
+    pub fn address_for(&self, chain: &str) -> String {
+        match (self.id(), crate::chains::chain_info(chain)) {
+            (Some(id), Some(info)) => crate::authorship::ss58_encode(info.ss58_prefix, &id),
+            _ => self.address.clone(),
+        }
     }
 }
 
-pub async fn ensure_account_json(
-    _app: &AppHandle,
-    quantus_node_path: &PathBuf,
-    out_path: &PathBuf,
-) -> Result<AccountJson> {
-    if out_path.exists() {
-        // accept existing file if it has address/ss58
-        let txt = fs::read_to_string(out_path)?;
-        if let Ok(a) = serde_json::from_str::<AccountJson>(&txt) {
-            if !a.address.is_empty() {
-                return Ok(a);
+/// `address` re-encoded with `chain`'s SS58 prefix (unchanged if it doesn't decode or
+/// the chain isn't in the table).
+pub fn address_for_chain(address: &str, chain: &str) -> String {
+    match (
+        crate::authorship::account_id(address),
+        crate::chains::chain_info(chain),
+    ) {
+        (Some(id), Some(info)) => crate::authorship::ss58_encode(info.ss58_prefix, &id),
+        _ => address.to_string(),
+    }
+}
+
+/// The address the node mines to on `chain`: the `rewards_address` setting when one was
+/// chosen with `set_rewards_address`, else the account's.
+pub fn rewards_address(app: &AppHandle, acct: &AccountJson, chain: &str) -> String {
+    let chosen = crate::settings::get(app).rewards_address;
+    if chosen.is_empty() {
+        acct.address_for(chain)
+    } else {
+        address_for_chain(&chosen, chain)
+    }
+}
+
+/// The account file exists but holds no usable address.
+#[derive(Debug, thiserror::Error)]
+pub enum AccountError {
+    #[error("account file {} was unusable ({reason}); moved to {}", .path.display(), .quarantined.display())]
+    Quarantined {
+        path: PathBuf,
+        quarantined: PathBuf,
+        reason: String,
+    },
+    #[error("account file {} is unreadable ({reason}) and may still hold a key; confirm to set it aside and create a new account", .path.display())]
+    NeedsConfirmation { path: PathBuf, reason: String },
+    /// E.g. the placeholder format of early demo builds; the node would mine to nowhere.
+    #[error("the rewards address {address} in {} is not a valid SS58 address ({reason}). It was probably created by an early demo build and can't receive rewards: move the file aside (it holds no usable key) and press Start again to generate a real account, then back up its secret phrase", .path.display())]
+    InvalidAddress {
+        path: PathBuf,
+        address: String,
+        reason: String,
+    },
+}
+
+enum AccountFile {
+    Valid(AccountJson),
+    Missing,
+    /// `may_hold_key`: looks like a partial write of our own format, so replacing it
+    /// could abandon a key; empty or foreign content doesn't.
+    Corrupt {
+        reason: String,
+        may_hold_key: bool,
+    },
+}
+
+/// Whether `path` holds an account this app can load.
+pub(crate) fn is_readable_account(path: &Path) -> bool {
+    matches!(read_account_file(path), AccountFile::Valid(_))
+}
+
+fn read_account_file(path: &Path) -> AccountFile {
+    let txt = match fs::read(path) {
+        Ok(b) => String::from_utf8_lossy(&b).into_owned(),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return AccountFile::Missing,
+        Err(e) => {
+            return AccountFile::Corrupt {
+                reason: e.to_string(),
+                may_hold_key: true,
             }
         }
-        if let Ok(v) = serde_json::from_str::<serde_json::Value>(&txt) {
-            if let Some(addr) = v
-                .get("address")
-                .and_then(|x| x.as_str())
-                .or_else(|| v.get("ss58").and_then(|x| x.as_str()))
-            {
-                return Ok(AccountJson {
-                    address: addr.to_string(),
-                    secret_phrase: None,
-                    seed: None,
-                    pub_key: None,
-                });
+    };
+    let data = crate::persist::decode_value::<AccountJson>(txt.as_bytes()).map(|(v, _)| v);
+    let typed = data
+        .as_ref()
+        .ok()
+        .and_then(|v| serde_json::from_value::<AccountJson>(v.clone()).ok());
+    if let Some(a) = typed {
+        if !a.address.is_empty() {
+            return AccountFile::Valid(a);
+        }
+    }
+    // lenient: any JSON object with address/ss58
+    if let Ok(v) = &data {
+        if let Some(addr) = v
+            .get("address")
+            .and_then(|x| x.as_str())
+            .or_else(|| v.get("ss58").and_then(|x| x.as_str()))
+            .filter(|a| !a.is_empty())
+        {
+            return AccountFile::Valid(AccountJson {
+                address: addr.to_string(),
+                secret_phrase: None,
+                seed: None,
+                pub_key: None,
+                backed_up: v.get("backed_up").and_then(|x| x.as_bool()) == Some(true),
+                account_id: v
+                    .get("account_id")
+                    .and_then(|x| x.as_str())
+                    .map(str::to_string),
+            });
+        }
+    }
+    let reason = if txt.trim().is_empty() {
+        "empty file".to_string()
+    } else {
+        data.err()
+            .map_or_else(|| "no address field".to_string(), |e| e.to_string())
+    };
+    let may_hold_key = ["address", "ss58", "secret_phrase", "seed"]
+        .iter()
+        .any(|k| txt.contains(k));
+    AccountFile::Corrupt {
+        reason,
+        may_hold_key,
+    }
+}
+
+// Tell the UI (`account:corrupt`); `quarantined` is None while confirmation is pending.
+fn emit_corrupt(
+    app: &AppHandle,
+    path: &Path,
+    reason: &str,
+    quarantined: Option<&Path>,
+    may_hold_key: bool,
+) {
+    events::emit(
+        app,
+        events::ACCOUNT_CORRUPT,
+        &serde_json::json!({
+            "path": path,
+            "reason": reason,
+            "quarantined": quarantined,
+            "mayHoldKey": may_hold_key,
+        }),
+    );
+}
+
+/// Quarantine a corrupt file that can't hold a key (returns where it went); otherwise
+/// leave it for the user to confirm.
+fn handle_corrupt(
+    app: &AppHandle,
+    path: &Path,
+    reason: &str,
+    may_hold_key: bool,
+) -> Result<PathBuf> {
+    if may_hold_key {
+        emit_corrupt(app, path, reason, None, true);
+        return Err(AccountError::NeedsConfirmation {
+            path: path.to_path_buf(),
+            reason: reason.to_string(),
+        }
+        .into());
+    }
+    let dest = crate::persist::quarantine(path)?;
+    eprintln!(
+        "account: quarantined {} ({reason}) to {}",
+        path.display(),
+        dest.display()
+    );
+    emit_corrupt(app, path, reason, Some(&dest), false);
+    Ok(dest)
+}
+
+/// Load the account for a start; a corrupt file is reported (and quarantined when safe)
+/// so the next `ensure_account_json` can recover.
+pub fn load_account(app: &AppHandle, path: &Path) -> Result<AccountJson> {
+    match read_account_file(path) {
+        AccountFile::Valid(a) => Ok(with_account_id(path, a)),
+        AccountFile::Missing => Err(anyhow!("account file {} is missing", path.display())),
+        AccountFile::Corrupt {
+            reason,
+            may_hold_key,
+        } => {
+            let quarantined = handle_corrupt(app, path, &reason, may_hold_key)?;
+            Err(AccountError::Quarantined {
+                path: path.to_path_buf(),
+                quarantined,
+                reason,
             }
+            .into())
+        }
+    }
+}
+
+/// The user accepted losing whatever the unreadable account file held: set it aside.
+/// Refuses when the file is actually usable.
+pub fn confirm_account_reset(app: &AppHandle, path: &Path) -> Result<()> {
+    match read_account_file(path) {
+        AccountFile::Valid(_) => Err(anyhow!("account file is readable; nothing to reset")),
+        AccountFile::Missing => Ok(()),
+        AccountFile::Corrupt { reason, .. } => {
+            handle_corrupt(app, path, &reason, false).map(|_| ())
+        }
+    }
+}
+
+/// Basic shape check for a rewards address (ss58, base58 alphabet).
+pub fn validate_address(addr: &str) -> Result<()> {
+    const B58: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+    if addr.len() < 40 || addr.len() > 64 {
+        return Err(anyhow!("invalid address length: {}", addr.len()));
+    }
+    if let Some(c) = addr.chars().find(|c| !B58.contains(*c)) {
+        return Err(anyhow!("invalid address character: {c:?}"));
+    }
+    Ok(())
+}
+
+/// Before a start: `acct`'s address must decode as SS58 (base58 with a 1- or 2-byte
+/// prefix, a 32-byte account id and a 2-byte checksum). `validate_address` alone only
+/// checks the alphabet and length, which the old demo placeholders pass.
+pub fn check_ss58(path: &Path, acct: &AccountJson) -> Result<(), AccountError> {
+    let invalid = |reason: &str| AccountError::InvalidAddress {
+        path: path.to_path_buf(),
+        address: acct.address.clone(),
+        reason: reason.to_string(),
+    };
+    validate_address(&acct.address).map_err(|e| invalid(&e.to_string()))?;
+    if crate::authorship::account_id(&acct.address).is_none() {
+        return Err(invalid("it doesn't decode to a 32-byte account id"));
+    }
+    Ok(())
+}
+
+/// Record that the user backed up the account's secret phrase. Only the flag is added;
+/// everything else in the file is kept as is.
+pub fn mark_backed_up(path: &Path) -> Result<()> {
+    if !matches!(read_account_file(path), AccountFile::Valid(_)) {
+        return Err(anyhow!("no usable account file at {}", path.display()));
+    }
+    set_fields(
+        path,
+        [
+            ("backed_up", true.into()),
+            ("backed_up_at", crate::timeutil::now_secs().into()),
+        ],
+    )
+}
+
+// Add/replace top-level fields of the account data, keeping the others as they are.
+fn set_fields<const N: usize>(path: &Path, fields: [(&str, serde_json::Value); N]) -> Result<()> {
+    let (mut v, _) = crate::persist::decode_value::<AccountJson>(&fs::read(path)?)?;
+    let obj = v
+        .as_object_mut()
+        .ok_or_else(|| anyhow!("account file {} is not a JSON object", path.display()))?;
+    for (k, val) in fields {
+        obj.insert(k.into(), val);
+    }
+    crate::persist::store_value::<AccountJson>(path, &v)
+}
+
+// Migrate a file written before `account_id` existed: decode the address once and store
+// the id next to it. A failed write only means it's decoded again next time.
+fn with_account_id(path: &Path, mut acct: AccountJson) -> AccountJson {
+    if acct.account_id.is_some() {
+        return acct;
+    }
+    let Some(id) = crate::authorship::account_id(&acct.address) else {
+        return acct;
+    };
+    let hex_id = format!("0x{}", hex::encode(id));
+    if let Err(e) = set_fields(path, [("account_id", hex_id.clone().into())]) {
+        eprintln!("account: {e}");
+    }
+    acct.account_id = Some(hex_id);
+    acct
+}
+
+// When the not-backed-up reminder was last sent for an address
+// (`{app_data_dir}/backup_reminder.json`).
+#[derive(Debug, Serialize, Deserialize)]
+struct BackupReminder {
+    address: String,
+    /// Unix seconds.
+    reminded_at: i64,
+}
+
+fn reminder_path(app: &AppHandle) -> PathBuf {
+    crate::paths::app_data_dir(app).join("backup_reminder.json")
+}
+
+/// On a start with an account that was never backed up: send `account:not-backed-up`
+/// (and a log line), at most once a day per address however often the node starts.
+pub fn remind_backup(app: &AppHandle, chain: &str, path: &Path, acct: &AccountJson) {
+    if acct.backed_up {
+        return;
+    }
+    let now = crate::timeutil::now_secs();
+    let file = reminder_path(app);
+    let last = fs::read(&file)
+        .ok()
+        .and_then(|b| serde_json::from_slice::<BackupReminder>(&b).ok())
+        .filter(|r| r.address == acct.address);
+    if last.is_some_and(|r| now - r.reminded_at < BACKUP_REMINDER_INTERVAL_SECS) {
+        return;
+    }
+    crate::session_log::ui(
+        app,
+        chain,
+        format!(
+            "The rewards account {} has not been backed up. Without its secret phrase (in {}) the rewards are lost with this machine.",
+            acct.address,
+            path.display()
+        ),
+    );
+    events::emit(
+        app,
+        events::ACCOUNT_NOT_BACKED_UP,
+        &serde_json::json!({
+            "chain": chain,
+            "address": acct.address,
+            "path": path,
+            "ts": crate::timeutil::now_ms(),
+        }),
+    );
+    let reminder = BackupReminder {
+        address: acct.address.clone(),
+        reminded_at: now,
+    };
+    if let Err(e) = serde_json::to_vec_pretty(&reminder)
+        .map_err(anyhow::Error::from)
+        .and_then(|b| crate::persist::write_atomic(&file, &b))
+    {
+        eprintln!("backup reminder: {e}");
+    }
+}
+
+pub async fn ensure_account_json(
+    app: &AppHandle,
+    quantus_node_path: &PathBuf,
+    out_path: &Path,
+) -> Result<AccountJson> {
+    // accept existing file if it has address/ss58; an unusable one is set aside first
+    match read_account_file(out_path) {
+        AccountFile::Valid(a) => return Ok(with_account_id(out_path, a)),
+        AccountFile::Missing => {}
+        AccountFile::Corrupt {
+            reason,
+            may_hold_key,
+        } => {
+            handle_corrupt(app, out_path, &reason, may_hold_key)?;
         }
     }
 
@@ -64,42 +410,188 @@ pub async fn ensure_account_json(
         ));
     }
     let stdout = String::from_utf8_lossy(&out.stdout);
+    let mut acct = match parse_keygen_output(&stdout) {
+        Ok(a) => a,
+        Err(field) => {
+            let saved = save_keygen_output(app, &stdout);
+            return Err(anyhow!(
+                "couldn't find the {field} in the `key quantus` output{}",
+                saved
+                    .map(|p| format!(" (raw output saved to {})", p.display()))
+                    .unwrap_or_default()
+            ));
+        }
+    };
+    acct.account_id = acct.id().map(|id| format!("0x{}", hex::encode(id)));
+    crate::persist::store_versioned(out_path, &acct)?;
+    Ok(acct)
+}
+
+/// Parse `quantus-node key quantus` output: JSON, or the "Quantus Account Details" banner.
+/// Tolerates CRLF and (for the address) translated labels. Err names the missing field.
+fn parse_keygen_output(raw: &str) -> Result<AccountJson, &'static str> {
+    let text = raw.replace("\r\n", "\n").replace('\r', "\n");
+
+    // JSON output (newer CLIs / --output-type json)
+    if let (Some(start), Some(end)) = (text.find('{'), text.rfind('}')) {
+        if let Ok(v) = serde_json::from_str::<serde_json::Value>(&text[start..=end]) {
+            let field = |keys: &[&str]| {
+                keys.iter()
+                    .find_map(|k| v.get(*k).and_then(|x| x.as_str()))
+                    .map(|s| s.trim().to_string())
+            };
+            if let Some(address) = field(&["ss58Address", "address", "ss58"]) {
+                return Ok(AccountJson {
+                    address,
+                    secret_phrase: field(&["secretPhrase", "secret_phrase"]),
+                    seed: field(&["secretSeed", "seed"]),
+                    pub_key: field(&["publicKey", "pub_key"]),
+                    backed_up: false,
+                    account_id: None,
+                });
+            }
+        }
+    }
 
-    // extract the block between the two X-lines
+    // Banner: fields between the X-lines (or anywhere, if the banner title is translated
+    // or the closing line is missing). The opening line ends at a newline so that, with
+    // no closing line, it can't be split into an empty banner.
     let re_block =
-        Regex::new(r"X{15,}\s*Quantus Account Details\s*X{15,}\s*(?P<body>[\s\S]*?)\s*X{15,}")
+        Regex::new(r"X{15,}\s*Quantus Account Details\s*X{15,}[ \t]*\n(?P<body>[\s\S]*?)\s*X{15,}")
             .unwrap();
     let body = re_block
-        .captures(&stdout)
-        .ok_or_else(|| anyhow!("couldn't find Quantus Account Details block"))?
-        .name("body")
-        .unwrap()
-        .as_str();
-
-    let address = capture(body, r"Address:\s*([^\s]+)")?;
-    let secret_phrase = capture_opt(body, r"Secret phrase:\s*(.+)");
-    let seed = capture_opt(body, r"Seed:\s*([0-9a-fx]+)");
-    let pub_key = capture_opt(body, r"Pub key:\s*([0-9a-fx]+)");
-
-    let acct = AccountJson {
+        .captures(&text)
+        .and_then(|c| c.name("body"))
+        .map_or(text.as_str(), |m| m.as_str());
+
+    let address = capture_opt(body, r"(?mi)^\s*Address:\s*(\S+)\s*$")
+        // translated label: the first "label: value" whose value is an address
+        .or_else(|| {
+            Regex::new(r"(?m)^\s*[^:\n]{1,40}:\s*(\S+)\s*$")
+                .unwrap()
+                .captures_iter(body)
+                .map(|c| c[1].to_string())
+                .find(|v| validate_address(v).is_ok())
+        })
+        .ok_or("address")?;
+    Ok(AccountJson {
         address,
-        secret_phrase,
-        seed,
-        pub_key,
-    };
-    fs::write(out_path, serde_json::to_vec_pretty(&acct)?)?;
-    Ok(acct)
+        secret_phrase: capture_opt(body, r"(?mi)^\s*Secret phrase:\s*(.+?)\s*$"),
+        seed: capture_opt(body, r"(?mi)^\s*Seed:\s*([0-9a-fx]+)\s*$"),
+        pub_key: capture_opt(body, r"(?mi)^\s*Pub key:\s*([0-9a-fx]+)\s*$"),
+        backed_up: false,
+        account_id: None,
+    })
 }
 
-fn capture(s: &str, pat: &str) -> Result<String> {
-    let re = Regex::new(pat).unwrap();
-    let c = re
-        .captures(s)
-        .ok_or_else(|| anyhow!("missing field: {pat}"))?;
-    Ok(c.get(1).unwrap().as_str().trim().to_string())
+/// Keep unparseable keygen output for a bug report. It contains the secret, so the
+/// file is only readable by the user (0600 on unix; per-user app data dir elsewhere).
+fn save_keygen_output(app: &AppHandle, raw: &str) -> Option<PathBuf> {
+    let dir = crate::paths::app_data_dir(app);
+    let ts = crate::timeutil::now_secs();
+    let path = dir.join(format!("keygen-output-{ts}.txt"));
+    let mut opts = fs::OpenOptions::new();
+    opts.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        opts.mode(0o600);
+    }
+    let mut f = opts.open(&path).ok()?;
+    f.write_all(raw.as_bytes()).ok()?;
+    eprintln!(
+        "account: saved unparseable keygen output to {}",
+        path.display()
+    );
+    Some(path)
 }
+
 fn capture_opt(s: &str, pat: &str) -> Option<String> {
     let re = Regex::new(pat).ok()?;
     let c = re.captures(s)?;
     Some(c.get(1)?.as_str().trim().to_string())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ADDRESS: &str = "qzpFUhjXpZnRCbNr9LW5sAkWmTus52Ffcf31MBpig82u1JArX";
+    const SEED: &str = "0x2b7e151628aed2a6abf7158809cf4f3c762e7160f38b4da56a784d9045190cfe";
+    const PHRASE: &str = "bottom drive obey lake curtain smoke basket hold race lonely fit walk \
+                          quantum sunset orbit cable mimic ranch velvet artist noble sponsor \
+                          timber equal";
+
+    fn fixture(name: &str) -> String {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/keygen")
+            .join(name);
+        fs::read_to_string(&path).unwrap_or_else(|e| panic!("{}: {e}", path.display()))
+    }
+
+    fn parse(name: &str) -> AccountJson {
+        parse_keygen_output(&fixture(name)).unwrap_or_else(|f| panic!("{name}: no {f}"))
+    }
+
+    #[test]
+    fn fixtures_keep_their_line_endings() {
+        assert!(fixture("banner_crlf.txt").contains("\r\n"));
+        assert!(!fixture("banner_lf.txt").contains('\r'));
+    }
+
+    #[test]
+    fn banner_lf_and_crlf() {
+        for name in ["banner_lf.txt", "banner_crlf.txt"] {
+            let a = parse(name);
+            assert_eq!(a.address, ADDRESS, "{name}");
+            assert_eq!(a.seed.as_deref(), Some(SEED), "{name}");
+            assert_eq!(a.secret_phrase.as_deref(), Some(PHRASE), "{name}");
+            let pub_key = a.pub_key.unwrap();
+            assert!(pub_key.starts_with("0x7c7c") && !pub_key.contains('\r'));
+            assert!(crate::authorship::checksum_ok(&a.address));
+        }
+    }
+
+    #[test]
+    fn partial_output() {
+        // cut off mid-phrase: no closing banner line and no final newline
+        let a = parse("partial_lf.txt");
+        assert_eq!(a.address, ADDRESS);
+        assert_eq!(a.seed.as_deref(), Some(SEED));
+        assert_eq!(a.secret_phrase.as_deref(), Some("bottom drive obey lake"));
+
+        // CRLF, cut off after the public key
+        let a = parse("partial_crlf.txt");
+        assert_eq!(a.address, ADDRESS);
+        assert_eq!(a.seed.as_deref(), Some(SEED));
+        assert!(!a.pub_key.unwrap().ends_with('\r'));
+        assert_eq!(a.secret_phrase, None);
+    }
+
+    #[test]
+    fn translated_labels() {
+        let a = parse("banner_de_crlf.txt");
+        assert_eq!(a.address, ADDRESS);
+        assert_eq!(a.seed.as_deref(), Some(SEED));
+        // only the address has a fallback for translated labels
+        assert_eq!(a.secret_phrase, None);
+    }
+
+    #[test]
+    fn json_after_log_noise() {
+        let a = parse("json.txt");
+        assert_eq!(a.address, ADDRESS);
+        assert_eq!(a.seed.as_deref(), Some(SEED));
+        assert_eq!(a.secret_phrase.as_deref(), Some(PHRASE));
+        assert!(a.pub_key.unwrap().starts_with("0x7c7c"));
+    }
+
+    #[test]
+    fn missing_address_is_named() {
+        assert_eq!(
+            parse_keygen_output(&fixture("no_address.txt")).err(),
+            Some("address")
+        );
+        assert_eq!(parse_keygen_output("").err(), Some("address"));
+    }
+}
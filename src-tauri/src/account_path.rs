@@ -1,8 +1,8 @@
 use std::path::PathBuf;
-use tauri::{AppHandle, Manager};
+use tauri::AppHandle;
 
 pub fn account_json_path(app: &AppHandle) -> PathBuf {
-    let dir = app.path().app_data_dir().expect("app_data_dir");
+    let dir = crate::paths::app_data_dir(app);
     std::fs::create_dir_all(&dir).ok();
     dir.join("mining-rewards-account.json")
 }
@@ -0,0 +1,151 @@
+use anyhow::{anyhow, Result};
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, VecDeque},
+    time::{Duration, Instant},
+};
+use tauri::AppHandle;
+
+/// Adaptive safe mode (`{app_data_dir}/adaptive_safe_mode.json`): enable safe sync when
+/// the node keeps reporting slow imports or request timeouts, whatever the block range.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AdaptiveSettings {
+    /// On by default.
+    pub enabled: bool,
+    /// Enable safe mode once more than this many trouble lines...
+    pub max_trouble_events: usize,
+    /// ...arrive within this many seconds while syncing.
+    pub window_secs: u64,
+    /// Disable it again after this long without trouble.
+    pub healthy_secs: u64,
+    /// Circuit breaker: at most this many automatic safe-mode restarts...
+    pub max_auto_restarts: usize,
+    /// ...per this many seconds; beyond that the adaptive trigger pauses.
+    pub restart_window_secs: u64,
+}
+
+impl crate::persist::Versioned for AdaptiveSettings {
+    const VERSION: u32 = 1;
+    const MIGRATIONS: &'static [crate::persist::Migration] = &[crate::persist::unchanged];
+}
+
+impl Default for AdaptiveSettings {
+    fn default() -> Self {
+        AdaptiveSettings {
+            enabled: true,
+            max_trouble_events: 5,
+            window_secs: 300,
+            healthy_secs: 600,
+            max_auto_restarts: 4,
+            restart_window_secs: 3600,
+        }
+    }
+}
+
+#[derive(Default)]
+struct Tracker {
+    // trouble lines inside the current window
+    events: VecDeque<Instant>,
+    last_trouble: Option<Instant>,
+    // automatic safe-mode restarts (range or adaptive), for the breaker
+    restarts: VecDeque<Instant>,
+    breaker_reported: bool,
+}
+
+static SETTINGS: crate::persist::Cached<AdaptiveSettings> =
+    crate::persist::Cached::new("adaptive_safe_mode.json");
+
+lazy_static! {
+    static ref TRACKERS: std::sync::Mutex<HashMap<String, Tracker>> =
+        std::sync::Mutex::new(HashMap::new());
+}
+
+/// Current settings (read from disk once, then cached).
+pub fn load_settings(app: &AppHandle) -> AdaptiveSettings {
+    SETTINGS.load(app)
+}
+
+pub fn save_settings(app: &AppHandle, s: &AdaptiveSettings) -> Result<()> {
+    if s.max_trouble_events == 0 || s.max_auto_restarts == 0 {
+        return Err(anyhow!("event and restart limits must be at least 1"));
+    }
+    if s.window_secs == 0 || s.healthy_secs == 0 || s.restart_window_secs == 0 {
+        return Err(anyhow!("time windows must be at least 1 second"));
+    }
+    SETTINGS.store(app, s)
+}
+
+fn prune(q: &mut VecDeque<Instant>, window: Duration, now: Instant) {
+    while q.front().is_some_and(|t| now.duration_since(*t) > window) {
+        q.pop_front();
+    }
+}
+
+/// A new node session: trouble seen by the previous process no longer counts, and the
+/// healthy timer starts over. The restart history is kept for the breaker.
+pub fn reset_session(chain: &str) {
+    if let Ok(mut t) = TRACKERS.lock() {
+        let t = t.entry(chain.to_string()).or_default();
+        t.events.clear();
+        t.last_trouble = Some(Instant::now());
+    }
+}
+
+/// Record a trouble line; returns the count in the window once it exceeds the limit
+/// (the window then starts over).
+pub fn note_trouble(chain: &str, s: &AdaptiveSettings) -> Option<usize> {
+    let now = Instant::now();
+    let mut trackers = TRACKERS.lock().ok()?;
+    let t = trackers.entry(chain.to_string()).or_default();
+    t.last_trouble = Some(now);
+    t.events.push_back(now);
+    prune(&mut t.events, Duration::from_secs(s.window_secs), now);
+    if t.events.len() > s.max_trouble_events {
+        let n = t.events.len();
+        t.events.clear();
+        return Some(n);
+    }
+    None
+}
+
+/// Whether `chain` has gone `healthy_secs` without trouble.
+pub fn is_healthy(chain: &str, s: &AdaptiveSettings) -> bool {
+    let Ok(trackers) = TRACKERS.lock() else {
+        return false;
+    };
+    trackers
+        .get(chain)
+        .and_then(|t| t.last_trouble)
+        .is_none_or(|t| t.elapsed() >= Duration::from_secs(s.healthy_secs))
+}
+
+/// Count an automatic safe-mode restart.
+pub fn note_restart(chain: &str) {
+    if let Ok(mut t) = TRACKERS.lock() {
+        t.entry(chain.to_string())
+            .or_default()
+            .restarts
+            .push_back(Instant::now());
+    }
+}
+
+/// Whether the breaker is open (too many automatic restarts lately). The bool is true
+/// only the first time it is seen open, so the caller can report it once.
+pub fn breaker_open(chain: &str, s: &AdaptiveSettings) -> (bool, bool) {
+    let Ok(mut trackers) = TRACKERS.lock() else {
+        return (false, false);
+    };
+    let t = trackers.entry(chain.to_string()).or_default();
+    prune(
+        &mut t.restarts,
+        Duration::from_secs(s.restart_window_secs),
+        Instant::now(),
+    );
+    let open = t.restarts.len() >= s.max_auto_restarts;
+    let first = open && !t.breaker_reported;
+    t.breaker_reported = open;
+    crate::gui_metrics::set_breaker(chain, open);
+    (open, first)
+}
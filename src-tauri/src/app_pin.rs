@@ -0,0 +1,173 @@
+use argon2::{
+    password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
+    Argon2,
+};
+use lazy_static::lazy_static;
+use serde::Serialize;
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+
+const MIN_LEN: usize = 4;
+const MAX_LEN: usize = 128;
+/// Wrong PINs allowed before attempts are delayed...
+const FREE_ATTEMPTS: u32 = 3;
+/// ...by 1s, 2s, 4s, ... up to this.
+const MAX_DELAY: Duration = Duration::from_secs(900);
+
+/// Error of a command behind the app PIN. `message` is always set; `pin_required` and
+/// `pin_invalid` ask the UI to prompt (again), after `retry_after_secs` if given.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum GuardedError {
+    PinRequired {
+        message: String,
+    },
+    PinInvalid {
+        message: String,
+        /// Attempts are refused until then, right PIN or not.
+        retry_after_secs: Option<u64>,
+    },
+    Other {
+        message: String,
+    },
+}
+
+impl GuardedError {
+    pub fn other(message: impl ToString) -> Self {
+        GuardedError::Other {
+            message: message.to_string(),
+        }
+    }
+}
+
+struct Attempts {
+    failures: u32,
+    locked_until: Option<Instant>,
+}
+
+lazy_static! {
+    // In memory only: restarting the app resets the delay, not the PIN.
+    static ref ATTEMPTS: std::sync::Mutex<Attempts> = std::sync::Mutex::new(Attempts {
+        failures: 0,
+        locked_until: None,
+    });
+}
+
+fn hash(pin: &str) -> Result<String, GuardedError> {
+    let salt = SaltString::encode_b64(&rand::random::<[u8; 16]>())
+        .map_err(|e| GuardedError::other(format!("PIN salt: {e}")))?;
+    Argon2::default()
+        .hash_password(pin.as_bytes(), &salt)
+        .map(|h| h.to_string())
+        .map_err(|e| GuardedError::other(format!("hashing the PIN failed: {e}")))
+}
+
+fn matches(pin: &str, phc: &str) -> bool {
+    PasswordHash::new(phc).is_ok_and(|h| {
+        Argon2::default()
+            .verify_password(pin.as_bytes(), &h)
+            .is_ok()
+    })
+}
+
+fn remaining(until: Instant) -> Option<u64> {
+    let left = until.saturating_duration_since(Instant::now());
+    (!left.is_zero()).then(|| left.as_secs().max(1))
+}
+
+// Check `pin` against the stored hash on a blocking thread (Argon2 takes a moment),
+// counting failures; during a delay nothing is checked.
+async fn verify(phc: String, pin: Option<String>) -> Result<(), GuardedError> {
+    let Some(pin) = pin.filter(|p| !p.is_empty()) else {
+        return Err(GuardedError::PinRequired {
+            message: "this action needs the app PIN".to_string(),
+        });
+    };
+    let locked = ATTEMPTS
+        .lock()
+        .ok()
+        .and_then(|a| a.locked_until)
+        .and_then(remaining);
+    if let Some(secs) = locked {
+        return Err(GuardedError::PinInvalid {
+            message: format!("too many wrong PINs; try again in {secs}s"),
+            retry_after_secs: Some(secs),
+        });
+    }
+    let ok = tokio::task::spawn_blocking(move || matches(&pin, &phc))
+        .await
+        .map_err(GuardedError::other)?;
+    let Ok(mut a) = ATTEMPTS.lock() else {
+        return Err(GuardedError::other("PIN attempt state poisoned"));
+    };
+    if ok {
+        a.failures = 0;
+        a.locked_until = None;
+        return Ok(());
+    }
+    a.failures += 1;
+    let delay = a
+        .failures
+        .checked_sub(FREE_ATTEMPTS)
+        .map(|n| Duration::from_secs(1u64.checked_shl(n).unwrap_or(u64::MAX)).min(MAX_DELAY));
+    a.locked_until = delay.map(|d| Instant::now() + d);
+    Err(GuardedError::PinInvalid {
+        message: match delay {
+            Some(d) => format!("wrong PIN; try again in {}s", d.as_secs()),
+            None => "wrong PIN".to_string(),
+        },
+        retry_after_secs: delay.map(|d| d.as_secs()),
+    })
+}
+
+// With settings.json unreadable there's no telling whether a PIN is set.
+fn refuse_if_damaged(app: &AppHandle) -> Result<(), GuardedError> {
+    match crate::settings::damaged(app) {
+        Some(why) => Err(GuardedError::other(format!(
+            "the settings can't be read ({why}); fix or remove the file and restart the app"
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// Gate for destructive commands: Ok when no PIN is set or `pin` is right.
+pub async fn check(app: &AppHandle, pin: Option<String>) -> Result<(), GuardedError> {
+    refuse_if_damaged(app)?;
+    match crate::settings::get(app).app_pin_hash {
+        Some(phc) => verify(phc, pin).await,
+        None => Ok(()),
+    }
+}
+
+/// `set_app_pin`: set a new PIN; replacing one needs the current PIN.
+pub async fn set(
+    app: &AppHandle,
+    pin: String,
+    current: Option<String>,
+) -> Result<(), GuardedError> {
+    check(app, current).await?;
+    let len = pin.chars().count();
+    if !(MIN_LEN..=MAX_LEN).contains(&len) {
+        return Err(GuardedError::other(format!(
+            "the PIN must be {MIN_LEN} to {MAX_LEN} characters"
+        )));
+    }
+    let phc = tokio::task::spawn_blocking(move || hash(&pin))
+        .await
+        .map_err(GuardedError::other)??;
+    crate::settings::set_pin_hash(app, Some(phc)).map_err(GuardedError::other)?;
+    crate::session_log::audit("-", "app-pin-set", "");
+    Ok(())
+}
+
+/// `clear_app_pin`: remove the PIN; needs the current one.
+pub async fn clear(app: &AppHandle, pin: Option<String>) -> Result<(), GuardedError> {
+    refuse_if_damaged(app)?;
+    if crate::settings::get(app).app_pin_hash.is_none() {
+        return Ok(());
+    }
+    check(app, pin).await?;
+    crate::settings::set_pin_hash(app, None).map_err(GuardedError::other)?;
+    crate::session_log::audit("-", "app-pin-cleared", "");
+    Ok(())
+}
@@ -0,0 +1,142 @@
+use anyhow::{anyhow, Result};
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tauri::AppHandle;
+use tokio::sync::Mutex;
+
+use crate::events;
+
+/// Where GUI releases are published.
+const GUI_REPO: &str = "Quantus-Network/miner-tauri-gui";
+/// A successful check is reused for this long (manual checks included).
+const CACHE_TTL: Duration = Duration::from_secs(60 * 60);
+/// Automatic checks run at most this often (tracked across restarts).
+const AUTO_CHECK_INTERVAL_SECS: i64 = 24 * 60 * 60;
+
+/// Result of comparing this build with the latest GUI release.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppUpdateInfo {
+    pub current: String,
+    pub latest: String,
+    pub notes: Option<String>,
+    /// Installer for this platform if the release has one, else the release page.
+    pub download_url: Option<String>,
+    pub update_available: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppUpdateSettings {
+    /// Daily background check (opt-out).
+    pub auto_check: bool,
+    /// Unix seconds of the last automatic check.
+    #[serde(default)]
+    pub last_check: Option<i64>,
+}
+
+impl crate::persist::Versioned for AppUpdateSettings {
+    const VERSION: u32 = 1;
+    const MIGRATIONS: &'static [crate::persist::Migration] = &[crate::persist::unchanged];
+}
+
+impl Default for AppUpdateSettings {
+    fn default() -> Self {
+        AppUpdateSettings {
+            auto_check: true,
+            last_check: None,
+        }
+    }
+}
+
+lazy_static! {
+    static ref CACHE: Mutex<Option<(std::time::Instant, AppUpdateInfo)>> = Mutex::new(None);
+}
+
+static SETTINGS: crate::persist::Cached<AppUpdateSettings> =
+    crate::persist::Cached::new("app_update.json");
+
+pub fn load_settings(app: &AppHandle) -> AppUpdateSettings {
+    SETTINGS.load(app)
+}
+
+pub fn save_settings(app: &AppHandle, s: &AppUpdateSettings) -> Result<()> {
+    SETTINGS.store(app, s)
+}
+
+// "v0.1.2" / "0.1.2" -> Version
+fn parse_version(tag: &str) -> Result<semver::Version> {
+    let v = tag.trim().trim_start_matches(['v', 'V']);
+    semver::Version::parse(v).map_err(|e| anyhow!("invalid version {tag:?}: {e}"))
+}
+
+// Installer asset for this OS, by extension.
+fn platform_asset(rel: &crate::installer::Release) -> Option<String> {
+    let exts: &[&str] = if cfg!(target_os = "macos") {
+        &[".dmg"]
+    } else if cfg!(windows) {
+        &[".msi", "-setup.exe"]
+    } else {
+        &[".AppImage", ".deb"]
+    };
+    exts.iter().find_map(|ext| {
+        rel.assets
+            .iter()
+            .find(|a| a.name.ends_with(ext))
+            .map(|a| a.browser_download_url.clone())
+    })
+}
+
+/// Compare `CARGO_PKG_VERSION` with the latest GUI release. Cached for `CACHE_TTL`
+/// unless `force`.
+pub async fn check(force: bool) -> Result<AppUpdateInfo> {
+    if !force {
+        if let Some((at, info)) = CACHE.lock().await.as_ref() {
+            if at.elapsed() < CACHE_TTL {
+                return Ok(info.clone());
+            }
+        }
+    }
+    let current = env!("CARGO_PKG_VERSION");
+    let rel = crate::installer::fetch_latest_release(GUI_REPO).await?;
+    let latest = parse_version(&rel.tag_name)?;
+    let info = AppUpdateInfo {
+        current: current.to_string(),
+        latest: latest.to_string(),
+        notes: rel.body.clone().filter(|b| !b.trim().is_empty()),
+        download_url: platform_asset(&rel).or_else(|| rel.html_url.clone()),
+        update_available: latest > parse_version(current)?,
+    };
+    *CACHE.lock().await = Some((std::time::Instant::now(), info.clone()));
+    Ok(info)
+}
+
+/// Background task: once a day (if `auto_check` is on) check for a newer GUI and emit
+/// `app:update-available` with the `AppUpdateInfo`.
+pub fn spawn_daily_check(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        // let startup (installs, deep links) settle first
+        tokio::time::sleep(Duration::from_secs(30)).await;
+        loop {
+            let mut settings = load_settings(&app);
+            let now = crate::timeutil::now_secs();
+            let due = settings
+                .last_check
+                .is_none_or(|t| now - t >= AUTO_CHECK_INTERVAL_SECS);
+            if settings.auto_check && due {
+                match check(false).await {
+                    Ok(info) => {
+                        settings.last_check = Some(now);
+                        let _ = save_settings(&app, &settings);
+                        if info.update_available {
+                            events::emit(&app, events::APP_UPDATE_AVAILABLE, &info);
+                        }
+                    }
+                    Err(e) => eprintln!("app update check failed: {e}"),
+                }
+            }
+            // re-evaluated hourly so toggling the setting takes effect without a restart
+            tokio::time::sleep(Duration::from_secs(60 * 60)).await;
+        }
+    });
+}
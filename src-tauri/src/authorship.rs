@@ -0,0 +1,332 @@
+use anyhow::Result;
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, path::PathBuf};
+use tauri::AppHandle;
+use tokio::sync::Mutex;
+
+/// Consensus engine id of the PoW pre-runtime digest that carries the block author.
+const POW_ENGINE: &[u8; 4] = b"pow_";
+/// Authored blocks this far below the best block are treated as final (no orphan checks).
+const REORG_DEPTH: u64 = 100;
+/// Entries kept per chain in the persisted history.
+const HISTORY_LIMIT: usize = 500;
+
+/// A block whose PoW digest names our rewards account.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthoredBlock {
+    pub height: u64,
+    pub hash: String,
+    /// Unix seconds when we saw it imported.
+    pub ts: i64,
+    /// No longer on the best chain (reorged out); not counted.
+    #[serde(default)]
+    pub orphaned: bool,
+    /// Milliseconds from our import until the bootnode had it (`propagation`).
+    #[serde(default, alias = "propagation_ms")]
+    pub propagation_ms: Option<u64>,
+    /// Whether the bootnode had it within `propagation::WINDOW`; null until known.
+    #[serde(default)]
+    pub propagated: Option<bool>,
+}
+
+/// What `record`/`set_canonical` changed.
+#[derive(Debug, Clone)]
+pub enum Change {
+    Authored(AuthoredBlock),
+    Orphaned(AuthoredBlock),
+    /// An orphaned block is back on the best chain.
+    Restored(AuthoredBlock),
+}
+
+lazy_static! {
+    // Blocks authored during the current node session per chain, keyed by hash so a
+    // re-import after a reorg isn't counted twice.
+    static ref SESSION: Mutex<HashMap<String, HashMap<String, AuthoredBlock>>> =
+        Mutex::new(HashMap::new());
+}
+
+const B58_ALPHABET: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+// Base58 (bitcoin alphabet) to bytes.
+fn base58_decode(s: &str) -> Option<Vec<u8>> {
+    // little-endian big integer
+    let mut out: Vec<u8> = Vec::new();
+    for c in s.bytes() {
+        let mut carry = B58_ALPHABET.iter().position(|&b| b == c)? as u32;
+        for byte in out.iter_mut() {
+            carry += *byte as u32 * 58;
+            *byte = carry as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            out.push(carry as u8);
+            carry >>= 8;
+        }
+    }
+    let zeros = s.bytes().take_while(|&c| c == b'1').count();
+    out.extend(std::iter::repeat_n(0, zeros));
+    out.reverse();
+    Some(out)
+}
+
+// Bytes to base58 (bitcoin alphabet).
+fn base58_encode(bytes: &[u8]) -> String {
+    // little-endian base-58 digits
+    let mut digits: Vec<u8> = Vec::new();
+    for &b in bytes {
+        let mut carry = b as u32;
+        for d in digits.iter_mut() {
+            carry += (*d as u32) << 8;
+            *d = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            digits.push((carry % 58) as u8);
+            carry /= 58;
+        }
+    }
+    let zeros = bytes.iter().take_while(|&&b| b == 0).count();
+    std::iter::repeat_n(b'1', zeros)
+        .chain(digits.iter().rev().map(|&d| B58_ALPHABET[d as usize]))
+        .map(char::from)
+        .collect()
+}
+
+/// `id` as an ss58 address with network `prefix` (one byte below 64, two above).
+pub fn ss58_encode(prefix: u16, id: &[u8; 32]) -> String {
+    use blake2::{Blake2b512, Digest};
+    let mut data = if prefix < 64 {
+        vec![prefix as u8]
+    } else {
+        vec![
+            ((prefix & 0b1111_1100) >> 2) as u8 | 0b0100_0000,
+            ((prefix >> 8) as u8) | (((prefix & 0b11) as u8) << 6),
+        ]
+    };
+    data.extend_from_slice(id);
+    let hash = Blake2b512::new()
+        .chain_update(b"SS58PRE")
+        .chain_update(&data)
+        .finalize();
+    data.extend_from_slice(&hash[..2]);
+    base58_encode(&data)
+}
+
+/// Whether an ss58 address decodes and its 2-byte checksum matches.
+pub fn checksum_ok(ss58: &str) -> bool {
+    use blake2::{Blake2b512, Digest};
+    let Some(bytes) = base58_decode(ss58.trim()) else {
+        return false;
+    };
+    if account_id(ss58).is_none() {
+        return false;
+    }
+    let (data, sum) = bytes.split_at(bytes.len() - 2);
+    let hash = Blake2b512::new()
+        .chain_update(b"SS58PRE")
+        .chain_update(data)
+        .finalize();
+    hash[..2] == *sum
+}
+
+/// The 32-byte account id in an ss58 address (1- or 2-byte prefix, 2-byte checksum;
+/// the checksum isn't verified here).
+pub fn account_id(ss58: &str) -> Option<[u8; 32]> {
+    let bytes = base58_decode(ss58.trim())?;
+    let prefix_len = if bytes.first()? & 0b0100_0000 != 0 {
+        2
+    } else {
+        1
+    };
+    if bytes.len() != prefix_len + 32 + 2 {
+        return None;
+    }
+    bytes[prefix_len..prefix_len + 32].try_into().ok()
+}
+
+// SCALE compact length: (value, bytes used). Four-byte mode at most.
+fn compact_len(b: &[u8]) -> Option<(usize, usize)> {
+    match b.first()? & 0b11 {
+        0 => Some(((b[0] >> 2) as usize, 1)),
+        1 => {
+            let v = u16::from_le_bytes([b[0], *b.get(1)?]);
+            Some(((v >> 2) as usize, 2))
+        }
+        2 => {
+            let v = u32::from_le_bytes(b.get(..4)?.try_into().ok()?);
+            Some(((v >> 2) as usize, 4))
+        }
+        _ => None,
+    }
+}
+
+// DigestItem::PreRuntime(engine, data) = 0x06 ++ engine id ++ compact len ++ data
+fn pre_runtime(item: &[u8], engine: &[u8; 4]) -> Option<Vec<u8>> {
+    let (&tag, rest) = item.split_first()?;
+    if tag != 6 || rest.get(..4)? != engine {
+        return None;
+    }
+    let (len, used) = compact_len(&rest[4..])?;
+    rest.get(4 + used..4 + used + len).map(<[u8]>::to_vec)
+}
+
+/// Author from a header's (`chain_getHeader` / new-head notification) PoW digest.
+pub fn header_author(header: &serde_json::Value) -> Option<Vec<u8>> {
+    header
+        .get("digest")?
+        .get("logs")?
+        .as_array()?
+        .iter()
+        .filter_map(|l| l.as_str())
+        .filter_map(|h| hex::decode(h.trim_start_matches("0x")).ok())
+        .find_map(|item| pre_runtime(&item, POW_ENGINE))
+}
+
+/// Whether `header` was authored by `account`.
+pub fn is_authored_by(header: &serde_json::Value, account: &[u8; 32]) -> bool {
+    header_author(header).is_some_and(|a| a.get(..32) == Some(&account[..]))
+}
+
+/// Forget the session's blocks (the node for `chain` was started afresh).
+pub async fn reset_session(chain: &str) {
+    SESSION.lock().await.remove(chain);
+}
+
+/// Non-orphaned blocks authored this session.
+pub async fn session_count(chain: &str) -> u64 {
+    SESSION
+        .lock()
+        .await
+        .get(chain)
+        .map(|m| m.values().filter(|b| !b.orphaned).count() as u64)
+        .unwrap_or(0)
+}
+
+/// Note an imported block of ours. None if this hash was already counted.
+pub async fn record(app: &AppHandle, chain: &str, height: u64, hash: &str) -> Option<Change> {
+    let block = AuthoredBlock {
+        height,
+        hash: hash.to_string(),
+        ts: crate::timeutil::now_secs(),
+        orphaned: false,
+        propagation_ms: None,
+        propagated: None,
+    };
+    {
+        let mut session = SESSION.lock().await;
+        let blocks = session.entry(chain.to_string()).or_default();
+        if blocks.contains_key(hash) {
+            return None;
+        }
+        blocks.insert(hash.to_string(), block.clone());
+    }
+    if let Err(e) = save_to_history(app, chain, &block) {
+        eprintln!("authored block history: {e}");
+    }
+    Some(Change::Authored(block))
+}
+
+/// Session blocks still within `REORG_DEPTH` of `best` (candidates for an orphan check).
+pub async fn recent(chain: &str, best: u64) -> Vec<AuthoredBlock> {
+    SESSION
+        .lock()
+        .await
+        .get(chain)
+        .map(|m| {
+            m.values()
+                .filter(|b| b.height + REORG_DEPTH >= best)
+                .cloned()
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Update a session block after comparing it with the canonical hash at its height.
+pub async fn set_canonical(
+    app: &AppHandle,
+    chain: &str,
+    hash: &str,
+    canonical: bool,
+) -> Option<Change> {
+    let block = {
+        let mut session = SESSION.lock().await;
+        let b = session.get_mut(chain)?.get_mut(hash)?;
+        if b.orphaned != canonical {
+            return None;
+        }
+        b.orphaned = !canonical;
+        b.clone()
+    };
+    if let Err(e) = save_to_history(app, chain, &block) {
+        eprintln!("authored block history: {e}");
+    }
+    Some(if canonical {
+        Change::Restored(block)
+    } else {
+        Change::Orphaned(block)
+    })
+}
+
+/// Record how a session block propagated (`propagation_ms` None: not seen in time).
+pub async fn set_propagation(
+    app: &AppHandle,
+    chain: &str,
+    hash: &str,
+    propagation_ms: Option<u64>,
+) -> Option<AuthoredBlock> {
+    let block = {
+        let mut session = SESSION.lock().await;
+        let b = session.get_mut(chain)?.get_mut(hash)?;
+        b.propagation_ms = propagation_ms;
+        b.propagated = Some(propagation_ms.is_some());
+        b.clone()
+    };
+    if let Err(e) = save_to_history(app, chain, &block) {
+        eprintln!("authored block history: {e}");
+    }
+    Some(block)
+}
+
+/// `{app_data_dir}/authored_blocks.json`: blocks by chain, at most `HISTORY_LIMIT` each.
+#[derive(Default, Serialize, Deserialize)]
+#[serde(transparent)]
+struct History(HashMap<String, Vec<AuthoredBlock>>);
+
+impl crate::persist::Versioned for History {
+    const VERSION: u32 = 1;
+    const MIGRATIONS: &'static [crate::persist::Migration] = &[crate::persist::unchanged];
+}
+
+fn history_path(app: &AppHandle) -> PathBuf {
+    crate::paths::app_data_dir(app).join("authored_blocks.json")
+}
+
+fn load_all(app: &AppHandle) -> HashMap<String, Vec<AuthoredBlock>> {
+    crate::persist::load_versioned::<History>(&history_path(app))
+        .unwrap_or_default()
+        .0
+}
+
+/// Persisted authored blocks for `chain`, newest first.
+pub fn history(app: &AppHandle, chain: &str) -> Vec<AuthoredBlock> {
+    let mut v = load_all(app).remove(chain).unwrap_or_default();
+    v.sort_by_key(|b| std::cmp::Reverse(b.height));
+    v
+}
+
+// Insert or update (by hash) one block in `{app_data_dir}/authored_blocks.json`.
+fn save_to_history(app: &AppHandle, chain: &str, block: &AuthoredBlock) -> Result<()> {
+    let mut all = load_all(app);
+    let blocks = all.entry(chain.to_string()).or_default();
+    match blocks.iter_mut().find(|b| b.hash == block.hash) {
+        Some(b) => *b = block.clone(),
+        None => blocks.push(block.clone()),
+    }
+    if blocks.len() > HISTORY_LIMIT {
+        blocks.sort_by_key(|b| std::cmp::Reverse(b.height));
+        blocks.truncate(HISTORY_LIMIT);
+    }
+    crate::persist::store_versioned(&history_path(app), &History(all))
+}
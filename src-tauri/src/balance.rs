@@ -0,0 +1,162 @@
+use anyhow::{anyhow, Result};
+
+/// Fraction digits shown for balances unless the caller asks for others.
+pub const DEFAULT_PRECISION: u32 = 6;
+
+/// Format a raw on-chain amount (plancks, as a decimal string up to u128::MAX) in whole
+/// tokens: `decimals` places, rounded half-up to `precision` fraction digits (trailing zeros
+/// trimmed) and with thousands separators, e.g. ("1234567890000000", 12, 4) -> "1,234.5679".
+/// Pure integer/string math, so nothing is lost above 2^53.
+pub fn format_balance(raw: &str, decimals: u32, precision: u32) -> Result<String> {
+    let raw = raw.trim();
+    let value: u128 = raw
+        .parse()
+        .map_err(|e| anyhow!("invalid balance {raw:?}: {e}"))?;
+    let decimals = decimals as usize;
+    let precision = (precision as usize).min(decimals);
+
+    // digits with at least one integer digit: "000123" for 123 at 5 decimals
+    let digits = format!("{value:0>width$}", width = decimals + 1);
+    let split = digits.len() - decimals;
+    let round_up = digits[split + precision..]
+        .bytes()
+        .next()
+        .is_some_and(|d| d >= b'5');
+    let mut kept = digits.as_bytes()[..split + precision].to_vec();
+    let mut int_len = split;
+    if round_up {
+        // propagate the carry; a carry out of the top digit adds one integer digit
+        let mut i = kept.len();
+        loop {
+            if i == 0 {
+                kept.insert(0, b'1');
+                int_len += 1;
+                break;
+            }
+            i -= 1;
+            if kept[i] == b'9' {
+                kept[i] = b'0';
+            } else {
+                kept[i] += 1;
+                break;
+            }
+        }
+    }
+    let kept = String::from_utf8(kept).expect("ascii digits");
+    let (int_part, frac_part) = kept.split_at(int_len);
+    let frac_part = frac_part.trim_end_matches('0');
+
+    let mut out = group_thousands(int_part);
+    if !frac_part.is_empty() {
+        out.push('.');
+        out.push_str(frac_part);
+    }
+    Ok(out)
+}
+
+// "1234567" -> "1,234,567"
+fn group_thousands(int_part: &str) -> String {
+    let mut out = String::with_capacity(int_part.len() + int_part.len() / 3);
+    for (i, c) in int_part.chars().enumerate() {
+        if i > 0 && (int_part.len() - i).is_multiple_of(3) {
+            out.push(',');
+        }
+        out.push(c);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    // The same rounding done with u128 division: whole tokens, rounded half-up to
+    // `precision` digits, trailing zeros trimmed, no separators.
+    fn reference(value: u128, decimals: u32, precision: u32) -> String {
+        let precision = precision.min(decimals);
+        let scale = 10u128.pow(decimals - precision);
+        let mut kept = value / scale;
+        if scale > 1 && value % scale >= scale / 2 {
+            kept += 1;
+        }
+        let unit = 10u128.pow(precision);
+        let frac = format!("{:0>width$}", kept % unit, width = precision as usize);
+        let frac = frac.trim_end_matches('0');
+        match frac {
+            "" => (kept / unit).to_string(),
+            _ => format!("{}.{frac}", kept / unit),
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn rounds_half_up(value: u128, decimals in 0u32..=38, precision in 0u32..=40) {
+            let out = format_balance(&value.to_string(), decimals, precision).unwrap();
+            prop_assert_eq!(out.replace(',', ""), reference(value, decimals, precision));
+        }
+
+        #[test]
+        fn groups_thousands(value: u128, decimals in 0u32..=38, precision in 0u32..=12) {
+            let out = format_balance(&value.to_string(), decimals, precision).unwrap();
+            let int_part = out.split('.').next().unwrap();
+            let groups: Vec<&str> = int_part.split(',').collect();
+            prop_assert!((1..=3).contains(&groups[0].len()), "{}", out);
+            prop_assert!(groups[1..].iter().all(|g| g.len() == 3), "{}", out);
+            prop_assert!(groups[0] == "0" || !groups[0].starts_with('0'), "{}", out);
+        }
+
+        #[test]
+        fn rejects_what_is_not_a_u128(raw in "[^0-9]*|-[0-9]+|[1-9][0-9]{39,44}") {
+            prop_assert!(format_balance(&raw, 12, 6).is_err());
+        }
+    }
+
+    #[test]
+    fn zero() {
+        for decimals in [0, 1, 12, 18, 38] {
+            for precision in [0, 6, 40] {
+                assert_eq!(format_balance("0", decimals, precision).unwrap(), "0");
+            }
+        }
+    }
+
+    #[test]
+    fn u128_max() {
+        let max = u128::MAX.to_string();
+        assert_eq!(
+            format_balance(&max, 0, 6).unwrap(),
+            "340,282,366,920,938,463,463,374,607,431,768,211,455"
+        );
+        assert_eq!(
+            format_balance(&max, 12, 6).unwrap(),
+            "340,282,366,920,938,463,463,374,607.431768"
+        );
+        assert_eq!(
+            format_balance(&max, 12, 2).unwrap(),
+            "340,282,366,920,938,463,463,374,607.43"
+        );
+        assert_eq!(format_balance(&max, 38, 0).unwrap(), "3");
+        assert_eq!(format_balance(&max, 38, 1).unwrap(), "3.4");
+        assert_eq!(
+            format_balance(&max, 38, 38).unwrap(),
+            "3.40282366920938463463374607431768211455"
+        );
+        // one more doesn't fit
+        assert!(format_balance("340282366920938463463374607431768211456", 12, 6).is_err());
+    }
+
+    #[test]
+    fn carries() {
+        assert_eq!(format_balance("999999500000", 12, 6).unwrap(), "1");
+        assert_eq!(format_balance("999999499999", 12, 6).unwrap(), "0.999999");
+        assert_eq!(
+            format_balance("999999999999999999", 12, 4).unwrap(),
+            "1,000,000"
+        );
+        assert_eq!(
+            format_balance(" 1234567890000000\n", 12, 4).unwrap(),
+            "1,234.5679"
+        );
+    }
+}
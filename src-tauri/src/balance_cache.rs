@@ -0,0 +1,149 @@
+use anyhow::Result;
+use lazy_static::lazy_static;
+use serde::Serialize;
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+use tauri::AppHandle;
+
+use crate::{events, rpc::BalanceView};
+
+/// `miner:balance` payload: a background refresh found a different balance.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct BalanceChanged {
+    chain: String,
+    address: String,
+    balance: BalanceView,
+    ts: i64,
+}
+
+struct Entry {
+    view: BalanceView,
+    fetched_at: Instant,
+    // a background refresh is underway
+    refreshing: bool,
+}
+
+#[derive(Default)]
+struct Cache {
+    // keyed by (chain, address in the chain's prefix)
+    entries: HashMap<(String, String), Entry>,
+    // fetches started before this can't be stored (they may predate a reward)
+    invalidated: HashMap<(String, String), Instant>,
+}
+
+lazy_static! {
+    static ref CACHE: std::sync::Mutex<Cache> = std::sync::Mutex::new(Cache::default());
+}
+
+fn key(chain: &str, address: &str) -> (String, String) {
+    (chain.to_string(), address.to_string())
+}
+
+/// `address`'s balance on `chain`. A cached value younger than `balance_ttl_secs` (the
+/// settings; 0 turns caching off) is returned right away and refreshed in the background,
+/// with `miner:balance` if it changed; otherwise, and with `force`, the indexer is asked.
+pub async fn get(app: &AppHandle, chain: &str, address: &str, force: bool) -> Result<BalanceView> {
+    let ttl = Duration::from_secs(crate::settings::get(app).balance_ttl_secs);
+    if !force {
+        let cached = CACHE.lock().ok().and_then(|mut c| {
+            let e = c.entries.get_mut(&key(chain, address))?;
+            if e.fetched_at.elapsed() > ttl {
+                return None;
+            }
+            let refresh = !e.refreshing;
+            e.refreshing = true;
+            Some((e.view.clone(), refresh))
+        });
+        if let Some((view, refresh)) = cached {
+            if refresh {
+                let (app, chain, address) = (app.clone(), chain.to_string(), address.to_string());
+                tauri::async_runtime::spawn(async move {
+                    refresh_in_background(&app, &chain, &address).await;
+                });
+            }
+            return Ok(view);
+        }
+    }
+    let (view, changed) = fetch(chain, address).await?;
+    if let Some(previous_ms) = changed {
+        crate::transfers::on_balance_change(app, chain, address, previous_ms);
+    }
+    Ok(view)
+}
+
+// Ask the indexer and cache the answer. When it differs from what was cached, also
+// when that was read (UTC ms).
+async fn fetch(chain: &str, address: &str) -> Result<(BalanceView, Option<i64>)> {
+    let started = Instant::now();
+    let res = crate::rpc::fetch_balance(chain, address).await;
+    let mut cache = CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    let k = key(chain, address);
+    let view = match res {
+        Ok(v) => v,
+        Err(e) => {
+            if let Some(entry) = cache.entries.get_mut(&k) {
+                entry.refreshing = false;
+            }
+            return Err(e);
+        }
+    };
+    if cache.invalidated.get(&k).is_some_and(|at| *at > started) {
+        return Ok((view, None));
+    }
+    let changed = cache
+        .entries
+        .get(&k)
+        .filter(|e| e.view.free != view.free || e.view.reserved != view.reserved)
+        .map(|e| crate::timeutil::now_ms() - e.fetched_at.elapsed().as_millis() as i64);
+    cache.entries.insert(
+        k,
+        Entry {
+            view: view.clone(),
+            fetched_at: Instant::now(),
+            refreshing: false,
+        },
+    );
+    drop(cache);
+    crate::snapshot::update(chain, |c| c.balance = serde_json::to_value(&view).ok());
+    Ok((view, changed))
+}
+
+async fn refresh_in_background(app: &AppHandle, chain: &str, address: &str) {
+    match fetch(chain, address).await {
+        Ok((view, Some(previous_ms))) => {
+            events::emit(
+                app,
+                events::MINER_BALANCE,
+                &BalanceChanged {
+                    chain: chain.to_string(),
+                    address: address.to_string(),
+                    balance: view,
+                    ts: crate::timeutil::now_ms(),
+                },
+            );
+            crate::transfers::on_balance_change(app, chain, address, previous_ms);
+        }
+        Ok(_) => {}
+        Err(e) => eprintln!("balance refresh: {e}"),
+    }
+}
+
+/// Forget the rewards account's balance on `chain`: a block was found, so the next
+/// `query_balance` asks the indexer.
+pub fn invalidate_own(app: &AppHandle, chain: &str) {
+    let path = crate::account_path::account_json_path(app);
+    let Ok(acct) = crate::account_cli::load_account(app, &path) else {
+        return;
+    };
+    let k = key(
+        chain,
+        &crate::account_cli::rewards_address(app, &acct, chain),
+    );
+    if let Ok(mut cache) = CACHE.lock() {
+        cache.entries.remove(&k);
+        cache.invalidated.insert(k, Instant::now());
+    }
+}
@@ -0,0 +1,181 @@
+use anyhow::{anyhow, Result};
+use lazy_static::lazy_static;
+use serde::Serialize;
+use std::{
+    process::Stdio,
+    sync::atomic::{AtomicBool, Ordering},
+    time::Duration,
+};
+use tauri::AppHandle;
+use tokio::{
+    io::{AsyncBufReadExt, BufReader},
+    process::Command,
+    sync::Notify,
+};
+
+use crate::events;
+use crate::parse::MinerEvent;
+
+/// Shortest and longest run per core count.
+const MIN_DURATION_SECS: u32 = 5;
+const MAX_DURATION_SECS: u32 = 600;
+/// Extra time a run gets (startup, final report) before it is killed.
+const RUN_GRACE: Duration = Duration::from_secs(30);
+
+static RUNNING: AtomicBool = AtomicBool::new(false);
+
+lazy_static! {
+    static ref CANCEL: Notify = Notify::new();
+}
+
+/// Hashrate samples for one core count.
+#[derive(Debug, Clone, Serialize)]
+pub struct BenchResult {
+    pub cores: usize,
+    pub samples: usize,
+    pub min_hps: f64,
+    pub avg_hps: f64,
+    pub max_hps: f64,
+}
+
+/// Whether a benchmark is running (mining must not start meanwhile).
+pub fn is_running() -> bool {
+    RUNNING.load(Ordering::SeqCst)
+}
+
+/// Stop a running benchmark; its process is killed and `run` returns an error.
+pub fn cancel() {
+    CANCEL.notify_waiters();
+}
+
+// Clears RUNNING however `run` ends.
+struct RunningGuard;
+
+impl Drop for RunningGuard {
+    fn drop(&mut self) {
+        RUNNING.store(false, Ordering::SeqCst);
+    }
+}
+
+// 1, half and all-but-one of the cores (deduplicated), like the external miner setting.
+fn default_core_counts() -> Vec<usize> {
+    let max = num_cpus::get().saturating_sub(1).max(1);
+    let mut v = vec![1, (max / 2).max(1), max];
+    v.dedup();
+    v
+}
+
+/// Benchmark the external miner (`quantus-miner benchmark`) for `duration_secs` at
+/// `cores` (or a few core counts), emitting `benchmark:progress` per hashrate sample.
+/// Refused while a node is running.
+pub async fn run(
+    app: &AppHandle,
+    cores: Option<usize>,
+    duration_secs: u32,
+) -> Result<Vec<BenchResult>> {
+    if crate::miner::is_running().await {
+        return Err(anyhow!("stop mining before running a benchmark"));
+    }
+    if RUNNING.swap(true, Ordering::SeqCst) {
+        return Err(anyhow!("a benchmark is already running"));
+    }
+    let _guard = RunningGuard;
+    let duration = duration_secs.clamp(MIN_DURATION_SECS, MAX_DURATION_SECS);
+    let bin = crate::installer::ensure_external_miner_installed(None).await?;
+    let counts = match cores {
+        Some(c) => vec![c.clamp(1, num_cpus::get().max(1))],
+        None => default_core_counts(),
+    };
+
+    let mut results = Vec::with_capacity(counts.len());
+    for (i, &n) in counts.iter().enumerate() {
+        let mut child = Command::new(&bin)
+            .env("RUST_LOG", "info")
+            .args([
+                "benchmark",
+                "--num-cores",
+                &n.to_string(),
+                "--duration",
+                &duration.to_string(),
+            ])
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            // also covers the command's future being dropped
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| crate::installer::diagnose_spawn_error("external miner", &bin, e))?;
+        let stdout = child.stdout.take().ok_or_else(|| anyhow!("no stdout"))?;
+        let stderr = child.stderr.take().ok_or_else(|| anyhow!("no stderr"))?;
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<String>();
+        for pipe in [
+            Box::new(stdout) as Box<dyn tokio::io::AsyncRead + Unpin + Send>,
+            Box::new(stderr),
+        ] {
+            let tx = tx.clone();
+            tauri::async_runtime::spawn(async move {
+                let mut lines = BufReader::new(pipe).lines();
+                while let Ok(Some(line)) = lines.next_line().await {
+                    let _ = tx.send(line);
+                }
+            });
+        }
+        drop(tx);
+
+        let started = tokio::time::Instant::now();
+        let deadline = started + Duration::from_secs(duration as u64) + RUN_GRACE;
+        let mut samples: Vec<f64> = Vec::new();
+        let mut last_line = String::new();
+        let outcome: Result<()> = loop {
+            tokio::select! {
+                line = rx.recv() => match line {
+                    Some(line) => {
+                        if let Some(MinerEvent::Hashrate { hps }) = crate::parse::parse_event(&line) {
+                            samples.push(hps);
+                            events::emit(app, events::BENCHMARK_PROGRESS, &serde_json::json!({
+                                    "cores": n,
+                                    "run": i + 1,
+                                    "runs": counts.len(),
+                                    "elapsedSecs": started.elapsed().as_secs(),
+                                    "durationSecs": duration,
+                                    "hps": hps,
+                                }));
+                        }
+                        if !line.trim().is_empty() {
+                            last_line = line;
+                        }
+                    }
+                    // both pipes closed: the process is done
+                    None => break Ok(()),
+                },
+                _ = CANCEL.notified() => break Err(anyhow!("benchmark cancelled")),
+                _ = tokio::time::sleep_until(deadline) => {
+                    break Err(anyhow!("benchmark with {n} cores didn't finish within {}s", deadline.duration_since(started).as_secs()))
+                }
+            }
+        };
+        if outcome.is_err() {
+            let _ = child.kill().await;
+        }
+        let status = child.wait().await;
+        outcome?;
+        if samples.is_empty() {
+            let detail = match status {
+                Ok(s) if !s.success() => format!("exited with {s}: {last_line}"),
+                _ => "no hashrate reported".to_string(),
+            };
+            return Err(anyhow!(
+                "benchmark with {n} cores failed ({detail}); this quantus-miner may not support `benchmark`"
+            ));
+        }
+        let sum: f64 = samples.iter().sum();
+        results.push(BenchResult {
+            cores: n,
+            samples: samples.len(),
+            min_hps: samples.iter().copied().fold(f64::INFINITY, f64::min),
+            avg_hps: sum / samples.len() as f64,
+            max_hps: samples.iter().copied().fold(0.0, f64::max),
+        });
+    }
+    Ok(results)
+}
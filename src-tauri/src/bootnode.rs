@@ -0,0 +1,178 @@
+use anyhow::{anyhow, Result};
+use futures_util::{SinkExt, StreamExt};
+use lazy_static::lazy_static;
+use serde::Serialize;
+use std::{
+    collections::HashMap,
+    sync::atomic::{AtomicU64, Ordering},
+    time::{Duration, Instant},
+};
+use tokio::sync::Mutex;
+use tokio_tungstenite::tungstenite::Message;
+
+/// Time between bootnode height probes.
+const PROBE_INTERVAL: Duration = Duration::from_secs(5);
+/// A probe that gets no reply within this long counts as failed.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(3);
+const PROBE_ID: u64 = 4242;
+
+/// RPC used to read the bootnode's height.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProbeMethod {
+    /// `system_syncState`: max(currentBlock, highestBlock).
+    #[default]
+    SyncState,
+    /// `chain_getHeader` (best block); used when the bootnode rejects `system_*` calls.
+    BestHeader,
+}
+
+impl ProbeMethod {
+    fn rpc(self) -> &'static str {
+        match self {
+            ProbeMethod::SyncState => "system_syncState",
+            ProbeMethod::BestHeader => "chain_getHeader",
+        }
+    }
+}
+
+/// Bootnode probe results per chain (`get_bootnode_probe`), to tell a bad endpoint
+/// from a quiet network.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ProbeStats {
+    pub url: String,
+    pub method: ProbeMethod,
+    pub attempts: u64,
+    pub successes: u64,
+    /// successes / attempts (0 before the first attempt)
+    pub success_rate: f64,
+    /// Whether the WebSocket to the bootnode is currently open.
+    pub connected: Option<bool>,
+    /// Bootnode's best/highest block from the last successful probe.
+    pub last_height: Option<u64>,
+    /// Unix seconds of the last successful probe.
+    pub last_success_ts: Option<i64>,
+    pub last_error: Option<String>,
+    #[serde(skip)]
+    pub last_success_at: Option<Instant>,
+}
+
+static PROBE_GEN_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+lazy_static! {
+    static ref PROBES: Mutex<HashMap<String, ProbeStats>> = Mutex::new(HashMap::new());
+    // Generation of the current probe task per chain; older tasks exit on their next loop.
+    static ref PROBE_GENS: Mutex<HashMap<String, u64>> = Mutex::new(HashMap::new());
+}
+
+pub async fn stats(chain: &str) -> Option<ProbeStats> {
+    PROBES.lock().await.get(chain).cloned()
+}
+
+// A JSON-RPC error reply (as opposed to a transport failure).
+#[derive(Debug, thiserror::Error)]
+#[error("error reply: {0}")]
+struct RpcError(serde_json::Value);
+
+// One request-response round trip; replies to other ids are skipped.
+async fn probe_once(ws: &mut crate::net::WsStream, method: ProbeMethod) -> Result<u64> {
+    let req = serde_json::json!({
+        "jsonrpc": "2.0", "id": PROBE_ID, "method": method.rpc(), "params": []
+    });
+    ws.send(Message::Text(req.to_string())).await?;
+    let reply = tokio::time::timeout(PROBE_TIMEOUT, async {
+        while let Some(msg) = ws.next().await {
+            if let Message::Text(txt) = msg? {
+                let val: serde_json::Value = serde_json::from_str(&txt)?;
+                if val.get("id").and_then(|i| i.as_u64()) == Some(PROBE_ID) {
+                    return Ok(val);
+                }
+            }
+        }
+        Err(anyhow!("connection closed"))
+    })
+    .await
+    .map_err(|_| anyhow!("no reply within {}s", PROBE_TIMEOUT.as_secs()))??;
+    let Some(res) = reply.get("result") else {
+        let err = reply.get("error").cloned().unwrap_or(reply);
+        return Err(RpcError(err).into());
+    };
+    let num = |k: &str| res.get(k).and_then(crate::miner::parse_u64_from_json);
+    match method {
+        ProbeMethod::SyncState => num("highestBlock")
+            .into_iter()
+            .chain(num("currentBlock"))
+            .max(),
+        ProbeMethod::BestHeader => num("number"),
+    }
+    .ok_or_else(|| anyhow!("reply without block numbers"))
+}
+
+/// End `chain`'s probe task (it exits at its next wake-up) and drop its stats.
+pub async fn stop_probe(chain: &str) {
+    PROBE_GENS.lock().await.remove(chain);
+    PROBES.lock().await.remove(chain);
+}
+
+/// Probe `url` every `PROBE_INTERVAL` over one persistent WebSocket (reconnecting as
+/// needed), switching to `BestHeader` if the bootnode refuses `system_syncState`.
+/// Replaces any earlier probe task for `chain`; `stop_probe` ends it.
+pub fn spawn_probe(chain: String, url: &'static str) {
+    let gen = PROBE_GEN_COUNTER.fetch_add(1, Ordering::SeqCst) + 1;
+    tauri::async_runtime::spawn(async move {
+        {
+            // tasks may register out of order; only the newest generation wins
+            let mut gens = PROBE_GENS.lock().await;
+            let cur = gens.entry(chain.clone()).or_insert(gen);
+            *cur = (*cur).max(gen);
+        }
+        PROBES.lock().await.insert(
+            chain.clone(),
+            ProbeStats {
+                url: url.to_string(),
+                ..Default::default()
+            },
+        );
+        let mut ws_opt: Option<crate::net::WsStream> = None;
+        let mut method = ProbeMethod::default();
+        loop {
+            if PROBE_GENS.lock().await.get(&chain) != Some(&gen) {
+                break;
+            }
+            if ws_opt.is_none() {
+                ws_opt = crate::net::connect_ws(url).await.ok();
+            }
+            let res = match ws_opt.as_mut() {
+                Some(ws) => probe_once(ws, method).await,
+                None => Err(anyhow!("couldn't connect")),
+            };
+            match &res {
+                Err(e) if e.is::<RpcError>() => {
+                    if method == ProbeMethod::SyncState {
+                        method = ProbeMethod::BestHeader;
+                    }
+                }
+                // a missed reply may arrive late and confuse the next probe; start over
+                Err(_) => ws_opt = None,
+                Ok(_) => {}
+            }
+            if let Some(s) = PROBES.lock().await.get_mut(&chain) {
+                s.attempts += 1;
+                s.method = method;
+                s.connected = Some(ws_opt.is_some());
+                match res {
+                    Ok(h) => {
+                        s.successes += 1;
+                        s.last_height = Some(h);
+                        s.last_success_ts = Some(crate::timeutil::now_secs());
+                        s.last_success_at = Some(Instant::now());
+                        s.last_error = None;
+                    }
+                    Err(e) => s.last_error = Some(e.to_string()),
+                }
+                s.success_rate = s.successes as f64 / s.attempts as f64;
+            }
+            tokio::time::sleep(PROBE_INTERVAL).await;
+        }
+    });
+}
@@ -0,0 +1,314 @@
+use anyhow::{anyhow, Result};
+use lazy_static::lazy_static;
+use serde::Serialize;
+use std::{collections::HashMap, time::Duration};
+use tokio::sync::Mutex;
+
+use crate::net::{self, RetryPolicy};
+
+/// Metadata is a few hundred KB of hex; give the local node longer than a status poll.
+const LOCAL_TIMEOUT: Duration = Duration::from_secs(5);
+
+lazy_static! {
+    // constants read from RPC, per chain (fetched once per app run)
+    static ref CONSTANTS: Mutex<HashMap<String, ChainConstants>> = Mutex::new(HashMap::new());
+}
+
+/// `get_chain_constants` result. Values the source can't tell are null; the static
+/// chain table only knows the token symbol/decimals.
+#[derive(Debug, Clone, Serialize)]
+pub struct ChainConstants {
+    pub chain: String,
+    /// Raw chain units as a decimal string (u128).
+    pub existential_deposit: Option<String>,
+    /// Expected time between blocks.
+    pub block_time_ms: Option<u64>,
+    /// Metadata constant the block time came from ("TargetBlockTime", "ExpectedBlockTime",
+    /// or "MinimumPeriod", which is doubled).
+    pub block_time_constant: Option<&'static str>,
+    pub ss58_prefix: Option<u16>,
+    pub token_symbol: String,
+    pub token_decimals: u32,
+    /// "local" | "bootnode" | "static"
+    pub source: &'static str,
+}
+
+// SCALE compact integer at the start of `b`: (value, encoded length).
+fn compact(b: &[u8]) -> Option<(u64, usize)> {
+    let first = *b.first()?;
+    match first & 0b11 {
+        0 => Some(((first >> 2) as u64, 1)),
+        1 => {
+            let v = u16::from_le_bytes([first, *b.get(1)?]) >> 2;
+            Some((v as u64, 2))
+        }
+        2 => {
+            let v = u32::from_le_bytes(b.get(..4)?.try_into().ok()?) >> 2;
+            Some((v as u64, 4))
+        }
+        _ => {
+            let n = (first >> 2) as usize + 4;
+            if n > 8 {
+                return None;
+            }
+            let mut buf = [0u8; 8];
+            buf[..n].copy_from_slice(b.get(1..1 + n)?);
+            Some((u64::from_le_bytes(buf), 1 + n))
+        }
+    }
+}
+
+/// Value bytes of the pallet constant `name` in V14+ metadata, if it is `width` bytes
+/// wide. Rather than decoding the whole type registry, look for the constant's encoding:
+/// compact name length, name, compact type id, compact value length, value.
+fn find_constant<'a>(meta: &'a [u8], name: &str, width: usize) -> Option<&'a [u8]> {
+    let needle = name.as_bytes();
+    if needle.is_empty() {
+        return None;
+    }
+    // names here are short, so the length prefix is the single-byte compact form
+    let prefix = (needle.len() as u8) << 2;
+    let mut from = 0;
+    while let Some(pos) = meta[from..]
+        .windows(needle.len())
+        .position(|w| w == needle)
+        .map(|p| p + from)
+    {
+        from = pos + 1;
+        if pos == 0 || meta[pos - 1] != prefix {
+            continue;
+        }
+        let rest = &meta[pos + needle.len()..];
+        let Some((_ty, n)) = compact(rest) else {
+            continue;
+        };
+        let rest = &rest[n..];
+        let Some((len, n)) = compact(rest) else {
+            continue;
+        };
+        if len as usize == width {
+            if let Some(v) = rest.get(n..n + width) {
+                return Some(v);
+            }
+        }
+    }
+    None
+}
+
+// Metadata bytes from the `state_getMetadata` hex string; only V14 and later are read.
+fn decode_metadata(hex_str: &str) -> Result<Vec<u8>> {
+    let bytes = hex::decode(hex_str.trim_start_matches("0x"))?;
+    if bytes.get(..4) != Some(b"meta") {
+        return Err(anyhow!("metadata has no magic number"));
+    }
+    match bytes.get(4) {
+        Some(&v) if v >= 14 => Ok(bytes),
+        Some(&v) => Err(anyhow!("metadata V{v} is not supported (need V14+)")),
+        None => Err(anyhow!("metadata is truncated")),
+    }
+}
+
+fn block_time(meta: &[u8]) -> Option<(u64, &'static str)> {
+    let u64_of = |b: &[u8]| Some(u64::from_le_bytes(b.try_into().ok()?));
+    for name in ["TargetBlockTime", "ExpectedBlockTime"] {
+        if let Some(ms) = find_constant(meta, name, 8).and_then(u64_of) {
+            return Some((ms, name));
+        }
+    }
+    // pallet_timestamp: blocks are at least twice the minimum period apart
+    find_constant(meta, "MinimumPeriod", 8)
+        .and_then(u64_of)
+        .map(|ms| (ms * 2, "MinimumPeriod"))
+}
+
+async fn call(url: &str, method: &str, timeout: Option<Duration>) -> Result<serde_json::Value> {
+    let body = serde_json::json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": [] });
+    let mut req = net::client().post(url).json(&body);
+    if let Some(t) = timeout {
+        req = req.timeout(t);
+    }
+    let val: serde_json::Value = req.send().await?.error_for_status()?.json().await?;
+    val.get("result")
+        .filter(|r| !r.is_null())
+        .cloned()
+        .ok_or_else(|| anyhow!("{method}: no result"))
+}
+
+// Read the constants through one endpoint. Metadata is required; properties are optional.
+async fn from_endpoint(
+    chain: &crate::chains::ChainInfo,
+    url: &str,
+    source: &'static str,
+) -> Result<ChainConstants> {
+    let (meta, props) = if source == "local" {
+        tokio::join!(
+            call(url, "state_getMetadata", Some(LOCAL_TIMEOUT)),
+            call(url, "system_properties", Some(LOCAL_TIMEOUT))
+        )
+    } else {
+        tokio::join!(
+            net::with_retry("state_getMetadata", RetryPolicy::QUICK, || call(
+                url,
+                "state_getMetadata",
+                None
+            )),
+            net::with_retry("system_properties", RetryPolicy::QUICK, || call(
+                url,
+                "system_properties",
+                None
+            ))
+        )
+    };
+    let meta = meta?;
+    let meta = decode_metadata(
+        meta.as_str()
+            .ok_or_else(|| anyhow!("metadata is not hex"))?,
+    )?;
+    let props = props.unwrap_or_default();
+
+    let existential_deposit = find_constant(&meta, "ExistentialDeposit", 16)
+        .and_then(|b| b.try_into().ok())
+        .map(|b| u128::from_le_bytes(b).to_string());
+    let (block_time_ms, block_time_constant) = block_time(&meta).unzip();
+    let ss58_prefix = props
+        .get("ss58Format")
+        .and_then(|v| v.as_u64())
+        .and_then(|v| u16::try_from(v).ok())
+        .or_else(|| {
+            find_constant(&meta, "SS58Prefix", 2)
+                .and_then(|b| b.try_into().ok())
+                .map(u16::from_le_bytes)
+        });
+    Ok(ChainConstants {
+        chain: chain.ui_name.to_string(),
+        existential_deposit,
+        block_time_ms,
+        block_time_constant,
+        ss58_prefix,
+        token_symbol: props
+            .get("tokenSymbol")
+            .and_then(crate::rpc::extract_symbol)
+            .unwrap_or_else(|| chain.token_symbol.to_string()),
+        token_decimals: props
+            .get("tokenDecimals")
+            .and_then(crate::rpc::extract_decimals)
+            .unwrap_or(chain.token_decimals),
+        source,
+    })
+}
+
+/// Constants for `chain`: from its local node when that is running, else its bootnode
+/// (cached for the session either way), else the static chain table (not cached, so
+/// RPC is tried again next time).
+pub async fn get(chain: &str) -> Result<ChainConstants> {
+    let info = crate::chains::chain_info(chain).ok_or_else(|| anyhow!("unknown chain"))?;
+    if let Some(c) = CONSTANTS.lock().await.get(chain) {
+        return Ok(c.clone());
+    }
+    let mut found = None;
+    if crate::miner::is_chain_alive(chain).await {
+        let url = crate::miner::local_http_url(chain).await;
+        match from_endpoint(info, &url, "local").await {
+            Ok(c) => found = Some(c),
+            Err(e) => eprintln!("chain constants (local node): {e}"),
+        }
+    }
+    if found.is_none() {
+        if let Some(ws) = info.bootnode_ws {
+            // substrate serves HTTP JSON-RPC on the same endpoint as WS
+            let url = ws
+                .replacen("wss://", "https://", 1)
+                .replacen("ws://", "http://", 1);
+            match from_endpoint(info, &url, "bootnode").await {
+                Ok(c) => found = Some(c),
+                Err(e) => eprintln!("chain constants (bootnode): {e}"),
+            }
+        }
+    }
+    if let Some(c) = found {
+        CONSTANTS.lock().await.insert(chain.to_string(), c.clone());
+        return Ok(c);
+    }
+    Ok(ChainConstants {
+        chain: info.ui_name.to_string(),
+        existential_deposit: None,
+        block_time_ms: None,
+        block_time_constant: None,
+        ss58_prefix: None,
+        token_symbol: info.token_symbol.to_string(),
+        token_decimals: info.token_decimals,
+        source: "static",
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // one constant as it sits in the metadata: name, type id, value
+    fn entry(name: &str, ty: &[u8], value: &[u8]) -> Vec<u8> {
+        let mut out = vec![(name.len() as u8) << 2];
+        out.extend_from_slice(name.as_bytes());
+        out.extend_from_slice(ty);
+        out.push((value.len() as u8) << 2);
+        out.extend_from_slice(value);
+        out
+    }
+
+    #[test]
+    fn compact_modes() {
+        // single byte
+        assert_eq!(compact(&[0x00]), Some((0, 1)));
+        assert_eq!(compact(&[0xfc, 0xff]), Some((63, 1)));
+        // two bytes
+        assert_eq!(compact(&[0x01, 0x01]), Some((64, 2)));
+        assert_eq!(compact(&[0xfd, 0xff]), Some((16383, 2)));
+        // four bytes
+        assert_eq!(compact(&[0x02, 0x00, 0x01, 0x00]), Some((16384, 4)));
+        assert_eq!(compact(&[0xfe, 0xff, 0xff, 0xff]), Some(((1 << 30) - 1, 4)));
+        // big integer: length in the upper six bits, then the value
+        assert_eq!(compact(&[0x03, 0x00, 0x00, 0x00, 0x40]), Some((1 << 30, 5)));
+        assert_eq!(
+            compact(&[0x13, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]),
+            Some((u64::MAX, 9))
+        );
+        // more than eight bytes doesn't fit
+        assert_eq!(compact(&[0x17, 0, 0, 0, 0, 0, 0, 0, 0, 1]), None);
+    }
+
+    #[test]
+    fn compact_truncated_is_none() {
+        assert_eq!(compact(&[]), None);
+        assert_eq!(compact(&[0x01]), None);
+        assert_eq!(compact(&[0x02, 0x00, 0x01]), None);
+        assert_eq!(compact(&[0x03, 0x00, 0x00, 0x00]), None);
+        assert_eq!(compact(&[0x13, 0xff, 0xff]), None);
+    }
+
+    #[test]
+    fn finds_constants_by_name_and_width() {
+        let ms = 10_000u64.to_le_bytes();
+        let mut meta = b"meta\x0e".to_vec();
+        // a longer name that contains the one we look for, and a two-byte type id
+        meta.extend(entry("XTargetBlockTime", &[0x01], &1u64.to_le_bytes()));
+        meta.extend(entry("TargetBlockTime", &[0x01, 0x04], &ms));
+        meta.extend(entry("SS58Prefix", &[0x08], &189u16.to_le_bytes()));
+
+        assert_eq!(find_constant(&meta, "TargetBlockTime", 8), Some(&ms[..]));
+        assert_eq!(block_time(&meta), Some((10_000, "TargetBlockTime")));
+        assert_eq!(find_constant(&meta, "SS58Prefix", 2), Some(&[189, 0][..]));
+        // wrong width, missing name
+        assert_eq!(find_constant(&meta, "SS58Prefix", 4), None);
+        assert_eq!(find_constant(&meta, "ExistentialDeposit", 16), None);
+        assert_eq!(find_constant(&meta, "", 8), None);
+    }
+
+    #[test]
+    fn truncated_metadata_is_none() {
+        let full = entry("MinimumPeriod", &[0x04], &3000u64.to_le_bytes());
+        assert_eq!(block_time(&full), Some((6000, "MinimumPeriod")));
+        for end in 0..full.len() {
+            assert_eq!(find_constant(&full[..end], "MinimumPeriod", 8), None);
+        }
+    }
+}
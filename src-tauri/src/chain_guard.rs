@@ -0,0 +1,195 @@
+use anyhow::{anyhow, Result};
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+use tokio::sync::Mutex;
+
+use crate::net::{self, RetryPolicy};
+
+/// Marker we keep next to the node's data: `{chain_dir}/gui-chain.json`.
+const MARKER_FILE: &str = "gui-chain.json";
+
+lazy_static! {
+    // genesis hashes reported by bootnodes, for chains whose table entry has none
+    static ref BOOTNODE_GENESIS: Mutex<HashMap<String, String>> = Mutex::new(HashMap::new());
+}
+
+/// Which chain the data in a chain directory belongs to, written once its node answered
+/// RPC and the genesis checked out.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChainMarker {
+    pub chain: String,
+    /// Set when the data came from a custom chain spec.
+    pub chain_spec_path: Option<String>,
+    pub genesis_hash: String,
+    /// Unix seconds.
+    pub written_at: i64,
+}
+
+/// The chain directory holds another network's data. Starting is refused; the UI offers
+/// `remediations`: "repair" (wipe the database) or "data_dir" (another `--base-path`).
+#[derive(Debug, Clone, Serialize, thiserror::Error)]
+#[error(
+    "{} holds data for {found}, not {expected}; use Repair to wipe it or start with another data folder (--base-path)",
+    .chain_dir.display()
+)]
+#[serde(rename_all = "camelCase")]
+pub struct ChainDataMismatch {
+    pub chain: String,
+    pub chain_dir: PathBuf,
+    pub expected: String,
+    pub found: String,
+    pub expected_genesis: Option<String>,
+    pub found_genesis: String,
+    pub remediations: Vec<&'static str>,
+}
+
+// "resonance" or "custom spec <path> (chain resonance)"
+fn describe(chain: &str, spec: Option<&str>) -> String {
+    match spec {
+        Some(spec) => format!("custom spec {spec} (chain {chain})"),
+        None => chain.to_string(),
+    }
+}
+
+fn marker_path(chain_dir: &Path) -> PathBuf {
+    chain_dir.join(MARKER_FILE)
+}
+
+fn load_marker(chain_dir: &Path) -> Option<ChainMarker> {
+    let bytes = fs::read(marker_path(chain_dir)).ok()?;
+    serde_json::from_slice(&bytes).ok()
+}
+
+/// The genesis hash `chain` must have: pinned in the chain table, else what its
+/// bootnode reports (asked once per session). None for custom specs, and for unpinned
+/// chains whose bootnode can't be reached.
+async fn expected_genesis(chain: &str, spec: Option<&str>) -> Option<String> {
+    if spec.is_some() {
+        return None;
+    }
+    match crate::chains::chain_info(chain)?.genesis_hash {
+        Some(h) => Some(h.to_string()),
+        None => bootnode_genesis(chain).await,
+    }
+}
+
+/// Before a start: refuse when `chain_dir` has a database that, according to its
+/// marker, belongs to another chain than `chain`/`spec`: another kind (spec or not),
+/// another known chain, or another genesis than `chain`'s. No database or no marker
+/// (data from before markers, or never synced) passes.
+pub async fn check(
+    chain: &str,
+    spec: Option<&str>,
+    chain_dir: &Path,
+) -> Result<(), ChainDataMismatch> {
+    let has_db = fs::read_dir(chain_dir.join("db").join("full"))
+        .map(|mut d| d.next().is_some())
+        .unwrap_or(false);
+    if !has_db {
+        return Ok(());
+    }
+    let Some(marker) = load_marker(chain_dir) else {
+        return Ok(());
+    };
+    let expected_genesis = expected_genesis(chain, spec).await;
+    let other_kind = marker.chain_spec_path.is_some() != spec.is_some();
+    // without a spec the marker's chain is a table name too
+    let other_chain = spec.is_none() && marker.chain != chain;
+    let other_genesis = expected_genesis
+        .as_deref()
+        .is_some_and(|g| !g.eq_ignore_ascii_case(&marker.genesis_hash));
+    if !other_kind && !other_chain && !other_genesis {
+        return Ok(());
+    }
+    Err(ChainDataMismatch {
+        chain: chain.to_string(),
+        chain_dir: chain_dir.to_path_buf(),
+        expected: describe(chain, spec),
+        found: describe(&marker.chain, marker.chain_spec_path.as_deref()),
+        expected_genesis,
+        found_genesis: marker.genesis_hash,
+        remediations: vec!["repair", "data_dir"],
+    })
+}
+
+// `chain_getBlockHash(0)` from `chain`'s bootnode (cached for the session).
+async fn bootnode_genesis(chain: &str) -> Option<String> {
+    if let Some(h) = BOOTNODE_GENESIS.lock().await.get(chain) {
+        return Some(h.clone());
+    }
+    let ws = crate::chains::chain_info(chain)?.bootnode_ws?;
+    // substrate serves HTTP JSON-RPC on the same endpoint as WS
+    let http_url = ws
+        .replacen("wss://", "https://", 1)
+        .replacen("ws://", "http://", 1);
+    let body = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "chain_getBlockHash",
+        "params": [0]
+    });
+    let val: serde_json::Value =
+        net::with_retry("chain_getBlockHash", RetryPolicy::QUICK, || async {
+            Ok(net::client()
+                .post(&http_url)
+                .json(&body)
+                .send()
+                .await?
+                .error_for_status()?
+                .json()
+                .await?)
+        })
+        .await
+        .ok()?;
+    let hash = val.get("result")?.as_str()?.to_string();
+    BOOTNODE_GENESIS
+        .lock()
+        .await
+        .insert(chain.to_string(), hash.clone());
+    Some(hash)
+}
+
+/// Once the node answers RPC: compare its genesis (`local_genesis`) with the expected
+/// one and, if it matches (or can't be known), record the marker for the next `check`.
+pub async fn verify(
+    chain: &str,
+    spec: Option<&str>,
+    chain_dir: &Path,
+    local_genesis: &str,
+) -> Result<(), ChainDataMismatch> {
+    if let Some(expected) = expected_genesis(chain, spec).await {
+        if !expected.eq_ignore_ascii_case(local_genesis) {
+            return Err(ChainDataMismatch {
+                chain: chain.to_string(),
+                chain_dir: chain_dir.to_path_buf(),
+                expected: describe(chain, spec),
+                found: format!("a chain with genesis {local_genesis}"),
+                expected_genesis: Some(expected),
+                found_genesis: local_genesis.to_string(),
+                remediations: vec!["repair", "data_dir"],
+            });
+        }
+    }
+    let marker = ChainMarker {
+        chain: chain.to_string(),
+        chain_spec_path: spec.map(str::to_string),
+        genesis_hash: local_genesis.to_string(),
+        written_at: crate::timeutil::now_secs(),
+    };
+    if let Err(e) = write_marker(chain_dir, &marker) {
+        eprintln!("chain marker: {e}");
+    }
+    Ok(())
+}
+
+fn write_marker(chain_dir: &Path, marker: &ChainMarker) -> Result<()> {
+    if !chain_dir.is_dir() {
+        return Err(anyhow!("{} does not exist", chain_dir.display()));
+    }
+    crate::persist::write_atomic(&marker_path(chain_dir), &serde_json::to_vec_pretty(marker)?)
+}
@@ -0,0 +1,339 @@
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+/// Static description of a chain the GUI knows about.
+/// This is the single place mapping the UI name to the node's CLI arg and on-disk id.
+#[derive(Debug)]
+pub struct ChainInfo {
+    /// Name used by the UI and in persisted settings ("resonance").
+    pub ui_name: &'static str,
+    /// Value passed to `quantus-node --chain`.
+    pub cli_arg: &'static str,
+    /// Directory name under `{base}/chains/` (the chain spec's `id`).
+    pub chain_id: &'static str,
+    /// Why the chain can't be selected or started yet, if it can't (completes
+    /// "<chain> is not available yet: …").
+    pub unavailable: Option<&'static str>,
+    /// Public bootnode JSON-RPC endpoint used for the "highest" block.
+    pub bootnode_ws: Option<&'static str>,
+    /// Known heavy block ranges that need safe sync.
+    pub default_safe_ranges: &'static [(u64, u64)],
+    /// Token symbol/decimals used for balances unless the bootnode reports others.
+    pub token_symbol: &'static str,
+    pub token_decimals: u32,
+    /// Indexer used for balance lookups, if the chain has one.
+    pub balance_graphql: Option<&'static str>,
+    /// Block 0 hash ("0x…"), checked against local data by `chain_guard`. Unpinned chains
+    /// are checked against what their bootnode reports, before a start too.
+    pub genesis_hash: Option<&'static str>,
+    /// SS58 address prefix; the rewards address is re-encoded with it per chain.
+    pub ss58_prefix: u16,
+}
+
+pub const CHAINS: &[ChainInfo] = &[
+    ChainInfo {
+        ui_name: "resonance",
+        cli_arg: "live_resonance",
+        chain_id: "resonance",
+        unavailable: None,
+        bootnode_ws: Some("wss://a.t.res.fm"),
+        // performance test produced heavy blocks in these windows
+        default_safe_ranges: &[(13300, 13399), (19500, 19599)],
+        token_symbol: "RES",
+        token_decimals: 12,
+        balance_graphql: Some("https://gql.res.fm/graphql"),
+        genesis_hash: None,
+        ss58_prefix: 189,
+    },
+    ChainInfo {
+        ui_name: "heisenberg",
+        cli_arg: "heisenberg",
+        chain_id: "heisenberg",
+        unavailable: Some("it requires quantus-node 0.1.6-98ceb8de72a"),
+        bootnode_ws: Some("wss://a.i.res.fm"),
+        default_safe_ranges: &[],
+        // placeholder until the chain is live; the bootnode's system_properties win
+        token_symbol: "QU",
+        token_decimals: 12,
+        balance_graphql: None,
+        genesis_hash: None,
+        // same as resonance until the chain registers its own
+        ss58_prefix: 189,
+    },
+    ChainInfo {
+        // mainnet (placeholder until launch; the node has no such chain yet)
+        ui_name: "quantus",
+        cli_arg: "quantus",
+        chain_id: "quantus",
+        unavailable: Some("mainnet hasn't launched; mine on Resonance until then"),
+        bootnode_ws: None,
+        default_safe_ranges: &[],
+        token_symbol: "QU",
+        token_decimals: 12,
+        balance_graphql: None,
+        genesis_hash: None,
+        // placeholder, like the rest of this entry
+        ss58_prefix: 189,
+    },
+];
+
+pub fn chain_info(ui_name: &str) -> Option<&'static ChainInfo> {
+    CHAINS.iter().find(|c| c.ui_name == ui_name)
+}
+
+/// A known chain marked `unavailable` was selected or started.
+#[derive(Debug, Clone, Serialize, thiserror::Error)]
+#[error("{chain} is not available yet: {reason}")]
+pub struct ChainNotYetAvailable {
+    pub chain: String,
+    pub reason: &'static str,
+}
+
+/// Err for a known chain that can't be used yet; unknown names pass (callers check those).
+pub fn ensure_available(ui_name: &str) -> Result<(), ChainNotYetAvailable> {
+    match chain_info(ui_name).and_then(|c| c.unavailable) {
+        Some(reason) => Err(ChainNotYetAvailable {
+            chain: ui_name.to_string(),
+            reason,
+        }),
+        None => Ok(()),
+    }
+}
+
+/// One entry of `list_chains`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ChainListing {
+    pub name: &'static str,
+    pub enabled: bool,
+    /// Why not, when `enabled` is false.
+    pub unavailable_reason: Option<&'static str>,
+    pub token_symbol: &'static str,
+    pub has_bootnode: bool,
+}
+
+/// Every known chain in display order, with whether it can be selected.
+pub fn listing() -> Vec<ChainListing> {
+    CHAINS
+        .iter()
+        .map(|c| ChainListing {
+            name: c.ui_name,
+            enabled: c.unavailable.is_none(),
+            unavailable_reason: c.unavailable,
+            token_symbol: c.token_symbol,
+            has_bootnode: c.bootnode_ws.is_some(),
+        })
+        .collect()
+}
+
+/// Base data dir used by quantus-node, e.g. on Linux: ~/.local/share/quantus-node
+pub fn node_base_path() -> Result<PathBuf> {
+    Ok(crate::paths::data_dir().join("quantus-node"))
+}
+
+/// A `--base-path`/`-d` in node extra args.
+pub fn base_path_from_args(args: &[String]) -> Option<PathBuf> {
+    let mut iter = args.iter();
+    while let Some(a) = iter.next() {
+        if let Some(p) = a.strip_prefix("--base-path=") {
+            return Some(PathBuf::from(p));
+        }
+        if a == "--base-path" || a == "-d" {
+            return iter.next().map(PathBuf::from);
+        }
+    }
+    None
+}
+
+/// A chain selection resolved to what we pass to the node and where it keeps its data.
+/// Every path the GUI touches in a node's data (Repair, Unlock, node key, import, the
+/// chain guard) comes from here, so it is the directory the node itself uses.
+#[derive(Debug, Clone)]
+pub struct ResolvedChain {
+    pub cli_arg: String,
+    pub chain_id: String,
+    /// The node's `--base-path`/`-d`, else `node_base_path()`.
+    pub base_path: PathBuf,
+}
+
+impl ResolvedChain {
+    /// {base}/chains/{chain_id}
+    pub fn chain_dir(&self) -> PathBuf {
+        self.base_path.join("chains").join(&self.chain_id)
+    }
+
+    /// {base}/chains/{chain_id}/db/full — what Repair wipes.
+    pub fn db_path(&self) -> PathBuf {
+        self.chain_dir().join("db").join("full")
+    }
+
+    /// {base}/chains/{chain_id}/db/full/LOCK — what Unlock removes.
+    pub fn db_lock_path(&self) -> PathBuf {
+        self.db_path().join("LOCK")
+    }
+
+    /// {base}/chains/{chain_id}/network/secret_dilithium
+    pub fn node_key_path(&self) -> PathBuf {
+        self.chain_dir().join("network").join("secret_dilithium")
+    }
+}
+
+/// Resolve a UI chain name, or an expert chain-spec override, to CLI arg + on-disk id,
+/// under the base path in `extra_args` (the node's). With an override the node gets
+/// `--chain <file>` and the id comes from the spec's `id`.
+pub fn resolve(
+    ui_name: &str,
+    chain_spec_path: Option<&Path>,
+    extra_args: &[String],
+) -> Result<ResolvedChain> {
+    let base_path = match base_path_from_args(extra_args) {
+        Some(p) => p,
+        None => node_base_path()?,
+    };
+    if let Some(spec) = chain_spec_path {
+        let chain_id = validate_chain_spec(spec)?;
+        return Ok(ResolvedChain {
+            cli_arg: spec.to_string_lossy().to_string(),
+            chain_id,
+            base_path,
+        });
+    }
+    let info = chain_info(ui_name).ok_or_else(|| anyhow!("unknown chain: {ui_name}"))?;
+    Ok(ResolvedChain {
+        cli_arg: info.cli_arg.to_string(),
+        chain_id: info.chain_id.to_string(),
+        base_path,
+    })
+}
+
+/// Like `resolve`, but refuses chains that can't be started yet.
+pub fn resolve_for_start(
+    ui_name: &str,
+    chain_spec_path: Option<&Path>,
+    extra_args: &[String],
+) -> Result<ResolvedChain> {
+    if chain_spec_path.is_none() {
+        ensure_available(ui_name)?;
+    }
+    resolve(ui_name, chain_spec_path, extra_args)
+}
+
+/// Check that a raw chain spec exists and parses as JSON with `id` and `name`.
+/// Returns the `id`, which is where the node stores its data (not the file name).
+pub fn validate_chain_spec(spec: &Path) -> Result<String> {
+    if !spec.is_file() {
+        return Err(anyhow!("chain spec not found: {}", spec.display()));
+    }
+    let bytes = std::fs::read(spec)
+        .map_err(|e| anyhow!("failed to read chain spec {}: {e}", spec.display()))?;
+    let v: serde_json::Value = serde_json::from_slice(&bytes)
+        .map_err(|e| anyhow!("chain spec {} is not valid JSON: {e}", spec.display()))?;
+    let field = |k: &str| {
+        v.get(k)
+            .and_then(|x| x.as_str())
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .ok_or_else(|| anyhow!("chain spec {} has no \"{k}\" field", spec.display()))
+    };
+    field("name")?;
+    let id = field("id")?;
+    // the id becomes a path component
+    if id.contains(['/', '\\']) || id == "." || id == ".." {
+        return Err(anyhow!(
+            "chain spec id {id:?} is not a valid directory name"
+        ));
+    }
+    Ok(id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(a: &[&str]) -> Vec<String> {
+        a.iter().map(|s| s.to_string()).collect()
+    }
+
+    // Where quantus-node itself opens its database for this command line: the base path
+    // given on it (else its default), then the chain spec's id. `spec_id` is what the
+    // `--chain` value's spec declares.
+    fn node_db_dir(node_args: &[String], spec_id: &str) -> PathBuf {
+        let mut base = node_base_path().unwrap();
+        let mut i = 0;
+        while i < node_args.len() {
+            let a = &node_args[i];
+            if let Some(p) = a.strip_prefix("--base-path=") {
+                base = PathBuf::from(p);
+            } else if (a == "--base-path" || a == "-d") && i + 1 < node_args.len() {
+                base = PathBuf::from(&node_args[i + 1]);
+                i += 1;
+            }
+            i += 1;
+        }
+        base.join("chains").join(spec_id).join("db").join("full")
+    }
+
+    // The node's command line as `start` builds it: `--chain` first, extra args last.
+    fn node_args(resolved: &ResolvedChain, extra: &[String]) -> Vec<String> {
+        let mut a = args(&["--chain", &resolved.cli_arg, "--validator"]);
+        a.extend(extra.iter().cloned());
+        a
+    }
+
+    #[test]
+    fn repair_path_is_the_node_db_dir_for_every_chain() {
+        let variants = [
+            args(&[]),
+            args(&["--base-path", "/srv/quantus"]),
+            args(&["--base-path=/srv/quantus"]),
+            args(&["-d", "/srv/quantus", "--rpc-port", "9955"]),
+        ];
+        for info in CHAINS {
+            for extra in &variants {
+                let resolved = resolve(info.ui_name, None, extra).unwrap();
+                let node_dir = node_db_dir(&node_args(&resolved, extra), info.chain_id);
+                assert_eq!(
+                    resolved.db_path(),
+                    node_dir,
+                    "{} with {extra:?}",
+                    info.ui_name
+                );
+                assert_eq!(resolved.db_lock_path(), node_dir.join("LOCK"));
+                assert_eq!(resolved.cli_arg, info.cli_arg);
+            }
+        }
+    }
+
+    #[test]
+    fn custom_spec_uses_the_spec_id() {
+        let dir = tempfile::tempdir().unwrap();
+        let spec = dir.path().join("my-spec.json");
+        std::fs::write(&spec, r#"{ "id": "dev_chain", "name": "Dev" }"#).unwrap();
+        let extra = args(&["-d", "/srv/quantus"]);
+        let resolved = resolve("resonance", Some(&spec), &extra).unwrap();
+        assert_eq!(resolved.chain_id, "dev_chain");
+        assert_eq!(resolved.cli_arg, spec.to_string_lossy());
+        assert_eq!(
+            resolved.db_path(),
+            node_db_dir(&node_args(&resolved, &extra), "dev_chain")
+        );
+
+        std::fs::write(&spec, r#"{ "id": "../resonance", "name": "Dev" }"#).unwrap();
+        assert!(resolve("resonance", Some(&spec), &extra).is_err());
+        std::fs::write(&spec, r#"{ "name": "Dev" }"#).unwrap();
+        assert!(resolve("resonance", Some(&spec), &extra).is_err());
+    }
+
+    #[test]
+    fn unknown_and_unavailable_chains() {
+        assert!(resolve("nope", None, &[]).is_err());
+        for info in CHAINS {
+            assert_eq!(
+                resolve_for_start(info.ui_name, None, &[]).is_ok(),
+                info.unavailable.is_none(),
+                "{}",
+                info.ui_name
+            );
+        }
+    }
+}
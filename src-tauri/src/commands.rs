@@ -1,10 +1,12 @@
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
-use tauri::{AppHandle, Emitter};
+use tauri::AppHandle;
 
 use crate::{
+    events,
     miner::{self, MinerConfig},
-    rpc,
+    phase::{self, MinerPhase},
+    restart::{self, RestartOp},
 };
 
 #[derive(Debug, Clone, Deserialize)]
@@ -12,12 +14,261 @@ pub struct ChainSelection {
     pub chain: String,
 }
 
+/// Shared chain name check used by commands and deep links.
+pub fn validate_chain(chain: &str) -> Result<(), String> {
+    if crate::chains::chain_info(chain).is_none() {
+        return Err("unknown chain".into());
+    }
+    Ok(())
+}
+
+/// Locations `open_path` may open; nothing outside this list.
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PathKind {
+    /// quantus-node base path (default ~/.local/share/quantus-node on Linux)
+    DataDir,
+    /// {base}/chains/{chain_id}/db
+    ChainDb,
+    /// {base}/chains/{chain_id}/network (node key)
+    NetworkKeyDir,
+    LogsDir,
+    AppDataDir,
+    /// folder holding the rewards account file
+    AccountDir,
+}
+
+/// Open one of the allow-listed locations in the file manager; returns the resolved
+/// path so the UI can show it. `chain` defaults to the active one (chain-specific kinds).
+#[tauri::command]
+pub async fn open_path(
+    app: AppHandle,
+    kind: PathKind,
+    chain: Option<String>,
+) -> Result<String, String> {
+    use tauri_plugin_opener::OpenerExt;
+
+    let chain_dir = || async {
+        let chain = miner::target_chain(chain.clone()).await?;
+        miner::chain_dir_for(&chain).await
+    };
+    let path = match kind {
+        PathKind::DataDir => match miner::target_chain(chain.clone()).await {
+            Ok(c) => miner::node_base_path_for(&c).await,
+            Err(_) => crate::chains::node_base_path(),
+        },
+        PathKind::ChainDb => chain_dir().await.map(|d| d.join("db")),
+        PathKind::NetworkKeyDir => chain_dir().await.map(|d| d.join("network")),
+        PathKind::LogsDir => Ok(crate::session_log::logs_dir()),
+        PathKind::AppDataDir => Ok(crate::paths::app_data_dir(&app)),
+        PathKind::AccountDir => crate::account_path::account_json_path(&app)
+            .parent()
+            .map(std::path::Path::to_path_buf)
+            .ok_or_else(|| anyhow::anyhow!("account file has no parent directory")),
+    }
+    .map_err(|e| e.to_string())?;
+
+    let shown = path.to_string_lossy().to_string();
+    if !path.is_dir() {
+        return Err(format!("{shown} doesn't exist yet"));
+    }
+    app.opener()
+        .open_path(shown.clone(), None::<&str>)
+        .map_err(|e| format!("couldn't open {shown}: {e}"))?;
+    Ok(shown)
+}
+
+/// Format a raw amount (e.g. a fee estimate) in whole tokens; `precision` defaults to 6.
+#[tauri::command]
+pub async fn format_balance(
+    raw: String,
+    decimals: u32,
+    precision: Option<u32>,
+) -> Result<String, String> {
+    crate::balance::format_balance(
+        &raw,
+        decimals,
+        precision.unwrap_or(crate::balance::DEFAULT_PRECISION),
+    )
+    .map_err(|e| e.to_string())
+}
+
+/// Compare this build with the latest GUI release (cached for an hour unless `force`).
+#[tauri::command]
+pub async fn check_app_update(
+    force: Option<bool>,
+) -> Result<crate::app_update::AppUpdateInfo, String> {
+    crate::app_update::check(force.unwrap_or(false))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_app_update_settings(app: AppHandle) -> crate::app_update::AppUpdateSettings {
+    crate::app_update::load_settings(&app)
+}
+
+/// Turn the daily background update check on or off.
+#[tauri::command]
+pub async fn set_app_update_auto_check(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut s = crate::app_update::load_settings(&app);
+    s.auto_check = enabled;
+    crate::app_update::save_settings(&app, &s).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_throttle_settings(app: AppHandle) -> crate::throttle::ThrottleSettings {
+    crate::throttle::load_settings(&app)
+}
+
+/// Save the thermal/load throttle policy; the background task picks it up on its next
+/// sample.
+#[tauri::command]
+pub async fn set_throttle_settings(
+    app: AppHandle,
+    settings: crate::throttle::ThrottleSettings,
+) -> Result<(), String> {
+    crate::throttle::save_settings(&app, &settings).map_err(|e| e.to_string())
+}
+
+/// Adaptive safe-mode thresholds (slow imports / sync timeouts).
+#[tauri::command]
+pub async fn get_adaptive_safe_mode(app: AppHandle) -> crate::adaptive::AdaptiveSettings {
+    crate::adaptive::load_settings(&app)
+}
+
+/// Save the adaptive safe-mode thresholds; they apply from the next log line.
+#[tauri::command]
+pub async fn set_adaptive_safe_mode(
+    app: AppHandle,
+    settings: crate::adaptive::AdaptiveSettings,
+) -> Result<(), String> {
+    crate::adaptive::save_settings(&app, &settings).map_err(|e| e.to_string())
+}
+
+/// How long a node gets to exit after SIGINT before it is killed.
+#[tauri::command]
+pub async fn get_shutdown_settings(app: AppHandle) -> crate::shutdown::ShutdownSettings {
+    crate::shutdown::load_settings(&app)
+}
+
+/// Save the stop grace period; applies from the next stop.
+#[tauri::command]
+pub async fn set_shutdown_settings(
+    app: AppHandle,
+    settings: crate::shutdown::ShutdownSettings,
+) -> Result<(), String> {
+    crate::shutdown::save_settings(&app, &settings).map_err(|e| e.to_string())
+}
+
+/// How external miner crashes are handled (auto-restart, crash-loop remediations).
+#[tauri::command]
+pub async fn get_external_crashloop_settings(
+    app: AppHandle,
+) -> crate::ext_crash::CrashLoopSettings {
+    crate::ext_crash::load_settings(&app)
+}
+
+/// Save the external miner crash handling; applies from the next exit.
+#[tauri::command]
+pub async fn set_external_crashloop_settings(
+    app: AppHandle,
+    settings: crate::ext_crash::CrashLoopSettings,
+) -> Result<(), String> {
+    crate::ext_crash::save_settings(&app, &settings).map_err(|e| e.to_string())
+}
+
+/// Dead-fork detection (auto-restart, how many checks confirm a fork).
+#[tauri::command]
+pub async fn get_fork_settings(app: AppHandle) -> crate::fork::ForkSettings {
+    crate::fork::load_settings(&app)
+}
+
+/// Save the dead-fork detection settings; applies from the next check.
+#[tauri::command]
+pub async fn set_fork_settings(
+    app: AppHandle,
+    settings: crate::fork::ForkSettings,
+) -> Result<(), String> {
+    crate::fork::save_settings(&app, &settings).map_err(|e| e.to_string())
+}
+
+/// The `/gui-metrics` Prometheus endpoint (off by default).
+#[tauri::command]
+pub async fn get_gui_metrics_settings(app: AppHandle) -> crate::gui_metrics::GuiMetricsSettings {
+    crate::gui_metrics::load_settings(&app)
+}
+
+/// Save the `/gui-metrics` settings; the endpoint is restarted (or stopped) at once.
+#[tauri::command]
+pub async fn set_gui_metrics_settings(
+    app: AppHandle,
+    settings: crate::gui_metrics::GuiMetricsSettings,
+) -> Result<(), String> {
+    crate::gui_metrics::save_settings(&app, &settings).map_err(|e| e.to_string())
+}
+
+/// Which events are notable (`miner:notable`) and at what level.
+#[tauri::command]
+pub async fn get_notable_rules(app: AppHandle) -> crate::notable::NotableRules {
+    crate::notable::load_rules(&app)
+}
+
+/// Save the notability rules; applies to the next event.
+#[tauri::command]
+pub async fn set_notable_rules(
+    app: AppHandle,
+    rules: crate::notable::NotableRules,
+) -> Result<(), String> {
+    crate::notable::save_rules(&app, &rules).map_err(|e| e.to_string())
+}
+
+/// Send a sample `miner:notable` of `kind` (e.g. "found_block") to preview its sound/toast.
+#[tauri::command]
+pub async fn test_notable_event(
+    app: AppHandle,
+    kind: String,
+    chain: Option<String>,
+) -> Result<crate::notable::Notable, String> {
+    let kind: crate::notable::NotableKind = serde_json::from_value(serde_json::json!(kind))
+        .map_err(|_| format!("unknown notable event kind {kind:?}"))?;
+    Ok(crate::notable::test_event(&app, kind, chain))
+}
+
+/// What this machine offers for mining: CPU count and what the throttle can measure.
+#[tauri::command]
+pub async fn get_system_requirements() -> serde_json::Value {
+    serde_json::json!({
+        "os": std::env::consts::OS,
+        "arch": std::env::consts::ARCH,
+        "cpus": num_cpus::get(),
+        // the external miner leaves one core free
+        "max_external_cores": num_cpus::get().saturating_sub(1).max(1),
+        "throttle": crate::throttle::capability(),
+        "cpu_temp_c": crate::throttle::cpu_temp_c(),
+        "load_per_cpu": crate::throttle::load_per_cpu(),
+    })
+}
+
 #[tauri::command]
 pub async fn select_chain(_app: AppHandle, sel: ChainSelection) -> Result<(), String> {
     // keep selection in frontend; backend doesn’t need to persist yet
-    if sel.chain != "resonance" && sel.chain != "heisenberg" && sel.chain != "quantus" {
-        return Err("unknown chain".into());
-    }
+    validate_chain(&sel.chain)?;
+    crate::chains::ensure_available(&sel.chain).map_err(|e| e.to_string())
+}
+
+/// Known chains with whether each can be selected yet (and why not), for the picker.
+#[tauri::command]
+pub fn list_chains() -> Vec<crate::chains::ChainListing> {
+    crate::chains::listing()
+}
+
+/// Called by the frontend once its event listeners are registered; flushes queued deep
+/// links and the startup data migration notice.
+#[tauri::command]
+pub async fn frontend_ready(app: AppHandle) -> Result<(), String> {
+    crate::data_migration::notify(&app);
+    crate::deeplink::mark_frontend_ready(&app).await;
     Ok(())
 }
 
@@ -34,28 +285,104 @@ pub struct StartMinerArgs {
     pub external_num_cores: Option<usize>,
     #[serde(default)]
     pub external_port: Option<u16>,
+    // raw chain spec JSON for local devnets; overrides `chain` for the node
+    #[serde(default)]
+    pub chain_spec_path: Option<String>,
+    // reinstall quantus-node if `binary_path` is stale and nothing is installed
+    #[serde(default = "default_true")]
+    pub allow_download: bool,
+    // "I know what I'm doing": pass flags like --unsafe-rpc-external through
+    #[serde(default)]
+    pub allow_unsafe_args: bool,
+    // threads for the node's built-in miner; mutually exclusive with the external miner
+    #[serde(default)]
+    pub internal_mining_threads: Option<u32>,
+    // one-shot: stop the node this long after it started (see `timed_stop.rs`)
+    #[serde(default)]
+    pub stop_after_secs: Option<u64>,
+}
+
+fn default_true() -> bool {
+    true
 }
 
 #[tauri::command]
-pub async fn start_miner(app: AppHandle, args: StartMinerArgs) -> Result<(), String> {
-    #[derive(Serialize)]
-    struct UiLog<'a> {
-        source: &'a str,
-        line: String,
+pub async fn start_miner(app: AppHandle, mut args: StartMinerArgs) -> Result<(), String> {
+    crate::session_log::command(&args.chain, "start_miner", "");
+    // before any download: a placeholder chain would only fail later, and less clearly
+    if args
+        .chain_spec_path
+        .as_deref()
+        .is_none_or(|p| p.trim().is_empty())
+    {
+        crate::chains::ensure_available(&args.chain).map_err(|e| e.to_string())?;
+    }
+    if crate::benchmark::is_running() {
+        return Err("a benchmark is running; cancel it or wait for it to finish".into());
+    }
+    args.extra_args = crate::extra_args::normalize(args.extra_args, args.allow_unsafe_args)
+        .map_err(|e| e.to_string())?;
+    if let Some(secs) = args.stop_after_secs {
+        crate::timed_stop::validate(secs).map_err(|e| e.to_string())?;
     }
 
-    let _ = app.emit(
-        "miner:log",
-        &UiLog {
-            source: "ui",
-            line: format!(
-                "Starting miner: binary={}, chain={}, rewards_address={}, extra_args={:?}",
-                args.binary_path, args.chain, args.rewards_address, args.extra_args
-            ),
-        },
+    // "default" (or empty) means the node in the configured install dir; a stale path
+    // falls back to (or reinstalls) that one
+    let binary = crate::installer::ensure_node_binary(&app, &args.binary_path, args.allow_download)
+        .await
+        .map_err(|e| e.to_string())?
+        .to_string_lossy()
+        .to_string();
+    let requested = args.binary_path.trim();
+    if binary != requested && !requested.is_empty() && requested != crate::installer::DEFAULT_BINARY
+    {
+        crate::session_log::ui(
+            &app,
+            &args.chain,
+            format!("Binary {} is not usable; using {binary}", args.binary_path),
+        );
+    }
+    args.binary_path = binary;
+    if let Some(threads) = args.internal_mining_threads {
+        let external = args.external_num_cores.is_some() && args.external_port.is_some();
+        crate::internal_miner::validate(
+            &app,
+            std::path::Path::new(&args.binary_path),
+            threads,
+            external,
+            &args.extra_args,
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    }
+
+    crate::session_log::ui(
+        &app,
+        &args.chain,
+        format!(
+            "Starting miner: binary={}, chain={}, rewards_address={}, extra_args={:?}",
+            args.binary_path, args.chain, args.rewards_address, args.extra_args
+        ),
     );
 
-    let app_clone = app.clone();
+    let chain_spec_path = args
+        .chain_spec_path
+        .map(|p| p.trim().to_string())
+        .filter(|p| !p.is_empty());
+    if let Some(p) = chain_spec_path.as_deref() {
+        crate::chains::validate_chain_spec(std::path::Path::new(p)).map_err(|e| e.to_string())?;
+    }
+
+    let Some(_guard) = restart::begin_start(&args.chain, || miner::is_chain_alive(&args.chain))
+        .await
+        .map_err(|e| e.to_string())?
+    else {
+        return Ok(());
+    };
+    miner::set_manual_safe_mode(&args.chain, &args.extra_args).await;
+    miner::clear_paused(&args.chain).await;
+    let (app_clone, chain) = (app.clone(), args.chain.clone());
+    let stop_after_secs = args.stop_after_secs;
     match miner::start(
         app,
         MinerConfig {
@@ -66,42 +393,378 @@ pub async fn start_miner(app: AppHandle, args: StartMinerArgs) -> Result<(), Str
             log_to_file: args.log_to_file,
             external_num_cores: args.external_num_cores,
             external_port: args.external_port,
+            chain_spec_path,
+            internal_mining_threads: args.internal_mining_threads,
         },
+        restart::RestartReason::Manual,
+        "",
     )
     .await
     {
+        // start returns once the node answered RPC or outlived the startup grace period
         Ok(_) => {
-            let _ = app_clone.emit(
-                "miner:log",
-                &UiLog {
-                    source: "ui",
-                    line: "Miner started".into(),
-                },
-            );
+            crate::session_log::ui(&app_clone, &chain, "Miner started");
+            if let Some(secs) = stop_after_secs {
+                // the node is already up: a timer that can't be armed doesn't fail the start
+                let line = match crate::timed_stop::arm(&app_clone, &chain, secs) {
+                    Ok(_) => format!("Stopping the node in {secs} s (timed stop)"),
+                    Err(e) => format!("Couldn't arm the timed stop: {e}"),
+                };
+                crate::session_log::ui(&app_clone, &chain, line);
+            }
             Ok(())
         }
         Err(e) => {
-            let msg = format!("Start failed: {e}");
-            let _ = app_clone.emit(
-                "miner:log",
-                &UiLog {
-                    source: "ui",
-                    line: msg.clone(),
-                },
-            );
+            if let Some(crate::installer::InstallError::BinaryQuarantined {
+                path,
+                detail,
+                remediation,
+                fixable,
+            }) = e.downcast_ref()
+            {
+                events::emit(
+                    &app_clone,
+                    events::MINER_QUARANTINED,
+                    &serde_json::json!({
+                        "path": path,
+                        "detail": detail,
+                        "remediation": remediation,
+                        "fixable": fixable,
+                    }),
+                );
+            }
+            if let Some(m) = e.downcast_ref::<crate::chain_guard::ChainDataMismatch>() {
+                events::emit(&app_clone, events::MINER_CHAIN_MISMATCH, m);
+                crate::remediation::offer(
+                    &app_clone,
+                    &chain,
+                    crate::remediation::ErrorKind::ChainMismatch,
+                    &m.to_string(),
+                );
+            }
+            if let Some(u) = e.downcast_ref::<crate::node_caps::UnsupportedBinary>() {
+                crate::remediation::offer(
+                    &app_clone,
+                    &chain,
+                    crate::remediation::ErrorKind::UnsupportedNode,
+                    &u.to_string(),
+                );
+            }
+            crate::session_log::ui(&app_clone, &chain, format!("Start failed: {e}"));
             Err(e.to_string())
         }
     }
 }
 
+/// Start a chain's node exactly as it last ran (see `miner::SessionCfg`): `chain`, else
+/// the most recently started one. Returns the chain.
 #[tauri::command]
-pub async fn stop_miner(app: AppHandle) -> Result<(), String> {
-    // Inform UI immediately that we're stopping so buttons flip without waiting.
-    let _ = app.emit(
-        "miner:state",
-        &serde_json::json!({ "running": false, "phase": "stopped" }),
-    );
-    miner::stop().await.map_err(|e| e.to_string())
+pub async fn resume_last_session(app: AppHandle, chain: Option<String>) -> Result<String, String> {
+    let session = miner::last_session(&app, chain.as_deref())
+        .ok_or_else(|| "no saved session to resume".to_string())?;
+    let chain = session.cfg.chain;
+    crate::session_log::command(&chain, "resume_last_session", "");
+    if crate::benchmark::is_running() {
+        return Err("a benchmark is running; cancel it or wait for it to finish".into());
+    }
+    let Some(_guard) = restart::begin_start(&chain, || miner::is_chain_alive(&chain))
+        .await
+        .map_err(|e| e.to_string())?
+    else {
+        return Ok(chain);
+    };
+    miner::resume_last_session(app, &chain)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(chain)
+}
+
+/// The saved session `resume_last_session` would start, if any.
+#[tauri::command]
+pub async fn get_last_session(
+    app: AppHandle,
+    chain: Option<String>,
+) -> Result<Option<miner::SessionCfg>, String> {
+    Ok(miner::last_session(&app, chain.as_deref()))
+}
+
+#[tauri::command]
+pub async fn stop_miner(app: AppHandle, chain: Option<String>) -> Result<(), String> {
+    let chain = miner::target_chain(chain)
+        .await
+        .map_err(|e| e.to_string())?;
+    crate::session_log::command(&chain, "stop_miner", "");
+    // a manual stop ends a timed session early; the timer has nothing left to do
+    crate::timed_stop::cancel(&chain);
+    let Some(_guard) = restart::begin(RestartOp::Stop, &chain)
+        .await
+        .map_err(|e| e.to_string())?
+    else {
+        return Ok(());
+    };
+    // Two phases: "stopping" right away so buttons flip, "stopped" only once the processes
+    // are verifiably gone ("stop_failed" otherwise; the node may still be mining).
+    events::emit_state(&app, &chain, true, "stopping");
+    // The phase tracks the active chain; stopping a background chain leaves it alone.
+    let is_active = miner::active_chain().await.as_deref() == Some(chain.as_str());
+    if is_active {
+        phase::set_phase(&app, MinerPhase::Stopping).await;
+    }
+    if let Err(e) = miner::stop_chain(&app, &chain).await {
+        events::emit(
+            &app,
+            events::MINER_STATE,
+            events::MinerState {
+                running: miner::is_chain_alive(&chain).await,
+                phase: "stop_failed",
+                chain: &chain,
+                error: Some(e.to_string()),
+                adopted: false,
+            },
+        );
+        if is_active {
+            phase::set_phase(
+                &app,
+                MinerPhase::Errored {
+                    kind: "stop".into(),
+                },
+            )
+            .await;
+        }
+        return Err(e.to_string());
+    }
+    miner::clear_paused(&chain).await;
+    events::emit_state(&app, &chain, false, "stopped");
+    if is_active {
+        phase::set_phase(&app, MinerPhase::Idle).await;
+    }
+    if let Err(e) = crate::snapshot::save(&app).await {
+        eprintln!("state snapshot: {e}");
+    }
+    Ok(())
+}
+
+/// Stop mining but keep the node syncing (see `miner::pause_mining`); returns how it was
+/// paused.
+#[tauri::command]
+pub async fn pause_mining(
+    app: AppHandle,
+    chain: Option<String>,
+) -> Result<miner::PauseMode, String> {
+    let chain = miner::target_chain(chain)
+        .await
+        .map_err(|e| e.to_string())?;
+    crate::session_log::command(&chain, "pause_mining", "");
+    miner::pause_mining(app, chain, "user")
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn resume_mining(app: AppHandle, chain: Option<String>) -> Result<(), String> {
+    let chain = miner::target_chain(chain)
+        .await
+        .map_err(|e| e.to_string())?;
+    crate::session_log::command(&chain, "resume_mining", "");
+    miner::resume_mining(app, chain, "user")
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MinerStateView {
+    pub phase: MinerPhase,
+    pub running: bool,
+    pub active_chain: Option<String>,
+    pub running_chains: Vec<String>,
+    /// Taken over from a previous GUI instance: no log output until restarted.
+    pub adopted_chains: Vec<String>,
+    /// Armed `stop_after_secs` timers, for a countdown.
+    pub timed_stops: Vec<crate::timed_stop::TimedStop>,
+    /// What the frontend is being sent (`set_event_verbosity`).
+    pub event_verbosity: events::Verbosity,
+}
+
+/// Why `chain`'s node was (re)started, newest first (all chains when `chain` is omitted).
+#[tauri::command]
+pub async fn get_restart_history(
+    app: AppHandle,
+    chain: Option<String>,
+) -> Result<Vec<restart::RestartRecord>, String> {
+    Ok(restart::history(&app, chain.as_deref()))
+}
+
+#[tauri::command]
+pub async fn get_miner_state() -> Result<MinerStateView, String> {
+    Ok(MinerStateView {
+        phase: phase::current().await,
+        running: miner::is_running().await,
+        active_chain: miner::active_chain().await,
+        running_chains: miner::running_chains().await,
+        adopted_chains: miner::adopted_chains().await,
+        timed_stops: crate::timed_stop::all(),
+        event_verbosity: events::verbosity(),
+    })
+}
+
+/// How much of the event stream to send: "minimal" (status, phase, state and notable
+/// only; no log lines), "normal" or "debug" (adds `miner:parse-debug`). Applies to the
+/// next event; back to "normal" on every app start.
+#[tauri::command]
+pub fn set_event_verbosity(level: events::Verbosity) {
+    events::set_verbosity(level);
+}
+
+/// Disarm the timed stop of `chain` (default: the active chain); the node keeps running.
+/// False when none was armed.
+#[tauri::command]
+pub async fn cancel_timed_stop(chain: Option<String>) -> Result<bool, String> {
+    let chain = miner::target_chain(chain)
+        .await
+        .map_err(|e| e.to_string())?;
+    crate::session_log::command(&chain, "cancel_timed_stop", "");
+    Ok(crate::timed_stop::cancel(&chain))
+}
+
+/// Nodes still running from a previous GUI instance (found via pidfiles at startup).
+#[tauri::command]
+pub async fn get_orphaned_nodes() -> Result<Vec<crate::pidfile::PidRecord>, String> {
+    Ok(crate::pidfile::orphans())
+}
+
+/// "Take over management" of an orphaned node: stop/restart/repair work again, but its
+/// log output is gone until it is restarted.
+#[tauri::command]
+pub async fn adopt_orphaned_node(app: AppHandle, chain: String) -> Result<(), String> {
+    crate::session_log::command(&chain, "adopt_orphaned_node", "");
+    miner::adopt(app, chain).await.map_err(|e| e.to_string())
+}
+
+/// Leave an orphaned node running unmanaged and stop offering to take it over.
+#[tauri::command]
+pub async fn dismiss_orphaned_node(chain: String) -> Result<(), String> {
+    crate::session_log::command(&chain, "dismiss_orphaned_node", "");
+    crate::pidfile::take_orphan(&chain);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_logfile_path(chain: Option<String>) -> Result<Option<String>, String> {
+    let Ok(chain) = miner::target_chain(chain).await else {
+        return Ok(None);
+    };
+    Ok(crate::session_log::current_path(&chain)
+        .await
+        .map(|p| p.to_string_lossy().to_string()))
+}
+
+/// Session log files on disk (node and external miner), newest first; only `chain`'s
+/// when given.
+#[tauri::command]
+pub async fn list_log_files(
+    chain: Option<String>,
+) -> Result<Vec<crate::session_log::LogFile>, String> {
+    Ok(crate::session_log::list_files(chain.as_deref()))
+}
+
+/// Lines of `chain`'s logs matching `query` (substring or regex, see `SearchOptions`),
+/// from the in-memory buffer or one session file, oldest first and a page at a time.
+#[tauri::command]
+pub async fn search_logs(
+    chain: Option<String>,
+    query: String,
+    options: Option<crate::log_search::SearchOptions>,
+) -> Result<crate::log_search::SearchResult, String> {
+    let chain = miner::target_chain(chain)
+        .await
+        .map_err(|e| e.to_string())?;
+    crate::log_search::search(&chain, &query, options.unwrap_or_default())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Write session logs to `dir` (absolute; None resets to the default) from now on;
+/// existing files move there in the background (`logs:move-progress`).
+#[tauri::command]
+pub async fn set_log_directory(app: AppHandle, dir: Option<String>) -> Result<String, String> {
+    crate::log_space::set_log_directory(&app, dir)
+        .await
+        .map(|p| p.to_string_lossy().to_string())
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn set_file_logging(
+    app: AppHandle,
+    chain: Option<String>,
+    enabled: bool,
+) -> Result<Option<String>, String> {
+    let chain = miner::target_chain(chain)
+        .await
+        .map_err(|e| e.to_string())?;
+    crate::session_log::command(&chain, "set_file_logging", &format!("enabled={enabled}"));
+    miner::set_file_logging(app, chain, enabled)
+        .await
+        .map(|p| p.map(|p| p.to_string_lossy().to_string()))
+        .map_err(|e| e.to_string())
+}
+
+/// Minimum severity of `miner:event`s sent to the UI ("debug" | "info" | "warn" | "error").
+#[tauri::command]
+pub async fn set_event_severity(level: crate::parse::Severity) -> Result<(), String> {
+    miner::set_min_event_severity(level).await;
+    Ok(())
+}
+
+/// Clear the quarantine flag (macOS) from the node and external miner, then start again.
+#[tauri::command]
+pub async fn retry_after_unquarantine(app: AppHandle, args: StartMinerArgs) -> Result<(), String> {
+    let node =
+        crate::installer::resolve_binary_path(&args.binary_path).map_err(|e| e.to_string())?;
+    crate::installer::remove_quarantine(std::path::Path::new(&node)).map_err(|e| e.to_string())?;
+    if let Ok(ext) = crate::installer::miner_binary_path() {
+        if ext.exists() {
+            crate::installer::remove_quarantine(&ext).map_err(|e| e.to_string())?;
+        }
+    }
+    start_miner(app, args).await
+}
+
+/// Split a pasted extra-args line and normalize it like `start_miner` would, so the UI can
+/// show what will actually be passed.
+#[tauri::command]
+pub fn normalize_extra_args(line: String, allow_unsafe: bool) -> Result<Vec<String>, String> {
+    crate::extra_args::split(&line)
+        .and_then(|args| crate::extra_args::normalize(args, allow_unsafe))
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_install_dir() -> Result<String, String> {
+    crate::installer::install_dir()
+        .map(|p| p.to_string_lossy().to_string())
+        .map_err(|e| e.to_string())
+}
+
+/// Move binaries to `dir` (absolute, writable) and use it from now on; None resets to the
+/// default. `dry_run` returns what would be moved instead.
+#[tauri::command]
+pub async fn set_install_dir(
+    app: AppHandle,
+    dir: Option<String>,
+    dry_run: Option<bool>,
+) -> Result<crate::plan::DryRun<String>, String> {
+    let plan = crate::installer::plan_install_dir(dir).map_err(|e| e.to_string())?;
+    if dry_run.unwrap_or(false) {
+        return Ok(crate::plan::DryRun::Manifest(plan.manifest().await));
+    }
+    if miner::is_running().await {
+        return Err("stop the miner before changing the install directory".into());
+    }
+    crate::installer::set_install_dir(&app, plan)
+        .await
+        .map(|p| crate::plan::DryRun::Applied(p.to_string_lossy().to_string()))
+        .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
@@ -110,46 +773,588 @@ pub async fn read_log_tail() -> Result<Vec<String>, String> {
     Ok(vec![])
 }
 
+/// Blocks authored by our rewards account: this node session's count and the persisted
+/// history (newest first, orphaned ones flagged).
+#[tauri::command]
+pub async fn get_authored_blocks(
+    app: AppHandle,
+    chain: Option<String>,
+) -> Result<serde_json::Value, String> {
+    let chain = miner::target_chain(chain)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(serde_json::json!({
+        "session": crate::authorship::session_count(&chain).await,
+        "history": crate::authorship::history(&app, &chain),
+    }))
+}
+
+/// Bootnode height probe for debugging: endpoint, method, success rate, last height
+/// and error. None for custom chains or before the status task started it.
+#[tauri::command]
+pub async fn get_bootnode_probe(
+    chain: Option<String>,
+) -> Result<Option<crate::bootnode::ProbeStats>, String> {
+    let chain = miner::target_chain(chain)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(crate::bootnode::stats(&chain).await)
+}
+
+/// Measure the external miner's hashrate for `duration_secs` per core count (`cores`,
+/// or 1 / half / all-but-one). Refused while mining; progress as `benchmark:progress`.
+#[tauri::command]
+pub async fn run_benchmark(
+    app: AppHandle,
+    cores: Option<usize>,
+    duration_secs: u32,
+) -> Result<Vec<crate::benchmark::BenchResult>, String> {
+    crate::benchmark::run(&app, cores, duration_secs)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Answer a `miner:safe-mode-countdown`: apply the safe sync change now, or veto it
+/// (not scheduled again for that reason or range until the node restarts).
+#[tauri::command]
+pub async fn confirm_safe_mode(chain: Option<String>, apply: bool) -> Result<(), String> {
+    let chain = miner::target_chain(chain)
+        .await
+        .map_err(|e| e.to_string())?;
+    crate::session_log::command(
+        &chain,
+        "confirm_safe_mode",
+        if apply { "apply" } else { "veto" },
+    );
+    crate::safe_mode_countdown::confirm(&chain, apply).map_err(|e| e.to_string())
+}
+
+/// Stop a running benchmark (its process is killed; `run_benchmark` then fails).
+#[tauri::command]
+pub async fn cancel_benchmark() -> Result<(), String> {
+    crate::benchmark::cancel();
+    Ok(())
+}
+
+/// `address`'s balance on `chain`, from the cache while it is fresh (refreshed in the
+/// background, `miner:balance` on change); `force` always asks the indexer.
 #[tauri::command]
 pub async fn query_balance(
-    _app: AppHandle,
+    app: AppHandle,
     chain: String,
     address: String,
+    force: Option<bool>,
 ) -> Result<crate::rpc::BalanceView, String> {
-    let ws = crate::rpc::bootnode_ws_for_chain(chain.as_str())
-        .ok_or_else(|| "unknown chain".to_string())?;
-    rpc::fetch_balance(ws, &address)
+    if miner::is_custom_chain_active(&chain).await {
+        return Err("balance lookup is unsupported for custom chain specs".into());
+    }
+    // the UI holds the address as created; the chain knows it by its own prefix
+    let address = crate::account_cli::address_for_chain(&address, &chain);
+    crate::balance_cache::get(&app, &chain, &address, force.unwrap_or(false))
         .await
         .map_err(|e| e.to_string())
 }
 
+/// Transfers to and from `address` on `chain`, newest first, `limit` (default 25, max
+/// 100) per page from `cursor`; `indexed` is false on chains without an indexer.
 #[tauri::command]
-pub async fn ensure_miner_and_account(app: AppHandle) -> Result<serde_json::Value, String> {
-    let miner_path = crate::installer::ensure_quantus_node_installed()
+pub async fn query_transfers(
+    chain: String,
+    address: String,
+    cursor: Option<String>,
+    limit: Option<u32>,
+) -> Result<crate::rpc::TransferPage, String> {
+    if miner::is_custom_chain_active(&chain).await {
+        return Err("transfer lookup is unsupported for custom chain specs".into());
+    }
+    let address = crate::account_cli::address_for_chain(&address, &chain);
+    crate::rpc::fetch_transfers(
+        &chain,
+        &address,
+        cursor.as_deref(),
+        limit.unwrap_or(25).clamp(1, 100),
+    )
+    .await
+    .map_err(|e| e.to_string())
+}
+
+/// The rewards address on `chain` (default: the active one), in the chain's SS58
+/// prefix: the one chosen with `set_rewards_address`, else the account's. For QR codes
+/// and explorer links.
+#[tauri::command]
+pub async fn get_account_address(app: AppHandle, chain: Option<String>) -> Result<String, String> {
+    let chain = miner::target_chain(chain)
         .await
         .map_err(|e| e.to_string())?;
     let acct_path = crate::account_path::account_json_path(&app);
-    let acct = crate::account_cli::ensure_account_json(&app, &miner_path, &acct_path)
+    let acct = crate::account_cli::load_account(&app, &acct_path).map_err(|e| e.to_string())?;
+    Ok(crate::account_cli::rewards_address(&app, &acct, &chain))
+}
+
+/// When this installation first started and first fully synced `chain` (default: the
+/// active one), and what it took.
+#[tauri::command]
+pub async fn get_chain_milestones(
+    app: AppHandle,
+    chain: Option<String>,
+) -> Result<crate::milestones::ChainMilestones, String> {
+    let chain = miner::target_chain(chain)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(crate::milestones::get(&app, &chain))
+}
+
+/// Counters for the current node session of `chain` (default: the active one).
+#[tauri::command]
+pub async fn get_session_stats(
+    chain: Option<String>,
+) -> Result<crate::stats::SessionStats, String> {
+    let chain = miner::target_chain(chain)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(crate::stats::get(&chain))
+}
+
+/// What the quantus-node at `binary_path` (default: the installed one) accepts, from its
+/// `--help`; the UI hides toggles for flags it lacks.
+#[tauri::command]
+pub async fn get_node_capabilities(
+    app: AppHandle,
+    binary_path: Option<String>,
+) -> Result<crate::node_caps::NodeCapabilities, String> {
+    let bin = match binary_path {
+        Some(p) => crate::installer::resolve_binary_path(&p).map_err(|e| e.to_string())?,
+        None => crate::installer::node_binary_path()
+            .map_err(|e| e.to_string())?
+            .to_string_lossy()
+            .to_string(),
+    };
+    crate::node_caps::probe(&app, std::path::Path::new(&bin))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// The RPC, Prometheus and P2P ports of each running node.
+#[tauri::command]
+pub async fn get_port_assignments() -> Vec<miner::PortAssignments> {
+    miner::port_assignments().await
+}
+
+/// libp2p connection figures of `chain`'s node from its Prometheus exporter: peers by
+/// direction, dial failures, dropped notification streams and whether it looks
+/// unreachable from the internet. None until the first read (every 15 s while running).
+#[tauri::command]
+pub async fn get_peer_metrics(
+    chain: Option<String>,
+) -> Result<Option<crate::peer_metrics::PeerMetrics>, String> {
+    let chain = miner::target_chain(chain)
         .await
         .map_err(|e| e.to_string())?;
+    Ok(crate::peer_metrics::current(&chain))
+}
+
+/// Webhook deliveries still queued (oldest first) and how many were dropped.
+#[tauri::command]
+pub fn get_pending_notifications(app: AppHandle) -> crate::outbox::PendingNotifications {
+    crate::outbox::pending(&app)
+}
 
+/// GUI preferences (`settings.rs`).
+#[tauri::command]
+pub fn get_settings(app: AppHandle) -> crate::settings::SettingsView {
+    crate::settings::get(&app).into()
+}
+
+/// Change some preferences (`patch` holds only the fields to change). Cheap enough to
+/// call on every keystroke: writes are debounced, `settings:changed` carries the diff.
+#[tauri::command]
+pub fn set_settings(
+    app: AppHandle,
+    patch: serde_json::Map<String, serde_json::Value>,
+) -> Result<crate::settings::SettingsView, String> {
+    crate::settings::set(&app, patch)
+        .map(Into::into)
+        .map_err(|e| e.to_string())
+}
+
+/// Set the app PIN that destructive commands ask for (`pin`); replacing an existing
+/// PIN needs it as `current_pin`.
+#[tauri::command]
+pub async fn set_app_pin(
+    app: AppHandle,
+    pin: String,
+    current_pin: Option<String>,
+) -> Result<(), crate::app_pin::GuardedError> {
+    crate::app_pin::set(&app, pin, current_pin).await
+}
+
+/// Remove the app PIN; needs the current one.
+#[tauri::command]
+pub async fn clear_app_pin(
+    app: AppHandle,
+    pin: Option<String>,
+) -> Result<(), crate::app_pin::GuardedError> {
+    crate::app_pin::clear(&app, pin).await
+}
+
+/// One document with what support asks for about `chain`'s node (versions, paths,
+/// endpoints, data dir, status, safe mode, recent restarts, settings), secrets redacted.
+#[tauri::command]
+pub async fn get_node_report(
+    app: AppHandle,
+    chain: Option<String>,
+) -> Result<crate::report::NodeReport, String> {
+    let chain = miner::target_chain(chain)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(crate::report::build(&app, &chain).await)
+}
+
+/// `get_node_report` as Markdown, for "Copy report".
+#[tauri::command]
+pub async fn get_node_report_markdown(
+    app: AppHandle,
+    chain: Option<String>,
+) -> Result<String, String> {
+    get_node_report(app, chain).await.map(|r| r.to_markdown())
+}
+
+/// Last persisted dashboard state (see `snapshot.rs`) for painting before live events
+/// arrive; None if there is none or it can't be read.
+#[tauri::command]
+pub fn get_last_snapshot(app: AppHandle) -> Option<crate::snapshot::Snapshot> {
+    crate::snapshot::load(&app)
+}
+
+/// Install what's missing and load or create the account. Runs as an "install"
+/// operation (`op_id`, see `cancel_operation`); a cancelled install fails with
+/// `SetupError::Cancelled` and leaves no partial downloads. Safe to call again after a
+/// crash: finished steps are kept (`setup_state.rs`) and a cut-off download resumes.
+#[tauri::command]
+pub async fn ensure_miner_and_account(
+    app: AppHandle,
+    include_external: Option<bool>,
+    op_id: Option<String>,
+) -> Result<serde_json::Value, crate::installer::SetupError> {
+    let op = crate::ops::Operation::begin(&app, "install", None, op_id);
+    // only reflect installation in the phase when nothing else is going on
+    let installing = matches!(
+        phase::current().await,
+        MinerPhase::Idle | MinerPhase::Errored { .. }
+    ) && phase::set_phase(&app, MinerPhase::Installing).await;
+    let res =
+        ensure_miner_and_account_inner(&app, include_external.unwrap_or(false), op.token()).await;
+    if installing {
+        let next = match &res {
+            Ok(_) => MinerPhase::Idle,
+            Err(crate::installer::SetupError::Cancelled { .. }) => MinerPhase::Idle,
+            Err(_) => MinerPhase::Errored {
+                kind: "install".into(),
+            },
+        };
+        phase::set_phase(&app, next).await;
+    }
+    op.finish(&res);
+    res
+}
+
+async fn ensure_miner_and_account_inner(
+    app: &AppHandle,
+    include_external: bool,
+    cancel: crate::ops::CancelToken,
+) -> Result<serde_json::Value, crate::installer::SetupError> {
+    use crate::setup_state::SetupStep;
+    // every step is idempotent (installed binaries and an existing account are kept), so
+    // a run after a crash continues where the last one stopped
+    let mut state = crate::setup_state::load(app);
+    let resumed_after = state.resuming_after();
+    if resumed_after.is_none() {
+        state = Default::default();
+    }
+    let bins =
+        crate::installer::ensure_all_binaries(app, include_external, Some(cancel.clone())).await;
+    // the external miner's outcome is reported on its own; it never fails setup
+    let (external_path, external_error) = match &bins.miner {
+        Some(Ok(p)) => {
+            crate::setup_state::complete(app, &mut state, SetupStep::ExternalMinerInstalled, p);
+            (Some(p.to_string_lossy().to_string()), None)
+        }
+        Some(Err(e)) => (
+            None,
+            Some(crate::installer::SetupError::from_error(e).await),
+        ),
+        None => (None, None),
+    };
+    let miner_path = match bins.node {
+        Ok(p) => p,
+        Err(e) => return Err(crate::installer::SetupError::from_error(&e).await),
+    };
+    crate::setup_state::complete(app, &mut state, SetupStep::NodeInstalled, &miner_path);
+    if let Err(e) = cancel.check() {
+        return Err(crate::installer::SetupError::Cancelled {
+            message: e.to_string(),
+        });
+    }
+    let acct_path = crate::account_path::account_json_path(app);
+    let acct = crate::account_cli::ensure_account_json(app, &miner_path, &acct_path)
+        .await
+        .map_err(|e| crate::installer::SetupError::other(e.to_string()))?;
+    crate::setup_state::complete(app, &mut state, SetupStep::AccountReady, &acct_path);
+    crate::setup_state::finish(app, &mut state);
+
+    // the UI never shows the secret phrase (it stays in the account file)
+    let mut account = serde_json::to_value(&acct)
+        .map_err(|e| crate::installer::SetupError::other(e.to_string()))?;
+    crate::redact::value(&mut account);
     Ok(serde_json::json!({
       "minerPath": miner_path.to_string_lossy(),
-      "account": acct,
+      "account": account,
       "accountJsonPath": acct_path.to_string_lossy(),
+      "externalMinerPath": external_path,
+      "externalMinerError": external_error,
+      "resumedAfter": resumed_after,
     }))
 }
 
+/// The user confirmed abandoning an unreadable account file that may hold a key:
+/// set it aside and create a new account.
+#[tauri::command]
+pub async fn confirm_account_reset(
+    app: AppHandle,
+) -> Result<serde_json::Value, crate::installer::SetupError> {
+    let acct_path = crate::account_path::account_json_path(&app);
+    crate::account_cli::confirm_account_reset(&app, &acct_path)
+        .map_err(|e| crate::installer::SetupError::other(e.to_string()))?;
+    ensure_miner_and_account(app, None, None).await
+}
+
+/// The user confirmed they wrote down the account's secret phrase: stops the
+/// `account:not-backed-up` reminders.
+#[tauri::command]
+pub fn mark_account_backed_up(app: AppHandle) -> Result<(), String> {
+    let acct_path = crate::account_path::account_json_path(&app);
+    crate::account_cli::mark_backed_up(&acct_path).map_err(|e| e.to_string())
+}
+
+/// Wipe the chain's database and restart its node; false when there was no config to
+/// restart with (only wiped). Pass `chain` explicitly when nothing was started this session.
+/// With `dry_run` only the manifest of what would be deleted is returned. A real repair
+/// runs as a "repair" operation (`op_id`, see `cancel_operation`).
+#[tauri::command]
+pub async fn repair_miner(
+    app: AppHandle,
+    chain: Option<String>,
+    dry_run: Option<bool>,
+    op_id: Option<String>,
+    pin: Option<String>,
+) -> Result<crate::plan::DryRun<bool>, crate::app_pin::GuardedError> {
+    use crate::app_pin::GuardedError;
+    let chain = miner::target_chain(chain)
+        .await
+        .map_err(GuardedError::other)?;
+    let dry_run = dry_run.unwrap_or(false);
+    if dry_run {
+        return miner::repair_and_restart(app, chain, true, None)
+            .await
+            .map_err(GuardedError::other);
+    }
+    crate::app_pin::check(&app, pin).await?;
+    crate::session_log::command(&chain, "repair_miner", "");
+    let op = crate::ops::Operation::begin(&app, "repair", Some(&chain), op_id);
+    let res = miner::repair_and_restart(app, chain, false, Some(op.token())).await;
+    op.finish(&res);
+    res.map_err(GuardedError::other)
+}
+
+/// Ask a running operation (`operation:started`) to stop at its next chunk or step;
+/// `operation:cancelled` follows once it has cleaned up. False if `id` isn't running.
+#[tauri::command]
+pub fn cancel_operation(id: String) -> bool {
+    crate::ops::cancel(&id)
+}
+
+/// Operations running now, e.g. to offer Cancel again after a reload of the UI.
+#[tauri::command]
+pub fn list_operations() -> Vec<crate::ops::OperationInfo> {
+    crate::ops::list()
+}
+
+/// Restart without `--rpc-external`/`--unsafe-rpc-external`/`--rpc-methods unsafe`.
+#[tauri::command]
+pub async fn restart_with_safe_rpc(app: AppHandle, chain: Option<String>) -> Result<(), String> {
+    let chain = miner::target_chain(chain)
+        .await
+        .map_err(|e| e.to_string())?;
+    crate::session_log::command(&chain, "restart_with_safe_rpc", "");
+    miner::restart_with_safe_rpc(app, chain)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Restart `chain`'s node with safe sync on; adaptive safe mode turns it off again once
+/// imports are healthy.
+#[tauri::command]
+pub async fn enable_safe_mode(app: AppHandle, chain: Option<String>) -> Result<(), String> {
+    let chain = miner::target_chain(chain)
+        .await
+        .map_err(|e| e.to_string())?;
+    crate::session_log::command(&chain, "enable_safe_mode", "");
+    miner::enable_safe_mode(app, chain)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Check `chain`'s database with its node stopped (`db_check::verify`): a pass/warn/fail
+/// report per check with remediations for what failed. Recorded in the audit log.
+#[tauri::command]
+pub async fn verify_chain_data(
+    app: AppHandle,
+    chain: Option<String>,
+) -> Result<crate::db_check::VerifyReport, String> {
+    let chain = miner::target_chain(chain)
+        .await
+        .map_err(|e| e.to_string())?;
+    crate::session_log::command(&chain, "verify_chain_data", "");
+    crate::db_check::verify(&app, &chain)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Compact `chain`'s database (`compact::compact`): over the running node's admin RPC
+/// when it has one, else with the node's `compact-db` subcommand while it is stopped.
+/// Returns the sizes before and after; recorded in the audit log.
+#[tauri::command]
+pub async fn compact_database(
+    app: AppHandle,
+    chain: Option<String>,
+) -> Result<crate::compact::CompactReport, String> {
+    let chain = miner::target_chain(chain)
+        .await
+        .map_err(|e| e.to_string())?;
+    crate::session_log::command(&chain, "compact_database", "");
+    crate::compact::compact(&app, &chain)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Restart with a freshly generated network key (the old one is kept as a backup);
+/// the remedy for `miner:duplicate-identity`. Returns the backup path.
+#[tauri::command]
+pub async fn regenerate_node_key(
+    app: AppHandle,
+    chain: Option<String>,
+    pin: Option<String>,
+) -> Result<String, crate::app_pin::GuardedError> {
+    use crate::app_pin::GuardedError;
+    let chain = miner::target_chain(chain)
+        .await
+        .map_err(GuardedError::other)?;
+    crate::app_pin::check(&app, pin).await?;
+    crate::session_log::command(&chain, "regenerate_node_key", "");
+    miner::regenerate_node_key(app, chain)
+        .await
+        .map(|p| p.to_string_lossy().to_string())
+        .map_err(GuardedError::other)
+}
+
+/// Mine to another rewards address (a new wallet) without losing sync: the node is
+/// restarted with only `--rewards-address` changed. Returns the address in `chain`'s
+/// SS58 prefix; `watch_previous` keeps the old one in `watched_addresses`.
+#[tauri::command]
+pub async fn set_rewards_address(
+    app: AppHandle,
+    address: String,
+    chain: Option<String>,
+    watch_previous: Option<bool>,
+) -> Result<String, String> {
+    let chain = miner::target_chain(chain)
+        .await
+        .map_err(|e| e.to_string())?;
+    crate::session_log::command(&chain, "set_rewards_address", &address);
+    miner::set_rewards_address(app, chain, address, watch_previous.unwrap_or(false))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Ask the chain's local node for health and sync state right now (the status task
+/// polls the same way every few seconds). `reachable: false` when RPC doesn't answer.
 #[tauri::command]
-pub async fn repair_miner(app: AppHandle) -> Result<(), String> {
-    miner::repair_and_restart(app)
+pub async fn get_node_status_now(
+    chain: Option<String>,
+) -> Result<crate::miner::LocalNodeStatus, String> {
+    let chain = miner::target_chain(chain)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(miner::query_local_node_status(&chain).await)
+}
+
+/// Existential deposit, block time and ss58 prefix for `chain`, read from the local
+/// node's (or bootnode's) metadata; `source` says where they came from.
+#[tauri::command]
+pub async fn get_chain_constants(
+    chain: Option<String>,
+) -> Result<crate::chain_constants::ChainConstants, String> {
+    let chain = miner::target_chain(chain)
+        .await
+        .map_err(|e| e.to_string())?;
+    crate::chain_constants::get(&chain)
         .await
         .map_err(|e| e.to_string())
 }
 
+/// Copy a synced node database from another machine (`source_path`: a node base path
+/// or its `chains/<id>` folder) into `chain`'s data folder, restarting the node if it
+/// was running. The network key is left out unless `include_network_key`; an existing
+/// database is only replaced with `overwrite`.
 #[tauri::command]
-pub async fn unlock_miner(app: AppHandle) -> Result<(), String> {
-    miner::unlock_and_restart(app)
+pub async fn import_chain_data(
+    app: AppHandle,
+    source_path: String,
+    chain: Option<String>,
+    overwrite: Option<bool>,
+    include_network_key: Option<bool>,
+    dry_run: Option<bool>,
+    pin: Option<String>,
+) -> Result<crate::plan::DryRun<crate::data_import::ImportResult>, crate::app_pin::GuardedError> {
+    use crate::app_pin::GuardedError;
+    let chain = miner::target_chain(chain)
+        .await
+        .map_err(GuardedError::other)?;
+    let dry_run = dry_run.unwrap_or(false);
+    if !dry_run {
+        // replacing a database or the node key needs the PIN; a plain import doesn't
+        if overwrite.unwrap_or(false) || include_network_key.unwrap_or(false) {
+            crate::app_pin::check(&app, pin).await?;
+        }
+        crate::session_log::command(
+            &chain,
+            "import_chain_data",
+            &format!(
+                "source={source_path} overwrite={overwrite:?} include_network_key={include_network_key:?}"
+            ),
+        );
+    }
+    miner::import_chain_data(
+        app,
+        chain,
+        std::path::PathBuf::from(source_path),
+        overwrite.unwrap_or(false),
+        include_network_key.unwrap_or(false),
+        dry_run,
+    )
+    .await
+    .map_err(GuardedError::other)
+}
+
+/// Remove the chain's leftover DB LOCK and restart its node; false when there was no
+/// config to restart with (only unlocked).
+#[tauri::command]
+pub async fn unlock_miner(app: AppHandle, chain: Option<String>) -> Result<bool, String> {
+    let chain = miner::target_chain(chain)
+        .await
+        .map_err(|e| e.to_string())?;
+    crate::session_log::command(&chain, "unlock_miner", "");
+    miner::unlock_and_restart(app, chain)
         .await
         .map_err(|e| e.to_string())
 }
@@ -180,6 +1385,7 @@ pub async fn set_safe_ranges(app: AppHandle, payload: SafeRangesPayload) -> Resu
     let mut new_map: std::collections::HashMap<String, Vec<(u64, u64)>> =
         std::collections::HashMap::new();
     for (k, ranges) in payload.chains {
+        crate::session_log::command(&k, "set_safe_ranges", &format!("{ranges:?}"));
         new_map.insert(k, ranges.into_iter().map(|p| (p[0], p[1])).collect());
     }
     crate::miner::save_safe_ranges(&app, &new_map).map_err(|e| e.to_string())?;
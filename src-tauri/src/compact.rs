@@ -0,0 +1,282 @@
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+use std::{
+    path::{Path, PathBuf},
+    process::Stdio,
+    sync::atomic::{AtomicBool, Ordering},
+    time::{Duration, Instant},
+};
+use tauri::AppHandle;
+use tokio::{
+    io::{AsyncBufReadExt, AsyncRead, BufReader},
+    process::Command,
+};
+
+use crate::{
+    events, node_caps,
+    restart::{self, RestartOp},
+};
+
+/// Admin RPC some node versions offer to compact a running node's database.
+pub const COMPACT_RPC: &str = "db_compact";
+/// RocksDB rewrites SST files before dropping the old ones; this share of the database
+/// size has to be free on its volume first.
+const OVERHEAD_PCT: u64 = 50;
+/// Never less free space than this, however small the database.
+const MIN_FREE: u64 = 1024 * 1024 * 1024;
+/// The subcommand gets this long before it is killed.
+const SUBCOMMAND_TIMEOUT: Duration = Duration::from_secs(2 * 3600);
+/// The RPC call returns once compaction finishes.
+const RPC_TIMEOUT: Duration = Duration::from_secs(2 * 3600);
+
+// one compaction at a time; the RPC path doesn't hold a restart guard
+static RUNNING: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CompactMethod {
+    /// `quantus-node compact-db` with the node stopped.
+    Subcommand,
+    /// `db_compact` on the running node.
+    Rpc,
+}
+
+/// `compact_database` result.
+#[derive(Debug, Clone, Serialize)]
+pub struct CompactReport {
+    pub chain: String,
+    pub method: CompactMethod,
+    pub db_path: String,
+    pub bytes_before: u64,
+    pub bytes_after: u64,
+    /// `bytes_before - bytes_after` (0 when it grew).
+    pub reclaimed_bytes: u64,
+    pub duration_secs: u64,
+    pub ts: i64,
+}
+
+struct Running;
+
+impl Running {
+    fn take() -> Option<Running> {
+        (!RUNNING.swap(true, Ordering::AcqRel)).then_some(Running)
+    }
+}
+
+impl Drop for Running {
+    fn drop(&mut self) {
+        RUNNING.store(false, Ordering::Release);
+    }
+}
+
+async fn db_bytes(db: &Path) -> u64 {
+    let db = db.to_path_buf();
+    tokio::task::spawn_blocking(move || crate::repair::measure(&db).1)
+        .await
+        .unwrap_or(0)
+}
+
+// Refuse before starting when the volume can't take the temporary copies.
+fn check_space(db: &Path, bytes: u64) -> Result<()> {
+    let needed = (bytes * OVERHEAD_PCT / 100).max(MIN_FREE);
+    match crate::installer::free_space(db) {
+        Some(free) if free < needed => Err(anyhow!(
+            "compaction needs {} MiB free next to the database, only {} MiB available",
+            needed / (1024 * 1024),
+            free / (1024 * 1024)
+        )),
+        _ => Ok(()),
+    }
+}
+
+async fn rpc_supported(url: &str) -> bool {
+    let body = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "rpc_methods",
+        "params": []
+    });
+    let Ok(resp) = crate::net::client().post(url).json(&body).send().await else {
+        return false;
+    };
+    let Ok(val) = resp.json::<serde_json::Value>().await else {
+        return false;
+    };
+    val.pointer("/result/methods")
+        .and_then(|m| m.as_array())
+        .is_some_and(|m| m.iter().any(|m| m.as_str() == Some(COMPACT_RPC)))
+}
+
+async fn compact_rpc(url: &str) -> Result<()> {
+    let body = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": COMPACT_RPC,
+        "params": []
+    });
+    let val: serde_json::Value = crate::net::client()
+        .post(url)
+        .json(&body)
+        .timeout(RPC_TIMEOUT)
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    match val.get("error") {
+        Some(e) => Err(anyhow!("{COMPACT_RPC}: {e}")),
+        None => Ok(()),
+    }
+}
+
+// Node output goes where its log lines always do: `miner:log` and the session file.
+async fn pipe<R: AsyncRead + Unpin>(
+    app: &AppHandle,
+    chain: &str,
+    source: &'static str,
+    out: Option<R>,
+) {
+    let Some(out) = out else {
+        return;
+    };
+    let mut lines = BufReader::new(out).lines();
+    while let Ok(Some(line)) = lines.next_line().await {
+        crate::session_log::record(chain, &line);
+        events::emit_log(app, source, line);
+    }
+}
+
+async fn compact_subcommand(app: &AppHandle, chain: &str, bin: &Path, base: &Path) -> Result<()> {
+    let resolved = crate::miner::resolved_chain_for(chain).await?;
+    let mut child = Command::new(bin)
+        .arg(node_caps::COMPACT_DB)
+        .arg("--chain")
+        .arg(&resolved.cli_arg)
+        .arg("--base-path")
+        .arg(base)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()?;
+    let (out, err) = (child.stdout.take(), child.stderr.take());
+    let run = async {
+        tokio::join!(
+            pipe(app, chain, "stdout", out),
+            pipe(app, chain, "stderr", err),
+            child.wait()
+        )
+        .2
+    };
+    // on timeout the child is dropped, which kills it
+    let status = tokio::time::timeout(SUBCOMMAND_TIMEOUT, run)
+        .await
+        .map_err(|_| {
+            anyhow!(
+                "`{}` didn't finish within {} min",
+                node_caps::COMPACT_DB,
+                SUBCOMMAND_TIMEOUT.as_secs() / 60
+            )
+        })??;
+    if !status.success() {
+        return Err(anyhow!("`{}` failed ({status})", node_caps::COMPACT_DB));
+    }
+    Ok(())
+}
+
+/// Compact `chain`'s database: with `db_compact` on the running node when it offers it,
+/// else with `quantus-node compact-db` while the node is stopped. Refuses without
+/// `OVERHEAD_PCT` of the database size free on its volume. Node output goes to
+/// `miner:log`; the run and the space reclaimed are recorded in the audit log.
+pub async fn compact(app: &AppHandle, chain: &str) -> Result<CompactReport> {
+    let _running = Running::take().ok_or_else(|| anyhow!("a compaction is already running"))?;
+    let chain_dir = crate::miner::chain_dir_for(chain).await?;
+    let db: PathBuf = chain_dir.join("db").join("full");
+    if !db.is_dir() {
+        return Err(anyhow!("no database at {}", db.display()));
+    }
+    let alive = crate::miner::is_chain_alive(chain).await;
+    let url = crate::miner::local_http_url(chain).await;
+    let method = if alive && rpc_supported(&url).await {
+        CompactMethod::Rpc
+    } else if alive {
+        return Err(anyhow!(
+            "this node can't compact while running (no {COMPACT_RPC} RPC); stop it first"
+        ));
+    } else {
+        CompactMethod::Subcommand
+    };
+
+    let bytes_before = db_bytes(&db).await;
+    check_space(&db, bytes_before)?;
+    let started = Instant::now();
+    crate::session_log::ui(
+        app,
+        chain,
+        format!(
+            "Compacting the database ({} MiB)",
+            bytes_before / (1024 * 1024)
+        ),
+    );
+    match method {
+        CompactMethod::Rpc => compact_rpc(&url).await?,
+        CompactMethod::Subcommand => {
+            // no start while the node's own tool has the database open
+            let _guard = restart::begin(RestartOp::Compact, chain)
+                .await?
+                .ok_or_else(|| anyhow!("a compaction is already running"))?;
+            if crate::miner::is_chain_alive(chain).await {
+                return Err(anyhow!("the {chain} node was started; stop it to compact"));
+            }
+            let bin = match crate::miner::last_cfg(chain).await {
+                Some(c) => PathBuf::from(crate::installer::resolve_binary_path(&c.binary_path)?),
+                None => crate::installer::node_binary_path()?,
+            };
+            let caps = node_caps::probe(app, &bin).await?;
+            if !caps.has_subcommand(node_caps::COMPACT_DB) {
+                return Err(anyhow!(
+                    "this quantus-node has no `{}` subcommand",
+                    node_caps::COMPACT_DB
+                ));
+            }
+            // {base}/chains/{chain_id}
+            let base = chain_dir
+                .parent()
+                .and_then(Path::parent)
+                .ok_or_else(|| anyhow!("invalid chain directory {}", chain_dir.display()))?;
+            compact_subcommand(app, chain, &bin, base).await?;
+        }
+    }
+    let bytes_after = db_bytes(&db).await;
+    let reclaimed_bytes = bytes_before.saturating_sub(bytes_after);
+    let report = CompactReport {
+        chain: chain.to_string(),
+        method,
+        db_path: db.display().to_string(),
+        bytes_before,
+        bytes_after,
+        reclaimed_bytes,
+        duration_secs: started.elapsed().as_secs(),
+        ts: crate::timeutil::now_ms(),
+    };
+    crate::session_log::ui(
+        app,
+        chain,
+        format!(
+            "Database compacted in {} s: {} MiB -> {} MiB",
+            report.duration_secs,
+            bytes_before / (1024 * 1024),
+            bytes_after / (1024 * 1024)
+        ),
+    );
+    crate::session_log::audit(
+        chain,
+        "database-compacted",
+        &format!(
+            "{:?}: {bytes_before} -> {bytes_after} bytes, {reclaimed_bytes} reclaimed",
+            method
+        )
+        .to_lowercase(),
+    );
+    Ok(report)
+}
@@ -0,0 +1,313 @@
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+use tauri::AppHandle;
+
+use crate::{
+    chains::ResolvedChain,
+    events,
+    plan::{Manifest, PlannedChange},
+};
+
+/// How often `miner:import-progress` is sent while copying.
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(500);
+
+/// `miner:import-progress` payload.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportProgress {
+    pub chain: String,
+    /// "scanning" | "copying" | "done"
+    pub stage: &'static str,
+    pub total_files: u64,
+    pub total_bytes: u64,
+    pub copied_files: u64,
+    pub copied_bytes: u64,
+}
+
+/// What `import_chain_data` did.
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportResult {
+    /// `{source}/chains/{chain_id}` (or the source itself when it is a chain directory).
+    pub source: String,
+    /// The `db/full` now in use.
+    pub destination: String,
+    pub files: u64,
+    pub bytes: u64,
+    /// Whether `network/secret_dilithium` was copied too.
+    pub network_key_imported: bool,
+    /// Whether the node was running and has been started again.
+    pub restarted: bool,
+}
+
+/// What an import will do, validated by `plan` before anything is touched.
+pub struct ImportPlan {
+    /// `{source}/chains/{chain_id}` (or the source itself).
+    pub src_dir: PathBuf,
+    /// The chain's `db/full`.
+    pub dest: PathBuf,
+    /// (source key, our key path) when the network key is to be copied and the source has one.
+    pub key: Option<(PathBuf, PathBuf)>,
+}
+
+/// The chain directory inside `source` for `chain_id`: `source` may be a node base path
+/// (holding `chains/<id>`) or a chain directory itself. Data for another chain is refused.
+fn source_chain_dir(source: &Path, chain_id: &str) -> Result<PathBuf> {
+    if !source.is_dir() {
+        return Err(anyhow!("{} is not a directory", source.display()));
+    }
+    let chains_dir = source.join("chains");
+    let dir = if chains_dir.is_dir() {
+        let dir = chains_dir.join(chain_id);
+        if !dir.is_dir() {
+            let found: Vec<String> = fs::read_dir(&chains_dir)
+                .into_iter()
+                .flatten()
+                .flatten()
+                .filter(|e| e.path().is_dir())
+                .map(|e| e.file_name().to_string_lossy().to_string())
+                .collect();
+            return Err(anyhow!(
+                "{} has no data for chain '{chain_id}' (found: {})",
+                source.display(),
+                if found.is_empty() {
+                    "nothing".to_string()
+                } else {
+                    found.join(", ")
+                }
+            ));
+        }
+        dir
+    } else {
+        let name = source
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        if name != chain_id {
+            return Err(anyhow!(
+                "{} is not a node data folder for chain '{chain_id}' (expected a folder with chains/{chain_id}, or chains/{chain_id} itself)",
+                source.display()
+            ));
+        }
+        source.to_path_buf()
+    };
+    let db = dir.join("db").join("full");
+    // RocksDB always keeps a CURRENT file pointing at its manifest
+    if !db.join("CURRENT").is_file() {
+        return Err(anyhow!(
+            "{} doesn't look like a node database (no db/full/CURRENT)",
+            dir.display()
+        ));
+    }
+    Ok(dir)
+}
+
+fn is_non_empty_dir(p: &Path) -> bool {
+    fs::read_dir(p).is_ok_and(|mut d| d.next().is_some())
+}
+
+// Copy every file under `from` into `to` (counting as we go).
+fn copy_counted(
+    from: &Path,
+    to: &Path,
+    files: &AtomicU64,
+    bytes: &AtomicU64,
+) -> std::io::Result<()> {
+    fs::create_dir_all(to)?;
+    for e in fs::read_dir(from)? {
+        let e = e?;
+        let meta = e.metadata()?;
+        let target = to.join(e.file_name());
+        if meta.is_dir() {
+            copy_counted(&e.path(), &target, files, bytes)?;
+        } else {
+            let n = fs::copy(e.path(), &target)?;
+            files.fetch_add(1, Ordering::Relaxed);
+            bytes.fetch_add(n, Ordering::Relaxed);
+        }
+    }
+    Ok(())
+}
+
+// Copy `from` to `to` on a blocking thread with `miner:import-progress` samples.
+async fn copy_with_progress(
+    app: &AppHandle,
+    chain: &str,
+    from: &Path,
+    to: &Path,
+) -> Result<(u64, u64)> {
+    let mut progress = ImportProgress {
+        chain: chain.to_string(),
+        stage: "scanning",
+        total_files: 0,
+        total_bytes: 0,
+        copied_files: 0,
+        copied_bytes: 0,
+    };
+    events::emit(app, events::MINER_IMPORT_PROGRESS, &progress);
+    let dir = from.to_path_buf();
+    let (total_files, total_bytes) =
+        tokio::task::spawn_blocking(move || crate::repair::measure(&dir)).await?;
+    progress.stage = "copying";
+    progress.total_files = total_files;
+    progress.total_bytes = total_bytes;
+    events::emit(app, events::MINER_IMPORT_PROGRESS, &progress);
+
+    let files = Arc::new(AtomicU64::new(0));
+    let bytes = Arc::new(AtomicU64::new(0));
+    let finished = Arc::new(AtomicBool::new(false));
+    let sampler = {
+        let (app, files, bytes, finished) =
+            (app.clone(), files.clone(), bytes.clone(), finished.clone());
+        let mut p = progress.clone();
+        tauri::async_runtime::spawn(async move {
+            while !finished.load(Ordering::Relaxed) {
+                tokio::time::sleep(PROGRESS_INTERVAL).await;
+                p.copied_files = files.load(Ordering::Relaxed);
+                p.copied_bytes = bytes.load(Ordering::Relaxed);
+                events::emit(&app, events::MINER_IMPORT_PROGRESS, &p);
+            }
+        })
+    };
+    let (src, dst) = (from.to_path_buf(), to.to_path_buf());
+    let (f, b) = (files.clone(), bytes.clone());
+    let res = tokio::task::spawn_blocking(move || copy_counted(&src, &dst, &f, &b)).await;
+    finished.store(true, Ordering::Relaxed);
+    let _ = sampler.await;
+    res?.map_err(|e| anyhow!("failed to copy {} to {}: {e}", from.display(), to.display()))?;
+    progress.stage = "done";
+    progress.copied_files = files.load(Ordering::Relaxed);
+    progress.copied_bytes = bytes.load(Ordering::Relaxed);
+    events::emit(app, events::MINER_IMPORT_PROGRESS, &progress);
+    Ok((progress.copied_files, progress.copied_bytes))
+}
+
+/// Copy `db/full` into the plan's destination: into `db/full.importing` first, then
+/// swapped in (the old database, if any, is deleted afterwards). The network key is only
+/// copied when planned; an existing key is kept as `secret_dilithium.bak-<ts>`.
+/// The node must already be stopped.
+pub async fn import_db(app: &AppHandle, chain: &str, plan: &ImportPlan) -> Result<(u64, u64)> {
+    let dest = &plan.dest;
+    let staging = dest.with_file_name("full.importing");
+    let replaced = dest.with_file_name("full.replaced");
+    for leftover in [&staging, &replaced] {
+        if leftover.exists() {
+            fs::remove_dir_all(leftover)?;
+        }
+    }
+    let (files, bytes) =
+        match copy_with_progress(app, chain, &plan.src_dir.join("db").join("full"), &staging).await
+        {
+            Ok(n) => n,
+            Err(e) => {
+                let _ = fs::remove_dir_all(&staging);
+                return Err(e);
+            }
+        };
+    if dest.exists() {
+        fs::rename(dest, &replaced)?;
+    }
+    fs::rename(&staging, dest)?;
+    // the old database is gone either way; an interrupted Repair must not wipe the new one
+    crate::repair::clear_pending(dest)?;
+    if replaced.exists() {
+        tokio::task::spawn_blocking(move || fs::remove_dir_all(replaced)).await??;
+    }
+
+    if let Some((src_key, key_path)) = &plan.key {
+        if key_path.exists() {
+            let backup = key_path.with_file_name(format!(
+                "secret_dilithium.bak-{}",
+                crate::timeutil::now_secs()
+            ));
+            fs::rename(key_path, &backup)?;
+        }
+        if let Some(parent) = key_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::copy(src_key, key_path)?;
+    }
+    Ok((files, bytes))
+}
+
+/// Validate `source_path` as node data for `chain` and check the destination, without
+/// touching anything. A non-empty destination database needs `overwrite`.
+pub fn plan(
+    source_path: &Path,
+    resolved: &ResolvedChain,
+    overwrite: bool,
+    include_network_key: bool,
+) -> Result<ImportPlan> {
+    let src_dir = source_chain_dir(source_path, &resolved.chain_id)?;
+    let dest = resolved.db_path();
+    let same = match (src_dir.canonicalize(), resolved.chain_dir().canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    };
+    if same {
+        return Err(anyhow!(
+            "{} is already this node's data folder",
+            src_dir.display()
+        ));
+    }
+    if is_non_empty_dir(&dest) && !overwrite {
+        return Err(anyhow!(
+            "{} already holds a database; import with overwrite to replace it",
+            dest.display()
+        ));
+    }
+    let src_key = src_dir.join("network").join("secret_dilithium");
+    let key = if include_network_key && src_key.is_file() {
+        Some((src_key, resolved.node_key_path()))
+    } else {
+        None
+    };
+    Ok(ImportPlan { src_dir, dest, key })
+}
+
+impl ImportPlan {
+    /// The manifest for `dry_run`.
+    pub async fn manifest(&self, chain: &str, restarts_node: bool) -> Manifest {
+        let mut changes: Vec<PlannedChange> = Vec::new();
+        changes.extend(crate::plan::change("delete", &self.dest, None).await);
+        changes.extend(
+            crate::plan::change(
+                "copy",
+                &self.src_dir.join("db").join("full"),
+                Some(&self.dest),
+            )
+            .await,
+        );
+        let mut notes = Vec::new();
+        if let Some((src_key, key_path)) = &self.key {
+            changes.extend(
+                crate::plan::change(
+                    "move",
+                    key_path,
+                    Some(&key_path.with_file_name("secret_dilithium.bak-<unix secs>")),
+                )
+                .await,
+            );
+            changes.extend(crate::plan::change("copy", src_key, Some(key_path)).await);
+            notes.push(
+                "The copied network key gives this node the source node's identity; don't run both."
+                    .into(),
+            );
+        }
+        Manifest {
+            operation: "import_chain_data",
+            chain: Some(chain.to_string()),
+            changes,
+            restarts_node,
+            notes,
+        }
+    }
+}
@@ -0,0 +1,303 @@
+use anyhow::{anyhow, Result};
+use lazy_static::lazy_static;
+use serde::Serialize;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+use tauri::AppHandle;
+
+use crate::events;
+
+/// Bundle identifiers earlier releases used; each names a directory next to the current
+/// `app_data_dir`. Newest first.
+const LEGACY_IDENTIFIERS: &[&str] = &[
+    "com.quantus.miner-gui",
+    "com.quantus.miner-tauri-gui",
+    "com.quantus.app",
+    "com.tauri.dev",
+];
+const ACCOUNT_FILE: &str = "mining-rewards-account.json";
+/// Copied along with the account, from the same directory, when missing here.
+const SETTINGS_FILES: &[&str] = &["settings.json"];
+
+/// `app:migrated-data` payload: files copied from an older app data directory at
+/// startup. The originals are left where they were.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Migrated {
+    pub from: String,
+    pub to: String,
+    pub files: Vec<String>,
+    pub ts: i64,
+}
+
+lazy_static! {
+    // Sent once the frontend listens (`frontend_ready`).
+    static ref PENDING: std::sync::Mutex<Option<Migrated>> = std::sync::Mutex::new(None);
+}
+
+// A legacy directory with a readable account file; the newest one wins.
+fn find_legacy(current: &Path) -> Option<PathBuf> {
+    let parent = current.parent()?;
+    LEGACY_IDENTIFIERS
+        .iter()
+        .map(|id| parent.join(id))
+        .filter(|dir| dir != current)
+        .filter(|dir| crate::account_cli::is_readable_account(&dir.join(ACCOUNT_FILE)))
+        .max_by_key(|dir| {
+            fs::metadata(dir.join(ACCOUNT_FILE))
+                .and_then(|m| m.modified())
+                .unwrap_or(SystemTime::UNIX_EPOCH)
+        })
+}
+
+// Copy via `write_atomic`, keeping the original's permissions (the account may hold a
+// key). JSON only: a broken file stays behind.
+fn copy_file(src: &Path, dst: &Path) -> Result<()> {
+    let bytes = fs::read(src)?;
+    serde_json::from_slice::<serde_json::Value>(&bytes)
+        .map_err(|e| anyhow!("{} isn't JSON: {e}", src.display()))?;
+    crate::persist::write_atomic(dst, &bytes)?;
+    if let Ok(meta) = fs::metadata(src) {
+        let _ = fs::set_permissions(dst, meta.permissions());
+    }
+    Ok(())
+}
+
+/// A migration found by `plan`: copy `files` from `legacy` to `current`.
+#[derive(Debug)]
+pub struct MigrationPlan {
+    legacy: PathBuf,
+    current: PathBuf,
+    /// The account first, then the settings missing here.
+    files: Vec<&'static str>,
+}
+
+/// The migration `current` needs, without touching anything: None when it has an
+/// account file (even an unreadable one) or no older directory has a readable one.
+fn plan(current: &Path) -> Option<MigrationPlan> {
+    if current.join(ACCOUNT_FILE).exists() {
+        return None;
+    }
+    let legacy = find_legacy(current)?;
+    let mut files = vec![ACCOUNT_FILE];
+    files.extend(
+        SETTINGS_FILES
+            .iter()
+            .filter(|name| legacy.join(name).is_file() && !current.join(name).exists()),
+    );
+    Some(MigrationPlan {
+        legacy,
+        current: current.to_path_buf(),
+        files,
+    })
+}
+
+/// Copy what `plan` found. Fails only when the account can't be copied; a settings
+/// file that can't be is left out of `Migrated::files`.
+fn apply(plan: MigrationPlan) -> Result<Migrated> {
+    fs::create_dir_all(&plan.current)?;
+    let mut files = Vec::new();
+    for name in plan.files {
+        let (src, dst) = (plan.legacy.join(name), plan.current.join(name));
+        match copy_file(&src, &dst) {
+            Ok(()) => files.push(name.to_string()),
+            Err(e) if name == ACCOUNT_FILE => return Err(anyhow!("{name}: {e}")),
+            Err(e) => eprintln!("data migration {name}: {e}"),
+        }
+    }
+    Ok(Migrated {
+        from: plan.legacy.display().to_string(),
+        to: plan.current.display().to_string(),
+        files,
+        ts: crate::timeutil::now_ms(),
+    })
+}
+
+/// At startup, before anything reads the app data directory: when it has no account
+/// file but a directory of an older bundle identifier has a readable one, copy that
+/// account and the settings missing here. Never replaces a file that exists (an account
+/// here, even an unreadable one, stops the migration).
+pub fn run(app: &AppHandle) {
+    let Some(plan) = plan(&crate::paths::app_data_dir(app)) else {
+        return;
+    };
+    match apply(plan) {
+        Ok(migrated) => {
+            if let Ok(mut pending) = PENDING.lock() {
+                *pending = Some(migrated);
+            }
+        }
+        Err(e) => eprintln!("data migration: {e}"),
+    }
+}
+
+/// Record a migration `run` made in the audit log (once the logs directory is known).
+pub fn audit() {
+    let Ok(pending) = PENDING.lock() else {
+        return;
+    };
+    if let Some(m) = pending.as_ref() {
+        crate::session_log::audit(
+            "-",
+            "app-data-migrated",
+            &format!("{} from {} to {}", m.files.join(","), m.from, m.to),
+        );
+    }
+}
+
+/// Send `app:migrated-data` for a migration made at startup (called by `frontend_ready`).
+pub fn notify(app: &AppHandle) {
+    let migrated = PENDING.lock().ok().and_then(|mut p| p.take());
+    if let Some(m) = migrated {
+        events::emit(app, events::APP_MIGRATED_DATA, &m);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ACCOUNT: &str = r#"{"address":"qzpFUhjXpZnRCbNr9LW5sAkWmTus52Ffcf31MBpig82u1JArX"}"#;
+
+    // `<tmp>/<id>`, the way app data directories sit side by side.
+    fn dir(root: &Path, id: &str) -> PathBuf {
+        let d = root.join(id);
+        fs::create_dir_all(&d).unwrap();
+        d
+    }
+
+    fn current(root: &Path) -> PathBuf {
+        root.join("com.quantus.miner")
+    }
+
+    #[test]
+    fn nothing_to_migrate() {
+        let tmp = tempfile::tempdir().unwrap();
+        assert!(plan(&current(tmp.path())).is_none());
+
+        // a legacy directory without a usable account
+        let legacy = dir(tmp.path(), "com.quantus.app");
+        fs::write(legacy.join(ACCOUNT_FILE), "{\"address\":").unwrap();
+        fs::write(legacy.join("settings.json"), "{}").unwrap();
+        assert!(plan(&current(tmp.path())).is_none());
+    }
+
+    #[test]
+    fn an_account_here_stops_it() {
+        let tmp = tempfile::tempdir().unwrap();
+        let legacy = dir(tmp.path(), "com.quantus.app");
+        fs::write(legacy.join(ACCOUNT_FILE), ACCOUNT).unwrap();
+        let here = dir(tmp.path(), "com.quantus.miner");
+        fs::write(here.join(ACCOUNT_FILE), "not json").unwrap();
+        assert!(plan(&here).is_none());
+    }
+
+    #[test]
+    fn the_newest_legacy_account_wins() {
+        let tmp = tempfile::tempdir().unwrap();
+        let set_age = |path: &Path, secs_ago: u64| {
+            let t = SystemTime::now() - std::time::Duration::from_secs(secs_ago);
+            fs::File::options()
+                .write(true)
+                .open(path)
+                .unwrap()
+                .set_modified(t)
+                .unwrap();
+        };
+        for (id, age) in [("com.quantus.app", 10), ("com.quantus.miner-gui", 3600)] {
+            let account = dir(tmp.path(), id).join(ACCOUNT_FILE);
+            fs::write(&account, ACCOUNT).unwrap();
+            set_age(&account, age);
+        }
+        let p = plan(&current(tmp.path())).unwrap();
+        assert_eq!(p.legacy, tmp.path().join("com.quantus.app"));
+    }
+
+    #[test]
+    fn plan_then_apply() {
+        let tmp = tempfile::tempdir().unwrap();
+        let legacy = dir(tmp.path(), "com.quantus.miner-tauri-gui");
+        fs::write(legacy.join(ACCOUNT_FILE), ACCOUNT).unwrap();
+        fs::write(legacy.join("settings.json"), r#"{"log_to_file":true}"#).unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(legacy.join(ACCOUNT_FILE), fs::Permissions::from_mode(0o600))
+                .unwrap();
+        }
+        let here = current(tmp.path());
+
+        let p = plan(&here).unwrap();
+        assert_eq!(p.files, [ACCOUNT_FILE, "settings.json"]);
+        // planning touched nothing
+        assert!(!here.exists());
+
+        let m = apply(p).unwrap();
+        assert_eq!(m.files, [ACCOUNT_FILE, "settings.json"]);
+        assert_eq!(m.from, legacy.display().to_string());
+        assert_eq!(
+            fs::read_to_string(here.join(ACCOUNT_FILE)).unwrap(),
+            ACCOUNT
+        );
+        assert!(crate::account_cli::is_readable_account(
+            &here.join(ACCOUNT_FILE)
+        ));
+        // the originals stay
+        assert!(legacy.join(ACCOUNT_FILE).exists());
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(here.join(ACCOUNT_FILE))
+                .unwrap()
+                .permissions()
+                .mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+        // done once
+        assert!(plan(&here).is_none());
+    }
+
+    #[test]
+    fn settings_here_are_kept() {
+        let tmp = tempfile::tempdir().unwrap();
+        let legacy = dir(tmp.path(), "com.tauri.dev");
+        fs::write(legacy.join(ACCOUNT_FILE), ACCOUNT).unwrap();
+        fs::write(legacy.join("settings.json"), r#"{"old":true}"#).unwrap();
+        let here = dir(tmp.path(), "com.quantus.miner");
+        fs::write(here.join("settings.json"), r#"{"new":true}"#).unwrap();
+
+        let m = apply(plan(&here).unwrap()).unwrap();
+        assert_eq!(m.files, [ACCOUNT_FILE]);
+        assert_eq!(
+            fs::read_to_string(here.join("settings.json")).unwrap(),
+            r#"{"new":true}"#
+        );
+    }
+
+    #[test]
+    fn broken_settings_are_left_behind() {
+        let tmp = tempfile::tempdir().unwrap();
+        let legacy = dir(tmp.path(), "com.quantus.app");
+        fs::write(legacy.join(ACCOUNT_FILE), ACCOUNT).unwrap();
+        fs::write(legacy.join("settings.json"), "{truncated").unwrap();
+        let here = current(tmp.path());
+
+        let m = apply(plan(&here).unwrap()).unwrap();
+        assert_eq!(m.files, [ACCOUNT_FILE]);
+        assert!(!here.join("settings.json").exists());
+    }
+
+    #[test]
+    fn apply_fails_when_the_account_went_away() {
+        let tmp = tempfile::tempdir().unwrap();
+        let legacy = dir(tmp.path(), "com.quantus.app");
+        fs::write(legacy.join(ACCOUNT_FILE), ACCOUNT).unwrap();
+        let p = plan(&current(tmp.path())).unwrap();
+        fs::remove_file(legacy.join(ACCOUNT_FILE)).unwrap();
+        assert!(apply(p).is_err());
+        assert!(!current(tmp.path()).join(ACCOUNT_FILE).exists());
+    }
+}
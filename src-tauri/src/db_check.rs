@@ -0,0 +1,382 @@
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Stdio,
+    time::Duration,
+};
+use tauri::AppHandle;
+use tokio::process::Command;
+
+use crate::{
+    node_caps,
+    remediation::{ErrorKind, RemediationAction},
+    restart::{self, RestartOp},
+};
+
+/// Node session files searched for corruption reports, newest first.
+const SESSION_FILES: usize = 5;
+/// `chain-info` opens the whole database; a big one can take a while.
+const CHAIN_INFO_TIMEOUT: Duration = Duration::from_secs(120);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    /// Not applicable or not possible here (`detail` says why).
+    Skipped,
+    Pass,
+    Warn,
+    Fail,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Check {
+    /// "db_dir" | "repair_pending" | "lock" | "rocksdb_files" | "session_logs" | "node_check"
+    pub name: &'static str,
+    pub status: CheckStatus,
+    pub detail: String,
+    /// What a failure or warning points to; its remediations are in the report.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_kind: Option<ErrorKind>,
+}
+
+impl Check {
+    fn new(name: &'static str, status: CheckStatus, detail: impl Into<String>) -> Self {
+        Check {
+            name,
+            status,
+            detail: detail.into(),
+            error_kind: None,
+        }
+    }
+
+    fn kind(mut self, kind: ErrorKind) -> Self {
+        self.error_kind = Some(kind);
+        self
+    }
+}
+
+/// `verify_chain_data` result.
+#[derive(Debug, Clone, Serialize)]
+pub struct VerifyReport {
+    pub chain: String,
+    pub db_path: String,
+    /// The worst status of `checks`.
+    pub status: CheckStatus,
+    pub checks: Vec<Check>,
+    /// For the problems found, most fitting first (see `remediation::actions`).
+    pub remediations: Vec<RemediationAction>,
+    pub ts: i64,
+}
+
+fn db_dir(db: &Path) -> Check {
+    match fs::metadata(db) {
+        Ok(m) if m.is_dir() => Check::new("db_dir", CheckStatus::Pass, db.display().to_string()),
+        Ok(_) => Check::new(
+            "db_dir",
+            CheckStatus::Fail,
+            format!("{} is not a directory", db.display()),
+        )
+        .kind(ErrorKind::DbCorrupt),
+        Err(_) => Check::new(
+            "db_dir",
+            CheckStatus::Warn,
+            format!(
+                "no database at {}; the node will sync from scratch",
+                db.display()
+            ),
+        ),
+    }
+}
+
+fn repair_pending(db: &Path) -> Check {
+    if crate::repair::is_pending(db) {
+        Check::new(
+            "repair_pending",
+            CheckStatus::Fail,
+            "an interrupted Repair left the database half deleted; the next start finishes it",
+        )
+        .kind(ErrorKind::DbCorrupt)
+    } else {
+        Check::new("repair_pending", CheckStatus::Pass, "no interrupted repair")
+    }
+}
+
+// RocksDB keeps its LOCK file after a clean exit too; it only matters while a process
+// holds it, which with our node stopped means one from a previous session.
+fn lock(chain: &str, lock_path: &Path) -> Check {
+    if let Some(o) = crate::pidfile::orphans().iter().find(|o| o.chain == chain) {
+        return Check::new(
+            "lock",
+            CheckStatus::Fail,
+            format!(
+                "a node from a previous session (pid {}) still has the database open",
+                o.pid
+            ),
+        )
+        .kind(ErrorKind::DbLocked);
+    }
+    let detail = if lock_path.exists() {
+        "LOCK is not held by a running node"
+    } else {
+        "no LOCK file"
+    };
+    Check::new("lock", CheckStatus::Pass, detail)
+}
+
+fn non_empty(p: &Path) -> bool {
+    fs::metadata(p).is_ok_and(|m| m.is_file() && m.len() > 0)
+}
+
+fn rocksdb_files(db: &Path) -> Check {
+    let current = db.join("CURRENT");
+    if !current.exists() {
+        // ParityDB (`--database paritydb`) has no CURRENT/MANIFEST
+        if db.join("metadata").exists() {
+            return Check::new(
+                "rocksdb_files",
+                CheckStatus::Skipped,
+                "not a RocksDB database (ParityDB)",
+            );
+        }
+        return Check::new(
+            "rocksdb_files",
+            CheckStatus::Fail,
+            format!("no CURRENT file in {}", db.display()),
+        )
+        .kind(ErrorKind::DbCorrupt);
+    }
+    // CURRENT names the live manifest: "MANIFEST-000123\n"
+    let manifest = fs::read_to_string(&current)
+        .ok()
+        .map(|s| s.trim().to_string())
+        .filter(|s| s.starts_with("MANIFEST-"));
+    let Some(manifest) = manifest else {
+        return Check::new(
+            "rocksdb_files",
+            CheckStatus::Fail,
+            "CURRENT is empty or doesn't name a MANIFEST",
+        )
+        .kind(ErrorKind::DbCorrupt);
+    };
+    if !non_empty(&db.join(&manifest)) {
+        return Check::new(
+            "rocksdb_files",
+            CheckStatus::Fail,
+            format!("{manifest}, named in CURRENT, is missing or empty"),
+        )
+        .kind(ErrorKind::DbCorrupt);
+    }
+    Check::new(
+        "rocksdb_files",
+        CheckStatus::Pass,
+        format!("CURRENT -> {manifest}"),
+    )
+}
+
+// A corruption report is resolved once a later session got as far as status lines
+// (the database opened and the node ran).
+fn session_logs(chain: &str) -> Check {
+    let files: Vec<_> = crate::session_log::list_files(Some(chain))
+        .into_iter()
+        .filter(|f| f.kind == "node")
+        .take(SESSION_FILES)
+        .collect();
+    if files.is_empty() {
+        return Check::new("session_logs", CheckStatus::Skipped, "no session logs");
+    }
+    let mut ran_since = false;
+    for f in &files {
+        let Ok(text) = fs::read_to_string(&f.path) else {
+            continue;
+        };
+        let corrupt = text.lines().find(|l| crate::parse::is_db_corruption(l));
+        match corrupt {
+            Some(line) if !ran_since => {
+                return Check::new(
+                    "session_logs",
+                    CheckStatus::Fail,
+                    format!("{}: {}", f.path, line.trim()),
+                )
+                .kind(ErrorKind::DbCorrupt);
+            }
+            Some(_) => {
+                return Check::new(
+                    "session_logs",
+                    CheckStatus::Pass,
+                    format!(
+                        "corruption reported in {}, but the node ran fine after it",
+                        f.path
+                    ),
+                );
+            }
+            None => {}
+        }
+        ran_since |= text
+            .lines()
+            .any(|l| crate::parse::parse_node_status(l).is_some());
+    }
+    Check::new(
+        "session_logs",
+        CheckStatus::Pass,
+        format!(
+            "no corruption reported in the last {} session(s)",
+            files.len()
+        ),
+    )
+}
+
+// `quantus-node chain-info` opens the database the way a start does.
+async fn node_check(
+    app: &AppHandle,
+    bin: &Path,
+    cli_arg: &str,
+    base: &Path,
+    db_present: bool,
+) -> Check {
+    let name = "node_check";
+    if !db_present {
+        return Check::new(name, CheckStatus::Skipped, "no database to open");
+    }
+    let caps = match node_caps::probe(app, bin).await {
+        Ok(c) => c,
+        Err(e) => return Check::new(name, CheckStatus::Skipped, e.to_string()),
+    };
+    if !caps.has_subcommand(node_caps::CHAIN_INFO) {
+        return Check::new(
+            name,
+            CheckStatus::Skipped,
+            format!(
+                "this quantus-node has no `{}` subcommand",
+                node_caps::CHAIN_INFO
+            ),
+        );
+    }
+    let out = Command::new(bin)
+        .arg(node_caps::CHAIN_INFO)
+        .arg("--chain")
+        .arg(cli_arg)
+        .arg("--base-path")
+        .arg(base)
+        .stdin(Stdio::null())
+        .kill_on_drop(true)
+        .output();
+    let out = match tokio::time::timeout(CHAIN_INFO_TIMEOUT, out).await {
+        Ok(Ok(out)) => out,
+        Ok(Err(e)) => return Check::new(name, CheckStatus::Warn, format!("couldn't run: {e}")),
+        Err(_) => {
+            return Check::new(
+                name,
+                CheckStatus::Warn,
+                format!(
+                    "`{}` didn't finish within {} s",
+                    node_caps::CHAIN_INFO,
+                    CHAIN_INFO_TIMEOUT.as_secs()
+                ),
+            )
+        }
+    };
+    let text = String::from_utf8_lossy(&[out.stdout, out.stderr].concat()).into_owned();
+    let lines: Vec<String> = text.lines().map(str::to_string).collect();
+    if out.status.success() {
+        let summary = lines
+            .iter()
+            .rev()
+            .find(|l| !l.trim().is_empty())
+            .map(|l| l.trim().to_string())
+            .unwrap_or_else(|| "database opened".into());
+        return Check::new(name, CheckStatus::Pass, summary);
+    }
+    let failure = crate::parse::classify_startup_failure(&lines);
+    match ErrorKind::from_startup(failure.kind) {
+        Some(kind @ (ErrorKind::DbCorrupt | ErrorKind::DbLocked)) => {
+            Check::new(name, CheckStatus::Fail, failure.reason).kind(kind)
+        }
+        _ => Check::new(name, CheckStatus::Warn, failure.reason),
+    }
+}
+
+/// Check `chain`'s database without starting the node, which must be stopped: directory
+/// layout, an interrupted repair, a LOCK still held, RocksDB's CURRENT/MANIFEST, corruption
+/// reported in recent session logs, and `quantus-node chain-info` when the node has it.
+/// The run is recorded in the audit log.
+pub async fn verify(app: &AppHandle, chain: &str) -> Result<VerifyReport> {
+    if crate::miner::is_chain_alive(chain).await {
+        return Err(anyhow!("stop the {chain} node before verifying its data"));
+    }
+    // no start while the database is being read
+    let _guard = restart::begin(RestartOp::Verify, chain)
+        .await?
+        .ok_or_else(|| anyhow!("a check is already running"))?;
+    let (cfg, resolved) = match crate::miner::last_cfg(chain).await {
+        Some(cfg) => {
+            let resolved = cfg.resolved_chain()?;
+            (Some(cfg), resolved)
+        }
+        None => (None, crate::chains::resolve(chain, None, &[])?),
+    };
+    let chain_dir = crate::miner::chain_dir_for(chain).await?;
+    let db: PathBuf = chain_dir.join("db").join("full");
+    // {base}/chains/{chain_id}
+    let base = chain_dir
+        .parent()
+        .and_then(Path::parent)
+        .ok_or_else(|| anyhow!("invalid chain directory {}", chain_dir.display()))?
+        .to_path_buf();
+    let bin = match &cfg {
+        Some(c) => PathBuf::from(crate::installer::resolve_binary_path(&c.binary_path)?),
+        None => crate::installer::node_binary_path()?,
+    };
+
+    let mut checks = vec![
+        db_dir(&db),
+        repair_pending(&db),
+        lock(chain, &db.join("LOCK")),
+    ];
+    let db_present = db.is_dir();
+    if db_present {
+        checks.push(rocksdb_files(&db));
+    }
+    let name = chain.to_string();
+    checks.push(
+        tokio::task::spawn_blocking(move || session_logs(&name))
+            .await
+            .map_err(|e| anyhow!("{e}"))?,
+    );
+    checks.push(node_check(app, &bin, &resolved.cli_arg, &base, db_present).await);
+
+    let status = checks
+        .iter()
+        .map(|c| c.status)
+        .max()
+        .unwrap_or(CheckStatus::Pass);
+    let mut kinds: Vec<ErrorKind> = Vec::new();
+    for k in checks.iter().filter_map(|c| c.error_kind) {
+        if !kinds.contains(&k) {
+            kinds.push(k);
+        }
+    }
+    let mut remediations = Vec::new();
+    for k in kinds {
+        remediations.extend(crate::remediation::actions(chain, k).await);
+    }
+    let summary = checks
+        .iter()
+        .map(|c| format!("{}={:?}", c.name, c.status).to_lowercase())
+        .collect::<Vec<_>>()
+        .join(" ");
+    crate::session_log::audit(
+        chain,
+        "chain-data-verified",
+        &format!("{:?}: {summary}", status).to_lowercase(),
+    );
+    Ok(VerifyReport {
+        chain: chain.to_string(),
+        db_path: db.display().to_string(),
+        status,
+        checks,
+        remediations,
+        ts: crate::timeutil::now_ms(),
+    })
+}
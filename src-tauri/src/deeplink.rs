@@ -0,0 +1,107 @@
+use anyhow::{anyhow, Result};
+use lazy_static::lazy_static;
+use serde::Serialize;
+use tauri::AppHandle;
+use tokio::sync::Mutex;
+
+use crate::events;
+
+/// Scheme registered for website "Mine to this address" links.
+pub const DEEPLINK_SCHEME: &str = "quantus";
+
+/// A parsed and validated `quantus://` link. The frontend decides what to do with it;
+/// the backend never starts the miner from a link on its own.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeepLinkIntent {
+    pub action: String, // currently only "mine"
+    pub chain: String,
+    pub rewards_address: Option<String>,
+    pub url: String,
+}
+
+#[derive(Default)]
+struct Delivery {
+    // Set once the frontend calls `frontend_ready`; until then intents are queued.
+    frontend_ready: bool,
+    // Intents received before the frontend registered its listeners (cold start).
+    pending: Vec<DeepLinkIntent>,
+}
+
+lazy_static! {
+    // One lock for both, so an intent can't be queued after the queue was flushed.
+    static ref DELIVERY: Mutex<Delivery> = Mutex::new(Delivery::default());
+}
+
+/// Parse `quantus://mine?chain=resonance&rewards=qz…` into an intent.
+/// Chain and address are validated with the same rules the commands use.
+pub fn parse_deeplink(raw: &str) -> Result<DeepLinkIntent> {
+    let url = url::Url::parse(raw.trim()).map_err(|e| anyhow!("invalid link: {e}"))?;
+    if url.scheme() != DEEPLINK_SCHEME {
+        return Err(anyhow!("unsupported scheme: {}", url.scheme()));
+    }
+    // `quantus://mine?...` puts the action in the host; `quantus:mine?...` in the path.
+    let action = url
+        .host_str()
+        .map(|h| h.to_string())
+        .unwrap_or_else(|| url.path().trim_matches('/').to_string())
+        .to_lowercase();
+    if action != "mine" {
+        return Err(anyhow!("unsupported action: {action}"));
+    }
+
+    let mut chain: Option<String> = None;
+    let mut rewards: Option<String> = None;
+    for (k, v) in url.query_pairs() {
+        match k.as_ref() {
+            "chain" => chain = Some(v.trim().to_lowercase()),
+            "rewards" | "rewards_address" | "address" => rewards = Some(v.trim().to_string()),
+            _ => {}
+        }
+    }
+
+    let chain = chain.unwrap_or_else(|| "resonance".to_string());
+    crate::commands::validate_chain(&chain).map_err(|e| anyhow!(e))?;
+    if let Some(addr) = rewards.as_deref() {
+        crate::account_cli::validate_address(addr)?;
+    }
+
+    Ok(DeepLinkIntent {
+        action,
+        chain,
+        rewards_address: rewards,
+        url: raw.trim().to_string(),
+    })
+}
+
+/// Handle one or more incoming links (deep-link plugin, single-instance forward, or argv).
+/// Valid intents are emitted as `app:deeplink`, or queued until the frontend is ready.
+pub async fn handle_urls(app: &AppHandle, urls: Vec<String>) {
+    for raw in urls {
+        if !raw.starts_with(&format!("{DEEPLINK_SCHEME}:")) {
+            continue;
+        }
+        match parse_deeplink(&raw) {
+            Ok(intent) => {
+                let mut delivery = DELIVERY.lock().await;
+                if delivery.frontend_ready {
+                    events::emit(app, events::APP_DEEPLINK, &intent);
+                } else {
+                    delivery.pending.push(intent);
+                }
+            }
+            Err(e) => {
+                events::emit_log(app, "ui", format!("Ignoring deep link {raw}: {e}"));
+            }
+        }
+    }
+}
+
+/// Mark the frontend as ready and flush any intents queued during cold start.
+pub async fn mark_frontend_ready(app: &AppHandle) {
+    let mut delivery = DELIVERY.lock().await;
+    delivery.frontend_ready = true;
+    for intent in delivery.pending.drain(..) {
+        events::emit(app, events::APP_DEEPLINK, &intent);
+    }
+}
@@ -0,0 +1,923 @@
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU8, Ordering};
+use tauri::{AppHandle, Emitter};
+
+use crate::parse::MinerEvent;
+
+// Every event the backend emits. The frontend listens on the same strings (`api.ts`);
+// add new ones here (and to `ALL`) rather than passing literals to `emit`.
+pub const ACCOUNT_CORRUPT: &str = "account:corrupt";
+pub const ACCOUNT_NOT_BACKED_UP: &str = "account:not-backed-up";
+pub const APP_DEEPLINK: &str = "app:deeplink";
+pub const APP_MIGRATED_DATA: &str = "app:migrated-data";
+pub const APP_UPDATE_AVAILABLE: &str = "app:update-available";
+pub const BENCHMARK_PROGRESS: &str = "benchmark:progress";
+pub const INSTALLER_PROGRESS: &str = "installer:progress";
+pub const LOGS_MOVE_PROGRESS: &str = "logs:move-progress";
+pub const MINER_AUTHORED: &str = "miner:authored";
+pub const MINER_BALANCE: &str = "miner:balance";
+pub const MINER_BINARY_MODIFIED: &str = "miner:binary-modified";
+pub const MINER_BLOCK_PROPAGATED: &str = "miner:block-propagated";
+pub const MINER_CHAIN_MISMATCH: &str = "miner:chain-mismatch";
+pub const MINER_DUPLICATE_IDENTITY: &str = "miner:duplicate-identity";
+pub const MINER_EVENT: &str = "miner:event";
+pub const MINER_EXITED: &str = "miner:exited";
+pub const MINER_EXTERNAL_CRASHLOOP: &str = "miner:external-crashloop";
+pub const MINER_EXTERNAL_STATUS: &str = "miner:external-status";
+pub const MINER_FORK_DETECTED: &str = "miner:fork-detected";
+pub const MINER_IMPORT_PRESSURE: &str = "miner:import-pressure";
+pub const MINER_IMPORT_PROGRESS: &str = "miner:import-progress";
+pub const MINER_LOG: &str = "miner:log";
+pub const MINER_LOG_THROTTLED: &str = "miner:log-throttled";
+pub const MINER_LOGFILE: &str = "miner:logfile";
+pub const MINER_META: &str = "miner:meta";
+pub const MINER_NOTABLE: &str = "miner:notable";
+pub const MINER_PARSE_DEBUG: &str = "miner:parse-debug";
+pub const MINER_PHASE: &str = "miner:phase";
+pub const MINER_QUARANTINED: &str = "miner:quarantined";
+pub const MINER_REMEDIATION: &str = "miner:remediation";
+pub const MINER_REPAIR_PROGRESS: &str = "miner:repair-progress";
+pub const MINER_RESTARTING: &str = "miner:restarting";
+pub const MINER_REWARDS_ADDRESS_CHANGED: &str = "miner:rewards-address-changed";
+pub const MINER_RUNTIME_UPGRADED: &str = "miner:runtime-upgraded";
+pub const MINER_SAFE_MODE_COUNTDOWN: &str = "miner:safe-mode-countdown";
+pub const MINER_SAFE_MODE_RECOMMENDATION: &str = "miner:safe-mode-recommendation";
+pub const MINER_SECURITY_WARNING: &str = "miner:security-warning";
+pub const MINER_START_DEGRADED: &str = "miner:start-degraded";
+pub const MINER_START_FAILED: &str = "miner:start-failed";
+pub const MINER_STARTED: &str = "miner:started";
+pub const MINER_STATE: &str = "miner:state";
+pub const MINER_STATUS: &str = "miner:status";
+pub const MINER_SYNC_COMPLETE: &str = "miner:sync-complete";
+pub const MINER_THROTTLE: &str = "miner:throttle";
+pub const MINER_TIMED_STOP: &str = "miner:timed-stop";
+pub const MINER_TRANSFER: &str = "miner:transfer";
+pub const OPERATION_CANCELLED: &str = "operation:cancelled";
+pub const OPERATION_STARTED: &str = "operation:started";
+pub const SETTINGS_CHANGED: &str = "settings:changed";
+pub const SETTINGS_FLUSH_FAILED: &str = "settings:flush-failed";
+pub const SETUP_PROGRESS: &str = "setup:progress";
+
+/// All event names, sorted. `emit` only accepts these (checked in debug builds).
+pub const ALL: &[&str] = &[
+    ACCOUNT_CORRUPT,
+    ACCOUNT_NOT_BACKED_UP,
+    APP_DEEPLINK,
+    APP_MIGRATED_DATA,
+    APP_UPDATE_AVAILABLE,
+    BENCHMARK_PROGRESS,
+    INSTALLER_PROGRESS,
+    LOGS_MOVE_PROGRESS,
+    MINER_AUTHORED,
+    MINER_BALANCE,
+    MINER_BINARY_MODIFIED,
+    MINER_BLOCK_PROPAGATED,
+    MINER_CHAIN_MISMATCH,
+    MINER_DUPLICATE_IDENTITY,
+    MINER_EVENT,
+    MINER_EXITED,
+    MINER_EXTERNAL_CRASHLOOP,
+    MINER_EXTERNAL_STATUS,
+    MINER_FORK_DETECTED,
+    MINER_IMPORT_PRESSURE,
+    MINER_IMPORT_PROGRESS,
+    MINER_LOG,
+    MINER_LOG_THROTTLED,
+    MINER_LOGFILE,
+    MINER_META,
+    MINER_NOTABLE,
+    MINER_PARSE_DEBUG,
+    MINER_PHASE,
+    MINER_QUARANTINED,
+    MINER_REMEDIATION,
+    MINER_REPAIR_PROGRESS,
+    MINER_RESTARTING,
+    MINER_REWARDS_ADDRESS_CHANGED,
+    MINER_RUNTIME_UPGRADED,
+    MINER_SAFE_MODE_COUNTDOWN,
+    MINER_SAFE_MODE_RECOMMENDATION,
+    MINER_SECURITY_WARNING,
+    MINER_START_DEGRADED,
+    MINER_START_FAILED,
+    MINER_STARTED,
+    MINER_STATE,
+    MINER_STATUS,
+    MINER_SYNC_COMPLETE,
+    MINER_THROTTLE,
+    MINER_TIMED_STOP,
+    MINER_TRANSFER,
+    OPERATION_CANCELLED,
+    OPERATION_STARTED,
+    SETTINGS_CHANGED,
+    SETTINGS_FLUSH_FAILED,
+    SETUP_PROGRESS,
+];
+
+/// How much of the event stream reaches the frontend (`set_event_verbosity`). Kept in
+/// memory only: every app start is back to `Normal`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Verbosity {
+    /// Only `MINIMAL` events; log lines still reach the ring buffer and session files.
+    Minimal,
+    Normal,
+    /// Everything, plus `miner:parse-debug` for every node line.
+    Debug,
+}
+
+/// What `Verbosity::Minimal` still sends: enough to draw the dashboard.
+const MINIMAL: [&str; 4] = [MINER_NOTABLE, MINER_PHASE, MINER_STATE, MINER_STATUS];
+
+static VERBOSITY: AtomicU8 = AtomicU8::new(Verbosity::Normal as u8);
+
+pub fn verbosity() -> Verbosity {
+    match VERBOSITY.load(Ordering::Relaxed) {
+        0 => Verbosity::Minimal,
+        2 => Verbosity::Debug,
+        _ => Verbosity::Normal,
+    }
+}
+
+/// Takes effect with the next event; nothing is replayed.
+pub fn set_verbosity(v: Verbosity) {
+    VERBOSITY.store(v as u8, Ordering::Relaxed);
+}
+
+/// `miner:log` payload.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LogMsg {
+    source: &'static str,
+    line: String,
+    /// UTC milliseconds since the epoch.
+    ts: i64,
+}
+
+/// `miner:state` payload: whether `chain`'s node runs, for the Start/Stop buttons.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MinerState<'a> {
+    pub running: bool,
+    /// "starting" | "running" | "stopping" | "stopped" | "stop_failed"
+    pub phase: &'static str,
+    pub chain: &'a str,
+    /// Why a stop failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Set for a node taken over from a crashed GUI.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub adopted: bool,
+}
+
+/// Payload structs serialize their fields in camelCase (`#[serde(rename_all =
+/// "camelCase")]`, and `json!` payloads are written that way). While this is true every
+/// key is sent under its old snake_case name as well, so the frontend can move over; once
+/// it reads only camelCase, set it to false to drop them.
+const SNAKE_CASE_KEYS: bool = true;
+
+/// Events whose payload has data as object keys (setting names), left as they are.
+const DATA_KEYED: [&str; 1] = [SETTINGS_CHANGED];
+
+/// `current_block` -> `currentBlock`.
+pub fn camel_case(key: &str) -> String {
+    let mut out = String::with_capacity(key.len());
+    let mut upper = false;
+    for c in key.chars() {
+        if c == '_' {
+            upper = !out.is_empty();
+        } else if upper {
+            out.extend(c.to_uppercase());
+            upper = false;
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// `currentBlock` -> `current_block`.
+pub fn snake_case(key: &str) -> String {
+    let mut out = String::with_capacity(key.len() + 4);
+    for c in key.chars() {
+        if c.is_ascii_uppercase() {
+            out.push('_');
+            out.push(c.to_ascii_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// Rename every object key in `v` to camelCase, for payloads stored before the structs
+/// were renamed (an older `last_state.json`).
+pub fn camel_case_keys(v: &mut serde_json::Value) {
+    match v {
+        serde_json::Value::Array(items) => items.iter_mut().for_each(camel_case_keys),
+        serde_json::Value::Object(map) => {
+            map.values_mut().for_each(camel_case_keys);
+            let snake: Vec<String> = map.keys().filter(|k| k.contains('_')).cloned().collect();
+            for key in snake {
+                let camel = camel_case(&key);
+                if let Some(value) = map.remove(&key) {
+                    map.entry(camel).or_insert(value);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+// Next to every camelCase key in `v`, the same value under its snake_case name.
+fn add_snake_case_keys(v: &mut serde_json::Value) {
+    match v {
+        serde_json::Value::Array(items) => items.iter_mut().for_each(add_snake_case_keys),
+        serde_json::Value::Object(map) => {
+            map.values_mut().for_each(add_snake_case_keys);
+            let camel: Vec<String> = map
+                .keys()
+                .filter(|k| k.chars().any(|c| c.is_ascii_uppercase()))
+                .cloned()
+                .collect();
+            for key in camel {
+                let snake = snake_case(&key);
+                if let Some(value) = map.get(&key).cloned() {
+                    map.entry(snake).or_insert(value);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Emit `payload` on `event`, one of the constants above. The only place that calls
+/// `Emitter::emit` (clippy's `disallowed-methods` flags any other). Payload field names
+/// are camelCase, with snake_case copies while `SNAKE_CASE_KEYS` is on.
+#[allow(clippy::disallowed_methods)]
+pub fn emit<S: Serialize + Clone>(app: &AppHandle, event: &'static str, payload: S) {
+    debug_assert!(
+        ALL.contains(&event),
+        "event {event:?} is not in events::ALL"
+    );
+    let mut value = match serde_json::to_value(&payload) {
+        Ok(v) => v,
+        Err(e) => {
+            eprintln!("event {event}: {e}");
+            return;
+        }
+    };
+    // secrets are redacted before they get here (`redact.rs`); one that didn't is a bug
+    debug_assert!(
+        !crate::redact::has_mnemonic(&value.to_string()),
+        "event {event:?} carries something that looks like a secret phrase"
+    );
+    // classified even when suppressed below: `miner:notable` is what minimal keeps
+    crate::notable::classify(app, event, &mut value);
+    crate::gui_metrics::observe(event, &value);
+    if verbosity() == Verbosity::Minimal && !MINIMAL.contains(&event) {
+        crate::gui_metrics::note_dropped("verbosity");
+        return;
+    }
+    if SNAKE_CASE_KEYS && !DATA_KEYED.contains(&event) {
+        add_snake_case_keys(&mut value);
+    }
+    if app.emit(event, value).is_err() {
+        crate::gui_metrics::note_dropped("emit_error");
+    }
+}
+
+/// A log view line from `source` ("stdout" | "stderr" | "ui"), stamped now.
+pub fn emit_log(app: &AppHandle, source: &'static str, line: String) {
+    emit(
+        app,
+        MINER_LOG,
+        LogMsg {
+            source,
+            line: crate::redact::string(line),
+            ts: crate::timeutil::now_ms(),
+        },
+    );
+}
+
+/// `miner:parse-debug` payload: what the parser made of one node output line.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ParseDebug<'a> {
+    chain: &'a str,
+    source: &'static str,
+    line: String,
+    /// None when the line isn't an event.
+    parsed: Option<&'a MinerEvent>,
+    /// False for a parsed event dropped as a duplicate.
+    forwarded: bool,
+    ts: i64,
+}
+
+/// With `Verbosity::Debug`, the parse result of a node line from `source` ("stdout" |
+/// "stderr").
+pub fn emit_parse_debug(
+    app: &AppHandle,
+    chain: &str,
+    source: &'static str,
+    line: &str,
+    parsed: Option<&MinerEvent>,
+    forwarded: bool,
+) {
+    if verbosity() != Verbosity::Debug {
+        return;
+    }
+    emit(
+        app,
+        MINER_PARSE_DEBUG,
+        ParseDebug {
+            chain,
+            source,
+            line: crate::redact::string(line.to_string()),
+            parsed,
+            forwarded,
+            ts: crate::timeutil::now_ms(),
+        },
+    );
+}
+
+/// `miner:state` for `chain` without an error.
+pub fn emit_state(app: &AppHandle, chain: &str, running: bool, phase: &'static str) {
+    emit(
+        app,
+        MINER_STATE,
+        MinerState {
+            running,
+            phase,
+            chain,
+            error: None,
+            adopted: false,
+        },
+    );
+}
+
+/// `miner:status`: a full status (`miner::finalize_status`) or a partial update.
+pub fn emit_status<S: Serialize + Clone>(app: &AppHandle, status: S) {
+    emit(app, MINER_STATUS, status);
+}
+
+/// `miner:meta`: node metadata, possibly partial (the UI merges non-null fields).
+pub fn emit_meta<S: Serialize + Clone>(app: &AppHandle, meta: S) {
+    emit(app, MINER_META, meta);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn wire<S: Serialize>(payload: &S) -> serde_json::Value {
+        serde_json::to_value(payload).unwrap()
+    }
+
+    #[test]
+    fn event_names_are_pinned() {
+        // renaming or adding an event changes what the frontend must listen on; update
+        // this list (and `api.ts`) on purpose
+        assert_eq!(
+            ALL,
+            [
+                "account:corrupt",
+                "account:not-backed-up",
+                "app:deeplink",
+                "app:migrated-data",
+                "app:update-available",
+                "benchmark:progress",
+                "installer:progress",
+                "logs:move-progress",
+                "miner:authored",
+                "miner:balance",
+                "miner:binary-modified",
+                "miner:block-propagated",
+                "miner:chain-mismatch",
+                "miner:duplicate-identity",
+                "miner:event",
+                "miner:exited",
+                "miner:external-crashloop",
+                "miner:external-status",
+                "miner:fork-detected",
+                "miner:import-pressure",
+                "miner:import-progress",
+                "miner:log",
+                "miner:log-throttled",
+                "miner:logfile",
+                "miner:meta",
+                "miner:notable",
+                "miner:parse-debug",
+                "miner:phase",
+                "miner:quarantined",
+                "miner:remediation",
+                "miner:repair-progress",
+                "miner:restarting",
+                "miner:rewards-address-changed",
+                "miner:runtime-upgraded",
+                "miner:safe-mode-countdown",
+                "miner:safe-mode-recommendation",
+                "miner:security-warning",
+                "miner:start-degraded",
+                "miner:start-failed",
+                "miner:started",
+                "miner:state",
+                "miner:status",
+                "miner:sync-complete",
+                "miner:throttle",
+                "miner:timed-stop",
+                "miner:transfer",
+                "operation:cancelled",
+                "operation:started",
+                "settings:changed",
+                "settings:flush-failed",
+                "setup:progress",
+            ]
+        );
+        assert!(ALL.windows(2).all(|w| w[0] < w[1]), "ALL must stay sorted");
+    }
+
+    // Every object key in `v`, however deep.
+    fn keys(v: &serde_json::Value, out: &mut Vec<String>) {
+        match v {
+            serde_json::Value::Array(items) => items.iter().for_each(|i| keys(i, out)),
+            serde_json::Value::Object(map) => {
+                for (k, v) in map {
+                    out.push(k.clone());
+                    keys(v, out);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    #[test]
+    fn case_conversions() {
+        assert_eq!(camel_case("current_block"), "currentBlock");
+        assert_eq!(camel_case("last_rpc_success_ts"), "lastRpcSuccessTs");
+        assert_eq!(camel_case("peers"), "peers");
+        assert_eq!(snake_case("currentBlock"), "current_block");
+        assert_eq!(snake_case("lastRpcSuccessTs"), "last_rpc_success_ts");
+        assert_eq!(snake_case("sha256"), "sha256");
+    }
+
+    #[test]
+    fn snake_case_copies_sit_next_to_camel_case_keys() {
+        let mut v = json!({
+            "currentBlock": 5,
+            "heavyBlock": { "elapsedSecs": 3 },
+            "workers": [{ "id": "0", "hashRate": 1.0 }],
+        });
+        add_snake_case_keys(&mut v);
+        assert_eq!(
+            v,
+            json!({
+                "currentBlock": 5,
+                "current_block": 5,
+                "heavyBlock": { "elapsedSecs": 3, "elapsed_secs": 3 },
+                "heavy_block": { "elapsedSecs": 3, "elapsed_secs": 3 },
+                "workers": [{ "id": "0", "hashRate": 1.0, "hash_rate": 1.0 }],
+            })
+        );
+    }
+
+    #[test]
+    fn stored_snake_case_keys_come_back_camel_case() {
+        let mut v = json!({ "current_block": 5, "heavy_block": { "elapsed_secs": 3 } });
+        camel_case_keys(&mut v);
+        assert_eq!(
+            v,
+            json!({ "currentBlock": 5, "heavyBlock": { "elapsedSecs": 3 } })
+        );
+    }
+
+    #[test]
+    fn log_and_state_payloads() {
+        let log = LogMsg {
+            source: "stdout",
+            line: "Idle".into(),
+            ts: 1,
+        };
+        assert_eq!(
+            wire(&log),
+            json!({ "source": "stdout", "line": "Idle", "ts": 1 })
+        );
+        let state = MinerState {
+            running: false,
+            phase: "stop_failed",
+            chain: "resonance",
+            error: Some("busy".into()),
+            adopted: true,
+        };
+        assert_eq!(
+            wire(&state),
+            json!({
+                "running": false,
+                "phase": "stop_failed",
+                "chain": "resonance",
+                "error": "busy",
+                "adopted": true,
+            })
+        );
+        let ev = MinerEvent::SlowImport {
+            block: Some(7),
+            seconds: 32.0,
+        };
+        let debug = ParseDebug {
+            chain: "resonance",
+            source: "stderr",
+            line: "Block import took 32s".into(),
+            parsed: Some(&ev),
+            forwarded: true,
+            ts: 2,
+        };
+        assert_eq!(
+            wire(&debug),
+            json!({
+                "chain": "resonance",
+                "source": "stderr",
+                "line": "Block import took 32s",
+                "parsed": { "type": "SlowImport", "block": 7, "seconds": 32.0 },
+                "forwarded": true,
+                "ts": 2,
+            })
+        );
+    }
+
+    #[test]
+    fn miner_event_and_phase_payloads() {
+        let status = MinerEvent::NodeStatus {
+            state: "syncing".into(),
+            peers: Some(3),
+            best: Some(10),
+            finalized: None,
+            target: Some(20),
+            bps: Some(1.5),
+        };
+        assert_eq!(
+            wire(&status),
+            json!({
+                "type": "NodeStatus",
+                "state": "syncing",
+                "peers": 3,
+                "best": 10,
+                "finalized": null,
+                "target": 20,
+                "bps": 1.5,
+            })
+        );
+        assert_eq!(wire(&MinerEvent::Connected), json!({ "type": "Connected" }));
+        assert_eq!(
+            wire(&crate::phase::MinerPhase::Restarting {
+                reason: "safe_mode_enable".into()
+            }),
+            json!({ "phase": "restarting", "reason": "safe_mode_enable" })
+        );
+        assert_eq!(
+            wire(&crate::phase::MinerPhase::WaitingForRpc),
+            json!({ "phase": "waiting_for_rpc" })
+        );
+    }
+
+    #[test]
+    fn progress_payloads() {
+        let repair = crate::repair::RepairProgress {
+            chain: "resonance".into(),
+            stage: "deleting",
+            total_files: 10,
+            total_bytes: 100,
+            removed_files: 4,
+            removed_bytes: 40,
+            resumed: false,
+        };
+        assert_eq!(
+            wire(&repair),
+            json!({
+                "chain": "resonance",
+                "stage": "deleting",
+                "totalFiles": 10,
+                "totalBytes": 100,
+                "removedFiles": 4,
+                "removedBytes": 40,
+                "resumed": false,
+            })
+        );
+        let import = crate::data_import::ImportProgress {
+            chain: "resonance".into(),
+            stage: "copying",
+            total_files: 10,
+            total_bytes: 100,
+            copied_files: 1,
+            copied_bytes: 10,
+        };
+        assert_eq!(
+            wire(&import),
+            json!({
+                "chain": "resonance",
+                "stage": "copying",
+                "totalFiles": 10,
+                "totalBytes": 100,
+                "copiedFiles": 1,
+                "copiedBytes": 10,
+            })
+        );
+    }
+
+    #[test]
+    fn chain_payloads() {
+        let mismatch = crate::chain_guard::ChainDataMismatch {
+            chain: "heisenberg".into(),
+            chain_dir: "/data/chains/heisenberg".into(),
+            expected: "heisenberg".into(),
+            found: "resonance".into(),
+            expected_genesis: None,
+            found_genesis: "0x01".into(),
+            remediations: vec!["repair"],
+        };
+        assert_eq!(
+            wire(&mismatch),
+            json!({
+                "chain": "heisenberg",
+                "chainDir": "/data/chains/heisenberg",
+                "expected": "heisenberg",
+                "found": "resonance",
+                "expectedGenesis": null,
+                "foundGenesis": "0x01",
+                "remediations": ["repair"],
+            })
+        );
+        let fork = crate::fork::ForkDetected {
+            chain: "resonance".into(),
+            height: 100,
+            local_hash: "0xaa".into(),
+            network_hash: "0xbb".into(),
+            network_height: Some(101),
+            checks: 3,
+            action: crate::fork::ForkAction::SuggestRestart,
+            ts: 5,
+        };
+        assert_eq!(
+            wire(&fork),
+            json!({
+                "chain": "resonance",
+                "height": 100,
+                "localHash": "0xaa",
+                "networkHash": "0xbb",
+                "networkHeight": 101,
+                "checks": 3,
+                "action": "suggest_restart",
+                "ts": 5,
+            })
+        );
+        let sync = crate::milestones::SyncComplete {
+            chain: "resonance".into(),
+            best_block: Some(500),
+            milestones: crate::milestones::ChainMilestones {
+                first_start_at: Some(10),
+                first_synced_at: Some(70),
+                sync_wall_secs: Some(60),
+                sync_runtime_secs: 50,
+                repairs_before_sync: 1,
+            },
+            ts: 6,
+        };
+        assert_eq!(
+            wire(&sync),
+            json!({
+                "chain": "resonance",
+                "bestBlock": 500,
+                "firstStartAt": 10,
+                "firstSyncedAt": 70,
+                "syncWallSecs": 60,
+                "syncRuntimeSecs": 50,
+                "repairsBeforeSync": 1,
+                "ts": 6,
+            })
+        );
+        let restart = crate::restart::RestartRecord {
+            chain: "resonance".into(),
+            reason: crate::restart::RestartReason::SafeModeEnable,
+            details: "range".into(),
+            ts: 7,
+        };
+        assert_eq!(
+            wire(&restart),
+            json!({
+                "chain": "resonance",
+                "reason": "safe_mode_enable",
+                "details": "range",
+                "ts": 7,
+            })
+        );
+    }
+
+    #[test]
+    fn app_payloads() {
+        let link = crate::deeplink::DeepLinkIntent {
+            action: "mine".into(),
+            chain: "resonance".into(),
+            rewards_address: Some("qz1".into()),
+            url: "quantus://mine?chain=resonance".into(),
+        };
+        assert_eq!(
+            wire(&link),
+            json!({
+                "action": "mine",
+                "chain": "resonance",
+                "rewardsAddress": "qz1",
+                "url": "quantus://mine?chain=resonance",
+            })
+        );
+        let update = crate::app_update::AppUpdateInfo {
+            current: "0.1.0".into(),
+            latest: "0.2.0".into(),
+            notes: None,
+            download_url: Some("https://example.com".into()),
+            update_available: true,
+        };
+        assert_eq!(
+            wire(&update),
+            json!({
+                "current": "0.1.0",
+                "latest": "0.2.0",
+                "notes": null,
+                "downloadUrl": "https://example.com",
+                "updateAvailable": true,
+            })
+        );
+        let migrated = crate::data_migration::Migrated {
+            from: "/old".into(),
+            to: "/new".into(),
+            files: vec!["settings.json".into()],
+            ts: 8,
+        };
+        assert_eq!(
+            wire(&migrated),
+            json!({ "from": "/old", "to": "/new", "files": ["settings.json"], "ts": 8 })
+        );
+        let external = crate::ext_status::ExternalStatus {
+            chain: "resonance".into(),
+            port: 9833,
+            healthy: true,
+            version: Some("1.0.0".into()),
+            hashrate: Some(2.0),
+            accepted: Some(1),
+            workers: vec![crate::ext_status::WorkerStatus {
+                id: "0".into(),
+                hashrate: Some(2.0),
+                accepted: None,
+            }],
+            error: None,
+            ts: 9,
+        };
+        assert_eq!(
+            wire(&external),
+            json!({
+                "chain": "resonance",
+                "port": 9833,
+                "healthy": true,
+                "version": "1.0.0",
+                "hashrate": 2.0,
+                "accepted": 1,
+                "workers": [{ "id": "0", "hashrate": 2.0, "accepted": null }],
+                "error": null,
+                "ts": 9,
+            })
+        );
+    }
+
+    #[test]
+    fn balance_and_block_payloads() {
+        let balance = crate::rpc::BalanceView {
+            address: "qz1".into(),
+            free: "1500000000000".into(),
+            reserved: "0".into(),
+            symbol: "RES".into(),
+            decimals: 12,
+            free_formatted: "1.5".into(),
+            reserved_formatted: "0".into(),
+        };
+        assert_eq!(
+            wire(&balance),
+            json!({
+                "address": "qz1",
+                "free": "1500000000000",
+                "reserved": "0",
+                "symbol": "RES",
+                "decimals": 12,
+                "freeFormatted": "1.5",
+                "reservedFormatted": "0",
+            })
+        );
+        let transfer = crate::rpc::Transfer {
+            id: "1-0".into(),
+            direction: "in",
+            counterparty: "qz2".into(),
+            amount: "1000".into(),
+            amount_formatted: "0.000000001".into(),
+            symbol: "RES".into(),
+            block: Some(3),
+            timestamp: Some(4),
+            extrinsic_hash: None,
+        };
+        assert_eq!(
+            wire(&transfer),
+            json!({
+                "id": "1-0",
+                "direction": "in",
+                "counterparty": "qz2",
+                "amount": "1000",
+                "amountFormatted": "0.000000001",
+                "symbol": "RES",
+                "block": 3,
+                "timestamp": 4,
+                "extrinsicHash": null,
+            })
+        );
+        let block = crate::authorship::AuthoredBlock {
+            height: 5,
+            hash: "0xcc".into(),
+            ts: 6,
+            orphaned: false,
+            propagation_ms: Some(250),
+            propagated: Some(true),
+        };
+        assert_eq!(
+            wire(&block),
+            json!({
+                "height": 5,
+                "hash": "0xcc",
+                "ts": 6,
+                "orphaned": false,
+                "propagationMs": 250,
+                "propagated": true,
+            })
+        );
+        let heavy = crate::sync_phase::HeavyBlock {
+            number: 9,
+            elapsed_secs: 40,
+        };
+        assert_eq!(wire(&heavy), json!({ "number": 9, "elapsedSecs": 40 }));
+    }
+
+    #[test]
+    fn session_stats_keys() {
+        let v = wire(&crate::stats::SessionStats::default());
+        let mut found = Vec::new();
+        keys(&v, &mut found);
+        found.sort();
+        assert_eq!(
+            found,
+            [
+                "avgPropagationMs",
+                "blocksPerMinBeforeSafeMode",
+                "blocksPerMinInSafeMode",
+                "importBacklog",
+                "maxImportBacklog",
+                "maxImportSecs",
+                "maxPropagationMs",
+                "pressureEpisodes",
+                "propagatedBlocks",
+                "safeModeSecs",
+                "safeModeSlowdown",
+                "slowImports",
+                "startedAt",
+                "unpropagatedBlocks",
+            ]
+        );
+    }
+
+    #[test]
+    fn persisted_records_still_read_snake_case() {
+        let old: crate::authorship::AuthoredBlock = serde_json::from_value(json!({
+            "height": 5, "hash": "0xcc", "ts": 6, "propagation_ms": 250,
+        }))
+        .unwrap();
+        assert_eq!(old.propagation_ms, Some(250));
+        let old: crate::provenance::Provenance = serde_json::from_value(json!({
+            "source_url": "https://example.com/node.tar.gz",
+            "release_tag": "v1.0.0",
+            "asset": "node.tar.gz",
+            "sha256": "ab",
+            "installed_at": 1,
+            "gui_version": "0.1.0",
+        }))
+        .unwrap();
+        assert_eq!(old.release_tag, "v1.0.0");
+        assert_eq!(
+            wire(&old),
+            json!({
+                "sourceUrl": "https://example.com/node.tar.gz",
+                "releaseTag": "v1.0.0",
+                "asset": "node.tar.gz",
+                "sha256": "ab",
+                "installedAt": 1,
+                "guiVersion": "0.1.0",
+            })
+        );
+        let old: crate::milestones::ChainMilestones =
+            serde_json::from_value(json!({ "sync_wall_secs": 60, "repairs_before_sync": 2 }))
+                .unwrap();
+        assert_eq!((old.sync_wall_secs, old.repairs_before_sync), (Some(60), 2));
+    }
+}
@@ -0,0 +1,138 @@
+use anyhow::{anyhow, Result};
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, VecDeque},
+    time::{Duration, Instant},
+};
+use tauri::AppHandle;
+
+/// What to do once the external miner is crash-looping, tried in the configured order
+/// (one step per detected loop).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Remediation {
+    /// Restart the miner with `skip_flag`, if its `--help` lists it.
+    SkipWork,
+    /// Restart the node, so the miner gets a new block template.
+    RestartNode,
+}
+
+/// External miner crash handling (`{app_data_dir}/external_crashloop.json`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CrashLoopSettings {
+    /// Restart the external miner when it exits on its own. On by default.
+    pub auto_restart: bool,
+    /// A crash loop is this many exits...
+    pub max_crashes: usize,
+    /// ...within this many seconds.
+    pub window_secs: u64,
+    /// Tried in order, one per detected loop; once all were tried the miner is left stopped.
+    pub remediations: Vec<Remediation>,
+    /// Flag for `Remediation::SkipWork`.
+    pub skip_flag: String,
+}
+
+impl crate::persist::Versioned for CrashLoopSettings {
+    const VERSION: u32 = 1;
+    const MIGRATIONS: &'static [crate::persist::Migration] = &[crate::persist::unchanged];
+}
+
+impl Default for CrashLoopSettings {
+    fn default() -> Self {
+        CrashLoopSettings {
+            auto_restart: true,
+            max_crashes: 3,
+            window_secs: 120,
+            remediations: vec![Remediation::SkipWork, Remediation::RestartNode],
+            skip_flag: "--skip-current-job".to_string(),
+        }
+    }
+}
+
+#[derive(Default)]
+struct Tracker {
+    // exits inside the current window
+    exits: VecDeque<Instant>,
+    // remediations already used in this run of crashes
+    step: usize,
+}
+
+static SETTINGS: crate::persist::Cached<CrashLoopSettings> =
+    crate::persist::Cached::new("external_crashloop.json");
+
+lazy_static! {
+    static ref TRACKERS: std::sync::Mutex<HashMap<String, Tracker>> =
+        std::sync::Mutex::new(HashMap::new());
+}
+
+/// Current settings (read from disk once, then cached).
+pub fn load_settings(app: &AppHandle) -> CrashLoopSettings {
+    SETTINGS.load(app)
+}
+
+pub fn save_settings(app: &AppHandle, s: &CrashLoopSettings) -> Result<()> {
+    if s.max_crashes < 2 {
+        return Err(anyhow!("a crash loop needs at least 2 crashes"));
+    }
+    if s.window_secs == 0 {
+        return Err(anyhow!("the time window must be at least 1 second"));
+    }
+    if s.remediations.contains(&Remediation::SkipWork) && !s.skip_flag.starts_with('-') {
+        return Err(anyhow!("the skip flag must start with '-'"));
+    }
+    SETTINGS.store(app, s)
+}
+
+/// A detected crash loop: how many exits, and which remediations are left to try
+/// (in order; empty once all were used).
+pub struct CrashLoop {
+    pub crashes: usize,
+    pub remaining: Vec<Remediation>,
+}
+
+/// Record an exit of `chain`'s external miner. Once `max_crashes` fall inside the
+/// window it's a loop: the window starts over and the next loop moves on to the
+/// remediation after the ones returned here. A window without crashes resets that.
+pub fn note_exit(chain: &str, s: &CrashLoopSettings) -> Option<CrashLoop> {
+    let now = Instant::now();
+    let window = Duration::from_secs(s.window_secs);
+    let mut trackers = TRACKERS.lock().ok()?;
+    let t = trackers.entry(chain.to_string()).or_default();
+    while t
+        .exits
+        .front()
+        .is_some_and(|e| now.duration_since(*e) > window)
+    {
+        t.exits.pop_front();
+    }
+    if t.exits.is_empty() {
+        t.step = 0;
+    }
+    t.exits.push_back(now);
+    if t.exits.len() < s.max_crashes {
+        return None;
+    }
+    let crashes = t.exits.len();
+    // keep the last exit so a crash right after the remediation continues the run
+    t.exits.drain(..crashes - 1);
+    let remaining = s.remediations.iter().skip(t.step).copied().collect();
+    Some(CrashLoop { crashes, remaining })
+}
+
+/// `remediation` was applied for `chain`'s current loop; the next loop tries the one
+/// after it.
+pub fn note_remediation(chain: &str, s: &CrashLoopSettings, remediation: Remediation) {
+    if let Ok(mut trackers) = TRACKERS.lock() {
+        let t = trackers.entry(chain.to_string()).or_default();
+        if let Some(i) = s
+            .remediations
+            .iter()
+            .skip(t.step)
+            .position(|r| *r == remediation)
+        {
+            t.step += i + 1;
+        }
+    }
+}
@@ -0,0 +1,210 @@
+use anyhow::{anyhow, Result};
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, time::Duration};
+use tauri::AppHandle;
+
+use crate::events;
+
+/// Path of the external miner's status endpoint on its HTTP port.
+const STATUS_PATH: &str = "/status";
+/// Time between polls once the endpoint has answered.
+const POLL_INTERVAL: Duration = Duration::from_secs(10);
+/// How long after a spawn the endpoint may stay unreachable before the miner is
+/// reported unhealthy; polls back off from `FIRST_RETRY` to `POLL_INTERVAL` meanwhile.
+const STARTUP_GRACE: Duration = Duration::from_secs(30);
+const FIRST_RETRY: Duration = Duration::from_millis(500);
+/// Per-request limit: the miner is local, a slow answer means it's stuck.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(3);
+
+lazy_static! {
+    // current poller per chain; an older poller sees a newer id and exits
+    static ref POLLERS: std::sync::Mutex<HashMap<String, u64>> =
+        std::sync::Mutex::new(HashMap::new());
+}
+
+// What the status endpoint returns. Miner versions differ in naming and in which fields
+// they report, so everything is optional and unknown fields are ignored.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct RawStatus {
+    version: Option<String>,
+    #[serde(alias = "hash_rate", alias = "total_hashrate", alias = "hashrate_hps")]
+    hashrate: Option<f64>,
+    #[serde(alias = "accepted_work", alias = "solutions_found")]
+    accepted: Option<u64>,
+    #[serde(alias = "threads")]
+    workers: Vec<RawWorker>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct RawWorker {
+    #[serde(alias = "index", alias = "thread", alias = "worker_id")]
+    id: Option<serde_json::Value>,
+    #[serde(alias = "hash_rate", alias = "hashrate_hps")]
+    hashrate: Option<f64>,
+    #[serde(alias = "accepted_work", alias = "solutions_found")]
+    accepted: Option<u64>,
+}
+
+/// One worker (thread) of the external miner.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkerStatus {
+    pub id: String,
+    pub hashrate: Option<f64>,
+    pub accepted: Option<u64>,
+}
+
+/// `miner:external-status` payload, sent after every poll. `healthy` is false once the
+/// endpoint has been unreachable past the startup grace period (`error` says why).
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExternalStatus {
+    pub chain: String,
+    pub port: u16,
+    pub healthy: bool,
+    pub version: Option<String>,
+    /// Total H/s: as reported, else the sum over workers.
+    pub hashrate: Option<f64>,
+    pub accepted: Option<u64>,
+    pub workers: Vec<WorkerStatus>,
+    pub error: Option<String>,
+    pub ts: i64,
+}
+
+impl ExternalStatus {
+    fn from_raw(chain: &str, port: u16, raw: RawStatus) -> Self {
+        let workers: Vec<WorkerStatus> = raw
+            .workers
+            .into_iter()
+            .enumerate()
+            .map(|(i, w)| WorkerStatus {
+                id: match w.id {
+                    Some(serde_json::Value::String(s)) => s,
+                    Some(v) if !v.is_null() => v.to_string(),
+                    _ => i.to_string(),
+                },
+                hashrate: w.hashrate,
+                accepted: w.accepted,
+            })
+            .collect();
+        // older miners only report per worker
+        let any = |f: fn(&WorkerStatus) -> bool| workers.iter().any(f);
+        let hashrate = raw.hashrate.or_else(|| {
+            any(|w| w.hashrate.is_some()).then(|| workers.iter().filter_map(|w| w.hashrate).sum())
+        });
+        let accepted = raw.accepted.or_else(|| {
+            any(|w| w.accepted.is_some()).then(|| workers.iter().filter_map(|w| w.accepted).sum())
+        });
+        ExternalStatus {
+            chain: chain.to_string(),
+            port,
+            healthy: true,
+            version: raw.version,
+            hashrate,
+            accepted,
+            workers,
+            error: None,
+            ts: crate::timeutil::now_ms(),
+        }
+    }
+
+    fn unhealthy(chain: &str, port: u16, error: String) -> Self {
+        ExternalStatus {
+            chain: chain.to_string(),
+            port,
+            healthy: false,
+            version: None,
+            hashrate: None,
+            accepted: None,
+            workers: Vec::new(),
+            error: Some(error),
+            ts: crate::timeutil::now_ms(),
+        }
+    }
+}
+
+async fn fetch(port: u16) -> Result<RawStatus> {
+    let url = format!("http://127.0.0.1:{port}{STATUS_PATH}");
+    let resp = crate::net::client()
+        .get(&url)
+        .timeout(REQUEST_TIMEOUT)
+        .send()
+        .await?
+        .error_for_status()?;
+    let body = resp.text().await?;
+    serde_json::from_str(&body).map_err(|e| anyhow!("unexpected status from {url}: {e}"))
+}
+
+// Whether `id` is still `chain`'s poller and its external miner still runs on `port`.
+async fn current(chain: &str, port: u16, id: u64) -> bool {
+    let latest = POLLERS.lock().ok().and_then(|m| m.get(chain).copied());
+    latest == Some(id) && crate::miner::external_miner(chain).await.map(|(_, p)| p) == Some(port)
+}
+
+/// Poll `chain`'s external miner on `port` until it stops (or is restarted, which spawns
+/// a new poller). Hashrates go the same way as ones parsed from the node's log.
+pub fn spawn(app: &AppHandle, chain: &str, port: u16) {
+    let id = {
+        let Ok(mut map) = POLLERS.lock() else {
+            return;
+        };
+        let id = map.get(chain).map_or(0, |n| n + 1);
+        map.insert(chain.to_string(), id);
+        id
+    };
+    let (app, chain) = (app.clone(), chain.to_string());
+    tauri::async_runtime::spawn(async move {
+        let started = tokio::time::Instant::now();
+        let mut retry = FIRST_RETRY;
+        let mut answered = false;
+        let mut reported_down = false;
+        // the miner was inserted into EXT_MINERS just before this; give it a moment
+        tokio::time::sleep(FIRST_RETRY).await;
+        while current(&chain, port, id).await {
+            let wait = match fetch(port).await {
+                Ok(raw) => {
+                    let status = ExternalStatus::from_raw(&chain, port, raw);
+                    if let Some(hps) = status.hashrate {
+                        crate::miner::report_hashrate(&app, &chain, hps).await;
+                    }
+                    if reported_down {
+                        crate::session_log::ui(
+                            &app,
+                            &chain,
+                            "External miner status endpoint is answering again.",
+                        );
+                    }
+                    events::emit(&app, events::MINER_EXTERNAL_STATUS, &status);
+                    (answered, reported_down) = (true, false);
+                    POLL_INTERVAL
+                }
+                Err(_) if !answered && started.elapsed() < STARTUP_GRACE => {
+                    // still starting up: back off quietly
+                    let wait = retry;
+                    retry = (retry * 2).min(POLL_INTERVAL);
+                    wait
+                }
+                Err(e) => {
+                    if !reported_down {
+                        crate::session_log::ui(
+                            &app,
+                            &chain,
+                            format!("External miner on port {port} is not reporting status: {e}"),
+                        );
+                        reported_down = true;
+                    }
+                    events::emit(
+                        &app,
+                        events::MINER_EXTERNAL_STATUS,
+                        &ExternalStatus::unhealthy(&chain, port, e.to_string()),
+                    );
+                    POLL_INTERVAL
+                }
+            };
+            tokio::time::sleep(wait).await;
+        }
+    });
+}
@@ -0,0 +1,360 @@
+use serde::Serialize;
+
+/// More than this many extra args is a paste gone wrong.
+const MAX_ARGS: usize = 64;
+const MAX_ARG_LEN: usize = 1024;
+const MAX_TOTAL_LEN: usize = 8 * 1024;
+
+/// Flags that expose the node's RPC to the network or unlock unsafe methods; only passed
+/// with `allow_unsafe`. `None` as value: the flag alone is enough.
+const UNSAFE_FLAGS: &[(&str, Option<&str>)] = &[
+    ("--unsafe-rpc-external", None),
+    ("--unsafe-ws-external", None),
+    ("--rpc-methods", Some("unsafe")),
+];
+
+/// Flags that make the node's RPC listen on all interfaces.
+const EXTERNAL_RPC_FLAGS: &[&str] = &[
+    "--rpc-external",
+    "--unsafe-rpc-external",
+    "--ws-external",
+    "--unsafe-ws-external",
+];
+
+#[derive(Debug, thiserror::Error)]
+pub enum ExtraArgsError {
+    #[error("extra arguments must not contain line breaks: {0:?}")]
+    Newline(String),
+    #[error("unterminated {0} quote in extra arguments")]
+    UnterminatedQuote(char),
+    #[error("extra arguments must start with a flag (e.g. --name), got {0:?}")]
+    NotAFlag(String),
+    #[error("too many extra arguments ({0}, max {MAX_ARGS})")]
+    TooMany(usize),
+    #[error("extra argument too long ({len} chars, max {max})")]
+    TooLong { len: usize, max: usize },
+    #[error("{0} exposes the node's RPC; enable \"I know what I'm doing\" to pass it")]
+    Unsafe(String),
+}
+
+/// Split a pasted command line into args: whitespace separates, '…' is literal, "…"
+/// allows \" and \\ escapes. Line breaks are rejected rather than guessed at.
+pub fn split(line: &str) -> Result<Vec<String>, ExtraArgsError> {
+    let line = line.trim();
+    if line.contains(['\n', '\r']) {
+        return Err(ExtraArgsError::Newline(line.to_string()));
+    }
+    let mut out = Vec::new();
+    let mut cur = String::new();
+    let mut in_token = false;
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' | '"' => {
+                in_token = true;
+                loop {
+                    match chars.next() {
+                        None => return Err(ExtraArgsError::UnterminatedQuote(c)),
+                        Some(q) if q == c => break,
+                        Some('\\') if c == '"' => match chars.next() {
+                            Some(e @ ('"' | '\\')) => cur.push(e),
+                            Some(e) => {
+                                cur.push('\\');
+                                cur.push(e);
+                            }
+                            None => return Err(ExtraArgsError::UnterminatedQuote(c)),
+                        },
+                        Some(q) => cur.push(q),
+                    }
+                }
+            }
+            c if c.is_whitespace() => {
+                if in_token {
+                    out.push(std::mem::take(&mut cur));
+                    in_token = false;
+                }
+            }
+            c => {
+                in_token = true;
+                cur.push(c);
+            }
+        }
+    }
+    if in_token {
+        out.push(cur);
+    }
+    Ok(out)
+}
+
+// "quantus-node", "./quantus-node", "C:\...\quantus-node.exe"
+fn is_node_binary(arg: &str) -> bool {
+    let name = arg.rsplit(['/', '\\']).next().unwrap_or(arg);
+    let name = name.to_ascii_lowercase();
+    name.trim_end_matches(".exe") == "quantus-node"
+}
+
+/// Normalize extra args for the node: drop a leading binary name, check that they start
+/// with a flag and stay within the size caps, and refuse `UNSAFE_FLAGS` unless
+/// `allow_unsafe`. Returns exactly what will be appended to argv.
+pub fn normalize(args: Vec<String>, allow_unsafe: bool) -> Result<Vec<String>, ExtraArgsError> {
+    let mut args: Vec<String> = args
+        .into_iter()
+        .map(|a| a.trim().to_string())
+        .filter(|a| !a.is_empty())
+        .collect();
+    if args.first().is_some_and(|a| is_node_binary(a)) {
+        args.remove(0);
+    }
+    if let Some(a) = args.iter().find(|a| a.contains(['\n', '\r', '\0'])) {
+        return Err(ExtraArgsError::Newline(a.clone()));
+    }
+    if let Some(first) = args.first().filter(|a| !a.starts_with('-')) {
+        return Err(ExtraArgsError::NotAFlag(first.clone()));
+    }
+    if args.len() > MAX_ARGS {
+        return Err(ExtraArgsError::TooMany(args.len()));
+    }
+    if let Some(a) = args.iter().find(|a| a.len() > MAX_ARG_LEN) {
+        return Err(ExtraArgsError::TooLong {
+            len: a.len(),
+            max: MAX_ARG_LEN,
+        });
+    }
+    let total: usize = args.iter().map(|a| a.len() + 1).sum();
+    if total > MAX_TOTAL_LEN {
+        return Err(ExtraArgsError::TooLong {
+            len: total,
+            max: MAX_TOTAL_LEN,
+        });
+    }
+    if !allow_unsafe {
+        if let Some(flag) = find_unsafe(&args) {
+            return Err(ExtraArgsError::Unsafe(flag));
+        }
+    }
+    Ok(args)
+}
+
+/// The node's RPC is reachable from other machines.
+#[derive(Debug, Clone, Serialize)]
+pub struct RpcExposure {
+    /// Non-loopback listen addresses from the node's "Running JSON-RPC server" line.
+    pub addrs: Vec<String>,
+    /// Exposure flags found in the node's args.
+    pub flags: Vec<String>,
+    /// Unsafe methods (`author_*`, key management) are callable remotely as well.
+    pub unsafe_methods: bool,
+    /// What a remote caller can do, for the warning text.
+    pub exposes: &'static str,
+}
+
+/// Check the node's effective RPC binding (`jsonrpc_addr`, e.g. "0.0.0.0:9944, allowed
+/// origins=...") and args. Exposed when any listen address isn't loopback, or when the
+/// address isn't known yet but an external flag was passed.
+pub fn rpc_exposure(jsonrpc_addr: Option<&str>, args: &[String]) -> Option<RpcExposure> {
+    let addrs: Vec<String> = jsonrpc_addr
+        .into_iter()
+        .flat_map(|a| a.split([',', ' ']))
+        .filter_map(|t| t.trim().parse::<std::net::SocketAddr>().ok())
+        .filter(|a| !a.ip().is_loopback())
+        .map(|a| a.to_string())
+        .collect();
+    let mut flags: Vec<String> = args
+        .iter()
+        .filter(|a| EXTERNAL_RPC_FLAGS.contains(&a.as_str()))
+        .cloned()
+        .collect();
+    let unsafe_methods = match find_unsafe(args) {
+        Some(f) => {
+            if !flags.contains(&f) {
+                flags.push(f);
+            }
+            true
+        }
+        None => false,
+    };
+    let exposed = !addrs.is_empty() || (jsonrpc_addr.is_none() && !flags.is_empty());
+    exposed.then_some(RpcExposure {
+        addrs,
+        flags,
+        unsafe_methods,
+        exposes: if unsafe_methods {
+            "all RPC methods, including author_* (insert/rotate session keys, submit and remove extrinsics) and node administration"
+        } else {
+            "chain and state queries, transaction submission and node information (peers, version, health)"
+        },
+    })
+}
+
+/// `args` without the flags that expose RPC externally or enable unsafe methods.
+pub fn strip_rpc_exposure(args: &[String]) -> Vec<String> {
+    let mut out = Vec::with_capacity(args.len());
+    let mut iter = args.iter().peekable();
+    while let Some(a) = iter.next() {
+        if EXTERNAL_RPC_FLAGS.contains(&a.as_str()) {
+            continue;
+        }
+        if a == "--rpc-methods" {
+            iter.next_if(|v| !v.starts_with('-'));
+            continue;
+        }
+        if a.starts_with("--rpc-methods=") {
+            continue;
+        }
+        out.push(a.clone());
+    }
+    out
+}
+
+// First `UNSAFE_FLAGS` match, as "--flag" or "--flag value".
+fn find_unsafe(args: &[String]) -> Option<String> {
+    for (i, arg) in args.iter().enumerate() {
+        let (name, inline) = match arg.split_once('=') {
+            Some((n, v)) => (n, Some(v)),
+            None => (arg.as_str(), None),
+        };
+        for (flag, value) in UNSAFE_FLAGS {
+            if name != *flag {
+                continue;
+            }
+            match value {
+                None => return Some(flag.to_string()),
+                Some(v) => {
+                    let given = inline.or_else(|| args.get(i + 1).map(String::as_str));
+                    if given.is_some_and(|g| g.eq_ignore_ascii_case(v)) {
+                        return Some(format!("{flag} {v}"));
+                    }
+                }
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(a: &[&str]) -> Vec<String> {
+        a.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn split_handles_quotes_and_escapes() {
+        assert_eq!(
+            split(r#"  --name 'my node'  --x="a \"b\" c\\d" --y "\n" ''"#).unwrap(),
+            args(&["--name", "my node", r#"--x=a "b" c\d"#, "--y", r"\n", ""])
+        );
+        // no escapes inside '…'
+        assert!(matches!(
+            split(r"--p 'it\'s'"),
+            Err(ExtraArgsError::UnterminatedQuote('\''))
+        ));
+        assert!(matches!(
+            split(r#"--name "open"#),
+            Err(ExtraArgsError::UnterminatedQuote('"'))
+        ));
+        assert!(matches!(
+            split(r#"--name "trailing\"#),
+            Err(ExtraArgsError::UnterminatedQuote('"'))
+        ));
+        assert!(matches!(split("--a\n--b"), Err(ExtraArgsError::Newline(_))));
+        assert!(split("   ").unwrap().is_empty());
+    }
+
+    #[test]
+    fn normalize_strips_a_leading_binary() {
+        for bin in [
+            "quantus-node",
+            "./quantus-node",
+            "/opt/q/quantus-node",
+            r"C:\Quantus\Quantus-Node.exe",
+        ] {
+            assert_eq!(
+                normalize(args(&[bin, "--name", "x"]), false).unwrap(),
+                args(&["--name", "x"])
+            );
+        }
+        // only in front
+        assert!(matches!(
+            normalize(args(&["--name", "quantus-node"]), false),
+            Ok(a) if a.len() == 2
+        ));
+        assert!(matches!(
+            normalize(args(&["quantus-miner", "--name"]), false),
+            Err(ExtraArgsError::NotAFlag(a)) if a == "quantus-miner"
+        ));
+        assert!(matches!(
+            normalize(vec!["--a".to_string(); MAX_ARGS + 1], false),
+            Err(ExtraArgsError::TooMany(_))
+        ));
+    }
+
+    #[test]
+    fn unsafe_rpc_methods_in_either_form() {
+        for a in [
+            args(&["--rpc-methods=unsafe"]),
+            args(&["--rpc-methods", "Unsafe"]),
+        ] {
+            assert_eq!(find_unsafe(&a).as_deref(), Some("--rpc-methods unsafe"));
+            assert!(matches!(
+                normalize(a.clone(), false),
+                Err(ExtraArgsError::Unsafe(_))
+            ));
+            assert_eq!(normalize(a.clone(), true).unwrap(), a);
+        }
+        assert_eq!(find_unsafe(&args(&["--rpc-methods=safe"])), None);
+        assert_eq!(find_unsafe(&args(&["--rpc-methods", "auto"])), None);
+        assert_eq!(find_unsafe(&args(&["--rpc-methods"])), None);
+    }
+
+    #[test]
+    fn unsafe_rpc_external_is_exposure() {
+        let a = args(&["--unsafe-rpc-external"]);
+        assert_eq!(find_unsafe(&a).as_deref(), Some("--unsafe-rpc-external"));
+
+        // address not known yet: the flag alone counts
+        let e = rpc_exposure(None, &a).unwrap();
+        assert!(e.addrs.is_empty());
+        assert_eq!(e.flags, a);
+        assert!(e.unsafe_methods);
+
+        // the node's actual binding wins once it's logged
+        assert!(rpc_exposure(Some("127.0.0.1:9944, [::1]:9944"), &a).is_none());
+        let e = rpc_exposure(Some("0.0.0.0:9944, allowed origins=[*]"), &[]).unwrap();
+        assert_eq!(e.addrs, ["0.0.0.0:9944"]);
+        assert!(!e.unsafe_methods);
+
+        assert!(rpc_exposure(None, &args(&["--name", "x"])).is_none());
+    }
+
+    #[test]
+    fn strip_keeps_unrelated_flags_and_values() {
+        let a = args(&[
+            "--name",
+            "n",
+            "--rpc-external",
+            "--rpc-methods",
+            "unsafe",
+            "--rpc-port",
+            "9955",
+            "--rpc-methods=safe",
+            "--unsafe-rpc-external",
+            "--rpc-methods",
+            "--pruning",
+            "archive",
+            "--ws-external",
+        ]);
+        assert_eq!(
+            strip_rpc_exposure(&a),
+            args(&["--name", "n", "--rpc-port", "9955", "--pruning", "archive"])
+        );
+        let plain = args(&[
+            "--name",
+            "--rpc-external-ish",
+            "--bootnodes",
+            "/ip4/1.2.3.4",
+        ]);
+        assert_eq!(strip_rpc_exposure(&plain), plain);
+    }
+}
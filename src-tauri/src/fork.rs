@@ -0,0 +1,297 @@
+use anyhow::{anyhow, Result};
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+use tauri::AppHandle;
+
+use crate::{
+    events,
+    net::{self, RetryPolicy},
+};
+
+/// A best block that hasn't moved for this long is checked without waiting for the
+/// interval: a node stuck on a dead fork usually stops advancing.
+const STALL: Duration = Duration::from_secs(120);
+/// Never two checks of a chain closer together than this (also the pace of the
+/// confirming checks after a mismatch).
+const MIN_GAP: Duration = Duration::from_secs(60);
+
+/// Dead-fork detection (`{app_data_dir}/fork_detection.json`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ForkSettings {
+    /// Restart the node once a fork is confirmed (otherwise it is only suggested).
+    pub auto_restart: bool,
+    /// Consecutive mismatching checks before it counts as a fork.
+    pub mismatches: u32,
+    /// Check a synced node this often even while it keeps advancing.
+    pub interval_secs: u64,
+}
+
+impl crate::persist::Versioned for ForkSettings {
+    const VERSION: u32 = 1;
+    const MIGRATIONS: &'static [crate::persist::Migration] = &[crate::persist::unchanged];
+}
+
+impl Default for ForkSettings {
+    fn default() -> Self {
+        ForkSettings {
+            auto_restart: false,
+            mismatches: 3,
+            interval_secs: 600,
+        }
+    }
+}
+
+/// What the app does about a confirmed fork.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ForkAction {
+    SuggestRestart,
+    /// `auto_restart` is on; the restart is underway.
+    Restarting,
+    /// Still on a fork after a restart: only Repair gets it back.
+    SuggestRepair,
+}
+
+/// `miner:fork-detected` payload.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ForkDetected {
+    pub chain: String,
+    /// Height both hashes were taken at (the local best block).
+    pub height: u64,
+    pub local_hash: String,
+    pub network_hash: String,
+    /// The bootnode's best block, when the probe has one.
+    pub network_height: Option<u64>,
+    /// Mismatching checks in a row.
+    pub checks: u32,
+    pub action: ForkAction,
+    pub ts: i64,
+}
+
+#[derive(Default)]
+struct Watch {
+    best: Option<u64>,
+    best_since: Option<Instant>,
+    last_check: Option<Instant>,
+    in_flight: bool,
+    mismatches: u32,
+    // announced for the current run of mismatches
+    alerted: bool,
+    // the node was restarted since the fork was announced
+    restarted: bool,
+}
+
+static SETTINGS: crate::persist::Cached<ForkSettings> =
+    crate::persist::Cached::new("fork_detection.json");
+
+lazy_static! {
+    static ref WATCHES: std::sync::Mutex<HashMap<String, Watch>> =
+        std::sync::Mutex::new(HashMap::new());
+}
+
+/// Current settings (read from disk once, then cached).
+pub fn load_settings(app: &AppHandle) -> ForkSettings {
+    SETTINGS.load(app)
+}
+
+pub fn save_settings(app: &AppHandle, s: &ForkSettings) -> Result<()> {
+    if s.mismatches == 0 {
+        return Err(anyhow!("at least 1 mismatching check is needed"));
+    }
+    if s.interval_secs < MIN_GAP.as_secs() {
+        return Err(anyhow!(
+            "the check interval must be at least {} seconds",
+            MIN_GAP.as_secs()
+        ));
+    }
+    SETTINGS.store(app, s)
+}
+
+/// A node for `chain` was started. Mismatches start over; if a fork had been announced,
+/// the next one is "still on a fork after a restart".
+pub fn note_start(chain: &str) {
+    if let Ok(mut watches) = WATCHES.lock() {
+        let w = watches.entry(chain.to_string()).or_default();
+        w.restarted = w.alerted || w.restarted;
+        w.alerted = false;
+        w.mismatches = 0;
+        w.best = None;
+        w.best_since = None;
+    }
+}
+
+/// Every status loop of a running node. Once it reports sync complete, compares its
+/// block hash at its best height with the bootnode's (`bootnode_ws`; None for custom
+/// specs) when the best block has stalled or every `interval_secs`, and after
+/// `mismatches` disagreeing checks in a row announces `miner:fork-detected`.
+pub fn tick(
+    app: &AppHandle,
+    chain: &str,
+    best: Option<u64>,
+    is_syncing: Option<bool>,
+    bootnode_ws: Option<&str>,
+) {
+    let (Some(best), Some(bootnode_ws)) = (best, bootnode_ws) else {
+        return;
+    };
+    if is_syncing != Some(false) {
+        return;
+    }
+    let settings = load_settings(app);
+    let now = Instant::now();
+    {
+        let Ok(mut watches) = WATCHES.lock() else {
+            return;
+        };
+        let w = watches.entry(chain.to_string()).or_default();
+        if w.best != Some(best) {
+            w.best = Some(best);
+            w.best_since = Some(now);
+        }
+        let since_check = w.last_check.map(|t| now.duration_since(t));
+        let stalled = w.best_since.is_some_and(|t| now.duration_since(t) >= STALL);
+        let due = match since_check {
+            None => true,
+            Some(d) if d < MIN_GAP => false,
+            Some(d) => {
+                stalled || w.mismatches > 0 || d >= Duration::from_secs(settings.interval_secs)
+            }
+        };
+        if w.in_flight || !due {
+            return;
+        }
+        w.in_flight = true;
+        w.last_check = Some(now);
+    }
+    let (app, chain, bootnode_ws) = (app.clone(), chain.to_string(), bootnode_ws.to_string());
+    tauri::async_runtime::spawn(async move {
+        check(&app, &chain, best, &bootnode_ws, &settings).await;
+    });
+}
+
+async fn check(app: &AppHandle, chain: &str, height: u64, bootnode_ws: &str, s: &ForkSettings) {
+    let local_url = crate::miner::local_http_url(chain).await;
+    let network_url = http_url(bootnode_ws);
+    let (local, network) = tokio::join!(
+        block_hash(&local_url, height),
+        block_hash(&network_url, height)
+    );
+    let Some((local_hash, network_hash, checks, action)) = verdict(chain, local, network, s) else {
+        return;
+    };
+
+    crate::snapshot::update(chain, |c| c.forks_detected += 1);
+    let network_height = crate::bootnode::stats(chain)
+        .await
+        .and_then(|p| p.last_height);
+    let advice = match action {
+        ForkAction::SuggestRestart => "Restart the node to rejoin the network.",
+        ForkAction::Restarting => "Restarting the node.",
+        ForkAction::SuggestRepair => "It is still there after a restart; run Repair.",
+    };
+    crate::session_log::ui(
+        app,
+        chain,
+        format!(
+            "Node is on a fork: block #{height} is {local_hash} here but {network_hash} on the network ({checks} checks). {advice}"
+        ),
+    );
+    crate::session_log::audit(
+        chain,
+        "fork-detected",
+        &format!("#{height} local {local_hash} network {network_hash}"),
+    );
+    events::emit(
+        app,
+        events::MINER_FORK_DETECTED,
+        &ForkDetected {
+            chain: chain.to_string(),
+            height,
+            local_hash,
+            network_hash,
+            network_height,
+            checks,
+            action,
+            ts: crate::timeutil::now_ms(),
+        },
+    );
+    if action == ForkAction::Restarting {
+        if let Err(e) = crate::miner::restart_for_fork(app, chain).await {
+            eprintln!("fork restart: {e}");
+        }
+    }
+}
+
+// Record one check's result; Some once it confirms a fork that wasn't announced yet.
+fn verdict(
+    chain: &str,
+    local: Result<Option<String>>,
+    network: Result<Option<String>>,
+    s: &ForkSettings,
+) -> Option<(String, String, u32, ForkAction)> {
+    let mut watches = WATCHES.lock().ok()?;
+    let w = watches.entry(chain.to_string()).or_default();
+    w.in_flight = false;
+    // unreachable, or the bootnode isn't at that height yet: no verdict either way
+    let (Ok(Some(local_hash)), Ok(Some(network_hash))) = (local, network) else {
+        return None;
+    };
+    if local_hash.eq_ignore_ascii_case(&network_hash) {
+        w.mismatches = 0;
+        w.alerted = false;
+        w.restarted = false;
+        return None;
+    }
+    w.mismatches += 1;
+    if w.alerted || w.mismatches < s.mismatches {
+        return None;
+    }
+    w.alerted = true;
+    let action = if w.restarted {
+        ForkAction::SuggestRepair
+    } else if s.auto_restart {
+        ForkAction::Restarting
+    } else {
+        ForkAction::SuggestRestart
+    };
+    Some((local_hash, network_hash, w.mismatches, action))
+}
+
+/// The HTTP JSON-RPC endpoint of a node's `ws_url` (substrate serves both on one port).
+pub(crate) fn http_url(ws_url: &str) -> String {
+    ws_url
+        .replacen("wss://", "https://", 1)
+        .replacen("ws://", "http://", 1)
+}
+
+/// `chain_getBlockHash(height)`; Ok(None) when the node doesn't have that block.
+pub(crate) async fn block_hash(url: &str, height: u64) -> Result<Option<String>> {
+    let body = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "chain_getBlockHash",
+        "params": [height]
+    });
+    let val: serde_json::Value =
+        net::with_retry("chain_getBlockHash", RetryPolicy::QUICK, || async {
+            Ok(net::client()
+                .post(url)
+                .json(&body)
+                .send()
+                .await?
+                .error_for_status()?
+                .json()
+                .await?)
+        })
+        .await?;
+    Ok(val
+        .get("result")
+        .and_then(|r| r.as_str())
+        .map(str::to_string))
+}
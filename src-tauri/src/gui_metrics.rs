@@ -0,0 +1,503 @@
+use anyhow::{anyhow, Result};
+use blake2::{Blake2s256, Digest};
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fmt::Write as _,
+    io::{Read, Write},
+    net::{SocketAddr, TcpListener, TcpStream},
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+use tauri::AppHandle;
+
+use crate::events;
+
+/// Path Prometheus scrapes.
+pub const PATH: &str = "/gui-metrics";
+/// The accept loop checks this often whether it was replaced or turned off.
+const POLL: Duration = Duration::from_millis(200);
+const IO_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The `/gui-metrics` endpoint (`{app_data_dir}/gui_metrics.json`). Off by default.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GuiMetricsSettings {
+    pub enabled: bool,
+    pub port: u16,
+    /// Listen on every interface so another machine can scrape; else 127.0.0.1 only.
+    pub bind_all: bool,
+    /// Export `quantus_gui_balance_planck` (the address only as a hash label).
+    pub include_balance: bool,
+}
+
+impl Default for GuiMetricsSettings {
+    fn default() -> Self {
+        GuiMetricsSettings {
+            enabled: false,
+            port: 9621,
+            bind_all: false,
+            include_balance: false,
+        }
+    }
+}
+
+impl crate::persist::Versioned for GuiMetricsSettings {
+    const VERSION: u32 = 1;
+    const MIGRATIONS: &'static [crate::persist::Migration] = &[crate::persist::unchanged];
+}
+
+// Values as of the last event that carried them, by chain.
+#[derive(Default)]
+struct Registry {
+    running: BTreeMap<String, bool>,
+    safe_mode: BTreeMap<String, bool>,
+    breaker_open: BTreeMap<String, bool>,
+    external_up: BTreeMap<String, bool>,
+    restarts: BTreeMap<(String, String), u64>,
+    authored: BTreeMap<String, u64>,
+    // (chain, address hash) -> free balance in planck
+    balance: BTreeMap<(String, String), String>,
+    dropped: BTreeMap<&'static str, u64>,
+}
+
+static SETTINGS: crate::persist::Cached<GuiMetricsSettings> =
+    crate::persist::Cached::new("gui_metrics.json");
+
+lazy_static! {
+    static ref REGISTRY: std::sync::Mutex<Registry> = std::sync::Mutex::new(Registry::default());
+}
+
+// Bumped on every (re)start of the listener; an older accept loop sees it and exits.
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Current settings (read from disk once, then cached).
+pub fn load_settings(app: &AppHandle) -> GuiMetricsSettings {
+    SETTINGS.load(app)
+}
+
+/// Save and apply: the listener is restarted (or stopped) right away.
+pub fn save_settings(app: &AppHandle, s: &GuiMetricsSettings) -> Result<()> {
+    if s.port < 1024 {
+        return Err(anyhow!("the port must be 1024 or higher"));
+    }
+    SETTINGS.store(app, s)?;
+    serve(app)
+}
+
+/// Start the listener if enabled (at startup and after every settings change).
+pub fn serve(app: &AppHandle) -> Result<()> {
+    let s = load_settings(app);
+    let generation = GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    if !s.enabled {
+        return Ok(());
+    }
+    let ip = if s.bind_all {
+        [0, 0, 0, 0]
+    } else {
+        [127, 0, 0, 1]
+    };
+    let addr = SocketAddr::from((ip, s.port));
+    let listener = TcpListener::bind(addr).map_err(|e| anyhow!("gui metrics on {addr}: {e}"))?;
+    listener.set_nonblocking(true)?;
+    let app = app.clone();
+    std::thread::spawn(move || {
+        while GENERATION.load(Ordering::SeqCst) == generation {
+            match listener.accept() {
+                Ok((stream, _)) => {
+                    if let Err(e) = respond(stream, &load_settings(&app)) {
+                        eprintln!("gui metrics: {e}");
+                    }
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => std::thread::sleep(POLL),
+                Err(e) => {
+                    eprintln!("gui metrics: {e}");
+                    std::thread::sleep(POLL);
+                }
+            }
+        }
+    });
+    Ok(())
+}
+
+// One request per connection: GET /gui-metrics, anything else is a 404.
+fn respond(mut stream: TcpStream, s: &GuiMetricsSettings) -> std::io::Result<()> {
+    stream.set_nonblocking(false)?;
+    stream.set_read_timeout(Some(IO_TIMEOUT))?;
+    stream.set_write_timeout(Some(IO_TIMEOUT))?;
+    let mut buf = [0u8; 2048];
+    let n = stream.read(&mut buf)?;
+    let request = String::from_utf8_lossy(&buf[..n]);
+    let target = request
+        .lines()
+        .next()
+        .and_then(|l| l.strip_prefix("GET "))
+        .and_then(|l| l.split_whitespace().next());
+    let (status, body) = match target {
+        Some(t) if t == PATH || t.starts_with(&format!("{PATH}?")) => ("200 OK", render(s)),
+        _ => (
+            "404 Not Found",
+            format!("not found; metrics are at {PATH}\n"),
+        ),
+    };
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\nContent-Type: text/plain; version=0.0.4; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )
+}
+
+fn str_of<'a>(v: &'a serde_json::Value, key: &str) -> Option<&'a str> {
+    v.get(key).and_then(|v| v.as_str())
+}
+
+// First 8 bytes of blake2s(address): tells rigs apart without publishing the address.
+fn address_hash(address: &str) -> String {
+    hex::encode(&Blake2s256::digest(address.as_bytes())[..8])
+}
+
+/// Every emitted event passes through here (`events::emit`) before it is sent, so the
+/// gauges move with exactly what the UI is told.
+pub fn observe(event: &'static str, payload: &serde_json::Value) {
+    let Ok(mut r) = REGISTRY.lock() else {
+        return;
+    };
+    let chain = str_of(payload, "chain").unwrap_or("").to_string();
+    match event {
+        events::MINER_STATE => {
+            if let Some(running) = payload.get("running").and_then(|v| v.as_bool()) {
+                r.running.insert(chain, running);
+            }
+        }
+        events::MINER_STATUS => {
+            if let Some(on) = payload.get("safeMode").and_then(|v| v.as_bool()) {
+                r.safe_mode.insert(chain, on);
+            }
+        }
+        events::MINER_RESTARTING => {
+            let reason = str_of(payload, "reason").unwrap_or("unknown").to_string();
+            *r.restarts.entry((chain, reason)).or_default() += 1;
+        }
+        events::MINER_AUTHORED if str_of(payload, "change") == Some("authored") => {
+            *r.authored.entry(chain).or_default() += 1;
+        }
+        events::MINER_EXTERNAL_STATUS => {
+            if let Some(up) = payload.get("healthy").and_then(|v| v.as_bool()) {
+                r.external_up.insert(chain, up);
+            }
+        }
+        events::MINER_BALANCE => {
+            let free = payload.pointer("/balance/free").and_then(|v| v.as_str());
+            if let (Some(address), Some(free)) = (str_of(payload, "address"), free) {
+                r.balance
+                    .insert((chain, address_hash(address)), free.to_string());
+            }
+        }
+        _ => {}
+    }
+}
+
+/// An event that wasn't delivered: "verbosity" (filtered by `set_event_verbosity`) or
+/// "emit_error".
+pub fn note_dropped(reason: &'static str) {
+    if let Ok(mut r) = REGISTRY.lock() {
+        *r.dropped.entry(reason).or_default() += 1;
+    }
+}
+
+/// The adaptive safe-mode breaker of `chain` was checked (`adaptive::breaker_open`).
+pub fn set_breaker(chain: &str, open: bool) {
+    if let Ok(mut r) = REGISTRY.lock() {
+        r.breaker_open.insert(chain.to_string(), open);
+    }
+}
+
+// `# HELP`/`# TYPE` then one sample per label set.
+fn family<K>(
+    out: &mut String,
+    name: &str,
+    kind: &str,
+    help: &str,
+    samples: impl Iterator<Item = (K, String)>,
+    labels: impl Fn(&K) -> String,
+) {
+    let _ = writeln!(out, "# HELP {name} {help}");
+    let _ = writeln!(out, "# TYPE {name} {kind}");
+    for (k, v) in samples {
+        let _ = writeln!(out, "{name}{{{}}} {v}", labels(&k));
+    }
+}
+
+fn escape(v: &str) -> String {
+    v.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn chain_label(c: &str) -> String {
+    format!("chain=\"{}\"", escape(c))
+}
+
+fn flag(b: &bool) -> String {
+    u8::from(*b).to_string()
+}
+
+/// The registry in Prometheus text exposition format (0.0.4).
+pub fn render(s: &GuiMetricsSettings) -> String {
+    let Ok(r) = REGISTRY.lock() else {
+        return String::new();
+    };
+    let mut out = String::new();
+    family(
+        &mut out,
+        "quantus_gui_miner_running",
+        "gauge",
+        "Whether the chain's node is running (1) or not (0).",
+        r.running.iter().map(|(c, v)| (c, flag(v))),
+        |c| chain_label(c),
+    );
+    family(
+        &mut out,
+        "quantus_gui_safe_mode_active",
+        "gauge",
+        "Whether the node runs in safe sync mode.",
+        r.safe_mode.iter().map(|(c, v)| (c, flag(v))),
+        |c| chain_label(c),
+    );
+    family(
+        &mut out,
+        "quantus_gui_circuit_breaker_open",
+        "gauge",
+        "Whether automatic safe-mode restarts are suspended after too many.",
+        r.breaker_open.iter().map(|(c, v)| (c, flag(v))),
+        |c| chain_label(c),
+    );
+    family(
+        &mut out,
+        "quantus_gui_external_miner_up",
+        "gauge",
+        "Whether the external miner answered its last health check.",
+        r.external_up.iter().map(|(c, v)| (c, flag(v))),
+        |c| chain_label(c),
+    );
+    family(
+        &mut out,
+        "quantus_gui_restarts_total",
+        "counter",
+        "Node restarts since the GUI started, by reason.",
+        r.restarts.iter().map(|(k, v)| (k, v.to_string())),
+        |(c, reason)| format!("{},reason=\"{}\"", chain_label(c), escape(reason)),
+    );
+    family(
+        &mut out,
+        "quantus_gui_blocks_authored_total",
+        "counter",
+        "Blocks authored since the GUI started.",
+        r.authored.iter().map(|(c, v)| (c, v.to_string())),
+        |c| chain_label(c),
+    );
+    if s.include_balance {
+        family(
+            &mut out,
+            "quantus_gui_balance_planck",
+            "gauge",
+            "Free balance of the rewards account in planck; address is a hash.",
+            r.balance.iter().map(|(k, v)| (k, v.clone())),
+            |(c, hash)| format!("{},address_hash=\"{hash}\"", chain_label(c)),
+        );
+    }
+    family(
+        &mut out,
+        "quantus_gui_event_drop_total",
+        "counter",
+        "Events not delivered to the frontend, by reason.",
+        r.dropped.iter().map(|(k, v)| (k, v.to_string())),
+        |reason| format!("reason=\"{reason}\""),
+    );
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::collections::BTreeSet;
+
+    #[derive(Debug)]
+    struct Sample {
+        name: String,
+        labels: BTreeMap<String, String>,
+        value: f64,
+    }
+
+    fn is_name(s: &str) -> bool {
+        let mut c = s.chars();
+        c.next()
+            .is_some_and(|f| f.is_ascii_alphabetic() || f == '_' || f == ':')
+            && c.all(|x| x.is_ascii_alphanumeric() || x == '_' || x == ':')
+    }
+
+    // `{name="value",...}` with the format's three escapes; returns the rest of the line.
+    fn parse_labels(mut rest: &str) -> Result<(BTreeMap<String, String>, &str), String> {
+        let mut labels = BTreeMap::new();
+        while let Some(after) = rest.strip_prefix(|c| c == '{' || c == ',') {
+            if let Some(end) = after.strip_prefix('}') {
+                return Ok((labels, end));
+            }
+            let (name, quoted) = after.split_once("=\"").ok_or("label without =\"")?;
+            if !is_name(name) || name.starts_with("__") {
+                return Err(format!("bad label name {name:?}"));
+            }
+            let mut value = String::new();
+            let mut chars = quoted.char_indices();
+            rest = loop {
+                match chars.next().ok_or("unterminated label value")? {
+                    (i, '"') => break &quoted[i + 1..],
+                    (_, '\\') => match chars.next().map(|(_, c)| c) {
+                        Some('\\') => value.push('\\'),
+                        Some('"') => value.push('"'),
+                        Some('n') => value.push('\n'),
+                        other => return Err(format!("bad escape {other:?}")),
+                    },
+                    (_, '\n') => return Err("raw newline in label value".into()),
+                    (_, c) => value.push(c),
+                }
+            };
+            if labels.insert(name.to_string(), value).is_some() {
+                return Err(format!("label {name} repeated"));
+            }
+            if let Some(end) = rest.strip_prefix('}') {
+                return Ok((labels, end));
+            }
+        }
+        Err("labels not closed".into())
+    }
+
+    // A strict reader of the text exposition format (0.0.4) for what `render` emits: each
+    // family has one HELP and one TYPE before its samples, every sample belongs to a typed
+    // family, label values are properly escaped, values are floats.
+    fn parse(text: &str) -> Result<Vec<Sample>, String> {
+        if !text.is_empty() && !text.ends_with('\n') {
+            return Err("no trailing newline".into());
+        }
+        let (mut helped, mut typed) = (BTreeSet::new(), BTreeSet::new());
+        let mut samples = Vec::new();
+        for line in text.lines() {
+            if let Some(rest) = line.strip_prefix("# HELP ") {
+                let (name, _) = rest.split_once(' ').ok_or("HELP without text")?;
+                if !is_name(name) || !helped.insert(name.to_string()) {
+                    return Err(format!("bad or repeated HELP {name:?}"));
+                }
+            } else if let Some(rest) = line.strip_prefix("# TYPE ") {
+                let (name, kind) = rest.split_once(' ').ok_or("TYPE without a type")?;
+                let known = ["counter", "gauge", "histogram", "summary", "untyped"];
+                if !known.contains(&kind) || samples.iter().any(|s: &Sample| s.name == name) {
+                    return Err(format!("bad TYPE line {line:?}"));
+                }
+                if !typed.insert(name.to_string()) {
+                    return Err(format!("TYPE {name} repeated"));
+                }
+            } else {
+                let end = line.find(['{', ' ']).ok_or("sample without a value")?;
+                let name = &line[..end];
+                if !is_name(name) || !typed.contains(name) {
+                    return Err(format!("sample of untyped metric {name:?}"));
+                }
+                let (labels, rest) = match &line[end..] {
+                    r if r.starts_with('{') => parse_labels(r)?,
+                    r => (BTreeMap::new(), r),
+                };
+                let value = rest
+                    .strip_prefix(' ')
+                    .and_then(|v| v.parse::<f64>().ok())
+                    .ok_or(format!("bad value in {line:?}"))?;
+                samples.push(Sample {
+                    name: name.to_string(),
+                    labels,
+                    value,
+                });
+            }
+        }
+        Ok(samples)
+    }
+
+    #[test]
+    fn exposition_output_parses() {
+        // a chain name and a reason that need escaping in label values
+        let chain = "expo\\\"test\"\nchain";
+        let address = "qzpFUhjXpZnRCbNr9LW5sAkWmTus52Ffcf31MBpig82u1JArX";
+        observe(
+            events::MINER_STATE,
+            &json!({ "chain": chain, "running": true }),
+        );
+        observe(
+            events::MINER_STATUS,
+            &json!({ "chain": chain, "safeMode": false }),
+        );
+        observe(
+            events::MINER_RESTARTING,
+            &json!({ "chain": chain, "reason": "safe-mode \"auto\"" }),
+        );
+        observe(
+            events::MINER_AUTHORED,
+            &json!({ "chain": chain, "change": "authored" }),
+        );
+        observe(
+            events::MINER_BALANCE,
+            &json!({ "chain": chain, "address": address, "balance": { "free": "1250000000000" } }),
+        );
+        set_breaker(chain, true);
+        note_dropped("verbosity");
+
+        let text = render(&GuiMetricsSettings {
+            include_balance: true,
+            ..Default::default()
+        });
+        let samples = parse(&text).unwrap_or_else(|e| panic!("{e}\n{text}"));
+        let of = |name: &str| {
+            samples
+                .iter()
+                .find(|s| {
+                    s.name == name && s.labels.get("chain").map(String::as_str) == Some(chain)
+                })
+                .unwrap_or_else(|| panic!("no {name} sample for the test chain"))
+        };
+        assert_eq!(of("quantus_gui_miner_running").value, 1.0);
+        assert_eq!(of("quantus_gui_safe_mode_active").value, 0.0);
+        assert_eq!(of("quantus_gui_circuit_breaker_open").value, 1.0);
+        assert_eq!(of("quantus_gui_blocks_authored_total").value, 1.0);
+        let restarts = of("quantus_gui_restarts_total");
+        assert_eq!(restarts.value, 1.0);
+        assert_eq!(restarts.labels["reason"], "safe-mode \"auto\"");
+        let balance = of("quantus_gui_balance_planck");
+        assert_eq!(balance.value, 1.25e12);
+        assert_eq!(balance.labels["address_hash"], address_hash(address));
+        assert!(samples
+            .iter()
+            .any(|s| s.name == "quantus_gui_event_drop_total"
+                && s.labels.get("reason").map(String::as_str) == Some("verbosity")
+                && s.value >= 1.0));
+    }
+
+    #[test]
+    fn balance_is_left_out_unless_enabled() {
+        let text = render(&GuiMetricsSettings::default());
+        parse(&text).unwrap_or_else(|e| panic!("{e}\n{text}"));
+        assert!(!text.contains("quantus_gui_balance_planck"));
+    }
+
+    #[test]
+    fn parse_rejects_broken_output() {
+        for bad in [
+            "quantus_gui_miner_running{chain=\"a\"} 1\n",
+            "# TYPE m gauge\nm{chain=\"a} 1\n",
+            "# TYPE m gauge\nm{chain=\"a\\q\"} 1\n",
+            "# TYPE m gauge\nm{chain=\"a\"} one\n",
+            "# TYPE m gauge\nm 1\n# TYPE m gauge\n",
+            "# TYPE m gauge\nm 1",
+        ] {
+            assert!(parse(bad).is_err(), "{bad:?} parsed");
+        }
+    }
+}
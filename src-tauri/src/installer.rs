@@ -1,61 +1,802 @@
 use anyhow::{anyhow, Result};
+use lazy_static::lazy_static;
 use std::{
     fs,
     path::{Path, PathBuf},
     process::Stdio,
+    time::{Duration, Instant},
 };
+use tauri::AppHandle;
 use tokio::process::Command;
 
+use crate::events;
+use crate::net::{self, RetryPolicy};
+use crate::ops::{CancelToken, Cancelled};
+
+/// Portable mode, or no home directory (LOCALAPPDATA on Windows): `{paths::base}/bin`.
 pub fn user_bin_dir() -> Result<PathBuf> {
     #[cfg(target_os = "linux")]
-    {
-        let d = dirs::home_dir()
-            .ok_or_else(|| anyhow!("no home"))?
-            .join(".local/bin");
-        fs::create_dir_all(&d)?;
-        Ok(d)
-    }
+    let d = crate::paths::home_dir().map(|h| h.join(".local/bin"));
     #[cfg(target_os = "macos")]
+    let d = crate::paths::home_dir().map(|h| h.join("bin"));
+    #[cfg(target_os = "windows")]
+    let d = std::env::var_os("LOCALAPPDATA")
+        .filter(|_| !crate::paths::is_portable())
+        .map(|base| {
+            PathBuf::from(base)
+                .join("Programs")
+                .join("Quantus")
+                .join("bin")
+        });
+    ensure_dir(d.unwrap_or_else(|| crate::paths::base().join("bin")))
+}
+
+/// `binary_path` value meaning "the quantus-node in the configured install dir".
+pub const DEFAULT_BINARY: &str = "default";
+
+/// How often `installer:progress` is sent while downloading.
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(500);
+
+lazy_static! {
+    // User-chosen install directory (None: the per-OS default from `user_bin_dir`).
+    static ref INSTALL_DIR: std::sync::Mutex<Option<PathBuf>> = std::sync::Mutex::new(None);
+    // GitHub's rate limit reset (unix secs) once we hit it; release fetches fail fast until then.
+    static ref RATE_LIMITED_UNTIL: std::sync::Mutex<Option<i64>> = std::sync::Mutex::new(None);
+}
+
+/// Sends `installer:progress` for one component ("node" | "miner"), so installs running
+/// side by side (`ensure_all_binaries`) can be told apart.
+pub struct InstallProgress {
+    /// None in tests: nothing is sent.
+    app: Option<AppHandle>,
+    component: &'static str,
+    /// Checked per downloaded chunk and between steps.
+    cancel: Option<CancelToken>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ProgressMsg<'a> {
+    component: &'static str,
+    /// "fetching_release" | "downloading" | "installing" | "installed" | "failed"
+    stage: &'a str,
+    downloaded: u64,
+    total: u64,
+    path: Option<String>,
+    error: Option<String>,
+}
+
+impl InstallProgress {
+    pub fn new(app: &AppHandle, component: &'static str, cancel: Option<CancelToken>) -> Self {
+        Self {
+            app: Some(app.clone()),
+            component,
+            cancel,
+        }
+    }
+
+    #[cfg(test)]
+    fn detached(component: &'static str, cancel: Option<CancelToken>) -> Self {
+        Self {
+            app: None,
+            component,
+            cancel,
+        }
+    }
+
+    fn check_cancelled(&self) -> Result<(), Cancelled> {
+        self.cancel.as_ref().map_or(Ok(()), CancelToken::check)
+    }
+
+    fn emit(&self, stage: &str, downloaded: u64, total: u64) {
+        let Some(app) = &self.app else {
+            return;
+        };
+        events::emit(
+            app,
+            events::INSTALLER_PROGRESS,
+            &ProgressMsg {
+                component: self.component,
+                stage,
+                downloaded,
+                total,
+                path: None,
+                error: None,
+            },
+        );
+    }
+
+    fn finish(&self, res: &Result<PathBuf>) {
+        let Some(app) = &self.app else {
+            return;
+        };
+        let (stage, path, error) = match res {
+            Ok(p) => ("installed", Some(p.to_string_lossy().to_string()), None),
+            Err(e) => ("failed", None, Some(e.to_string())),
+        };
+        events::emit(
+            app,
+            events::INSTALLER_PROGRESS,
+            &ProgressMsg {
+                component: self.component,
+                stage,
+                downloaded: 0,
+                total: 0,
+                path,
+                error,
+            },
+        );
+    }
+}
+
+#[derive(serde::Serialize, Deserialize, Default)]
+struct InstallDirFile {
+    install_dir: Option<String>,
+}
+
+fn install_dir_config_path(app: &AppHandle) -> PathBuf {
+    crate::paths::app_data_dir(app).join("install_dir.json")
+}
+
+/// Load the persisted install directory (called once at startup).
+pub fn load_install_dir(app: &AppHandle) {
+    let dir = fs::read(install_dir_config_path(app))
+        .ok()
+        .and_then(|b| serde_json::from_slice::<InstallDirFile>(&b).ok())
+        .and_then(|f| f.install_dir)
+        .map(PathBuf::from);
+    if let Ok(mut cur) = INSTALL_DIR.lock() {
+        *cur = dir;
+    }
+}
+
+/// Where binaries live: the configured directory, else the per-OS default.
+pub fn install_dir() -> Result<PathBuf> {
+    let configured = INSTALL_DIR.lock().ok().and_then(|d| d.clone());
+    match configured {
+        Some(d) => ensure_dir(d),
+        None => user_bin_dir(),
+    }
+}
+
+/// Path of quantus-node inside the install directory (installed or not).
+pub fn node_binary_path() -> Result<PathBuf> {
+    Ok(install_dir()?.join(exe_name()))
+}
+
+/// Path of the external miner inside the install directory (installed or not).
+pub fn miner_binary_path() -> Result<PathBuf> {
+    Ok(install_dir()?.join(miner_exe_name()))
+}
+
+/// Resolve a `binary_path` from the frontend; empty or `DEFAULT_BINARY` means the installed node.
+pub fn resolve_binary_path(p: &str) -> Result<String> {
+    let p = p.trim();
+    if p.is_empty() || p == DEFAULT_BINARY {
+        return Ok(node_binary_path()?.to_string_lossy().to_string());
+    }
+    Ok(p.to_string())
+}
+
+/// The node binary to spawn for a `binary_path` from the frontend, which may be stale
+/// (deleted or moved since `ensure_miner_and_account`). Falls back to the node in the
+/// install dir and reinstalls it if that is missing too, with `setup:progress` events
+/// (`{ stage: "reinstalling" | "installed", path }`); with `allow_download` off that is
+/// a `BinaryMissing` error instead.
+pub async fn ensure_node_binary(
+    app: &AppHandle,
+    requested: &str,
+    allow_download: bool,
+) -> Result<PathBuf> {
+    let requested = PathBuf::from(resolve_binary_path(requested)?);
+    if is_runnable(&requested) {
+        return Ok(requested);
+    }
+    let installed = node_binary_path()?;
+    if is_runnable(&installed) {
+        return Ok(installed);
+    }
+    if !allow_download {
+        return Err(InstallError::BinaryMissing { path: requested }.into());
+    }
+    let progress = |stage: &str, path: &Path| {
+        events::emit(
+            app,
+            events::SETUP_PROGRESS,
+            &serde_json::json!({ "stage": stage, "path": path.to_string_lossy() }),
+        );
+    };
+    progress("reinstalling", &installed);
+    let path =
+        ensure_quantus_node_installed(Some(&InstallProgress::new(app, "node", None))).await?;
+    progress("installed", &path);
+    Ok(path)
+}
+
+/// A validated install directory change (see `plan_install_dir`).
+pub struct InstallDirPlan {
+    /// None: back to the default.
+    new_dir: Option<PathBuf>,
+    old: PathBuf,
+    target: PathBuf,
+}
+
+/// Validate an install directory change (None: back to the default) without touching
+/// anything; whether the target is writable is only known when applying.
+pub fn plan_install_dir(dir: Option<String>) -> Result<InstallDirPlan> {
+    let new_dir = match dir.map(|d| d.trim().to_string()).filter(|d| !d.is_empty()) {
+        Some(d) => {
+            let p = PathBuf::from(&d);
+            if !p.is_absolute() {
+                return Err(anyhow!("install directory must be an absolute path: {d}"));
+            }
+            Some(p)
+        }
+        None => None,
+    };
+    let old = install_dir()?;
+    let target = match &new_dir {
+        Some(p) => p.clone(),
+        None => user_bin_dir()?,
+    };
+    Ok(InstallDirPlan {
+        new_dir,
+        old,
+        target,
+    })
+}
+
+impl InstallDirPlan {
+    // (from, to) for each installed binary that would move
+    fn moves(&self) -> Vec<(PathBuf, PathBuf)> {
+        if self.old == self.target {
+            return Vec::new();
+        }
+        [exe_name(), miner_exe_name()]
+            .into_iter()
+            .map(|name| (self.old.join(name), self.target.join(name)))
+            .filter(|(from, _)| is_installed(from))
+            .collect()
+    }
+
+    /// The manifest for `dry_run`.
+    pub async fn manifest(&self) -> crate::plan::Manifest {
+        let mut changes = Vec::new();
+        if !self.target.exists() {
+            changes.push(crate::plan::PlannedChange {
+                action: "create",
+                path: self.target.display().to_string(),
+                to: None,
+                files: 0,
+                bytes: 0,
+            });
+        }
+        for (from, to) in self.moves() {
+            changes.extend(crate::plan::change("move", &from, Some(&to)).await);
+        }
+        crate::plan::Manifest {
+            operation: "set_install_dir",
+            chain: None,
+            changes,
+            restarts_node: false,
+            notes: vec![format!(
+                "{} is checked for write access when the change is applied.",
+                self.target.display()
+            )],
+        }
+    }
+}
+
+/// Apply an install directory change. Existing binaries are copied over, checked with
+/// `--version`, and only then removed from the old place.
+pub async fn set_install_dir(app: &AppHandle, plan: InstallDirPlan) -> Result<PathBuf> {
+    check_writable(&plan.target)?;
+
+    for (from, to) in plan.moves() {
+        install_binary(&from, &to)?;
+        make_executable(&to);
+        if let Err(e) = verify_binary(&to).await {
+            let _ = fs::remove_file(&to);
+            return Err(anyhow!("copied {} fails to run: {e}", to.display()));
+        }
+        let _ = fs::remove_file(&from);
+        // the record goes along; the binary is the same file
+        let (rec_from, rec_to) = (
+            crate::provenance::record_path(&from),
+            crate::provenance::record_path(&to),
+        );
+        if rec_from.exists() && fs::copy(&rec_from, &rec_to).is_ok() {
+            let _ = fs::remove_file(&rec_from);
+        }
+    }
+
+    let path = install_dir_config_path(app);
+    if let Some(parent) = path.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    let file = InstallDirFile {
+        install_dir: plan
+            .new_dir
+            .as_ref()
+            .map(|p| p.to_string_lossy().to_string()),
+    };
+    crate::persist::write_atomic(&path, &serde_json::to_vec_pretty(&file)?)?;
+    if let Ok(mut cur) = INSTALL_DIR.lock() {
+        *cur = plan.new_dir;
+    }
+    Ok(plan.target)
+}
+
+// `<bin> --version` must exit successfully.
+async fn verify_binary(bin: &Path) -> Result<()> {
+    let out = Command::new(bin)
+        .arg("--version")
+        .stdin(Stdio::null())
+        .output()
+        .await?;
+    if !out.status.success() {
+        return Err(anyhow!("--version exited with {}", out.status));
+    }
+    Ok(())
+}
+
+fn make_executable(p: &Path) {
+    #[cfg(unix)]
     {
-        let d = dirs::home_dir()
-            .ok_or_else(|| anyhow!("no home"))?
-            .join("bin");
-        fs::create_dir_all(&d)?;
-        Ok(d)
+        use std::os::unix::fs::PermissionsExt;
+        if let Ok(meta) = fs::metadata(p) {
+            let mut perms = meta.permissions();
+            perms.set_mode(0o755);
+            let _ = fs::set_permissions(p, perms);
+        }
     }
-    #[cfg(target_os = "windows")]
+    #[cfg(not(unix))]
+    let _ = p;
+}
+
+fn ensure_dir(d: PathBuf) -> Result<PathBuf> {
+    match fs::create_dir_all(&d) {
+        Ok(()) => Ok(d),
+        Err(source) => Err(InstallError::DestinationNotWritable { dir: d, source }.into()),
+    }
+}
+
+// A zero-length file is what an interrupted install leaves behind; don't treat it as installed.
+fn is_installed(p: &Path) -> bool {
+    fs::metadata(p).map(|m| m.len() > 0).unwrap_or(false)
+}
+
+// Installed, a regular file and (on unix) executable by someone.
+fn is_runnable(p: &Path) -> bool {
+    if !is_installed(p) {
+        return false;
+    }
+    let Ok(meta) = fs::metadata(p) else {
+        return false;
+    };
+    #[cfg(unix)]
     {
-        let base = std::env::var_os("LOCALAPPDATA")
-            .map(PathBuf::from)
-            .ok_or_else(|| anyhow!("LOCALAPPDATA missing"))?;
-        let d = base.join("Programs").join("Quantus").join("bin");
-        fs::create_dir_all(&d)?;
-        Ok(d)
+        use std::os::unix::fs::PermissionsExt;
+        meta.is_file() && meta.permissions().mode() & 0o111 != 0
     }
+    #[cfg(not(unix))]
+    meta.is_file()
 }
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 #[derive(Deserialize)]
-struct Release {
-    tag_name: String,
-    assets: Vec<Asset>,
+pub(crate) struct Release {
+    pub tag_name: String,
+    pub assets: Vec<Asset>,
+    // release notes (markdown) and page, used for GUI update info
+    #[serde(default)]
+    pub body: Option<String>,
+    #[serde(default)]
+    pub html_url: Option<String>,
 }
 #[derive(Deserialize)]
-struct Asset {
-    name: String,
-    browser_download_url: String,
+pub(crate) struct Asset {
+    pub name: String,
+    pub browser_download_url: String,
+    #[serde(default)]
+    size: u64,
+}
+
+/// Install failures worth telling apart from a bare io error.
+#[derive(Debug, thiserror::Error)]
+pub enum InstallError {
+    #[error("install directory {} is not writable: {source}", .dir.display())]
+    DestinationNotWritable {
+        dir: PathBuf,
+        source: std::io::Error,
+    },
+    #[error(
+        "not enough free space in {}: need ~{} MB, {} MB available",
+        .path.display(),
+        .needed / 1_000_000,
+        .available / 1_000_000
+    )]
+    InsufficientSpace {
+        path: PathBuf,
+        needed: u64,
+        available: u64,
+    },
+    #[error("installed binary {} is missing or empty", .0.display())]
+    IncompleteInstall(PathBuf),
+    #[error("quantus-node not found at {} and automatic download is off", .path.display())]
+    BinaryMissing { path: PathBuf },
+    #[error("the latest {what} release has no asset for this platform ({wanted}); available: {}", .available.join(", "))]
+    NoAssetForPlatform {
+        what: &'static str,
+        wanted: String,
+        available: Vec<String>,
+    },
+    #[error("GitHub API rate limit reached{}", .reset_at.map(|t| format!(" (resets at unix time {t})")).unwrap_or_default())]
+    RateLimited { reset_at: Option<i64> },
+    #[error("couldn't unpack {}: {reason}", .archive.display())]
+    ExtractionFailed { archive: PathBuf, reason: String },
+    #[error("installed binary {} doesn't run: {reason}", .path.display())]
+    VerificationFailed { path: PathBuf, reason: String },
+    /// Antivirus/Gatekeeper removed or blocked the binary. `fixable`: `remove_quarantine`
+    /// can clear it (macOS quarantine xattr); otherwise the user has to follow `remediation`.
+    #[error("{} was blocked by the OS ({detail}). {remediation}", .path.display())]
+    #[cfg_attr(not(any(target_os = "macos", windows)), allow(dead_code))]
+    BinaryQuarantined {
+        path: PathBuf,
+        detail: String,
+        remediation: &'static str,
+        fixable: bool,
+    },
+}
+
+/// Install failure as sent to the UI by `ensure_miner_and_account`. `message` is always
+/// set; `retryable`/`reset_at` say whether a Retry button makes sense.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SetupError {
+    NoAssetForPlatform {
+        message: String,
+        available: Vec<String>,
+        /// Version of a copy we already have (`--version`), so the UI can offer to keep it.
+        installed_version: Option<String>,
+    },
+    Network {
+        message: String,
+        retryable: bool,
+    },
+    RateLimited {
+        message: String,
+        reset_at: Option<i64>,
+    },
+    ExtractionFailed {
+        message: String,
+    },
+    VerificationFailed {
+        message: String,
+    },
+    /// Stopped through `cancel_operation`; nothing was installed.
+    Cancelled {
+        message: String,
+    },
+    Other {
+        message: String,
+    },
+}
+
+impl SetupError {
+    pub async fn from_error(err: &anyhow::Error) -> Self {
+        let message = err.to_string();
+        match err.downcast_ref::<InstallError>() {
+            Some(InstallError::NoAssetForPlatform {
+                what, available, ..
+            }) => SetupError::NoAssetForPlatform {
+                message,
+                available: available.clone(),
+                installed_version: installed_version(what).await,
+            },
+            Some(InstallError::RateLimited { reset_at }) => SetupError::RateLimited {
+                message,
+                reset_at: *reset_at,
+            },
+            Some(InstallError::ExtractionFailed { .. } | InstallError::IncompleteInstall(_)) => {
+                SetupError::ExtractionFailed { message }
+            }
+            Some(InstallError::VerificationFailed { .. }) => {
+                SetupError::VerificationFailed { message }
+            }
+            Some(_) => SetupError::Other { message },
+            None if crate::ops::is_cancelled(err) => SetupError::Cancelled { message },
+            None if net::is_network_error(err) => SetupError::Network {
+                retryable: net::is_retryable(err),
+                message,
+            },
+            None => SetupError::Other { message },
+        }
+    }
+
+    pub fn other(message: impl Into<String>) -> Self {
+        SetupError::Other {
+            message: message.into(),
+        }
+    }
+}
+
+// `--version` of an installed copy of `what` (install dir first, then the default dir).
+async fn installed_version(what: &str) -> Option<String> {
+    let name = if what == "quantus-miner" {
+        miner_exe_name()
+    } else {
+        exe_name()
+    };
+    let candidates = [install_dir().ok(), user_bin_dir().ok()];
+    for dir in candidates.into_iter().flatten() {
+        let bin = dir.join(name);
+        if !is_installed(&bin) {
+            continue;
+        }
+        if let Some(v) = binary_version(&bin).await {
+            return Some(v);
+        }
+    }
+    None
+}
+
+/// What `bin --version` prints; None if it isn't there or fails.
+pub async fn binary_version(bin: &Path) -> Option<String> {
+    let out = Command::new(bin)
+        .arg("--version")
+        .stdin(Stdio::null())
+        .output()
+        .await
+        .ok()?;
+    let v = String::from_utf8_lossy(&out.stdout).trim().to_string();
+    (out.status.success() && !v.is_empty()).then_some(v)
+}
+
+#[cfg(target_os = "macos")]
+const QUARANTINE_XATTR: &str = "com.apple.quarantine";
+
+#[cfg(target_os = "macos")]
+const MACOS_QUARANTINE_FIX: &str =
+    "Use \"Unquarantine and retry\", or run `xattr -d com.apple.quarantine <path>`.";
+#[cfg(target_os = "macos")]
+const MACOS_GATEKEEPER_FIX: &str = "macOS refused to run a binary from an unidentified developer: \
+     open System Settings → Privacy & Security and choose \"Open Anyway\", then start again.";
+#[cfg(windows)]
+const WINDOWS_AV_FIX: &str = "Windows Security (or another antivirus) quarantined it: restore it \
+     from Protection history or add an exclusion for the install directory, then start again \
+     (a missing binary is downloaded again).";
+#[cfg(windows)]
+const WINDOWS_POLICY_FIX: &str =
+    "A system policy (SmartScreen/AppLocker) blocks it: allow the binary or ask your administrator.";
+
+// Windows error codes that mean "blocked", not "broken".
+#[cfg(windows)]
+const ERROR_ACCESS_DENIED: i32 = 5;
+#[cfg(windows)]
+const ERROR_VIRUS_INFECTED: i32 = 225;
+#[cfg(windows)]
+const ERROR_VIRUS_DELETED: i32 = 226;
+#[cfg(windows)]
+const ERROR_ACCESS_DISABLED_BY_POLICY: i32 = 1260;
+
+/// Whether macOS has flagged `p` as downloaded from the internet.
+#[cfg(target_os = "macos")]
+fn has_quarantine_xattr(p: &Path) -> bool {
+    std::process::Command::new("xattr")
+        .arg("-p")
+        .arg(QUARANTINE_XATTR)
+        .arg(p)
+        .output()
+        .map(|o| o.status.success())
+        .unwrap_or(false)
+}
+
+/// Clear the macOS quarantine xattr from `p` (no-op elsewhere or when not set).
+pub fn remove_quarantine(p: &Path) -> Result<()> {
+    #[cfg(target_os = "macos")]
+    {
+        if !has_quarantine_xattr(p) {
+            return Ok(());
+        }
+        let out = std::process::Command::new("xattr")
+            .arg("-d")
+            .arg(QUARANTINE_XATTR)
+            .arg(p)
+            .output()?;
+        if !out.status.success() {
+            return Err(anyhow!(
+                "xattr -d {QUARANTINE_XATTR} {}: {}",
+                p.display(),
+                String::from_utf8_lossy(&out.stderr).trim()
+            ));
+        }
+        eprintln!("installer: removed {QUARANTINE_XATTR} from {}", p.display());
+    }
+    #[cfg(not(target_os = "macos"))]
+    let _ = p;
+    Ok(())
+}
+
+/// Right after install: clear quarantine on macOS; on Windows fail if antivirus already
+/// took the file away.
+fn check_not_quarantined(p: &Path) -> Result<()> {
+    #[cfg(windows)]
+    if !p.exists() {
+        return Err(InstallError::BinaryQuarantined {
+            path: p.to_path_buf(),
+            detail: "removed right after install".into(),
+            remediation: WINDOWS_AV_FIX,
+            fixable: false,
+        }
+        .into());
+    }
+    remove_quarantine(p)
+}
+
+/// Turn a failed spawn of `bin` into `BinaryQuarantined` when the OS blocked it,
+/// else a plain "spawn {what}" error. Logs the raw OS error code either way.
+pub fn diagnose_spawn_error(what: &str, bin: &Path, err: std::io::Error) -> anyhow::Error {
+    let code = err.raw_os_error();
+    eprintln!(
+        "spawn {what}: {} failed: {err} (os error code {})",
+        bin.display(),
+        code.map_or_else(|| "none".to_string(), |c| c.to_string())
+    );
+    #[cfg(any(target_os = "macos", windows))]
+    let quarantined = |detail: String, remediation: &'static str, fixable: bool| {
+        anyhow::Error::from(InstallError::BinaryQuarantined {
+            path: bin.to_path_buf(),
+            detail,
+            remediation,
+            fixable,
+        })
+    };
+    #[cfg(target_os = "macos")]
+    {
+        if has_quarantine_xattr(bin) {
+            return quarantined(
+                format!("{QUARANTINE_XATTR} is set"),
+                MACOS_QUARANTINE_FIX,
+                true,
+            );
+        }
+        if code == Some(nix::libc::EPERM) || code == Some(nix::libc::EACCES) {
+            return quarantined(err.to_string(), MACOS_GATEKEEPER_FIX, false);
+        }
+    }
+    #[cfg(windows)]
+    {
+        if !bin.exists() {
+            return quarantined("binary is missing".into(), WINDOWS_AV_FIX, false);
+        }
+        match code {
+            Some(ERROR_VIRUS_INFECTED | ERROR_VIRUS_DELETED | ERROR_ACCESS_DENIED) => {
+                return quarantined(err.to_string(), WINDOWS_AV_FIX, false);
+            }
+            Some(ERROR_ACCESS_DISABLED_BY_POLICY) => {
+                return quarantined(err.to_string(), WINDOWS_POLICY_FIX, false);
+            }
+            _ => {}
+        }
+    }
+    anyhow!("spawn {what}: {err}")
+}
+
+// Archives unpack to a few times their size; used to estimate space needs up front.
+const UNPACK_FACTOR: u64 = 4;
+
+/// Create/delete a probe file so a read-only dir fails before we download anything.
+fn check_writable(dir: &Path) -> Result<(), InstallError> {
+    let probe = dir.join(".quantus-write-probe");
+    fs::create_dir_all(dir)
+        .and_then(|_| fs::write(&probe, b""))
+        .and_then(|_| fs::remove_file(&probe))
+        .map_err(|source| InstallError::DestinationNotWritable {
+            dir: dir.to_path_buf(),
+            source,
+        })
+}
+
+/// Free bytes available to us on the volume holding `path` (None if unknown).
+pub(crate) fn free_space(path: &Path) -> Option<u64> {
+    #[cfg(unix)]
+    {
+        let s = nix::sys::statvfs::statvfs(path).ok()?;
+        Some(s.blocks_available() as u64 * s.fragment_size() as u64)
+    }
+    #[cfg(windows)]
+    {
+        use std::os::windows::ffi::OsStrExt;
+        let wide: Vec<u16> = path.as_os_str().encode_wide().chain(Some(0)).collect();
+        let mut avail: u64 = 0;
+        // SAFETY: `wide` is NUL-terminated and the out pointers are valid or null.
+        let ok = unsafe {
+            windows_sys::Win32::Storage::FileSystem::GetDiskFreeSpaceExW(
+                wide.as_ptr(),
+                &mut avail,
+                std::ptr::null_mut(),
+                std::ptr::null_mut(),
+            )
+        };
+        (ok != 0).then_some(avail)
+    }
+    #[cfg(not(any(unix, windows)))]
+    {
+        let _ = path;
+        None
+    }
+}
+
+fn check_space(path: &Path, needed: u64) -> Result<(), InstallError> {
+    match free_space(path) {
+        Some(available) if available < needed => Err(InstallError::InsufficientSpace {
+            path: path.to_path_buf(),
+            needed,
+            available,
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// Fail early (before downloading) if `bin_dir` is read-only or a volume is too full.
+/// `unpacked` is the expected size in the destination and in the temp dir; the download
+/// itself goes to the download cache.
+fn preflight(bin_dir: &Path, tmp_dir: &Path, asset: &Asset, unpacked: u64) -> Result<()> {
+    check_writable(bin_dir)?;
+    let cache = download_cache_dir()?;
+    let cached = cache_paths(&cache, asset)
+        .1
+        .metadata()
+        .map(|m| m.len())
+        .unwrap_or(0);
+    check_space(&cache, asset.size.saturating_sub(cached))?;
+    check_space(tmp_dir, unpacked)?;
+    check_space(bin_dir, unpacked)?;
+    Ok(())
+}
+
+// Note where `dest` came from; an install without a record still works (it just shows
+// as user-provided).
+async fn record_provenance(dest: &Path, rel: &Release, asset: &Asset) {
+    let res = crate::provenance::record(
+        dest,
+        &asset.browser_download_url,
+        &rel.tag_name,
+        &asset.name,
+    )
+    .await;
+    if let Err(e) = res {
+        eprintln!("provenance {}: {e}", dest.display());
+    }
+}
+
+/// Copy a verified binary into place via a temp name so `dest` is never left truncated.
+fn install_binary(src: &Path, dest: &Path) -> Result<()> {
+    if fs::metadata(src).map(|m| m.len() == 0).unwrap_or(true) {
+        return Err(InstallError::IncompleteInstall(src.to_path_buf()).into());
+    }
+    let partial = dest.with_extension("partial");
+    if let Err(e) = fs::copy(src, &partial).and_then(|_| fs::rename(&partial, dest)) {
+        let _ = fs::remove_file(&partial);
+        return Err(e.into());
+    }
+    Ok(())
 }
 
 #[derive(Clone, Debug)]
 pub struct ExternalMinerConfig {
     pub num_cores: usize,
     pub port: u16,
+    /// Appended after `--num-cores`/`--port` (e.g. the crash-loop skip flag).
+    pub extra_args: Vec<String>,
 }
 
 #[derive(Debug)]
 pub struct ExternalMinerHandle {
     pub port: u16,
+    pub num_cores: usize,
     pub child: tokio::process::Child,
 }
 
@@ -99,23 +840,212 @@ fn target() -> Target {
     }
 }
 
-pub async fn ensure_quantus_node_installed() -> Result<PathBuf> {
-    let bin_dir = user_bin_dir()?;
+/// Latest GitHub release of `repo` ("owner/name"), retried on transient failures.
+/// Once GitHub reported the rate limit, later calls fail without a request until it resets.
+pub(crate) async fn fetch_latest_release(repo: &str) -> Result<Release> {
+    let limited = RATE_LIMITED_UNTIL.lock().ok().and_then(|r| *r);
+    if let Some(reset_at) = limited.filter(|t| *t > crate::timeutil::now_secs()) {
+        return Err(InstallError::RateLimited {
+            reset_at: Some(reset_at),
+        }
+        .into());
+    }
+    let url = format!("https://api.github.com/repos/{repo}/releases/latest");
+    net::with_retry(&format!("GET {url}"), RetryPolicy::GITHUB, || async {
+        let resp = net::client().get(&url).send().await?;
+        // not retried: waiting seconds won't help with an hourly quota
+        if let Some(reset_at) = rate_limit_reset(&resp) {
+            if let Ok(mut r) = RATE_LIMITED_UNTIL.lock() {
+                *r = reset_at;
+            }
+            return Err(InstallError::RateLimited { reset_at }.into());
+        }
+        let rel: Release = resp.error_for_status()?.json().await?;
+        Ok(rel)
+    })
+    .await
+}
+
+// Some(reset time) when GitHub refused the request for exceeding the API rate limit.
+fn rate_limit_reset(resp: &reqwest::Response) -> Option<Option<i64>> {
+    let status = resp.status();
+    let header = |name: &str| {
+        resp.headers()
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string)
+    };
+    let exhausted = header("x-ratelimit-remaining").as_deref() == Some("0");
+    let limited = status == reqwest::StatusCode::TOO_MANY_REQUESTS
+        || (status == reqwest::StatusCode::FORBIDDEN && exhausted);
+    limited.then(|| header("x-ratelimit-reset").and_then(|v| v.parse().ok()))
+}
+
+/// Downloads are kept this long in the cache (complete or partial), then removed.
+const CACHE_MAX_AGE: Duration = Duration::from_secs(7 * 24 * 3600);
+
+/// Release downloads: `{cache_dir}/quantus-miner/downloads`, so one interrupted by the
+/// app closing resumes on the next launch.
+fn download_cache_dir() -> Result<PathBuf> {
+    let d = crate::paths::cache_dir()
+        .join("quantus-miner")
+        .join("downloads");
+    fs::create_dir_all(&d)?;
+    Ok(d)
+}
+
+// Remove cache entries not touched for `CACHE_MAX_AGE`.
+fn prune_download_cache(dir: &Path) {
+    for e in fs::read_dir(dir).into_iter().flatten().flatten() {
+        let old = e
+            .metadata()
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|t| t.elapsed().ok())
+            .is_some_and(|age| age > CACHE_MAX_AGE);
+        if old {
+            let _ = fs::remove_file(e.path());
+        }
+    }
+}
+
+// Cache file of `asset`, keyed by name and expected size (a re-uploaded asset of
+// another size is another entry); `.part` while incomplete.
+fn cache_paths(dir: &Path, asset: &Asset) -> (PathBuf, PathBuf) {
+    let done = dir.join(format!("{}-{}", asset.size, asset.name));
+    let mut part = done.clone().into_os_string();
+    part.push(".part");
+    (done, PathBuf::from(part))
+}
+
+/// `asset` in the download cache, downloading what's missing. A partial file from an
+/// earlier attempt or app session is resumed with a Range request; a server that ignores
+/// the range gets the file from the start. A cancelled download stops at the next chunk
+/// and deletes its partial file; any other failure keeps it for the next attempt.
+async fn download_cached(asset: &Asset, progress: Option<&InstallProgress>) -> Result<PathBuf> {
+    let dir = download_cache_dir()?;
+    prune_download_cache(&dir);
+    download_into(&dir, asset, progress).await
+}
+
+// `download_cached` for the cache directory `dir`.
+async fn download_into(
+    dir: &Path,
+    asset: &Asset,
+    progress: Option<&InstallProgress>,
+) -> Result<PathBuf> {
+    let (done, part) = cache_paths(dir, asset);
+    if fs::metadata(&done).is_ok_and(|m| m.len() == asset.size) {
+        return Ok(done);
+    }
+    let res = download_to(&asset.browser_download_url, &part, asset.size, progress).await;
+    match res {
+        Ok(()) => {
+            fs::rename(&part, &done)?;
+            Ok(done)
+        }
+        Err(e) => {
+            if crate::ops::is_cancelled(&e) {
+                let _ = fs::remove_file(&part);
+            }
+            Err(e)
+        }
+    }
+}
+
+/// Download `url` (`total` bytes, as the release lists it; 0 if unknown) into `dest`,
+/// continuing from what `dest` already holds when the server honours a Range request.
+async fn download_to(
+    url: &str,
+    dest: &Path,
+    total: u64,
+    progress: Option<&InstallProgress>,
+) -> Result<()> {
+    use tokio::io::AsyncWriteExt;
+    net::with_retry(
+        &format!("download {url}"),
+        RetryPolicy::DOWNLOAD,
+        || async {
+            if let Some(p) = progress {
+                p.check_cancelled()?;
+            }
+            let mut have = fs::metadata(dest).map(|m| m.len()).unwrap_or(0);
+            if total > 0 && have > total {
+                fs::remove_file(dest)?;
+                have = 0;
+            }
+            if total > 0 && have == total {
+                return Ok(());
+            }
+            let mut req = net::client().get(url);
+            if have > 0 {
+                req = req.header(reqwest::header::RANGE, format!("bytes={have}-"));
+            }
+            let mut resp = req.send().await?;
+            // the partial file doesn't match what the server has: start over
+            if resp.status() == reqwest::StatusCode::RANGE_NOT_SATISFIABLE {
+                fs::remove_file(dest)?;
+                have = 0;
+                resp = net::client().get(url).send().await?;
+            }
+            let mut resp = resp.error_for_status()?;
+            let resumed = have > 0 && resp.status() == reqwest::StatusCode::PARTIAL_CONTENT;
+            let mut file = if resumed {
+                tokio::fs::OpenOptions::new()
+                    .append(true)
+                    .open(dest)
+                    .await?
+            } else {
+                tokio::fs::File::create(dest).await?
+            };
+            let (mut downloaded, mut last) = (if resumed { have } else { 0 }, Instant::now());
+            if let Some(p) = progress {
+                p.emit("downloading", downloaded, total);
+            }
+            while let Some(chunk) = resp.chunk().await? {
+                if let Some(p) = progress {
+                    p.check_cancelled()?;
+                }
+                file.write_all(&chunk).await?;
+                downloaded += chunk.len() as u64;
+                if let Some(p) = progress.filter(|_| last.elapsed() >= PROGRESS_INTERVAL) {
+                    p.emit("downloading", downloaded, total);
+                    last = Instant::now();
+                }
+            }
+            file.flush().await?;
+            if total > 0 && downloaded != total {
+                return Err(anyhow!("download ended at {downloaded} of {total} bytes"));
+            }
+            Ok(())
+        },
+    )
+    .await
+}
+
+/// Install quantus-node unless it is there, reporting to `progress` when given.
+pub async fn ensure_quantus_node_installed(progress: Option<&InstallProgress>) -> Result<PathBuf> {
+    let res = install_node(progress).await;
+    if let Some(p) = progress {
+        p.finish(&res);
+    }
+    res
+}
+
+async fn install_node(progress: Option<&InstallProgress>) -> Result<PathBuf> {
+    let bin_dir = install_dir()?;
     let dest = bin_dir.join(exe_name());
-    if dest.exists() {
+    if is_installed(&dest) {
         return Ok(dest);
     }
 
-    let client = reqwest::Client::builder()
-        .user_agent("quantus-miner/0.1")
-        .build()?;
-    let rel: Release = client
-        .get("https://api.github.com/repos/Quantus-Network/chain/releases/latest")
-        .send()
-        .await?
-        .error_for_status()?
-        .json()
-        .await?;
+    if let Some(p) = progress {
+        p.emit("fetching_release", 0, 0);
+    }
+    let rel = fetch_latest_release("Quantus-Network/chain").await?;
+    if let Some(p) = progress {
+        p.check_cancelled()?;
+    }
 
     let tgt = target();
     let wanted_prefix = format!(
@@ -126,38 +1056,48 @@ pub async fn ensure_quantus_node_installed() -> Result<PathBuf> {
         .assets
         .iter()
         .find(|a| a.name.starts_with(&wanted_prefix) && a.name.ends_with(tgt.ext))
-        .ok_or_else(|| anyhow!("no asset for target: {wanted_prefix}{}", tgt.ext))?;
+        .ok_or_else(|| InstallError::NoAssetForPlatform {
+            what: "quantus-node",
+            wanted: format!("{wanted_prefix}{}", tgt.ext),
+            available: rel.assets.iter().map(|a| a.name.clone()).collect(),
+        })?;
 
     let tmp = tempfile::Builder::new().prefix("quantus-node-").tempdir()?;
-    let archive_path = tmp.path().join(&asset.name);
-
-    let mut resp = client
-        .get(&asset.browser_download_url)
-        .send()
-        .await?
-        .error_for_status()?;
-    let mut file = tokio::fs::File::create(&archive_path).await?;
-    use tokio::io::AsyncWriteExt;
-    while let Some(chunk) = resp.chunk().await? {
-        file.write_all(&chunk).await?;
+    preflight(&bin_dir, tmp.path(), asset, asset.size * UNPACK_FACTOR)?;
+    let archive_path = download_cached(asset, progress).await?;
+    if let Some(p) = progress {
+        p.check_cancelled()?;
+        p.emit("installing", asset.size, asset.size);
     }
-    file.flush().await?;
 
-    if tgt.ext == ".tar.gz" {
-        extract_tar_gz(&archive_path, &bin_dir)?;
+    // Unpack into the temp dir first; only a complete binary gets copied into bin_dir.
+    let staging = tmp.path().join("unpacked");
+    fs::create_dir_all(&staging)?;
+    let extracted = if tgt.ext == ".tar.gz" {
+        extract_tar_gz(&archive_path, &staging)
     } else {
-        extract_zip(&archive_path, &bin_dir)?;
-    }
+        extract_zip(&archive_path, &staging)
+    };
+    let extraction_failed = |reason: String| InstallError::ExtractionFailed {
+        archive: archive_path.clone(),
+        reason,
+    };
+    extracted.map_err(|e| extraction_failed(e.to_string()))?;
+    let unpacked = find_file(&staging, exe_name())
+        .ok_or_else(|| extraction_failed(format!("no {} in the archive", exe_name())))?;
+    install_binary(&unpacked, &dest)?;
 
-    #[cfg(unix)]
-    {
-        use std::os::unix::fs::PermissionsExt;
-        if let Ok(meta) = fs::metadata(&dest) {
-            let mut p = meta.permissions();
-            p.set_mode(0o755);
-            let _ = fs::set_permissions(&dest, p);
+    make_executable(&dest);
+    check_not_quarantined(&dest)?;
+    if let Err(e) = verify_binary(&dest).await {
+        let _ = fs::remove_file(&dest);
+        return Err(InstallError::VerificationFailed {
+            path: dest,
+            reason: e.to_string(),
         }
+        .into());
     }
+    record_provenance(&dest, &rel, asset).await;
 
     Ok(dest)
 }
@@ -174,31 +1114,39 @@ fn miner_exe_name() -> &'static str {
     }
 }
 
-/// Ensure external parallel miner is installed (downloads from GitHub releases)
-pub async fn ensure_external_miner_installed() -> Result<PathBuf> {
-    let bin_dir = user_bin_dir()?;
+/// Ensure external parallel miner is installed (downloads from GitHub releases),
+/// reporting to `progress` when given.
+pub async fn ensure_external_miner_installed(
+    progress: Option<&InstallProgress>,
+) -> Result<PathBuf> {
+    let res = install_external_miner(progress).await;
+    if let Some(p) = progress {
+        p.finish(&res);
+    }
+    res
+}
+
+async fn install_external_miner(progress: Option<&InstallProgress>) -> Result<PathBuf> {
+    let bin_dir = install_dir()?;
     let dest = bin_dir.join(miner_exe_name());
-    if dest.exists() {
+    if is_installed(&dest) {
         return Ok(dest);
     }
 
-    let client = reqwest::Client::builder()
-        .user_agent("quantus-miner/0.1")
-        .build()?;
     // fetch latest release (same mechanism as quantus-node)
-    let rel: Release = client
-        .get("https://api.github.com/repos/Quantus-Network/quantus-miner/releases/latest")
-        .send()
-        .await?
-        .error_for_status()?
-        .json()
-        .await?;
+    if let Some(p) = progress {
+        p.emit("fetching_release", 0, 0);
+    }
+    let rel = fetch_latest_release("Quantus-Network/quantus-miner").await?;
+    if let Some(p) = progress {
+        p.check_cancelled()?;
+    }
 
-    // Current release assets are plain binaries named like:
-    //  - quantus-miner-linux-x86_64
-    //  - quantus-miner-macos-aarch64
-    //  - quantus-miner-windows-x86_64.exe
-    // So we match by platform-friendly substrings instead of archived names.
+    // Release assets are plain binaries or archives (binary plus README) named like:
+    //  - quantus-miner-linux-x86_64[.tar.gz]
+    //  - quantus-miner-macos-aarch64[.tar.gz]
+    //  - quantus-miner-windows-x86_64(.exe|.zip)
+    // So we match by platform-friendly substrings and tell the kind by extension.
     #[cfg(target_os = "linux")]
     let want_os = "linux";
     #[cfg(target_os = "macos")]
@@ -215,10 +1163,11 @@ pub async fn ensure_external_miner_installed() -> Result<PathBuf> {
 
     let name_matches = |n: &str| {
         let nl = n.to_lowercase();
+        let plain = MinerAsset::of(&nl) == MinerAsset::Binary;
         nl.starts_with("quantus-miner")
             && nl.contains(want_os)
             && nl.contains(want_arch)
-            && (!is_windows || nl.ends_with(".exe"))
+            && (!plain || !is_windows || nl.ends_with(".exe"))
             && (is_windows || !nl.ends_with(".exe"))
     };
 
@@ -226,62 +1175,151 @@ pub async fn ensure_external_miner_installed() -> Result<PathBuf> {
         .assets
         .iter()
         .find(|a| name_matches(&a.name))
-        .ok_or_else(|| {
-            anyhow!(
-                "no external miner asset for target (os={}, arch={}): available={:?}",
-                want_os,
-                want_arch,
-                rel.assets.iter().map(|a| &a.name).collect::<Vec<_>>()
-            )
+        .ok_or_else(|| InstallError::NoAssetForPlatform {
+            what: "quantus-miner",
+            wanted: format!("quantus-miner-{want_os}-{want_arch}"),
+            available: rel.assets.iter().map(|a| a.name.clone()).collect(),
         })?;
+    let kind = MinerAsset::of(&asset.name.to_lowercase());
 
     // Download to a temp path
     let tmp = tempfile::Builder::new()
         .prefix("quantus-miner-")
         .tempdir()?;
-    let download_path = tmp.path().join(&asset.name);
-
-    let mut resp = client
-        .get(&asset.browser_download_url)
-        .send()
-        .await?
-        .error_for_status()?;
-    let mut file = tokio::fs::File::create(&download_path).await?;
-    use tokio::io::AsyncWriteExt;
-    while let Some(chunk) = resp.chunk().await? {
-        file.write_all(&chunk).await?;
+    let unpacked_size = match kind {
+        MinerAsset::Binary => asset.size,
+        _ => asset.size * UNPACK_FACTOR,
+    };
+    preflight(&bin_dir, tmp.path(), asset, unpacked_size)?;
+    let download_path = download_cached(asset, progress).await?;
+    if let Some(p) = progress {
+        p.check_cancelled()?;
+        p.emit("installing", asset.size, asset.size);
+    }
+
+    // An archive is unpacked into the temp dir and only the executable is installed;
+    // a plain binary is placed as is. Either way it ends up as miner_exe_name().
+    let src = match kind {
+        MinerAsset::Binary => download_path,
+        MinerAsset::TarGz | MinerAsset::Zip => {
+            let staging = tmp.path().join("unpacked");
+            fs::create_dir_all(&staging)?;
+            let extracted = if kind == MinerAsset::TarGz {
+                extract_tar_gz(&download_path, &staging)
+            } else {
+                extract_zip(&download_path, &staging)
+            };
+            let extraction_failed = |reason: String| InstallError::ExtractionFailed {
+                archive: download_path.clone(),
+                reason,
+            };
+            extracted.map_err(|e| extraction_failed(e.to_string()))?;
+            find_miner_exe(&staging).ok_or_else(|| {
+                extraction_failed(format!("no {} executable in the archive", miner_exe_name()))
+            })?
+        }
+    };
+    install_binary(&src, &dest)?;
+
+    make_executable(&dest);
+    check_not_quarantined(&dest)?;
+    record_provenance(&dest, &rel, asset).await;
+
+    Ok(dest)
+}
+
+/// How an external miner release asset is packaged, by its (lowercased) name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MinerAsset {
+    Binary,
+    TarGz,
+    Zip,
+}
+
+impl MinerAsset {
+    fn of(name: &str) -> Self {
+        if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+            MinerAsset::TarGz
+        } else if name.ends_with(".zip") {
+            MinerAsset::Zip
+        } else {
+            MinerAsset::Binary
+        }
     }
-    file.flush().await?;
+}
 
-    // If the asset is a plain binary, move it into place and make it executable.
-    // If it's ever distributed as an archive again, extend this logic accordingly.
-    // For now, releases list plain binaries; just place them as miner_exe_name().
-    let final_dest = bin_dir.join(miner_exe_name());
-    // On Windows, keep .exe; on others, remove any suffix and rename to the expected name
-    std::fs::copy(&download_path, &final_dest)?;
+// The miner executable in an unpacked archive, at its root or in a (versioned) folder:
+// a file named miner_exe_name(), else a runnable `quantus-miner*` file (zip drops the
+// exec bit, so there only the exact name works on unix).
+fn find_miner_exe(dir: &Path) -> Option<PathBuf> {
+    find_file(dir, miner_exe_name()).or_else(|| find_runnable_miner(dir))
+}
 
-    #[cfg(unix)]
-    {
-        use std::os::unix::fs::PermissionsExt;
-        if let Ok(meta) = fs::metadata(&final_dest) {
-            let mut p = meta.permissions();
-            p.set_mode(0o755);
-            let _ = fs::set_permissions(&final_dest, p);
+fn find_runnable_miner(dir: &Path) -> Option<PathBuf> {
+    for entry in fs::read_dir(dir).ok()?.flatten() {
+        let p = entry.path();
+        if p.is_dir() {
+            if let Some(found) = find_runnable_miner(&p) {
+                return Some(found);
+            }
+            continue;
+        }
+        let name = p
+            .file_name()
+            .map(|n| n.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        let text = [".md", ".txt", ".sha256", ".sig", ".asc"]
+            .iter()
+            .any(|ext| name.ends_with(ext));
+        let exe = !cfg!(windows) || name.ends_with(".exe");
+        if name.starts_with("quantus-miner") && !text && exe && is_runnable(&p) {
+            return Some(p);
         }
     }
+    None
+}
 
-    Ok(dest)
+/// Outcome of `ensure_all_binaries`; each install succeeds or fails on its own.
+pub struct Binaries {
+    pub node: Result<PathBuf>,
+    /// None when the external miner wasn't asked for.
+    pub miner: Option<Result<PathBuf>>,
+}
+
+/// Install quantus-node and, with `include_external`, the external miner at the same
+/// time; progress of both goes to `installer:progress` tagged "node" / "miner". Both
+/// stop at their next chunk or step once `cancel` is set, removing their partial
+/// downloads; downloads cut short otherwise resume next time (`download_cached`).
+pub async fn ensure_all_binaries(
+    app: &AppHandle,
+    include_external: bool,
+    cancel: Option<CancelToken>,
+) -> Binaries {
+    let (node_progress, miner_progress) = (
+        InstallProgress::new(app, "node", cancel.clone()),
+        InstallProgress::new(app, "miner", cancel),
+    );
+    let miner = async {
+        if include_external {
+            Some(ensure_external_miner_installed(Some(&miner_progress)).await)
+        } else {
+            None
+        }
+    };
+    let (node, miner) = tokio::join!(ensure_quantus_node_installed(Some(&node_progress)), miner);
+    Binaries { node, miner }
 }
 
 /// Spawn the external miner with provided config and return a handle
 pub async fn spawn_external_miner(cfg: ExternalMinerConfig) -> Result<ExternalMinerHandle> {
-    let bin = ensure_external_miner_installed().await?;
-    let args: Vec<String> = vec![
+    let bin = ensure_external_miner_installed(None).await?;
+    let mut args: Vec<String> = vec![
         "--num-cores".into(),
         cfg.num_cores.to_string(),
         "--port".into(),
         cfg.port.to_string(),
     ];
+    args.extend(cfg.extra_args);
 
     let mut cmd = Command::new(&bin);
     // Ensure the external miner emits logs
@@ -291,14 +1329,38 @@ pub async fn spawn_external_miner(cfg: ExternalMinerConfig) -> Result<ExternalMi
         .stdout(Stdio::piped())
         .stderr(Stdio::piped());
 
-    let child = cmd.spawn()?;
+    let child = cmd
+        .spawn()
+        .map_err(|e| diagnose_spawn_error("external miner", &bin, e))?;
 
     Ok(ExternalMinerHandle {
         port: cfg.port,
+        num_cores: cfg.num_cores,
         child,
     })
 }
 
+/// Whether the installed external miner's `--help` lists `flag`.
+pub async fn external_miner_supports(flag: &str) -> bool {
+    let Ok(bin) = ensure_external_miner_installed(None).await else {
+        return false;
+    };
+    let out = Command::new(&bin)
+        .arg("--help")
+        .stdin(Stdio::null())
+        .kill_on_drop(true)
+        .output();
+    match tokio::time::timeout(Duration::from_secs(5), out).await {
+        Ok(Ok(o)) => {
+            let text = [o.stdout, o.stderr].concat();
+            String::from_utf8_lossy(&text)
+                .split(|c: char| c.is_whitespace() || c == ',' || c == '=')
+                .any(|w| w == flag)
+        }
+        _ => false,
+    }
+}
+
 fn exe_name() -> &'static str {
     #[cfg(target_os = "windows")]
     {
@@ -310,6 +1372,21 @@ fn exe_name() -> &'static str {
     }
 }
 
+// First file named `name` under `dir` (archives may nest the binary in a folder).
+fn find_file(dir: &Path, name: &str) -> Option<PathBuf> {
+    for entry in fs::read_dir(dir).ok()?.flatten() {
+        let p = entry.path();
+        if p.is_dir() {
+            if let Some(found) = find_file(&p, name) {
+                return Some(found);
+            }
+        } else if p.file_name().is_some_and(|n| n == name) {
+            return Some(p);
+        }
+    }
+    None
+}
+
 fn extract_tar_gz(archive: &Path, out_dir: &Path) -> Result<()> {
     let f = fs::File::open(archive)?;
     let gz = flate2::read::GzDecoder::new(f);
@@ -335,3 +1412,234 @@ fn extract_zip(archive: &Path, out_dir: &Path) -> Result<()> {
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    const SIZE: u64 = 1 << 20;
+
+    // A release download server on localhost: answers each request with `SIZE` bytes,
+    // in 16 KiB chunks every `pace` (until the client goes away).
+    async fn release_server(pace: Duration) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((mut sock, _)) = listener.accept().await {
+                tokio::spawn(async move {
+                    let mut req = Vec::new();
+                    let mut buf = [0u8; 1024];
+                    while !req.windows(4).any(|w| w == b"\r\n\r\n") {
+                        match sock.read(&mut buf).await {
+                            Ok(0) | Err(_) => return,
+                            Ok(n) => req.extend_from_slice(&buf[..n]),
+                        }
+                    }
+                    let head = format!(
+                        "HTTP/1.1 200 OK\r\nContent-Length: {SIZE}\r\n\
+                         Content-Type: application/octet-stream\r\n\r\n"
+                    );
+                    if sock.write_all(head.as_bytes()).await.is_err() {
+                        return;
+                    }
+                    let chunk = vec![0x5a; 16 * 1024];
+                    for _ in 0..SIZE / chunk.len() as u64 {
+                        if sock.write_all(&chunk).await.is_err() {
+                            return;
+                        }
+                        tokio::time::sleep(pace).await;
+                    }
+                });
+            }
+        });
+        format!("http://{addr}/quantus-node-v0.1.6-x86_64-unknown-linux-gnu.tar.gz")
+    }
+
+    fn asset(url: String) -> Asset {
+        Asset {
+            name: "quantus-node-v0.1.6-x86_64-unknown-linux-gnu.tar.gz".into(),
+            browser_download_url: url,
+            size: SIZE,
+        }
+    }
+
+    fn files_in(dir: &Path) -> Vec<String> {
+        let mut names: Vec<String> = fs::read_dir(dir)
+            .unwrap()
+            .flatten()
+            .map(|e| e.file_name().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[tokio::test]
+    async fn cancelled_download_removes_its_partial_file() {
+        let cache = tempfile::tempdir().unwrap();
+        let asset = asset(release_server(Duration::from_millis(20)).await);
+        let cancel = CancelToken::default();
+        let progress = InstallProgress::detached("node", Some(cancel.clone()));
+        let (_, part) = cache_paths(cache.path(), &asset);
+
+        let download = download_into(cache.path(), &asset, Some(&progress));
+        let cancel_midway = async {
+            // once some of it is on disk
+            while fs::metadata(&part).map_or(0, |m| m.len()) < 64 * 1024 {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+            cancel
+                .flag()
+                .store(true, std::sync::atomic::Ordering::Relaxed);
+        };
+        let (res, ()) = tokio::join!(download, cancel_midway);
+
+        let err = res.unwrap_err();
+        assert!(crate::ops::is_cancelled(&err), "{err:#}");
+        assert_eq!(files_in(cache.path()), Vec::<String>::new());
+    }
+
+    #[tokio::test]
+    async fn cancelled_before_the_first_byte() {
+        let cache = tempfile::tempdir().unwrap();
+        let asset = asset(release_server(Duration::ZERO).await);
+        let cancel = CancelToken::default();
+        cancel
+            .flag()
+            .store(true, std::sync::atomic::Ordering::Relaxed);
+        let progress = InstallProgress::detached("node", Some(cancel));
+        let err = download_into(cache.path(), &asset, Some(&progress))
+            .await
+            .unwrap_err();
+        assert!(crate::ops::is_cancelled(&err));
+        assert_eq!(files_in(cache.path()), Vec::<String>::new());
+    }
+
+    #[tokio::test]
+    async fn finished_download_leaves_only_the_cache_entry() {
+        let cache = tempfile::tempdir().unwrap();
+        let asset = asset(release_server(Duration::ZERO).await);
+        let progress = InstallProgress::detached("node", Some(CancelToken::default()));
+        let path = download_into(cache.path(), &asset, Some(&progress))
+            .await
+            .unwrap();
+        assert_eq!(fs::metadata(&path).unwrap().len(), SIZE);
+        assert_eq!(
+            files_in(cache.path()),
+            [format!(
+                "{SIZE}-quantus-node-v0.1.6-x86_64-unknown-linux-gnu.tar.gz"
+            )]
+        );
+    }
+
+    #[cfg(unix)]
+    const MINER: &[u8] = b"#!/bin/sh\necho 'quantus-miner 1.2.0'\n";
+
+    // Unpacks `tests/fixtures/archives/<name>` the way the miner install does and
+    // returns the staging dir with the executable found in it.
+    #[cfg(unix)]
+    fn unpack(name: &str) -> (tempfile::TempDir, PathBuf) {
+        let archive = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/archives")
+            .join(name);
+        let staging = tempfile::tempdir().unwrap();
+        match MinerAsset::of(name) {
+            MinerAsset::TarGz => extract_tar_gz(&archive, staging.path()).unwrap(),
+            MinerAsset::Zip => extract_zip(&archive, staging.path()).unwrap(),
+            MinerAsset::Binary => panic!("{name} is not an archive"),
+        }
+        let exe = find_miner_exe(staging.path()).expect("no miner executable");
+        (staging, exe)
+    }
+
+    // Installs what `unpack` found and checks that only the executable lands, runnable.
+    #[cfg(unix)]
+    fn assert_installs(exe: &Path) {
+        let bin = tempfile::tempdir().unwrap();
+        let dest = bin.path().join(miner_exe_name());
+        install_binary(exe, &dest).unwrap();
+        make_executable(&dest);
+        assert_eq!(fs::read(&dest).unwrap(), MINER);
+        assert!(is_runnable(&dest));
+        assert_eq!(files_in(bin.path()), [miner_exe_name()]);
+    }
+
+    #[test]
+    fn miner_asset_kind_by_extension() {
+        assert_eq!(
+            MinerAsset::of("quantus-miner-linux-x86_64"),
+            MinerAsset::Binary
+        );
+        assert_eq!(
+            MinerAsset::of("quantus-miner-windows-x86_64.exe"),
+            MinerAsset::Binary
+        );
+        assert_eq!(
+            MinerAsset::of("quantus-miner-linux-x86_64.tar.gz"),
+            MinerAsset::TarGz
+        );
+        assert_eq!(
+            MinerAsset::of("quantus-miner-macos-aarch64.tgz"),
+            MinerAsset::TarGz
+        );
+        assert_eq!(
+            MinerAsset::of("quantus-miner-windows-x86_64.zip"),
+            MinerAsset::Zip
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn tar_gz_with_the_binary_at_the_root() {
+        let (staging, exe) = unpack("miner-flat.tar.gz");
+        assert_eq!(exe, staging.path().join("quantus-miner"));
+        assert!(is_runnable(&exe));
+        assert_installs(&exe);
+    }
+
+    // The binary carries a platform suffix here and only its exec bit tells it apart
+    // from the checksum and the README next to it.
+    #[cfg(unix)]
+    #[test]
+    fn tar_gz_with_the_binary_in_a_versioned_folder() {
+        let (staging, exe) = unpack("miner-nested.tar.gz");
+        assert_eq!(
+            exe,
+            staging
+                .path()
+                .join("quantus-miner-v1.2.0/quantus-miner-x86_64-unknown-linux-gnu")
+        );
+        assert_installs(&exe);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn zip_with_the_binary_at_the_root() {
+        let (staging, exe) = unpack("miner-flat.zip");
+        assert_eq!(exe, staging.path().join("quantus-miner"));
+        assert_installs(&exe);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn zip_with_the_binary_in_a_versioned_folder() {
+        let (staging, exe) = unpack("miner-nested.zip");
+        assert_eq!(
+            exe,
+            staging.path().join("quantus-miner-v1.2.0/quantus-miner")
+        );
+        assert_installs(&exe);
+    }
+
+    #[test]
+    fn archive_without_a_miner() {
+        let staging = tempfile::tempdir().unwrap();
+        fs::write(staging.path().join("README.md"), "# quantus-miner\n").unwrap();
+        fs::write(
+            staging.path().join("quantus-miner.sha256"),
+            "00  quantus-miner\n",
+        )
+        .unwrap();
+        assert_eq!(find_miner_exe(staging.path()), None);
+    }
+}
@@ -0,0 +1,70 @@
+use std::path::Path;
+use tauri::AppHandle;
+
+use crate::node_caps::NodeCapabilities;
+
+/// Thread-count flags of quantus-node's built-in miner, by preference; releases have
+/// used different names, so the binary's capabilities (`node_caps`) decide.
+const THREAD_FLAGS: [&str; 3] = [
+    "--max-mining-threads",
+    "--mining-threads",
+    "--miner-threads",
+];
+
+#[derive(Debug, thiserror::Error)]
+pub enum InternalMiningError {
+    #[error(
+        "internal mining threads and the external miner are mutually exclusive: with the external miner the node doesn't mine itself; turn one of them off"
+    )]
+    WithExternalMiner,
+    #[error("internal mining threads must be at least 1")]
+    Zero,
+    #[error("{threads} internal mining threads requested, but this machine has {cores} cores")]
+    TooManyThreads { threads: u32, cores: usize },
+    #[error("{0} is already in the extra arguments; remove it or clear the thread count")]
+    AlsoInExtraArgs(&'static str),
+    #[error("this quantus-node ({version}) has no option for its miner's thread count")]
+    Unsupported { version: String },
+}
+
+/// The flag `caps`' node takes for its built-in miner's thread count, if any.
+pub fn threads_flag(caps: &NodeCapabilities) -> Option<&'static str> {
+    THREAD_FLAGS.into_iter().find(|f| caps.supports(f))
+}
+
+/// Check a start's `threads` for the node at `bin`: at least 1, at most the core count,
+/// not together with the external miner (`external`) or a thread flag in `extra_args`,
+/// and supported by that node. Returns the flag to pass.
+pub async fn validate(
+    app: &AppHandle,
+    bin: &Path,
+    threads: u32,
+    external: bool,
+    extra_args: &[String],
+) -> Result<&'static str, InternalMiningError> {
+    if external {
+        return Err(InternalMiningError::WithExternalMiner);
+    }
+    if threads == 0 {
+        return Err(InternalMiningError::Zero);
+    }
+    let cores = num_cpus::get();
+    if threads as usize > cores {
+        return Err(InternalMiningError::TooManyThreads { threads, cores });
+    }
+    if let Some(flag) = THREAD_FLAGS.into_iter().find(|f| {
+        extra_args
+            .iter()
+            .any(|a| a == f || a.strip_prefix(f).is_some_and(|r| r.starts_with('=')))
+    }) {
+        return Err(InternalMiningError::AlsoInExtraArgs(flag));
+    }
+    let caps = crate::node_caps::probe(app, bin).await.ok();
+    caps.as_ref()
+        .and_then(threads_flag)
+        .ok_or_else(|| InternalMiningError::Unsupported {
+            version: caps
+                .and_then(|c| c.version)
+                .unwrap_or_else(|| "unknown version".into()),
+        })
+}
@@ -0,0 +1,302 @@
+use anyhow::{anyhow, Result};
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    io::{BufRead, BufReader},
+    path::{Path, PathBuf},
+};
+
+/// Matches returned when `limit` isn't given...
+const DEFAULT_LIMIT: usize = 200;
+/// ...and never more than this per call; `next_cursor` fetches the rest.
+const MAX_LIMIT: usize = 1000;
+/// Longest regex accepted, in characters.
+const MAX_PATTERN_LEN: usize = 512;
+/// Compiled program and lazy DFA caps: a pattern like `(a{100}){100}` is refused
+/// instead of taking memory and time to build.
+const REGEX_SIZE_LIMIT: usize = 1 << 20;
+const DFA_SIZE_LIMIT: usize = 2 << 20;
+/// Lines longer than this are cut in results (the match is still tested on all of it).
+const MAX_LINE_CHARS: usize = 4096;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchMode {
+    #[default]
+    Substring,
+    Regex,
+}
+
+/// `search_logs` options; everything is optional.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct SearchOptions {
+    pub mode: SearchMode,
+    pub case_sensitive: bool,
+    /// "node" | "external" | "ui" | "command"; empty means all.
+    pub sources: Vec<String>,
+    /// "error" | "warn" | "info" | "debug" | "trace"; empty means all. Lines without a
+    /// level of their own (GUI messages, commands) count as "info".
+    pub levels: Vec<String>,
+    /// UTC milliseconds, inclusive; lines without a timestamp are left out when set.
+    pub since: Option<i64>,
+    pub until: Option<i64>,
+    /// A session file from `list_log_files` (path or file name); None searches the
+    /// chain's in-memory buffer (the last 2000 lines of every source).
+    pub file: Option<String>,
+    pub limit: Option<usize>,
+    /// `next_cursor` of the previous call: the line number to go on from.
+    pub cursor: Option<u64>,
+}
+
+/// One matching line.
+#[derive(Debug, Clone, Serialize)]
+pub struct LogMatch {
+    /// 1-based; in the buffer, counted since the chain's last fresh start.
+    pub line_no: u64,
+    pub ts: Option<i64>,
+    pub source: Option<String>,
+    pub level: Option<String>,
+    /// The line without its timestamp and source columns.
+    pub text: String,
+}
+
+/// `search_logs` result.
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchResult {
+    pub matches: Vec<LogMatch>,
+    /// Pass as `cursor` for more matches; None when the end was reached.
+    pub next_cursor: Option<u64>,
+    /// Lines looked at by this call.
+    pub scanned: u64,
+    /// The file searched, or None for the buffer.
+    pub file: Option<String>,
+}
+
+enum Matcher {
+    Substring { needle: String, fold: bool },
+    Regex(Regex),
+}
+
+impl Matcher {
+    fn new(query: &str, o: &SearchOptions) -> Result<Matcher> {
+        match o.mode {
+            SearchMode::Substring => Ok(Matcher::Substring {
+                needle: if o.case_sensitive {
+                    query.to_string()
+                } else {
+                    query.to_lowercase()
+                },
+                fold: !o.case_sensitive,
+            }),
+            SearchMode::Regex => {
+                if query.chars().count() > MAX_PATTERN_LEN {
+                    return Err(anyhow!(
+                        "the pattern is longer than {MAX_PATTERN_LEN} characters"
+                    ));
+                }
+                RegexBuilder::new(query)
+                    .case_insensitive(!o.case_sensitive)
+                    .size_limit(REGEX_SIZE_LIMIT)
+                    .dfa_size_limit(DFA_SIZE_LIMIT)
+                    .build()
+                    .map(Matcher::Regex)
+                    .map_err(|e| anyhow!("invalid pattern: {e}"))
+            }
+        }
+    }
+
+    fn is_match(&self, text: &str) -> bool {
+        match self {
+            Matcher::Substring { needle, fold: true } => text.to_lowercase().contains(needle),
+            Matcher::Substring { needle, .. } => text.contains(needle.as_str()),
+            Matcher::Regex(re) => re.is_match(text),
+        }
+    }
+}
+
+// A session line split into its columns: "<rfc3339> <source:8> <text>". Lines in
+// another shape (older external miner files) are all text.
+fn split_line(line: &str) -> (Option<i64>, Option<&str>, &str) {
+    let Some((ts, rest)) = line.split_once(' ') else {
+        return (None, None, line);
+    };
+    let Some(ts) = crate::timeutil::parse_rfc3339_ms(ts) else {
+        return (None, None, line);
+    };
+    let (source, text) = match (rest.get(..8), rest.get(9..)) {
+        (Some(col), Some(text)) if rest.as_bytes()[8] == b' ' => (col.trim_end(), text),
+        _ => (rest.trim_end(), ""),
+    };
+    (Some(ts), Some(source), text)
+}
+
+// The node's level column ("2025-01-01 12:00:00 WARN ..."), looked for in the first
+// few words, colour codes removed.
+fn level_of(text: &str) -> Option<&'static str> {
+    static ANSI: once_cell::sync::Lazy<Regex> =
+        once_cell::sync::Lazy::new(|| Regex::new(r"\x1b\[[0-9;]*m").unwrap());
+    text.split_whitespace()
+        .take(4)
+        .find_map(|w| match ANSI.replace_all(w, "").as_ref() {
+            "ERROR" => Some("error"),
+            "WARN" => Some("warn"),
+            "INFO" => Some("info"),
+            "DEBUG" => Some("debug"),
+            "TRACE" => Some("trace"),
+            _ => None,
+        })
+}
+
+struct Filter {
+    matcher: Matcher,
+    sources: Vec<String>,
+    levels: Vec<String>,
+    since: Option<i64>,
+    until: Option<i64>,
+}
+
+impl Filter {
+    fn check(&self, line_no: u64, line: &str) -> Option<LogMatch> {
+        let (ts, source, text) = split_line(line);
+        if !self.sources.is_empty() && !source.is_some_and(|s| self.sources.iter().any(|f| f == s))
+        {
+            return None;
+        }
+        if self.since.is_some_and(|s| ts.is_none_or(|t| t < s))
+            || self.until.is_some_and(|u| ts.is_none_or(|t| t > u))
+        {
+            return None;
+        }
+        let level = level_of(text);
+        if !self.levels.is_empty() && !self.levels.iter().any(|l| l == level.unwrap_or("info")) {
+            return None;
+        }
+        if !self.matcher.is_match(text) {
+            return None;
+        }
+        let text = match text.char_indices().nth(MAX_LINE_CHARS) {
+            Some((i, _)) => format!("{}…", &text[..i]),
+            None => text.to_string(),
+        };
+        Some(LogMatch {
+            line_no,
+            ts,
+            source: source.map(str::to_string),
+            level: level.map(str::to_string),
+            text,
+        })
+    }
+}
+
+// Collects up to `limit` matches from numbered lines starting at `from`.
+struct Collector<'a> {
+    filter: &'a Filter,
+    limit: usize,
+    from: u64,
+    matches: Vec<LogMatch>,
+    scanned: u64,
+}
+
+impl Collector<'_> {
+    /// False once full: the caller stops, and `line_no` is where the next call starts.
+    fn push(&mut self, line_no: u64, line: &str) -> bool {
+        if line_no < self.from {
+            return true;
+        }
+        if self.matches.len() >= self.limit {
+            return false;
+        }
+        self.scanned += 1;
+        if let Some(m) = self.filter.check(line_no, line) {
+            self.matches.push(m);
+        }
+        true
+    }
+}
+
+// Only session files under the logs directory, as `list_log_files` reports them.
+fn resolve_file(chain: &str, name: &str) -> Result<PathBuf> {
+    let wanted = Path::new(name);
+    crate::session_log::list_files(Some(chain))
+        .into_iter()
+        .map(|f| PathBuf::from(f.path))
+        .find(|p| p == wanted || p.file_name() == Some(wanted.as_os_str()))
+        .ok_or_else(|| anyhow!("no {chain} session log named {name}"))
+}
+
+fn search_file(path: &Path, c: &mut Collector) -> Result<Option<u64>> {
+    let reader = BufReader::new(fs::File::open(path)?);
+    let mut line_no = 0;
+    // byte lines: a half-written or non-UTF-8 line doesn't end the search
+    for line in reader.split(b'\n') {
+        line_no += 1;
+        let line = line?;
+        let line = String::from_utf8_lossy(&line);
+        if !c.push(line_no, line.trim_end_matches('\r')) {
+            return Ok(Some(line_no));
+        }
+    }
+    Ok(None)
+}
+
+/// Search `chain`'s logs for `query` (an empty query matches every line that passes the
+/// filters). The buffer is searched under its lock for a moment; a file is read line by
+/// line on a blocking thread.
+pub async fn search(chain: &str, query: &str, o: SearchOptions) -> Result<SearchResult> {
+    let filter = Filter {
+        matcher: Matcher::new(query, &o)?,
+        sources: o.sources.iter().map(|s| s.to_lowercase()).collect(),
+        levels: o.levels.iter().map(|l| l.to_lowercase()).collect(),
+        since: o.since,
+        until: o.until,
+    };
+    let limit = o.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+    let from = o.cursor.unwrap_or(1);
+    match o.file {
+        None => {
+            let (first, lines) = crate::session_log::ring_lines(chain).await;
+            let mut c = Collector {
+                filter: &filter,
+                limit,
+                // lines that already fell out of the buffer are skipped
+                from: from.max(first),
+                matches: Vec::new(),
+                scanned: 0,
+            };
+            let next_cursor = lines
+                .iter()
+                .zip(first..)
+                .find(|(line, no)| !c.push(*no, line))
+                .map(|(_, no)| no);
+            Ok(SearchResult {
+                matches: c.matches,
+                next_cursor,
+                scanned: c.scanned,
+                file: None,
+            })
+        }
+        Some(name) => {
+            let path = resolve_file(chain, &name)?;
+            let shown = path.display().to_string();
+            tokio::task::spawn_blocking(move || {
+                let mut c = Collector {
+                    filter: &filter,
+                    limit,
+                    from,
+                    matches: Vec::new(),
+                    scanned: 0,
+                };
+                let next_cursor = search_file(&path, &mut c)?;
+                Ok(SearchResult {
+                    matches: c.matches,
+                    next_cursor,
+                    scanned: c.scanned,
+                    file: Some(shown),
+                })
+            })
+            .await?
+        }
+    }
+}
@@ -0,0 +1,316 @@
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+use std::{
+    collections::HashSet,
+    fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+use tauri::AppHandle;
+
+use crate::events;
+
+/// Throttle file logging below this much free space on a volume shared with a node DB...
+const LOW_SPACE_BYTES: u64 = 5 << 30;
+/// ...and stop throttling above this much.
+const RESUME_SPACE_BYTES: u64 = 8 << 30;
+const CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// `miner:log-throttled` payload: session files started (or stopped) keeping only
+/// warnings and errors, because the logs share a nearly full volume with a node database.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LogThrottled {
+    throttled: bool,
+    logs_dir: String,
+    /// The chain directory found on the same volume (null once not throttled).
+    db_dir: Option<String>,
+    free_bytes: Option<u64>,
+    threshold_bytes: u64,
+    ts: i64,
+}
+
+/// `logs:move-progress` payload, once per file and once when done (`done`).
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MoveProgress {
+    from: String,
+    to: String,
+    moved: usize,
+    total: usize,
+    /// Files still being written by a running node/external miner; left where they are.
+    skipped: usize,
+    bytes: u64,
+    done: bool,
+    error: Option<String>,
+}
+
+// `p` or its nearest existing ancestor, with symlinks resolved
+fn resolve(p: &Path) -> Option<PathBuf> {
+    p.ancestors().find_map(|a| fs::canonicalize(a).ok())
+}
+
+/// Whether `a` and `b` are on the same volume. Symlinks are followed, so a DB dir that
+/// links to another disk counts as that disk. False when either can't be resolved.
+pub fn same_volume(a: &Path, b: &Path) -> bool {
+    let (Some(a), Some(b)) = (resolve(a), resolve(b)) else {
+        return false;
+    };
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        match (fs::metadata(&a), fs::metadata(&b)) {
+            (Ok(ma), Ok(mb)) => ma.dev() == mb.dev(),
+            _ => false,
+        }
+    }
+    #[cfg(windows)]
+    {
+        // canonical paths start with their volume (`\\?\C:`, `\\?\UNC\server\share`)
+        use std::path::Component;
+        let volume = |p: &Path| match p.components().next() {
+            Some(Component::Prefix(v)) => Some(v.as_os_str().to_ascii_lowercase()),
+            _ => None,
+        };
+        volume(&a).is_some() && volume(&a) == volume(&b)
+    }
+    #[cfg(not(any(unix, windows)))]
+    {
+        let _ = (a, b);
+        false
+    }
+}
+
+async fn check(app: &AppHandle) {
+    let logs = crate::session_log::logs_dir();
+    // (chain, its chain directory) on the logs' volume
+    let mut shared = None;
+    for chain in crate::miner::running_chains().await {
+        if let Ok(dir) = crate::miner::chain_dir_for(&chain).await {
+            if same_volume(&logs, &dir) {
+                shared = Some((chain, dir));
+                break;
+            }
+        }
+    }
+    let free = shared
+        .as_ref()
+        .and_then(|_| resolve(&logs))
+        .and_then(|p| crate::installer::free_space(&p));
+    let was = crate::session_log::throttled();
+    let limit = if was {
+        RESUME_SPACE_BYTES
+    } else {
+        LOW_SPACE_BYTES
+    };
+    let throttled = free.is_some_and(|f| f < limit);
+    if crate::session_log::set_throttled(throttled) == throttled {
+        return;
+    }
+    let gib = |b: u64| b as f64 / (1u64 << 30) as f64;
+    let line = if throttled {
+        format!(
+            "Only {:.1} GiB free on the disk holding both the logs and the node database; session log files keep warnings and errors only until it's above {:.0} GiB. Choose another log directory to keep full logs.",
+            gib(free.unwrap_or_default()),
+            gib(RESUME_SPACE_BYTES)
+        )
+    } else {
+        "Full session logging resumed.".to_string()
+    };
+    for chain in crate::miner::running_chains().await {
+        crate::session_log::ui(app, &chain, line.clone());
+    }
+    if let (true, Some((chain, _))) = (throttled, &shared) {
+        crate::remediation::offer(
+            app,
+            chain,
+            crate::remediation::ErrorKind::LowDiskSpace,
+            &line,
+        );
+    }
+    events::emit(
+        app,
+        events::MINER_LOG_THROTTLED,
+        &LogThrottled {
+            throttled,
+            logs_dir: logs.display().to_string(),
+            db_dir: shared
+                .filter(|_| throttled)
+                .map(|(_, d)| d.display().to_string()),
+            free_bytes: free,
+            threshold_bytes: limit,
+            ts: crate::timeutil::now_ms(),
+        },
+    );
+}
+
+/// Background task: every minute, throttle session files while the logs directory
+/// shares a nearly full volume with a running node's database.
+pub fn spawn(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            check(&app).await;
+            tokio::time::sleep(CHECK_INTERVAL).await;
+        }
+    });
+}
+
+fn check_writable(dir: &Path) -> Result<()> {
+    let probe = dir.join(".quantus-write-probe");
+    fs::create_dir_all(dir)
+        .and_then(|_| fs::write(&probe, b""))
+        .and_then(|_| fs::remove_file(&probe))
+        .map_err(|e| anyhow!("log directory {} is not writable: {e}", dir.display()))
+}
+
+// files under `dir`, recursively
+fn walk(dir: &Path, out: &mut Vec<PathBuf>) {
+    for e in fs::read_dir(dir).into_iter().flatten().flatten() {
+        let p = e.path();
+        if p.is_dir() {
+            walk(&p, out);
+        } else {
+            out.push(p);
+        }
+    }
+}
+
+// Session files still being written: each running chain's node file and the newest
+// external miner file of chains whose external miner runs.
+async fn open_files() -> HashSet<PathBuf> {
+    let mut open = HashSet::new();
+    for chain in crate::miner::running_chains().await {
+        if let Some(p) = crate::session_log::current_path(&chain).await {
+            open.insert(p);
+        }
+        if crate::miner::external_miner(&chain).await.is_some() {
+            let newest = crate::session_log::list_files(Some(&chain))
+                .into_iter()
+                .find(|f| f.kind == "ext");
+            if let Some(f) = newest {
+                open.insert(PathBuf::from(f.path));
+            }
+        }
+    }
+    open
+}
+
+fn move_file(from: &Path, to: &Path) -> std::io::Result<u64> {
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let len = fs::metadata(from)?.len();
+    // rename fails across volumes: copy, then remove
+    if fs::rename(from, to).is_err() {
+        fs::copy(from, to)?;
+        fs::remove_file(from)?;
+    }
+    Ok(len)
+}
+
+/// Use `dir` (absolute; None: the default) for session logs from now on, and move the
+/// existing ones there in the background (`logs:move-progress`). Files a running process
+/// still writes stay behind. Returns the new directory.
+pub async fn set_log_directory(app: &AppHandle, dir: Option<String>) -> Result<PathBuf> {
+    let new_dir = match dir.map(|d| d.trim().to_string()).filter(|d| !d.is_empty()) {
+        Some(d) => {
+            let p = PathBuf::from(&d);
+            if !p.is_absolute() {
+                return Err(anyhow!("log directory must be an absolute path: {d}"));
+            }
+            Some(p)
+        }
+        None => None,
+    };
+    let old = crate::session_log::logs_dir();
+    let target = new_dir
+        .clone()
+        .unwrap_or_else(crate::session_log::default_logs_dir);
+    check_writable(&target)?;
+    // exact paths: a missing old dir has nothing to move and nothing to nest in
+    let (old_real, target_real) = (fs::canonicalize(&old).ok(), fs::canonicalize(&target).ok());
+    if old_real.is_some() && old_real == target_real {
+        crate::session_log::set_log_dir(app, new_dir.as_deref())?;
+        return Ok(target);
+    }
+    if let (Some(o), Some(t)) = (&old_real, &target_real) {
+        if t.starts_with(o) {
+            return Err(anyhow!(
+                "the new log directory can't be inside the current one ({})",
+                old.display()
+            ));
+        }
+    }
+    // before switching: it looks in the current directory
+    let open = open_files().await;
+    crate::session_log::set_log_dir(app, new_dir.as_deref())?;
+
+    let (app, from, to) = (app.clone(), old, target.clone());
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut files = Vec::new();
+        walk(&from, &mut files);
+        let total = files.len();
+        let mut progress = MoveProgress {
+            from: from.display().to_string(),
+            to: to.display().to_string(),
+            moved: 0,
+            total,
+            skipped: 0,
+            bytes: 0,
+            done: false,
+            error: None,
+        };
+        for f in files {
+            if open.contains(&f) {
+                progress.skipped += 1;
+                continue;
+            }
+            let Ok(rel) = f.strip_prefix(&from) else {
+                continue;
+            };
+            match move_file(&f, &to.join(rel)) {
+                Ok(len) => {
+                    progress.moved += 1;
+                    progress.bytes += len;
+                }
+                Err(e) => {
+                    eprintln!("log move: {}: {e}", f.display());
+                    progress.error = Some(format!("{}: {e}", f.display()));
+                }
+            }
+            events::emit(&app, events::LOGS_MOVE_PROGRESS, &progress);
+        }
+        progress.done = true;
+        events::emit(&app, events::LOGS_MOVE_PROGRESS, &progress);
+    });
+    Ok(target)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn throttled_payload_is_camel_case() {
+        let t = LogThrottled {
+            throttled: true,
+            logs_dir: "/logs".into(),
+            db_dir: Some("/data/chains/resonance".into()),
+            free_bytes: Some(1 << 30),
+            threshold_bytes: 2 << 30,
+            ts: 1,
+        };
+        assert_eq!(
+            serde_json::to_value(&t).unwrap(),
+            json!({
+                "throttled": true,
+                "logsDir": "/logs",
+                "dbDir": "/data/chains/resonance",
+                "freeBytes": 1u64 << 30,
+                "thresholdBytes": 2u64 << 30,
+                "ts": 1,
+            })
+        );
+    }
+}
@@ -2,34 +2,220 @@
 
 mod account_cli;
 mod account_path;
+mod adaptive;
+mod app_pin;
+mod app_update;
+mod authorship;
+mod balance;
+mod balance_cache;
+mod benchmark;
+mod bootnode;
+mod chain_constants;
+mod chain_guard;
+mod chains;
 mod commands;
+mod compact;
+mod data_import;
+mod data_migration;
+mod db_check;
+mod deeplink;
+mod events;
+mod ext_crash;
+mod ext_status;
+mod extra_args;
+mod fork;
+mod gui_metrics;
 mod installer;
+mod internal_miner;
+mod log_search;
+mod log_space;
+mod milestones;
 mod miner;
+mod net;
+mod node_caps;
+mod notable;
+mod ops;
+mod outbox;
 mod parse;
+mod paths;
+mod peer_metrics;
+mod persist;
+mod phase;
+mod pidfile;
+mod plan;
+mod propagation;
+mod provenance;
+mod redact;
+mod remediation;
+mod repair;
+mod report;
+mod restart;
 mod rpc;
+mod safe_mode_countdown;
+mod session_log;
+mod settings;
+mod setup_state;
+mod shutdown;
+mod snapshot;
+mod stats;
+mod sync_phase;
+mod telemetry;
+mod throttle;
+mod timed_stop;
+mod timeutil;
+mod transfers;
 
 use commands::*;
 use tauri::{LogicalSize, Manager, Size};
+use tauri_plugin_deep_link::DeepLinkExt;
 
 fn main() {
-    tauri::Builder::default()
+    let mut builder = tauri::Builder::default();
+    // Must be the first plugin: a second launch (e.g. from a quantus:// link) forwards
+    // its URL to this instance via the deep-link feature and exits.
+    #[cfg(desktop)]
+    {
+        builder = builder.plugin(tauri_plugin_single_instance::init(|app, _argv, _cwd| {
+            if let Some(win) = app.get_webview_window("main") {
+                let _ = win.unminimize();
+                let _ = win.set_focus();
+            }
+        }));
+    }
+    builder
         //.plugin(tauri_plugin_shell::init())
         //.plugin(tauri_plugin_process::init())
         //.plugin(tauri_plugin_updater::init())
+        .plugin(tauri_plugin_deep_link::init())
         .plugin(tauri_plugin_opener::init())
         .invoke_handler(tauri::generate_handler![
             ensure_miner_and_account,
             start_miner,
+            run_benchmark,
+            cancel_benchmark,
             stop_miner,
+            resume_last_session,
+            get_last_session,
+            pause_mining,
+            resume_mining,
             read_log_tail,
             query_balance,
+            query_transfers,
+            get_account_address,
+            get_authored_blocks,
+            get_bootnode_probe,
+            get_node_status_now,
+            get_chain_constants,
+            get_last_snapshot,
+            get_node_report,
+            get_node_report_markdown,
+            get_settings,
+            set_settings,
+            set_app_pin,
+            clear_app_pin,
+            get_chain_milestones,
+            get_session_stats,
+            get_port_assignments,
+            get_peer_metrics,
+            get_node_capabilities,
+            get_pending_notifications,
+            open_path,
             select_chain,
+            list_chains,
             repair_miner,
+            cancel_operation,
+            list_operations,
             unlock_miner,
+            restart_with_safe_rpc,
+            regenerate_node_key,
+            set_rewards_address,
+            verify_chain_data,
+            compact_database,
+            enable_safe_mode,
+            import_chain_data,
             get_safe_ranges,
             set_safe_ranges,
+            frontend_ready,
+            get_logfile_path,
+            list_log_files,
+            search_logs,
+            set_log_directory,
+            set_file_logging,
+            get_miner_state,
+            cancel_timed_stop,
+            confirm_safe_mode,
+            set_event_verbosity,
+            get_restart_history,
+            get_orphaned_nodes,
+            adopt_orphaned_node,
+            dismiss_orphaned_node,
+            set_event_severity,
+            get_install_dir,
+            normalize_extra_args,
+            set_install_dir,
+            retry_after_unquarantine,
+            confirm_account_reset,
+            mark_account_backed_up,
+            format_balance,
+            check_app_update,
+            get_app_update_settings,
+            set_app_update_auto_check,
+            get_throttle_settings,
+            set_throttle_settings,
+            get_adaptive_safe_mode,
+            set_adaptive_safe_mode,
+            get_external_crashloop_settings,
+            set_external_crashloop_settings,
+            get_fork_settings,
+            set_fork_settings,
+            get_gui_metrics_settings,
+            set_gui_metrics_settings,
+            get_notable_rules,
+            set_notable_rules,
+            test_notable_event,
+            get_shutdown_settings,
+            set_shutdown_settings,
+            get_system_requirements,
         ])
         .setup(|app| {
+            // Register quantus:// at runtime where the OS needs it (Linux, Windows dev builds).
+            #[cfg(any(target_os = "linux", all(debug_assertions, windows)))]
+            {
+                let _ = app.deep_link().register_all();
+            }
+            // before anything reads the app data directory
+            data_migration::run(app.handle());
+            installer::load_install_dir(app.handle());
+            session_log::load_log_dir(app.handle());
+            paths::check_at_start(app.handle());
+            data_migration::audit();
+            pidfile::init(app.handle());
+            app_update::spawn_daily_check(app.handle().clone());
+            snapshot::spawn_periodic_save(app.handle().clone());
+            outbox::spawn(app.handle().clone());
+            telemetry::spawn_daily_ping(app.handle().clone());
+            throttle::spawn(app.handle().clone());
+            log_space::spawn(app.handle().clone());
+            if let Err(e) = gui_metrics::serve(app.handle()) {
+                eprintln!("{e}");
+            }
+            let handle = app.handle().clone();
+            app.deep_link().on_open_url(move |event| {
+                let urls: Vec<String> = event.urls().iter().map(|u| u.to_string()).collect();
+                let handle = handle.clone();
+                tauri::async_runtime::spawn(async move {
+                    deeplink::handle_urls(&handle, urls).await;
+                });
+            });
+            // Cold start: the launching link is queued until the frontend calls frontend_ready.
+            if let Ok(Some(urls)) = app.deep_link().get_current() {
+                let urls: Vec<String> = urls.iter().map(|u| u.to_string()).collect();
+                let handle = app.handle().clone();
+                tauri::async_runtime::spawn(async move {
+                    deeplink::handle_urls(&handle, urls).await;
+                });
+            }
+
             if let Some(win) = app.get_webview_window("main") {
                 // Try to size to 90% of the primary monitor; fallback to a large default.
                 if let Ok(Some(monitor)) = app.primary_monitor() {
@@ -45,6 +231,11 @@ fn main() {
             }
             Ok(())
         })
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while running tauri application")
+        .run(|app, event| {
+            if let tauri::RunEvent::Exit = event {
+                settings::flush_now(app);
+            }
+        });
 }
@@ -0,0 +1,221 @@
+use anyhow::Result;
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fs,
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+use tauri::AppHandle;
+
+use crate::events;
+
+/// Status updates further apart than this mean the node wasn't running in between; the
+/// gap isn't counted as runtime.
+const MAX_TICK_GAP: Duration = Duration::from_secs(60);
+/// Runtime alone is written at most this often (milestones are written right away).
+const RUNTIME_SAVE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// How this installation got to its first full sync of a chain
+/// (`{app_data_dir}/milestones.json`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ChainMilestones {
+    /// First node start for the chain (unix seconds); the genesis sync starts here.
+    #[serde(alias = "first_start_at")]
+    pub first_start_at: Option<i64>,
+    /// First status with `is_syncing == false` and peers (unix seconds).
+    #[serde(alias = "first_synced_at")]
+    pub first_synced_at: Option<i64>,
+    /// `first_synced_at - first_start_at`.
+    #[serde(alias = "sync_wall_secs")]
+    pub sync_wall_secs: Option<i64>,
+    /// Time the node ran (with status updates) until then.
+    #[serde(alias = "sync_runtime_secs")]
+    pub sync_runtime_secs: u64,
+    /// Repairs (database wipes) done before it.
+    #[serde(alias = "repairs_before_sync")]
+    pub repairs_before_sync: u32,
+}
+
+/// `miner:sync-complete` payload, sent once per chain per installation.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncComplete {
+    pub chain: String,
+    pub best_block: Option<u64>,
+    #[serde(flatten)]
+    pub milestones: ChainMilestones,
+    pub ts: i64,
+}
+
+struct Ticks {
+    // last status update per chain, for the runtime count
+    last: HashMap<String, Instant>,
+    last_save: Option<Instant>,
+}
+
+lazy_static! {
+    static ref MILESTONES: std::sync::Mutex<Option<HashMap<String, ChainMilestones>>> =
+        std::sync::Mutex::new(None);
+    static ref TICKS: std::sync::Mutex<Ticks> = std::sync::Mutex::new(Ticks {
+        last: HashMap::new(),
+        last_save: None,
+    });
+}
+
+fn milestones_path(app: &AppHandle) -> PathBuf {
+    crate::paths::app_data_dir(app).join("milestones.json")
+}
+
+fn load_all(app: &AppHandle) -> HashMap<String, ChainMilestones> {
+    fs::read(milestones_path(app))
+        .ok()
+        .and_then(|b| serde_json::from_slice(&b).ok())
+        .unwrap_or_default()
+}
+
+fn save_all(app: &AppHandle, all: &HashMap<String, ChainMilestones>) -> Result<()> {
+    let path = milestones_path(app);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    crate::persist::write_atomic(&path, &serde_json::to_vec_pretty(all)?)
+}
+
+// Change `chain`'s milestones (loaded on first use); `f` returns whether to save now.
+fn update<T>(
+    app: &AppHandle,
+    chain: &str,
+    f: impl FnOnce(&mut ChainMilestones) -> (bool, T),
+) -> Option<T> {
+    let mut guard = MILESTONES.lock().ok()?;
+    let all = guard.get_or_insert_with(|| load_all(app));
+    let (save, out) = f(all.entry(chain.to_string()).or_default());
+    if save {
+        if let Err(e) = save_all(app, all) {
+            eprintln!("milestones: {e}");
+        }
+    }
+    Some(out)
+}
+
+/// `chain`'s milestones so far (all empty for a chain never started).
+pub fn get(app: &AppHandle, chain: &str) -> ChainMilestones {
+    update(app, chain, |m| (false, m.clone())).unwrap_or_default()
+}
+
+/// A node for `chain` was started; the first one starts the clock.
+pub fn note_start(app: &AppHandle, chain: &str) {
+    update(app, chain, |m| {
+        let first = m.first_start_at.is_none();
+        if first {
+            m.first_start_at = Some(crate::timeutil::now_secs());
+        }
+        (first, ())
+    });
+}
+
+/// `chain`'s database was wiped by Repair; counted until its first full sync.
+pub fn note_repair(app: &AppHandle, chain: &str) {
+    update(app, chain, |m| {
+        let counted = m.first_synced_at.is_none();
+        if counted {
+            m.repairs_before_sync += 1;
+        }
+        (counted, ())
+    });
+}
+
+/// Every status update of a running node: adds to the runtime until the first full sync
+/// and records (and announces with `miner:sync-complete`) that sync when it happens.
+pub fn note_status(
+    app: &AppHandle,
+    chain: &str,
+    is_syncing: Option<bool>,
+    peers: Option<u32>,
+    best_block: Option<u64>,
+) {
+    let now = Instant::now();
+    let (elapsed, save_runtime) = {
+        let Ok(mut ticks) = TICKS.lock() else {
+            return;
+        };
+        let elapsed = ticks
+            .last
+            .insert(chain.to_string(), now)
+            .map(|t| now.duration_since(t))
+            .filter(|d| *d <= MAX_TICK_GAP)
+            .unwrap_or_default();
+        let save = ticks
+            .last_save
+            .is_none_or(|t| now.duration_since(t) >= RUNTIME_SAVE_INTERVAL);
+        if save {
+            ticks.last_save = Some(now);
+        }
+        (elapsed, save)
+    };
+    let synced = is_syncing == Some(false) && peers.unwrap_or(0) > 0;
+    let completed = update(app, chain, |m| {
+        if m.first_synced_at.is_some() {
+            return (false, None);
+        }
+        m.sync_runtime_secs += elapsed.as_secs();
+        let now_secs = crate::timeutil::now_secs();
+        // an adopted node may be the first we ever saw of this chain
+        let started = *m.first_start_at.get_or_insert(now_secs);
+        if !synced {
+            return (save_runtime, None);
+        }
+        m.first_synced_at = Some(now_secs);
+        m.sync_wall_secs = Some(now_secs - started);
+        (true, Some(m.clone()))
+    })
+    .flatten();
+    let Some(milestones) = completed else {
+        return;
+    };
+    crate::session_log::ui(
+        app,
+        chain,
+        format!(
+            "First full sync of {chain} complete after {} ({} of node runtime, {} repair(s)).",
+            human_duration(milestones.sync_wall_secs.unwrap_or(0) as u64),
+            human_duration(milestones.sync_runtime_secs),
+            milestones.repairs_before_sync
+        ),
+    );
+    crate::session_log::audit(
+        chain,
+        "first-sync-complete",
+        &format!(
+            "wall {}s, runtime {}s, repairs {}",
+            milestones.sync_wall_secs.unwrap_or(0),
+            milestones.sync_runtime_secs,
+            milestones.repairs_before_sync
+        ),
+    );
+    events::emit(
+        app,
+        events::MINER_SYNC_COMPLETE,
+        &SyncComplete {
+            chain: chain.to_string(),
+            best_block,
+            milestones,
+            ts: crate::timeutil::now_ms(),
+        },
+    );
+}
+
+// "3d 4h", "2h 5m", "7m"
+fn human_duration(secs: u64) -> String {
+    let (d, h, m) = (secs / 86_400, secs % 86_400 / 3600, secs % 3600 / 60);
+    if d > 0 {
+        format!("{d}d {h}h")
+    } else if h > 0 {
+        format!("{h}h {m}m")
+    } else {
+        format!("{m}m")
+    }
+}
@@ -1,30 +1,45 @@
 use anyhow::{anyhow, Result};
 use lazy_static::lazy_static;
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, fs, path::PathBuf, process::Stdio, time::Duration};
-use tauri::{AppHandle, Emitter, Manager};
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    fs,
+    path::{Path, PathBuf},
+    process::Stdio,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+use tauri::AppHandle;
 use tokio::{
     io::{AsyncBufReadExt, BufReader},
     process::Command,
     sync::Mutex,
 };
 
-use crate::account_cli::AccountJson;
 use crate::account_path::account_json_path;
-use crate::parse::parse_event;
-
-#[derive(Debug, Clone, Serialize)]
-struct LogMsg {
-    source: &'static str,
-    line: String,
-}
+use crate::chains::{self, ResolvedChain};
+use crate::events;
+use crate::ops::CancelToken;
+use crate::parse::{parse_event, EventDedupe, MinerEvent, Severity, Stream};
+use crate::phase::{self, MinerPhase};
+use crate::plan::{DryRun, Manifest};
+use crate::restart::{self, RestartOp, RestartReason};
+use crate::shutdown::NodeExit;
 
 #[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
 struct MinerMeta {
     // From our own start context
     binary: Option<String>,
+    // Checked against the installer's record at start (`provenance.rs`)
+    binary_provenance: Option<crate::provenance::ProvenanceCheck>,
     chain: Option<String>,
     rewards_address: Option<String>,
+    // Portable mode and standard directories replaced (`paths.rs`)
+    paths: Option<crate::paths::PathsReport>,
 
     // From startup logs
     version: Option<String>,
@@ -36,6 +51,16 @@ struct MinerMeta {
     jsonrpc_addr: Option<String>,
     prometheus_addr: Option<String>,
     highest_known_block: Option<u64>,
+    // Ports the node was started with (see `PortAssignments`)
+    rpc_port: Option<u16>,
+    prometheus_port: Option<u16>,
+    p2p_port: Option<u16>,
+    // Threads passed to the node's built-in miner
+    internal_mining_threads: Option<u32>,
+
+    // From state_getRuntimeVersion (spec_name, spec_version, impl_version)
+    #[serde(flatten)]
+    runtime: Option<RuntimeVersion>,
 
     // System info
     os: Option<String>,
@@ -49,6 +74,33 @@ struct MinerMeta {
     vm: Option<String>,
 }
 
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RuntimeVersion {
+    spec_name: Option<String>,
+    spec_version: u64,
+    impl_version: Option<u64>,
+}
+
+/// Payload of `miner:runtime-upgraded`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RuntimeUpgrade {
+    chain: String,
+    spec_name: Option<String>,
+    from: u64,
+    to: u64,
+    /// Block import failed with a runtime-version error since the upgrade: the node
+    /// binary is likely too old.
+    suggest_update: bool,
+    error: Option<String>,
+    /// Tag of the latest quantus-node release (when suggesting an update).
+    latest_node_release: Option<String>,
+}
+
+/// Runtime version is re-read once the best block moved this far.
+const RUNTIME_CHECK_BLOCKS: u64 = 100;
+
 // Update MinerMeta with interesting values parsed from a single stderr log line.
 // Returns true if any field changed.
 fn update_meta_from_line(meta: &mut MinerMeta, line: &str) -> bool {
@@ -175,37 +227,851 @@ fn update_meta_from_line(meta: &mut MinerMeta, line: &str) -> bool {
     changed
 }
 
+// Runtime state is keyed by chain (UI name) so several chains can run side by side.
 lazy_static! {
-    static ref MINER: Mutex<Option<tokio::process::Child>> = Mutex::new(None);
-    // external parallel miner handle; if running, we stop it on shutdown
-    static ref EXT_MINER: Mutex<Option<crate::installer::ExternalMinerHandle>> = Mutex::new(None);
-    static ref LAST_CFG: Mutex<Option<MinerConfig>> = Mutex::new(None);
-    static ref REPAIRING: Mutex<bool> = Mutex::new(false);
-    // When true, we are currently running with '--max-blocks-per-request 1'
-    static ref SAFE_MODE_ACTIVE: Mutex<bool> = Mutex::new(false);
-    // A pending request to enable/disable safe mode detected by the stderr reader.
-    static ref SAFE_MODE_PENDING: Mutex<Option<bool>> = Mutex::new(None);
+    static ref MINERS: Mutex<HashMap<String, tokio::process::Child>> = Mutex::new(HashMap::new());
+    // Nodes left running by a crashed GUI and taken over (pid only: no pipes, no handle).
+    static ref ADOPTED: Mutex<HashMap<String, u32>> = Mutex::new(HashMap::new());
+    // external parallel miner handles; if running, we stop them on shutdown
+    static ref EXT_MINERS: Mutex<HashMap<String, crate::installer::ExternalMinerHandle>> =
+        Mutex::new(HashMap::new());
+    static ref LAST_CFGS: Mutex<HashMap<String, MinerConfig>> = Mutex::new(HashMap::new());
+    // Most recently started chain; commands without an explicit chain target it.
+    static ref ACTIVE_CHAIN: Mutex<Option<String>> = Mutex::new(None);
+    static ref REPAIRING: Mutex<HashSet<String>> = Mutex::new(HashSet::new());
+    // Chains currently running with '--max-blocks-per-request 1', and why ("range" | "adaptive")
+    static ref SAFE_MODE_ACTIVE: Mutex<HashMap<String, &'static str>> = Mutex::new(HashMap::new());
+    // Pending requests to enable/disable safe mode (with reason) detected by the stderr readers.
+    static ref SAFE_MODE_PENDING: Mutex<HashMap<String, crate::safe_mode_countdown::Toggle>> =
+        Mutex::new(HashMap::new());
+    // Chains whose mining is paused (`pause_mining`) and how.
+    static ref PAUSED: Mutex<HashMap<String, PauseMode>> = Mutex::new(HashMap::new());
+    // Chains the user started with their own '--max-blocks-per-request'; automation leaves them alone.
+    static ref MANUAL_SAFE_MODE: Mutex<HashSet<String>> = Mutex::new(HashSet::new());
     // Per-chain troublesome ranges (loaded/saved from a simple JSON file in app data dir).
     pub static ref SAFE_RANGES: Mutex<std::collections::HashMap<String, Vec<(u64, u64)>>> =
         Mutex::new(load_safe_ranges_or_default());
-    // Dynamic local RPC endpoint discovered from logs. Default to 127.0.0.1:9944.
-    pub static ref LOCAL_WS_URL: Mutex<String> = Mutex::new(crate::rpc::local_ws_endpoint().to_string());
+    // Local RPC endpoint per chain (assigned at start, refined from logs). Default 127.0.0.1:9944.
+    static ref LOCAL_WS_URLS: Mutex<HashMap<String, String>> = Mutex::new(HashMap::new());
+    // Ports each running node was started with.
+    static ref PORTS: Mutex<HashMap<String, PortAssignments>> = Mutex::new(HashMap::new());
+    // Generation of the current status task per chain (see spawn_status_task).
+    static ref STATUS_GENS: Mutex<HashMap<String, u64>> = Mutex::new(HashMap::new());
+    // Latest values from the node's own status lines per chain (RPC-free fallback).
+    static ref LOG_STATUS: Mutex<HashMap<String, LogStatus>> = Mutex::new(HashMap::new());
+    // Network height per chain: max of log targets, "highest known block" and bootnode
+    // heads; never decreases within a node session.
+    static ref NETWORK_HEIGHTS: Mutex<HashMap<String, u64>> = Mutex::new(HashMap::new());
+    // Chains whose status task must drop its RPC connection and resubscribe on the next
+    // loop; `true` also forgets best/peers/is_syncing (the database was wiped).
+    static ref RPC_RESET: Mutex<HashMap<String, bool>> = Mutex::new(HashMap::new());
+    // Events below this severity are parsed (and used internally) but not sent to the UI.
+    static ref MIN_EVENT_SEVERITY: Mutex<Severity> = Mutex::new(Severity::Info);
+    // Chains whose node has answered local RPC since it was started (ends the grace period).
+    static ref RPC_UP: Mutex<HashSet<String>> = Mutex::new(HashSet::new());
+    // What each chain's node said about itself at startup, to recognise it over RPC.
+    static ref NODE_IDENTITIES: Mutex<HashMap<String, NodeIdentity>> = Mutex::new(HashMap::new());
+    // Last runtime version seen per chain; kept across node restarts to spot upgrades.
+    static ref RUNTIME_VERSIONS: Mutex<HashMap<String, RuntimeVersion>> = Mutex::new(HashMap::new());
+    // Per chain, the current node session's filter for events mirrored to both streams.
+    static ref EVENT_DEDUPES: Mutex<HashMap<String, Arc<std::sync::Mutex<EventDedupe>>>> =
+        Mutex::new(HashMap::new());
+    // Runtime upgrades seen this node session, until a version error has been reported.
+    static ref RUNTIME_UPGRADES: Mutex<HashMap<String, RuntimeUpgrade>> = Mutex::new(HashMap::new());
+    // Duplicate-identity log lines seen this node session (count, already reported).
+    static ref DUPLICATE_IDENTITY_HITS: Mutex<HashMap<String, (u32, bool)>> =
+        Mutex::new(HashMap::new());
+}
+
+/// Duplicate-identity lines in one node session before we tell the user; a single one
+/// can be a peer's stale dial.
+const DUPLICATE_IDENTITY_THRESHOLD: u32 = 3;
+
+#[derive(Debug, Clone, Default)]
+struct NodeIdentity {
+    // "Chain specification: …" (what system_chain returns)
+    chain_spec: Option<String>,
+    // "Local node identity is: …" (what system_localPeerId returns)
+    peer_id: Option<String>,
+}
+
+/// A node that exits this soon after spawning failed to start (bad flag, DB, port clash).
+const STARTUP_GRACE: Duration = Duration::from_secs(10);
+/// Stderr lines kept for diagnosing a failed start.
+const STARTUP_STDERR_LINES: usize = 50;
+/// How long a node may take to log its startup banner before `miner:start-degraded`.
+const BANNER_TIMEOUT: Duration = Duration::from_secs(60);
+/// How often a running external miner is checked for having exited.
+const EXT_WATCH_INTERVAL: Duration = Duration::from_secs(1);
+/// Pause before restarting an external miner that exited on its own.
+const EXT_RESTART_DELAY: Duration = Duration::from_secs(2);
+/// quantus-node's default ports.
+const DEFAULT_RPC_PORT: u16 = 9944;
+const DEFAULT_PROMETHEUS_PORT: u16 = 9615;
+const DEFAULT_P2P_PORT: u16 = 30333;
+
+/// Progress of one node session's startup banner, shared by the stderr reader and the
+/// `BANNER_TIMEOUT` check.
+struct BannerWatch {
+    done: bool,
+    missing: Vec<&'static str>,
+}
+
+// Core banner fields not logged yet; all present means the node is up.
+fn missing_banner_fields(meta: &MinerMeta) -> Vec<&'static str> {
+    [
+        ("version", &meta.version),
+        ("chain_spec", &meta.chain_spec),
+        ("local_identity", &meta.local_identity),
+        ("jsonrpc_addr", &meta.jsonrpc_addr),
+    ]
+    .into_iter()
+    .filter(|(_, v)| v.is_none())
+    .map(|(name, _)| name)
+    .collect()
+}
+
+/// Payload of `miner:started`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct NodeStarted<'a> {
+    chain: &'a str,
+    meta: &'a MinerMeta,
+    took_ms: u64,
+}
+
+/// Payload of `miner:start-degraded`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct StartDegraded {
+    chain: String,
+    missing: Vec<&'static str>,
+    waited_ms: u64,
+}
+
+/// The node exited during `STARTUP_GRACE`.
+#[derive(Debug, thiserror::Error)]
+#[error("node exited during startup ({status}): {}", .failure.reason)]
+pub struct StartupFailed {
+    pub status: String,
+    pub failure: crate::parse::StartupFailure,
+}
+
+pub async fn set_min_event_severity(level: Severity) {
+    *MIN_EVENT_SEVERITY.lock().await = level;
+}
+
+// Record what the backend needs from a parsed event, then emit it as "miner:event"
+// unless it's below the minimum severity or a repeated Connected.
+async fn forward_event(app: &AppHandle, chain: &str, ev: MinerEvent, connected_seen: &AtomicBool) {
+    note_log_status(chain, &ev).await;
+    crate::sync_phase::note_event(chain, &ev);
+    crate::stats::note_import_event(app, chain, &ev);
+    if matches!(ev, MinerEvent::FoundBlock { .. }) {
+        crate::balance_cache::invalidate_own(app, chain);
+    }
+    if let MinerEvent::Hashrate { hps } = ev {
+        crate::snapshot::update(chain, |c| c.hashrate = Some(hps));
+    }
+    if matches!(ev, MinerEvent::Connected) && connected_seen.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    if ev.severity() < *MIN_EVENT_SEVERITY.lock().await {
+        return;
+    }
+    events::emit(app, events::MINER_EVENT, &ev);
+}
+
+// False for the copy of an event the node also printed on the other stream.
+fn admit_event(dedupe: &std::sync::Mutex<EventDedupe>, stream: Stream, ev: &MinerEvent) -> bool {
+    dedupe
+        .lock()
+        .map_or(true, |mut d| d.admit(stream, ev, std::time::Instant::now()))
+}
+
+/// Events from `chain`'s current node session dropped as stdout/stderr mirrors.
+pub async fn suppressed_duplicate_events(chain: &str) -> u64 {
+    match EVENT_DEDUPES.lock().await.get(chain) {
+        Some(d) => d.lock().map_or(0, |d| d.suppressed),
+        None => 0,
+    }
+}
+
+/// A hashrate reported by the external miner's status endpoint: recorded and emitted
+/// like one parsed from the node's log.
+pub async fn report_hashrate(app: &AppHandle, chain: &str, hps: f64) {
+    forward_event(
+        app,
+        chain,
+        MinerEvent::Hashrate { hps },
+        &AtomicBool::new(false),
+    )
+    .await;
+}
+
+// Ask the chain's status task to reconnect (and optionally forget cached values).
+async fn request_rpc_reset(chain: &str, wiped: bool) {
+    let mut map = RPC_RESET.lock().await;
+    let w = map.entry(chain.to_string()).or_insert(false);
+    *w |= wiped;
+}
+
+// Raise the chain's network height to at least `n`; returns the new value.
+async fn bump_network_height(chain: &str, n: u64) -> u64 {
+    let mut map = NETWORK_HEIGHTS.lock().await;
+    let h = map.entry(chain.to_string()).or_insert(n);
+    *h = (*h).max(n);
+    *h
+}
+
+async fn network_height(chain: &str) -> Option<u64> {
+    NETWORK_HEIGHTS.lock().await.get(chain).copied()
+}
+
+// Percent of the network height we have synced, clamped to 0..=100
+// (the local best may briefly run ahead of a stale target).
+fn sync_percent(best: Option<u64>, network: Option<u64>) -> Option<u8> {
+    match (best, network) {
+        (Some(b), Some(n)) if n > 0 => Some((b.saturating_mul(100) / n).min(100) as u8),
+        _ => None,
+    }
+}
+
+/// Peers/heights parsed from the node's Idle/Syncing/Preparing log lines.
+#[derive(Debug, Clone, Default)]
+struct LogStatus {
+    peers: Option<u32>,
+    best: Option<u64>,
+    finalized: Option<u64>,
+    target: Option<u64>,
+    is_syncing: Option<bool>,
+}
+
+// Remember a NodeStatus event so the status task can fill in what RPC doesn't provide.
+async fn note_log_status(chain: &str, ev: &MinerEvent) {
+    if let MinerEvent::NodeStatus {
+        state,
+        peers,
+        best,
+        finalized,
+        target,
+        ..
+    } = ev
+    {
+        let mut map = LOG_STATUS.lock().await;
+        let s = map.entry(chain.to_string()).or_default();
+        s.peers = peers.or(s.peers);
+        s.best = best.or(s.best);
+        s.finalized = finalized.or(s.finalized);
+        // Idle lines carry no target; keep the last one seen while syncing
+        s.target = target.or(s.target);
+        s.is_syncing = Some(state != "idle");
+        drop(map);
+        if let Some(t) = target {
+            bump_network_height(chain, *t).await;
+        }
+    }
+}
+
+// Fill fields RPC hasn't provided from the log-derived status; RPC values win.
+// Records where the local values came from in `source`, and reports the shared
+// network height as `highest_block` together with the derived `sync_percent`.
+async fn finalize_status(app: &AppHandle, chain: &str, mut s: MinerStatus) -> MinerStatus {
+    let from_rpc = s.peers.is_some() || s.current_block.is_some() || s.is_syncing.is_some();
+    let log = LOG_STATUS
+        .lock()
+        .await
+        .get(chain)
+        .cloned()
+        .unwrap_or_default();
+    let mut from_log = false;
+    if s.peers.is_none() && log.peers.is_some() {
+        s.peers = log.peers;
+        from_log = true;
+    }
+    if s.current_block.is_none() && log.best.is_some() {
+        s.current_block = log.best;
+        from_log = true;
+    }
+    if s.is_syncing.is_none() && log.is_syncing.is_some() {
+        s.is_syncing = log.is_syncing;
+        from_log = true;
+    }
+    if let Some(h) = s.highest_block {
+        bump_network_height(chain, h).await;
+    }
+    s.highest_block = network_height(chain).await;
+    s.sync_percent = sync_percent(s.current_block, s.highest_block);
+    s.finalized_block = s.finalized_block.or(log.finalized);
+    s.paused = PAUSED.lock().await.contains_key(chain);
+    s.mining = !s.paused && s.is_syncing == Some(false);
+    let phase = crate::sync_phase::current(chain, s.is_syncing);
+    s.sync_phase = phase.map(|p| p.0);
+    s.heavy_block = phase.and_then(|p| p.1);
+    if let Some(m) = crate::peer_metrics::current(chain) {
+        s.inbound_peers = m.inbound_peers;
+        s.outbound_peers = m.outbound_peers;
+        s.nat_suspected = m.nat_suspected;
+    }
+    s.source = match (from_rpc, from_log) {
+        (true, true) => Some("mixed"),
+        (true, false) => Some("rpc"),
+        (false, true) => Some("log"),
+        (false, false) => None,
+    };
+    crate::snapshot::update(chain, |c| c.status = serde_json::to_value(&s).ok());
+    crate::milestones::note_status(app, chain, s.is_syncing, s.peers, s.current_block);
+    s
+}
+
+static STATUS_GEN_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Local RPC endpoint of the node for `chain`.
+pub async fn local_ws_url(chain: &str) -> String {
+    LOCAL_WS_URLS
+        .lock()
+        .await
+        .get(chain)
+        .cloned()
+        .unwrap_or_else(|| crate::rpc::local_ws_endpoint().to_string())
+}
+
+// HTTP form of the chain's local RPC endpoint (ws://127.0.0.1:9944 -> http://127.0.0.1:9944).
+pub(crate) async fn local_http_url(chain: &str) -> String {
+    let u = local_ws_url(chain).await;
+    if let Some(rest) = u.strip_prefix("ws://") {
+        format!("http://{}", rest)
+    } else if let Some(rest) = u.strip_prefix("wss://") {
+        format!("https://{}", rest)
+    } else {
+        // fallback
+        u.replace("ws://", "http://").replace("wss://", "https://")
+    }
+}
+
+// One JSON-RPC call to the chain's local node over HTTP; None on any failure.
+async fn local_rpc(
+    chain: &str,
+    method: &str,
+    params: serde_json::Value,
+) -> Option<serde_json::Value> {
+    let body = serde_json::json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+    let resp = crate::net::client()
+        .post(local_http_url(chain).await)
+        .json(&body)
+        .timeout(Duration::from_millis(800))
+        .send()
+        .await
+        .ok()?;
+    let val: serde_json::Value = resp.json().await.ok()?;
+    val.get("result").filter(|r| !r.is_null()).cloned()
+}
+
+// First RPC answer of a node session: check its genesis against the chain it was
+// started as, and record the chain directory's marker for the next start.
+async fn verify_chain_data(app: &AppHandle, chain: &str) {
+    let Some(cfg) = last_cfg(chain).await else {
+        return;
+    };
+    let Some(genesis) = local_rpc(chain, "chain_getBlockHash", serde_json::json!([0]))
+        .await
+        .and_then(|v| v.as_str().map(str::to_string))
+    else {
+        return;
+    };
+    let Ok(chain_dir) = cfg.resolved_chain().map(|r| r.chain_dir()) else {
+        return;
+    };
+    if let Err(m) =
+        crate::chain_guard::verify(chain, cfg.chain_spec_path.as_deref(), &chain_dir, &genesis)
+            .await
+    {
+        crate::session_log::ui(app, chain, m.to_string());
+        events::emit(app, events::MINER_CHAIN_MISMATCH, &m);
+        // syncing on would only add to the wrong database
+        if let Err(e) = stop_for_chain_mismatch(app, chain).await {
+            crate::session_log::ui(app, chain, format!("Stopping the node failed: {e}"));
+        }
+        crate::remediation::offer(
+            app,
+            chain,
+            crate::remediation::ErrorKind::ChainMismatch,
+            &m.to_string(),
+        );
+    }
+}
+
+// Stop `chain`'s node, like `stop_miner`, after its data turned out to be another
+// network's.
+async fn stop_for_chain_mismatch(app: &AppHandle, chain: &str) -> Result<()> {
+    let Some(_guard) = restart::begin(RestartOp::Stop, chain).await? else {
+        return Ok(());
+    };
+    events::emit_state(app, chain, true, "stopping");
+    let is_active = active_chain().await.as_deref() == Some(chain);
+    if is_active {
+        phase::set_phase(app, MinerPhase::Stopping).await;
+    }
+    stop_chain(app, chain).await?;
+    events::emit_state(app, chain, false, "stopped");
+    if is_active {
+        phase::set_phase(
+            app,
+            MinerPhase::Errored {
+                kind: "chain_mismatch".into(),
+            },
+        )
+        .await;
+    }
+    Ok(())
+}
+
+// Read the node's runtime version; update meta and emit `miner:runtime-upgraded` when
+// spec_version went up. False if the node didn't answer.
+async fn check_runtime_version(app: &AppHandle, chain: &str) -> bool {
+    let Some(v) = local_rpc(chain, "state_getRuntimeVersion", serde_json::json!([])).await else {
+        return false;
+    };
+    let Some(spec_version) = v.get("specVersion").and_then(|x| x.as_u64()) else {
+        return false;
+    };
+    let rt = RuntimeVersion {
+        spec_name: v
+            .get("specName")
+            .and_then(|x| x.as_str())
+            .map(str::to_string),
+        spec_version,
+        impl_version: v.get("implVersion").and_then(|x| x.as_u64()),
+    };
+    let prev = RUNTIME_VERSIONS
+        .lock()
+        .await
+        .insert(chain.to_string(), rt.clone());
+    let partial = serde_json::json!({
+        "chain": chain,
+        "specName": rt.spec_name,
+        "specVersion": rt.spec_version,
+        "implVersion": rt.impl_version,
+    });
+    crate::snapshot::merge_meta(chain, &partial);
+    events::emit_meta(app, &partial);
+
+    if let Some(prev) = prev.filter(|p| p.spec_version < spec_version) {
+        let upgrade = RuntimeUpgrade {
+            chain: chain.to_string(),
+            spec_name: rt.spec_name.clone(),
+            from: prev.spec_version,
+            to: spec_version,
+            suggest_update: false,
+            error: None,
+            latest_node_release: None,
+        };
+        crate::session_log::ui(
+            app,
+            chain,
+            format!(
+                "Runtime upgraded on {chain}: spec_version {} -> {spec_version}",
+                prev.spec_version
+            ),
+        );
+        events::emit(app, events::MINER_RUNTIME_UPGRADED, &upgrade);
+        RUNTIME_UPGRADES
+            .lock()
+            .await
+            .insert(chain.to_string(), upgrade);
+    }
+    true
+}
+
+// A runtime-version error after an upgrade: re-emit the upgrade suggesting a node update
+// (once per upgrade).
+async fn note_runtime_version_error(app: &AppHandle, chain: &str, line: &str) {
+    let Some(mut upgrade) = RUNTIME_UPGRADES.lock().await.remove(chain) else {
+        return;
+    };
+    upgrade.suggest_update = true;
+    upgrade.error = Some(line.trim().to_string());
+    crate::remediation::offer(
+        app,
+        chain,
+        crate::remediation::ErrorKind::RuntimeVersion,
+        line,
+    );
+    let (app, chain) = (app.clone(), chain.to_string());
+    // the release lookup may take a while; don't hold up the log reader
+    tauri::async_runtime::spawn(async move {
+        upgrade.latest_node_release =
+            crate::installer::fetch_latest_release("Quantus-Network/chain")
+                .await
+                .ok()
+                .map(|r| r.tag_name);
+        crate::session_log::ui(
+            &app,
+            &chain,
+            format!(
+                "Block import fails after the runtime upgrade to spec_version {}; the node binary likely needs an update{}",
+                upgrade.to,
+                upgrade
+                    .latest_node_release
+                    .as_deref()
+                    .map(|t| format!(" (latest release {t})"))
+                    .unwrap_or_default()
+            ),
+        );
+        events::emit(&app, events::MINER_RUNTIME_UPGRADED, &upgrade);
+    });
+}
+
+// Our block at `height` was imported: count it (once per hash) and tell the UI.
+async fn note_authored_head(app: &AppHandle, chain: &str, height: u64) {
+    let imported_at = std::time::Instant::now();
+    let Some(hash) = local_rpc(chain, "chain_getBlockHash", serde_json::json!([height]))
+        .await
+        .and_then(|h| h.as_str().map(str::to_string))
+    else {
+        return;
+    };
+    if let Some(change) = crate::authorship::record(app, chain, height, &hash).await {
+        if let crate::authorship::Change::Authored(b) = &change {
+            crate::propagation::spawn(app, chain, b.clone(), imported_at);
+        }
+        emit_authorship(app, chain, change).await;
+    }
+}
+
+/// Compare recent authored blocks with the canonical chain; flag reorged-out ones.
+pub(crate) async fn recheck_authored(app: &AppHandle, chain: &str, best: u64) {
+    for b in crate::authorship::recent(chain, best).await {
+        let Some(canonical) =
+            local_rpc(chain, "chain_getBlockHash", serde_json::json!([b.height])).await
+        else {
+            continue;
+        };
+        let on_chain = canonical.as_str() == Some(b.hash.as_str());
+        if let Some(change) = crate::authorship::set_canonical(app, chain, &b.hash, on_chain).await
+        {
+            emit_authorship(app, chain, change).await;
+        }
+    }
+}
+
+async fn emit_authorship(app: &AppHandle, chain: &str, change: crate::authorship::Change) {
+    use crate::authorship::Change;
+    let (kind, block) = match &change {
+        Change::Authored(b) => ("authored", b),
+        Change::Orphaned(b) => ("orphaned", b),
+        Change::Restored(b) => ("restored", b),
+    };
+    let count = crate::authorship::session_count(chain).await;
+    crate::snapshot::update(chain, |c| c.authored = count);
+    crate::session_log::ui(
+        app,
+        chain,
+        format!(
+            "Block #{} {} ({kind}; {count} authored this session)",
+            block.height, block.hash
+        ),
+    );
+    let payload =
+        serde_json::json!({ "chain": chain, "change": kind, "block": block, "count": count });
+    events::emit(app, events::MINER_AUTHORED, &payload);
+    crate::outbox::webhook(app, events::MINER_AUTHORED, &payload);
+    if let Change::Authored(b) = &change {
+        crate::balance_cache::invalidate_own(app, chain);
+        let ev = MinerEvent::FoundBlock {
+            height: Some(b.height),
+            hash: Some(b.hash.clone()),
+            verified: true,
+        };
+        if ev.severity() >= *MIN_EVENT_SEVERITY.lock().await {
+            events::emit(app, events::MINER_EVENT, &ev);
+        }
+    }
+}
+
+/// Last start configuration for `chain`.
+pub async fn last_cfg(chain: &str) -> Option<MinerConfig> {
+    LAST_CFGS.lock().await.get(chain).cloned()
+}
+
+fn saved_cfgs_path(app: &AppHandle) -> PathBuf {
+    crate::paths::app_data_dir(app).join("last_config.json")
+}
+
+fn load_saved_cfgs(app: &AppHandle) -> HashMap<String, MinerConfig> {
+    fs::read(saved_cfgs_path(app))
+        .ok()
+        .and_then(|b| serde_json::from_slice(&b).ok())
+        .unwrap_or_default()
+}
+
+// Remember a user start's config in `{app_data_dir}/last_config.json` (one per chain).
+fn save_cfg(app: &AppHandle, cfg: &MinerConfig) -> Result<()> {
+    let path = saved_cfgs_path(app);
+    let mut all = load_saved_cfgs(app);
+    all.insert(cfg.chain.clone(), cfg.clone());
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    crate::persist::write_atomic(&path, &serde_json::to_vec_pretty(&all)?)
+}
+
+/// A node start as it actually ran, saved after every successful start in
+/// `{app_data_dir}/last_session.json` (one per chain) for `resume_last_session`. Unlike
+/// `last_config.json` (the user's settings) the extra args include flags we injected.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionCfg {
+    pub cfg: MinerConfig,
+    /// Picked because another chain's node was running.
+    pub rpc_port: Option<u16>,
+    pub prometheus_port: Option<u16>,
+    /// Why safe mode was on ("range" | "adaptive"); None when off or set by the user.
+    pub safe_mode: Option<String>,
+    /// `--max-blocks-per-request` came from the user's own extra args.
+    pub manual_safe_mode: bool,
+    pub reason: RestartReason,
+    /// Unix seconds.
+    pub started_at: i64,
+}
+
+// `--rpc-port`/`--prometheus-port` chosen by `start_node`.
+#[derive(Default)]
+struct PickedPorts {
+    rpc: Option<u16>,
+    prometheus: Option<u16>,
+}
+
+/// The ports a chain's node listens on (`get_port_assignments`), whether picked by the
+/// app, given in the extra args or the node's defaults.
+#[derive(Debug, Clone, Serialize)]
+pub struct PortAssignments {
+    pub chain: String,
+    pub rpc: u16,
+    pub prometheus: u16,
+    pub p2p: u16,
+}
+
+/// Port assignments of the running nodes, by chain.
+pub async fn port_assignments() -> Vec<PortAssignments> {
+    let running: Vec<String> = MINERS.lock().await.keys().cloned().collect();
+    let ports = PORTS.lock().await;
+    let mut out: Vec<PortAssignments> = running
+        .iter()
+        .filter_map(|c| ports.get(c).cloned())
+        .collect();
+    out.sort_by(|a, b| a.chain.cmp(&b.chain));
+    out
+}
+
+/// The ports `chain`'s node was started with.
+pub(crate) async fn ports_for(chain: &str) -> Option<PortAssignments> {
+    PORTS.lock().await.get(chain).cloned()
+}
+
+// Whether `args` set `flag` ("--flag N" or "--flag=N").
+fn has_flag(args: &[String], flag: &str) -> bool {
+    args.iter()
+        .any(|a| a == flag || a.strip_prefix(flag).is_some_and(|r| r.starts_with('=')))
+}
+
+// The port `args` give for `flag`, if any.
+fn flag_port(args: &[String], flag: &str) -> Option<u16> {
+    args.iter().enumerate().find_map(|(i, a)| {
+        let v = if a == flag {
+            args.get(i + 1)?.as_str()
+        } else {
+            a.strip_prefix(flag)?.strip_prefix('=')?
+        };
+        v.parse().ok()
+    })
+}
+
+fn sessions_path(app: &AppHandle) -> PathBuf {
+    crate::paths::app_data_dir(app).join("last_session.json")
+}
+
+fn load_sessions(app: &AppHandle) -> HashMap<String, SessionCfg> {
+    fs::read(sessions_path(app))
+        .ok()
+        .and_then(|b| serde_json::from_slice(&b).ok())
+        .unwrap_or_default()
+}
+
+fn save_session(app: &AppHandle, session: SessionCfg) -> Result<()> {
+    let path = sessions_path(app);
+    let mut all = load_sessions(app);
+    all.insert(session.cfg.chain.clone(), session);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    crate::persist::write_atomic(&path, &serde_json::to_vec_pretty(&all)?)
+}
+
+/// The saved session for `chain`, or the most recently started one.
+pub fn last_session(app: &AppHandle, chain: Option<&str>) -> Option<SessionCfg> {
+    let all = load_sessions(app);
+    match chain {
+        Some(c) => all.get(c).cloned(),
+        None => all.into_values().max_by_key(|s| s.started_at),
+    }
+}
+
+/// Start the saved session's node again exactly as it ran: same extra args (safe-mode
+/// flags included), the same picked ports when they are still free, and safe mode
+/// marked active so it is turned off again once past its range. A binary that has gone
+/// missing since is replaced like in `start_miner`.
+pub async fn resume_last_session(app: AppHandle, chain: &str) -> Result<()> {
+    let session =
+        last_session(&app, Some(chain)).ok_or_else(|| anyhow!("no saved session to resume"))?;
+    let SessionCfg {
+        mut cfg,
+        rpc_port,
+        prometheus_port,
+        safe_mode,
+        manual_safe_mode,
+        ..
+    } = session;
+    let chain = cfg.chain.clone();
+    if is_chain_alive(&chain).await {
+        return Err(anyhow!("{chain} is already running"));
+    }
+    cfg.binary_path = crate::installer::ensure_node_binary(&app, &cfg.binary_path, true)
+        .await?
+        .to_string_lossy()
+        .to_string();
+    for (flag, port) in [
+        ("--rpc-port", rpc_port),
+        ("--prometheus-port", prometheus_port),
+    ] {
+        let Some(port) = port else {
+            continue;
+        };
+        if has_flag(&cfg.extra_args, flag) {
+            continue;
+        }
+        if free_local_port(port) == Some(port) {
+            cfg.extra_args.extend([flag.to_string(), port.to_string()]);
+        } else {
+            crate::session_log::ui(
+                &app,
+                &chain,
+                format!("Port {port} from the last session is taken; {flag} is picked again"),
+            );
+        }
+    }
+    {
+        let mut manual = MANUAL_SAFE_MODE.lock().await;
+        if manual_safe_mode {
+            manual.insert(chain.clone());
+        } else {
+            manual.remove(&chain);
+        }
+    }
+    let safe_reason = match safe_mode.as_deref() {
+        Some("adaptive") => Some("adaptive"),
+        Some(_) => Some("range"),
+        None => None,
+    };
+    match safe_reason {
+        Some(r) => SAFE_MODE_ACTIVE.lock().await.insert(chain.clone(), r),
+        None => SAFE_MODE_ACTIVE.lock().await.remove(&chain),
+    };
+    clear_paused(&chain).await;
+    start(
+        app,
+        cfg,
+        RestartReason::ResumeSession,
+        safe_reason.unwrap_or(""),
+    )
+    .await
+}
+
+/// Config to restart `chain` with: this session's last start, else the last user start
+/// saved by an earlier session (so Repair/Unlock work right after the GUI restarted).
+/// The bool is true for a saved one.
+async fn restart_cfg(app: &AppHandle, chain: &str) -> Option<(MinerConfig, bool)> {
+    if let Some(cfg) = last_cfg(chain).await {
+        return Some((cfg, false));
+    }
+    load_saved_cfgs(app).remove(chain).map(|cfg| (cfg, true))
+}
+
+fn note_saved_cfg(app: &AppHandle, chain: &str) {
+    crate::session_log::ui(
+        app,
+        chain,
+        "No node was started in this session; using the configuration saved by the last start.",
+    );
+}
+
+/// Node data dir for `chain`: a `--base-path`/`-d` from its last extra args, else the
+/// default `chains::node_base_path`.
+pub async fn node_base_path_for(chain: &str) -> Result<PathBuf> {
+    let args = last_cfg(chain)
+        .await
+        .map(|c| c.extra_args)
+        .unwrap_or_default();
+    match chains::base_path_from_args(&args) {
+        Some(p) => Ok(p),
+        None => chains::node_base_path(),
+    }
+}
+
+/// `chain` resolved as it last ran (else with the defaults).
+pub async fn resolved_chain_for(chain: &str) -> Result<ResolvedChain> {
+    match last_cfg(chain).await {
+        Some(cfg) => cfg.resolved_chain(),
+        None => chains::resolve(chain, None, &[]),
+    }
+}
+
+/// `{base}/chains/{chain_id}` for `chain` as it last ran (else the defaults).
+pub async fn chain_dir_for(chain: &str) -> Result<PathBuf> {
+    Ok(resolved_chain_for(chain).await?.chain_dir())
+}
+
+/// Why safe mode is on for `chain` ("range" | "adaptive"), and whether the user's own
+/// extra args set it.
+pub async fn safe_mode(chain: &str) -> (Option<&'static str>, bool) {
+    let reason = SAFE_MODE_ACTIVE.lock().await.get(chain).copied();
+    (reason, MANUAL_SAFE_MODE.lock().await.contains(chain))
+}
+
+/// The chain a command targets: the explicit one, else the most recently started.
+pub async fn target_chain(chain: Option<String>) -> Result<String> {
+    match chain {
+        Some(c) => Ok(c),
+        None => ACTIVE_CHAIN
+            .lock()
+            .await
+            .clone()
+            .ok_or_else(|| anyhow!("no previous miner configuration available")),
+    }
+}
+
+/// Most recently started chain; the one the phase machine and UI follow.
+pub async fn active_chain() -> Option<String> {
+    ACTIVE_CHAIN.lock().await.clone()
+}
+
+async fn is_active_chain(chain: &str) -> bool {
+    ACTIVE_CHAIN.lock().await.as_deref() == Some(chain)
 }
 
 // Helpers for per-chain safe-ranges persistence (JSON at data_dir/quantus-miner/safe_ranges.json)
 fn default_safe_ranges() -> HashMap<String, Vec<(u64, u64)>> {
-    let mut m: HashMap<String, Vec<(u64, u64)>> = HashMap::new();
-    // Resonance: performance test produced heavy blocks in these windows.
-    m.insert(
-        "resonance".to_string(),
-        vec![(13300, 13399), (19500, 19599)],
-    );
-    m
+    chains::CHAINS
+        .iter()
+        .filter(|c| !c.default_safe_ranges.is_empty())
+        .map(|c| (c.ui_name.to_string(), c.default_safe_ranges.to_vec()))
+        .collect()
 }
 
 // Global config path (without requiring an AppHandle), used for early initialization.
-fn safe_ranges_config_path_global() -> Option<PathBuf> {
-    dirs::data_dir().map(|p| p.join("quantus-miner").join("safe_ranges.json"))
+fn safe_ranges_config_path_global() -> PathBuf {
+    crate::paths::data_dir()
+        .join("quantus-miner")
+        .join("safe_ranges.json")
 }
 
 #[derive(Deserialize, Serialize)]
@@ -214,47 +1080,39 @@ struct SafeRangesFile {
     chains: HashMap<String, Vec<[u64; 2]>>,
 }
 
+// version 0: the same object without the envelope
+impl crate::persist::Versioned for SafeRangesFile {
+    const VERSION: u32 = 1;
+    const MIGRATIONS: &'static [crate::persist::Migration] = &[crate::persist::unchanged];
+}
+
+impl SafeRangesFile {
+    fn into_map(self) -> HashMap<String, Vec<(u64, u64)>> {
+        self.chains
+            .into_iter()
+            .map(|(k, ranges)| (k, ranges.into_iter().map(|p| (p[0], p[1])).collect()))
+            .collect()
+    }
+}
+
 // Load ranges from global config; fall back to defaults on error.
 fn load_safe_ranges_or_default() -> HashMap<String, Vec<(u64, u64)>> {
-    if let Some(cfg) = safe_ranges_config_path_global() {
-        if let Ok(bytes) = fs::read(&cfg) {
-            if let Ok(sr) = serde_json::from_slice::<SafeRangesFile>(&bytes) {
-                let mut out: HashMap<String, Vec<(u64, u64)>> = HashMap::new();
-                for (k, ranges) in sr.chains {
-                    let v = ranges.into_iter().map(|p| (p[0], p[1])).collect();
-                    out.insert(k, v);
-                }
-                return out;
-            }
-        }
-    }
-    default_safe_ranges()
+    crate::persist::load_versioned::<SafeRangesFile>(&safe_ranges_config_path_global())
+        .map(SafeRangesFile::into_map)
+        .unwrap_or_else(default_safe_ranges)
 }
 
 // App-specific config path (uses app data dir).
-fn safe_ranges_config_path_app(app: &AppHandle) -> Option<PathBuf> {
-    match app.path().app_data_dir() {
-        Ok(p) => Some(p.join("safe_ranges.json")),
-        Err(_) => None,
-    }
+fn safe_ranges_config_path_app(app: &AppHandle) -> PathBuf {
+    crate::paths::app_data_dir(app).join("safe_ranges.json")
 }
 
 // Load ranges preferring the app path; fallback to global path; fallback to defaults.
 fn load_safe_ranges(app: &AppHandle) -> HashMap<String, Vec<(u64, u64)>> {
-    if let Some(p) = safe_ranges_config_path_app(app) {
-        if let Ok(bytes) = fs::read(&p) {
-            if let Ok(sr) = serde_json::from_slice::<SafeRangesFile>(&bytes) {
-                let mut out: HashMap<String, Vec<(u64, u64)>> = HashMap::new();
-                for (k, ranges) in sr.chains {
-                    let v = ranges.into_iter().map(|p| (p[0], p[1])).collect();
-                    out.insert(k, v);
-                }
-                return out;
-            }
-        }
-    }
-    // fallback to global
-    load_safe_ranges_or_default()
+    crate::persist::load_versioned::<SafeRangesFile>(&safe_ranges_config_path_app(app))
+        .map(SafeRangesFile::into_map)
+        // fallback to global
+        .unwrap_or_else(load_safe_ranges_or_default)
 }
 
 // Save ranges to the app data directory (creates parent dirs as needed).
@@ -263,50 +1121,20 @@ pub fn save_safe_ranges(app: &AppHandle, map: &HashMap<String, Vec<(u64, u64)>>)
     for (k, v) in map {
         chains.insert(k.clone(), v.iter().map(|(a, b)| [*a, *b]).collect());
     }
-    let to_write = SafeRangesFile { chains };
-    let json = serde_json::to_vec_pretty(&to_write)?;
-    if let Some(path) = safe_ranges_config_path_app(app) {
-        if let Some(parent) = path.parent() {
-            let _ = fs::create_dir_all(parent);
-        }
-        fs::write(&path, json)?;
-    }
-    Ok(())
+    crate::persist::store_versioned(
+        &safe_ranges_config_path_app(app),
+        &SafeRangesFile { chains },
+    )
 }
 
 // --- Node key helpers ---
-// Base data dir used by quantus-node, e.g. on Linux: ~/.local/share/quantus-node
-fn node_base_path() -> Result<std::path::PathBuf> {
-    let data = dirs::data_dir().ok_or_else(|| anyhow!("no data_dir available"))?;
-    Ok(data.join("quantus-node"))
-}
-
-// On-disk chain id mapping (resonance -> "resonance", etc.)
-fn chain_id_for_ui(chain_ui: &str) -> &str {
-    match chain_ui {
-        "resonance" => "resonance",
-        "heisenberg" => "heisenberg",
-        "quantus" => "quantus",
-        other => other,
-    }
-}
-
-// {base}/chains/{chain_id}/network/secret_dilithium
-fn node_key_file_path_for_chain(chain_id: &str) -> Result<std::path::PathBuf> {
-    Ok(node_base_path()?
-        .join("chains")
-        .join(chain_id)
-        .join("network")
-        .join("secret_dilithium"))
-}
-
 // Ensure the node key exists; if missing, generate it via:
 //   quantus-node key generate-node-key --file <path>
 async fn ensure_node_key_for(
-    chain_id: &str,
+    chain: &ResolvedChain,
     quantus_node_path: &std::path::Path,
 ) -> Result<std::path::PathBuf> {
-    let key_path = node_key_file_path_for_chain(chain_id)?;
+    let key_path = chain.node_key_path();
     if let Some(parent) = key_path.parent() {
         let _ = std::fs::create_dir_all(parent);
     }
@@ -341,7 +1169,57 @@ async fn ensure_node_key_for(
     Ok(key_path)
 }
 
-#[derive(Debug, Clone, Serialize)]
+// Count a duplicate-identity line; at the threshold, explain the cause to the UI
+// (once per node session) and offer `regenerate_node_key`.
+async fn note_duplicate_identity(app: &AppHandle, chain: &str, line: &str) {
+    let count = {
+        let mut hits = DUPLICATE_IDENTITY_HITS.lock().await;
+        let entry = hits.entry(chain.to_string()).or_insert((0, false));
+        entry.0 += 1;
+        if entry.1 || entry.0 < DUPLICATE_IDENTITY_THRESHOLD {
+            return;
+        }
+        entry.1 = true;
+        entry.0
+    };
+    let peer_id = NODE_IDENTITIES
+        .lock()
+        .await
+        .get(chain)
+        .and_then(|i| i.peer_id.clone());
+    let key_path = match last_cfg(chain).await.map(|c| c.resolved_chain()) {
+        Some(Ok(rc)) => Some(rc.node_key_path()),
+        _ => None,
+    };
+    crate::session_log::ui(
+        app,
+        chain,
+        format!(
+            "Another node is using this node's identity ({}); was the data folder copied to another machine?",
+            peer_id.as_deref().unwrap_or("unknown peer id")
+        ),
+    );
+    events::emit(
+        app,
+        events::MINER_DUPLICATE_IDENTITY,
+        &serde_json::json!({
+            "chain": chain,
+            "peerId": peer_id,
+            "keyPath": key_path,
+            "count": count,
+            "line": line.trim(),
+            "explanation": "Another node on the network uses the same network key (secret_dilithium). This happens when a data folder is copied to a second machine; peers disconnect and ban both nodes. Mining to the same rewards address from several machines is fine, but each node needs its own key.",
+        }),
+    );
+    crate::remediation::offer(
+        app,
+        chain,
+        crate::remediation::ErrorKind::DuplicateIdentity,
+        line,
+    );
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MinerConfig {
     pub chain: String, // "resonance" | "heisenberg"
     pub rewards_address: String,
@@ -351,262 +1229,674 @@ pub struct MinerConfig {
     // external parallel miner settings
     pub external_num_cores: Option<usize>, // 1..(available-cores-1)
     pub external_port: Option<u16>,        // e.g., 9833
+    // expert override: raw chain spec file passed as `--chain <file>`
+    pub chain_spec_path: Option<String>,
+    // threads for the node's built-in miner (see `internal_miner`); not with external
+    #[serde(default)]
+    pub internal_mining_threads: Option<u32>,
 }
 
-pub async fn start(app: AppHandle, cfg: MinerConfig) -> Result<()> {
-    // notify UI that a (re)start is in progress so it can flip Start/Stop buttons
-    let _ = app.emit(
-        "miner:state",
-        &serde_json::json!({ "running": false, "phase": "starting" }),
-    );
-    // ensure previous child is stopped
-    stop().await.ok();
+impl MinerConfig {
+    /// CLI arg and on-disk paths for this config, honouring a `--base-path` in the extra
+    /// args (see `chains::resolve`).
+    pub fn resolved_chain(&self) -> Result<ResolvedChain> {
+        chains::resolve(
+            &self.chain,
+            self.chain_spec_path.as_deref().map(Path::new),
+            &self.extra_args,
+        )
+    }
 
-    // create safe_ranges.json if missing (persist current map to app data dir)
-    if let Some(cfg_path) = safe_ranges_config_path_app(&app) {
-        if !cfg_path.exists() {
-            if let Err(e) = save_safe_ranges(&app, &SAFE_RANGES.lock().await.clone()) {
-                let _ = app.emit(
-                    "miner:log",
-                    &LogMsg {
-                        source: "ui",
-                        line: format!("Failed to create safe_ranges.json: {e}"),
-                    },
-                );
-            } else {
-                let _ = app.emit(
-                    "miner:log",
-                    &LogMsg {
-                        source: "ui",
-                        line: format!("Created {}", cfg_path.display()),
-                    },
-                );
-            }
-        }
-    }
-
-    // load user-defined safe ranges (if any) and update the in-memory map
-    {
-        let loaded = load_safe_ranges(&app);
-        let mut guard = SAFE_RANGES.lock().await;
-        *guard = loaded;
+    /// True when started from a raw chain spec file instead of a known chain.
+    pub fn is_custom_chain(&self) -> bool {
+        self.chain_spec_path.is_some()
     }
 
-    let acct_path = account_json_path(&app);
-    let acct = AccountJson::load_from_file(&acct_path)?;
-    // Map UI chain to CLI arg; disable heisenberg until required binary is released
-    let cli_chain = match cfg.chain.as_str() {
-        "resonance" => "live_resonance",
-        "heisenberg" => {
-            return Err(anyhow!(
-                "Heisenberg is not available yet (requires quantus-node 0.1.6-98ceb8de72a)"
-            ));
-        }
-        other => other,
-    };
-
-    // ensure node key exists and fetch its path for the selected chain
-    let chain_id = chain_id_for_ui(&cfg.chain);
-    let node_key_path =
-        ensure_node_key_for(chain_id, std::path::Path::new(&cfg.binary_path)).await?;
-
-    {
-        // remember the last start configuration for potential auto-repair restart
-        let mut last = LAST_CFG.lock().await;
-        *last = Some(cfg.clone());
+    fn resolved_chain_for_start(&self) -> Result<ResolvedChain> {
+        chains::resolve_for_start(
+            &self.chain,
+            self.chain_spec_path.as_deref().map(Path::new),
+            &self.extra_args,
+        )
     }
+}
 
-    let mut args = vec![
-        "--chain".into(),
-        cli_chain.into(),
-        "--validator".into(),
-        "--node-key-file".into(),
-        node_key_path.to_string_lossy().to_string(),
-        "--rewards-address".into(),
-        acct.address.clone(),
-    ];
-    // Choose a new random P2P port in 30333-30999 on every start
-    let p2p_port: u16 = 30333 + (rand::random::<u16>() % (30999 - 30333 + 1));
-    let _ = app.emit(
-        "miner:log",
-        &LogMsg {
-            source: "ui",
-            line: format!("Using randomized P2P port: {}", p2p_port),
-        },
+// Spawn the external miner for `chain` with `cores` (clamped to 1..available-1) on
+// `port`, forward (and optionally tee to a file) its output, and wait briefly for the
+// port. `extra_args` go after the usual ones. Failures are logged to the UI; the node
+// then runs without it.
+async fn start_external_miner(
+    app: &AppHandle,
+    chain: &str,
+    cores: usize,
+    port: u16,
+    log_to_file: bool,
+    extra_args: Vec<String>,
+) {
+    // clamp cores to sane range: 1..(available-1)
+    let max_cores = num_cpus::get().saturating_sub(1).max(1);
+    let want_cores = cores.min(max_cores).max(1);
+    crate::session_log::ui(
+        app,
+        chain,
+        format!(
+            "Starting external miner with --num-cores {} on port {}",
+            want_cores, port
+        ),
     );
-    args.push("--port".into());
-    args.push(p2p_port.to_string());
-    args.extend(cfg.extra_args.clone());
-
-    let bin_path = cfg.binary_path.clone();
-
-    // If external miner is requested, start it first and wait for its HTTP port to accept
-    if let (Some(cores), Some(port)) = (cfg.external_num_cores, cfg.external_port) {
-        // clamp cores to sane range: 1..(available-1)
-        let max_cores = num_cpus::get().saturating_sub(1).max(1);
-        let want_cores = cores.min(max_cores).max(1);
-        let _ = app.emit(
-            "miner:log",
-            &LogMsg {
-                source: "ui",
-                line: format!(
-                    "Starting external miner with --num-cores {} on port {}",
-                    want_cores, port
-                ),
-            },
-        );
-        match crate::installer::spawn_external_miner(crate::installer::ExternalMinerConfig {
-            num_cores: want_cores,
-            port,
-        })
-        .await
-        {
-            Ok(mut handle) => {
-                // external miner file logging when log_to_file is on
-                if cfg.log_to_file {
-                    if let Some(mut p) = dirs::data_local_dir() {
-                        p.push("quantus-miner");
-                        p.push("logs");
-                        let _ = std::fs::create_dir_all(&p);
-                        let pid = handle.child.id().unwrap_or(0);
-                        let ts = time::OffsetDateTime::now_utc()
-                            .format(&time::format_description::well_known::Rfc3339)
-                            .unwrap_or_else(|_| "now".into())
-                            .replace(':', "-");
-                        let fname = format!("quantus-miner-{}-{}.log", pid, ts);
-                        p.push(fname);
-                        if let Ok(f) = std::fs::File::create(&p) {
-                            // Inform UI of external miner logfile path
-                            let _ = app.emit(
-                                "miner:log",
-                                &LogMsg {
-                                    source: "ui",
-                                    line: format!(
-                                        "External miner logging to file: {}",
-                                        p.display()
-                                    ),
-                                },
-                            );
-                            let _ = app.emit(
-                                "miner:logfile",
-                                &serde_json::json!({ "path": p.display().to_string(), "kind": "ext" }),
-                            );
-                            // tee stdout/stderr to file
-                            if let Some(out) = handle.child.stdout.take() {
-                                let mut writer = f.try_clone().ok();
-                                let app_clone2 = app.clone();
-                                tauri::async_runtime::spawn(async move {
-                                    use tokio::io::{AsyncBufReadExt, BufReader};
-                                    let mut reader = BufReader::new(out).lines();
-                                    while let Ok(Some(line)) = reader.next_line().await {
-                                        if let Some(ref mut wf) = writer {
-                                            use std::io::Write;
-                                            let _ = writeln!(wf, "{}", line);
-                                        }
-                                        let _ = app_clone2.emit(
-                                            "miner:log",
-                                            &LogMsg {
-                                                source: "stdout",
-                                                line,
-                                            },
-                                        );
-                                    }
-                                });
-                            }
-                            if let Some(err) = handle.child.stderr.take() {
-                                let mut writer = f.try_clone().ok();
-                                let app_clone2 = app.clone();
-                                tauri::async_runtime::spawn(async move {
-                                    use tokio::io::{AsyncBufReadExt, BufReader};
-                                    let mut reader = BufReader::new(err).lines();
-                                    while let Ok(Some(line)) = reader.next_line().await {
-                                        if let Some(ref mut wf) = writer {
-                                            use std::io::Write;
-                                            let _ = writeln!(wf, "{}", line);
-                                        }
-                                        let _ = app_clone2.emit(
-                                            "miner:log",
-                                            &LogMsg {
-                                                source: "stderr",
-                                                line,
-                                            },
-                                        );
-                                    }
-                                });
-                            }
-                        }
-                    }
-                } else {
-                    // forward external miner stdout/stderr to UI logs (no file)
+    match crate::installer::spawn_external_miner(crate::installer::ExternalMinerConfig {
+        num_cores: want_cores,
+        port,
+        extra_args,
+    })
+    .await
+    {
+        Ok(mut handle) => {
+            // external miner file logging when log_to_file is on
+            if log_to_file {
+                let pid = handle.child.id().unwrap_or(0);
+                if let Ok((p, mut f)) =
+                    crate::session_log::create_log_file(chain, "quantus-miner", pid)
+                {
+                    use std::io::Write;
+                    let _ = writeln!(
+                        f,
+                        "{}",
+                        crate::session_log::header_line(chain, "quantus-miner", pid)
+                    );
+                    // Inform UI of external miner logfile path
+                    crate::session_log::ui(
+                        app,
+                        chain,
+                        format!("External miner logging to file: {}", p.display()),
+                    );
+                    events::emit(
+                        app,
+                        events::MINER_LOGFILE,
+                        &serde_json::json!({ "path": p.display().to_string(), "kind": "ext", "chain": chain }),
+                    );
+                    // tee stdout/stderr to file
                     if let Some(out) = handle.child.stdout.take() {
-                        let app_clone2 = app.clone();
+                        let mut writer = f.try_clone().ok();
+                        let (app_clone2, chain) = (app.clone(), chain.to_string());
                         tauri::async_runtime::spawn(async move {
                             use tokio::io::{AsyncBufReadExt, BufReader};
                             let mut reader = BufReader::new(out).lines();
                             while let Ok(Some(line)) = reader.next_line().await {
-                                let _ = app_clone2.emit(
-                                    "miner:log",
-                                    &LogMsg {
-                                        source: "stdout",
-                                        line,
-                                    },
+                                if let Some(ref mut wf) = writer {
+                                    if crate::session_log::keep_in_file(&line) {
+                                        use std::io::Write;
+                                        let _ = writeln!(wf, "{}", line);
+                                    }
+                                }
+                                crate::session_log::emit(
+                                    &app_clone2,
+                                    &chain,
+                                    "stdout",
+                                    "external",
+                                    line,
                                 );
                             }
                         });
                     }
                     if let Some(err) = handle.child.stderr.take() {
-                        let app_clone2 = app.clone();
+                        let mut writer = f.try_clone().ok();
+                        let (app_clone2, chain) = (app.clone(), chain.to_string());
                         tauri::async_runtime::spawn(async move {
                             use tokio::io::{AsyncBufReadExt, BufReader};
                             let mut reader = BufReader::new(err).lines();
                             while let Ok(Some(line)) = reader.next_line().await {
-                                let _ = app_clone2.emit(
-                                    "miner:log",
-                                    &LogMsg {
-                                        source: "stderr",
-                                        line,
-                                    },
+                                if let Some(ref mut wf) = writer {
+                                    if crate::session_log::keep_in_file(&line) {
+                                        use std::io::Write;
+                                        let _ = writeln!(wf, "{}", line);
+                                    }
+                                }
+                                crate::session_log::emit(
+                                    &app_clone2,
+                                    &chain,
+                                    "stderr",
+                                    "external",
+                                    line,
                                 );
                             }
                         });
                     }
                 }
-
-                // Wait for HTTP port to accept connections (simple TCP connect loop)
-                let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
-                let addr = format!("127.0.0.1:{}", handle.port);
-                let mut ready = false;
-                while std::time::Instant::now() < deadline {
-                    if std::net::TcpStream::connect(&addr).is_ok() {
-                        ready = true;
-                        break;
-                    }
-                    std::thread::sleep(std::time::Duration::from_millis(150));
+            } else {
+                // forward external miner stdout/stderr to UI logs (no file)
+                if let Some(out) = handle.child.stdout.take() {
+                    let (app_clone2, chain) = (app.clone(), chain.to_string());
+                    tauri::async_runtime::spawn(async move {
+                        use tokio::io::{AsyncBufReadExt, BufReader};
+                        let mut reader = BufReader::new(out).lines();
+                        while let Ok(Some(line)) = reader.next_line().await {
+                            crate::session_log::emit(
+                                &app_clone2,
+                                &chain,
+                                "stdout",
+                                "external",
+                                line,
+                            );
+                        }
+                    });
                 }
-                if !ready {
-                    let _ = app.emit(
-                        "miner:log",
-                        &LogMsg {
-                            source: "ui",
-                            line: format!(
-                                "External miner port {} not accepting connections yet; quantus-node may get connection refused until it comes up",
-                                port
-                            ),
-                        },
-                    );
+                if let Some(err) = handle.child.stderr.take() {
+                    let (app_clone2, chain) = (app.clone(), chain.to_string());
+                    tauri::async_runtime::spawn(async move {
+                        use tokio::io::{AsyncBufReadExt, BufReader};
+                        let mut reader = BufReader::new(err).lines();
+                        while let Ok(Some(line)) = reader.next_line().await {
+                            crate::session_log::emit(
+                                &app_clone2,
+                                &chain,
+                                "stderr",
+                                "external",
+                                line,
+                            );
+                        }
+                    });
                 }
-                *EXT_MINER.lock().await = Some(handle);
             }
-            Err(e) => {
-                let _ = app.emit(
-                    "miner:log",
-                    &LogMsg {
-                        source: "ui",
-                        line: format!("Failed to start external miner: {e}"),
-                    },
+
+            // Wait for HTTP port to accept connections (simple TCP connect loop)
+            let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+            let addr = format!("127.0.0.1:{}", handle.port);
+            let mut ready = false;
+            while std::time::Instant::now() < deadline {
+                if std::net::TcpStream::connect(&addr).is_ok() {
+                    ready = true;
+                    break;
+                }
+                std::thread::sleep(std::time::Duration::from_millis(150));
+            }
+            if !ready {
+                crate::session_log::ui(
+                    app,
+                    chain,
+                    format!(
+                        "External miner port {} not accepting connections yet; quantus-node may get connection refused until it comes up",
+                        port
+                    ),
                 );
             }
+            let pid = handle.child.id();
+            EXT_MINERS.lock().await.insert(chain.to_string(), handle);
+            track_external_miner(app, chain, port, pid);
+        }
+        Err(e) => {
+            crate::session_log::ui(app, chain, format!("Failed to start external miner: {e}"));
+        }
+    }
+}
+
+// A new external miner (`pid`) for `chain` on `port`: poll its status endpoint and
+// watch for it exiting.
+fn track_external_miner(app: &AppHandle, chain: &str, port: u16, pid: Option<u32>) {
+    crate::ext_status::spawn(app, chain, port);
+    let (app, chain) = (app.clone(), chain.to_string());
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(EXT_WATCH_INTERVAL).await;
+            // stopping or replacing the miner takes it out of EXT_MINERS first, so an
+            // exit seen here is one nobody asked for
+            let exited = {
+                let mut map = EXT_MINERS.lock().await;
+                let Some(handle) = map.get_mut(&chain) else {
+                    return;
+                };
+                if handle.child.id() != pid {
+                    return;
+                }
+                match handle.child.try_wait() {
+                    Ok(Some(status)) => map.remove(&chain).map(|h| (status, h.num_cores, h.port)),
+                    _ => None,
+                }
+            };
+            if let Some((status, cores, port)) = exited {
+                on_external_exit(app, chain, status, cores, port).await;
+                return;
+            }
+        }
+    });
+}
+
+// `chain`'s external miner exited by itself: restart it, or once it is crash-looping
+// apply the next configured remediation (see `ext_crash`).
+async fn on_external_exit(
+    app: AppHandle,
+    chain: String,
+    status: std::process::ExitStatus,
+    cores: usize,
+    port: u16,
+) {
+    crate::session_log::ui(
+        &app,
+        &chain,
+        format!("External miner exited unexpectedly ({status})"),
+    );
+    crate::session_log::audit(&chain, "external-miner-exited", &status.to_string());
+    let settings = crate::ext_crash::load_settings(&app);
+    if !settings.auto_restart {
+        crate::session_log::ui(&app, &chain, "Not restarting it: automatic restart is off.");
+        return;
+    }
+    tokio::time::sleep(EXT_RESTART_DELAY).await;
+    // a node restart in the meantime brings its own external miner
+    if EXT_MINERS.lock().await.contains_key(&chain) || !is_chain_alive(&chain).await {
+        return;
+    }
+    let log_to_file = last_cfg(&chain).await.is_some_and(|c| c.log_to_file);
+    let Some(crash_loop) = crate::ext_crash::note_exit(&chain, &settings) else {
+        start_external_miner(&app, &chain, cores, port, log_to_file, Vec::new()).await;
+        return;
+    };
+
+    use crate::ext_crash::Remediation;
+    let mut skipped = Vec::new();
+    let mut action = None;
+    for remediation in crash_loop.remaining {
+        match remediation {
+            Remediation::SkipWork => {
+                if !crate::installer::external_miner_supports(&settings.skip_flag).await {
+                    skipped.push(format!("quantus-miner has no {}", settings.skip_flag));
+                    continue;
+                }
+                start_external_miner(
+                    &app,
+                    &chain,
+                    cores,
+                    port,
+                    log_to_file,
+                    vec![settings.skip_flag.clone()],
+                )
+                .await;
+            }
+            Remediation::RestartNode => {
+                let Some(cfg) = last_cfg(&chain).await else {
+                    skipped.push("no previous node configuration".to_string());
+                    continue;
+                };
+                if let Err(e) = restart_for_crash_loop(&app, &chain, cfg).await {
+                    skipped.push(format!("node restart failed: {e}"));
+                    continue;
+                }
+            }
+        }
+        crate::ext_crash::note_remediation(&chain, &settings, remediation);
+        action = Some(remediation);
+        break;
+    }
+
+    let detail = match action {
+        Some(Remediation::SkipWork) => format!(
+            "restarted the external miner with {} to skip the current work",
+            settings.skip_flag
+        ),
+        Some(Remediation::RestartNode) => "restarted the node for a new block template".to_string(),
+        None => "left the external miner stopped; restart mining to try again".to_string(),
+    };
+    let detail = if skipped.is_empty() {
+        detail
+    } else {
+        format!("{detail} ({})", skipped.join("; "))
+    };
+    crate::session_log::ui(
+        &app,
+        &chain,
+        format!(
+            "External miner crashed {} times within {}s: {detail}.",
+            crash_loop.crashes, settings.window_secs
+        ),
+    );
+    let action = match action {
+        Some(Remediation::SkipWork) => "skip_work",
+        Some(Remediation::RestartNode) => "restart_node",
+        None => "gave_up",
+    };
+    crate::session_log::audit(
+        &chain,
+        "external-crashloop",
+        &format!("crashes={} action={action} {detail}", crash_loop.crashes),
+    );
+    events::emit(
+        &app,
+        events::MINER_EXTERNAL_CRASHLOOP,
+        &serde_json::json!({
+            "chain": chain,
+            "crashes": crash_loop.crashes,
+            "windowSecs": settings.window_secs,
+            "exitStatus": status.to_string(),
+            "action": action,
+            "detail": detail,
+            "ts": crate::timeutil::now_ms(),
+        }),
+    );
+}
+
+// Restart `chain`'s node with `cfg` (which also respawns its external miner).
+async fn restart_for_crash_loop(app: &AppHandle, chain: &str, cfg: MinerConfig) -> Result<()> {
+    let Some(_guard) = restart::begin(RestartOp::ExternalCrashLoop, chain).await? else {
+        return Ok(());
+    };
+    phase::set_phase(
+        app,
+        MinerPhase::Restarting {
+            reason: restart::describe(RestartReason::ExternalCrashLoop, ""),
+        },
+    )
+    .await;
+    events::emit_state(app, chain, false, "stopped");
+    stop_chain(app, chain).await?;
+    start(app.clone(), cfg, RestartReason::ExternalCrashLoop, "").await
+}
+
+/// Restart `chain`'s node with its last configuration after `fork.rs` found it on a
+/// dead fork.
+pub(crate) async fn restart_for_fork(app: &AppHandle, chain: &str) -> Result<()> {
+    let cfg = last_cfg(chain)
+        .await
+        .ok_or_else(|| anyhow!("no previous node configuration"))?;
+    let Some(_guard) = restart::begin(RestartOp::ForkDetected, chain).await? else {
+        return Ok(());
+    };
+    phase::set_phase(
+        app,
+        MinerPhase::Restarting {
+            reason: restart::describe(RestartReason::ForkDetected, ""),
+        },
+    )
+    .await;
+    events::emit_state(app, chain, false, "stopped");
+    stop_chain(app, chain).await?;
+    start(app.clone(), cfg, RestartReason::ForkDetected, "").await
+}
+
+/// Start `cfg`'s node; `reason`/`details` go to `restart::announce`.
+pub async fn start(
+    app: AppHandle,
+    cfg: MinerConfig,
+    reason: RestartReason,
+    details: &str,
+) -> Result<()> {
+    restart::announce(&app, &cfg.chain, reason, details);
+    // whatever the UI changed last is on disk before the node depends on it
+    crate::settings::flush_now(&app);
+    if reason == RestartReason::Manual {
+        if let Err(e) = save_cfg(&app, &cfg) {
+            eprintln!("last config: {e}");
+        }
+    }
+    // the phase machine follows the most recently started chain
+    *ACTIVE_CHAIN.lock().await = Some(cfg.chain.clone());
+    if phase::current().await.is_node_up() {
+        phase::set_phase(&app, MinerPhase::Stopping).await;
+    }
+    phase::set_phase(&app, MinerPhase::StartingNode).await;
+    let session_cfg = cfg.clone();
+    match start_node(app.clone(), cfg).await {
+        Ok(ports) => {
+            let chain = &session_cfg.chain;
+            crate::milestones::note_start(&app, chain);
+            crate::fork::note_start(chain);
+            let safe_mode = match reason {
+                RestartReason::SafeModeEnable => Some(details.to_string()),
+                RestartReason::SafeModeDisable => None,
+                _ => SAFE_MODE_ACTIVE
+                    .lock()
+                    .await
+                    .get(chain)
+                    .map(|r| r.to_string()),
+            };
+            let session = SessionCfg {
+                manual_safe_mode: MANUAL_SAFE_MODE.lock().await.contains(chain),
+                cfg: session_cfg,
+                rpc_port: ports.rpc,
+                prometheus_port: ports.prometheus,
+                safe_mode,
+                reason,
+                started_at: crate::timeutil::now_secs(),
+            };
+            if let Err(e) = save_session(&app, session) {
+                eprintln!("last session: {e}");
+            }
+            Ok(())
+        }
+        Err(e) => {
+            let kind = e
+                .downcast_ref::<StartupFailed>()
+                .map_or("start", |f| f.failure.kind);
+            phase::set_phase(&app, MinerPhase::Errored { kind: kind.into() }).await;
+            Err(e)
+        }
+    }
+}
+
+async fn start_node(app: AppHandle, mut cfg: MinerConfig) -> Result<PickedPorts> {
+    // notify UI that a (re)start is in progress so it can flip Start/Stop buttons
+    events::emit_state(&app, &cfg.chain, false, "starting");
+    // ensure the previous child for this chain is stopped
+    stop_chain(&app, &cfg.chain).await.ok();
+    // log-derived status and network height belong to the old process
+    LOG_STATUS.lock().await.remove(&cfg.chain);
+    NETWORK_HEIGHTS.lock().await.remove(&cfg.chain);
+    RPC_UP.lock().await.remove(&cfg.chain);
+    NODE_IDENTITIES.lock().await.remove(&cfg.chain);
+    crate::authorship::reset_session(&cfg.chain).await;
+    RUNTIME_UPGRADES.lock().await.remove(&cfg.chain);
+    DUPLICATE_IDENTITY_HITS.lock().await.remove(&cfg.chain);
+    crate::adaptive::reset_session(&cfg.chain);
+    crate::stats::reset_session(&cfg.chain);
+    crate::peer_metrics::reset_session(&cfg.chain);
+    crate::safe_mode_countdown::reset_session(&cfg.chain);
+    crate::sync_phase::reset_session(&cfg.chain);
+
+    // create safe_ranges.json if missing (persist current map to app data dir)
+    let cfg_path = safe_ranges_config_path_app(&app);
+    if !cfg_path.exists() {
+        if let Err(e) = save_safe_ranges(&app, &SAFE_RANGES.lock().await.clone()) {
+            crate::session_log::ui(
+                &app,
+                &cfg.chain,
+                format!("Failed to create safe_ranges.json: {e}"),
+            );
+        } else {
+            crate::session_log::ui(&app, &cfg.chain, format!("Created {}", cfg_path.display()));
+        }
+    }
+
+    // load user-defined safe ranges (if any) and update the in-memory map
+    {
+        let loaded = load_safe_ranges(&app);
+        let mut guard = SAFE_RANGES.lock().await;
+        *guard = loaded;
+    }
+
+    let acct_path = account_json_path(&app);
+    let acct = crate::account_cli::load_account(&app, &acct_path)?;
+    // refuse to mine to an address nobody can spend from
+    crate::account_cli::check_ss58(&acct_path, &acct)?;
+    crate::account_cli::remind_backup(&app, &cfg.chain, &acct_path, &acct);
+    // what the node gets as --rewards-address, and authorship checks against
+    cfg.rewards_address = crate::account_cli::rewards_address(&app, &acct, &cfg.chain);
+    // Map UI chain (or a custom spec) to CLI arg and on-disk id; refuses unavailable chains
+    let resolved = cfg.resolved_chain_for_start()?;
+
+    // The app was closed while Repair was deleting the database: finish the wipe before
+    // the node can open what's left of it.
+    let db_path = resolved.db_path();
+    if crate::repair::is_pending(&db_path) {
+        crate::session_log::ui(
+            &app,
+            &cfg.chain,
+            format!(
+                "A database repair was interrupted; finishing the wipe of {} before starting...",
+                db_path.display()
+            ),
+        );
+        crate::repair::wipe_db(&app, &cfg.chain, &db_path, true, None).await?;
+        request_rpc_reset(&cfg.chain, true).await;
+    }
+
+    // data left by another network (or a stale custom spec) under the same chain id
+    crate::chain_guard::check(
+        &cfg.chain,
+        cfg.chain_spec_path.as_deref(),
+        &resolved.chain_dir(),
+    )
+    .await?;
+
+    // ensure node key exists and fetch its path for the selected chain
+    let node_key_path =
+        ensure_node_key_for(&resolved, std::path::Path::new(&cfg.binary_path)).await?;
+
+    // Same slot, different spec: drop runtime state that belongs to the previous chain.
+    let chain_changed = {
+        LAST_CFGS
+            .lock()
+            .await
+            .get(&cfg.chain)
+            .is_some_and(|prev| prev.chain_spec_path != cfg.chain_spec_path)
+    };
+    if chain_changed {
+        reset_chain_state(&cfg.chain).await;
+    }
+
+    {
+        // remember the last start configuration for potential auto-repair restart
+        let mut last = LAST_CFGS.lock().await;
+        last.insert(cfg.chain.clone(), cfg.clone());
+    }
+
+    if cfg.is_custom_chain() {
+        crate::session_log::ui(
+            &app,
+            &cfg.chain,
+            format!(
+                "Using custom chain spec {} (on-disk id \"{}\"); bootnode height, balance and safe sync ranges are unsupported for custom chains",
+                resolved.cli_arg, resolved.chain_id
+            ),
+        );
+    }
+
+    // What this binary accepts (unknown if its --help can't be read; nothing is gated
+    // then): a missing required flag fails the start, optional features are skipped.
+    let caps = crate::node_caps::note_start(&app, &cfg.chain, Path::new(&cfg.binary_path)).await;
+    if let Some(caps) = &caps {
+        caps.require()?;
+    }
+
+    // a paused chain restarts the same way: without --validator, or without its external miner
+    let paused = PAUSED.lock().await.get(&cfg.chain).copied();
+    let mut args: Vec<String> = vec!["--chain".into(), resolved.cli_arg.clone()];
+    if paused != Some(PauseMode::NoValidator) {
+        args.push("--validator".into());
+    }
+    args.extend([
+        "--node-key-file".into(),
+        node_key_path.to_string_lossy().to_string(),
+        "--rewards-address".into(),
+        cfg.rewards_address.clone(),
+    ]);
+    // Ports: the user's own flags win. Otherwise the node's defaults while they are free
+    // and no other chain runs here, else the next free ones, so a second chain or a
+    // second instance doesn't collide on RPC, Prometheus or P2P.
+    let others_running = {
+        MINERS
+            .lock()
+            .await
+            .keys()
+            .any(|c| c.as_str() != cfg.chain.as_str())
+    };
+    let pick = |default: u16| {
+        if !others_running && free_local_port(default) == Some(default) {
+            None
+        } else {
+            free_local_port(default + 1)
+        }
+    };
+    let mut picked = PickedPorts::default();
+    if !has_flag(&cfg.extra_args, "--rpc-port") {
+        if let Some(port) = pick(DEFAULT_RPC_PORT) {
+            args.extend(["--rpc-port".into(), port.to_string()]);
+            picked.rpc = Some(port);
+        }
+    }
+    if !has_flag(&cfg.extra_args, "--prometheus-port") {
+        if let Some(port) = pick(DEFAULT_PROMETHEUS_PORT) {
+            args.extend(["--prometheus-port".into(), port.to_string()]);
+            picked.prometheus = Some(port);
+        }
+    }
+    // A new random P2P port in 30333-30999 on every start (the next free one from there)
+    let mut p2p_port = flag_port(&cfg.extra_args, "--port");
+    if !has_flag(&cfg.extra_args, "--port") {
+        let from: u16 = 30333 + (rand::random::<u16>() % (30999 - 30333 + 1));
+        let port = free_local_port(from).unwrap_or(from);
+        crate::session_log::ui(
+            &app,
+            &cfg.chain,
+            format!("Using randomized P2P port: {port}"),
+        );
+        args.extend(["--port".into(), port.to_string()]);
+        p2p_port = Some(port);
+    }
+    let ports = PortAssignments {
+        chain: cfg.chain.clone(),
+        rpc: picked
+            .rpc
+            .or_else(|| flag_port(&cfg.extra_args, "--rpc-port"))
+            .unwrap_or(DEFAULT_RPC_PORT),
+        prometheus: picked
+            .prometheus
+            .or_else(|| flag_port(&cfg.extra_args, "--prometheus-port"))
+            .unwrap_or(DEFAULT_PROMETHEUS_PORT),
+        p2p: p2p_port.unwrap_or(DEFAULT_P2P_PORT),
+    };
+    LOCAL_WS_URLS
+        .lock()
+        .await
+        .insert(cfg.chain.clone(), format!("ws://127.0.0.1:{}", ports.rpc));
+    PORTS.lock().await.insert(cfg.chain.clone(), ports.clone());
+    // the node's own miner; `start_miner` checked the count and the combination
+    let internal_threads = cfg.internal_mining_threads.filter(|_| paused.is_none());
+    let internal_flag = caps.as_ref().and_then(crate::internal_miner::threads_flag);
+    let internal_threads = match (internal_threads, internal_flag) {
+        (Some(threads), Some(flag)) => {
+            args.extend([flag.to_string(), threads.to_string()]);
+            Some(threads)
         }
+        (Some(_), None) => {
+            crate::session_log::ui(
+                &app,
+                &cfg.chain,
+                "This quantus-node has no option for its miner's thread count; starting with its default",
+            );
+            None
+        }
+        (None, _) => None,
+    };
+    args.extend(cfg.extra_args.clone());
+    if crate::node_caps::chain_lacks(&cfg.chain, crate::node_caps::SAFE_SYNC)
+        && has_max_blocks_arg(&args)
+    {
+        remove_max_blocks_arg(&mut args);
+        crate::session_log::ui(
+            &app,
+            &cfg.chain,
+            "This quantus-node has no --max-blocks-per-request; starting without safe sync (update the node to use it)",
+        );
+    }
+
+    let bin_path = cfg.binary_path.clone();
+
+    let external = match (cfg.external_num_cores, cfg.external_port) {
+        (Some(cores), Some(port)) if paused.is_none() => Some((cores, port)),
+        _ => None,
+    };
+    // If external miner is requested, start it first and wait for its HTTP port to accept
+    if let Some((cores, port)) = external {
+        start_external_miner(&app, &cfg.chain, cores, port, cfg.log_to_file, Vec::new()).await;
     }
 
     // Now build and spawn quantus-node
@@ -614,131 +1904,106 @@ pub async fn start(app: AppHandle, cfg: MinerConfig) -> Result<()> {
 
     // Emit planned command for UI (binary + args), matching what we will actually run.
     // We haven't pushed args into `cmd` yet, so emit using the args vector we built.
-    let _ = app.emit(
-        "miner:log",
-        &LogMsg {
-            source: "ui",
-            line: format!("Planned command: {} {}", bin_path, args.join(" ")),
-        },
+    crate::session_log::ui(
+        &app,
+        &cfg.chain,
+        format!("Planned command: {} {}", bin_path, args.join(" ")),
     );
 
+    // for the RPC exposure check once the node reports its listen address
+    let node_args = args.clone();
     cmd.args(args)
         .stdin(Stdio::null())
         .stdout(Stdio::piped())
         .stderr(Stdio::piped());
 
-    let mut child = cmd.spawn().map_err(|e| anyhow!("spawn miner: {e}"))?;
+    let mut child = cmd.spawn().map_err(|e| {
+        crate::installer::diagnose_spawn_error("miner", std::path::Path::new(&bin_path), e)
+    })?;
 
     let stdout = child.stdout.take().unwrap();
     let stderr = child.stderr.take().unwrap();
 
-    // spawn external parallel miner if requested
-    if let (Some(cores), Some(port)) = (cfg.external_num_cores, cfg.external_port) {
-        // clamp cores to sane range: 1..(available-1)
-        let max_cores = num_cpus::get().saturating_sub(1).max(1);
-        let want_cores = cores.min(max_cores).max(1);
-        let _ = app.emit(
-            "miner:log",
-            &LogMsg {
-                source: "ui",
-                line: format!(
-                    "Starting external miner with --num-cores {} on port {}",
-                    want_cores, port
-                ),
-            },
-        );
-        match crate::installer::spawn_external_miner(crate::installer::ExternalMinerConfig {
-            num_cores: want_cores,
-            port,
-        })
-        .await
-        {
-            Ok(handle) => {
-                *EXT_MINER.lock().await = Some(handle);
-            }
-            Err(e) => {
-                let _ = app.emit(
-                    "miner:log",
-                    &LogMsg {
-                        source: "ui",
-                        line: format!("Failed to start external miner: {e}"),
-                    },
-                );
-            }
-        }
-    }
-
-    // Prepare optional file logger
-    let mut log_file: Option<std::fs::File> = None;
+    // Session log file for the node (a new file per start; closed when logging is off)
     if cfg.log_to_file {
-        if let Some(mut p) = dirs::data_local_dir() {
-            // Use an app-specific log dir
-            p.push("quantus-miner");
-            p.push("logs");
-            let _ = std::fs::create_dir_all(&p);
-            // Include PID in filename
-            let pid = child.id().unwrap_or(0);
-            let ts = time::OffsetDateTime::now_utc()
-                .format(&time::format_description::well_known::Rfc3339)
-                .unwrap_or_else(|_| "now".into())
-                .replace(':', "-");
-            let fname = format!("quantus-node-{}-{}.log", pid, ts);
-            p.push(fname);
-            if let Ok(f) = std::fs::File::create(&p) {
-                log_file = Some(f);
-                // Inform UI of logfile path (node)
-                let _ = app.emit(
-                    "miner:log",
-                    &LogMsg {
-                        source: "ui",
-                        line: format!("Node logging to file: {}", p.display()),
-                    },
-                );
-                let _ = app.emit(
-                    "miner:logfile",
-                    &serde_json::json!({ "path": p.display().to_string(), "kind": "node" }),
-                );
-            }
+        let pid = child.id().unwrap_or(0);
+        if let Err(e) = crate::session_log::open(&app, &cfg.chain, pid, false).await {
+            crate::session_log::ui(
+                &app,
+                &cfg.chain,
+                format!("Failed to open node log file: {e}"),
+            );
         }
+    } else {
+        crate::session_log::close(&app, &cfg.chain).await;
     }
 
     // Emit initial meta snapshot with known context
-    let _ = app.emit(
-        "miner:meta",
-        &MinerMeta {
-            binary: Some(cfg.binary_path.clone()),
-            chain: Some(cfg.chain.clone()),
-            rewards_address: Some(acct.address.clone()),
-            ..Default::default()
-        },
-    );
+    let provenance =
+        crate::provenance::check_at_start(&app, &cfg.chain, Path::new(&cfg.binary_path)).await;
+    let initial_meta = MinerMeta {
+        binary: Some(cfg.binary_path.clone()),
+        binary_provenance: provenance,
+        chain: Some(cfg.chain.clone()),
+        rewards_address: Some(cfg.rewards_address.clone()),
+        paths: Some(crate::paths::report(&app)),
+        rpc_port: Some(ports.rpc),
+        prometheus_port: Some(ports.prometheus),
+        p2p_port: Some(ports.p2p),
+        internal_mining_threads: internal_threads,
+        ..Default::default()
+    };
+    crate::snapshot::update(&cfg.chain, |c| c.meta = None);
+    crate::snapshot::merge_meta(&cfg.chain, &initial_meta);
+    events::emit_meta(&app, &initial_meta);
     // include a status snapshot that also carries safe mode
-    let _ = app.emit(
-        "miner:status",
+    events::emit_status(
+        &app,
         &serde_json::json!({
             "peers": null,
-            "current_block": null,
-            "highest_block": null,
-            "is_syncing": null,
-            "safe_mode": *SAFE_MODE_ACTIVE.lock().await
+            "currentBlock": null,
+            "highestBlock": null,
+            "isSyncing": null,
+            "safeMode": SAFE_MODE_ACTIVE.lock().await.contains_key(&cfg.chain),
+            "chain": cfg.chain
         }),
     );
 
+    // shared by both readers: Connected is only forwarded once per node session
+    let connected_seen = Arc::new(AtomicBool::new(false));
+    let dedupe = Arc::new(std::sync::Mutex::new(EventDedupe::default()));
+    EVENT_DEDUPES
+        .lock()
+        .await
+        .insert(cfg.chain.clone(), dedupe.clone());
+
     let app_clone = app.clone();
-    // Clone a file handle for stdout task if enabled
-    let log_file_stdout = log_file.as_ref().and_then(|f| f.try_clone().ok());
+    let chain_for_stdout = cfg.chain.clone();
+    let connected_seen_out = connected_seen.clone();
+    let dedupe_out = dedupe.clone();
     tauri::async_runtime::spawn(async move {
+        let chain = chain_for_stdout;
+        let connected_seen = connected_seen_out;
         let mut reader = BufReader::new(stdout).lines();
-        let mut file = log_file_stdout;
         while let Ok(Some(line)) = reader.next_line().await {
-            if let Some(ev) = parse_event(&line) {
-                let _ = app_clone.emit("miner:event", &ev);
-            }
-            // write to file if enabled
-            if let Some(ref mut fh) = file {
-                use std::io::Write;
-                let _ = writeln!(fh, "{}", line);
+            let parsed = parse_event(&line);
+            let admitted = parsed
+                .as_ref()
+                .is_some_and(|ev| admit_event(&dedupe_out, Stream::Stdout, ev));
+            events::emit_parse_debug(
+                &app_clone,
+                &chain,
+                "stdout",
+                &line,
+                parsed.as_ref(),
+                admitted,
+            );
+            if let (true, Some(ev)) = (admitted, parsed) {
+                forward_event(&app_clone, &chain, ev, &connected_seen).await;
             }
+            // ring buffer + session file (if enabled)
+            crate::session_log::record(&chain, &line);
+            crate::sync_phase::note_line(&chain, &line);
             // parse a dynamic local RPC ws url from occasional log lines, e.g.:
             // "Running JSON-RPC server: addr=127.0.0.1:9944,[::1]:9944"
             if let Some(pos) = line.find("Running JSON-RPC server: addr=") {
@@ -746,73 +2011,88 @@ pub async fn start(app: AppHandle, cfg: MinerConfig) -> Result<()> {
                 if let Some(end) = rest.find(',') {
                     let addr = rest[..end].trim();
                     if !addr.is_empty() {
-                        // update shared LOCAL_WS_URL so status task reconnects to the right port
+                        // update this chain's endpoint so its status task reconnects to the right port
                         {
-                            let mut u = LOCAL_WS_URL.lock().await;
-                            *u = format!("ws://{}", addr);
+                            let mut u = LOCAL_WS_URLS.lock().await;
+                            u.insert(chain.clone(), format!("ws://{}", addr));
                         }
-                        let _ = app_clone.emit(
-                            "miner:log",
-                            &LogMsg {
-                                source: "ui",
-                                line: format!("Detected local RPC endpoint: ws://{}", addr),
-                            },
+                        crate::session_log::ui(
+                            &app_clone,
+                            &chain,
+                            format!("Detected local RPC endpoint: ws://{}", addr),
                         );
                     }
                 }
             }
-            let _ = app_clone.emit(
-                "miner:log",
-                &LogMsg {
-                    source: "stdout",
-                    line: line.clone(),
-                },
-            );
+            events::emit_log(&app_clone, "stdout", line.clone());
         }
     });
 
+    // last stderr lines, for explaining an early exit
+    let startup_stderr = Arc::new(std::sync::Mutex::new(VecDeque::new()));
+
+    let spawned_at = tokio::time::Instant::now();
+    let banner = Arc::new(std::sync::Mutex::new(BannerWatch {
+        done: false,
+        missing: missing_banner_fields(&MinerMeta::default()),
+    }));
+
     let app_clone = app.clone();
-    // Clone a file handle for stderr task if enabled
-    let log_file_stderr = log_file.as_ref().and_then(|f| f.try_clone().ok());
+    let chain_for_stderr = cfg.chain.clone();
+    let startup_stderr_in = startup_stderr.clone();
+    let banner_in = banner.clone();
     tauri::async_runtime::spawn(async move {
+        let chain = chain_for_stderr;
+        let connected_seen = connected_seen;
         let mut reader = BufReader::new(stderr).lines();
-        let mut meta = MinerMeta::default();
-        let mut file = log_file_stderr;
+        let mut meta = MinerMeta {
+            chain: Some(chain.clone()),
+            ..Default::default()
+        };
+        let mut banner_seen = false;
         while let Ok(Some(line)) = reader.next_line().await {
-            // surface stderr as logs; parse too (some miners log success to stderr)
-            if let Some(ev) = parse_event(&line) {
-                let _ = app_clone.emit("miner:event", &ev);
+            if let Ok(mut buf) = startup_stderr_in.lock() {
+                if buf.len() >= STARTUP_STDERR_LINES {
+                    buf.pop_front();
+                }
+                buf.push_back(line.clone());
             }
-            // write to file if enabled
-            if let Some(ref mut fh) = file {
-                use std::io::Write;
-                let _ = writeln!(fh, "{}", line);
+            // surface stderr as logs; parse too (some miners log success to stderr)
+            let parsed = parse_event(&line);
+            let admitted = parsed
+                .as_ref()
+                .is_some_and(|ev| admit_event(&dedupe, Stream::Stderr, ev));
+            events::emit_parse_debug(
+                &app_clone,
+                &chain,
+                "stderr",
+                &line,
+                parsed.as_ref(),
+                admitted,
+            );
+            if let (true, Some(ev)) = (admitted, parsed) {
+                forward_event(&app_clone, &chain, ev, &connected_seen).await;
             }
+            // ring buffer + session file (if enabled)
+            crate::session_log::record(&chain, &line);
+            crate::sync_phase::note_line(&chain, &line);
             let low = line.to_lowercase();
-            let _ = app_clone.emit(
-                "miner:log",
-                &LogMsg {
-                    source: "stderr",
-                    line: line.clone(),
-                },
-            );
+            events::emit_log(&app_clone, "stderr", line.clone());
             // detect and update dynamic local RPC endpoint from stderr too
             if let Some(pos) = line.find("Running JSON-RPC server: addr=") {
                 let rest = &line[pos + "Running JSON-RPC server: addr=".len()..];
                 if let Some(end) = rest.find(',') {
                     let addr = rest[..end].trim();
                     if !addr.is_empty() {
-                        // update shared LOCAL_WS_URL so status task reconnects to the right port
+                        // update this chain's endpoint so its status task reconnects to the right port
                         {
-                            let mut u = LOCAL_WS_URL.lock().await;
-                            *u = format!("ws://{}", addr);
+                            let mut u = LOCAL_WS_URLS.lock().await;
+                            u.insert(chain.clone(), format!("ws://{}", addr));
                         }
-                        let _ = app_clone.emit(
-                            "miner:log",
-                            &LogMsg {
-                                source: "ui",
-                                line: format!("Detected local RPC endpoint: ws://{}", addr),
-                            },
+                        crate::session_log::ui(
+                            &app_clone,
+                            &chain,
+                            format!("Detected local RPC endpoint: ws://{}", addr),
                         );
                     }
                 }
@@ -833,7 +2113,15 @@ pub async fn start(app: AppHandle, cfg: MinerConfig) -> Result<()> {
                 }
                 if let Ok(cur_block) = num_str.parse::<u64>() {
                     // Determine chain to select applicable ranges
-                    let chain_ui = { LAST_CFG.lock().await.as_ref().map(|c| c.chain.clone()) };
+                    // Known heavy ranges only apply to known chains, not custom specs
+                    let chain_ui = {
+                        LAST_CFGS
+                            .lock()
+                            .await
+                            .get(&chain)
+                            .filter(|c| !c.is_custom_chain())
+                            .map(|c| c.chain.clone())
+                    };
                     if let Some(chain_name) = chain_ui {
                         // Load ranges for current chain from settings (fallback to default map)
                         let ranges_vec = {
@@ -848,9 +2136,11 @@ pub async fn start(app: AppHandle, cfg: MinerConfig) -> Result<()> {
                         let ranges_slice: Vec<(u64, u64)> = ranges_vec;
                         let ranges = &ranges_slice;
                         // Are we inside any troublesome range?
-                        let in_range = ranges
+                        let hit = ranges
                             .iter()
-                            .any(|(s, e)| cur_block >= *s && cur_block <= *e);
+                            .find(|(s, e)| cur_block >= *s && cur_block <= *e)
+                            .copied();
+                        let in_range = hit.is_some();
                         // Consider "past_all" only when we're clearly beyond the end of all ranges
                         // by a safety margin to avoid flapping near the boundary.
                         let safety_margin: u64 = 50;
@@ -862,67 +2152,326 @@ pub async fn start(app: AppHandle, cfg: MinerConfig) -> Result<()> {
                         let _approaching =
                             !past_all && cur_block >= min_start.saturating_sub(pre_window);
 
-                        let active_now = { *SAFE_MODE_ACTIVE.lock().await };
+                        let active_now = { SAFE_MODE_ACTIVE.lock().await.get(&chain).copied() };
+                        let repairing = { REPAIRING.lock().await.contains(&chain) };
+                        let manual = { MANUAL_SAFE_MODE.lock().await.contains(&chain) };
+                        crate::stats::note_block(
+                            &app_clone,
+                            &chain,
+                            cur_block,
+                            active_now.is_some() || manual,
+                            manual,
+                            past_all && !in_range,
+                        );
+                        let unsupported =
+                            crate::node_caps::chain_lacks(&chain, crate::node_caps::SAFE_SYNC);
                         // Request enable when approaching/in-range and not yet active
-                        if !active_now && in_range {
+                        if repairing || manual || unsupported {
+                            // no safe-mode scheduling while the database is being wiped,
+                            // when the user set --max-blocks-per-request themselves,
+                            // or when the node doesn't have it
+                        } else if active_now.is_none() && in_range {
+                            let toggle = crate::safe_mode_countdown::Toggle {
+                                enable: true,
+                                reason: "range",
+                                range: hit,
+                            };
                             let mut pend = SAFE_MODE_PENDING.lock().await;
-                            *pend = Some(true);
-                            let _ = app_clone.emit(
-                                "miner:log",
-                                &LogMsg {
-                                    source: "ui",
-                                    line: format!("Approaching heavy blocks at #{cur_block}. Scheduling safe sync enable (--max-blocks-per-request 1)..."),
-                                },
-                            );
-                        // Request disable when past all ranges and currently active
-                        } else if active_now && past_all && !in_range {
+                            // once per range: the countdown leaves it pending a while
+                            if !pend.contains_key(&chain)
+                                && !crate::safe_mode_countdown::is_vetoed(&chain, toggle)
+                            {
+                                pend.insert(chain.clone(), toggle);
+                                crate::session_log::ui(
+                                    &app_clone,
+                                    &chain,
+                                    format!("Approaching heavy blocks at #{cur_block}. Scheduling safe sync enable (--max-blocks-per-request 1)..."),
+                                );
+                            }
+                        // Request disable when past all ranges and active because of them
+                        // (adaptive safe mode is turned off by its own healthy timer)
+                        } else if active_now == Some("range") && past_all && !in_range {
+                            let toggle = crate::safe_mode_countdown::Toggle {
+                                enable: false,
+                                reason: "range",
+                                range: None,
+                            };
                             let mut pend = SAFE_MODE_PENDING.lock().await;
-                            *pend = Some(false);
-                            let _ = app_clone.emit(
-                                "miner:log",
-                                &LogMsg {
-                                    source: "ui",
-                                    line: format!("Past heavy block range(s) at #{cur_block}. Scheduling safe sync disable..."),
-                                },
-                            );
+                            if !pend.contains_key(&chain)
+                                && !crate::safe_mode_countdown::is_vetoed(&chain, toggle)
+                            {
+                                pend.insert(chain.clone(), toggle);
+                                crate::session_log::ui(
+                                    &app_clone,
+                                    &chain,
+                                    format!("Past heavy block range(s) at #{cur_block}. Scheduling safe sync disable..."),
+                                );
+                            }
                         }
                     }
                 }
             }
 
-            // Update and emit miner meta if this line contains interesting info.
-            if update_meta_from_line(&mut meta, &line) {
-                let _ = app_clone.emit("miner:meta", &meta);
+            // Adaptive safe mode: repeated slow imports / request timeouts while syncing,
+            // wherever the heavy blocks are (the static ranges only know old ones).
+            if let Some(trouble) = crate::parse::sync_trouble(&line) {
+                let settings = crate::adaptive::load_settings(&app_clone);
+                let syncing = {
+                    LOG_STATUS
+                        .lock()
+                        .await
+                        .get(&chain)
+                        .and_then(|s| s.is_syncing)
+                        == Some(true)
+                };
+                let toggle = crate::safe_mode_countdown::Toggle {
+                    enable: true,
+                    reason: "adaptive",
+                    range: None,
+                };
+                let blocked = { REPAIRING.lock().await.contains(&chain) }
+                    || { MANUAL_SAFE_MODE.lock().await.contains(&chain) }
+                    || { SAFE_MODE_ACTIVE.lock().await.contains_key(&chain) }
+                    || crate::node_caps::chain_lacks(&chain, crate::node_caps::SAFE_SYNC)
+                    || crate::safe_mode_countdown::is_vetoed(&chain, toggle);
+                if settings.enabled && syncing && !blocked {
+                    if let Some(n) = crate::adaptive::note_trouble(&chain, &settings) {
+                        let (open, first) = crate::adaptive::breaker_open(&chain, &settings);
+                        let msg = if open {
+                            first.then(|| format!(
+                                "Sync keeps stalling ({n} slow imports/timeouts in {}s), but safe sync was toggled {} times within {}s; not restarting again for now.",
+                                settings.window_secs, settings.max_auto_restarts, settings.restart_window_secs
+                            ))
+                        } else {
+                            SAFE_MODE_PENDING
+                                .lock()
+                                .await
+                                .entry(chain.clone())
+                                .or_insert(toggle);
+                            Some(format!(
+                                "Sync keeps stalling ({n} slow imports/timeouts in {}s, last: {trouble:?}). Scheduling adaptive safe sync enable (--max-blocks-per-request 1)...",
+                                settings.window_secs
+                            ))
+                        };
+                        if let Some(line) = msg {
+                            crate::session_log::ui(&app_clone, &chain, line);
+                        }
+                    }
+                }
+            }
+
+            // Update and emit miner meta if this line contains interesting info.
+            let prev_rpc_addr = meta.jsonrpc_addr.clone();
+            let mut meta_changed = update_meta_from_line(&mut meta, &line);
+            if meta.jsonrpc_addr != prev_rpc_addr {
+                warn_if_rpc_exposed(&app_clone, &chain, meta.jsonrpc_addr.as_deref(), &node_args);
+            }
+            // "highest known block" is only logged at startup; follow the network height after
+            if let Some(n) = meta.highest_known_block {
+                let h = bump_network_height(&chain, n).await;
+                if meta.highest_known_block != Some(h) {
+                    meta.highest_known_block = Some(h);
+                    meta_changed = true;
+                }
+            } else if let Some(h) = network_height(&chain).await {
+                meta.highest_known_block = Some(h);
+                meta_changed = true;
+            }
+            if meta_changed {
+                NODE_IDENTITIES.lock().await.insert(
+                    chain.clone(),
+                    NodeIdentity {
+                        chain_spec: meta.chain_spec.clone(),
+                        peer_id: meta.local_identity.clone(),
+                    },
+                );
+                meta.runtime = RUNTIME_VERSIONS.lock().await.get(&chain).cloned();
+                crate::snapshot::merge_meta(&chain, &meta);
+                events::emit_meta(&app_clone, &meta);
+                if !banner_seen {
+                    let missing = missing_banner_fields(&meta);
+                    banner_seen = missing.is_empty();
+                    if let Ok(mut b) = banner_in.lock() {
+                        b.done = banner_seen;
+                        b.missing = missing;
+                    }
+                    if banner_seen {
+                        note_node_started(&app_clone, &chain, &meta, spawned_at.elapsed()).await;
+                    }
+                }
+            }
+
+            if crate::parse::is_runtime_version_error(&line) {
+                note_runtime_version_error(&app_clone, &chain, &line).await;
+            }
+            if crate::parse::is_duplicate_identity(&line) {
+                note_duplicate_identity(&app_clone, &chain, &line).await;
             }
 
             // Detect RocksDB corruption that needs a DB wipe and full resync:
             // "Invalid argument: Column families not opened: col12, col11, ..."
             if low.contains("invalid argument: column families not opened") {
-                // Backend will not auto-repair here to avoid non-Send spawn issues.
-                // Emit a hint so the UI can offer a "Repair" action that calls `repair_and_restart`.
-                let _ = app_clone.emit(
-                    "miner:log",
-                    &LogMsg {
-                        source: "ui",
-                        line: "Detected RocksDB corruption. Please use Repair to wipe the database and restart (full resync will be required).".into(),
-                    },
+                // Not repaired automatically: the UI offers Repair (`remediation`).
+                crate::session_log::ui(
+                    &app_clone,
+                    &chain,
+                    "Detected RocksDB corruption; the database has to be wiped and synced again.",
+                );
+                crate::remediation::offer(
+                    &app_clone,
+                    &chain,
+                    crate::remediation::ErrorKind::DbCorrupt,
+                    &line,
                 );
             }
         }
     });
 
     // spawn a background task that periodically queries the local node JSON-RPC
-    spawn_status_task(app.clone());
-    *MINER.lock().await = Some(child);
+    spawn_status_task(app.clone(), cfg.chain.clone());
+    let pid = child.id();
+    MINERS.lock().await.insert(cfg.chain.clone(), child);
+    // lets a GUI that restarts after a crash find this node again
+    if let Some(pid) = pid {
+        let rpc_url = local_ws_url(&cfg.chain).await;
+        if let Err(e) = crate::pidfile::write(&cfg.chain, pid, &rpc_url, &cfg) {
+            eprintln!("pidfile: {e}");
+        }
+    }
+    phase::set_phase(&app, MinerPhase::WaitingForRpc).await;
+    spawn_banner_timeout(app.clone(), cfg.chain.clone(), pid, banner, spawned_at);
     // notify UI that process is now running
-    let _ = app.emit(
-        "miner:state",
-        &serde_json::json!({ "running": true, "phase": "running" }),
+    events::emit_state(&app, &cfg.chain, true, "running");
+    await_startup(&app, &cfg.chain, pid, &startup_stderr).await?;
+    Ok(picked)
+}
+
+/// The node logged its whole startup banner: emit `miner:started` and leave
+/// `WaitingForRpc` (RPC replies move the phase on from there).
+async fn note_node_started(app: &AppHandle, chain: &str, meta: &MinerMeta, took: Duration) {
+    events::emit(
+        app,
+        events::MINER_STARTED,
+        &NodeStarted {
+            chain,
+            meta,
+            took_ms: took.as_millis() as u64,
+        },
     );
-    Ok(())
+    if is_active_chain(chain).await {
+        let paused = PAUSED.lock().await.contains_key(chain);
+        phase::set_phase_from(
+            app,
+            &MinerPhase::WaitingForRpc,
+            phase_for_syncing(None, paused),
+        )
+        .await;
+    }
+}
+
+// After `BANNER_TIMEOUT`, report which banner fields the node `pid` still hasn't logged
+// (nothing if it has, or if it was stopped or replaced meanwhile).
+fn spawn_banner_timeout(
+    app: AppHandle,
+    chain: String,
+    pid: Option<u32>,
+    banner: Arc<std::sync::Mutex<BannerWatch>>,
+    spawned_at: tokio::time::Instant,
+) {
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep_until(spawned_at + BANNER_TIMEOUT).await;
+        let missing = match banner.lock() {
+            Ok(b) if !b.done => b.missing.clone(),
+            _ => return,
+        };
+        let ours = match MINERS.lock().await.get(&chain) {
+            Some(child) => pid.is_some() && child.id() == pid,
+            None => false,
+        };
+        if !ours {
+            return;
+        }
+        crate::session_log::ui(
+            &app,
+            &chain,
+            format!(
+                "Node startup is incomplete after {}s (not logged yet: {}). A slow disk delays this; a node stuck here may be broken.",
+                BANNER_TIMEOUT.as_secs(),
+                missing.join(", ")
+            ),
+        );
+        events::emit(
+            &app,
+            events::MINER_START_DEGRADED,
+            &StartDegraded {
+                chain,
+                missing,
+                waited_ms: BANNER_TIMEOUT.as_millis() as u64,
+            },
+        );
+    });
+}
+
+/// Watch a freshly spawned node until it answers RPC or `STARTUP_GRACE` passes.
+/// If it exits first, clean up, emit `miner:start-failed` and return `StartupFailed`.
+async fn await_startup(
+    app: &AppHandle,
+    chain: &str,
+    pid: Option<u32>,
+    stderr: &std::sync::Mutex<VecDeque<String>>,
+) -> Result<()> {
+    let deadline = tokio::time::Instant::now() + STARTUP_GRACE;
+    loop {
+        if RPC_UP.lock().await.contains(chain) {
+            return Ok(());
+        }
+        let exited = {
+            let mut miners = MINERS.lock().await;
+            match miners.get_mut(chain) {
+                Some(child) if child.id() == pid => child.try_wait().ok().flatten(),
+                // stopped or replaced meanwhile: not ours to judge
+                _ => return Ok(()),
+            }
+        };
+        if let Some(status) = exited {
+            // give the stderr reader a moment to drain the last lines
+            tokio::time::sleep(Duration::from_millis(300)).await;
+            let lines: Vec<String> = stderr
+                .lock()
+                .map(|b| b.iter().cloned().collect())
+                .unwrap_or_default();
+            let failure = crate::parse::classify_startup_failure(&lines);
+            stop_chain(app, chain).await.ok();
+            events::emit_state(app, chain, false, "stopped");
+            events::emit(
+                app,
+                events::MINER_START_FAILED,
+                &serde_json::json!({
+                    "chain": chain,
+                    "exitCode": status.code(),
+                    "kind": failure.kind,
+                    "reason": failure.reason,
+                    "lines": lines,
+                }),
+            );
+            if let Some(kind) = crate::remediation::ErrorKind::from_startup(failure.kind) {
+                crate::remediation::offer(app, chain, kind, &failure.reason);
+            }
+            return Err(StartupFailed {
+                status: status.to_string(),
+                failure,
+            }
+            .into());
+        }
+        if tokio::time::Instant::now() >= deadline {
+            return Ok(());
+        }
+        tokio::time::sleep(Duration::from_millis(250)).await;
+    }
 }
 
 #[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
 struct MinerStatus {
     peers: Option<u32>,
     current_block: Option<u64>,
@@ -931,10 +2480,32 @@ struct MinerStatus {
     bootnode_connected: Option<bool>,
     bootnode_host: Option<String>,
     bootnode_stale_secs: Option<u64>,
+    // chain this snapshot belongs to, so the UI can drop stale ones after a switch
+    chain: Option<String>,
+    // from the node's log lines only (RPC doesn't report it here)
+    finalized_block: Option<u64>,
+    // where peers/best/is_syncing came from: "rpc", "log" or "mixed"
+    source: Option<&'static str>,
+    // current_block / highest_block, clamped at 100
+    sync_percent: Option<u8>,
+    // UTC milliseconds of the last successful local RPC reply; older values are stale
+    last_rpc_success_ts: Option<i64>,
+    // the local RPC endpoint answers as a different node; its values are ignored
+    foreign_node: bool,
+    // set by finalize_status: producing blocks, or paused by pause_mining
+    mining: bool,
+    paused: bool,
+    // set by finalize_status from the node's Prometheus exporter (`peer_metrics.rs`)
+    inbound_peers: Option<u64>,
+    outbound_peers: Option<u64>,
+    nat_suspected: bool,
+    // set by finalize_status while syncing (`sync_phase.rs`); heavy_block when "executing"
+    sync_phase: Option<crate::sync_phase::SyncPhase>,
+    heavy_block: Option<crate::sync_phase::HeavyBlock>,
 }
 
 /// Attempt to parse a u64 from a JSON value that may be a number or a 0x-prefixed hex string.
-fn parse_u64_from_json(v: &serde_json::Value) -> Option<u64> {
+pub(crate) fn parse_u64_from_json(v: &serde_json::Value) -> Option<u64> {
     match v {
         serde_json::Value::Number(n) => n.as_u64(),
         serde_json::Value::String(s) => {
@@ -949,94 +2520,156 @@ fn parse_u64_from_json(v: &serde_json::Value) -> Option<u64> {
     }
 }
 
-/// Query the local Substrate JSON-RPC (ws://127.0.0.1:9944) for health and sync state.
-#[allow(dead_code)]
-async fn query_local_node_status() -> Result<MinerStatus> {
-    let url = "ws://127.0.0.1:9944";
-    let (mut ws, _) = tokio_tungstenite::connect_async(url)
-        .await
-        .map_err(|e| anyhow!("ws connect: {e}"))?;
-
-    // Prepare requests
-    let req_health = serde_json::json!({
-        "jsonrpc": "2.0",
-        "id": 1,
-        "method": "system_health",
-        "params": []
-    });
-    let req_sync = serde_json::json!({
-        "jsonrpc": "2.0",
-        "id": 2,
-        "method": "system_syncState",
-        "params": []
-    });
+/// Fresh answer from a chain's local node: `system_health` and `system_syncState`.
+#[derive(Debug, Clone, Serialize)]
+pub struct LocalNodeStatus {
+    pub chain: String,
+    pub rpc_url: String,
+    /// Whether either call got an answer at all.
+    pub reachable: bool,
+    pub peers: Option<u32>,
+    pub is_syncing: Option<bool>,
+    pub current_block: Option<u64>,
+    /// The node's own view of the network height (not the bootnode's).
+    pub highest_block: Option<u64>,
+    pub sync_percent: Option<u8>,
+    /// UTC milliseconds since the epoch.
+    pub checked_at: i64,
+}
+
+/// Query `chain`'s local node for health and sync state (both calls concurrently via
+/// `local_rpc`). Used by the status task and by `get_node_status_now`.
+pub async fn query_local_node_status(chain: &str) -> LocalNodeStatus {
+    let (health, sync) = tokio::join!(
+        local_rpc(chain, "system_health", serde_json::json!([])),
+        local_rpc(chain, "system_syncState", serde_json::json!([])),
+    );
+    let current_block = sync
+        .as_ref()
+        .and_then(|s| s.get("currentBlock"))
+        .and_then(parse_u64_from_json);
+    let highest_block = sync
+        .as_ref()
+        .and_then(|s| s.get("highestBlock"))
+        .and_then(parse_u64_from_json);
+    LocalNodeStatus {
+        chain: chain.to_string(),
+        rpc_url: local_http_url(chain).await,
+        reachable: health.is_some() || sync.is_some(),
+        peers: health
+            .as_ref()
+            .and_then(|h| h.get("peers"))
+            .and_then(|x| x.as_u64())
+            .map(|x| x as u32),
+        is_syncing: health
+            .as_ref()
+            .and_then(|h| h.get("isSyncing"))
+            .and_then(|x| x.as_bool()),
+        current_block,
+        highest_block,
+        sync_percent: sync_percent(current_block, highest_block),
+        checked_at: crate::timeutil::now_ms(),
+    }
+}
 
+/// Ask the node behind `ws` who it is. Some(reason) if it isn't `chain`'s node (another
+/// Substrate node on the same port); None if it matches or can't tell yet.
+async fn foreign_node_reason(ws: &mut crate::net::WsStream, chain: &str) -> Option<String> {
     use futures_util::{SinkExt, StreamExt};
     use tokio_tungstenite::tungstenite::Message;
 
-    ws.send(Message::Text(req_health.to_string()))
+    let expected = NODE_IDENTITIES
+        .lock()
         .await
-        .map_err(|e| anyhow!("ws send health: {e}"))?;
-    ws.send(Message::Text(req_sync.to_string()))
-        .await
-        .map_err(|e| anyhow!("ws send syncState: {e}"))?;
-
-    let mut peers: Option<u32> = None;
-    let mut is_syncing: Option<bool> = None;
-    let mut current_block: Option<u64> = None;
-    let mut highest_block: Option<u64> = None;
-
-    // Collect responses with a timeout
-    let _ = tokio::time::timeout(Duration::from_millis(1500), async {
-        while peers.is_none()
-            || current_block.is_none()
-            || highest_block.is_none()
-            || is_syncing.is_none()
-        {
-            match ws.next().await {
-                Some(Ok(Message::Text(txt))) => {
-                    if let Ok(val) = serde_json::from_str::<serde_json::Value>(&txt) {
-                        let id = val.get("id").and_then(|x| x.as_i64()).unwrap_or_default();
-                        let res = val
-                            .get("result")
-                            .cloned()
-                            .unwrap_or(serde_json::Value::Null);
-                        if id == 1 {
-                            // system_health
-                            peers = res.get("peers").and_then(|x| x.as_u64()).map(|x| x as u32);
-                            is_syncing = res.get("isSyncing").and_then(|x| x.as_bool());
-                        } else if id == 2 {
-                            // system_syncState
-                            current_block = res.get("currentBlock").and_then(parse_u64_from_json);
-                            highest_block = res.get("highestBlock").and_then(parse_u64_from_json);
-                        }
-                    }
-                }
-                Some(Ok(_)) => continue,
-                Some(Err(_e)) => break,
-                None => break,
-            }
+        .get(chain)
+        .cloned()
+        .unwrap_or_default();
+    if expected.chain_spec.is_none() && expected.peer_id.is_none() {
+        return None;
+    }
+    for (id, method) in [(3001, "system_chain"), (3002, "system_localPeerId")] {
+        let req = serde_json::json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": [] });
+        ws.send(Message::Text(req.to_string())).await.ok()?;
+    }
+    let (mut got_chain, mut got_peer) = (None, None);
+    let deadline = tokio::time::Instant::now() + Duration::from_millis(1500);
+    while got_chain.is_none() || got_peer.is_none() {
+        let Ok(Some(Ok(Message::Text(txt)))) = tokio::time::timeout_at(deadline, ws.next()).await
+        else {
+            break;
+        };
+        let Ok(val) = serde_json::from_str::<serde_json::Value>(&txt) else {
+            continue;
+        };
+        let result = val
+            .get("result")
+            .and_then(|r| r.as_str())
+            .map(str::to_string);
+        match val.get("id").and_then(|i| i.as_u64()) {
+            Some(3001) => got_chain = result,
+            Some(3002) => got_peer = result,
+            _ => {}
         }
-    })
-    .await;
+    }
+    if let (Some(want), Some(got)) = (&expected.chain_spec, &got_chain) {
+        if want != got {
+            return Some(format!("chain {got:?}, expected {want:?}"));
+        }
+    }
+    if let (Some(want), Some(got)) = (&expected.peer_id, &got_peer) {
+        if want != got {
+            return Some(format!("peer id {got}, expected {want}"));
+        }
+    }
+    None
+}
 
-    Ok(MinerStatus {
-        peers,
-        current_block,
-        highest_block,
-        is_syncing,
-        bootnode_connected: None,
-        bootnode_host: None,
-        bootnode_stale_secs: None,
-    })
+// Clear per-chain runtime state (safe mode, detected RPC endpoint, recent log lines).
+// The status task and meta snapshot are rebuilt by the following start.
+async fn reset_chain_state(chain: &str) {
+    SAFE_MODE_ACTIVE.lock().await.remove(chain);
+    SAFE_MODE_PENDING.lock().await.remove(chain);
+    crate::safe_mode_countdown::reset_session(chain);
+    LOCAL_WS_URLS.lock().await.remove(chain);
+    PORTS.lock().await.remove(chain);
+    LOG_STATUS.lock().await.remove(chain);
+    NETWORK_HEIGHTS.lock().await.remove(chain);
+    // the old spec's runtime; kept, it would be compared against the new one's
+    RUNTIME_VERSIONS.lock().await.remove(chain);
+    crate::session_log::clear_ring(chain).await;
+}
+
+// First port >= `from` we can bind on localhost (best effort; the node binds it shortly after).
+fn free_local_port(from: u16) -> Option<u16> {
+    (from..from.saturating_add(200))
+        .find(|p| std::net::TcpListener::bind(("127.0.0.1", *p)).is_ok())
+}
+
+// Syncing until the node reports it is caught up (Paused while mining is paused).
+fn phase_for_syncing(is_syncing: Option<bool>, paused: bool) -> MinerPhase {
+    if paused {
+        MinerPhase::Paused
+    } else if is_syncing == Some(false) {
+        MinerPhase::Mining
+    } else {
+        MinerPhase::Syncing
+    }
 }
 
 /// Spawn a repeating background task that emits "miner:status" with peer and height info,
 /// and performs pending safe-mode toggles requested by the stderr task.
 /// This runs independently of the miner process; if the node is not up yet, it will emit
 /// empty fields until it can connect.
-fn spawn_status_task(app: AppHandle) {
+fn spawn_status_task(app: AppHandle, chain: String) {
+    // Newer tasks for the same chain supersede older ones; an old task exits on its next loop.
+    let gen = STATUS_GEN_COUNTER.fetch_add(1, Ordering::SeqCst) + 1;
     tauri::async_runtime::spawn(async move {
+        {
+            // tasks may register out of order; only the newest generation wins
+            let mut gens = STATUS_GENS.lock().await;
+            let cur = gens.entry(chain.clone()).or_insert(gen);
+            *cur = (*cur).max(gen);
+        }
         use futures_util::{SinkExt, StreamExt};
         use tokio_tungstenite::tungstenite::Message;
 
@@ -1044,69 +2677,220 @@ fn spawn_status_task(app: AppHandle) {
         let mut highest: Option<u64> = None;
         let mut peers: Option<u32> = None;
         let mut is_syncing: Option<bool> = None;
+        let mut last_rpc_success_ts: Option<i64> = None;
+        // set while 127.0.0.1:<port> answers as some other node (checked on every connect)
+        let mut foreign_node = false;
         let mut bootnode_connected: Option<bool> = None;
         let mut bootnode_host: Option<String> = None;
+        // heads whose PoW digest names this account count as authored by us
+        let our_account = last_cfg(&chain)
+            .await
+            .and_then(|c| crate::authorship::account_id(&c.rewards_address));
 
         // Keep a WS connection + subscription to local node heads; periodically poll health
         let mut sub_id: Option<String> = None;
-        let mut ws_opt: Option<
-            tokio_tungstenite::WebSocketStream<
-                tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>,
-            >,
-        > = None;
+        let mut ws_opt: Option<crate::net::WsStream> = None;
         let mut tick: u32 = 0;
-        // persistent bootnode ws and last update tracking
-        let mut ws_boot_opt: Option<
-            tokio_tungstenite::WebSocketStream<
-                tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>,
-            >,
-        > = None;
+        // best block at the last runtime version check
+        let mut runtime_checked_at: Option<u64> = None;
+        // bootnode probe (spawned on the first loop) and its last successful reply
+        let mut bootnode_probe_started = false;
         let mut last_bootnode_update: Option<std::time::Instant> = None;
+        // failed local connects in a row (backoff: `RetryPolicy::LOCAL_RECONNECT`)
+        let mut connect_failures: u32 = 0;
 
         loop {
+            if STATUS_GENS.lock().await.get(&chain) != Some(&gen) {
+                break;
+            }
+            // The node was stopped/restarted: the old socket is dead, reconnect right away
+            if let Some(wiped) = { RPC_RESET.lock().await.remove(&chain) } {
+                ws_opt = None;
+                sub_id = None;
+                connect_failures = 0;
+                runtime_checked_at = None;
+                if wiped {
+                    best = None;
+                    peers = None;
+                    is_syncing = None;
+                }
+            }
             // Handle any pending safe-mode toggle (set by stderr reader)
-            if let Some(pending) = { SAFE_MODE_PENDING.lock().await.take() } {
-                // Perform toggle here (this future runs under tauri async spawn and is Send)
-                let _ = set_safe_mode(app.clone(), pending).await;
+            // Adaptive safe mode goes off once imports have been healthy for a while
+            let adaptive_active =
+                { SAFE_MODE_ACTIVE.lock().await.get(&chain) == Some(&"adaptive") };
+            if adaptive_active {
+                let settings = crate::adaptive::load_settings(&app);
+                let toggle = crate::safe_mode_countdown::Toggle {
+                    enable: false,
+                    reason: "adaptive",
+                    range: None,
+                };
+                if crate::adaptive::is_healthy(&chain, &settings)
+                    && !crate::adaptive::breaker_open(&chain, &settings).0
+                    && !crate::safe_mode_countdown::is_vetoed(&chain, toggle)
+                {
+                    let mut pend = SAFE_MODE_PENDING.lock().await;
+                    if !pend.contains_key(&chain) {
+                        pend.insert(chain.clone(), toggle);
+                        crate::session_log::ui(
+                            &app,
+                            &chain,
+                            format!(
+                                "No slow imports or sync timeouts for {}s. Scheduling safe sync disable...",
+                                settings.healthy_secs
+                            ),
+                        );
+                    }
+                }
+            }
+            let pending = { SAFE_MODE_PENDING.lock().await.get(&chain).copied() };
+            if let Some(toggle) = pending {
+                // A repair restarts from genesis anyway; drop toggles requested meanwhile.
+                let step = if REPAIRING.lock().await.contains(&chain) {
+                    crate::safe_mode_countdown::reset_session(&chain);
+                    crate::safe_mode_countdown::Step::Skip
+                } else {
+                    // a deadline checked each round, so the loop keeps running meanwhile
+                    let secs = crate::settings::get(&app).safe_mode_countdown_secs;
+                    crate::safe_mode_countdown::step(&app, &chain, toggle, secs)
+                };
+                match step {
+                    crate::safe_mode_countdown::Step::Wait => {}
+                    crate::safe_mode_countdown::Step::Skip => {
+                        SAFE_MODE_PENDING.lock().await.remove(&chain);
+                    }
+                    crate::safe_mode_countdown::Step::Apply => {
+                        SAFE_MODE_PENDING.lock().await.remove(&chain);
+                        // Perform toggle here (this future runs under tauri async spawn and is Send)
+                        let _ =
+                            set_safe_mode(app.clone(), chain.clone(), toggle.enable, toggle.reason)
+                                .await;
+                    }
+                }
             }
 
             // Ensure WS connection to local node JSON-RPC
             if ws_opt.is_none() {
                 // try the dynamic endpoint first; if it fails, fallback once to default
                 // attempt connection to dynamic endpoint first (from shared state)
-                let current_ws = { LOCAL_WS_URL.lock().await.clone() };
-                let ws = match tokio_tungstenite::connect_async(&current_ws).await {
-                    Ok((ws, _)) => ws,
-                    Err(_) => {
+                let current_ws = local_ws_url(&chain).await;
+                let def = crate::rpc::local_ws_endpoint().to_string();
+                // the default port may belong to another chain's node; only fall back when alone
+                let may_fallback = current_ws != def && MINERS.lock().await.len() <= 1;
+                let connected = match crate::net::connect_ws(&current_ws).await {
+                    Ok(ws) => Some(ws),
+                    Err(_) if may_fallback => {
                         // fallback to default endpoint and update shared value
-                        let def = crate::rpc::local_ws_endpoint().to_string();
-                        match tokio_tungstenite::connect_async(&def).await {
-                            Ok((ws, _)) => {
-                                let mut u = LOCAL_WS_URL.lock().await;
-                                *u = def;
-                                ws
-                            }
-                            Err(_) => {
-                                // Emit whatever we have and retry shortly
-                                let _ = app.emit(
-                                    "miner:status",
-                                    &MinerStatus {
-                                        peers,
-                                        current_block: best,
-                                        highest_block: highest,
-                                        is_syncing,
-                                        bootnode_connected,
-                                        bootnode_host: bootnode_host.clone(),
-                                        bootnode_stale_secs: last_bootnode_update
-                                            .map(|t| t.elapsed().as_secs()),
-                                    },
-                                );
-                                tokio::time::sleep(Duration::from_millis(1200)).await;
-                                continue;
+                        match crate::net::connect_ws(&def).await {
+                            Ok(ws) => {
+                                let mut u = LOCAL_WS_URLS.lock().await;
+                                u.insert(chain.clone(), def);
+                                Some(ws)
                             }
+                            Err(_) => None,
                         }
                     }
+                    Err(_) => None,
                 };
+                let Some(ws) = connected else {
+                    // Emit whatever we have (log lines fill the gaps) and retry shortly
+                    let snapshot = MinerStatus {
+                        peers,
+                        current_block: best,
+                        highest_block: highest,
+                        is_syncing,
+                        bootnode_connected,
+                        bootnode_host: bootnode_host.clone(),
+                        bootnode_stale_secs: last_bootnode_update.map(|t| t.elapsed().as_secs()),
+                        chain: Some(chain.clone()),
+                        finalized_block: None,
+                        source: None,
+                        sync_percent: None,
+                        last_rpc_success_ts,
+                        foreign_node,
+                        mining: false,
+                        paused: false,
+                        inbound_peers: None,
+                        outbound_peers: None,
+                        nat_suspected: false,
+                        sync_phase: None,
+                        heavy_block: None,
+                    };
+                    events::emit_status(&app, &finalize_status(&app, &chain, snapshot).await);
+                    // back off while the node stays down (it may be stopped for good in
+                    // adopt/external-monitor mode), logging each time the delay grows
+                    connect_failures = connect_failures.saturating_add(1);
+                    let policy = crate::net::RetryPolicy::LOCAL_RECONNECT;
+                    let backoff = policy.backoff_for(connect_failures);
+                    if connect_failures == 1 || backoff > policy.backoff_for(connect_failures - 1) {
+                        eprintln!(
+                            "status {chain}: local RPC {current_ws} unreachable ({connect_failures} attempts); retrying every ~{}ms",
+                            backoff.as_millis()
+                        );
+                    }
+                    tokio::time::sleep(policy.delay_for(connect_failures)).await;
+                    continue;
+                };
+                if connect_failures > 0 {
+                    eprintln!(
+                        "status {chain}: local RPC back after {connect_failures} failed attempts"
+                    );
+                    connect_failures = 0;
+                }
+                let mut ws = ws;
+                match foreign_node_reason(&mut ws, &chain).await {
+                    Some(reason) => {
+                        if !foreign_node {
+                            let msg = format!(
+                                "Local RPC at {} is not our {chain} node ({reason}); ignoring its data",
+                                local_ws_url(&chain).await
+                            );
+                            crate::session_log::ui(&app, &chain, msg.clone());
+                            events::emit(
+                                &app,
+                                events::MINER_EVENT,
+                                &MinerEvent::Error {
+                                    message: msg,
+                                    severity: Severity::Warn,
+                                },
+                            );
+                        }
+                        foreign_node = true;
+                        // nothing from it feeds status, phase or the start grace period
+                        best = None;
+                        peers = None;
+                        is_syncing = None;
+                        last_rpc_success_ts = None;
+                        let snapshot = MinerStatus {
+                            peers,
+                            current_block: best,
+                            highest_block: highest,
+                            is_syncing,
+                            bootnode_connected,
+                            bootnode_host: bootnode_host.clone(),
+                            bootnode_stale_secs: last_bootnode_update
+                                .map(|t| t.elapsed().as_secs()),
+                            chain: Some(chain.clone()),
+                            finalized_block: None,
+                            source: None,
+                            sync_percent: None,
+                            last_rpc_success_ts,
+                            foreign_node,
+                            mining: false,
+                            paused: false,
+                            inbound_peers: None,
+                            outbound_peers: None,
+                            nat_suspected: false,
+                            sync_phase: None,
+                            heavy_block: None,
+                        };
+                        events::emit_status(&app, &finalize_status(&app, &chain, snapshot).await);
+                        tokio::time::sleep(Duration::from_millis(2000)).await;
+                        continue;
+                    }
+                    None => foreign_node = false,
+                }
                 ws_opt = Some(ws);
                 sub_id = None;
             }
@@ -1141,6 +2925,7 @@ fn spawn_status_task(app: AppHandle) {
 
             // Read one message with a small timeout; update best height on new head
             let mut _got_update = false;
+            let mut ws_idle = false;
             if let Ok(Some(msg)) = tokio::time::timeout(Duration::from_millis(400), ws.next()).await
             {
                 match msg {
@@ -1158,10 +2943,25 @@ fn spawn_status_task(app: AppHandle) {
                                 });
                             if let Some(numv) = head.get("number") {
                                 if let Some(n) = parse_u64_from_json(numv) {
+                                    last_rpc_success_ts = Some(crate::timeutil::now_ms());
                                     if best != Some(n) {
                                         best = Some(n);
                                         _got_update = true;
                                     }
+                                    if our_account.is_some_and(|a| {
+                                        crate::authorship::is_authored_by(&head, &a)
+                                    }) {
+                                        note_authored_head(&app, &chain, n).await;
+                                    }
+                                    // first RPC reply moves WaitingForRpc forward
+                                    if is_active_chain(&chain).await {
+                                        let paused = PAUSED.lock().await.contains_key(&chain);
+                                        phase::set_phase_if_node_up(
+                                            &app,
+                                            phase_for_syncing(is_syncing, paused),
+                                        )
+                                        .await;
+                                    }
                                 }
                             }
                         }
@@ -1174,237 +2974,692 @@ fn spawn_status_task(app: AppHandle) {
                     }
                 }
             } else {
-                // WebSocket idle; poll local RPC over HTTP once per second for best height
-                #[derive(serde::Deserialize)]
-                struct RpcResp {
-                    result: Option<serde_json::Value>,
-                }
-                let http_url = {
-                    let u = LOCAL_WS_URL.lock().await.clone();
-                    // ws://127.0.0.1:9944 -> http://127.0.0.1:9944
-                    if let Some(rest) = u.strip_prefix("ws://") {
-                        format!("http://{}", rest)
-                    } else if let Some(rest) = u.strip_prefix("wss://") {
-                        format!("https://{}", rest)
-                    } else {
-                        // fallback
-                        u.replace("ws://", "http://").replace("wss://", "https://")
-                    }
-                };
-                let body = serde_json::json!({
-                    "jsonrpc": "2.0",
-                    "id": 1,
-                    "method": "system_syncState",
-                    "params": []
-                });
-                if let Ok(client) = reqwest::Client::builder()
-                    .user_agent("quantus-miner/0.1")
-                    .build()
-                {
-                    if let Ok(resp) = client.post(&http_url).json(&body).send().await {
-                        if let Ok(r) = resp.json::<RpcResp>().await {
-                            if let Some(res) = r.result {
-                                if let Some(cb) =
-                                    res.get("currentBlock").and_then(parse_u64_from_json)
-                                {
-                                    if best != Some(cb) {
-                                        best = Some(cb);
-                                        _got_update = true;
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
+                // WebSocket idle: poll over HTTP instead (below)
+                ws_idle = true;
             }
 
-            // Periodic health polling (peers, isSyncing)
+            // Periodic checks: authored blocks, runtime version, node health
             tick = tick.wrapping_add(1);
-            if tick % 5 == 0 {
-                let req_health = serde_json::json!({
-                    "jsonrpc":"2.0","id":2001,"method":"system_health","params":[]
-                });
-                let _ = ws.send(Message::Text(req_health.to_string())).await;
-                if let Ok(Some(Ok(Message::Text(txt)))) =
-                    tokio::time::timeout(Duration::from_millis(600), ws.next()).await
-                {
-                    if let Ok(val) = serde_json::from_str::<serde_json::Value>(&txt) {
-                        if let Some(res) = val.get("result") {
-                            if let Some(p) = res.get("peers").and_then(|x| x.as_u64()) {
-                                let np = p as u32;
-                                if peers != Some(np) {
-                                    peers = Some(np);
-                                    _got_update = true;
-                                }
-                            }
-                            if let Some(s) = res.get("isSyncing").and_then(|x| x.as_bool()) {
-                                if is_syncing != Some(s) {
-                                    is_syncing = Some(s);
-                                    _got_update = true;
-                                }
-                            }
-                        }
+            if tick.is_multiple_of(10) {
+                if let Some(b) = best {
+                    recheck_authored(&app, &chain, b).await;
+                }
+            }
+            if let Some(b) = best {
+                let due =
+                    runtime_checked_at.is_none_or(|at| b < at || b >= at + RUNTIME_CHECK_BLOCKS);
+                if due && check_runtime_version(&app, &chain).await {
+                    runtime_checked_at = Some(b);
+                }
+            }
+            // Health (peers, isSyncing) every 5 loops, and best height whenever the
+            // subscription was quiet; same query as `get_node_status_now`
+            if ws_idle || tick.is_multiple_of(5) {
+                let now = query_local_node_status(&chain).await;
+                if now.reachable {
+                    last_rpc_success_ts = Some(now.checked_at);
+                }
+                if let Some(cb) = now.current_block {
+                    if best != Some(cb) {
+                        best = Some(cb);
+                        _got_update = true;
                     }
                 }
+                if now.peers.is_some() && peers != now.peers {
+                    peers = now.peers;
+                    _got_update = true;
+                }
+                if now.is_syncing.is_some() && is_syncing != now.is_syncing {
+                    is_syncing = now.is_syncing;
+                    _got_update = true;
+                }
+                if now.is_syncing.is_some() && is_active_chain(&chain).await {
+                    let paused = PAUSED.lock().await.contains_key(&chain);
+                    phase::set_phase_if_node_up(&app, phase_for_syncing(is_syncing, paused)).await;
+                }
             }
 
-            // Bootnode highest: maintain a persistent connection/subscription with long timeouts
-            if ws_boot_opt.is_none() {
-                if let Some(chain_name) =
-                    { LAST_CFG.lock().await.as_ref().map(|c| c.chain.clone()) }
+            // Bootnode highest: read from the probe task (bootnode.rs), which polls
+            // system_syncState on its own interval (custom specs have no bootnode)
+            if !bootnode_probe_started {
+                bootnode_probe_started = true;
+                let known_chain = {
+                    LAST_CFGS
+                        .lock()
+                        .await
+                        .get(&chain)
+                        .filter(|c| !c.is_custom_chain())
+                        .map(|c| c.chain.clone())
+                };
+                if let Some(url) = known_chain
+                    .as_deref()
+                    .and_then(crate::rpc::bootnode_ws_for_chain)
                 {
-                    if let Some(url) = crate::rpc::bootnode_ws_for_chain(chain_name.as_str()) {
-                        bootnode_host = Some(url.to_string());
-                        match tokio_tungstenite::connect_async(url).await {
-                            Ok((mut ws_b, _)) => {
-                                bootnode_connected = Some(true);
-                                let req = serde_json::json!({
-                                    "jsonrpc":"2.0","id":4242,"method":"chain_subscribeNewHeads","params":[]
-                                });
-                                let _ = ws_b.send(Message::Text(req.to_string())).await;
-                                ws_boot_opt = Some(ws_b);
-                                // mark connection time as last update baseline (no head yet)
-                                last_bootnode_update = Some(std::time::Instant::now());
-                            }
-                            Err(_) => {
-                                bootnode_connected = Some(false);
-                            }
-                        }
-                    }
+                    bootnode_host = Some(url.to_string());
+                    crate::bootnode::spawn_probe(chain.clone(), url);
                 }
-            } else {
-                // Poll bootnode subscription in a non-blocking way: 1s timeout per loop
-                if let Some(ws_b) = ws_boot_opt.as_mut() {
-                    if let Ok(Some(Ok(Message::Text(txt)))) =
-                        tokio::time::timeout(Duration::from_secs(1), ws_b.next()).await
-                    {
-                        if let Ok(val) = serde_json::from_str::<serde_json::Value>(&txt) {
-                            // Ignore initial subscription id result; update on head notifications
-                            if let Some(head) = val.get("params").and_then(|p| p.get("result")) {
-                                if let Some(num) = head.get("number").and_then(parse_u64_from_json)
-                                {
-                                    let new_h = match highest {
-                                        Some(x) => Some(x.max(num)),
-                                        None => Some(num),
-                                    };
-                                    if new_h != highest {
-                                        highest = new_h;
-                                        _got_update = true;
-                                    }
-                                    last_bootnode_update = Some(std::time::Instant::now());
-                                }
-                            }
-                        }
+            }
+            if let Some(probe) = crate::bootnode::stats(&chain).await {
+                bootnode_connected = probe.connected;
+                last_bootnode_update = probe.last_success_at;
+                if let Some(h) = probe.last_height {
+                    let new_h = Some(highest.map_or(h, |x| x.max(h)));
+                    if new_h != highest {
+                        highest = new_h;
+                        _got_update = true;
                     }
                 }
             }
 
+            crate::fork::tick(&app, &chain, best, is_syncing, bootnode_host.as_deref());
+            crate::peer_metrics::tick(&app, &chain);
+
+            // A newer task took over while we were waiting on sockets; don't emit stale values
+            if STATUS_GENS.lock().await.get(&chain) != Some(&gen) {
+                break;
+            }
+            if last_rpc_success_ts.is_some() && RPC_UP.lock().await.insert(chain.clone()) {
+                let (app, chain) = (app.clone(), chain.clone());
+                tauri::async_runtime::spawn(async move {
+                    verify_chain_data(&app, &chain).await;
+                });
+            }
             // Always emit a snapshot so UI can reflect latest best/highest even if unchanged this tick
-            let _ = app.emit(
-                "miner:status",
-                &MinerStatus {
-                    peers,
-                    current_block: best,
-                    highest_block: highest,
-                    is_syncing,
-                    bootnode_connected,
-                    bootnode_host: bootnode_host.clone(),
-                    bootnode_stale_secs: last_bootnode_update.map(|t| t.elapsed().as_secs()),
-                },
-            );
+            let snapshot = MinerStatus {
+                peers,
+                current_block: best,
+                highest_block: highest,
+                is_syncing,
+                bootnode_connected,
+                bootnode_host: bootnode_host.clone(),
+                bootnode_stale_secs: last_bootnode_update.map(|t| t.elapsed().as_secs()),
+                chain: Some(chain.clone()),
+                finalized_block: None,
+                source: None,
+                sync_percent: None,
+                last_rpc_success_ts,
+                foreign_node,
+                mining: false,
+                paused: false,
+                inbound_peers: None,
+                outbound_peers: None,
+                nat_suspected: false,
+                sync_phase: None,
+                heavy_block: None,
+            };
+            events::emit_status(&app, &finalize_status(&app, &chain, snapshot).await);
             // Ensure we loop roughly once per second to keep HTTP polling cadence
             tokio::time::sleep(Duration::from_millis(1000)).await;
         }
     });
 }
 
-pub async fn stop() -> Result<()> {
-    // notify UI that a stop is requested
-    if MINER.lock().await.as_ref().is_some() {
-        // best-effort fire before actually killing
-        // we don't have AppHandle here, so we can't emit; state will be confirmed on next start
+/// Remember whether the user started `chain` with their own `--max-blocks-per-request`;
+/// safe-mode automation (ranges and adaptive) then leaves it alone.
+pub async fn set_manual_safe_mode(chain: &str, extra_args: &[String]) {
+    let mut manual = MANUAL_SAFE_MODE.lock().await;
+    if has_max_blocks_arg(extra_args) {
+        manual.insert(chain.to_string());
+    } else {
+        manual.remove(chain);
+    }
+}
+
+/// Whether we currently hold a spawned (or adopted) node process.
+pub async fn is_running() -> bool {
+    !MINERS.lock().await.is_empty() || !ADOPTED.lock().await.is_empty()
+}
+
+/// Whether `chain`'s node process exists and hasn't exited.
+pub async fn is_chain_alive(chain: &str) -> bool {
+    if let Some(&pid) = ADOPTED.lock().await.get(chain) {
+        return crate::pidfile::process_identity(pid).is_some();
+    }
+    match MINERS.lock().await.get_mut(chain) {
+        Some(child) => matches!(child.try_wait(), Ok(None)),
+        None => false,
+    }
+}
+
+/// Chains that currently have a spawned (or adopted) node.
+pub async fn running_chains() -> Vec<String> {
+    let mut v: Vec<String> = MINERS.lock().await.keys().cloned().collect();
+    v.extend(ADOPTED.lock().await.keys().cloned());
+    v.sort();
+    v.dedup();
+    v
+}
+
+/// Chains whose node was taken over from a previous GUI instance.
+pub async fn adopted_chains() -> Vec<String> {
+    let mut v: Vec<String> = ADOPTED.lock().await.keys().cloned().collect();
+    v.sort();
+    v
+}
+
+/// Take over a node left running by a crashed GUI (see `pidfile`): restore its config
+/// and RPC endpoint and attach the status task, so stop/restart/repair work again. Its
+/// stdout/stderr went with the old GUI, so there are no log lines, log-derived events,
+/// hashrate or session log for it until it is restarted.
+pub async fn adopt(app: AppHandle, chain: String) -> Result<()> {
+    let rec = crate::pidfile::take_orphan(&chain)
+        .ok_or_else(|| anyhow!("no orphaned node for {chain}"))?;
+    if !crate::pidfile::is_alive(&rec) {
+        crate::pidfile::remove(&chain);
+        return Err(anyhow!("node {} for {chain} is no longer running", rec.pid));
+    }
+    if MINERS.lock().await.contains_key(&chain) {
+        return Err(anyhow!("a node for {chain} is already managed by this app"));
+    }
+    LAST_CFGS
+        .lock()
+        .await
+        .insert(chain.clone(), rec.cfg.clone());
+    LOCAL_WS_URLS
+        .lock()
+        .await
+        .insert(chain.clone(), rec.rpc_url.clone());
+    ADOPTED.lock().await.insert(chain.clone(), rec.pid);
+    *ACTIVE_CHAIN.lock().await = Some(chain.clone());
+    crate::session_log::audit(&chain, "node-adopted", &format!("pid {}", rec.pid));
+    crate::session_log::ui(
+        &app,
+        &chain,
+        format!(
+            "Took over node pid {} ({}) from a previous session. Its log output isn't available; restart it to see logs again.",
+            rec.pid, rec.rpc_url
+        ),
+    );
+    spawn_status_task(app.clone(), chain.clone());
+    phase::set_phase(&app, MinerPhase::StartingNode).await;
+    phase::set_phase(&app, MinerPhase::WaitingForRpc).await;
+    events::emit(
+        &app,
+        events::MINER_STATE,
+        events::MinerState {
+            running: true,
+            phase: "running",
+            chain: &chain,
+            error: None,
+            adopted: true,
+        },
+    );
+    Ok(())
+}
+
+/// How `pause_mining` paused a chain (and so how `resume_mining` undoes it).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum PauseMode {
+    /// The external miner was stopped; the node kept running (and its peers).
+    ExternalMiner { cores: usize, port: u16 },
+    /// No external miner: the node was restarted without `--validator` (slower: the
+    /// restart drops peers and the node catches up again).
+    NoValidator,
+}
+
+/// Whether (and how) `chain`'s mining is paused.
+pub async fn paused(chain: &str) -> Option<PauseMode> {
+    PAUSED.lock().await.get(chain).copied()
+}
+
+/// Forget a pause (the user stopped or started the node themselves).
+pub async fn clear_paused(chain: &str) {
+    PAUSED.lock().await.remove(chain);
+}
+
+/// Stop mining on `chain` but keep the node syncing: stop its external miner if it has
+/// one, otherwise restart the node without `--validator`. No-op if already paused.
+/// `by` says who asked ("user" | "throttle").
+pub async fn pause_mining(app: AppHandle, chain: String, by: &str) -> Result<PauseMode> {
+    let Some(_guard) = restart::begin(RestartOp::Pause(true), &chain).await? else {
+        return Err(anyhow!("pause already in progress"));
+    };
+    if let Some(mode) = paused(&chain).await {
+        return Ok(mode);
     }
+    if !is_chain_alive(&chain).await {
+        return Err(anyhow!("the {chain} node is not running"));
+    }
+    let is_active = is_active_chain(&chain).await;
+    let ext = EXT_MINERS.lock().await.remove(&chain);
+    let mode = if let Some(mut handle) = ext {
+        if let Err(e) = crate::shutdown::kill_and_reap(&mut handle.child, "external miner").await {
+            EXT_MINERS.lock().await.insert(chain.clone(), handle);
+            return Err(e);
+        }
+        let mode = PauseMode::ExternalMiner {
+            cores: handle.num_cores,
+            port: handle.port,
+        };
+        PAUSED.lock().await.insert(chain.clone(), mode);
+        crate::session_log::ui(
+            &app,
+            &chain,
+            "Mining paused: external miner stopped, the node keeps syncing.",
+        );
+        mode
+    } else {
+        let cfg = last_cfg(&chain)
+            .await
+            .ok_or_else(|| anyhow!("no previous miner configuration available"))?;
+        PAUSED
+            .lock()
+            .await
+            .insert(chain.clone(), PauseMode::NoValidator);
+        crate::session_log::ui(
+            &app,
+            &chain,
+            "Mining paused: no external miner, restarting the node without --validator (it will reconnect to peers and catch up)...",
+        );
+        phase::set_phase(
+            &app,
+            MinerPhase::Restarting {
+                reason: restart::describe(RestartReason::Pause, by),
+            },
+        )
+        .await;
+        events::emit_state(&app, &chain, false, "stopped");
+        let res = match stop_chain(&app, &chain).await {
+            Ok(_) => start(app.clone(), cfg, RestartReason::Pause, by).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = res {
+            PAUSED.lock().await.remove(&chain);
+            return Err(e);
+        }
+        PauseMode::NoValidator
+    };
+    if is_active {
+        phase::set_phase(&app, MinerPhase::Paused).await;
+    }
+    Ok(mode)
+}
+
+/// Undo `pause_mining`: restart the external miner on its old port, or restart the node
+/// with `--validator`. No-op if not paused.
+pub async fn resume_mining(app: AppHandle, chain: String, by: &str) -> Result<()> {
+    let Some(_guard) = restart::begin(RestartOp::Pause(false), &chain).await? else {
+        return Ok(());
+    };
+    let Some(mode) = PAUSED.lock().await.remove(&chain) else {
+        return Ok(());
+    };
+    let cfg = last_cfg(&chain).await;
+    match mode {
+        PauseMode::ExternalMiner { cores, port } => {
+            let log_to_file = cfg.is_some_and(|c| c.log_to_file);
+            start_external_miner(&app, &chain, cores, port, log_to_file, Vec::new()).await;
+            if !EXT_MINERS.lock().await.contains_key(&chain) {
+                PAUSED.lock().await.insert(chain.clone(), mode);
+                return Err(anyhow!("external miner didn't start; still paused"));
+            }
+            if is_active_chain(&chain).await {
+                // the status task moves on to Mining once the node reports it's caught up
+                phase::set_phase(&app, MinerPhase::Syncing).await;
+            }
+        }
+        PauseMode::NoValidator => {
+            let Some(cfg) = cfg else {
+                PAUSED.lock().await.insert(chain.clone(), mode);
+                return Err(anyhow!("no previous miner configuration available"));
+            };
+            phase::set_phase(
+                &app,
+                MinerPhase::Restarting {
+                    reason: restart::describe(RestartReason::Resume, by),
+                },
+            )
+            .await;
+            events::emit_state(&app, &chain, false, "stopped");
+            stop_chain(&app, &chain).await?;
+            start(app.clone(), cfg, RestartReason::Resume, by).await?;
+        }
+    }
+    crate::session_log::ui(&app, &chain, "Mining resumed.");
+    Ok(())
+}
+
+/// (cores, port) of `chain`'s running external miner.
+pub async fn external_miner(chain: &str) -> Option<(usize, u16)> {
+    EXT_MINERS
+        .lock()
+        .await
+        .get(chain)
+        .map(|h| (h.num_cores, h.port))
+}
+
+/// Restart `chain`'s external miner with `cores` on the same port (the node keeps
+/// running; it reconnects once the port is back). Skipped while a node restart is in
+/// flight.
+pub async fn set_external_cores(app: &AppHandle, chain: &str, cores: usize) -> Result<()> {
+    if let Some((op, c)) = restart::in_flight() {
+        return Err(anyhow!("{op:?} in progress on {c}"));
+    }
+    let log_to_file = last_cfg(chain).await.is_some_and(|c| c.log_to_file);
+    let Some(mut handle) = EXT_MINERS.lock().await.remove(chain) else {
+        return Err(anyhow!("no external miner running for {chain}"));
+    };
+    let port = handle.port;
+    crate::shutdown::kill_and_reap(&mut handle.child, "external miner").await?;
+    start_external_miner(app, chain, cores, port, log_to_file, Vec::new()).await;
+    if !EXT_MINERS.lock().await.contains_key(chain) {
+        return Err(anyhow!("external miner didn't restart"));
+    }
+    Ok(())
+}
+
+/// Stop the node (and its external miner) for one chain: SIGINT, up to the configured
+/// grace period to exit, then kill. Succeeds only once the processes have been reaped; on
+/// failure the child is kept so `is_running` stays truthful and a retry can find it.
+/// Returns how the node went (None: there was none), also logged and sent as `miner:exited`.
+pub async fn stop_chain(app: &AppHandle, chain: &str) -> Result<Option<NodeExit>> {
     // stop external miner first if running
-    if let Some(mut ext) = EXT_MINER.lock().await.take() {
+    if let Some(mut ext) = EXT_MINERS.lock().await.remove(chain) {
         eprintln!("ui: Stopping external miner on port {}", ext.port);
-        // attempt graceful kill
-        let _ = ext.child.kill().await;
+        if let Err(e) = crate::shutdown::kill_and_reap(&mut ext.child, "external miner").await {
+            EXT_MINERS.lock().await.insert(chain.to_string(), ext);
+            return Err(e);
+        }
     }
 
-    if let Some(mut child) = MINER.lock().await.take() {
-        #[cfg(target_family = "unix")]
-        {
-            use nix::sys::signal::{kill, Signal::SIGINT};
-            use nix::unistd::Pid;
-            let _ = kill(Pid::from_raw(child.id().unwrap_or(0) as i32), SIGINT);
-            tokio::time::sleep(Duration::from_millis(400)).await;
+    let grace = crate::shutdown::load_settings(app).grace();
+    let started = tokio::time::Instant::now();
+    let mut exit = None;
+    let adopted = ADOPTED.lock().await.get(chain).copied();
+    if let Some(pid) = adopted {
+        request_rpc_reset(chain, false).await;
+        exit = Some(crate::shutdown::stop_adopted(pid, grace).await?);
+        ADOPTED.lock().await.remove(chain);
+    }
+
+    let child = MINERS.lock().await.remove(chain);
+    if let Some(mut child) = child {
+        request_rpc_reset(chain, false).await;
+        match crate::shutdown::stop_child(&mut child, grace).await {
+            Ok(e) => exit = Some(e),
+            Err(e) => {
+                MINERS.lock().await.insert(chain.to_string(), child);
+                return Err(e);
+            }
         }
-        let _ = child.kill().await;
+    }
+    crate::pidfile::remove(chain);
+    crate::bootnode::stop_probe(chain).await;
+    if let Some(exit) = exit {
+        note_node_exit(app, chain, exit, started.elapsed(), grace);
+    }
+    Ok(exit)
+}
+
+// Log, record and emit (`miner:exited`) how a stopped node went away.
+fn note_node_exit(app: &AppHandle, chain: &str, exit: NodeExit, took: Duration, grace: Duration) {
+    let line = match exit {
+        NodeExit::Killed => format!(
+            "Node {} after not exiting within {}s of SIGINT.",
+            exit.describe(),
+            grace.as_secs()
+        ),
+        _ => format!("Node {} after {} ms.", exit.describe(), took.as_millis()),
+    };
+    crate::session_log::ui(app, chain, line);
+    crate::snapshot::update(chain, |c| c.last_exit = Some(exit));
+    events::emit(
+        app,
+        events::MINER_EXITED,
+        &serde_json::json!({
+            "chain": chain,
+            "exit": exit,
+            "tookMs": took.as_millis() as u64,
+            "graceSecs": grace.as_secs(),
+            "ts": crate::timeutil::now_ms(),
+        }),
+    );
+}
+
+/// Wipe `chain`'s database and restart its node. Without any known configuration
+/// (see `restart_cfg`) only the wipe is done; returns whether the node was restarted.
+/// With `dry_run` nothing is touched and the plan's manifest is returned instead.
+/// `cancel` stops the wipe (see `repair::wipe_db`); a cancelled repair doesn't restart.
+pub async fn repair_and_restart(
+    app: AppHandle,
+    chain: String,
+    dry_run: bool,
+    cancel: Option<CancelToken>,
+) -> Result<DryRun<bool>> {
+    if dry_run {
+        let plan = plan_repair(&app, &chain).await?;
+        return Ok(DryRun::Manifest(plan.manifest(&chain).await));
+    }
+    let Some(_guard) = restart::begin(RestartOp::Repair, &chain).await? else {
+        return Ok(DryRun::Applied(true));
+    };
+    // While set, the log reader and status task won't schedule safe-mode restarts.
+    REPAIRING.lock().await.insert(chain.clone());
+    let res = match plan_repair(&app, &chain).await {
+        Ok(plan) => repair_inner(app, &chain, plan, cancel).await,
+        Err(e) => Err(e),
+    };
+    REPAIRING.lock().await.remove(&chain);
+    res.map(DryRun::Applied)
+}
+
+// Refuse to touch a chain's files while a node we don't manage may still hold them.
+fn ensure_no_orphan(chain: &str) -> Result<()> {
+    if crate::pidfile::orphans().iter().any(|o| o.chain == chain) {
+        return Err(anyhow!(
+            "a {chain} node from a previous session is still running; take it over or stop it first"
+        ));
     }
     Ok(())
 }
 
-pub async fn repair_and_restart(app: AppHandle) -> Result<()> {
-    // We rely on the last configuration to restart after repair.
-    let cfg = { LAST_CFG.lock().await.clone() }
-        .ok_or_else(|| anyhow!("no previous miner configuration available"))?;
+/// What a repair will do, resolved before anything is touched.
+struct RepairPlan {
+    db_path: PathBuf,
+    /// Config to restart with; None: only wipe, the user starts the node.
+    cfg: Option<MinerConfig>,
+    /// `cfg` was saved by an earlier session.
+    saved_cfg: bool,
+}
 
-    let chain_id = chain_id_for_ui(&cfg.chain);
-    let db_path = node_base_path()?
-        .join("chains")
-        .join(chain_id)
-        .join("db")
-        .join("full");
-
-    let _ = app.emit(
-        "miner:log",
-        &LogMsg {
-            source: "ui",
-            line: "Stopping node to repair database...".into(),
-        },
-    );
+async fn plan_repair(app: &AppHandle, chain: &str) -> Result<RepairPlan> {
+    // We rely on the last configuration to restart after repair; without one the wipe
+    // still happens at the default location and the user starts the node.
+    let (cfg, saved_cfg) = match restart_cfg(app, chain).await {
+        Some((cfg, saved)) => (Some(cfg), saved),
+        None => (None, false),
+    };
+    let db_path = match &cfg {
+        Some(cfg) => cfg.resolved_chain()?.db_path(),
+        None => {
+            ensure_no_orphan(chain)?;
+            chains::resolve(chain, None, &[])?.db_path()
+        }
+    };
+    Ok(RepairPlan {
+        db_path,
+        cfg,
+        saved_cfg,
+    })
+}
+
+impl RepairPlan {
+    async fn manifest(&self, chain: &str) -> Manifest {
+        let mut notes = vec!["The node resyncs from genesis afterwards.".to_string()];
+        if self.cfg.is_none() {
+            notes.push(
+                "No saved configuration to restart with: the node has to be started by hand."
+                    .into(),
+            );
+        } else if self.saved_cfg {
+            notes.push("Restarts with the configuration saved by the last start.".into());
+        }
+        Manifest {
+            operation: "repair",
+            chain: Some(chain.to_string()),
+            changes: crate::plan::change("delete", &self.db_path, None)
+                .await
+                .into_iter()
+                .collect(),
+            restarts_node: self.cfg.is_some(),
+            notes,
+        }
+    }
+}
+
+async fn repair_inner(
+    app: AppHandle,
+    chain: &str,
+    plan: RepairPlan,
+    cancel: Option<CancelToken>,
+) -> Result<bool> {
+    let RepairPlan {
+        db_path,
+        cfg,
+        saved_cfg,
+    } = plan;
+    if saved_cfg {
+        note_saved_cfg(&app, chain);
+    }
+    // cancelled while waiting for another restart of this chain: nothing touched yet
+    if let Some(c) = &cancel {
+        c.check()?;
+    }
+
+    phase::set_phase(&app, MinerPhase::Repairing).await;
+    crate::session_log::ui(&app, chain, "Stopping node to repair database...");
     // Inform UI immediately that we're stopping to resync so buttons flip.
-    let _ = app.emit(
-        "miner:state",
-        &serde_json::json!({ "running": false, "phase": "stopped" }),
-    );
-    let _ = stop().await;
+    events::emit_state(&app, chain, false, "stopped");
+    // never delete the database under a node that is still running
+    if let Err(e) = stop_chain(&app, chain).await {
+        phase::set_phase(
+            &app,
+            MinerPhase::Errored {
+                kind: "stop".into(),
+            },
+        )
+        .await;
+        return Err(e);
+    }
 
-    if db_path.exists() {
-        std::fs::remove_dir_all(&db_path)
-            .map_err(|e| anyhow!("failed to wipe database at {}: {e}", db_path.display()))?;
+    // progress goes out as miner:repair-progress; an interrupted wipe is finished on the
+    // next start (see start_node)
+    if let Err(e) = crate::repair::wipe_db(&app, chain, &db_path, false, cancel.as_ref()).await {
+        if crate::ops::is_cancelled(&e) {
+            let line = if crate::repair::is_pending(&db_path) {
+                "Repair cancelled part way; the rest of the database is deleted on the next start."
+            } else {
+                "Repair cancelled before anything was deleted; the node stays stopped."
+            };
+            crate::session_log::ui(&app, chain, line);
+            phase::set_phase(&app, MinerPhase::Idle).await;
+            return Err(e);
+        }
+        phase::set_phase(
+            &app,
+            MinerPhase::Errored {
+                kind: "repair".into(),
+            },
+        )
+        .await;
+        return Err(e);
     }
 
-    let _ = app.emit(
-        "miner:log",
-        &LogMsg {
-            source: "ui",
-            line: format!(
-                "Database wiped at {}. Restarting node to resync from scratch...",
+    crate::milestones::note_repair(&app, chain);
+
+    // Old heights and peers are meaningless now; clear them in the UI and the status task.
+    request_rpc_reset(chain, true).await;
+    LOG_STATUS.lock().await.remove(chain);
+    events::emit_status(
+        &app,
+        &serde_json::json!({
+            "peers": null,
+            "currentBlock": null,
+            "isSyncing": null,
+            "syncPercent": null,
+            "dbWiped": true,
+            "chain": chain
+        }),
+    );
+    let Some(cfg) = cfg else {
+        crate::session_log::ui(
+            &app,
+            chain,
+            format!(
+                "Database wiped at {}. No saved configuration to restart with: press Start to resync from scratch.",
                 db_path.display()
             ),
-        },
+        );
+        phase::set_phase(&app, MinerPhase::Idle).await;
+        return Ok(false);
+    };
+    crate::session_log::ui(
+        &app,
+        chain,
+        format!(
+            "Database wiped at {}. Restarting node to resync from scratch...",
+            db_path.display()
+        ),
     );
 
-    start(app, cfg).await
+    start(app, cfg, RestartReason::Repair, "").await?;
+    Ok(true)
+}
+
+/// Turn safe sync on for a running `chain` now, as adaptive safe mode does (so it goes
+/// off again once imports are healthy). The `EnableSafeMode` remediation.
+pub async fn enable_safe_mode(app: AppHandle, chain: String) -> Result<()> {
+    if !is_chain_alive(&chain).await {
+        return Err(anyhow!("{chain} isn't running"));
+    }
+    if MANUAL_SAFE_MODE.lock().await.contains(&chain) {
+        return Err(anyhow!(
+            "safe sync is already set in the extra args (--max-blocks-per-request)"
+        ));
+    }
+    if REPAIRING.lock().await.contains(&chain) {
+        return Err(anyhow!("a repair is under way"));
+    }
+    if crate::node_caps::chain_lacks(&chain, crate::node_caps::SAFE_SYNC) {
+        return Err(anyhow!(
+            "this quantus-node has no --max-blocks-per-request; update it to use safe sync"
+        ));
+    }
+    set_safe_mode(app, chain, true, "adaptive").await
 }
 
-// Toggle safe mode by restarting with/without '--max-blocks-per-request 1'
-async fn set_safe_mode(app: AppHandle, enable: bool) -> Result<()> {
-    // Avoid redundant work
+// Toggle safe mode by restarting with/without '--max-blocks-per-request 1'.
+// `reason` ("range" | "adaptive") is remembered while it is on.
+async fn set_safe_mode(
+    app: AppHandle,
+    chain: String,
+    enable: bool,
+    reason: &'static str,
+) -> Result<()> {
+    let Some(_guard) = restart::begin(RestartOp::SafeMode(enable), &chain).await? else {
+        return Ok(());
+    };
+    // Avoid redundant work (also covers a request that queued behind an identical one)
     {
-        let active = *SAFE_MODE_ACTIVE.lock().await;
+        let active = SAFE_MODE_ACTIVE.lock().await.contains_key(&chain);
         if active == enable {
             return Ok(());
         }
     }
+    if enable && crate::node_caps::chain_lacks(&chain, crate::node_caps::SAFE_SYNC) {
+        crate::session_log::ui(
+            &app,
+            &chain,
+            "Safe sync skipped: this quantus-node has no --max-blocks-per-request (update the node to use it)",
+        );
+        return Ok(());
+    }
 
     // Read last cfg
-    let mut cfg = {
-        let lock = LAST_CFG.lock().await;
-        lock.clone()
-    }
-    .ok_or_else(|| anyhow!("no previous miner configuration available"))?;
+    let mut cfg = last_cfg(&chain)
+        .await
+        .ok_or_else(|| anyhow!("no previous miner configuration available"))?;
 
     // Adjust extra_args
     if enable {
@@ -1417,27 +3672,43 @@ async fn set_safe_mode(app: AppHandle, enable: bool) -> Result<()> {
     }
 
     // Stop and restart
+    let why = if enable {
+        RestartReason::SafeModeEnable
+    } else {
+        RestartReason::SafeModeDisable
+    };
+    phase::set_phase(
+        &app,
+        MinerPhase::Restarting {
+            reason: restart::describe(why, reason),
+        },
+    )
+    .await;
     // Inform UI immediately so buttons flip.
-    let _ = app.emit(
-        "miner:state",
-        &serde_json::json!({ "running": false, "phase": "stopped" }),
-    );
-    let _ = stop().await;
-    start(app.clone(), cfg).await?;
+    events::emit_state(&app, &chain, false, "stopped");
+    crate::adaptive::note_restart(&chain);
+    let _ = stop_chain(&app, &chain).await;
+    start(app.clone(), cfg, why, reason).await?;
     // Mark state
     {
         let mut active = SAFE_MODE_ACTIVE.lock().await;
-        *active = enable;
+        if enable {
+            active.insert(chain.clone(), reason);
+        } else {
+            active.remove(&chain);
+        }
     }
     // Emit status update so UI can show "Safe Sync" badge immediately
-    let _ = app.emit(
-        "miner:status",
+    events::emit_status(
+        &app,
         &serde_json::json!({
             "peers": null,
-            "current_block": null,
-            "highest_block": null,
-            "is_syncing": null,
-            "safe_mode": enable
+            "currentBlock": null,
+            "highestBlock": null,
+            "isSyncing": null,
+            "safeMode": enable,
+            "safeModeReason": enable.then_some(reason),
+            "chain": chain
         }),
     );
     Ok(())
@@ -1472,60 +3743,782 @@ fn remove_max_blocks_arg(args: &mut Vec<String>) {
     }
 }
 
-pub async fn unlock_and_restart(app: AppHandle) -> Result<()> {
-    // Use last known configuration (same approach as repair_and_restart)
-    let cfg = { LAST_CFG.lock().await.clone() }
+// Emit `miner:security-warning` (and write an audit entry) when the node's RPC is
+// reachable from other machines.
+fn warn_if_rpc_exposed(app: &AppHandle, chain: &str, jsonrpc_addr: Option<&str>, args: &[String]) {
+    let Some(exp) = crate::extra_args::rpc_exposure(jsonrpc_addr, args) else {
+        return;
+    };
+    let detail = format!(
+        "RPC reachable from other machines (addrs={:?}, flags={:?}); exposes {}",
+        exp.addrs, exp.flags, exp.exposes
+    );
+    crate::session_log::audit(chain, "rpc-exposed", &detail);
+    crate::session_log::ui(
+        app,
+        chain,
+        format!("WARNING: {detail}. Use \"Restart with local-only RPC\" to undo."),
+    );
+    events::emit(
+        app,
+        events::MINER_SECURITY_WARNING,
+        &serde_json::json!({
+            "chain": chain,
+            "kind": "rpc_exposed",
+            "addrs": exp.addrs,
+            "flags": exp.flags,
+            "unsafeMethods": exp.unsafe_methods,
+            "exposes": exp.exposes,
+        }),
+    );
+}
+
+/// Restart `chain` without the flags that expose its RPC (`--rpc-external`,
+/// `--unsafe-rpc-external`, `--rpc-methods ...`).
+pub async fn restart_with_safe_rpc(app: AppHandle, chain: String) -> Result<()> {
+    let Some(_guard) = restart::begin(RestartOp::SafeRpc, &chain).await? else {
+        return Ok(());
+    };
+    let mut cfg = last_cfg(&chain)
+        .await
         .ok_or_else(|| anyhow!("no previous miner configuration available"))?;
+    let removed = crate::extra_args::rpc_exposure(None, &cfg.extra_args)
+        .map(|e| e.flags)
+        .unwrap_or_default();
+    if removed.is_empty() {
+        return Err(anyhow!(
+            "no RPC exposure flags in the extra args; nothing to remove"
+        ));
+    }
+    cfg.extra_args = crate::extra_args::strip_rpc_exposure(&cfg.extra_args);
+    crate::session_log::audit(&chain, "rpc-restricted", &format!("removed {removed:?}"));
+    crate::session_log::ui(
+        &app,
+        &chain,
+        format!("Restarting node with local-only RPC (removed {removed:?})..."),
+    );
+
+    phase::set_phase(
+        &app,
+        MinerPhase::Restarting {
+            reason: restart::describe(RestartReason::LocalOnlyRpc, ""),
+        },
+    )
+    .await;
+    events::emit_state(&app, &chain, false, "stopped");
+    let _ = stop_chain(&app, &chain).await;
+    start(app, cfg, RestartReason::LocalOnlyRpc, "").await
+}
 
-    let chain_id = chain_id_for_ui(&cfg.chain);
-    let lock_path = node_base_path()?
-        .join("chains")
-        .join(chain_id)
-        .join("db")
-        .join("full")
-        .join("LOCK");
-
-    let _ = app.emit(
-        "miner:log",
-        &LogMsg {
-            source: "ui",
-            line: format!(
-                "Unlock requested. Stopping node and removing lock file at {} (will restart from current state).",
+/// Give this chain's node a new network identity: stop it, move `secret_dilithium` aside
+/// (`secret_dilithium.bak-<unix secs>`) and start again, which generates a fresh key.
+/// Returns the backup path.
+pub async fn regenerate_node_key(app: AppHandle, chain: String) -> Result<std::path::PathBuf> {
+    let Some(_guard) = restart::begin(RestartOp::RegenerateNodeKey, &chain).await? else {
+        return Err(anyhow!("node key regeneration already in progress"));
+    };
+    let cfg = last_cfg(&chain)
+        .await
+        .ok_or_else(|| anyhow!("no previous miner configuration available"))?;
+    let key_path = cfg.resolved_chain()?.node_key_path();
+    if !key_path.exists() {
+        return Err(anyhow!("no node key at {}", key_path.display()));
+    }
+
+    phase::set_phase(
+        &app,
+        MinerPhase::Restarting {
+            reason: restart::describe(RestartReason::NewNodeKey, ""),
+        },
+    )
+    .await;
+    events::emit_state(&app, &chain, false, "stopped");
+    let _ = stop_chain(&app, &chain).await;
+
+    let backup = key_path.with_file_name(format!(
+        "secret_dilithium.bak-{}",
+        crate::timeutil::now_secs()
+    ));
+    std::fs::rename(&key_path, &backup)
+        .map_err(|e| anyhow!("failed to move {} aside: {e}", key_path.display()))?;
+    crate::session_log::audit(
+        &chain,
+        "node-key-regenerated",
+        &format!("old key moved to {}", backup.display()),
+    );
+    crate::session_log::ui(
+        &app,
+        &chain,
+        format!(
+            "Old node key moved to {}. Restarting node with a new identity...",
+            backup.display()
+        ),
+    );
+    start(app, cfg, RestartReason::NewNodeKey, "").await?;
+    Ok(backup)
+}
+
+/// `miner:rewards-address-changed` payload: `chain`'s node mines to `address` from now
+/// on (restarted with it when it was running).
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RewardsAddressChanged {
+    chain: String,
+    address: String,
+    previous: String,
+    restarted: bool,
+    ts: i64,
+}
+
+/// Mine to `address` instead: persist it (settings and `LAST_CFGS`) and, when `chain`'s
+/// node runs, restart it with the same arguments but the new `--rewards-address`. The
+/// database and node key are left alone, so sync carries on where it was. With
+/// `watch_previous` the old address joins `watched_addresses`. If the restart fails,
+/// the old address is put back and the node stays stopped.
+pub async fn set_rewards_address(
+    app: AppHandle,
+    chain: String,
+    address: String,
+    watch_previous: bool,
+) -> Result<String> {
+    let address = address.trim().to_string();
+    crate::account_cli::validate_address(&address)?;
+    if !crate::authorship::checksum_ok(&address) {
+        return Err(anyhow!(
+            "{address} is not a valid SS58 address (bad checksum)"
+        ));
+    }
+    let Some(_guard) = restart::begin(RestartOp::RewardsAddress, &chain).await? else {
+        return Err(anyhow!("a rewards address change is already in progress"));
+    };
+    let acct_path = account_json_path(&app);
+    let acct = crate::account_cli::load_account(&app, &acct_path)?;
+    let previous = crate::account_cli::rewards_address(&app, &acct, &chain);
+    let new = crate::account_cli::address_for_chain(&address, &chain);
+    if new == previous {
+        return Ok(new);
+    }
+
+    let old_settings = crate::settings::get(&app);
+    let mut patch = serde_json::Map::new();
+    // the account's own address is kept as "" so it follows the account file
+    let chosen = if crate::authorship::account_id(&address) == acct.id() {
+        String::new()
+    } else {
+        address.clone()
+    };
+    patch.insert("rewards_address".into(), chosen.into());
+    if watch_previous && !old_settings.watched_addresses.contains(&previous) {
+        let mut watched = old_settings.watched_addresses.clone();
+        watched.push(previous.clone());
+        patch.insert("watched_addresses".into(), serde_json::json!(watched));
+    }
+    crate::settings::set(&app, patch)?;
+    crate::settings::flush_now(&app);
+    let cfg = {
+        let mut last = LAST_CFGS.lock().await;
+        last.get_mut(&chain).map(|c| {
+            c.rewards_address = new.clone();
+            c.clone()
+        })
+    };
+
+    let running = running_chains().await.contains(&chain);
+    let restarted = match cfg {
+        Some(cfg) if running => {
+            phase::set_phase(
+                &app,
+                MinerPhase::Restarting {
+                    reason: restart::describe(RestartReason::RewardsAddressChange, ""),
+                },
+            )
+            .await;
+            events::emit_state(&app, &chain, false, "stopped");
+            crate::session_log::ui(
+                &app,
+                &chain,
+                format!("Restarting node to mine to {new} (database and node key unchanged)..."),
+            );
+            let res = match stop_chain(&app, &chain).await {
+                Ok(_) => start(app.clone(), cfg, RestartReason::RewardsAddressChange, "").await,
+                Err(e) => Err(e),
+            };
+            if let Err(e) = res {
+                let mut undo = serde_json::Map::new();
+                undo.insert(
+                    "rewards_address".into(),
+                    old_settings.rewards_address.into(),
+                );
+                undo.insert(
+                    "watched_addresses".into(),
+                    serde_json::json!(old_settings.watched_addresses),
+                );
+                if let Err(e) = crate::settings::set(&app, undo) {
+                    eprintln!("rewards address: {e}");
+                }
+                if let Some(c) = LAST_CFGS.lock().await.get_mut(&chain) {
+                    c.rewards_address = previous.clone();
+                }
+                return Err(anyhow!(
+                    "restarting with the new rewards address failed, kept {previous}: {e}"
+                ));
+            }
+            true
+        }
+        _ => false,
+    };
+    crate::session_log::audit(
+        &chain,
+        "rewards-address-changed",
+        &format!("{previous} -> {new}"),
+    );
+    events::emit(
+        &app,
+        events::MINER_REWARDS_ADDRESS_CHANGED,
+        &RewardsAddressChanged {
+            chain,
+            address: new.clone(),
+            previous,
+            restarted,
+            ts: crate::timeutil::now_ms(),
+        },
+    );
+    Ok(new)
+}
+
+/// Import another machine's synced database for `chain` (see `data_import`): validate
+/// the source, stop the node if it runs, copy `db/full` in with `miner:import-progress`,
+/// and start the node again if it was running.
+pub async fn import_chain_data(
+    app: AppHandle,
+    chain: String,
+    source_path: std::path::PathBuf,
+    overwrite: bool,
+    include_network_key: bool,
+    dry_run: bool,
+) -> Result<DryRun<crate::data_import::ImportResult>> {
+    let guard = if dry_run {
+        None
+    } else {
+        let Some(guard) = restart::begin(RestartOp::Import, &chain).await? else {
+            return Err(anyhow!("an import is already in progress"));
+        };
+        Some(guard)
+    };
+    let cfg = last_cfg(&chain).await;
+    let resolved = match &cfg {
+        Some(cfg) => cfg.resolved_chain()?,
+        None => chains::resolve(&chain, None, &[])?,
+    };
+    let plan = crate::data_import::plan(&source_path, &resolved, overwrite, include_network_key)?;
+    let was_running = running_chains().await.contains(&chain);
+    if dry_run {
+        return Ok(DryRun::Manifest(
+            plan.manifest(&chain, was_running && cfg.is_some()).await,
+        ));
+    }
+    let _guard = guard;
+    let (src_dir, dest) = (plan.src_dir.clone(), plan.dest.clone());
+
+    if was_running {
+        phase::set_phase(
+            &app,
+            MinerPhase::Restarting {
+                reason: restart::describe(RestartReason::Import, ""),
+            },
+        )
+        .await;
+        events::emit_state(&app, &chain, false, "stopped");
+        if let Err(e) = stop_chain(&app, &chain).await {
+            phase::set_phase(
+                &app,
+                MinerPhase::Errored {
+                    kind: "stop".into(),
+                },
+            )
+            .await;
+            return Err(e);
+        }
+    }
+    crate::session_log::ui(
+        &app,
+        &chain,
+        format!(
+            "Importing chain data from {} into {}{}...",
+            src_dir.display(),
+            dest.display(),
+            if include_network_key {
+                " (with its network key)"
+            } else {
+                " (network key not copied)"
+            }
+        ),
+    );
+    let (files, bytes) = match crate::data_import::import_db(&app, &chain, &plan).await {
+        Ok(n) => n,
+        Err(e) => {
+            if was_running {
+                phase::set_phase(
+                    &app,
+                    MinerPhase::Errored {
+                        kind: "import".into(),
+                    },
+                )
+                .await;
+            }
+            return Err(e);
+        }
+    };
+    crate::session_log::audit(
+        &chain,
+        "chain-data-imported",
+        &format!(
+            "{files} files ({bytes} bytes) from {}{}",
+            src_dir.display(),
+            if include_network_key {
+                ", network key included"
+            } else {
+                ""
+            }
+        ),
+    );
+    // heights and peers from the old database are meaningless now
+    request_rpc_reset(&chain, true).await;
+    LOG_STATUS.lock().await.remove(&chain);
+
+    let restarted = match cfg {
+        Some(cfg) if was_running => {
+            start(app, cfg, RestartReason::Import, "").await?;
+            true
+        }
+        _ => false,
+    };
+    Ok(DryRun::Applied(crate::data_import::ImportResult {
+        source: src_dir.to_string_lossy().to_string(),
+        destination: dest.to_string_lossy().to_string(),
+        files,
+        bytes,
+        network_key_imported: plan.key.is_some(),
+        restarted,
+    }))
+}
+
+/// Remove `chain`'s leftover DB LOCK file and restart its node. Without any known
+/// configuration (see `restart_cfg`) only the LOCK is removed; returns whether the node
+/// was restarted.
+pub async fn unlock_and_restart(app: AppHandle, chain: String) -> Result<bool> {
+    let Some(_guard) = restart::begin(RestartOp::Unlock, &chain).await? else {
+        return Ok(true);
+    };
+    // Use last known configuration (same approach as repair_and_restart)
+    let cfg = match restart_cfg(&app, &chain).await {
+        Some((cfg, saved)) => {
+            if saved {
+                note_saved_cfg(&app, &chain);
+            }
+            Some(cfg)
+        }
+        None => None,
+    };
+    let lock_path = match &cfg {
+        Some(cfg) => cfg.resolved_chain()?.db_lock_path(),
+        None => {
+            ensure_no_orphan(&chain)?;
+            chains::resolve(&chain, None, &[])?.db_lock_path()
+        }
+    };
+
+    let Some(cfg) = cfg else {
+        if lock_path.exists() {
+            std::fs::remove_file(&lock_path)
+                .map_err(|e| anyhow!("failed to remove LOCK at {}: {e}", lock_path.display()))?;
+        }
+        crate::session_log::ui(
+            &app,
+            &chain,
+            format!(
+                "LOCK file at {} cleared. No saved configuration to restart with: press Start to run the node.",
                 lock_path.display()
             ),
-        },
+        );
+        return Ok(false);
+    };
+    crate::session_log::ui(
+        &app,
+        &chain,
+        format!(
+            "Unlock requested. Stopping node and removing lock file at {} (will restart from current state).",
+            lock_path.display()
+        ),
     );
 
+    phase::set_phase(
+        &app,
+        MinerPhase::Restarting {
+            reason: restart::describe(RestartReason::Unlock, ""),
+        },
+    )
+    .await;
     // Stop first to avoid races while touching the lock file
     // Inform UI immediately that we're stopping to unlock so buttons flip.
-    let _ = app.emit(
-        "miner:state",
-        &serde_json::json!({ "running": false, "phase": "stopped" }),
-    );
-    let _ = stop().await;
+    events::emit_state(&app, &chain, false, "stopped");
+    let _ = stop_chain(&app, &chain).await;
 
     if lock_path.exists() {
         std::fs::remove_file(&lock_path)
             .map_err(|e| anyhow!("failed to remove LOCK at {}: {e}", lock_path.display()))?;
-        let _ = app.emit(
-            "miner:log",
-            &LogMsg {
-                source: "ui",
-                line: "LOCK file removed. Restarting node...".into(),
+        crate::session_log::ui(&app, &chain, "LOCK file removed. Restarting node...");
+    } else {
+        crate::session_log::ui(
+            &app,
+            &chain,
+            format!(
+                "No LOCK file found at {}. Restarting node anyway...",
+                lock_path.display()
+            ),
+        );
+    }
+
+    start(app, cfg, RestartReason::Unlock, "").await?;
+    Ok(true)
+}
+
+/// Turn node file logging on or off mid-run. Enabling seeds the new file with the
+/// in-memory ring buffer; the choice is remembered for automatic restarts.
+pub async fn set_file_logging(
+    app: AppHandle,
+    chain: String,
+    enabled: bool,
+) -> Result<Option<PathBuf>> {
+    if let Some(cfg) = LAST_CFGS.lock().await.get_mut(&chain) {
+        cfg.log_to_file = enabled;
+    }
+    if !enabled {
+        crate::session_log::close(&app, &chain).await;
+        return Ok(None);
+    }
+    if let Some(p) = crate::session_log::current_path(&chain).await {
+        return Ok(Some(p));
+    }
+    let pid = MINERS
+        .lock()
+        .await
+        .get(&chain)
+        .and_then(|c| c.id())
+        .unwrap_or(0);
+    let path = crate::session_log::open(&app, &chain, pid, true).await?;
+    Ok(Some(path))
+}
+
+/// Whether the current (or last) run of `chain` uses a custom chain spec.
+pub async fn is_custom_chain_active(chain: &str) -> bool {
+    LAST_CFGS
+        .lock()
+        .await
+        .get(chain)
+        .map(|c| c.is_custom_chain())
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn wire<S: Serialize>(payload: &S) -> serde_json::Value {
+        serde_json::to_value(payload).unwrap()
+    }
+
+    #[test]
+    fn status_payload() {
+        let status = MinerStatus {
+            peers: Some(8),
+            current_block: Some(90),
+            highest_block: Some(100),
+            is_syncing: Some(true),
+            bootnode_connected: Some(true),
+            bootnode_host: Some("a.quantus.cat".into()),
+            bootnode_stale_secs: Some(2),
+            chain: Some("resonance".into()),
+            finalized_block: Some(80),
+            source: Some("rpc"),
+            sync_percent: Some(90),
+            last_rpc_success_ts: Some(1000),
+            foreign_node: false,
+            mining: false,
+            paused: false,
+            inbound_peers: Some(3),
+            outbound_peers: Some(5),
+            nat_suspected: false,
+            sync_phase: Some(crate::sync_phase::SyncPhase::Executing),
+            heavy_block: Some(crate::sync_phase::HeavyBlock {
+                number: 91,
+                elapsed_secs: 30,
+            }),
+        };
+        assert_eq!(
+            wire(&status),
+            json!({
+                "peers": 8,
+                "currentBlock": 90,
+                "highestBlock": 100,
+                "isSyncing": true,
+                "bootnodeConnected": true,
+                "bootnodeHost": "a.quantus.cat",
+                "bootnodeStaleSecs": 2,
+                "chain": "resonance",
+                "finalizedBlock": 80,
+                "source": "rpc",
+                "syncPercent": 90,
+                "lastRpcSuccessTs": 1000,
+                "foreignNode": false,
+                "mining": false,
+                "paused": false,
+                "inboundPeers": 3,
+                "outboundPeers": 5,
+                "natSuspected": false,
+                "syncPhase": "executing",
+                "heavyBlock": { "number": 91, "elapsedSecs": 30 },
+            })
+        );
+    }
+
+    #[test]
+    fn meta_payloads() {
+        let meta = MinerMeta {
+            chain: Some("resonance".into()),
+            rewards_address: Some("qz1".into()),
+            jsonrpc_addr: Some("127.0.0.1:9944".into()),
+            rpc_port: Some(9944),
+            internal_mining_threads: Some(2),
+            runtime: Some(RuntimeVersion {
+                spec_name: Some("quantus-runtime".into()),
+                spec_version: 110,
+                impl_version: Some(1),
+            }),
+            cpu_cores: Some(8),
+            ..Default::default()
+        };
+        let v = wire(&meta);
+        for (key, value) in [
+            ("chain", json!("resonance")),
+            ("rewardsAddress", json!("qz1")),
+            ("jsonrpcAddr", json!("127.0.0.1:9944")),
+            ("rpcPort", json!(9944)),
+            ("internalMiningThreads", json!(2)),
+            ("specName", json!("quantus-runtime")),
+            ("specVersion", json!(110)),
+            ("implVersion", json!(1)),
+            ("cpuCores", json!(8)),
+            ("binaryProvenance", json!(null)),
+            ("highestKnownBlock", json!(null)),
+        ] {
+            assert_eq!(v[key], value, "{key}");
+        }
+        let started = NodeStarted {
+            chain: "resonance",
+            meta: &MinerMeta::default(),
+            took_ms: 1200,
+        };
+        let v = wire(&started);
+        assert_eq!(
+            (&v["chain"], &v["tookMs"]),
+            (&json!("resonance"), &json!(1200))
+        );
+        assert!(v["meta"].is_object());
+        assert_eq!(
+            wire(&StartDegraded {
+                chain: "resonance".into(),
+                missing: vec!["rpc"],
+                waited_ms: 30000,
+            }),
+            json!({ "chain": "resonance", "missing": ["rpc"], "waitedMs": 30000 })
+        );
+    }
+
+    // What a node reader task does with its lines: parse, then drop the other stream's
+    // mirror. Returns the admitted events.
+    async fn read_stream(
+        output: String,
+        stream: Stream,
+        dedupe: Arc<std::sync::Mutex<EventDedupe>>,
+    ) -> Vec<MinerEvent> {
+        use tokio::io::AsyncBufReadExt;
+        let mut lines = tokio::io::BufReader::new(std::io::Cursor::new(output)).lines();
+        let mut admitted = Vec::new();
+        while let Ok(Some(line)) = lines.next_line().await {
+            if let Some(ev) = parse_event(&line).filter(|ev| admit_event(&dedupe, stream, ev)) {
+                admitted.push(ev);
+            }
+            tokio::task::yield_now().await;
+        }
+        admitted
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn mirrored_lines_give_one_event() {
+        const FOUND: &str = "2025-06-12 10:14:02 INFO quantus_miner: 🎉 Successfully mined and \
+                             submitted a new block → 0x5f1c2a99e0b3d4e7 (height: 18250)";
+        let stdout =
+            format!("{FOUND}\nhashrate: 1200 H/s\n✨ Imported #18250\nhashrate: 1200 H/s\n");
+        let stderr = format!("{FOUND}\nhashrate: 1200 H/s\n");
+        let dedupe = Arc::new(std::sync::Mutex::new(EventDedupe::default()));
+        let out = tokio::spawn(read_stream(stdout, Stream::Stdout, dedupe.clone()));
+        let err = tokio::spawn(read_stream(stderr, Stream::Stderr, dedupe.clone()));
+        let events: Vec<MinerEvent> = [out.await.unwrap(), err.await.unwrap()].concat();
+
+        let found = events
+            .iter()
+            .filter(|e| matches!(e, MinerEvent::FoundBlock { .. }))
+            .count();
+        assert_eq!(found, 1, "{events:?}");
+        // the stdout repeat is a second report, not a mirror
+        let hashrates = events
+            .iter()
+            .filter(|e| matches!(e, MinerEvent::Hashrate { .. }))
+            .count();
+        assert_eq!(hashrates, 2, "{events:?}");
+        assert_eq!(dedupe.lock().unwrap().suppressed, 2);
+    }
+
+    #[test]
+    fn sync_percent_is_clamped() {
+        assert_eq!(sync_percent(Some(0), Some(20_000)), Some(0));
+        assert_eq!(sync_percent(Some(19_999), Some(20_000)), Some(99));
+        assert_eq!(sync_percent(Some(20_000), Some(20_000)), Some(100));
+        // the local best ahead of a stale target
+        assert_eq!(sync_percent(Some(20_050), Some(20_000)), Some(100));
+        assert_eq!(sync_percent(Some(u64::MAX), Some(1)), Some(100));
+        // nothing to compare against
+        assert_eq!(sync_percent(Some(5), Some(0)), None);
+        assert_eq!(sync_percent(Some(5), None), None);
+        assert_eq!(sync_percent(None, Some(20_000)), None);
+    }
+
+    // Per-chain runtime state `chain` still has.
+    async fn leftovers(chain: &str) -> Vec<&'static str> {
+        let mut found = Vec::new();
+        let mut check = |name, present: bool| {
+            if present {
+                found.push(name);
+            }
+        };
+        check(
+            "safe_mode_active",
+            SAFE_MODE_ACTIVE.lock().await.contains_key(chain),
+        );
+        check(
+            "safe_mode_pending",
+            SAFE_MODE_PENDING.lock().await.contains_key(chain),
+        );
+        check(
+            "local_ws_url",
+            LOCAL_WS_URLS.lock().await.contains_key(chain),
+        );
+        check("ports", PORTS.lock().await.contains_key(chain));
+        check("log_status", LOG_STATUS.lock().await.contains_key(chain));
+        check(
+            "network_height",
+            NETWORK_HEIGHTS.lock().await.contains_key(chain),
+        );
+        check(
+            "runtime_version",
+            RUNTIME_VERSIONS.lock().await.contains_key(chain),
+        );
+        found
+    }
+
+    #[tokio::test]
+    async fn switching_chains_leaves_no_stale_state() {
+        let chain = "resonance";
+        SAFE_MODE_ACTIVE.lock().await.insert(chain.into(), "range");
+        SAFE_MODE_PENDING.lock().await.insert(
+            chain.into(),
+            crate::safe_mode_countdown::Toggle {
+                enable: false,
+                reason: "range",
+                range: None,
             },
         );
-    } else {
-        let _ = app.emit(
-            "miner:log",
-            &LogMsg {
-                source: "ui",
-                line: format!(
-                    "No LOCK file found at {}. Restarting node anyway...",
-                    lock_path.display()
-                ),
+        LOCAL_WS_URLS
+            .lock()
+            .await
+            .insert(chain.into(), "ws://127.0.0.1:9945".into());
+        PORTS.lock().await.insert(
+            chain.into(),
+            PortAssignments {
+                chain: chain.into(),
+                rpc: 9945,
+                prometheus: 9616,
+                p2p: 30400,
+            },
+        );
+        LOG_STATUS.lock().await.insert(
+            chain.into(),
+            LogStatus {
+                best: Some(19_550),
+                ..Default::default()
             },
         );
+        NETWORK_HEIGHTS.lock().await.insert(chain.into(), 20_000);
+        RUNTIME_VERSIONS.lock().await.insert(
+            chain.into(),
+            RuntimeVersion {
+                spec_name: Some("quantus-runtime".into()),
+                spec_version: 110,
+                impl_version: Some(1),
+            },
+        );
+        assert_eq!(leftovers(chain).await.len(), 7);
+
+        // nothing of the resonance session is visible to heisenberg
+        assert_eq!(leftovers("heisenberg").await, Vec::<&str>::new());
+        assert_eq!(safe_mode("heisenberg").await, (None, false));
+
+        // a different spec in the resonance slot starts from nothing
+        reset_chain_state(chain).await;
+        assert_eq!(leftovers(chain).await, Vec::<&str>::new());
     }
 
-    start(app, cfg).await
+    #[test]
+    fn upgrade_and_address_payloads() {
+        let upgrade = RuntimeUpgrade {
+            chain: "resonance".into(),
+            spec_name: Some("quantus-runtime".into()),
+            from: 109,
+            to: 110,
+            suggest_update: true,
+            error: None,
+            latest_node_release: Some("v0.2.0".into()),
+        };
+        assert_eq!(
+            wire(&upgrade),
+            json!({
+                "chain": "resonance",
+                "specName": "quantus-runtime",
+                "from": 109,
+                "to": 110,
+                "suggestUpdate": true,
+                "error": null,
+                "latestNodeRelease": "v0.2.0",
+            })
+        );
+        let changed = RewardsAddressChanged {
+            chain: "resonance".into(),
+            address: "qz2".into(),
+            previous: "qz1".into(),
+            restarted: true,
+            ts: 3,
+        };
+        assert_eq!(
+            wire(&changed),
+            json!({
+                "chain": "resonance",
+                "address": "qz2",
+                "previous": "qz1",
+                "restarted": true,
+                "ts": 3,
+            })
+        );
+    }
 }
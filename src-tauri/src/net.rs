@@ -0,0 +1,182 @@
+use anyhow::{anyhow, Result};
+use lazy_static::lazy_static;
+use std::{future::Future, time::Duration};
+
+/// TCP/TLS connect timeout for every outbound HTTP and WS connection.
+pub const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+/// Max gap between reads; guards downloads that stall without failing.
+pub const READ_TIMEOUT: Duration = Duration::from_secs(30);
+
+pub type WsStream =
+    tokio_tungstenite::WebSocketStream<tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>>;
+
+lazy_static! {
+    // One pooled client for the whole app (cloning it is cheap).
+    static ref CLIENT: reqwest::Client = reqwest::Client::builder()
+        .user_agent(user_agent())
+        .connect_timeout(CONNECT_TIMEOUT)
+        .read_timeout(READ_TIMEOUT)
+        .build()
+        .unwrap_or_else(|_| reqwest::Client::new());
+}
+
+/// `quantus-miner-gui/<version> (<os>-<arch>)`, sent with every request so bootnode and
+/// release API operators can tell GUI versions apart.
+pub fn user_agent() -> String {
+    format!(
+        "quantus-miner-gui/{} ({}-{})",
+        env!("CARGO_PKG_VERSION"),
+        std::env::consts::OS,
+        std::env::consts::ARCH
+    )
+}
+
+/// Shared HTTP client with connect/read timeouts.
+pub fn client() -> reqwest::Client {
+    CLIENT.clone()
+}
+
+/// Connect a WebSocket with the shared connect timeout.
+pub async fn connect_ws(url: &str) -> Result<WsStream> {
+    match tokio::time::timeout(CONNECT_TIMEOUT, tokio_tungstenite::connect_async(url)).await {
+        Ok(Ok((ws, _))) => Ok(ws),
+        Ok(Err(e)) => Err(anyhow!("ws connect {url}: {e}")),
+        Err(_) => Err(anyhow!("ws connect {url}: timed out")),
+    }
+}
+
+/// How often and how patiently `with_retry` retries a call.
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+    /// Upper bound for a single attempt (None: rely on the client's timeouts).
+    pub attempt_timeout: Option<Duration>,
+}
+
+impl RetryPolicy {
+    /// Interactive lookups (balance, chain properties): fail fast.
+    pub const QUICK: RetryPolicy = RetryPolicy {
+        max_attempts: 3,
+        base_delay: Duration::from_millis(300),
+        max_delay: Duration::from_secs(2),
+        attempt_timeout: Some(Duration::from_secs(10)),
+    };
+    /// GitHub release metadata.
+    pub const GITHUB: RetryPolicy = RetryPolicy {
+        max_attempts: 4,
+        base_delay: Duration::from_secs(1),
+        max_delay: Duration::from_secs(8),
+        attempt_timeout: Some(Duration::from_secs(30)),
+    };
+    /// Binary downloads: no overall cap, the read timeout catches stalls.
+    pub const DOWNLOAD: RetryPolicy = RetryPolicy {
+        max_attempts: 3,
+        base_delay: Duration::from_secs(2),
+        max_delay: Duration::from_secs(10),
+        attempt_timeout: None,
+    };
+    /// The status task reconnecting to its local node: retried for as long as the task
+    /// lives (not through `with_retry`), a few times a minute at most once capped.
+    pub const LOCAL_RECONNECT: RetryPolicy = RetryPolicy {
+        max_attempts: u32::MAX,
+        base_delay: Duration::from_millis(1200),
+        max_delay: Duration::from_secs(30),
+        attempt_timeout: None,
+    };
+
+    /// The backoff before retry `attempt` (from 1), without jitter.
+    pub fn backoff_for(&self, attempt: u32) -> Duration {
+        let exp = self
+            .base_delay
+            .saturating_mul(1u32 << attempt.saturating_sub(1).min(16));
+        exp.min(self.max_delay)
+    }
+
+    /// Capped exponential backoff with "equal jitter": half fixed, half random.
+    pub fn delay_for(&self, attempt: u32) -> Duration {
+        let capped = self.backoff_for(attempt);
+        capped / 2 + capped.mul_f64(rand::random::<f64>() / 2.0)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("timed out after {0:?}")]
+struct AttemptTimeout(Duration);
+
+/// Whether an error is worth retrying: connect/timeout failures and 5xx/429 yes,
+/// other HTTP statuses (4xx) and decode errors no.
+pub fn is_retryable(err: &anyhow::Error) -> bool {
+    if err.downcast_ref::<AttemptTimeout>().is_some() {
+        return true;
+    }
+    if let Some(e) = err.downcast_ref::<reqwest::Error>() {
+        if let Some(status) = e.status() {
+            return status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS;
+        }
+        return e.is_connect() || e.is_timeout() || e.is_request() || e.is_body();
+    }
+    if let Some(e) = err.downcast_ref::<tokio_tungstenite::tungstenite::Error>() {
+        use tokio_tungstenite::tungstenite::Error as WsError;
+        return match e {
+            WsError::Http(resp) => resp.status().is_server_error(),
+            WsError::Io(_) | WsError::ConnectionClosed | WsError::AlreadyClosed => true,
+            _ => false,
+        };
+    }
+    err.downcast_ref::<std::io::Error>().is_some()
+}
+
+/// Whether an error came from the network (HTTP, WebSocket or a timed-out attempt) rather
+/// than, say, the local filesystem.
+pub fn is_network_error(err: &anyhow::Error) -> bool {
+    err.downcast_ref::<AttemptTimeout>().is_some()
+        || err.downcast_ref::<reqwest::Error>().is_some()
+        || err
+            .downcast_ref::<tokio_tungstenite::tungstenite::Error>()
+            .is_some()
+}
+
+/// Run `call` until it succeeds, fails with a non-retryable error, or the policy's
+/// attempts are used up. `what` names the call in the logs.
+pub async fn with_retry<T, F, Fut>(what: &str, policy: RetryPolicy, mut call: F) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let mut attempt = 1;
+    loop {
+        let res = match policy.attempt_timeout {
+            Some(t) => match tokio::time::timeout(t, call()).await {
+                Ok(r) => r,
+                Err(_) => Err(AttemptTimeout(t).into()),
+            },
+            None => call().await,
+        };
+        match res {
+            Ok(v) => {
+                if attempt > 1 {
+                    eprintln!("net: {what} succeeded on attempt {attempt}");
+                }
+                return Ok(v);
+            }
+            Err(e) if attempt < policy.max_attempts && is_retryable(&e) => {
+                let delay = policy.delay_for(attempt);
+                eprintln!(
+                    "net: {what} attempt {attempt}/{} failed: {e}; retrying in {}ms",
+                    policy.max_attempts,
+                    delay.as_millis()
+                );
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+            }
+            Err(e) => {
+                if attempt > 1 {
+                    eprintln!("net: {what} gave up after {attempt} attempts: {e}");
+                }
+                return Err(e);
+            }
+        }
+    }
+}
@@ -0,0 +1,232 @@
+use anyhow::{anyhow, Result};
+use lazy_static::lazy_static;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    fs,
+    path::{Path, PathBuf},
+    process::Stdio,
+    time::{Duration, SystemTime},
+};
+use tauri::AppHandle;
+use tokio::process::Command;
+
+/// Flags every start passes; a node without one of them can't be driven by this app.
+pub const REQUIRED: [&str; 5] = [
+    "--chain",
+    "--validator",
+    "--node-key-file",
+    "--rewards-address",
+    "--port",
+];
+/// Safe sync: skipped, with a notice, on nodes that don't have it.
+pub const SAFE_SYNC: &str = "--max-blocks-per-request";
+/// Opens the database read-only and prints the best/finalized blocks (`db_check.rs`).
+pub const CHAIN_INFO: &str = "chain-info";
+/// Compacts the database offline, on node versions that have it (`compact.rs`).
+pub const COMPACT_DB: &str = "compact-db";
+/// Subcommands whose `--help` is probed too (account creation uses `key quantus`).
+const SUBCOMMANDS: [&[&str]; 3] = [&["key", "quantus"], &[CHAIN_INFO], &[COMPACT_DB]];
+
+static RE_FLAG: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?:^|[\s,\[(])(--[a-z0-9][a-z0-9-]*)").unwrap());
+
+/// What an installed quantus-node accepts, from its `--help` (`get_node_capabilities`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeCapabilities {
+    pub binary: String,
+    /// blake2s-256 of the binary (hex); the cache key, so an update is probed again.
+    pub hash: String,
+    pub version: Option<String>,
+    /// Every `--flag` in `--help`.
+    pub flags: BTreeSet<String>,
+    /// Flags per probed subcommand, e.g. "key quantus".
+    pub subcommands: BTreeMap<String, BTreeSet<String>>,
+    /// Unix seconds.
+    pub probed_at: i64,
+}
+
+impl NodeCapabilities {
+    pub fn supports(&self, flag: &str) -> bool {
+        self.flags.contains(flag)
+    }
+
+    /// Whether `sub` (one of `SUBCOMMANDS`, e.g. "key quantus") answered its `--help`.
+    pub fn has_subcommand(&self, sub: &str) -> bool {
+        self.subcommands.contains_key(sub)
+    }
+
+    /// Err naming the `REQUIRED` flags this node lacks.
+    pub fn require(&self) -> Result<(), UnsupportedBinary> {
+        let missing: Vec<String> = REQUIRED
+            .iter()
+            .filter(|f| !self.supports(f))
+            .map(|f| f.to_string())
+            .collect();
+        if missing.is_empty() {
+            return Ok(());
+        }
+        Err(UnsupportedBinary {
+            binary: self.binary.clone(),
+            version: self
+                .version
+                .clone()
+                .unwrap_or_else(|| "unknown version".into()),
+            missing,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, thiserror::Error)]
+#[error("quantus-node {version} ({binary}) doesn't support {missing:?}, which this app needs; update it (delete it to have the app reinstall the latest)")]
+pub struct UnsupportedBinary {
+    pub binary: String,
+    pub version: String,
+    pub missing: Vec<String>,
+}
+
+// size and mtime of a binary when it was hashed
+type Stamp = (u64, Option<SystemTime>);
+
+lazy_static! {
+    // hash -> capabilities, loaded from `{app_data_dir}/node_capabilities.json` on first use
+    static ref CACHE: std::sync::Mutex<Option<HashMap<String, NodeCapabilities>>> =
+        std::sync::Mutex::new(None);
+    // path -> (stamp, hash), so an unchanged binary isn't hashed again
+    static ref HASHES: std::sync::Mutex<HashMap<PathBuf, (Stamp, String)>> =
+        std::sync::Mutex::new(HashMap::new());
+    // capabilities of the binary each chain's node was last started with
+    static ref CHAINS: std::sync::Mutex<HashMap<String, NodeCapabilities>> =
+        std::sync::Mutex::new(HashMap::new());
+}
+
+fn cache_path(app: &AppHandle) -> PathBuf {
+    crate::paths::app_data_dir(app).join("node_capabilities.json")
+}
+
+async fn file_hash(bin: &Path) -> Result<String> {
+    let meta = fs::metadata(bin)?;
+    let stamp: Stamp = (meta.len(), meta.modified().ok());
+    if let Some((seen, hash)) = HASHES.lock().ok().and_then(|h| h.get(bin).cloned()) {
+        if seen == stamp {
+            return Ok(hash);
+        }
+    }
+    let path = bin.to_path_buf();
+    let hash = tokio::task::spawn_blocking(move || -> Result<String> {
+        use blake2::{Blake2s256, Digest};
+        let mut hasher = Blake2s256::new();
+        std::io::copy(&mut fs::File::open(&path)?, &mut hasher)?;
+        Ok(hex::encode(hasher.finalize()))
+    })
+    .await??;
+    if let Ok(mut h) = HASHES.lock() {
+        h.insert(bin.to_path_buf(), (stamp, hash.clone()));
+    }
+    Ok(hash)
+}
+
+async fn help_flags(bin: &Path, subcommand: &[&str]) -> Option<BTreeSet<String>> {
+    let out = Command::new(bin)
+        .args(subcommand)
+        .arg("--help")
+        .stdin(Stdio::null())
+        .kill_on_drop(true)
+        .output();
+    let out = tokio::time::timeout(Duration::from_secs(5), out)
+        .await
+        .ok()?
+        .ok()?;
+    // an unknown subcommand fails, pointing at `--help`
+    if !out.status.success() {
+        return None;
+    }
+    let text = String::from_utf8_lossy(&[out.stdout, out.stderr].concat()).into_owned();
+    let flags: BTreeSet<String> = RE_FLAG
+        .captures_iter(&text)
+        .map(|c| c[1].to_string())
+        .collect();
+    (!flags.is_empty()).then_some(flags)
+}
+
+/// `bin`'s capabilities: cached by its hash (in memory and on disk), probed with
+/// `--help` on first use. Err when its help can't be read; callers then gate nothing.
+pub async fn probe(app: &AppHandle, bin: &Path) -> Result<NodeCapabilities> {
+    let hash = file_hash(bin).await?;
+    let cached = CACHE.lock().ok().and_then(|mut c| {
+        c.get_or_insert_with(|| {
+            fs::read(cache_path(app))
+                .ok()
+                .and_then(|b| serde_json::from_slice(&b).ok())
+                .unwrap_or_default()
+        })
+        .get(&hash)
+        .cloned()
+    });
+    if let Some(caps) = cached {
+        return Ok(caps);
+    }
+    let flags = help_flags(bin, &[])
+        .await
+        .ok_or_else(|| anyhow!("{} --help printed no flags", bin.display()))?;
+    let mut subcommands = BTreeMap::new();
+    for sub in SUBCOMMANDS {
+        if let Some(f) = help_flags(bin, sub).await {
+            subcommands.insert(sub.join(" "), f);
+        }
+    }
+    let caps = NodeCapabilities {
+        binary: bin.display().to_string(),
+        hash: hash.clone(),
+        version: crate::installer::binary_version(bin).await,
+        flags,
+        subcommands,
+        probed_at: crate::timeutil::now_secs(),
+    };
+    if let Ok(mut c) = CACHE.lock() {
+        let all = c.get_or_insert_with(HashMap::new);
+        all.insert(hash, caps.clone());
+        if let Err(e) = save_cache(app, all) {
+            eprintln!("node capabilities: {e}");
+        }
+    }
+    Ok(caps)
+}
+
+fn save_cache(app: &AppHandle, all: &HashMap<String, NodeCapabilities>) -> Result<()> {
+    let path = cache_path(app);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    crate::persist::write_atomic(&path, &serde_json::to_vec_pretty(all)?)
+}
+
+/// Probe the binary `chain`'s node is about to start with and remember it for
+/// `chain_lacks`. None when it couldn't be probed.
+pub async fn note_start(app: &AppHandle, chain: &str, bin: &Path) -> Option<NodeCapabilities> {
+    let caps = match probe(app, bin).await {
+        Ok(c) => Some(c),
+        Err(e) => {
+            eprintln!("node capabilities: {e}");
+            None
+        }
+    };
+    if let Ok(mut chains) = CHAINS.lock() {
+        match &caps {
+            Some(c) => chains.insert(chain.to_string(), c.clone()),
+            None => chains.remove(chain),
+        };
+    }
+    caps
+}
+
+/// True when `chain`'s node is known not to take `flag` (false when unknown).
+pub fn chain_lacks(chain: &str, flag: &str) -> bool {
+    CHAINS
+        .lock()
+        .ok()
+        .and_then(|c| c.get(chain).map(|caps| !caps.supports(flag)))
+        .unwrap_or(false)
+}
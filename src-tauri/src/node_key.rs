@@ -10,8 +10,7 @@ use std::{
 /// - macOS:   /Users/you/Library/Application Support/quantus-node
 /// - Windows: C:\Users\you\AppData\Roaming\quantus-node
 pub fn node_base_path() -> Result<PathBuf> {
-    let data = dirs::data_dir().ok_or_else(|| anyhow!("no data_dir available"))?;
-    Ok(data.join("quantus-node"))
+    Ok(crate::paths::data_dir().join("quantus-node"))
 }
 
 /// Maps the UI chain selector to the on-disk chain identifier used in paths.
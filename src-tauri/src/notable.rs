@@ -0,0 +1,413 @@
+use anyhow::{anyhow, Result};
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashMap, VecDeque},
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+use tauri::AppHandle;
+
+use crate::events;
+
+/// The hashrate peak a drop is measured against is the highest report this recent.
+const HASHRATE_WINDOW: Duration = Duration::from_secs(600);
+/// The same found block (log line, then verified) sounds once.
+const FOUND_BLOCK_DEDUPE: Duration = Duration::from_secs(120);
+const MIN_NO_PEERS_SECS: u64 = 30;
+
+/// How much an event deserves the user's attention (sounds, toasts).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Notability {
+    None,
+    Info,
+    Warning,
+    Critical,
+}
+
+/// What a rule matches. Each has a default level, overridable in `NotableRules::levels`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotableKind {
+    /// Any `FoundBlock` (once per height).
+    FoundBlock,
+    /// An authored block was reorged out.
+    OrphanedBlock,
+    /// An authored block never reached the bootnode (`miner:block-propagated`).
+    UnpropagatedBlock,
+    /// The hashrate fell `hashrate_drop_pct` below its recent peak.
+    HashrateDrop,
+    /// No peers for `no_peers_secs`.
+    NoPeers,
+    /// A node `Error` event of severity error...
+    NodeError,
+    /// ...and of severity warn.
+    NodeWarning,
+    /// The node was found already exited when it was stopped.
+    NodeExited,
+    StartFailed,
+    ForkDetected,
+    DuplicateIdentity,
+    Remediation,
+    SyncComplete,
+}
+
+impl NotableKind {
+    pub const ALL: [NotableKind; 13] = [
+        Self::FoundBlock,
+        Self::OrphanedBlock,
+        Self::UnpropagatedBlock,
+        Self::HashrateDrop,
+        Self::NoPeers,
+        Self::NodeError,
+        Self::NodeWarning,
+        Self::NodeExited,
+        Self::StartFailed,
+        Self::ForkDetected,
+        Self::DuplicateIdentity,
+        Self::Remediation,
+        Self::SyncComplete,
+    ];
+
+    fn default_level(self) -> Notability {
+        match self {
+            Self::FoundBlock | Self::SyncComplete | Self::NodeWarning => Notability::Info,
+            Self::OrphanedBlock
+            | Self::UnpropagatedBlock
+            | Self::HashrateDrop
+            | Self::NoPeers
+            | Self::NodeExited
+            | Self::Remediation => Notability::Warning,
+            Self::NodeError | Self::StartFailed | Self::ForkDetected | Self::DuplicateIdentity => {
+                Notability::Critical
+            }
+        }
+    }
+
+    // what `test_notable_event` sends
+    fn sample_message(self) -> &'static str {
+        match self {
+            Self::FoundBlock => "Block #12345 found",
+            Self::OrphanedBlock => "Block #12345 was orphaned",
+            Self::UnpropagatedBlock => "Block #12345 didn't reach the network; likely orphaned",
+            Self::HashrateDrop => "Hashrate dropped 60% (from 2.5 MH/s to 1.0 MH/s)",
+            Self::NoPeers => "No peers for 5 min",
+            Self::NodeError => "Node error: Essential task failed",
+            Self::NodeWarning => "Node warning: Low peer count",
+            Self::NodeExited => "The node had exited on its own",
+            Self::StartFailed => "Node failed to start",
+            Self::ForkDetected => "The node seems stuck on a fork",
+            Self::DuplicateIdentity => "Another node uses this node's network key",
+            Self::Remediation => "A problem with a suggested fix was found",
+            Self::SyncComplete => "Sync complete",
+        }
+    }
+}
+
+/// Which events are notable (`{app_data_dir}/notable_rules.json`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NotableRules {
+    /// Per kind; kinds left out use their default.
+    pub levels: BTreeMap<NotableKind, Notability>,
+    /// Percent below the 10 min peak that counts as a hashrate drop (1-99).
+    pub hashrate_drop_pct: u32,
+    /// Seconds at 0 peers before it's notable.
+    pub no_peers_secs: u64,
+}
+
+impl Default for NotableRules {
+    fn default() -> Self {
+        NotableRules {
+            levels: NotableKind::ALL
+                .iter()
+                .map(|&k| (k, k.default_level()))
+                .collect(),
+            hashrate_drop_pct: 50,
+            no_peers_secs: 300,
+        }
+    }
+}
+
+impl NotableRules {
+    pub fn level(&self, kind: NotableKind) -> Notability {
+        self.levels
+            .get(&kind)
+            .copied()
+            .unwrap_or_else(|| kind.default_level())
+    }
+}
+
+impl crate::persist::Versioned for NotableRules {
+    const VERSION: u32 = 1;
+    const MIGRATIONS: &'static [crate::persist::Migration] = &[crate::persist::unchanged];
+}
+
+/// `miner:notable` payload: one notable event, compact enough to drive sounds and toasts
+/// without listening to everything.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Notable {
+    pub kind: NotableKind,
+    pub notability: Notability,
+    /// The event it was classified from.
+    pub event: &'static str,
+    pub chain: Option<String>,
+    pub message: String,
+    /// Sent by `test_notable_event`.
+    pub test: bool,
+    pub ts: i64,
+}
+
+#[derive(Default)]
+struct State {
+    // per chain ("" for events without one)
+    hashrate: HashMap<String, VecDeque<(Instant, f64)>>,
+    hashrate_dropped: HashMap<String, bool>,
+    no_peers_since: HashMap<String, Instant>,
+    no_peers_sent: HashMap<String, bool>,
+    found: VecDeque<(Option<u64>, Instant)>,
+}
+
+lazy_static! {
+    static ref RULES: std::sync::Mutex<Option<NotableRules>> = std::sync::Mutex::new(None);
+    static ref STATE: std::sync::Mutex<State> = std::sync::Mutex::new(State::default());
+}
+
+fn rules_path(app: &AppHandle) -> PathBuf {
+    crate::paths::app_data_dir(app).join("notable_rules.json")
+}
+
+pub fn load_rules(app: &AppHandle) -> NotableRules {
+    if let Some(r) = RULES.lock().ok().and_then(|r| r.clone()) {
+        return r;
+    }
+    let mut r: NotableRules = crate::persist::load_versioned(&rules_path(app)).unwrap_or_default();
+    for k in NotableKind::ALL {
+        r.levels.entry(k).or_insert_with(|| k.default_level());
+    }
+    if let Ok(mut cached) = RULES.lock() {
+        *cached = Some(r.clone());
+    }
+    r
+}
+
+pub fn save_rules(app: &AppHandle, r: &NotableRules) -> Result<()> {
+    if !(1..=99).contains(&r.hashrate_drop_pct) {
+        return Err(anyhow!(
+            "the hashrate drop must be between 1 and 99 percent"
+        ));
+    }
+    if r.no_peers_secs < MIN_NO_PEERS_SECS {
+        return Err(anyhow!(
+            "the no-peers time must be at least {MIN_NO_PEERS_SECS} seconds"
+        ));
+    }
+    let path = rules_path(app);
+    crate::persist::store_versioned(&path, r)?;
+    if let Ok(mut cached) = RULES.lock() {
+        *cached = Some(r.clone());
+    }
+    Ok(())
+}
+
+fn hps_text(hps: f64) -> String {
+    if hps >= 1e6 {
+        format!("{:.1} MH/s", hps / 1e6)
+    } else if hps >= 1e3 {
+        format!("{:.1} kH/s", hps / 1e3)
+    } else {
+        format!("{hps:.0} H/s")
+    }
+}
+
+// The rule `payload` of `event` matches, with a message; None if it isn't notable.
+fn matched(
+    rules: &NotableRules,
+    event: &str,
+    chain: &str,
+    payload: &serde_json::Value,
+) -> Option<(NotableKind, String)> {
+    let str_of = |k: &str| payload.get(k).and_then(|v| v.as_str());
+    let u64_of = |k: &str| payload.get(k).and_then(|v| v.as_u64());
+    let now = Instant::now();
+    match event {
+        events::MINER_EVENT => match str_of("type")? {
+            "FoundBlock" => {
+                let height = u64_of("height");
+                let mut state = STATE.lock().ok()?;
+                state
+                    .found
+                    .retain(|(_, at)| now.duration_since(*at) < FOUND_BLOCK_DEDUPE);
+                if height.is_some() && state.found.iter().any(|(h, _)| *h == height) {
+                    return None;
+                }
+                state.found.push_back((height, now));
+                let at = height.map(|h| format!(" #{h}")).unwrap_or_default();
+                Some((NotableKind::FoundBlock, format!("Block{at} found")))
+            }
+            "Error" => {
+                let message = str_of("message").unwrap_or_default();
+                if str_of("severity") == Some("error") {
+                    Some((NotableKind::NodeError, format!("Node error: {message}")))
+                } else {
+                    Some((NotableKind::NodeWarning, format!("Node warning: {message}")))
+                }
+            }
+            "Hashrate" => {
+                let hps = payload.get("hps")?.as_f64()?;
+                let mut state = STATE.lock().ok()?;
+                let samples = state.hashrate.entry(chain.to_string()).or_default();
+                samples.retain(|(at, _)| now.duration_since(*at) <= HASHRATE_WINDOW);
+                let peak = samples.iter().map(|(_, h)| *h).fold(0.0, f64::max);
+                samples.push_back((now, hps));
+                let floor = peak * (100 - rules.hashrate_drop_pct.min(99)) as f64 / 100.0;
+                let dropped = peak > 0.0 && hps < floor;
+                let was = state
+                    .hashrate_dropped
+                    .insert(chain.to_string(), dropped)
+                    .unwrap_or(false);
+                (dropped && !was).then(|| {
+                    (
+                        NotableKind::HashrateDrop,
+                        format!(
+                            "Hashrate dropped {:.0}% (from {} to {})",
+                            (1.0 - hps / peak) * 100.0,
+                            hps_text(peak),
+                            hps_text(hps)
+                        ),
+                    )
+                })
+            }
+            _ => None,
+        },
+        events::MINER_STATUS => {
+            // partial updates may not carry peers
+            let peers = u64_of("peers")?;
+            let mut state = STATE.lock().ok()?;
+            if peers > 0 {
+                state.no_peers_since.remove(chain);
+                state.no_peers_sent.remove(chain);
+                return None;
+            }
+            let since = *state.no_peers_since.entry(chain.to_string()).or_insert(now);
+            let due = now.duration_since(since).as_secs() >= rules.no_peers_secs;
+            if !due || state.no_peers_sent.insert(chain.to_string(), true) == Some(true) {
+                return None;
+            }
+            Some((
+                NotableKind::NoPeers,
+                format!("No peers for {} min", rules.no_peers_secs.div_ceil(60)),
+            ))
+        }
+        events::MINER_AUTHORED => (str_of("change")? == "orphaned").then(|| {
+            let height = payload.get("block").and_then(|b| b.get("height"));
+            (
+                NotableKind::OrphanedBlock,
+                format!(
+                    "Block #{} was orphaned",
+                    height.cloned().unwrap_or_default()
+                ),
+            )
+        }),
+        events::MINER_BLOCK_PROPAGATED => (!payload.get("propagated")?.as_bool()?).then(|| {
+            (
+                NotableKind::UnpropagatedBlock,
+                format!(
+                    "Block #{} didn't reach the network; likely orphaned",
+                    u64_of("height").unwrap_or_default()
+                ),
+            )
+        }),
+        // a stop the user or the app asked for isn't notable; a node already gone is
+        events::MINER_EXITED => {
+            let kind = payload.get("exit")?.get("kind")?.as_str()?;
+            (kind == "already_dead").then(|| {
+                (
+                    NotableKind::NodeExited,
+                    "The node had exited on its own".to_string(),
+                )
+            })
+        }
+        events::MINER_START_FAILED => Some((
+            NotableKind::StartFailed,
+            match str_of("reason") {
+                Some(r) => format!("Node failed to start: {r}"),
+                None => "Node failed to start".to_string(),
+            },
+        )),
+        events::MINER_FORK_DETECTED => Some((
+            NotableKind::ForkDetected,
+            "The node seems stuck on a fork".to_string(),
+        )),
+        events::MINER_DUPLICATE_IDENTITY => Some((
+            NotableKind::DuplicateIdentity,
+            "Another node uses this node's network key".to_string(),
+        )),
+        events::MINER_REMEDIATION => Some((
+            NotableKind::Remediation,
+            str_of("detail")
+                .unwrap_or("A problem with a suggested fix was found")
+                .to_string(),
+        )),
+        events::MINER_SYNC_COMPLETE => {
+            Some((NotableKind::SyncComplete, "Sync complete".to_string()))
+        }
+        _ => None,
+    }
+}
+
+/// Run `payload`, about to be sent on `event`, through the rules. A notable one gets a
+/// `notability` field and is announced on `miner:notable`. Called by `events::emit` for
+/// every event.
+pub fn classify(app: &AppHandle, event: &'static str, payload: &mut serde_json::Value) {
+    if event == events::MINER_NOTABLE || event == events::MINER_LOG {
+        return;
+    }
+    let rules = load_rules(app);
+    let chain = payload
+        .get("chain")
+        .and_then(|c| c.as_str())
+        .map(str::to_string);
+    let Some((kind, message)) = matched(&rules, event, chain.as_deref().unwrap_or(""), payload)
+    else {
+        return;
+    };
+    let notability = rules.level(kind);
+    if notability == Notability::None {
+        return;
+    }
+    if let Some(obj) = payload.as_object_mut() {
+        obj.insert("notability".to_string(), serde_json::json!(notability));
+    }
+    events::emit(
+        app,
+        events::MINER_NOTABLE,
+        &Notable {
+            kind,
+            notability,
+            event,
+            chain,
+            message,
+            test: false,
+            ts: crate::timeutil::now_ms(),
+        },
+    );
+}
+
+/// Send a sample `miner:notable` of `kind` at its configured level, to preview the
+/// sound or toast it gets.
+pub fn test_event(app: &AppHandle, kind: NotableKind, chain: Option<String>) -> Notable {
+    let notable = Notable {
+        kind,
+        notability: load_rules(app).level(kind),
+        event: events::MINER_NOTABLE,
+        chain,
+        message: kind.sample_message().to_string(),
+        test: true,
+        ts: crate::timeutil::now_ms(),
+    };
+    events::emit(app, events::MINER_NOTABLE, &notable);
+    notable
+}
@@ -0,0 +1,170 @@
+use lazy_static::lazy_static;
+use serde::Serialize;
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc,
+    },
+};
+use tauri::AppHandle;
+
+use crate::events;
+
+lazy_static! {
+    // running operations by id
+    static ref OPS: std::sync::Mutex<HashMap<String, OperationInfo>> =
+        std::sync::Mutex::new(HashMap::new());
+}
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+/// An operation was cancelled through `cancel_operation`. Never retried (`net::is_retryable`).
+#[derive(Debug, thiserror::Error)]
+#[error("cancelled")]
+pub struct Cancelled;
+
+/// Shared flag a long-running operation checks between chunks/steps.
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// `Err(Cancelled)` once cancelled, for `?` at step boundaries.
+    pub fn check(&self) -> Result<(), Cancelled> {
+        if self.is_cancelled() {
+            Err(Cancelled)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// The raw flag, for blocking code that can't hold the token.
+    pub fn flag(&self) -> Arc<AtomicBool> {
+        self.0.clone()
+    }
+}
+
+/// Whether `err` (anywhere in its chain) is a cancellation.
+pub fn is_cancelled(err: &anyhow::Error) -> bool {
+    err.chain().any(|e| e.is::<Cancelled>())
+}
+
+/// `operation:started` / `operation:cancelled` payload, and an entry of `list_operations`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OperationInfo {
+    pub id: String,
+    /// "install" | "repair"
+    pub kind: &'static str,
+    pub chain: Option<String>,
+    /// Unix milliseconds.
+    pub started_at: i64,
+    #[serde(skip)]
+    token: CancelToken,
+}
+
+/// A registered long-running operation; unregistered on drop.
+pub struct Operation {
+    app: AppHandle,
+    info: OperationInfo,
+}
+
+impl Operation {
+    /// Register an operation and send `operation:started`, which carries the id the UI
+    /// passes to `cancel_operation`. `id` lets the caller pick it (so the UI knows it
+    /// before the event arrives); an id already in use gets a fresh one instead.
+    pub fn begin(
+        app: &AppHandle,
+        kind: &'static str,
+        chain: Option<&str>,
+        id: Option<String>,
+    ) -> Self {
+        let mut ops = OPS.lock().unwrap_or_else(|e| e.into_inner());
+        let id = id
+            .filter(|id| !id.is_empty() && !ops.contains_key(id))
+            .unwrap_or_else(|| format!("{kind}-{}", NEXT_ID.fetch_add(1, Ordering::Relaxed)));
+        let info = OperationInfo {
+            id: id.clone(),
+            kind,
+            chain: chain.map(str::to_string),
+            started_at: crate::timeutil::now_ms(),
+            token: CancelToken::default(),
+        };
+        ops.insert(id, info.clone());
+        drop(ops);
+        events::emit(app, events::OPERATION_STARTED, &info);
+        Operation {
+            app: app.clone(),
+            info,
+        }
+    }
+
+    pub fn token(&self) -> CancelToken {
+        self.info.token.clone()
+    }
+
+    /// Send the terminal `operation:cancelled` when the operation ended because it was
+    /// cancelled (its partial state is cleaned up by then).
+    pub fn finish<T, E>(&self, res: &Result<T, E>) {
+        if res.is_err() && self.info.token.is_cancelled() {
+            events::emit(&self.app, events::OPERATION_CANCELLED, &self.info);
+        }
+    }
+}
+
+impl Drop for Operation {
+    fn drop(&mut self) {
+        if let Ok(mut ops) = OPS.lock() {
+            ops.remove(&self.info.id);
+        }
+    }
+}
+
+/// Ask operation `id` to stop at its next check; false if no such operation is running.
+pub fn cancel(id: &str) -> bool {
+    let Ok(ops) = OPS.lock() else {
+        return false;
+    };
+    match ops.get(id) {
+        Some(op) => {
+            op.token.0.store(true, Ordering::Relaxed);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Operations running now, oldest first.
+pub fn list() -> Vec<OperationInfo> {
+    let mut ops: Vec<OperationInfo> = OPS
+        .lock()
+        .map(|m| m.values().cloned().collect())
+        .unwrap_or_default();
+    ops.sort_by_key(|o| o.started_at);
+    ops
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn info_payload_is_camel_case() {
+        let info = OperationInfo {
+            id: "repair-1".into(),
+            kind: "repair",
+            chain: Some("resonance".into()),
+            started_at: 1000,
+            token: CancelToken::default(),
+        };
+        assert_eq!(
+            serde_json::to_value(&info).unwrap(),
+            json!({ "id": "repair-1", "kind": "repair", "chain": "resonance", "startedAt": 1000 })
+        );
+    }
+}
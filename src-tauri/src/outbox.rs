@@ -0,0 +1,221 @@
+use anyhow::Result;
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use std::{collections::VecDeque, fs, path::PathBuf, time::Duration};
+use tauri::AppHandle;
+use tokio::sync::Notify;
+
+/// Deliveries older than this are dropped undelivered.
+const MAX_AGE_SECS: i64 = 24 * 3600;
+/// Beyond this many queued deliveries the oldest are dropped.
+const MAX_ENTRIES: usize = 500;
+/// Retry delay after the first failure, doubled per further failure up to `MAX_BACKOFF_SECS`.
+const BACKOFF_SECS: i64 = 10;
+const MAX_BACKOFF_SECS: i64 = 600;
+
+/// One queued webhook POST.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Delivery {
+    pub id: u64,
+    pub url: String,
+    /// Event name, e.g. "miner:authored".
+    pub event: String,
+    pub payload: serde_json::Value,
+    /// Unix seconds.
+    pub queued_at: i64,
+    pub attempts: u32,
+    /// Unix seconds; not tried before this.
+    pub next_attempt_at: i64,
+    pub last_error: Option<String>,
+}
+
+/// Contents of `{app_data_dir}/outbox.json`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct Outbox {
+    next_id: u64,
+    entries: VecDeque<Delivery>,
+    dropped_expired: u64,
+    dropped_full: u64,
+}
+
+/// `get_pending_notifications`: the queue, oldest first, and what was dropped so far.
+#[derive(Debug, Clone, Serialize)]
+pub struct PendingNotifications {
+    pub entries: Vec<Delivery>,
+    /// Older than 24 h.
+    pub dropped_expired: u64,
+    /// Pushed out by a full queue.
+    pub dropped_full: u64,
+}
+
+lazy_static! {
+    static ref OUTBOX: std::sync::Mutex<Option<Outbox>> = std::sync::Mutex::new(None);
+    // something was queued; wakes the dispatcher
+    static ref WAKE: Notify = Notify::new();
+}
+
+fn outbox_path(app: &AppHandle) -> PathBuf {
+    crate::paths::app_data_dir(app).join("outbox.json")
+}
+
+fn save(app: &AppHandle, ob: &Outbox) -> Result<()> {
+    let path = outbox_path(app);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    crate::persist::write_atomic(&path, &serde_json::to_vec_pretty(ob)?)
+}
+
+// Change the queue (loaded on first use); `f` returns whether to write it.
+fn update<T>(app: &AppHandle, f: impl FnOnce(&mut Outbox) -> (bool, T)) -> T {
+    let mut guard = OUTBOX.lock().unwrap_or_else(|e| e.into_inner());
+    let ob = guard.get_or_insert_with(|| {
+        fs::read(outbox_path(app))
+            .ok()
+            .and_then(|b| serde_json::from_slice(&b).ok())
+            .unwrap_or_default()
+    });
+    let (changed, out) = f(ob);
+    if changed {
+        if let Err(e) = save(app, ob) {
+            eprintln!("outbox: {e}");
+        }
+    }
+    out
+}
+
+// Drop expired entries, then the oldest ones over `MAX_ENTRIES`; true if any went.
+fn prune(ob: &mut Outbox, now: i64) -> bool {
+    let before = ob.entries.len();
+    ob.entries.retain(|d| now - d.queued_at <= MAX_AGE_SECS);
+    let expired = before - ob.entries.len();
+    ob.dropped_expired += expired as u64;
+    let mut full = 0;
+    while ob.entries.len() > MAX_ENTRIES {
+        ob.entries.pop_front();
+        full += 1;
+    }
+    ob.dropped_full += full;
+    if expired > 0 || full > 0 {
+        eprintln!("outbox: dropped {expired} expired and {full} overflowing deliveries");
+    }
+    expired > 0 || full > 0
+}
+
+fn backoff(attempts: u32) -> i64 {
+    BACKOFF_SECS
+        .saturating_mul(1 << attempts.saturating_sub(1).min(16))
+        .min(MAX_BACKOFF_SECS)
+}
+
+/// Queue `payload` for the webhook in the settings (`webhook_url`; empty means off).
+/// Everything goes through the queue, so deliveries arrive in order even after an
+/// outage; see `spawn`.
+pub fn webhook(app: &AppHandle, event: &str, payload: &impl Serialize) {
+    let url = crate::settings::get(app).webhook_url;
+    let url = url.trim();
+    if !(url.starts_with("http://") || url.starts_with("https://")) {
+        return;
+    }
+    let payload = match serde_json::to_value(payload) {
+        Ok(v) => v,
+        Err(e) => {
+            eprintln!("outbox: {e}");
+            return;
+        }
+    };
+    let now = crate::timeutil::now_secs();
+    update(app, |ob| {
+        ob.next_id += 1;
+        ob.entries.push_back(Delivery {
+            id: ob.next_id,
+            url: url.to_string(),
+            event: event.to_string(),
+            payload,
+            queued_at: now,
+            attempts: 0,
+            next_attempt_at: now,
+            last_error: None,
+        });
+        prune(ob, now);
+        (true, ())
+    });
+    WAKE.notify_one();
+}
+
+/// The queue as it is now (`get_pending_notifications`).
+pub fn pending(app: &AppHandle) -> PendingNotifications {
+    update(app, |ob| {
+        (
+            false,
+            PendingNotifications {
+                entries: ob.entries.iter().cloned().collect(),
+                dropped_expired: ob.dropped_expired,
+                dropped_full: ob.dropped_full,
+            },
+        )
+    })
+}
+
+async fn deliver(d: &Delivery) -> Result<()> {
+    crate::net::client()
+        .post(&d.url)
+        .json(&serde_json::json!({
+            "event": d.event,
+            "payload": d.payload,
+            "queued_at": d.queued_at,
+            "attempt": d.attempts + 1,
+        }))
+        .send()
+        .await?
+        .error_for_status()?;
+    Ok(())
+}
+
+/// The dispatcher: delivers the queue strictly oldest first. A failed delivery stays at
+/// the head and is retried with backoff, holding back newer ones, so a receiver never
+/// sees events out of order. Whatever is left at exit is picked up on the next start.
+pub fn spawn(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let now = crate::timeutil::now_secs();
+            let head = update(&app, |ob| {
+                let pruned = prune(ob, now);
+                (pruned, ob.entries.front().cloned())
+            });
+            let Some(head) = head else {
+                WAKE.notified().await;
+                continue;
+            };
+            let wait = head.next_attempt_at - now;
+            if wait > 0 {
+                tokio::select! {
+                    _ = tokio::time::sleep(Duration::from_secs(wait as u64)) => {}
+                    _ = WAKE.notified() => {}
+                }
+                continue;
+            }
+            let res = deliver(&head).await;
+            update(&app, |ob| {
+                // pruned while we were sending
+                if ob.entries.front().map(|d| d.id) != Some(head.id) {
+                    return (false, ());
+                }
+                match res {
+                    Ok(()) => {
+                        ob.entries.pop_front();
+                    }
+                    Err(e) => {
+                        if let Some(d) = ob.entries.front_mut() {
+                            d.attempts += 1;
+                            d.next_attempt_at = crate::timeutil::now_secs() + backoff(d.attempts);
+                            d.last_error = Some(e.to_string());
+                        }
+                    }
+                }
+                (true, ())
+            });
+        }
+    });
+}
@@ -1,8 +1,18 @@
 use regex::Regex;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+
+/// How important an event is; events below the configured minimum aren't sent to the UI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
 
 #[derive(Debug, Clone, Serialize)]
-#[serde(tag = "type")]
+#[serde(tag = "type", rename_all_fields = "camelCase")]
 pub enum MinerEvent {
     Connected,
     Hashrate {
@@ -12,23 +22,123 @@ pub enum MinerEvent {
     FoundBlock {
         height: Option<u64>,
         hash: Option<String>,
+        /// Seen imported with our rewards account as PoW author (not just a log line).
+        verified: bool,
     },
     Error {
         message: String,
+        severity: Severity, // Warn or Error
+    },
+    /// "Block import took 32s" and similar, at or above `SLOW_IMPORT_SECS`.
+    SlowImport {
+        block: Option<u64>,
+        seconds: f64,
+    },
+    /// The import queue reported a backlog (`queued` blocks, when the line says).
+    ImportBacklog {
+        queued: Option<u64>,
+    },
+    /// Node informant line ("💤 Idle (7 peers), best: #123 …" / "⚙️ Syncing … target=#N …").
+    NodeStatus {
+        state: String, // "idle" | "syncing" | "preparing"
+        peers: Option<u32>,
+        best: Option<u64>,
+        finalized: Option<u64>,
+        target: Option<u64>,
+        /// Blocks imported per second ("syncing 12.3 bps"); idle lines have none.
+        bps: Option<f64>,
     },
 }
 
+impl MinerEvent {
+    pub fn severity(&self) -> Severity {
+        match self {
+            MinerEvent::NodeStatus { .. } | MinerEvent::ImportBacklog { .. } => Severity::Debug,
+            MinerEvent::Connected
+            | MinerEvent::SlowImport { .. }
+            | MinerEvent::Hashrate { .. }
+            | MinerEvent::ShareAccepted
+            | MinerEvent::FoundBlock { .. } => Severity::Info,
+            MinerEvent::Error { severity, .. } => *severity,
+        }
+    }
+}
+
+/// The node's output stream a line came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stream {
+    Stdout,
+    Stderr,
+}
+
+/// The node mirrors some messages to both stdout and stderr. An event equal to one
+/// parsed from the other stream this recently is that mirror, not a second event.
+pub const MIRROR_WINDOW: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// Drops the mirrored copy of an event, shared by a node's two reader tasks. Repeats on
+/// the same stream are kept: those are real (a second identical hashrate report).
+#[derive(Debug, Default)]
+pub struct EventDedupe {
+    // events admitted within the window: stream, serialized event, when
+    recent: std::collections::VecDeque<(Stream, String, std::time::Instant)>,
+    /// Mirrored events dropped so far.
+    pub suppressed: u64,
+}
+
+impl EventDedupe {
+    /// Whether `ev`, read from `stream` at `now`, should be forwarded.
+    pub fn admit(&mut self, stream: Stream, ev: &MinerEvent, now: std::time::Instant) -> bool {
+        self.recent
+            .retain(|(_, _, at)| now.saturating_duration_since(*at) <= MIRROR_WINDOW);
+        let Ok(key) = serde_json::to_string(ev) else {
+            return true;
+        };
+        if let Some(i) = self
+            .recent
+            .iter()
+            .position(|(s, k, _)| *s != stream && *k == key)
+        {
+            // matched once: a third copy is a new event again
+            self.recent.remove(i);
+            self.suppressed += 1;
+            return false;
+        }
+        self.recent.push_back((stream, key, now));
+        true
+    }
+}
+
+/// Messages that are errors even when the node doesn't log them at WARN/ERROR.
+const KNOWN_ERRORS: &[&str] = &[
+    "invalid argument: column families not opened",
+    "resource temporarily unavailable",
+    "essential task",
+    "panicked at",
+    "error while loading shared libraries",
+];
+
 pub fn parse_event(line: &str) -> Option<MinerEvent> {
     let l = line.to_lowercase();
 
-    // very forgiving first pass; tighten once you know exact strings
-    if l.contains("connected to") || l.contains("syncing") {
+    // informant status lines mention "syncing" and peers, so check them first
+    if let Some(ev) = parse_node_status(&l) {
+        return Some(ev);
+    }
+    // The caller only forwards the first of these per session.
+    if is_connected_line(&l) {
         return Some(MinerEvent::Connected);
     }
     // hashrate: "hashrate: 1234 H/s" or "H/s=1234.56"
     if let Some(ev) = parse_hashrate(&l) {
         return Some(ev);
     }
+    // before errors: the node logs heavy imports at WARN
+    if let Some((block, seconds)) = slow_import(&l) {
+        return Some(MinerEvent::SlowImport { block, seconds });
+    }
+    if let Some(queued) = import_backlog(&l) {
+        return Some(MinerEvent::ImportBacklog { queued });
+    }
     if l.contains("share accepted") || l.contains("accepted share") {
         return Some(MinerEvent::ShareAccepted);
     }
@@ -43,16 +153,215 @@ pub fn parse_event(line: &str) -> Option<MinerEvent> {
             .or_else(|| capture_str(&l, r"(?:hash|block)[ =:]+([0-9a-fx]+)"));
         // height is sometimes not present in the success line; leave as None if not found
         let height = capture_u64(&l, r"height[ =:]+(\d+)");
-        return Some(MinerEvent::FoundBlock { height, hash });
+        return Some(MinerEvent::FoundBlock {
+            height,
+            hash,
+            verified: false,
+        });
     }
-    if l.contains("error") || l.contains("failed") {
+    if let Some(severity) = error_severity(line, &l) {
         return Some(MinerEvent::Error {
             message: line.trim().to_string(),
+            severity,
         });
     }
     None
 }
 
+/// Why a node died during startup, from its last stderr lines.
+#[derive(Debug, Clone, Serialize)]
+pub struct StartupFailure {
+    /// "port_in_use" | "db_corrupt" | "db_locked" | "disk_full" | "bad_argument"
+    /// | "missing_library" | "panic" | "exited"
+    pub kind: &'static str,
+    /// The line that gave it away (or the last error line).
+    pub reason: String,
+}
+
+/// Startup failure signatures, checked in order against lowercased lines.
+const STARTUP_FAILURES: &[(&str, &str)] = &[
+    ("address already in use", "port_in_use"),
+    ("column families not opened", "db_corrupt"),
+    ("corruption", "db_corrupt"),
+    ("resource temporarily unavailable", "db_locked"),
+    ("lock hold by current process", "db_locked"),
+    ("no space left on device", "disk_full"),
+    ("unexpected argument", "bad_argument"),
+    ("invalid value", "bad_argument"),
+    ("error while loading shared libraries", "missing_library"),
+    ("panicked at", "panic"),
+];
+
+/// A line reporting a corrupt database (the `db_corrupt` startup signatures).
+pub fn is_db_corruption(line: &str) -> bool {
+    let l = line.to_lowercase();
+    STARTUP_FAILURES
+        .iter()
+        .any(|(needle, kind)| *kind == "db_corrupt" && l.contains(needle))
+}
+
+pub fn classify_startup_failure(lines: &[String]) -> StartupFailure {
+    for (needle, kind) in STARTUP_FAILURES {
+        if let Some(line) = lines.iter().find(|l| l.to_lowercase().contains(needle)) {
+            return StartupFailure {
+                kind,
+                reason: line.trim().to_string(),
+            };
+        }
+    }
+    // otherwise the last error-level line, else the last line at all
+    let reason = lines
+        .iter()
+        .rev()
+        .find(|l| error_severity(l, &l.to_lowercase()) == Some(Severity::Error))
+        .or_else(|| lines.iter().rev().find(|l| !l.trim().is_empty()))
+        .map(|l| l.trim().to_string())
+        .unwrap_or_else(|| "no output".into());
+    StartupFailure {
+        kind: "exited",
+        reason,
+    }
+}
+
+/// Messages (lowercased) that mean the node can't execute the chain's current runtime,
+/// typically because the binary predates a runtime upgrade.
+const RUNTIME_VERSION_ERRORS: &[&str] = &[
+    "runtime version",
+    "spec_version",
+    "versionmismatch",
+    "incompatible runtime",
+    "is not found in the runtime",
+    "exported method",
+    "missing host function",
+    "unsupported host function",
+    "failed to instantiate a new wasm module",
+    "cannot create a runtime",
+];
+
+/// An error/warning line saying block import or execution failed because of the runtime
+/// version (see `RUNTIME_VERSION_ERRORS`).
+pub fn is_runtime_version_error(line: &str) -> bool {
+    let l = line.to_lowercase();
+    error_severity(line, &l).is_some() && RUNTIME_VERSION_ERRORS.iter().any(|k| l.contains(k))
+}
+
+/// A warning or error line (the node's level field or a known failure): what session
+/// files keep while `log_space` throttles them.
+pub fn is_problem_line(line: &str) -> bool {
+    error_severity(line, &line.to_lowercase()).is_some()
+}
+
+/// Messages (lowercased) about another node using our peer id: the same network key
+/// (`secret_dilithium`) runs on two machines and peers drop or ban one of them.
+const DUPLICATE_IDENTITY: &[&str] = &[
+    "duplicate peer id",
+    "duplicate peerid",
+    "duplicate connection",
+    "peer id already in use",
+    "peerid already in use",
+    "local peer id conflict",
+    "reason: duplicate",
+    "disconnected: duplicate",
+    "dialed the local peer",
+];
+
+/// A log line showing that our node identity is duplicated elsewhere (see
+/// `DUPLICATE_IDENTITY`); any level, the node reports most of these at debug/info.
+pub fn is_duplicate_identity(line: &str) -> bool {
+    let l = line.to_lowercase();
+    DUPLICATE_IDENTITY.iter().any(|k| l.contains(k))
+}
+
+/// Block imports slower than this count as sync trouble.
+pub const SLOW_IMPORT_SECS: f64 = 5.0;
+
+/// Signs that sync is thrashing on heavy blocks (see `sync_trouble`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncTrouble {
+    /// "Block import took 12.3 seconds" and similar, at or above `SLOW_IMPORT_SECS`.
+    SlowImport,
+    /// A block/sync request to a peer timed out.
+    RequestTimeout,
+    /// The import queue reported a backlog.
+    ImportBacklog,
+}
+
+// (block, seconds) of a block import at or above `SLOW_IMPORT_SECS`; `l` lowercased.
+fn slow_import(l: &str) -> Option<(Option<u64>, f64)> {
+    static RE_SLOW: once_cell::sync::Lazy<Regex> = once_cell::sync::Lazy::new(|| {
+        Regex::new(
+            r"(?:block import|importing block|import of block).*?took\s+([\d.]+)\s*(ms|s|sec)",
+        )
+        .unwrap()
+    });
+    let c = RE_SLOW.captures(l)?;
+    let secs = c[1].parse::<f64>().unwrap_or(f64::MAX);
+    let secs = if &c[2] == "ms" { secs / 1000.0 } else { secs };
+    if secs < SLOW_IMPORT_SECS {
+        return None;
+    }
+    let block = capture_u64(l, r"#(\d+)").or_else(|| capture_u64(l, r"block\s+(\d+)"));
+    Some((block, secs))
+}
+
+// Import queue backlog warnings ("import queue is full", "import queue backlog: 512
+// blocks", "too many blocks in the import queue"); the count if the line has one.
+fn import_backlog(l: &str) -> Option<Option<u64>> {
+    if !l.contains("import queue") && !l.contains("import-queue") {
+        return None;
+    }
+    let backlog = ["backlog", "is full", "too many", "pending"]
+        .iter()
+        .any(|k| l.contains(k));
+    backlog.then(|| {
+        capture_u64(l, r"(\d+)\s+(?:blocks|pending|queued)")
+            .or_else(|| capture_u64(l, r"(?:backlog|queued|pending)[ =:]+(\d+)"))
+    })
+}
+
+/// Classify a node log line as sync trouble; feeds the adaptive safe-mode trigger.
+pub fn sync_trouble(line: &str) -> Option<SyncTrouble> {
+    let l = line.to_lowercase();
+    if slow_import(&l).is_some() {
+        return Some(SyncTrouble::SlowImport);
+    }
+    if import_backlog(&l).is_some() {
+        return Some(SyncTrouble::ImportBacklog);
+    }
+    let timed_out = l.contains("timeout") || l.contains("timed out");
+    if timed_out && l.contains("request") {
+        return Some(SyncTrouble::RequestTimeout);
+    }
+    None
+}
+
+fn is_connected_line(l: &str) -> bool {
+    static RE: once_cell::sync::Lazy<Regex> =
+        once_cell::sync::Lazy::new(|| Regex::new(r"(?:^|[^a-z])connected to\b").unwrap());
+    RE.is_match(l) && !l.contains("not connected to")
+}
+
+/// Severity of a problem line: the node's own WARN/ERROR level field, or a known
+/// failure message. Plain mentions of "error"/"failed" (e.g. "0 failed imports",
+/// a peer named "error") are not problems.
+fn error_severity(line: &str, l: &str) -> Option<Severity> {
+    // "2025-01-01 12:00:00 ERROR tokio-runtime-worker sync: …" (level is upper-case)
+    static RE_LEVEL: once_cell::sync::Lazy<Regex> = once_cell::sync::Lazy::new(|| {
+        Regex::new(r"^(?:\S+\s+\S+\s+)?(?:\x1b\[[0-9;]*m)?(ERROR|WARN)\b").unwrap()
+    });
+    if let Some(c) = RE_LEVEL.captures(line.trim_start()) {
+        return Some(if &c[1] == "ERROR" {
+            Severity::Error
+        } else {
+            Severity::Warn
+        });
+    }
+    if KNOWN_ERRORS.iter().any(|k| l.contains(k)) {
+        return Some(Severity::Error);
+    }
+    None
+}
+
 fn parse_hashrate(l: &str) -> Option<MinerEvent> {
     // try couple formats
     static RE1: once_cell::sync::Lazy<Regex> =
@@ -67,6 +376,73 @@ fn parse_hashrate(l: &str) -> Option<MinerEvent> {
     None
 }
 
+/// Parse the node's periodic status line. Examples:
+///  - "💤 idle (7 peers), best: #123 (0xab…), finalized #120 (0xcd…), ⬇ 1.2kib/s ⬆ 0.4kib/s"
+///  - "⚙️  syncing 12.3 bps, target=#4567 (8 peers), best: #1234 (0x…), finalized #1000 (0x…)"
+///  - "⏩ preparing  0.0 bps, target=#4567 (3 peers), best: #0 (0x…), finalized #0 (0x…)"
+///
+/// Expects an already lowercased line.
+pub fn parse_node_status(l: &str) -> Option<MinerEvent> {
+    static RE_STATE: once_cell::sync::Lazy<Regex> = once_cell::sync::Lazy::new(|| {
+        Regex::new(r"\b(idle|syncing|preparing)\b[^,]*?(?:,\s*target=#\d+\s*)?\(\d+\s+peers?\)")
+            .unwrap()
+    });
+    static RE_PEERS: once_cell::sync::Lazy<Regex> =
+        once_cell::sync::Lazy::new(|| Regex::new(r"\((\d+)\s+peers?\)").unwrap());
+    static RE_BEST: once_cell::sync::Lazy<Regex> =
+        once_cell::sync::Lazy::new(|| Regex::new(r"best:\s*#(\d+)").unwrap());
+    static RE_FINALIZED: once_cell::sync::Lazy<Regex> =
+        once_cell::sync::Lazy::new(|| Regex::new(r"finalized\s*#(\d+)").unwrap());
+    static RE_TARGET: once_cell::sync::Lazy<Regex> =
+        once_cell::sync::Lazy::new(|| Regex::new(r"target=#(\d+)").unwrap());
+    static RE_BPS: once_cell::sync::Lazy<Regex> =
+        once_cell::sync::Lazy::new(|| Regex::new(r"([\d.]+)\s*bps\b").unwrap());
+
+    let state = RE_STATE.captures(l)?.get(1)?.as_str().to_string();
+    let num = |re: &Regex| re.captures(l).and_then(|c| c[1].parse::<u64>().ok());
+    Some(MinerEvent::NodeStatus {
+        state,
+        peers: num(&RE_PEERS).map(|p| p as u32),
+        best: num(&RE_BEST),
+        finalized: num(&RE_FINALIZED),
+        target: num(&RE_TARGET),
+        bps: RE_BPS.captures(l).and_then(|c| c[1].parse::<f64>().ok()),
+    })
+}
+
+/// Warp and state sync stages, from lines the informant has no idle/syncing state for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncStage {
+    /// "⏩ Warping, Downloading finality proofs, 12.3 MiB (5 peers), …"
+    Warp,
+    /// "⏩ Warping, Downloading state, …", "⏩ Warping, Importing state, …" and the
+    /// state sync progress lines of a fast sync.
+    State,
+    /// "Warp sync is complete", "State sync is complete": block sync takes over.
+    Done,
+}
+
+/// Classify a node log line as a warp/state sync stage.
+pub fn sync_stage(line: &str) -> Option<SyncStage> {
+    let l = line.to_lowercase();
+    if (l.contains("warp sync") || l.contains("state sync")) && l.contains("complete") {
+        return Some(SyncStage::Done);
+    }
+    if l.contains("warping") {
+        let state = l.contains("state") && !l.contains("finality proofs");
+        return Some(if state {
+            SyncStage::State
+        } else {
+            SyncStage::Warp
+        });
+    }
+    if l.contains("state sync") || l.contains("downloading state") || l.contains("importing state")
+    {
+        return Some(SyncStage::State);
+    }
+    None
+}
+
 fn capture_u64(l: &str, pat: &str) -> Option<u64> {
     let re = Regex::new(pat).ok()?;
     let c = re.captures(l)?;
@@ -77,3 +453,124 @@ fn capture_str(l: &str, pat: &str) -> Option<String> {
     let c = re.captures(l)?;
     Some(c.get(1)?.as_str().to_string())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CORPUS: &str = include_str!("../tests/fixtures/node_log_corpus.txt");
+
+    // The corpus' one-line form of what a line parses to.
+    fn corpus_summary(ev: Option<MinerEvent>) -> String {
+        let opt = |name: &str, v: Option<String>| v.map(|v| format!(" {name}={v}"));
+        let fields = |fs: Vec<Option<String>>| fs.into_iter().flatten().collect::<String>();
+        match ev {
+            None => "none".into(),
+            Some(MinerEvent::Connected) => "connected".into(),
+            Some(MinerEvent::Hashrate { hps }) => format!("hashrate {hps}"),
+            Some(MinerEvent::ShareAccepted) => "share".into(),
+            Some(MinerEvent::FoundBlock { height, hash, .. }) => format!(
+                "found{}",
+                fields(vec![
+                    opt("hash", hash),
+                    opt("height", height.map(|h| h.to_string())),
+                ])
+            ),
+            Some(MinerEvent::Error { severity, .. }) => match severity {
+                Severity::Error => "error".into(),
+                _ => "warn".into(),
+            },
+            Some(MinerEvent::SlowImport { block, seconds }) => format!(
+                "slow-import{} seconds={seconds}",
+                fields(vec![opt("block", block.map(|b| b.to_string()))])
+            ),
+            Some(MinerEvent::ImportBacklog { queued }) => format!(
+                "backlog{}",
+                fields(vec![opt("queued", queued.map(|q| q.to_string()))])
+            ),
+            Some(MinerEvent::NodeStatus {
+                state,
+                peers,
+                best,
+                finalized,
+                target,
+                bps,
+            }) => format!(
+                "status {state}{}",
+                fields(vec![
+                    opt("peers", peers.map(|v| v.to_string())),
+                    opt("best", best.map(|v| v.to_string())),
+                    opt("finalized", finalized.map(|v| v.to_string())),
+                    opt("target", target.map(|v| v.to_string())),
+                    opt("bps", bps.map(|v| v.to_string())),
+                ])
+            ),
+        }
+    }
+
+    // (line number, expected, line) for each corpus entry.
+    fn corpus() -> Vec<(usize, &'static str, &'static str)> {
+        CORPUS
+            .lines()
+            .enumerate()
+            .filter(|(_, l)| !l.is_empty() && !l.starts_with('#'))
+            .map(|(i, l)| {
+                let (expected, line) = l.split_once(" | ").expect("`<expected> | <line>`");
+                (i + 1, expected, line)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn corpus_classification() {
+        let cases = corpus();
+        assert!(cases.len() >= 200, "corpus shrank to {}", cases.len());
+        let wrong: Vec<String> = cases
+            .into_iter()
+            .filter_map(|(n, expected, line)| {
+                let got = corpus_summary(parse_event(line));
+                (got != expected).then(|| format!("line {n}: expected `{expected}`, got `{got}`"))
+            })
+            .collect();
+        assert!(wrong.is_empty(), "\n{}", wrong.join("\n"));
+    }
+
+    #[test]
+    fn corpus_sync_trouble() {
+        for (n, expected, line) in corpus() {
+            let trouble = sync_trouble(line);
+            match expected.split(' ').next() {
+                Some("slow-import") => {
+                    assert_eq!(trouble, Some(SyncTrouble::SlowImport), "line {n}")
+                }
+                Some("backlog") => {
+                    assert_eq!(trouble, Some(SyncTrouble::ImportBacklog), "line {n}")
+                }
+                // request timeouts are the only trouble without an event of their own
+                _ => assert!(
+                    matches!(trouble, None | Some(SyncTrouble::RequestTimeout)),
+                    "line {n}: {trouble:?}"
+                ),
+            }
+        }
+        let timeouts = corpus()
+            .into_iter()
+            .filter(|(_, _, l)| sync_trouble(l) == Some(SyncTrouble::RequestTimeout))
+            .count();
+        assert!(timeouts >= 2, "{timeouts}");
+    }
+
+    #[test]
+    fn corpus_severities() {
+        // what the default minimum (info) keeps: everything but status lines and the
+        // backlog gauge
+        for (n, expected, line) in corpus() {
+            let Some(ev) = parse_event(line) else {
+                continue;
+            };
+            let shown = ev.severity() >= Severity::Info;
+            let gauge = expected.starts_with("status") || expected.starts_with("backlog");
+            assert_eq!(shown, !gauge, "line {n}");
+        }
+    }
+}
@@ -0,0 +1,136 @@
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::path::PathBuf;
+use tauri::{AppHandle, Manager};
+
+/// Next to the executable, this file turns portable mode on, as `--portable` does.
+const MARKER: &str = "portable.marker";
+
+/// A standard directory that wasn't available, and what was used instead.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Fallback {
+    /// "data_dir" | "data_local_dir" | "cache_dir" | "home_dir" | "app_data_dir"
+    pub dir: &'static str,
+    pub used: String,
+}
+
+/// How this run resolves its directories (`miner:meta` and the node report).
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PathsReport {
+    /// Everything lives under `base` (`--portable` or `portable.marker`).
+    pub portable: bool,
+    /// `data/` beside the executable: the portable root, and where missing standard
+    /// directories are replaced.
+    pub base: String,
+    /// Standard directories the OS didn't provide; empty in portable mode.
+    pub fallbacks: Vec<Fallback>,
+}
+
+static EXE_DIR: Lazy<Option<PathBuf>> = Lazy::new(|| {
+    std::env::current_exe()
+        .ok()
+        .and_then(|e| e.parent().map(PathBuf::from))
+});
+
+// `data/` beside the executable; the temp dir when even that isn't known.
+static BASE: Lazy<PathBuf> = Lazy::new(|| match EXE_DIR.as_ref() {
+    Some(d) => d.join("data"),
+    None => std::env::temp_dir().join("quantus-miner-data"),
+});
+
+static PORTABLE: Lazy<bool> = Lazy::new(|| {
+    std::env::args().skip(1).any(|a| a == "--portable")
+        || EXE_DIR.as_ref().is_some_and(|d| d.join(MARKER).is_file())
+});
+
+pub fn is_portable() -> bool {
+    *PORTABLE
+}
+
+/// Root of portable mode and of every fallback.
+pub fn base() -> PathBuf {
+    BASE.clone()
+}
+
+// `standard` unless portable or missing, else `sub` under the base.
+fn resolve(standard: Option<PathBuf>, sub: Option<&str>) -> PathBuf {
+    match standard {
+        Some(p) if !is_portable() => p,
+        _ => sub.map_or_else(base, |s| BASE.join(s)),
+    }
+}
+
+/// `dirs::data_dir`: the node's base path and the global safe ranges live under it.
+pub fn data_dir() -> PathBuf {
+    resolve(dirs::data_dir(), None)
+}
+
+/// `dirs::data_local_dir`: the default session logs directory.
+pub fn data_local_dir() -> PathBuf {
+    resolve(dirs::data_local_dir(), None)
+}
+
+/// `dirs::cache_dir`: release downloads.
+pub fn cache_dir() -> PathBuf {
+    resolve(dirs::cache_dir(), Some("cache"))
+}
+
+/// The user's home; None in portable mode or when unknown (callers pick a place under
+/// `base` then).
+pub fn home_dir() -> Option<PathBuf> {
+    dirs::home_dir().filter(|_| !is_portable())
+}
+
+/// Tauri's app data directory (settings, account, state files).
+pub fn app_data_dir(app: &AppHandle) -> PathBuf {
+    resolve(app.path().app_data_dir().ok(), Some("app"))
+}
+
+/// Which standard directories are missing and what replaces them.
+pub fn report(app: &AppHandle) -> PathsReport {
+    let mut fallbacks = Vec::new();
+    if !is_portable() {
+        let mut check = |dir: &'static str, missing: bool, used: PathBuf| {
+            if missing {
+                fallbacks.push(Fallback {
+                    dir,
+                    used: used.display().to_string(),
+                });
+            }
+        };
+        check("data_dir", dirs::data_dir().is_none(), data_dir());
+        check(
+            "data_local_dir",
+            dirs::data_local_dir().is_none(),
+            data_local_dir(),
+        );
+        check("cache_dir", dirs::cache_dir().is_none(), cache_dir());
+        check("home_dir", dirs::home_dir().is_none(), BASE.join("bin"));
+        check(
+            "app_data_dir",
+            app.path().app_data_dir().is_err(),
+            app_data_dir(app),
+        );
+    }
+    PathsReport {
+        portable: is_portable(),
+        base: BASE.display().to_string(),
+        fallbacks,
+    }
+}
+
+/// At startup, once the logs directory is known: say (stderr and the audit log) when
+/// running portable or with replaced directories.
+pub fn check_at_start(app: &AppHandle) {
+    let r = report(app);
+    if r.portable {
+        eprintln!("paths: portable mode, everything under {}", r.base);
+        crate::session_log::audit("-", "portable-mode", &r.base);
+    }
+    for f in &r.fallbacks {
+        eprintln!("paths: no {} available; using {}", f.dir, f.used);
+        crate::session_log::audit("-", "dir-fallback", &format!("{} -> {}", f.dir, f.used));
+    }
+}
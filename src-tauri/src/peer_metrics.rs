@@ -0,0 +1,215 @@
+use lazy_static::lazy_static;
+use serde::Serialize;
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+use tauri::AppHandle;
+
+/// How often the node's Prometheus exporter is read.
+const SCRAPE_INTERVAL: Duration = Duration::from_secs(15);
+const SCRAPE_TIMEOUT: Duration = Duration::from_secs(5);
+/// Outbound peers but nobody dialing in for this long: the node is likely behind NAT.
+const NAT_AFTER: Duration = Duration::from_secs(600);
+
+/// libp2p connection figures from the node's Prometheus exporter (`get_peer_metrics`).
+/// Counters are since the node started; fields the node doesn't export stay null.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct PeerMetrics {
+    /// Open connections we accepted...
+    pub inbound_peers: Option<u64>,
+    /// ...and those we dialed.
+    pub outbound_peers: Option<u64>,
+    /// Dials in progress.
+    pub pending_dials: Option<u64>,
+    /// Dials that failed (any reason).
+    pub failed_dials: Option<u64>,
+    /// Incoming connections dropped during the handshake.
+    pub handshake_errors: Option<u64>,
+    /// Notification streams (block announces, transactions) closed on us.
+    pub notification_streams_closed: Option<u64>,
+    /// No inbound connection for `NAT_AFTER` while outbound ones work.
+    pub nat_suspected: bool,
+    /// Seconds since the last inbound connection was seen (or since the node started).
+    pub no_inbound_secs: Option<u64>,
+    /// The port to forward when `nat_suspected`.
+    pub p2p_port: Option<u16>,
+    /// UTC milliseconds of the last successful read.
+    pub scraped_at: Option<i64>,
+}
+
+#[derive(Default)]
+struct Watch {
+    metrics: PeerMetrics,
+    no_inbound_since: Option<Instant>,
+    last_scrape: Option<Instant>,
+    in_flight: bool,
+    nat_reported: bool,
+}
+
+lazy_static! {
+    static ref WATCHES: Mutex<HashMap<String, Watch>> = Mutex::new(HashMap::new());
+}
+
+/// The last figures read for `chain`'s node.
+pub fn current(chain: &str) -> Option<PeerMetrics> {
+    let watches = WATCHES.lock().ok()?;
+    let w = watches.get(chain)?;
+    w.metrics.scraped_at.is_some().then(|| w.metrics.clone())
+}
+
+/// A new node session: its counters start over.
+pub fn reset_session(chain: &str) {
+    if let Ok(mut watches) = WATCHES.lock() {
+        watches.remove(chain);
+    }
+}
+
+/// Every status loop of a running node; reads its exporter every `SCRAPE_INTERVAL`.
+pub fn tick(app: &AppHandle, chain: &str) {
+    {
+        let Ok(mut watches) = WATCHES.lock() else {
+            return;
+        };
+        let w = watches.entry(chain.to_string()).or_default();
+        let due = w.last_scrape.is_none_or(|t| t.elapsed() >= SCRAPE_INTERVAL);
+        if w.in_flight || !due {
+            return;
+        }
+        w.in_flight = true;
+        w.last_scrape = Some(Instant::now());
+    }
+    let (app, chain) = (app.clone(), chain.to_string());
+    tauri::async_runtime::spawn(async move {
+        let ports = crate::miner::ports_for(&chain).await;
+        let scraped = match &ports {
+            Some(p) => scrape(p.prometheus).await,
+            None => None,
+        };
+        let Ok(mut watches) = WATCHES.lock() else {
+            return;
+        };
+        let w = watches.entry(chain.clone()).or_default();
+        w.in_flight = false;
+        let Some(mut m) = scraped else {
+            return;
+        };
+        let now = Instant::now();
+        match m.inbound_peers {
+            Some(0) => {
+                w.no_inbound_since.get_or_insert(now);
+            }
+            Some(_) => w.no_inbound_since = None,
+            None => {}
+        }
+        let quiet = w.no_inbound_since.map(|t| now.duration_since(t));
+        m.no_inbound_secs = quiet.map(|d| d.as_secs());
+        m.nat_suspected =
+            m.outbound_peers.unwrap_or(0) > 0 && quiet.is_some_and(|d| d >= NAT_AFTER);
+        m.p2p_port = ports.map(|p| p.p2p);
+        let report = m.nat_suspected && !w.nat_reported;
+        w.nat_reported |= m.nat_suspected;
+        w.metrics = m.clone();
+        drop(watches);
+        if report {
+            crate::session_log::ui(
+                &app,
+                &chain,
+                format!(
+                    "No inbound peers for {} min while {} outbound connections work; the node appears unreachable from the internet. Forwarding P2P port {} on the router lets other nodes connect.",
+                    NAT_AFTER.as_secs() / 60,
+                    m.outbound_peers.unwrap_or(0),
+                    m.p2p_port.map_or("-".into(), |p| p.to_string())
+                ),
+            );
+        }
+    });
+}
+
+async fn scrape(port: u16) -> Option<PeerMetrics> {
+    let url = format!("http://127.0.0.1:{port}/metrics");
+    let resp = crate::net::client()
+        .get(&url)
+        .timeout(SCRAPE_TIMEOUT)
+        .send()
+        .await
+        .and_then(|r| r.error_for_status());
+    let text = match resp {
+        Ok(r) => r.text().await.ok()?,
+        Err(e) => {
+            eprintln!("peer metrics {url}: {e}");
+            return None;
+        }
+    };
+    Some(parse(&text))
+}
+
+// One sample of the exposition format: `name{label="v",...} value`.
+fn sample(line: &str) -> Option<(&str, &str, f64)> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+        return None;
+    }
+    let (head, rest) = match line.find('{') {
+        Some(i) => {
+            let end = line[i..].find('}')? + i;
+            (&line[..i], (&line[i + 1..end], &line[end + 1..]))
+        }
+        None => {
+            let i = line.find(char::is_whitespace)?;
+            (&line[..i], ("", &line[i..]))
+        }
+    };
+    let (labels, value) = rest;
+    let value = value.split_whitespace().next()?.parse().ok()?;
+    Some((head, labels, value))
+}
+
+fn label<'a>(labels: &'a str, key: &str) -> Option<&'a str> {
+    labels.split(',').find_map(|kv| {
+        let (k, v) = kv.split_once('=')?;
+        (k.trim() == key).then(|| v.trim().trim_matches('"'))
+    })
+}
+
+// Connections by direction are opened minus closed; the gauge-less counters are summed
+// over their labels.
+fn parse(text: &str) -> PeerMetrics {
+    let mut opened: HashMap<&str, f64> = HashMap::new();
+    let mut closed: HashMap<&str, f64> = HashMap::new();
+    let mut m = PeerMetrics::default();
+    let add = |slot: &mut Option<u64>, v: f64| *slot = Some(slot.unwrap_or(0) + v as u64);
+    for (name, labels, v) in text.lines().filter_map(sample) {
+        match name {
+            "substrate_sub_libp2p_connections_opened_total" => {
+                *opened
+                    .entry(label(labels, "direction").unwrap_or(""))
+                    .or_default() += v;
+            }
+            "substrate_sub_libp2p_connections_closed_total" => {
+                *closed
+                    .entry(label(labels, "direction").unwrap_or(""))
+                    .or_default() += v;
+            }
+            "substrate_sub_libp2p_pending_connections" => add(&mut m.pending_dials, v),
+            "substrate_sub_libp2p_pending_connections_errors_total" => add(&mut m.failed_dials, v),
+            "substrate_sub_libp2p_incoming_connections_handshake_errors_total" => {
+                add(&mut m.handshake_errors, v)
+            }
+            "substrate_sub_libp2p_notifications_streams_closed_total" => {
+                add(&mut m.notification_streams_closed, v)
+            }
+            _ => {}
+        }
+    }
+    let open = |dir: &str| {
+        opened
+            .get(dir)
+            .map(|o| (o - closed.get(dir).copied().unwrap_or(0.0)).max(0.0) as u64)
+    };
+    m.inbound_peers = open("in");
+    m.outbound_peers = open("out");
+    m.scraped_at = Some(crate::timeutil::now_ms());
+    m
+}
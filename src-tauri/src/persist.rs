@@ -0,0 +1,288 @@
+use anyhow::Result;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    ffi::OsString,
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+/// Turns a file's data from one version into the next.
+pub type Migration = fn(serde_json::Value) -> Result<serde_json::Value>;
+
+/// A persisted type: stored as `{ "version": VERSION, "data": ... }`. Files without that
+/// envelope (written before it existed) are version 0.
+pub trait Versioned: Serialize + DeserializeOwned {
+    const VERSION: u32;
+    /// `MIGRATIONS[n]` migrates version `n` data to `n + 1`; one per version.
+    const MIGRATIONS: &'static [Migration];
+}
+
+/// A migration for a version whose data is the same as the next one's (e.g. 0 -> 1 when
+/// only the envelope was added).
+pub fn unchanged(v: serde_json::Value) -> Result<serde_json::Value> {
+    Ok(v)
+}
+
+#[derive(Serialize)]
+struct Envelope<'a, T: ?Sized> {
+    version: u32,
+    data: &'a T,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawEnvelope {
+    version: u32,
+    data: serde_json::Value,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ReadError {
+    #[error("{0}")]
+    Io(#[from] std::io::Error),
+    #[error("unreadable: {0}")]
+    Invalid(String),
+    /// Written by a newer app; left alone so that version can still read it.
+    #[error("written by a newer version of the app (format {found}, this one knows {known})")]
+    Newer { found: u32, known: u32 },
+}
+
+/// `bytes` as `T`'s data, migrated to `T::VERSION`, with the version it was stored as.
+pub fn decode_value<T: Versioned>(bytes: &[u8]) -> Result<(serde_json::Value, u32), ReadError> {
+    debug_assert_eq!(T::MIGRATIONS.len(), T::VERSION as usize);
+    let v: serde_json::Value =
+        serde_json::from_slice(bytes).map_err(|e| ReadError::Invalid(e.to_string()))?;
+    let (found, mut data) = match serde_json::from_value::<RawEnvelope>(v.clone()) {
+        Ok(env) => (env.version, env.data),
+        Err(_) => (0, v),
+    };
+    if found > T::VERSION {
+        return Err(ReadError::Newer {
+            found,
+            known: T::VERSION,
+        });
+    }
+    for (n, migrate) in T::MIGRATIONS.iter().enumerate().skip(found as usize) {
+        data = migrate(data)
+            .map_err(|e| ReadError::Invalid(format!("migrating from format {n}: {e}")))?;
+    }
+    Ok((data, found))
+}
+
+/// `path` as `T`; None when it doesn't exist.
+pub fn read_versioned<T: Versioned>(path: &Path) -> Result<Option<T>, ReadError> {
+    let bytes = match fs::read(path) {
+        Ok(b) => b,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+    let (data, _) = decode_value::<T>(&bytes)?;
+    serde_json::from_value(data)
+        .map(Some)
+        .map_err(|e| ReadError::Invalid(e.to_string()))
+}
+
+/// `path` as `T`, for stores that can start over: None when missing. An unreadable file
+/// is quarantined (`quarantine`) and its `.bak` used instead, if that reads; one from a
+/// newer app version is left in place. Never fails the caller.
+pub fn load_versioned<T: Versioned>(path: &Path) -> Option<T> {
+    match read_versioned(path) {
+        Ok(v) => v,
+        Err(e @ ReadError::Newer { .. }) => {
+            eprintln!("persist: {}: {e}", path.display());
+            None
+        }
+        Err(e) => {
+            match quarantine(path) {
+                Ok(to) => eprintln!("persist: {} {e}; moved to {}", path.display(), to.display()),
+                Err(qe) => eprintln!("persist: {} {e}; not moved: {qe}", path.display()),
+            }
+            read_versioned(&backup_path(path)).ok().flatten()
+        }
+    }
+}
+
+/// Store `data` in `path` atomically (`write_atomic`), creating its directory. The
+/// previous file, if it was valid JSON, is kept as `<file>.bak`.
+pub fn store_versioned<T: Versioned>(path: &Path, data: &T) -> Result<()> {
+    store(path, T::VERSION, data)
+}
+
+/// `store_versioned` for `T`'s data as JSON (from `decode_value`), so edits keep fields
+/// `T` doesn't know.
+pub fn store_value<T: Versioned>(path: &Path, data: &serde_json::Value) -> Result<()> {
+    store(path, T::VERSION, data)
+}
+
+fn store<D: Serialize + ?Sized>(path: &Path, version: u32, data: &D) -> Result<()> {
+    let bytes = serde_json::to_vec_pretty(&Envelope { version, data })?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    if let Ok(previous) = fs::read(path) {
+        if serde_json::from_slice::<serde_json::Value>(&previous).is_ok() {
+            write_atomic(&backup_path(path), &previous)?;
+        }
+    }
+    write_atomic(path, &bytes)
+}
+
+/// A file under `{app_data_dir}` read once through `load_versioned` (defaults when
+/// missing or unusable), then served from memory; `store` writes it through
+/// `store_versioned` and updates the copy.
+pub struct Cached<T> {
+    name: &'static str,
+    value: std::sync::Mutex<Option<T>>,
+}
+
+impl<T: Versioned + Clone + Default> Cached<T> {
+    pub const fn new(name: &'static str) -> Self {
+        Cached {
+            name,
+            value: std::sync::Mutex::new(None),
+        }
+    }
+
+    pub fn load(&self, app: &tauri::AppHandle) -> T {
+        self.load_in(&crate::paths::app_data_dir(app))
+    }
+
+    pub fn store(&self, app: &tauri::AppHandle, data: &T) -> Result<()> {
+        self.store_in(&crate::paths::app_data_dir(app), data)
+    }
+
+    fn load_in(&self, dir: &Path) -> T {
+        let mut value = self.value.lock().unwrap_or_else(|e| e.into_inner());
+        value
+            .get_or_insert_with(|| load_versioned(&dir.join(self.name)).unwrap_or_default())
+            .clone()
+    }
+
+    fn store_in(&self, dir: &Path, data: &T) -> Result<()> {
+        let mut value = self.value.lock().unwrap_or_else(|e| e.into_inner());
+        store_versioned(&dir.join(self.name), data)?;
+        *value = Some(data.clone());
+        Ok(())
+    }
+}
+
+/// `<file>.bak`.
+pub fn backup_path(path: &Path) -> PathBuf {
+    let mut name: OsString = path.as_os_str().to_owned();
+    name.push(".bak");
+    PathBuf::from(name)
+}
+
+/// Move an unusable file out of the way: `<file>.corrupt-<unix ts>`.
+pub fn quarantine(path: &Path) -> Result<PathBuf> {
+    let ts = crate::timeutil::now_secs();
+    let mut name = path.as_os_str().to_owned();
+    name.push(format!(".corrupt-{ts}"));
+    let dest = PathBuf::from(name);
+    fs::rename(path, &dest)?;
+    Ok(dest)
+}
+
+/// Write via a temp file + fsync + rename so a crash or full disk never leaves a partial file.
+pub(crate) fn write_atomic(path: &Path, bytes: &[u8]) -> Result<()> {
+    let mut tmp_name = path.as_os_str().to_owned();
+    tmp_name.push(".tmp");
+    let tmp = PathBuf::from(tmp_name);
+    let res = (|| -> std::io::Result<()> {
+        let mut f = fs::File::create(&tmp)?;
+        f.write_all(bytes)?;
+        f.sync_all()?;
+        fs::rename(&tmp, path)?;
+        // persist the rename itself (best effort; not supported on Windows)
+        #[cfg(unix)]
+        if let Some(dir) = path.parent() {
+            if let Ok(d) = fs::File::open(dir) {
+                let _ = d.sync_all();
+            }
+        }
+        Ok(())
+    })();
+    if res.is_err() {
+        let _ = fs::remove_file(&tmp);
+    }
+    Ok(res?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+    #[serde(default)]
+    struct Knobs {
+        enabled: bool,
+        limit: u32,
+    }
+
+    impl Versioned for Knobs {
+        const VERSION: u32 = 1;
+        const MIGRATIONS: &'static [Migration] = &[unchanged];
+    }
+
+    #[test]
+    fn cached_reads_legacy_files_and_stores_atomically() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("knobs.json"),
+            r#"{"enabled":true,"limit":3}"#,
+        )
+        .unwrap();
+        let file = Cached::<Knobs>::new("knobs.json");
+        let legacy = Knobs {
+            enabled: true,
+            limit: 3,
+        };
+        assert_eq!(file.load_in(dir.path()), legacy);
+
+        let next = Knobs {
+            enabled: false,
+            limit: 9,
+        };
+        file.store_in(dir.path(), &next).unwrap();
+        assert_eq!(file.load_in(dir.path()), next);
+        let stored: Option<Knobs> = read_versioned(&dir.path().join("knobs.json")).unwrap();
+        assert_eq!(stored, Some(next));
+        let backup: Option<Knobs> =
+            read_versioned(&backup_path(&dir.path().join("knobs.json"))).unwrap();
+        assert_eq!(backup, Some(legacy));
+        assert!(!dir.path().join("knobs.json.tmp").exists());
+    }
+
+    // A torn file is set aside and its backup used, not silently replaced by defaults.
+    #[test]
+    fn cached_recovers_a_torn_file_from_its_backup() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("knobs.json");
+        let saved = Knobs {
+            enabled: true,
+            limit: 7,
+        };
+        store_versioned(&path, &saved).unwrap();
+        store_versioned(&path, &saved).unwrap();
+        fs::write(&path, br#"{"version":1,"data":{"enab"#).unwrap();
+        assert_eq!(
+            Cached::<Knobs>::new("knobs.json").load_in(dir.path()),
+            saved
+        );
+    }
+
+    #[test]
+    fn cached_keeps_the_old_value_when_the_write_fails() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = Cached::<Knobs>::new("knobs.json");
+        assert_eq!(file.load_in(dir.path()), Knobs::default());
+        fs::create_dir(dir.path().join("knobs.json")).unwrap();
+        let next = Knobs {
+            enabled: true,
+            limit: 1,
+        };
+        assert!(file.store_in(dir.path(), &next).is_err());
+        assert_eq!(file.load_in(dir.path()), Knobs::default());
+    }
+}
@@ -0,0 +1,268 @@
+use lazy_static::lazy_static;
+use serde::Serialize;
+use tauri::AppHandle;
+use tokio::sync::Mutex;
+
+use crate::events;
+
+/// Explicit miner lifecycle phase, emitted as `miner:phase` on every change.
+///
+/// Allowed transitions (anything else is ignored and logged):
+/// - `Idle` → `Installing` | `StartingNode` | `Repairing`
+/// - `Installing` → `Idle` | `StartingNode`
+/// - `StartingNode` → `WaitingForRpc`
+/// - `WaitingForRpc` → `Syncing` | `Mining` | `Paused` | `Restarting` | `Repairing`
+/// - `Syncing` ↔ `Mining`, and both → `Paused` | `Restarting` | `Repairing`
+/// - `Paused` → `Syncing` | `Mining` | `StartingNode` | `Restarting` | `Repairing`
+/// - `Restarting` → `StartingNode` | `Repairing`
+/// - `Repairing` → `StartingNode`
+/// - `Stopping` → `Idle` | `StartingNode`
+/// - `Errored` → `Idle` | `Installing` | `StartingNode` | `Repairing`
+/// - any phase → `Stopping` | `Errored`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(
+    tag = "phase",
+    rename_all = "snake_case",
+    rename_all_fields = "camelCase"
+)]
+pub enum MinerPhase {
+    Idle,
+    Installing,
+    StartingNode,
+    WaitingForRpc,
+    Syncing,
+    Mining,
+    /// Node up and syncing, mining paused (`pause_mining`).
+    Paused,
+    Restarting {
+        reason: String,
+    },
+    Repairing,
+    Stopping,
+    Errored {
+        kind: String,
+    },
+}
+
+impl MinerPhase {
+    /// Whether moving from `self` to `next` is an allowed transition.
+    pub fn can_transition_to(&self, next: &MinerPhase) -> bool {
+        use MinerPhase::*;
+        if matches!(next, Stopping | Errored { .. }) {
+            return true;
+        }
+        match self {
+            Idle => matches!(next, Installing | StartingNode | Repairing),
+            Installing => matches!(next, Idle | StartingNode),
+            StartingNode => matches!(next, WaitingForRpc),
+            WaitingForRpc => matches!(
+                next,
+                Syncing | Mining | Paused | Restarting { .. } | Repairing
+            ),
+            Syncing => matches!(next, Mining | Paused | Restarting { .. } | Repairing),
+            Mining => matches!(next, Syncing | Paused | Restarting { .. } | Repairing),
+            Paused => matches!(
+                next,
+                Syncing | Mining | StartingNode | Restarting { .. } | Repairing
+            ),
+            Restarting { .. } => matches!(next, StartingNode | Repairing),
+            // Idle: repaired without a config to restart with
+            Repairing => matches!(next, StartingNode | Idle),
+            Stopping => matches!(next, Idle | StartingNode),
+            Errored { .. } => matches!(next, Idle | Installing | StartingNode | Repairing),
+        }
+    }
+
+    /// True while the node process is expected to be up (used by RPC-driven transitions).
+    pub fn is_node_up(&self) -> bool {
+        matches!(
+            self,
+            MinerPhase::WaitingForRpc
+                | MinerPhase::Syncing
+                | MinerPhase::Mining
+                | MinerPhase::Paused
+        )
+    }
+}
+
+lazy_static! {
+    static ref PHASE: Mutex<MinerPhase> = Mutex::new(MinerPhase::Idle);
+}
+
+/// Current phase snapshot.
+pub async fn current() -> MinerPhase {
+    PHASE.lock().await.clone()
+}
+
+/// Move to `next` if the transition is allowed; emits `miner:phase` on change.
+/// Returns true when the phase actually changed.
+pub async fn set_phase(app: &AppHandle, next: MinerPhase) -> bool {
+    transition(app, next, |_| true).await
+}
+
+/// Like `set_phase`, but only applies when the node is currently up.
+/// Used by the status task so RPC replies arriving after a stop can't revive the phase.
+pub async fn set_phase_if_node_up(app: &AppHandle, next: MinerPhase) -> bool {
+    transition(app, next, MinerPhase::is_node_up).await
+}
+
+/// Like `set_phase`, but only while the phase is still `from`, so a late trigger can't
+/// undo a phase that has already moved on.
+pub async fn set_phase_from(app: &AppHandle, from: &MinerPhase, next: MinerPhase) -> bool {
+    transition(app, next, |cur| cur == from).await
+}
+
+async fn transition(
+    app: &AppHandle,
+    next: MinerPhase,
+    applies: impl Fn(&MinerPhase) -> bool,
+) -> bool {
+    let mut guard = PHASE.lock().await;
+    if *guard == next || !applies(&guard) {
+        return false;
+    }
+    if !guard.can_transition_to(&next) {
+        events::emit_log(
+            app,
+            "ui",
+            format!("Ignoring phase transition {:?} -> {:?}", *guard, next),
+        );
+        return false;
+    }
+    *guard = next.clone();
+    drop(guard);
+    events::emit(app, events::MINER_PHASE, &next);
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MinerPhase::{self, *};
+
+    fn restarting(reason: &str) -> MinerPhase {
+        Restarting {
+            reason: reason.to_string(),
+        }
+    }
+
+    fn errored(kind: &str) -> MinerPhase {
+        Errored {
+            kind: kind.to_string(),
+        }
+    }
+
+    // Follow `steps` from `Idle`, failing at the first one that isn't allowed.
+    fn walk(steps: &[MinerPhase]) {
+        let mut cur = Idle;
+        for next in steps {
+            assert!(
+                cur.can_transition_to(next),
+                "{cur:?} -> {next:?} should be allowed"
+            );
+            cur = next.clone();
+        }
+    }
+
+    #[test]
+    fn start_sync_mine_stop() {
+        walk(&[
+            StartingNode,
+            WaitingForRpc,
+            Syncing,
+            Mining,
+            Syncing,
+            Mining,
+            Stopping,
+            Idle,
+        ]);
+    }
+
+    #[test]
+    fn repair_while_safe_mode_is_on() {
+        walk(&[
+            StartingNode,
+            WaitingForRpc,
+            Syncing,
+            // a heavy range turns safe mode on: the node restarts with it
+            restarting("safe_mode_enable"),
+            StartingNode,
+            WaitingForRpc,
+            Syncing,
+            // the user repairs while still in safe mode
+            Repairing,
+            // and the node comes back with the same (safe mode) config
+            StartingNode,
+            WaitingForRpc,
+            Syncing,
+            Mining,
+        ]);
+        // a repair asked for in the middle of the safe-mode restart
+        walk(&[
+            StartingNode,
+            WaitingForRpc,
+            Mining,
+            restarting("safe_mode_enable"),
+            Repairing,
+            StartingNode,
+        ]);
+        // repaired with nothing to restart
+        walk(&[StartingNode, WaitingForRpc, Syncing, Repairing, Idle]);
+    }
+
+    #[test]
+    fn errors_and_stops_are_always_allowed() {
+        let all = [
+            Idle,
+            Installing,
+            StartingNode,
+            WaitingForRpc,
+            Syncing,
+            Mining,
+            Paused,
+            restarting("manual"),
+            Repairing,
+            Stopping,
+            errored("spawn"),
+        ];
+        for from in &all {
+            assert!(from.can_transition_to(&Stopping), "{from:?} -> Stopping");
+            assert!(from.can_transition_to(&errored("x")), "{from:?} -> Errored");
+        }
+    }
+
+    #[test]
+    fn rejected_transitions() {
+        for (from, to) in [
+            (Idle, Mining),
+            (Idle, Syncing),
+            (Installing, Mining),
+            (StartingNode, Mining),
+            (StartingNode, Repairing),
+            (WaitingForRpc, StartingNode),
+            (Syncing, StartingNode),
+            (Mining, Idle),
+            (restarting("safe_mode_enable"), Syncing),
+            (restarting("safe_mode_enable"), Mining),
+            (Repairing, Syncing),
+            (Repairing, Mining),
+            (Repairing, WaitingForRpc),
+            (Stopping, Mining),
+            (errored("spawn"), Mining),
+        ] {
+            assert!(
+                !from.can_transition_to(&to),
+                "{from:?} -> {to:?} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn node_up_phases() {
+        for p in [WaitingForRpc, Syncing, Mining, Paused] {
+            assert!(p.is_node_up(), "{p:?}");
+        }
+        for p in [Idle, StartingNode, Repairing, Stopping, restarting("x")] {
+            assert!(!p.is_node_up(), "{p:?}");
+        }
+    }
+}
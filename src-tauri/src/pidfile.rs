@@ -0,0 +1,152 @@
+use anyhow::{anyhow, Result};
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use std::{fs, path::PathBuf};
+use tauri::AppHandle;
+
+use crate::miner::MinerConfig;
+
+/// Written for every spawned node as `{app_data_dir}/pids/<chain>.json` and removed on a
+/// clean stop, so a GUI that crashed can find (and take over) a node still running.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PidRecord {
+    pub pid: u32,
+    /// OS process start time as reported at spawn; guards against PID reuse.
+    pub start_time: String,
+    pub chain: String,
+    /// Local RPC endpoint the node was started with.
+    pub rpc_url: String,
+    /// Unix seconds.
+    pub started_at: i64,
+    /// What it was started with, for stop/restart after a take-over.
+    pub cfg: MinerConfig,
+}
+
+lazy_static! {
+    // {app_data_dir}/pids, set by `init`.
+    static ref PID_DIR: std::sync::Mutex<Option<PathBuf>> = std::sync::Mutex::new(None);
+    // Nodes from pidfiles that are still alive, found by `init`; taken by adopt.
+    static ref ORPHANS: std::sync::Mutex<Vec<PidRecord>> = std::sync::Mutex::new(Vec::new());
+}
+
+fn pid_path(chain: &str) -> Option<PathBuf> {
+    let dir = PID_DIR.lock().ok()?.clone()?;
+    Some(dir.join(format!("{chain}.json")))
+}
+
+/// (process name, start time) of a running process; None if there's no such process.
+#[cfg(target_os = "linux")]
+pub fn process_identity(pid: u32) -> Option<(String, String)> {
+    let name = fs::read_to_string(format!("/proc/{pid}/comm")).ok()?;
+    let stat = fs::read_to_string(format!("/proc/{pid}/stat")).ok()?;
+    // fields after "(comm)": state is field 3, starttime field 22
+    let after = &stat[stat.rfind(')')? + 1..];
+    let start = after.split_whitespace().nth(19)?;
+    Some((name.trim().to_string(), start.to_string()))
+}
+
+/// (process name, start time) of a running process; None if there's no such process.
+#[cfg(all(target_family = "unix", not(target_os = "linux")))]
+pub fn process_identity(pid: u32) -> Option<(String, String)> {
+    let out = std::process::Command::new("ps")
+        .args(["-o", "lstart=,comm=", "-p", &pid.to_string()])
+        .output()
+        .ok()?;
+    let line = String::from_utf8_lossy(&out.stdout).trim().to_string();
+    // "Mon Jan  6 10:00:00 2025 /path/to/quantus-node": lstart is five fields
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    if !out.status.success() || fields.len() < 6 {
+        return None;
+    }
+    Some((fields[5..].join(" "), fields[..5].join(" ")))
+}
+
+/// (process name, start time) of a running process; None if there's no such process.
+#[cfg(windows)]
+pub fn process_identity(pid: u32) -> Option<(String, String)> {
+    let script = format!(
+        "$p = Get-Process -Id {pid} -ErrorAction Stop; $p.ProcessName; $p.StartTime.ToFileTimeUtc()"
+    );
+    let out = std::process::Command::new("powershell")
+        .args(["-NoProfile", "-NonInteractive", "-Command", &script])
+        .output()
+        .ok()?;
+    if !out.status.success() {
+        return None;
+    }
+    let text = String::from_utf8_lossy(&out.stdout);
+    let mut lines = text.lines().map(str::trim).filter(|l| !l.is_empty());
+    Some((lines.next()?.to_string(), lines.next()?.to_string()))
+}
+
+/// Whether `rec`'s process is still the node we spawned: same start time and a
+/// quantus-node process name.
+pub fn is_alive(rec: &PidRecord) -> bool {
+    process_identity(rec.pid).is_some_and(|(name, start)| {
+        start == rec.start_time && name.to_ascii_lowercase().contains("quantus-node")
+    })
+}
+
+/// Record a freshly spawned node.
+pub fn write(chain: &str, pid: u32, rpc_url: &str, cfg: &MinerConfig) -> Result<()> {
+    let path = pid_path(chain).ok_or_else(|| anyhow!("pidfile directory not set"))?;
+    let (_, start_time) =
+        process_identity(pid).ok_or_else(|| anyhow!("process {pid} not found"))?;
+    let rec = PidRecord {
+        pid,
+        start_time,
+        chain: chain.to_string(),
+        rpc_url: rpc_url.to_string(),
+        started_at: crate::timeutil::now_secs(),
+        cfg: cfg.clone(),
+    };
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    crate::persist::write_atomic(&path, &serde_json::to_vec_pretty(&rec)?)
+}
+
+/// Forget `chain`'s node (it was stopped).
+pub fn remove(chain: &str) {
+    if let Some(path) = pid_path(chain) {
+        let _ = fs::remove_file(path);
+    }
+}
+
+/// Set the pidfile directory and collect nodes left running by a previous GUI instance
+/// (called once at startup). Pidfiles of processes that are gone are deleted.
+pub fn init(app: &AppHandle) {
+    let dir = crate::paths::app_data_dir(app).join("pids");
+    let mut found = Vec::new();
+    for entry in fs::read_dir(&dir).into_iter().flatten().flatten() {
+        let rec = fs::read(entry.path())
+            .ok()
+            .and_then(|b| serde_json::from_slice::<PidRecord>(&b).ok());
+        match rec {
+            Some(rec) if is_alive(&rec) => found.push(rec),
+            _ => {
+                let _ = fs::remove_file(entry.path());
+            }
+        }
+    }
+    if let Ok(mut d) = PID_DIR.lock() {
+        *d = Some(dir);
+    }
+    if let Ok(mut o) = ORPHANS.lock() {
+        *o = found;
+    }
+}
+
+/// Nodes found at startup that nobody manages yet (and that are still alive).
+pub fn orphans() -> Vec<PidRecord> {
+    let mut list = ORPHANS.lock().map(|o| o.clone()).unwrap_or_default();
+    list.retain(is_alive);
+    list
+}
+
+/// Remove `chain`'s orphan from the list (it was adopted or dismissed).
+pub fn take_orphan(chain: &str) -> Option<PidRecord> {
+    let mut o = ORPHANS.lock().ok()?;
+    let ix = o.iter().position(|r| r.chain == chain)?;
+    Some(o.remove(ix))
+}
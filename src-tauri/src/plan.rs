@@ -0,0 +1,65 @@
+use serde::Serialize;
+use std::path::Path;
+
+/// One filesystem change a destructive operation would make.
+#[derive(Debug, Clone, Serialize)]
+pub struct PlannedChange {
+    /// "delete" | "create" | "move" | "copy"
+    pub action: &'static str,
+    pub path: String,
+    /// Destination of a move or copy.
+    pub to: Option<String>,
+    pub files: u64,
+    pub bytes: u64,
+}
+
+/// What a destructive command would do (`dry_run: true`): every path it would create,
+/// delete, move or copy, with sizes as they are now. Nothing has been touched.
+#[derive(Debug, Clone, Serialize)]
+pub struct Manifest {
+    /// "repair" | "import_chain_data" | "set_install_dir"
+    pub operation: &'static str,
+    pub chain: Option<String>,
+    pub changes: Vec<PlannedChange>,
+    /// Whether the node would be stopped and started again.
+    pub restarts_node: bool,
+    /// Anything else worth showing in a confirmation dialog.
+    pub notes: Vec<String>,
+}
+
+/// Result of a command that supports `dry_run`: the manifest, or what the applied
+/// operation returns. Serialized as either one directly.
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum DryRun<T> {
+    Manifest(Manifest),
+    Applied(T),
+}
+
+/// (files, bytes) at `path`: a directory is walked, a file counts once, a missing path
+/// is (0, 0).
+pub async fn measure(path: &Path) -> (u64, u64) {
+    let path = path.to_path_buf();
+    tokio::task::spawn_blocking(move || match std::fs::metadata(&path) {
+        Ok(m) if m.is_dir() => crate::repair::measure(&path),
+        Ok(m) => (1, m.len()),
+        Err(_) => (0, 0),
+    })
+    .await
+    .unwrap_or_default()
+}
+
+/// A change at `path` (optionally to `to`), measured now; None when `path` doesn't exist.
+pub async fn change(action: &'static str, path: &Path, to: Option<&Path>) -> Option<PlannedChange> {
+    if !path.exists() {
+        return None;
+    }
+    let (files, bytes) = measure(path).await;
+    Some(PlannedChange {
+        action,
+        path: path.display().to_string(),
+        to: to.map(|p| p.display().to_string()),
+        files,
+        bytes,
+    })
+}
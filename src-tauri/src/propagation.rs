@@ -0,0 +1,131 @@
+use serde::Serialize;
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+
+use crate::{authorship::AuthoredBlock, events};
+
+/// An authored block the bootnode doesn't have this long after our import is likely
+/// orphaned.
+pub const WINDOW: Duration = Duration::from_secs(60);
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// `miner:block-propagated` payload: follows `FoundBlock` once the bootnode has the
+/// block, or once `WINDOW` passed without it.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct BlockPropagated {
+    chain: String,
+    height: u64,
+    hash: String,
+    propagated: bool,
+    /// From our import until the bootnode returned this hash at `height`.
+    propagation_ms: Option<u64>,
+    /// The bootnode's block at `height` when it isn't ours.
+    bootnode_hash: Option<String>,
+    /// Our node agrees it was reorged out (see `authorship::set_canonical`).
+    orphaned: bool,
+    ts: i64,
+}
+
+/// Poll the bootnode for `block` (imported by our node at `imported_at`) until it has it
+/// at the same height or `WINDOW` passes, then record the result in the authored history
+/// and session stats and send `miner:block-propagated`. Chains without a bootnode (custom
+/// specs) aren't measured.
+pub fn spawn(app: &AppHandle, chain: &str, block: AuthoredBlock, imported_at: Instant) {
+    let Some(ws) = crate::rpc::bootnode_ws_for_chain(chain) else {
+        return;
+    };
+    let (app, chain, url) = (app.clone(), chain.to_string(), crate::fork::http_url(ws));
+    tauri::async_runtime::spawn(async move {
+        let mut bootnode_hash = None;
+        let mut propagation_ms = None;
+        while imported_at.elapsed() < WINDOW {
+            match crate::fork::block_hash(&url, block.height).await {
+                Ok(Some(h)) if h == block.hash => {
+                    propagation_ms = Some(imported_at.elapsed().as_millis() as u64);
+                    break;
+                }
+                // the bootnode may still switch to our block
+                Ok(Some(h)) => bootnode_hash = Some(h),
+                Ok(None) => {}
+                Err(e) => eprintln!("propagation {chain} #{}: {e}", block.height),
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+        if propagation_ms.is_none() {
+            // cross-check with our own node; flags the block if it was reorged out
+            crate::miner::recheck_authored(&app, &chain, block.height).await;
+        }
+        // None: the node was restarted meanwhile and the session forgot the block
+        let Some(updated) =
+            crate::authorship::set_propagation(&app, &chain, &block.hash, propagation_ms).await
+        else {
+            return;
+        };
+        crate::stats::note_propagation(&chain, propagation_ms);
+        let line = match propagation_ms {
+            Some(ms) => format!(
+                "Block #{} reached the bootnode in {:.1} s",
+                block.height,
+                ms as f64 / 1000.0
+            ),
+            None if updated.orphaned => format!(
+                "Block #{} never reached the bootnode and is no longer on our best chain (orphaned)",
+                block.height
+            ),
+            None => format!(
+                "Block #{} not on the bootnode {} s after import; likely orphaned",
+                block.height,
+                WINDOW.as_secs()
+            ),
+        };
+        crate::session_log::ui(&app, &chain, line);
+        events::emit(
+            &app,
+            events::MINER_BLOCK_PROPAGATED,
+            &BlockPropagated {
+                chain,
+                height: block.height,
+                hash: block.hash,
+                propagated: propagation_ms.is_some(),
+                propagation_ms,
+                bootnode_hash: bootnode_hash.filter(|_| propagation_ms.is_none()),
+                orphaned: updated.orphaned,
+                ts: crate::timeutil::now_ms(),
+            },
+        );
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn payload_is_camel_case() {
+        let p = BlockPropagated {
+            chain: "resonance".into(),
+            height: 10,
+            hash: "0xaa".into(),
+            propagated: false,
+            propagation_ms: None,
+            bootnode_hash: Some("0xbb".into()),
+            orphaned: true,
+            ts: 1,
+        };
+        assert_eq!(
+            serde_json::to_value(&p).unwrap(),
+            json!({
+                "chain": "resonance",
+                "height": 10,
+                "hash": "0xaa",
+                "propagated": false,
+                "propagationMs": null,
+                "bootnodeHash": "0xbb",
+                "orphaned": true,
+                "ts": 1,
+            })
+        );
+    }
+}
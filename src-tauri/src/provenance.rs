@@ -0,0 +1,229 @@
+use anyhow::Result;
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+    collections::HashMap,
+    ffi::OsString,
+    fs,
+    io::Read,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+use tauri::AppHandle;
+
+use crate::events;
+
+/// Where an installed binary came from (`<binary>.provenance.json`, written by the
+/// installer after a successful install).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Provenance {
+    #[serde(alias = "source_url")]
+    pub source_url: String,
+    #[serde(alias = "release_tag")]
+    pub release_tag: String,
+    pub asset: String,
+    /// Of the installed executable, not the downloaded archive.
+    pub sha256: String,
+    /// Unix seconds.
+    #[serde(alias = "installed_at")]
+    pub installed_at: i64,
+    /// The GUI version that installed it.
+    #[serde(alias = "gui_version")]
+    pub gui_version: String,
+}
+
+impl crate::persist::Versioned for Provenance {
+    const VERSION: u32 = 1;
+    const MIGRATIONS: &'static [crate::persist::Migration] = &[crate::persist::unchanged];
+}
+
+/// A binary checked against its record (`miner:meta` and the node report).
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProvenanceCheck {
+    /// "verified" (matches the record) | "modified" (differs from it) | "unknown" (no
+    /// record: user-provided, or installed before records were kept)
+    pub status: &'static str,
+    pub sha256: String,
+    pub record: Option<Provenance>,
+}
+
+impl ProvenanceCheck {
+    /// One line for reports: the release it came from, or what is known instead.
+    pub fn summary(&self) -> String {
+        match (&self.record, self.status) {
+            (Some(r), "verified") => {
+                format!("{} from {} ({})", r.release_tag, r.source_url, r.asset)
+            }
+            (Some(r), _) => format!(
+                "modified since {} was installed: sha256 {}, recorded {}",
+                r.release_tag, self.sha256, r.sha256
+            ),
+            (None, _) => format!("unknown/user-provided, sha256 {}", self.sha256),
+        }
+    }
+}
+
+/// `miner:binary-modified` payload: the binary on disk isn't the one that was installed.
+/// A warning; the node is started anyway.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct BinaryModified {
+    chain: String,
+    path: String,
+    sha256: String,
+    expected_sha256: String,
+    release_tag: String,
+    ts: i64,
+}
+
+lazy_static! {
+    // path -> (size, mtime, sha256): an unchanged file isn't hashed again
+    static ref HASHES: std::sync::Mutex<HashMap<PathBuf, (u64, SystemTime, String)>> =
+        std::sync::Mutex::new(HashMap::new());
+}
+
+/// `<binary>.provenance.json`.
+pub fn record_path(bin: &Path) -> PathBuf {
+    let mut name: OsString = bin.as_os_str().to_owned();
+    name.push(".provenance.json");
+    PathBuf::from(name)
+}
+
+fn hash_file(path: &Path) -> Result<String> {
+    let meta = fs::metadata(path)?;
+    let stamp = (meta.len(), meta.modified()?);
+    if let Some((size, mtime, hash)) = HASHES.lock().ok().and_then(|h| h.get(path).cloned()) {
+        if (size, mtime) == stamp {
+            return Ok(hash);
+        }
+    }
+    let mut f = fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buf = vec![0u8; 1 << 16];
+    loop {
+        let n = f.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+    }
+    let hash = hex::encode(hasher.finalize());
+    if let Ok(mut h) = HASHES.lock() {
+        h.insert(path.to_path_buf(), (stamp.0, stamp.1, hash.clone()));
+    }
+    Ok(hash)
+}
+
+async fn hash(path: &Path) -> Result<String> {
+    let path = path.to_path_buf();
+    tokio::task::spawn_blocking(move || hash_file(&path)).await?
+}
+
+/// Write the record for a freshly installed `bin` (from `asset` of `release_tag`).
+pub async fn record(bin: &Path, source_url: &str, release_tag: &str, asset: &str) -> Result<()> {
+    let p = Provenance {
+        source_url: source_url.to_string(),
+        release_tag: release_tag.to_string(),
+        asset: asset.to_string(),
+        sha256: hash(bin).await?,
+        installed_at: crate::timeutil::now_secs(),
+        gui_version: env!("CARGO_PKG_VERSION").to_string(),
+    };
+    crate::persist::store_versioned(&record_path(bin), &p)
+}
+
+/// Hash `bin` and compare it with its record; None when it can't be read.
+pub async fn check(bin: &Path) -> Option<ProvenanceCheck> {
+    let sha256 = match hash(bin).await {
+        Ok(h) => h,
+        Err(e) => {
+            eprintln!("provenance {}: {e}", bin.display());
+            return None;
+        }
+    };
+    let record: Option<Provenance> = crate::persist::load_versioned(&record_path(bin));
+    let status = match &record {
+        Some(r) if r.sha256 == sha256 => "verified",
+        Some(_) => "modified",
+        None => "unknown",
+    };
+    Some(ProvenanceCheck {
+        status,
+        sha256,
+        record,
+    })
+}
+
+/// `check` at node start; a modified binary gets `miner:binary-modified` and a log line.
+pub async fn check_at_start(app: &AppHandle, chain: &str, bin: &Path) -> Option<ProvenanceCheck> {
+    let c = check(bin).await?;
+    if let (Some(r), "modified") = (&c.record, c.status) {
+        crate::session_log::ui(
+            app,
+            chain,
+            format!(
+                "{} differs from the {} release installed on {}; it was replaced or modified after install",
+                bin.display(),
+                r.release_tag,
+                crate::timeutil::rfc3339(
+                    time::OffsetDateTime::from_unix_timestamp(r.installed_at)
+                        .unwrap_or(time::OffsetDateTime::UNIX_EPOCH)
+                )
+            ),
+        );
+        events::emit(
+            app,
+            events::MINER_BINARY_MODIFIED,
+            &BinaryModified {
+                chain: chain.to_string(),
+                path: bin.display().to_string(),
+                sha256: c.sha256.clone(),
+                expected_sha256: r.sha256.clone(),
+                release_tag: r.release_tag.clone(),
+                ts: crate::timeutil::now_ms(),
+            },
+        );
+    }
+    Some(c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn payloads_are_camel_case() {
+        let m = BinaryModified {
+            chain: "resonance".into(),
+            path: "/bin/quantus-node".into(),
+            sha256: "aa".into(),
+            expected_sha256: "bb".into(),
+            release_tag: "v1.0.0".into(),
+            ts: 1,
+        };
+        assert_eq!(
+            serde_json::to_value(&m).unwrap(),
+            json!({
+                "chain": "resonance",
+                "path": "/bin/quantus-node",
+                "sha256": "aa",
+                "expectedSha256": "bb",
+                "releaseTag": "v1.0.0",
+                "ts": 1,
+            })
+        );
+        let check = ProvenanceCheck {
+            status: "unknown",
+            sha256: "aa".into(),
+            record: None,
+        };
+        assert_eq!(
+            serde_json::to_value(&check).unwrap(),
+            json!({ "status": "unknown", "sha256": "aa", "record": null })
+        );
+    }
+}
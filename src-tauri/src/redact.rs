@@ -0,0 +1,228 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::borrow::Cow;
+
+pub const REDACTED: &str = "[REDACTED]";
+
+// 24 short lowercase words in a row: a BIP-39 style secret phrase. The whole run goes,
+// so a word just before the phrase doesn't leave its last word showing.
+static RE_MNEMONIC: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\b(?:[a-z]{3,8}[ \t]+){23,}[a-z]{3,8}\b").unwrap());
+// 64-byte hex (an expanded secret key); nothing public we log is that long
+static RE_HEX64: Lazy<Regex> = Lazy::new(|| Regex::new(r"\b0x[0-9a-fA-F]{128}\b").unwrap());
+// `secret...`/`seed`/`private key` fields with a quoted value (JSON, or a quoted phrase)
+static RE_QUOTED: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?i)("?\b(?:secret\w*|seed|private[ _]?key)"?\s*[:=]\s*)"[^"]*""#).unwrap()
+});
+// the same unquoted (`key: value`, `key=value`) and secret-looking CLI flags; group 1 is
+// kept. A 32-byte seed is only redacted this way ("Seed: 0x…", `--seed 0x…`): bare, it
+// can't be told from a block hash.
+static RE_FIELD: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?i)(\b(?:secret\w*|seed|private[ _]?key)\s*[:=]\s*|--[\w-]*(?:key|secret|seed|password|phrase|token)[\w-]*(?:=|[ \t]+))[^\s,}-][^\s,}]*",
+    )
+    .unwrap()
+});
+
+// `out` with `re` replaced, still borrowed if nothing matched
+fn replace<'a>(out: Cow<'a, str>, re: &Regex, rep: &str) -> Cow<'a, str> {
+    let replaced = match re.replace_all(&out, rep) {
+        Cow::Owned(r) => Some(r),
+        Cow::Borrowed(_) => None,
+    };
+    replaced.map_or(out, Cow::Owned)
+}
+
+/// `s` with secret phrases, 64-byte hex keys and the values of secret fields/flags
+/// replaced by `REDACTED`. Borrowed when there was nothing to redact.
+pub fn text(s: &str) -> Cow<'_, str> {
+    let out = replace(
+        Cow::Borrowed(s),
+        &RE_QUOTED,
+        &format!("${{1}}\"{REDACTED}\""),
+    );
+    let out = replace(out, &RE_FIELD, &format!("${{1}}{REDACTED}"));
+    let out = replace(out, &RE_MNEMONIC, REDACTED);
+    replace(out, &RE_HEX64, REDACTED)
+}
+
+/// `text` for an owned line, reusing it when nothing needed redacting.
+pub fn string(s: String) -> String {
+    let redacted = match text(&s) {
+        Cow::Owned(r) => Some(r),
+        Cow::Borrowed(_) => None,
+    };
+    redacted.unwrap_or(s)
+}
+
+fn is_secret_key(key: &str) -> bool {
+    let k = key.to_ascii_lowercase();
+    k.starts_with("secret") || k == "seed" || k == "private_key" || k == "privatekey"
+}
+
+/// Redact a JSON value in place: fields named `secret*`/`seed`/`private_key` (any case) lose their
+/// value, every other string goes through `text`.
+pub fn value(v: &mut serde_json::Value) {
+    match v {
+        serde_json::Value::String(s) => {
+            if let Cow::Owned(r) = text(s) {
+                *s = r;
+            }
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(value),
+        serde_json::Value::Object(map) => {
+            for (k, v) in map.iter_mut() {
+                if is_secret_key(k) && !v.is_null() {
+                    *v = serde_json::Value::String(REDACTED.to_string());
+                } else {
+                    value(v);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Whether `s` contains something that looks like a secret phrase. Emitting one is a bug:
+/// `events::emit` asserts against it in debug builds.
+pub fn has_mnemonic(s: &str) -> bool {
+    RE_MNEMONIC.is_match(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const PHRASE: &str = "bottom drive obey lake curtain smoke basket hold race lonely fit walk \
+                          quantum sunset orbit cable mimic ranch velvet artist noble sponsor \
+                          timber equal";
+    const SEED: &str = "0x2b7e151628aed2a6abf7158809cf4f3c762e7160f38b4da56a784d9045190cfe";
+    const BLOCK_HASH: &str = "0x8e61f2c3b1a9d7e5c4b3a2918f7e6d5c4b3a29180f1e2d3c4b5a69788796a5b4";
+
+    fn expanded_key() -> String {
+        format!("0x{}", "a1b2c3d4".repeat(16))
+    }
+
+    #[test]
+    fn secret_phrase() {
+        let line = format!("Secret phrase: {PHRASE}");
+        assert_eq!(text(&line), format!("Secret phrase: {REDACTED}"));
+        // mid-line, unlabelled
+        let line = format!("2025-06-12 10:14:02 imported account from \"{PHRASE}\" ok");
+        assert!(!text(&line).contains("obey"));
+        assert!(has_mnemonic(PHRASE));
+        assert!(!has_mnemonic(&text(PHRASE)));
+        // shorter runs of words are ordinary log text
+        let line = "💤 Idle (8 peers), best: #18236 (0x473c…e2d0), finalized #18233 (0x4739…41bb)";
+        assert!(matches!(text(line), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn keygen_json() {
+        let payload = format!(
+            r#"{{"ss58Address":"qzpFUhjXpZnRCbNr9LW5sAkWmTus52Ffcf31MBpig82u1JArX","secretPhrase":"{PHRASE}","secretSeed":"{SEED}","seed":"{SEED}","secretKey":"{}"}}"#,
+            expanded_key()
+        );
+        let out = text(&payload);
+        assert!(!out.contains(SEED) && !out.contains("obey") && !out.contains("a1b2c3d4"));
+        assert!(out.contains("qzpFUhjXpZnRCbNr9LW5sAkWmTus52Ffcf31MBpig82u1JArX"));
+        let v: serde_json::Value = serde_json::from_str(&out).unwrap();
+        for k in ["secretPhrase", "secretSeed", "seed", "secretKey"] {
+            assert_eq!(v[k], REDACTED, "{k}");
+        }
+
+        let mut v = json!({
+            "address": "qzpFUhjXpZnRCbNr9LW5sAkWmTus52Ffcf31MBpig82u1JArX",
+            "secret_phrase": PHRASE,
+            "Seed": SEED,
+            "secretKey": expanded_key(),
+            "private_key": SEED,
+            "pub_key": BLOCK_HASH,
+            "backed_up": false,
+            "nested": [{"seed": SEED}, {"note": format!("phrase {PHRASE}")}],
+        });
+        value(&mut v);
+        for k in ["secret_phrase", "Seed", "secretKey", "private_key"] {
+            assert_eq!(v[k], REDACTED, "{k}");
+        }
+        assert_eq!(v["nested"][0]["seed"], REDACTED);
+        // the word before the phrase goes with it, rather than its last word staying
+        assert_eq!(v["nested"][1]["note"], REDACTED);
+        assert_eq!(v["pub_key"], BLOCK_HASH);
+        assert_eq!(v["backed_up"], false);
+    }
+
+    #[test]
+    fn labelled_32_byte_seeds() {
+        for line in [
+            format!("Seed: {SEED}"),
+            format!("Secret seed:       {SEED}"),
+            format!("seed={SEED}"),
+            format!("Private key: {SEED}"),
+            format!("{{\"privateKey\": \"{SEED}\"}}"),
+        ] {
+            assert!(!text(&line).contains(SEED), "{line}");
+        }
+    }
+
+    #[test]
+    fn key_flags() {
+        let cmd = format!(
+            "quantus-node --chain resonance --validator --node-key {SEED} \
+             --rewards-address qzpFUhjXpZnRCbNr9LW5sAkWmTus52Ffcf31MBpig82u1JArX \
+             --rpc-port 9944"
+        );
+        let out = text(&cmd);
+        assert!(out.contains(&format!("--node-key {REDACTED} ")), "{out}");
+        assert!(out.contains("--rewards-address qzpFUhjXpZnRCbNr9LW5sAkWmTus52Ffcf31MBpig82u1JArX"));
+        assert!(out.contains("--chain resonance --validator") && out.contains("--rpc-port 9944"));
+
+        for (flag, sep) in [
+            ("--node-key", "="),
+            ("--node-key-file", " "),
+            ("--keystore-key", "="),
+            ("--password", " "),
+            ("--suri-secret", " "),
+        ] {
+            let line = format!("args: {flag}{sep}deadbeef01 --name miner");
+            assert_eq!(
+                text(&line),
+                format!("args: {flag}{sep}{REDACTED} --name miner"),
+                "{flag}"
+            );
+        }
+        // a flag followed by another flag has no value to hide
+        assert_eq!(text("--node-key --validator"), "--node-key --validator");
+    }
+
+    #[test]
+    fn block_hashes_are_kept() {
+        for line in [
+            format!("✨ Imported #18241 ({BLOCK_HASH})"),
+            format!("🎁 Prepared block for proposing at 18246 [hash: {BLOCK_HASH}]"),
+            format!("Successfully mined and submitted a new block → {BLOCK_HASH}"),
+            format!(r#"{{"hash":"{BLOCK_HASH}","parentHash":"{BLOCK_HASH}"}}"#),
+            format!("🔨 Initializing Genesis block/state (header-hash: {BLOCK_HASH})"),
+        ] {
+            assert!(matches!(text(&line), Cow::Borrowed(_)), "{line}");
+        }
+        let mut v = json!({"block": {"hash": BLOCK_HASH, "number": 18241}});
+        value(&mut v);
+        assert_eq!(v["block"]["hash"], BLOCK_HASH);
+    }
+
+    #[test]
+    fn expanded_keys() {
+        let line = format!("derived {} for the session", expanded_key());
+        assert_eq!(text(&line), format!("derived {REDACTED} for the session"));
+    }
+
+    #[test]
+    fn string_reuses_clean_lines() {
+        let line = String::from("💤 Idle (8 peers)");
+        let ptr = line.as_ptr();
+        let out = string(line);
+        assert_eq!(out.as_ptr(), ptr);
+    }
+}
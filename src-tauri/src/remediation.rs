@@ -0,0 +1,274 @@
+use lazy_static::lazy_static;
+use serde::Serialize;
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+use tauri::AppHandle;
+
+use crate::events;
+
+/// The same remediation isn't sent again for a chain within this long.
+const RESEND_AFTER: Duration = Duration::from_secs(60);
+const NODE_RELEASES_URL: &str = "https://github.com/Quantus-Network/chain/releases/latest";
+
+/// A classified problem that has remediations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorKind {
+    DbCorrupt,
+    DbLocked,
+    /// Blocks fail to import after a runtime upgrade.
+    RuntimeVersion,
+    /// The node binary lacks flags the app needs (`node_caps::UnsupportedBinary`).
+    UnsupportedNode,
+    /// The chain directory holds another network's data (`chain_guard`).
+    ChainMismatch,
+    DuplicateIdentity,
+    ImportPressure,
+    /// The node's disk is full, or nearly (`log_space`).
+    LowDiskSpace,
+}
+
+impl ErrorKind {
+    /// From `parse::StartupFailure::kind`; None for failures without a remediation.
+    pub fn from_startup(kind: &str) -> Option<Self> {
+        match kind {
+            "db_corrupt" => Some(Self::DbCorrupt),
+            "db_locked" => Some(Self::DbLocked),
+            "disk_full" => Some(Self::LowDiskSpace),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    Repair,
+    Unlock,
+    EnableSafeMode,
+    UpdateNode,
+    FreeDiskSpace,
+    RegenerateNodeKey,
+}
+
+/// What must hold for an action to be offered as available.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Precondition {
+    NodeRunning,
+    SafeSyncSupported,
+    SafeSyncOff,
+}
+
+impl Precondition {
+    async fn holds(self, chain: &str) -> bool {
+        match self {
+            Self::NodeRunning => crate::miner::is_chain_alive(chain).await,
+            Self::SafeSyncSupported => {
+                !crate::node_caps::chain_lacks(chain, crate::node_caps::SAFE_SYNC)
+            }
+            Self::SafeSyncOff => {
+                let (reason, manual) = crate::miner::safe_mode(chain).await;
+                reason.is_none() && !manual
+            }
+        }
+    }
+
+    fn describe(self) -> &'static str {
+        match self {
+            Self::NodeRunning => "the node isn't running",
+            Self::SafeSyncSupported => "this quantus-node has no safe sync",
+            Self::SafeSyncOff => "safe sync is already on",
+        }
+    }
+}
+
+/// The remediations for `kind`, most fitting first. New remediations go here; the
+/// places that detect problems only name the kind.
+pub fn actions_for(kind: ErrorKind) -> &'static [Action] {
+    match kind {
+        ErrorKind::DbCorrupt => &[Action::Repair],
+        ErrorKind::DbLocked => &[Action::Unlock],
+        ErrorKind::RuntimeVersion | ErrorKind::UnsupportedNode => &[Action::UpdateNode],
+        ErrorKind::ChainMismatch => &[Action::Repair],
+        ErrorKind::DuplicateIdentity => &[Action::RegenerateNodeKey],
+        ErrorKind::ImportPressure => &[Action::EnableSafeMode],
+        ErrorKind::LowDiskSpace => &[Action::FreeDiskSpace],
+    }
+}
+
+impl Action {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Repair => "Repair (wipe the database and resync)",
+            Self::Unlock => "Unlock the database and restart",
+            Self::EnableSafeMode => "Turn on safe sync",
+            Self::UpdateNode => "Update quantus-node",
+            Self::FreeDiskSpace => "Open the database folder",
+            Self::RegenerateNodeKey => "Generate a new node key",
+        }
+    }
+
+    fn preconditions(self) -> &'static [Precondition] {
+        match self {
+            Self::EnableSafeMode => &[
+                Precondition::NodeRunning,
+                Precondition::SafeSyncSupported,
+                Precondition::SafeSyncOff,
+            ],
+            Self::RegenerateNodeKey => &[Precondition::NodeRunning],
+            _ => &[],
+        }
+    }
+
+    // (command, args) for the frontend to invoke; None for a link
+    fn command(self, chain: &str) -> Option<(&'static str, serde_json::Value)> {
+        let args = serde_json::json!({ "chain": chain });
+        match self {
+            Self::Repair => Some(("repair_miner", args)),
+            Self::Unlock => Some(("unlock_miner", args)),
+            Self::EnableSafeMode => Some(("enable_safe_mode", args)),
+            // the app doesn't update installed binaries; the release page has the new one
+            Self::UpdateNode => None,
+            Self::FreeDiskSpace => Some((
+                "open_path",
+                serde_json::json!({ "kind": "chain_db", "chain": chain }),
+            )),
+            Self::RegenerateNodeKey => Some(("regenerate_node_key", args)),
+        }
+    }
+
+    fn url(self) -> Option<&'static str> {
+        (self == Self::UpdateNode).then_some(NODE_RELEASES_URL)
+    }
+}
+
+/// One offered remediation: invoke `command` with `args`, or open `url`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemediationAction {
+    pub action: Action,
+    pub label: &'static str,
+    pub command: Option<&'static str>,
+    pub args: Option<serde_json::Value>,
+    pub url: Option<&'static str>,
+    /// False when a precondition doesn't hold (`blocked_by` says which).
+    pub available: bool,
+    pub blocked_by: Option<&'static str>,
+}
+
+/// `miner:remediation` payload.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Remediation {
+    chain: String,
+    error_kind: ErrorKind,
+    /// The line or message the problem was recognized from.
+    detail: String,
+    actions: Vec<RemediationAction>,
+    ts: i64,
+}
+
+lazy_static! {
+    static ref SENT: std::sync::Mutex<HashMap<(String, ErrorKind), Instant>> =
+        std::sync::Mutex::new(HashMap::new());
+}
+
+/// `kind`'s actions for `chain`, with their preconditions checked now.
+pub async fn actions(chain: &str, kind: ErrorKind) -> Vec<RemediationAction> {
+    let mut out = Vec::new();
+    for &action in actions_for(kind) {
+        let mut blocked_by = None;
+        for &p in action.preconditions() {
+            if !p.holds(chain).await {
+                blocked_by = Some(p.describe());
+                break;
+            }
+        }
+        let (command, args) = action.command(chain).unzip();
+        out.push(RemediationAction {
+            action,
+            label: action.label(),
+            command,
+            args,
+            url: action.url(),
+            available: blocked_by.is_none(),
+            blocked_by,
+        });
+    }
+    out
+}
+
+/// A problem of `kind` was recognized on `chain` (from `detail`): send `miner:remediation`
+/// with its actions, at most once per `RESEND_AFTER`. Callable from sync code.
+pub fn offer(app: &AppHandle, chain: &str, kind: ErrorKind, detail: &str) {
+    {
+        let Ok(mut sent) = SENT.lock() else {
+            return;
+        };
+        let key = (chain.to_string(), kind);
+        if sent.get(&key).is_some_and(|at| at.elapsed() < RESEND_AFTER) {
+            return;
+        }
+        sent.insert(key, Instant::now());
+    }
+    let (app, chain, detail) = (app.clone(), chain.to_string(), detail.trim().to_string());
+    tauri::async_runtime::spawn(async move {
+        let actions = actions(&chain, kind).await;
+        events::emit(
+            &app,
+            events::MINER_REMEDIATION,
+            &Remediation {
+                chain,
+                error_kind: kind,
+                detail,
+                actions,
+                ts: crate::timeutil::now_ms(),
+            },
+        );
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn payload_is_camel_case() {
+        let r = Remediation {
+            chain: "resonance".into(),
+            error_kind: ErrorKind::DbLocked,
+            detail: "lock held".into(),
+            actions: vec![RemediationAction {
+                action: Action::Unlock,
+                label: "Unlock",
+                command: Some("unlock_miner"),
+                args: Some(json!({ "chain": "resonance" })),
+                url: None,
+                available: false,
+                blocked_by: Some("node_running"),
+            }],
+            ts: 1,
+        };
+        assert_eq!(
+            serde_json::to_value(&r).unwrap(),
+            json!({
+                "chain": "resonance",
+                "errorKind": "db_locked",
+                "detail": "lock held",
+                "actions": [{
+                    "action": "unlock",
+                    "label": "Unlock",
+                    "command": "unlock_miner",
+                    "args": { "chain": "resonance" },
+                    "url": null,
+                    "available": false,
+                    "blockedBy": "node_running",
+                }],
+                "ts": 1,
+            })
+        );
+    }
+}
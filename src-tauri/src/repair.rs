@@ -0,0 +1,210 @@
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+use tauri::AppHandle;
+
+use crate::events;
+use crate::ops::{CancelToken, Cancelled};
+
+/// How often `miner:repair-progress` is sent while deleting.
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(500);
+const MARKER_FILE: &str = "repair-in-progress";
+
+/// Written next to the database before a wipe starts and removed once it is done; if
+/// the app dies in between, the next start finishes the wipe first.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Marker {
+    db_path: PathBuf,
+    /// Unix seconds.
+    started_at: i64,
+}
+
+/// `miner:repair-progress` payload.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RepairProgress {
+    pub chain: String,
+    /// "scanning" | "deleting" | "done" | "cancelled"
+    pub stage: &'static str,
+    pub total_files: u64,
+    pub total_bytes: u64,
+    pub removed_files: u64,
+    pub removed_bytes: u64,
+    /// Finishing a wipe interrupted by an app exit.
+    pub resumed: bool,
+}
+
+// {db_path}/../repair-in-progress, i.e. {base}/chains/{chain_id}/db/repair-in-progress
+fn marker_path(db_path: &Path) -> Result<PathBuf> {
+    let parent = db_path
+        .parent()
+        .ok_or_else(|| anyhow!("invalid database path {}", db_path.display()))?;
+    Ok(parent.join(MARKER_FILE))
+}
+
+/// Whether a wipe of `db_path` was started and never finished.
+pub fn is_pending(db_path: &Path) -> bool {
+    marker_path(db_path).is_ok_and(|m| m.exists())
+}
+
+/// Drop an interrupted wipe's marker once `db_path` has been replaced by other means
+/// (an import), so the next start doesn't delete the new database.
+pub fn clear_pending(db_path: &Path) -> Result<()> {
+    match fs::remove_file(marker_path(db_path)?) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+        _ => Ok(()),
+    }
+}
+
+// (files, bytes) under `dir`; unreadable entries are skipped.
+pub(crate) fn measure(dir: &Path) -> (u64, u64) {
+    let mut files = 0;
+    let mut bytes = 0;
+    let mut stack = vec![dir.to_path_buf()];
+    while let Some(d) = stack.pop() {
+        let Ok(entries) = fs::read_dir(&d) else {
+            continue;
+        };
+        for e in entries.flatten() {
+            match e.metadata() {
+                Ok(m) if m.is_dir() => stack.push(e.path()),
+                Ok(m) => {
+                    files += 1;
+                    bytes += m.len();
+                }
+                Err(_) => {}
+            }
+        }
+    }
+    (files, bytes)
+}
+
+// Remove every file under `dir` (counting as we go), then the emptied directories.
+// Stops before the next file once `cancel` is set.
+fn remove_counted(
+    dir: &Path,
+    files: &AtomicU64,
+    bytes: &AtomicU64,
+    cancel: &AtomicBool,
+) -> std::io::Result<()> {
+    for e in fs::read_dir(dir)? {
+        if cancel.load(Ordering::Relaxed) {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::Interrupted,
+                Cancelled,
+            ));
+        }
+        let e = e?;
+        let meta = e.metadata()?;
+        if meta.is_dir() {
+            remove_counted(&e.path(), files, bytes, cancel)?;
+        } else {
+            fs::remove_file(e.path())?;
+            files.fetch_add(1, Ordering::Relaxed);
+            bytes.fetch_add(meta.len(), Ordering::Relaxed);
+        }
+    }
+    fs::remove_dir(dir)
+}
+
+/// Delete `db_path` with `miner:repair-progress` events: mark it, measure it, then remove
+/// it file by file on a blocking thread while a sampler reports what's gone. The marker
+/// is removed only once the directory is.
+///
+/// With `cancel` set before deleting began nothing is touched and the marker goes away;
+/// set while deleting, the marker stays so the next start finishes the half-deleted
+/// database. Either way `Cancelled` is returned after a "cancelled" progress event.
+pub async fn wipe_db(
+    app: &AppHandle,
+    chain: &str,
+    db_path: &Path,
+    resumed: bool,
+    cancel: Option<&CancelToken>,
+) -> Result<()> {
+    let cancel = cancel.cloned().unwrap_or_default();
+    let marker = marker_path(db_path)?;
+    if !resumed {
+        if let Some(parent) = marker.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let m = Marker {
+            db_path: db_path.to_path_buf(),
+            started_at: crate::timeutil::now_secs(),
+        };
+        fs::write(&marker, serde_json::to_vec_pretty(&m)?)?;
+    }
+    let mut progress = RepairProgress {
+        chain: chain.to_string(),
+        stage: "scanning",
+        total_files: 0,
+        total_bytes: 0,
+        removed_files: 0,
+        removed_bytes: 0,
+        resumed,
+    };
+    events::emit(app, events::MINER_REPAIR_PROGRESS, &progress);
+
+    if db_path.exists() {
+        let dir = db_path.to_path_buf();
+        let (total_files, total_bytes) = tokio::task::spawn_blocking(move || measure(&dir)).await?;
+        if cancel.is_cancelled() {
+            // an interrupted wipe being finished has already lost data: keep its marker
+            if !resumed {
+                let _ = clear_pending(db_path);
+            }
+            progress.stage = "cancelled";
+            events::emit(app, events::MINER_REPAIR_PROGRESS, &progress);
+            return Err(Cancelled.into());
+        }
+        progress.stage = "deleting";
+        progress.total_files = total_files;
+        progress.total_bytes = total_bytes;
+        events::emit(app, events::MINER_REPAIR_PROGRESS, &progress);
+
+        let files = Arc::new(AtomicU64::new(0));
+        let bytes = Arc::new(AtomicU64::new(0));
+        let finished = Arc::new(AtomicBool::new(false));
+        let sampler = {
+            let (app, files, bytes, finished) =
+                (app.clone(), files.clone(), bytes.clone(), finished.clone());
+            let mut p = progress.clone();
+            tauri::async_runtime::spawn(async move {
+                while !finished.load(Ordering::Relaxed) {
+                    tokio::time::sleep(PROGRESS_INTERVAL).await;
+                    p.removed_files = files.load(Ordering::Relaxed);
+                    p.removed_bytes = bytes.load(Ordering::Relaxed);
+                    events::emit(&app, events::MINER_REPAIR_PROGRESS, &p);
+                }
+            })
+        };
+        let dir = db_path.to_path_buf();
+        let (f, b, c) = (files.clone(), bytes.clone(), cancel.flag());
+        let res = tokio::task::spawn_blocking(move || remove_counted(&dir, &f, &b, &c)).await;
+        finished.store(true, Ordering::Relaxed);
+        let _ = sampler.await;
+        progress.removed_files = files.load(Ordering::Relaxed);
+        progress.removed_bytes = bytes.load(Ordering::Relaxed);
+        if cancel.is_cancelled() && res.as_ref().is_ok_and(|r| r.is_err()) {
+            progress.stage = "cancelled";
+            events::emit(app, events::MINER_REPAIR_PROGRESS, &progress);
+            return Err(Cancelled.into());
+        }
+        res?.map_err(|e| anyhow!("failed to wipe database at {}: {e}", db_path.display()))?;
+    }
+
+    fs::remove_file(&marker).or_else(|e| match e.kind() {
+        std::io::ErrorKind::NotFound => Ok(()),
+        _ => Err(e),
+    })?;
+    progress.stage = "done";
+    events::emit(app, events::MINER_REPAIR_PROGRESS, &progress);
+    Ok(())
+}
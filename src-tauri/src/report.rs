@@ -0,0 +1,359 @@
+use serde::Serialize;
+use std::{fmt::Write, path::Path};
+use tauri::AppHandle;
+
+/// Flags whose value must not leave the machine (matched as substrings of the flag).
+const SECRET_FLAGS: &[&str] = &["key", "secret", "seed", "password", "phrase", "token"];
+const REDACTED: &str = "<redacted>";
+/// Restart history entries in a report.
+const RESTARTS: usize = 3;
+
+/// Node or external miner binary.
+#[derive(Debug, Clone, Serialize)]
+pub struct BinaryInfo {
+    pub path: Option<String>,
+    pub version: Option<String>,
+    /// The installer's record checked against the file (`provenance.rs`).
+    pub provenance: Option<crate::provenance::ProvenanceCheck>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ExternalMinerInfo {
+    #[serde(flatten)]
+    pub binary: BinaryInfo,
+    pub running: bool,
+    pub cores: Option<usize>,
+    pub port: Option<u16>,
+}
+
+/// Everything support usually asks for about one chain's node, in one document
+/// (`get_node_report`; `to_markdown` for the "Copy report" button). Secrets in args
+/// and settings are redacted and the home directory is shown as `~`.
+#[derive(Debug, Clone, Serialize)]
+pub struct NodeReport {
+    /// RFC 3339.
+    pub generated_at: String,
+    pub gui_version: String,
+    pub os: String,
+    pub arch: String,
+    pub chain: String,
+    pub chain_spec_path: Option<String>,
+    pub running: bool,
+    pub adopted: bool,
+    pub paused: bool,
+    pub node: BinaryInfo,
+    pub external_miner: ExternalMinerInfo,
+    pub peer_id: Option<String>,
+    pub rpc_addr: Option<String>,
+    pub prometheus_addr: Option<String>,
+    pub data_dir: Option<String>,
+    pub paths: crate::paths::PathsReport,
+    pub db_files: Option<u64>,
+    pub db_bytes: Option<u64>,
+    /// The last `miner:status` (see `snapshot.rs`).
+    pub status: Option<serde_json::Value>,
+    /// "range" | "adaptive" while safe mode is on.
+    pub safe_mode: Option<&'static str>,
+    pub manual_safe_mode: bool,
+    pub restarts: Vec<crate::restart::RestartRecord>,
+    /// Node events dropped this session as copies mirrored to stdout and stderr.
+    pub duplicate_events_suppressed: u64,
+    pub extra_args: Vec<String>,
+    pub settings: crate::settings::SettingsView,
+}
+
+// Home directory prefix replaced by "~".
+fn tidy_path(p: &Path) -> String {
+    match crate::paths::home_dir().and_then(|h| p.strip_prefix(h).ok().map(Path::to_path_buf)) {
+        Some(rest) => Path::new("~").join(rest).to_string_lossy().to_string(),
+        None => p.to_string_lossy().to_string(),
+    }
+}
+
+fn is_secret_flag(flag: &str) -> bool {
+    let name = flag.trim_start_matches('-').to_ascii_lowercase();
+    flag.starts_with('-') && SECRET_FLAGS.iter().any(|s| name.contains(s))
+}
+
+/// Node args with the values of secret-looking flags (`--node-key`, ...) redacted, both
+/// `--flag value` and `--flag=value`.
+fn sanitize_args(args: &[String]) -> Vec<String> {
+    let mut out = Vec::with_capacity(args.len());
+    let mut hide_next = false;
+    for a in args {
+        if hide_next && !a.starts_with('-') {
+            out.push(REDACTED.to_string());
+            hide_next = false;
+            continue;
+        }
+        hide_next = false;
+        match a.split_once('=') {
+            Some((flag, _)) if is_secret_flag(flag) => out.push(format!("{flag}={REDACTED}")),
+            _ => {
+                hide_next = is_secret_flag(a);
+                out.push(a.clone());
+            }
+        }
+    }
+    out
+}
+
+// a string field of the `miner:meta` snapshot
+fn meta_str(meta: Option<&serde_json::Value>, key: &str) -> Option<String> {
+    meta?.get(key)?.as_str().map(str::to_string)
+}
+
+/// Build the report for `chain` from what the app knows now; nothing here fails, missing
+/// pieces are left empty.
+pub async fn build(app: &AppHandle, chain: &str) -> NodeReport {
+    let snap = crate::snapshot::chain(chain)
+        .or_else(|| crate::snapshot::load(app).and_then(|mut s| s.chains.remove(chain)));
+    let meta = snap.as_ref().and_then(|s| s.meta.as_ref());
+    let cfg = crate::miner::last_cfg(chain).await;
+    let running = crate::miner::is_chain_alive(chain).await;
+
+    let node_path = cfg
+        .as_ref()
+        .map_or(crate::installer::DEFAULT_BINARY, |c| c.binary_path.as_str())
+        .to_string();
+    let node_path = crate::installer::resolve_binary_path(&node_path).ok();
+    let node_version = match meta_str(meta, "version") {
+        Some(v) => Some(v),
+        None => match &node_path {
+            Some(p) => crate::installer::binary_version(Path::new(p)).await,
+            None => None,
+        },
+    };
+
+    let node_provenance = match &node_path {
+        Some(p) => crate::provenance::check(Path::new(p)).await,
+        None => None,
+    };
+
+    let ext_path = crate::installer::miner_binary_path()
+        .ok()
+        .filter(|p| p.exists());
+    let ext_version = match &ext_path {
+        Some(p) => crate::installer::binary_version(p).await,
+        None => None,
+    };
+    let ext_provenance = match &ext_path {
+        Some(p) => crate::provenance::check(p).await,
+        None => None,
+    };
+    let ext_running = crate::miner::external_miner(chain).await;
+
+    let chain_dir = crate::miner::chain_dir_for(chain).await.ok();
+    let (db_files, db_bytes) = match chain_dir.as_ref().map(|d| d.join("db").join("full")) {
+        Some(db) if db.exists() => {
+            match tokio::task::spawn_blocking(move || crate::repair::measure(&db)).await {
+                Ok((f, b)) => (Some(f), Some(b)),
+                Err(_) => (None, None),
+            }
+        }
+        _ => (None, None),
+    };
+
+    let rpc_addr = match meta_str(meta, "jsonrpcAddr") {
+        Some(a) => Some(a),
+        None if running => Some(crate::miner::local_ws_url(chain).await),
+        None => None,
+    };
+    let (safe_mode, manual_safe_mode) = crate::miner::safe_mode(chain).await;
+    let mut settings = crate::settings::get(app);
+    settings.extra_args = sanitize_args(
+        &settings
+            .extra_args
+            .split_whitespace()
+            .map(str::to_string)
+            .collect::<Vec<_>>(),
+    )
+    .join(" ");
+
+    NodeReport {
+        generated_at: crate::timeutil::rfc3339(time::OffsetDateTime::now_utc()),
+        gui_version: env!("CARGO_PKG_VERSION").to_string(),
+        os: std::env::consts::OS.to_string(),
+        arch: std::env::consts::ARCH.to_string(),
+        chain: chain.to_string(),
+        chain_spec_path: cfg
+            .as_ref()
+            .and_then(|c| c.chain_spec_path.as_deref())
+            .map(|p| tidy_path(Path::new(p))),
+        running,
+        adopted: crate::miner::adopted_chains()
+            .await
+            .iter()
+            .any(|c| c == chain),
+        paused: crate::miner::paused(chain).await.is_some(),
+        node: BinaryInfo {
+            path: node_path.map(|p| tidy_path(Path::new(&p))),
+            version: node_version,
+            provenance: node_provenance,
+        },
+        external_miner: ExternalMinerInfo {
+            binary: BinaryInfo {
+                path: ext_path.as_deref().map(tidy_path),
+                version: ext_version,
+                provenance: ext_provenance,
+            },
+            running: ext_running.is_some(),
+            cores: ext_running.map(|(c, _)| c),
+            port: ext_running.map(|(_, p)| p),
+        },
+        peer_id: meta_str(meta, "localIdentity"),
+        rpc_addr,
+        prometheus_addr: meta_str(meta, "prometheusAddr"),
+        data_dir: chain_dir.as_deref().map(tidy_path),
+        paths: crate::paths::report(app),
+        db_files,
+        db_bytes,
+        status: snap.and_then(|s| s.status).map(|mut v| {
+            crate::redact::value(&mut v);
+            v
+        }),
+        safe_mode,
+        manual_safe_mode,
+        restarts: crate::restart::history(app, Some(chain))
+            .into_iter()
+            .take(RESTARTS)
+            .collect(),
+        duplicate_events_suppressed: crate::miner::suppressed_duplicate_events(chain).await,
+        extra_args: sanitize_args(&cfg.map(|c| c.extra_args).unwrap_or_default()),
+        settings: settings.into(),
+    }
+}
+
+fn or_dash(v: Option<impl std::fmt::Display>) -> String {
+    v.map_or_else(|| "-".to_string(), |v| v.to_string())
+}
+
+impl NodeReport {
+    /// For pasting into a support request.
+    pub fn to_markdown(&self) -> String {
+        let mut md = String::new();
+        let _ = writeln!(md, "## Quantus miner report ({})", self.chain);
+        let _ = writeln!(md);
+        let _ = writeln!(md, "- Generated: {}", self.generated_at);
+        let _ = writeln!(
+            md,
+            "- GUI: {} on {}/{}",
+            self.gui_version, self.os, self.arch
+        );
+        let state = match (self.running, self.adopted, self.paused) {
+            (false, _, _) => "stopped",
+            (true, _, true) => "running (paused)",
+            (true, true, false) => "running (adopted)",
+            (true, false, false) => "running",
+        };
+        let _ = writeln!(md, "- Node: {state}");
+        let _ = writeln!(
+            md,
+            "- Node binary: {} ({})",
+            or_dash(self.node.path.as_ref()),
+            or_dash(self.node.version.as_ref())
+        );
+        if let Some(p) = &self.node.provenance {
+            let _ = writeln!(md, "  - Provenance: {}", p.summary());
+        }
+        let ext = &self.external_miner;
+        let _ = writeln!(
+            md,
+            "- External miner: {} ({}){}",
+            or_dash(ext.binary.path.as_ref()),
+            or_dash(ext.binary.version.as_ref()),
+            match (ext.cores, ext.port) {
+                (Some(c), Some(p)) => format!(", running with {c} cores on port {p}"),
+                _ => ", not running".to_string(),
+            }
+        );
+        if let Some(p) = &ext.binary.provenance {
+            let _ = writeln!(md, "  - Provenance: {}", p.summary());
+        }
+        if let Some(spec) = &self.chain_spec_path {
+            let _ = writeln!(md, "- Chain spec: {spec}");
+        }
+        let _ = writeln!(md, "- Peer id: {}", or_dash(self.peer_id.as_ref()));
+        let _ = writeln!(md, "- RPC: {}", or_dash(self.rpc_addr.as_ref()));
+        let _ = writeln!(
+            md,
+            "- Prometheus: {}",
+            or_dash(self.prometheus_addr.as_ref())
+        );
+        let _ = writeln!(
+            md,
+            "- Data dir: {} (database: {} files, {})",
+            or_dash(self.data_dir.as_ref()),
+            or_dash(self.db_files),
+            self.db_bytes
+                .map_or("-".to_string(), |b| format!("{:.2} GB", b as f64 / 1e9))
+        );
+        if self.paths.portable {
+            let _ = writeln!(md, "- Portable: yes, under {}", self.paths.base);
+        }
+        for f in &self.paths.fallbacks {
+            let _ = writeln!(md, "- No {} available; using {}", f.dir, f.used);
+        }
+        let safe_mode = match (self.safe_mode, self.manual_safe_mode) {
+            (_, true) => "on (set in extra args)".to_string(),
+            (Some(reason), false) => format!("on ({reason})"),
+            (None, false) => "off".to_string(),
+        };
+        let _ = writeln!(md, "- Safe mode: {safe_mode}");
+        let extra = if self.extra_args.is_empty() {
+            "-".to_string()
+        } else {
+            format!("`{}`", self.extra_args.join(" "))
+        };
+        let _ = writeln!(md, "- Extra args: {extra}");
+        let _ = writeln!(
+            md,
+            "- Duplicate events suppressed: {}",
+            self.duplicate_events_suppressed
+        );
+        if let Some(status) = &self.status {
+            let _ = writeln!(md, "\n### Status\n");
+            for key in [
+                "peers",
+                "currentBlock",
+                "highestBlock",
+                "finalizedBlock",
+                "isSyncing",
+                "syncPercent",
+                "source",
+            ] {
+                if let Some(v) = status.get(key).filter(|v| !v.is_null()) {
+                    let _ = writeln!(md, "- {key}: {v}");
+                }
+            }
+        }
+        if !self.restarts.is_empty() {
+            let _ = writeln!(md, "\n### Recent restarts\n");
+            for r in &self.restarts {
+                let when =
+                    time::OffsetDateTime::from_unix_timestamp_nanos(r.ts as i128 * 1_000_000)
+                        .map(crate::timeutil::rfc3339)
+                        .unwrap_or_default();
+                let reason = serde_json::to_value(r.reason)
+                    .ok()
+                    .and_then(|v| v.as_str().map(str::to_string))
+                    .unwrap_or_default();
+                let details = if r.details.is_empty() {
+                    String::new()
+                } else {
+                    format!(" ({})", r.details)
+                };
+                let _ = writeln!(md, "- {when}: {reason}{details}");
+            }
+        }
+        let _ = writeln!(md, "\n### Settings\n");
+        let _ = writeln!(md, "```json");
+        let _ = writeln!(
+            md,
+            "{}",
+            serde_json::to_string_pretty(&self.settings).unwrap_or_default()
+        );
+        let _ = writeln!(md, "```");
+        crate::redact::string(md)
+    }
+}
@@ -0,0 +1,471 @@
+use anyhow::Result;
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, fs, path::PathBuf, sync::Arc};
+use tauri::AppHandle;
+use tokio::sync::{Mutex, OwnedMutexGuard};
+
+use crate::events;
+
+/// Restart records kept per chain in `{app_data_dir}/restart_history.json`.
+const HISTORY_LIMIT: usize = 200;
+
+/// Operations that stop and/or (re)start the node. All of them go through `begin`
+/// so only one start/stop sequence runs at a time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RestartOp {
+    Start,
+    Stop,
+    SafeMode(bool),
+    Repair,
+    /// Copying another machine's database in (`import_chain_data`).
+    Import,
+    Unlock,
+    SafeRpc,
+    RegenerateNodeKey,
+    /// `pause_mining` (true) / `resume_mining` (false).
+    Pause(bool),
+    /// Node restart to get a crash-looping external miner a new block template.
+    ExternalCrashLoop,
+    /// Node restart to get off a dead fork (`fork.rs`).
+    ForkDetected,
+    /// `verify_chain_data`; keeps the node from starting while its database is checked.
+    Verify,
+    /// `compact_database` through the node subcommand (node stopped).
+    Compact,
+    /// `set_rewards_address`: the same start with another `--rewards-address`.
+    RewardsAddress,
+}
+
+impl RestartOp {
+    // Repeating one of these while it is already in flight is a no-op.
+    // (A repeated Start is an error instead, see `RestartError::AlreadyStarting`.)
+    fn coalesces(&self) -> bool {
+        matches!(
+            self,
+            RestartOp::Stop
+                | RestartOp::SafeMode(_)
+                | RestartOp::Unlock
+                | RestartOp::SafeRpc
+                | RestartOp::RegenerateNodeKey
+                | RestartOp::Pause(_)
+                | RestartOp::ExternalCrashLoop
+                | RestartOp::ForkDetected
+        )
+    }
+}
+
+/// Why the node is being (re)started; every start goes through `announce` with one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RestartReason {
+    /// `start_miner` (including a retry after clearing quarantine).
+    Manual,
+    SafeModeEnable,
+    SafeModeDisable,
+    Repair,
+    Import,
+    Unlock,
+    /// `restart_with_safe_rpc`.
+    LocalOnlyRpc,
+    /// `regenerate_node_key`.
+    NewNodeKey,
+    Pause,
+    Resume,
+    /// `resume_last_session`.
+    ResumeSession,
+    /// The external miner kept crashing (`ext_crash.rs`).
+    ExternalCrashLoop,
+    /// The node was on a dead fork (`fork.rs`).
+    ForkDetected,
+    /// `set_rewards_address`.
+    RewardsAddressChange,
+}
+
+impl RestartReason {
+    fn label(self) -> &'static str {
+        match self {
+            RestartReason::Manual => "manual start",
+            RestartReason::SafeModeEnable => "safe mode enable",
+            RestartReason::SafeModeDisable => "safe mode disable",
+            RestartReason::Repair => "repair",
+            RestartReason::Import => "import",
+            RestartReason::Unlock => "unlock",
+            RestartReason::LocalOnlyRpc => "local-only rpc",
+            RestartReason::NewNodeKey => "new node key",
+            RestartReason::Pause => "pause",
+            RestartReason::Resume => "resume",
+            RestartReason::ResumeSession => "resume last session",
+            RestartReason::ExternalCrashLoop => "external miner crash loop",
+            RestartReason::ForkDetected => "fork detected",
+            RestartReason::RewardsAddressChange => "rewards address change",
+        }
+    }
+}
+
+/// `miner:restarting` payload and `restart_history.json` entry.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RestartRecord {
+    pub chain: String,
+    pub reason: RestartReason,
+    /// What triggered it, when there is more to say ("range", "throttle", ...); may be empty.
+    pub details: String,
+    /// UTC milliseconds since the epoch.
+    pub ts: i64,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum RestartError {
+    #[error("busy: {0:?} in progress on {1}")]
+    Busy(RestartOp, String),
+    /// A second Start for a chain whose start is still in flight (e.g. a double click).
+    #[error("already starting: {0}")]
+    AlreadyStarting(String),
+    /// Start for a chain whose node is already up; stop it first.
+    #[error("already running: {0}")]
+    AlreadyRunning(String),
+}
+
+lazy_static! {
+    // Serializes start/stop sequences; held for the whole operation.
+    static ref SEQ: Arc<Mutex<()>> = Arc::new(Mutex::new(()));
+    // Operation (and its chain) currently holding SEQ (for coalescing and Busy errors).
+    static ref IN_FLIGHT: std::sync::Mutex<Option<(RestartOp, String)>> =
+        std::sync::Mutex::new(None);
+}
+
+/// Held for the duration of a restart-causing operation; releases the slot on drop.
+pub struct RestartGuard {
+    _seq: OwnedMutexGuard<()>,
+}
+
+impl Drop for RestartGuard {
+    fn drop(&mut self) {
+        if let Ok(mut cur) = IN_FLIGHT.lock() {
+            *cur = None;
+        }
+    }
+}
+
+/// Reserve the start/stop sequence for `op` on `chain`.
+/// - `Ok(Some(guard))`: proceed; the guard must be held until the operation finishes.
+/// - `Ok(None)`: an identical operation on the same chain is already running; merged into it.
+/// - `Err(Busy)`: that chain is being repaired, imported into or compacted; nothing else
+///   may touch it until it's done.
+/// - `Err(AlreadyStarting)`: `op` is Start and that chain is already starting.
+///
+/// Any other overlapping request (including ones for other chains) waits its turn.
+pub async fn begin(op: RestartOp, chain: &str) -> Result<Option<RestartGuard>, RestartError> {
+    if let Some((cur, cur_chain)) = in_flight() {
+        if cur_chain == chain {
+            if matches!(
+                cur,
+                RestartOp::Repair | RestartOp::Import | RestartOp::Compact
+            ) {
+                return Err(RestartError::Busy(cur, cur_chain));
+            }
+            if cur == RestartOp::Start && op == RestartOp::Start {
+                return Err(RestartError::AlreadyStarting(cur_chain));
+            }
+            if cur == op && op.coalesces() {
+                return Ok(None);
+            }
+        }
+    }
+    let seq = SEQ.clone().lock_owned().await;
+    if let Ok(mut cur) = IN_FLIGHT.lock() {
+        *cur = Some((op, chain.to_string()));
+    }
+    Ok(Some(RestartGuard { _seq: seq }))
+}
+
+/// `begin(Start)`, then `alive` once the slot is ours: a concurrent Start that got it
+/// first may have brought the node up while this one waited (`AlreadyRunning` then).
+pub async fn begin_start<F>(
+    chain: &str,
+    alive: impl FnOnce() -> F,
+) -> Result<Option<RestartGuard>, RestartError>
+where
+    F: std::future::Future<Output = bool>,
+{
+    let Some(guard) = begin(RestartOp::Start, chain).await? else {
+        return Ok(None);
+    };
+    if alive().await {
+        return Err(RestartError::AlreadyRunning(chain.to_string()));
+    }
+    Ok(Some(guard))
+}
+
+/// Operation and chain currently in flight, if any.
+pub fn in_flight() -> Option<(RestartOp, String)> {
+    IN_FLIGHT.lock().ok().and_then(|g| g.clone())
+}
+
+fn history_path(app: &AppHandle) -> PathBuf {
+    crate::paths::app_data_dir(app).join("restart_history.json")
+}
+
+fn load_all(app: &AppHandle) -> HashMap<String, Vec<RestartRecord>> {
+    fs::read(history_path(app))
+        .ok()
+        .and_then(|b| serde_json::from_slice(&b).ok())
+        .unwrap_or_default()
+}
+
+/// Persisted restarts for `chain` (all chains when None), newest first.
+pub fn history(app: &AppHandle, chain: Option<&str>) -> Vec<RestartRecord> {
+    let mut v: Vec<RestartRecord> = load_all(app)
+        .into_iter()
+        .filter(|(c, _)| chain.is_none_or(|chain| chain == c))
+        .flat_map(|(_, v)| v)
+        .collect();
+    v.sort_by_key(|r| std::cmp::Reverse(r.ts));
+    v
+}
+
+fn save_to_history(app: &AppHandle, record: &RestartRecord) -> Result<()> {
+    let path = history_path(app);
+    let mut all = load_all(app);
+    let records = all.entry(record.chain.clone()).or_default();
+    records.push(record.clone());
+    if records.len() > HISTORY_LIMIT {
+        records.drain(..records.len() - HISTORY_LIMIT);
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    crate::persist::write_atomic(&path, &serde_json::to_vec_pretty(&all)?)?;
+    Ok(())
+}
+
+/// "safe mode enable (range)": the reason as shown in `MinerPhase::Restarting` and logs.
+pub fn describe(reason: RestartReason, details: &str) -> String {
+    if details.is_empty() {
+        reason.label().to_string()
+    } else {
+        format!("{} ({details})", reason.label())
+    }
+}
+
+/// Record that `chain`'s node is being (re)started for `reason` (`miner::start` calls
+/// this): emits `miner:restarting`, appends to the restart history and heads the next
+/// session log file with it.
+pub fn announce(app: &AppHandle, chain: &str, reason: RestartReason, details: &str) {
+    let record = RestartRecord {
+        chain: chain.to_string(),
+        reason,
+        details: details.to_string(),
+        ts: crate::timeutil::now_ms(),
+    };
+    events::emit(app, events::MINER_RESTARTING, &record);
+    if let Err(e) = save_to_history(app, &record) {
+        eprintln!("restart history: {e}");
+    }
+    crate::session_log::set_start_reason(
+        chain,
+        format!("Start reason: {}", describe(reason, details)),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+
+    // A node for the coordinator to start: `spawns` counts children, `alive` is what
+    // `is_chain_alive` would say.
+    #[derive(Default)]
+    struct FakeNode {
+        spawns: AtomicU32,
+        alive: AtomicBool,
+    }
+
+    // What start_miner does around `miner::start`.
+    async fn start(node: Arc<FakeNode>, chain: String) -> Result<(), RestartError> {
+        let alive = || async { node.alive.load(Ordering::SeqCst) };
+        let Some(_guard) = begin_start(&chain, alive).await? else {
+            return Ok(());
+        };
+        tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+        node.spawns.fetch_add(1, Ordering::SeqCst);
+        node.alive.store(true, Ordering::SeqCst);
+        Ok(())
+    }
+
+    const SITES: &[(&str, &str)] = &[
+        ("miner.rs", include_str!("miner.rs")),
+        ("commands.rs", include_str!("commands.rs")),
+    ];
+
+    fn all_reasons() -> Vec<RestartReason> {
+        use RestartReason::*;
+        // a new variant must be added here (and given a start site)
+        let _exhaustive = |r: RestartReason| match r {
+            Manual | SafeModeEnable | SafeModeDisable | Repair | Import | Unlock | LocalOnlyRpc
+            | NewNodeKey | Pause | Resume | ResumeSession | ExternalCrashLoop | ForkDetected
+            | RewardsAddressChange => {}
+        };
+        vec![
+            Manual,
+            SafeModeEnable,
+            SafeModeDisable,
+            Repair,
+            Import,
+            Unlock,
+            LocalOnlyRpc,
+            NewNodeKey,
+            Pause,
+            Resume,
+            ResumeSession,
+            ExternalCrashLoop,
+            ForkDetected,
+            RewardsAddressChange,
+        ]
+    }
+
+    // Top-level comma-separated arguments of the call whose `(` is at `open`.
+    fn call_args(src: &str, open: usize) -> Vec<String> {
+        let (mut depth, mut args, mut cur) = (0, Vec::new(), String::new());
+        for c in src[open..].chars() {
+            match c {
+                '(' | '{' | '[' => depth += 1,
+                ')' | '}' | ']' => depth -= 1,
+                _ => {}
+            }
+            if depth == 0 {
+                break;
+            }
+            if c == ',' && depth == 1 {
+                args.push(std::mem::take(&mut cur));
+            } else if !(depth == 1 && c == '(') {
+                cur.push(c);
+            }
+        }
+        args.push(cur);
+        args.into_iter()
+            .map(|a| a.split_whitespace().collect::<Vec<_>>().join(" "))
+            .filter(|a| !a.is_empty())
+            .collect()
+    }
+
+    // (file, offset, reason argument, details argument) of every `miner::start` call.
+    fn start_calls() -> Vec<(&'static str, usize, String, String)> {
+        let re = regex::Regex::new(r"(?:miner::|[^\w.:])start\(\s*app\b").unwrap();
+        SITES
+            .iter()
+            .flat_map(|(file, src)| {
+                re.find_iter(src)
+                    .filter(|m| !src[..m.start() + 1].ends_with("fn "))
+                    .map(move |m| {
+                        let open = m.start() + m.as_str().find('(').unwrap();
+                        let args = call_args(src, open);
+                        assert_eq!(args.len(), 4, "{file}: start({args:?})");
+                        (*file, m.start(), args[2].clone(), args[3].clone())
+                    })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn nodes_are_only_spawned_through_start() {
+        let (_, miner) = SITES[0];
+        let body_start = miner.find("pub async fn start(").unwrap();
+        let body_end = body_start + miner[body_start..].find("\n}\n").unwrap();
+        let calls: Vec<usize> = miner
+            .match_indices("start_node(")
+            .map(|(i, _)| i)
+            .filter(|i| !miner[..*i].ends_with("fn "))
+            .collect();
+        assert_eq!(calls.len(), 1);
+        assert!((body_start..body_end).contains(&calls[0]));
+        assert!(miner[body_start..body_end].contains("restart::announce(&app, &cfg.chain, reason"));
+    }
+
+    #[test]
+    fn every_restart_site_records_a_reason() {
+        let calls = start_calls();
+        // (guards against the pattern silently matching nothing)
+        assert!(calls.len() >= 10, "{calls:?}");
+        let mut named = HashSet::new();
+        for (file, _, reason, _) in &calls {
+            match reason.rsplit_once("RestartReason::") {
+                Some((_, variant)) => {
+                    named.insert(variant.to_string());
+                }
+                // set_safe_mode picks enable/disable first
+                None if reason == "why" => {
+                    named.insert("SafeModeEnable".into());
+                    named.insert("SafeModeDisable".into());
+                }
+                None => panic!("{file}: start() with reason `{reason}`"),
+            }
+        }
+        for r in all_reasons() {
+            assert!(
+                named.contains(&format!("{r:?}")),
+                "no start site records {r:?}"
+            );
+            assert!(!r.label().is_empty());
+        }
+    }
+
+    #[test]
+    fn restarting_phase_matches_the_recorded_reason() {
+        let calls = start_calls();
+        let (_, miner) = SITES[0];
+        let mut checked = 0;
+        for (phase_at, _) in miner.match_indices("MinerPhase::Restarting {") {
+            let describe = phase_at + miner[phase_at..].find("restart::describe(").unwrap();
+            let args = call_args(miner, describe + "restart::describe".len());
+            // the node start that follows
+            let (_, _, reason, details) = calls
+                .iter()
+                .find(|(file, at, ..)| *file == "miner.rs" && *at > phase_at)
+                .unwrap();
+            let reason = reason.trim_start_matches("restart::");
+            assert_eq!(
+                (args[0].as_str(), &args[1]),
+                (reason, details),
+                "at {phase_at}"
+            );
+            checked += 1;
+        }
+        assert!(checked >= 9, "{checked}");
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn concurrent_starts_spawn_one_child() {
+        for i in 0..50 {
+            let node = Arc::new(FakeNode::default());
+            let chain = format!("concurrent-start-{i}");
+            let a = tokio::spawn(start(node.clone(), chain.clone()));
+            let b = tokio::spawn(start(node.clone(), chain.clone()));
+            let results = [a.await.unwrap(), b.await.unwrap()];
+            assert_eq!(node.spawns.load(Ordering::SeqCst), 1, "round {i}");
+            let refused = results
+                .iter()
+                .filter(|r| {
+                    matches!(
+                        r,
+                        Err(RestartError::AlreadyStarting(_) | RestartError::AlreadyRunning(_))
+                    )
+                })
+                .count();
+            assert_eq!(refused, 1, "round {i}: {results:?}");
+        }
+    }
+
+    #[tokio::test]
+    async fn start_after_start_is_already_running() {
+        let node = Arc::new(FakeNode::default());
+        start(node.clone(), "sequential-start".into())
+            .await
+            .unwrap();
+        let again = start(node.clone(), "sequential-start".into()).await;
+        assert!(matches!(again, Err(RestartError::AlreadyRunning(_))));
+        assert_eq!(node.spawns.load(Ordering::SeqCst), 1);
+    }
+}
@@ -1,16 +1,19 @@
-use anyhow::Result;
+use anyhow::{anyhow, Result};
+use lazy_static::lazy_static;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tokio::sync::Mutex;
+
+use crate::net::{self, RetryPolicy};
+
+lazy_static! {
+    // symbol/decimals reported by each chain's bootnode (fetched once per app run)
+    static ref CHAIN_PROPERTIES: Mutex<HashMap<String, (String, u32)>> = Mutex::new(HashMap::new());
+}
 
 /// Central place to resolve chain endpoints used across the app.
 pub fn bootnode_ws_for_chain(chain: &str) -> Option<&'static str> {
-    match chain {
-        // testnets
-        "resonance" => Some("wss://a.t.res.fm"),
-        "heisenberg" => Some("wss://a.i.res.fm"),
-        // mainnet (placeholder – disabled in UI for now)
-        "quantus" => None,
-        _ => None,
-    }
+    crate::chains::chain_info(chain).and_then(|c| c.bootnode_ws)
 }
 
 /// Local node JSON-RPC endpoint (substrate default).
@@ -19,11 +22,34 @@ pub fn local_ws_endpoint() -> &'static str {
 }
 
 #[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct BalanceView {
     pub address: String,
-    pub free: String,   // raw string value (chain units, e.g., plancks)
-    pub symbol: String, // e.g., "RES"
-    pub decimals: u32,  // e.g., 12
+    pub free: String,     // raw string value (chain units, e.g., plancks)
+    pub reserved: String, // raw, like `free`
+    pub symbol: String,   // e.g., "RES"
+    pub decimals: u32,    // e.g., 12
+    // whole tokens for display, e.g. "1,234.5" (see balance::format_balance)
+    pub free_formatted: String,
+    pub reserved_formatted: String,
+}
+
+impl BalanceView {
+    fn new(address: &str, free: String, reserved: String, symbol: String, decimals: u32) -> Self {
+        let fmt = |raw: &str| {
+            crate::balance::format_balance(raw, decimals, crate::balance::DEFAULT_PRECISION)
+                .unwrap_or_else(|_| raw.to_string())
+        };
+        BalanceView {
+            address: address.to_string(),
+            free_formatted: fmt(&free),
+            reserved_formatted: fmt(&reserved),
+            free,
+            reserved,
+            symbol,
+            decimals,
+        }
+    }
 }
 
 // Structure used to decode system_properties
@@ -36,7 +62,7 @@ struct SystemProperties {
 }
 
 // Safe extractors for potential string/array forms
-fn extract_symbol(v: &serde_json::Value) -> Option<String> {
+pub(crate) fn extract_symbol(v: &serde_json::Value) -> Option<String> {
     match v {
         serde_json::Value::String(s) => Some(s.clone()),
         serde_json::Value::Array(arr) => {
@@ -45,7 +71,7 @@ fn extract_symbol(v: &serde_json::Value) -> Option<String> {
         _ => None,
     }
 }
-fn extract_decimals(v: &serde_json::Value) -> Option<u32> {
+pub(crate) fn extract_decimals(v: &serde_json::Value) -> Option<u32> {
     match v {
         serde_json::Value::Number(n) => n.as_u64().map(|x| x as u32),
         serde_json::Value::Array(arr) => arr.first().and_then(|x| x.as_u64()).map(|x| x as u32),
@@ -53,72 +79,93 @@ fn extract_decimals(v: &serde_json::Value) -> Option<u32> {
     }
 }
 
-/// Query local RPC for system properties (symbol/decimals)
-async fn fetch_local_chain_properties() -> (String, u32) {
+// Symbol and decimals from a `system_properties` result, each falling back to
+// `defaults` when missing or malformed.
+fn token_properties(result: serde_json::Value, defaults: (String, u32)) -> (String, u32) {
+    let props: SystemProperties = serde_json::from_value(result).unwrap_or(SystemProperties {
+        token_symbol: None,
+        token_decimals: None,
+    });
+    let symbol = props
+        .token_symbol
+        .as_ref()
+        .and_then(extract_symbol)
+        .unwrap_or(defaults.0);
+    let decimals = props
+        .token_decimals
+        .as_ref()
+        .and_then(extract_decimals)
+        .unwrap_or(defaults.1);
+    (symbol, decimals)
+}
+
+/// Token symbol and decimals for `chain`: the chain's own bootnode `system_properties`
+/// when reachable (cached), else the defaults from the chain table.
+/// Never asks the local node, which may be down or running a different chain.
+async fn fetch_chain_properties(chain: &crate::chains::ChainInfo) -> (String, u32) {
+    let defaults = (chain.token_symbol.to_string(), chain.token_decimals);
+    if let Some(cached) = CHAIN_PROPERTIES.lock().await.get(chain.ui_name) {
+        return cached.clone();
+    }
+    let Some(ws) = chain.bootnode_ws else {
+        return defaults;
+    };
+
     #[derive(Deserialize)]
     struct RpcResp {
         result: Option<serde_json::Value>,
     }
 
-    let client = match reqwest::Client::builder()
-        .user_agent("quantus-miner/0.1")
-        .build()
-    {
-        Ok(c) => c,
-        Err(_) => return ("".into(), 12),
-    };
-
     let body = serde_json::json!({
         "jsonrpc": "2.0",
         "id": 1,
         "method": "system_properties",
         "params": []
     });
+    // substrate serves HTTP JSON-RPC on the same endpoint as WS
+    let http_url = ws
+        .replacen("wss://", "https://", 1)
+        .replacen("ws://", "http://", 1);
 
-    if let Ok(resp) = client
-        .post(local_ws_endpoint().replace("ws://", "http://"))
-        .json(&body)
-        .send()
-        .await
-        .and_then(|r| r.error_for_status())
-    {
-        if let Ok(r) = resp.json::<RpcResp>().await {
-            if let Some(result) = r.result {
-                let props: SystemProperties =
-                    serde_json::from_value(result).unwrap_or(SystemProperties {
-                        token_symbol: None,
-                        token_decimals: None,
-                    });
-                let symbol = props
-                    .token_symbol
-                    .as_ref()
-                    .and_then(extract_symbol)
-                    .unwrap_or_else(|| "RES".to_string());
-                let decimals = props
-                    .token_decimals
-                    .as_ref()
-                    .and_then(extract_decimals)
-                    .unwrap_or(12);
-                return (symbol, decimals);
-            }
+    let resp = net::with_retry("system_properties", RetryPolicy::QUICK, || async {
+        let r: RpcResp = net::client()
+            .post(&http_url)
+            .json(&body)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        Ok(r)
+    })
+    .await;
+    if let Ok(r) = resp {
+        if let Some(result) = r.result {
+            let props = token_properties(result, defaults);
+            CHAIN_PROPERTIES
+                .lock()
+                .await
+                .insert(chain.ui_name.to_string(), props.clone());
+            return props;
         }
     }
 
-    // Fallback defaults
-    ("RES".into(), 12)
+    // Bootnode unreachable: table defaults (not cached, so we retry next time)
+    defaults
 }
 
-/// Fetch balance using network-specific strategy.
-/// For Resonance testnet we use Subsquid GraphQL.
-/// For other chains we return "0" until endpoints exist.
-pub async fn fetch_balance(ws_url: &str, address: &str) -> Result<BalanceView> {
-    let (symbol, decimals) = fetch_local_chain_properties().await;
+/// Fetch the balance of `address` on `chain`, with that chain's symbol/decimals.
+/// Chains with an indexer (Resonance: Subsquid GraphQL) are queried there;
+/// for other chains we return "0" until endpoints exist.
+pub async fn fetch_balance(chain: &str, address: &str) -> Result<BalanceView> {
+    let info = crate::chains::chain_info(chain).ok_or_else(|| anyhow!("unknown chain"))?;
+    let (symbol, decimals) = fetch_chain_properties(info).await;
 
-    // Resonance-only: use Subsquid GraphQL (https://gql.res.fm/graphql)
-    if ws_url.contains("res.fm") {
+    if let Some(graphql_url) = info.balance_graphql {
         #[derive(Deserialize)]
         struct AccountById {
             free: Option<String>,
+            reserved: Option<String>,
         }
         #[derive(Deserialize)]
         struct Data {
@@ -130,43 +177,237 @@ pub async fn fetch_balance(ws_url: &str, address: &str) -> Result<BalanceView> {
             data: Option<Data>,
         }
 
-        let client = reqwest::Client::builder()
-            .user_agent("quantus-miner/0.1")
-            .build()?;
-
         let query = r#"query Account($accountId: String!){ accountById(id: $accountId){ id free reserved } }"#;
         let body = serde_json::json!({
             "query": query,
             "variables": { "accountId": address }
         });
 
-        let resp: GraphQLResponse = client
-            .post("https://gql.res.fm/graphql")
+        let resp = net::with_retry("balance query", RetryPolicy::QUICK, || async {
+            let r: GraphQLResponse = net::client()
+                .post(graphql_url)
+                .json(&body)
+                .send()
+                .await?
+                .error_for_status()?
+                .json()
+                .await?;
+            Ok(r)
+        })
+        .await?;
+
+        let account = resp.data.and_then(|d| d.account_by_id);
+        let (free, reserved) = match account {
+            Some(a) => (a.free, a.reserved),
+            None => (None, None),
+        };
+        return Ok(BalanceView::new(
+            address,
+            free.unwrap_or_else(|| "0".to_string()),
+            reserved.unwrap_or_else(|| "0".to_string()),
+            symbol,
+            decimals,
+        ));
+    }
+
+    // Fallback for other chains (heisenberg/mainnet TBD)
+    Ok(BalanceView::new(
+        address,
+        "0".into(),
+        "0".into(),
+        symbol,
+        decimals,
+    ))
+}
+
+/// A transfer to or from an address, as the chain's indexer reports it.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Transfer {
+    /// The indexer's id for it (a batch can hold several transfers of one extrinsic).
+    pub id: String,
+    /// "in" (to the address) or "out" (from it).
+    pub direction: &'static str,
+    /// The other side's address.
+    pub counterparty: String,
+    pub amount: String, // raw, like `BalanceView::free`
+    pub amount_formatted: String,
+    pub symbol: String,
+    pub block: Option<u64>,
+    /// UTC milliseconds of the block.
+    pub timestamp: Option<i64>,
+    /// None for transfers no extrinsic made (e.g. minted rewards).
+    pub extrinsic_hash: Option<String>,
+}
+
+/// One page of `fetch_transfers`, newest first.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransferPage {
+    pub transfers: Vec<Transfer>,
+    /// Pass back for the next (older) page; None at the end.
+    pub next_cursor: Option<String>,
+    /// False on chains without an indexer: only balance totals are known there.
+    pub indexed: bool,
+}
+
+/// Transfers to or from `address` on `chain`, newest first, `limit` from `cursor` (an
+/// offset from a previous page). Chains without an indexer give an empty page with
+/// `indexed: false`.
+pub async fn fetch_transfers(
+    chain: &str,
+    address: &str,
+    cursor: Option<&str>,
+    limit: u32,
+) -> Result<TransferPage> {
+    let info = crate::chains::chain_info(chain).ok_or_else(|| anyhow!("unknown chain"))?;
+    let Some(graphql_url) = info.balance_graphql else {
+        return Ok(TransferPage {
+            transfers: Vec::new(),
+            next_cursor: None,
+            indexed: false,
+        });
+    };
+    let offset: u32 = match cursor {
+        Some(c) => c.parse().map_err(|_| anyhow!("invalid cursor {c:?}"))?,
+        None => 0,
+    };
+    let (symbol, decimals) = fetch_chain_properties(info).await;
+
+    #[derive(Deserialize)]
+    struct AccountRef {
+        id: String,
+    }
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct TransferNode {
+        id: String,
+        amount: Option<String>,
+        block_number: Option<serde_json::Value>,
+        timestamp: Option<String>,
+        extrinsic_hash: Option<String>,
+        from: Option<AccountRef>,
+        to: Option<AccountRef>,
+    }
+    #[derive(Deserialize)]
+    struct Data {
+        transfers: Vec<TransferNode>,
+    }
+    #[derive(Deserialize)]
+    struct GraphQLResponse {
+        data: Option<Data>,
+        errors: Option<serde_json::Value>,
+    }
+
+    let query = r#"query Transfers($accountId: String!, $limit: Int!, $offset: Int!){ transfers(where: {OR: [{from: {id_eq: $accountId}}, {to: {id_eq: $accountId}}]}, orderBy: [timestamp_DESC, id_DESC], limit: $limit, offset: $offset){ id amount blockNumber timestamp extrinsicHash from { id } to { id } } }"#;
+    let body = serde_json::json!({
+        "query": query,
+        "variables": { "accountId": address, "limit": limit, "offset": offset }
+    });
+    let resp = net::with_retry("transfers query", RetryPolicy::QUICK, || async {
+        let r: GraphQLResponse = net::client()
+            .post(graphql_url)
             .json(&body)
             .send()
             .await?
+            .error_for_status()?
             .json()
             .await?;
+        Ok(r)
+    })
+    .await?;
+    let data = match (resp.data, resp.errors) {
+        (Some(d), _) => d,
+        (None, Some(e)) => return Err(anyhow!("indexer: {e}")),
+        (None, None) => return Err(anyhow!("indexer returned no data")),
+    };
+
+    let full = data.transfers.len() as u32 >= limit;
+    let transfers = data
+        .transfers
+        .into_iter()
+        .map(|t| {
+            let from = t.from.map(|a| a.id).unwrap_or_default();
+            let to = t.to.map(|a| a.id).unwrap_or_default();
+            let (direction, counterparty) = if to == address {
+                ("in", from)
+            } else {
+                ("out", to)
+            };
+            let amount = t.amount.unwrap_or_else(|| "0".to_string());
+            Transfer {
+                id: t.id,
+                direction,
+                counterparty,
+                amount_formatted: crate::balance::format_balance(
+                    &amount,
+                    decimals,
+                    crate::balance::DEFAULT_PRECISION,
+                )
+                .unwrap_or_else(|_| amount.clone()),
+                amount,
+                symbol: symbol.clone(),
+                // a BigInt scalar may come as a string
+                block: t.block_number.and_then(|b| match b {
+                    serde_json::Value::Number(n) => n.as_u64(),
+                    serde_json::Value::String(s) => s.parse().ok(),
+                    _ => None,
+                }),
+                timestamp: t
+                    .timestamp
+                    .as_deref()
+                    .and_then(crate::timeutil::parse_rfc3339_ms),
+                extrinsic_hash: t.extrinsic_hash,
+            }
+        })
+        .collect();
+    Ok(TransferPage {
+        transfers,
+        next_cursor: full.then(|| (offset + limit).to_string()),
+        indexed: true,
+    })
+}
 
-        let free = resp
-            .data
-            .and_then(|d| d.account_by_id)
-            .and_then(|a| a.free)
-            .unwrap_or_else(|| "0".to_string());
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
 
-        return Ok(BalanceView {
-            address: address.to_string(),
-            free,
-            symbol,
-            decimals,
+    fn props(result: serde_json::Value) -> (String, u32) {
+        token_properties(result, ("QU".into(), 12))
+    }
+
+    #[test]
+    fn scalar_properties() {
+        let result = json!({"ss58Format": 189, "tokenDecimals": 12, "tokenSymbol": "RES"});
+        assert_eq!(props(result), ("RES".into(), 12));
+    }
+
+    #[test]
+    fn array_properties() {
+        // multi-token chains list one entry per token; the first is the native one
+        let result = json!({
+            "ss58Format": 189,
+            "tokenDecimals": [9, 18],
+            "tokenSymbol": ["HEI", "USDX"]
         });
+        assert_eq!(props(result), ("HEI".into(), 9));
     }
 
-    // Fallback for other chains (heisenberg/mainnet TBD)
-    Ok(BalanceView {
-        address: address.to_string(),
-        free: "0".into(),
-        symbol,
-        decimals,
-    })
+    #[test]
+    fn missing_or_malformed_properties_use_defaults() {
+        assert_eq!(props(json!({})), ("QU".into(), 12));
+        assert_eq!(
+            props(json!({"tokenSymbol": [], "tokenDecimals": []})),
+            ("QU".into(), 12)
+        );
+        assert_eq!(
+            props(json!({"tokenSymbol": 5, "tokenDecimals": "12"})),
+            ("QU".into(), 12)
+        );
+        // one half present
+        assert_eq!(props(json!({"tokenSymbol": "RES"})), ("RES".into(), 12));
+        assert_eq!(props(json!({"tokenDecimals": [10]})), ("QU".into(), 10));
+        assert_eq!(props(json!(null)), ("QU".into(), 12));
+    }
 }
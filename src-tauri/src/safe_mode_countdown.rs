@@ -0,0 +1,217 @@
+use anyhow::{anyhow, Result};
+use lazy_static::lazy_static;
+use serde::Serialize;
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+use tauri::AppHandle;
+
+use crate::events;
+
+/// Longest countdown `safe_mode_countdown_secs` can ask for.
+const MAX_SECS: u64 = 300;
+
+/// A safe-mode toggle as the detectors schedule it; `range` is the heavy block range
+/// behind a "range" enable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Toggle {
+    pub enable: bool,
+    pub reason: &'static str,
+    pub range: Option<(u64, u64)>,
+}
+
+/// What the status loop does with a pending toggle this round.
+pub enum Step {
+    /// Still counting down; leave it pending.
+    Wait,
+    Apply,
+    /// Vetoed: drop it.
+    Skip,
+}
+
+/// `miner:safe-mode-countdown` payload: sent when a countdown starts (`outcome` null) and
+/// when it ends ("applied", "vetoed" or "expired", the last also applying it).
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Countdown {
+    chain: String,
+    enable: bool,
+    reason: &'static str,
+    seconds: u64,
+    range_start: Option<u64>,
+    range_end: Option<u64>,
+    /// UTC milliseconds the toggle is applied at unless vetoed.
+    deadline: i64,
+    outcome: Option<&'static str>,
+    ts: i64,
+}
+
+struct Running {
+    toggle: Toggle,
+    deadline: Instant,
+    deadline_ms: i64,
+    seconds: u64,
+    // `confirm_safe_mode` answer, picked up by the next `step`
+    decision: Option<bool>,
+}
+
+lazy_static! {
+    static ref RUNNING: Mutex<HashMap<String, Running>> = Mutex::new(HashMap::new());
+    // Toggles vetoed since the chain's node last started; not scheduled again until then.
+    static ref VETOED: Mutex<HashMap<String, Vec<Toggle>>> = Mutex::new(HashMap::new());
+}
+
+/// Whether `toggle` was vetoed during the current node session.
+pub fn is_vetoed(chain: &str, toggle: Toggle) -> bool {
+    VETOED
+        .lock()
+        .is_ok_and(|v| v.get(chain).is_some_and(|t| t.contains(&toggle)))
+}
+
+/// The node (re)started: vetoes and any countdown belong to the old session.
+pub fn reset_session(chain: &str) {
+    if let Ok(mut v) = VETOED.lock() {
+        v.remove(chain);
+    }
+    if let Ok(mut r) = RUNNING.lock() {
+        r.remove(chain);
+    }
+}
+
+/// `confirm_safe_mode`: apply the counting-down toggle now (`apply`) or veto it.
+pub fn confirm(chain: &str, apply: bool) -> Result<()> {
+    let mut running = RUNNING
+        .lock()
+        .map_err(|_| anyhow!("safe mode countdown state poisoned"))?;
+    let r = running
+        .get_mut(chain)
+        .ok_or_else(|| anyhow!("no safe mode change is counting down on {chain}"))?;
+    r.decision = Some(apply);
+    Ok(())
+}
+
+/// Called by the status loop every round while `toggle` is pending: starts a countdown
+/// of `seconds` (sending `miner:safe-mode-countdown`), then reports the answer or the
+/// deadline. 0 seconds applies at once, without an event (headless use).
+pub fn step(app: &AppHandle, chain: &str, toggle: Toggle, seconds: u64) -> Step {
+    if seconds == 0 {
+        return Step::Apply;
+    }
+    let Ok(mut running) = RUNNING.lock() else {
+        return Step::Apply;
+    };
+    let current = running.get(chain).filter(|r| r.toggle == toggle);
+    let Some(r) = current else {
+        // none yet, or one for a toggle that was replaced
+        let seconds = seconds.min(MAX_SECS);
+        let deadline_ms = crate::timeutil::now_ms() + (seconds * 1000) as i64;
+        running.insert(
+            chain.to_string(),
+            Running {
+                toggle,
+                deadline: Instant::now() + Duration::from_secs(seconds),
+                deadline_ms,
+                seconds,
+                decision: None,
+            },
+        );
+        drop(running);
+        crate::session_log::ui(
+            app,
+            chain,
+            format!(
+                "Safe sync {} in {seconds}s unless cancelled",
+                if toggle.enable { "enable" } else { "disable" }
+            ),
+        );
+        send(app, chain, toggle, seconds, deadline_ms, None);
+        return Step::Wait;
+    };
+    let outcome = match r.decision {
+        Some(true) => "applied",
+        Some(false) => "vetoed",
+        None if Instant::now() >= r.deadline => "expired",
+        None => return Step::Wait,
+    };
+    let (seconds, deadline_ms) = (r.seconds, r.deadline_ms);
+    running.remove(chain);
+    drop(running);
+    send(app, chain, toggle, seconds, deadline_ms, Some(outcome));
+    if outcome != "vetoed" {
+        return Step::Apply;
+    }
+    if let Ok(mut v) = VETOED.lock() {
+        v.entry(chain.to_string()).or_default().push(toggle);
+    }
+    crate::session_log::ui(
+        app,
+        chain,
+        format!(
+            "Safe sync {} cancelled; not scheduled again for this reason until the node restarts",
+            if toggle.enable { "enable" } else { "disable" }
+        ),
+    );
+    Step::Skip
+}
+
+fn send(
+    app: &AppHandle,
+    chain: &str,
+    toggle: Toggle,
+    seconds: u64,
+    deadline: i64,
+    outcome: Option<&'static str>,
+) {
+    events::emit(
+        app,
+        events::MINER_SAFE_MODE_COUNTDOWN,
+        &Countdown {
+            chain: chain.to_string(),
+            enable: toggle.enable,
+            reason: toggle.reason,
+            seconds,
+            range_start: toggle.range.map(|r| r.0),
+            range_end: toggle.range.map(|r| r.1),
+            deadline,
+            outcome,
+            ts: crate::timeutil::now_ms(),
+        },
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn payload_is_camel_case() {
+        let c = Countdown {
+            chain: "resonance".into(),
+            enable: true,
+            reason: "range",
+            seconds: 30,
+            range_start: Some(100),
+            range_end: Some(200),
+            deadline: 5000,
+            outcome: None,
+            ts: 1,
+        };
+        assert_eq!(
+            serde_json::to_value(&c).unwrap(),
+            json!({
+                "chain": "resonance",
+                "enable": true,
+                "reason": "range",
+                "seconds": 30,
+                "rangeStart": 100,
+                "rangeEnd": 200,
+                "deadline": 5000,
+                "outcome": null,
+                "ts": 1,
+            })
+        );
+    }
+}
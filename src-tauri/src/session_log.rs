@@ -0,0 +1,449 @@
+use anyhow::{anyhow, Result};
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, VecDeque},
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicBool, Ordering},
+};
+use tauri::AppHandle;
+use tokio::sync::{mpsc, Mutex};
+
+use crate::events;
+use crate::timeutil;
+
+/// How many recent log lines we keep in memory for seeding a file opened mid-run.
+const RING_CAPACITY: usize = 2000;
+/// How many lines written while no file is open (early start errors) are kept for it.
+const PENDING_CAPACITY: usize = 500;
+
+// A chain's recent lines; `dropped` counts those that fell off the front, so a line
+// keeps its number (`dropped + index + 1`) while newer ones arrive.
+#[derive(Default)]
+struct Ring {
+    lines: VecDeque<String>,
+    dropped: u64,
+}
+
+struct SessionFile {
+    path: PathBuf,
+    file: fs::File,
+}
+
+// One session log line, in the order it was produced.
+struct Entry {
+    chain: String,
+    /// "node" | "external" | "ui" | "command"
+    column: &'static str,
+    line: String,
+    ts: time::OffsetDateTime,
+}
+
+// Space is low on the volume shared with a node database (`log_space`): node and
+// external miner lines reach files only when they are warnings or errors.
+static THROTTLED: AtomicBool = AtomicBool::new(false);
+
+lazy_static! {
+    // Recent formatted log lines (all sources) per chain, oldest first.
+    static ref RINGS: Mutex<HashMap<String, Ring>> = Mutex::new(HashMap::new());
+    // Currently open node session log file per chain.
+    static ref SESSIONS: Mutex<HashMap<String, SessionFile>> = Mutex::new(HashMap::new());
+    // Lines produced while the chain had no open file, written when one is opened.
+    static ref PENDING: Mutex<HashMap<String, VecDeque<String>>> = Mutex::new(HashMap::new());
+    // Why the chain's node is being started (`restart::announce`), for its next file.
+    static ref START_REASONS: std::sync::Mutex<HashMap<String, Entry>> =
+        std::sync::Mutex::new(HashMap::new());
+    // Logs directory chosen with `set_log_directory` (None: `default_logs_dir`).
+    static ref LOG_DIR: std::sync::Mutex<Option<PathBuf>> = std::sync::Mutex::new(None);
+    // The writer task: every line reaches the file through it, so sources stay interleaved.
+    static ref WRITER: mpsc::UnboundedSender<Entry> = spawn_writer();
+}
+
+/// A session log file found by `list_files`.
+#[derive(Debug, Clone, Serialize)]
+pub struct LogFile {
+    pub path: String,
+    /// None for older external miner files, named before logs were kept per chain.
+    pub chain: Option<String>,
+    /// "node" | "ext"
+    pub kind: &'static str,
+    pub size: u64,
+    /// Unix seconds.
+    pub modified: Option<i64>,
+}
+
+/// Default directory for session logs: {local_data_dir}/quantus-miner/logs
+pub fn default_logs_dir() -> PathBuf {
+    crate::paths::data_local_dir()
+        .join("quantus-miner")
+        .join("logs")
+}
+
+/// Directory for session logs: the one set with `set_log_directory`, else the default.
+pub fn logs_dir() -> PathBuf {
+    LOG_DIR
+        .lock()
+        .ok()
+        .and_then(|d| d.clone())
+        .unwrap_or_else(default_logs_dir)
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct LogDirFile {
+    log_dir: Option<String>,
+}
+
+fn log_dir_config_path(app: &AppHandle) -> PathBuf {
+    crate::paths::app_data_dir(app).join("log_dir.json")
+}
+
+/// Load the persisted logs directory (called once at startup).
+pub fn load_log_dir(app: &AppHandle) {
+    let dir = fs::read(log_dir_config_path(app))
+        .ok()
+        .and_then(|b| serde_json::from_slice::<LogDirFile>(&b).ok())
+        .and_then(|f| f.log_dir)
+        .map(PathBuf::from);
+    if let Ok(mut cur) = LOG_DIR.lock() {
+        *cur = dir;
+    }
+}
+
+/// Persist `dir` as the logs directory (None: the default) and use it for new files.
+pub fn set_log_dir(app: &AppHandle, dir: Option<&Path>) -> Result<()> {
+    let path = log_dir_config_path(app);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let file = LogDirFile {
+        log_dir: dir.map(|p| p.to_string_lossy().to_string()),
+    };
+    crate::persist::write_atomic(&path, &serde_json::to_vec_pretty(&file)?)?;
+    if let Ok(mut cur) = LOG_DIR.lock() {
+        *cur = dir.map(Path::to_path_buf);
+    }
+    Ok(())
+}
+
+/// Keep only warnings and errors from the node and external miner in session files
+/// (`log_space`). Returns the previous setting.
+pub fn set_throttled(on: bool) -> bool {
+    THROTTLED.swap(on, Ordering::Relaxed)
+}
+
+pub fn throttled() -> bool {
+    THROTTLED.load(Ordering::Relaxed)
+}
+
+/// Whether a process output line goes to a session file: always, unless throttled.
+pub fn keep_in_file(line: &str) -> bool {
+    !throttled() || crate::parse::is_problem_line(line)
+}
+
+/// Create a new log file for `chain`: `{logs_dir}/{chain}/{prefix}-{chain}-{pid}-{stamp}.log`
+/// (`timeutil::file_stamp`; the directory is created). Never reuses an existing file.
+pub fn create_log_file(chain: &str, prefix: &str, pid: u32) -> Result<(PathBuf, fs::File)> {
+    let dir = logs_dir().join(chain);
+    fs::create_dir_all(&dir)?;
+    // the stamp's counter makes a collision unlikely; create_new makes it harmless
+    for _ in 0..8 {
+        let path = dir.join(format!(
+            "{prefix}-{chain}-{pid}-{}.log",
+            timeutil::file_stamp()
+        ));
+        match fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)
+        {
+            Ok(file) => return Ok((path, file)),
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(e.into()),
+        }
+    }
+    Err(anyhow!(
+        "could not find a free log file name in {}",
+        dir.display()
+    ))
+}
+
+/// First line of every session file, naming the chain and the process it logs.
+pub fn header_line(chain: &str, process: &str, pid: u32) -> String {
+    format_entry(&Entry {
+        chain: chain.to_string(),
+        column: "ui",
+        line: format!("Session log for chain {chain} ({process} pid {pid})"),
+        ts: time::OffsetDateTime::now_utc(),
+    })
+}
+
+// kind and chain from `quantus-node-<chain>-<pid>-<ts>.log` / `quantus-miner-[<chain>-]<pid>-<ts>.log`
+fn parse_log_name(name: &str) -> Option<(&'static str, Option<String>)> {
+    let stem = name.strip_suffix(".log")?;
+    let (kind, rest) = if let Some(rest) = stem.strip_prefix("quantus-node-") {
+        ("node", rest)
+    } else {
+        ("ext", stem.strip_prefix("quantus-miner-")?)
+    };
+    let first = rest.split('-').next().unwrap_or_default();
+    let chain = (!first.is_empty() && !first.chars().all(|c| c.is_ascii_digit()))
+        .then(|| first.to_string());
+    Some((kind, chain))
+}
+
+/// Session log files (per-chain directories and older flat ones), newest first;
+/// only `chain`'s when given.
+pub fn list_files(chain: Option<&str>) -> Vec<LogFile> {
+    let root = logs_dir();
+    // (directory, chain it belongs to when it is a chain directory)
+    let mut dirs = vec![(root.clone(), None)];
+    for e in fs::read_dir(&root).into_iter().flatten().flatten() {
+        if e.path().is_dir() {
+            let name = e.file_name().to_string_lossy().to_string();
+            dirs.push((e.path(), Some(name)));
+        }
+    }
+    let mut files = Vec::new();
+    for (dir, dir_chain) in dirs {
+        for e in fs::read_dir(&dir).into_iter().flatten().flatten() {
+            let name = e.file_name().to_string_lossy().to_string();
+            let Some((kind, name_chain)) = parse_log_name(&name) else {
+                continue;
+            };
+            let file_chain = dir_chain.clone().or(name_chain);
+            if chain.is_some_and(|c| file_chain.as_deref() != Some(c)) {
+                continue;
+            }
+            let Ok(meta) = e.metadata() else {
+                continue;
+            };
+            files.push(LogFile {
+                path: e.path().display().to_string(),
+                chain: file_chain,
+                kind,
+                size: meta.len(),
+                modified: meta
+                    .modified()
+                    .ok()
+                    .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+                    .map(|d| d.as_secs() as i64),
+            });
+        }
+    }
+    files.sort_by_key(|f| std::cmp::Reverse(f.modified));
+    files
+}
+
+// Whether the oldest line had to go.
+fn push_capped(q: &mut VecDeque<String>, cap: usize, line: String) -> bool {
+    let full = q.len() >= cap;
+    if full {
+        q.pop_front();
+    }
+    q.push_back(line);
+    full
+}
+
+// "<rfc3339 utc> <source> <line>", the source padded to a fixed-width column.
+fn format_entry(e: &Entry) -> String {
+    format!("{} {:<8} {}", timeutil::rfc3339(e.ts), e.column, e.line)
+}
+
+fn spawn_writer() -> mpsc::UnboundedSender<Entry> {
+    let (tx, mut rx) = mpsc::unbounded_channel::<Entry>();
+    tauri::async_runtime::spawn(async move {
+        while let Some(e) = rx.recv().await {
+            let line = format_entry(&e);
+            {
+                let mut rings = RINGS.lock().await;
+                let ring = rings.entry(e.chain.clone()).or_default();
+                if push_capped(&mut ring.lines, RING_CAPACITY, line.clone()) {
+                    ring.dropped += 1;
+                }
+            }
+            // GUI and command lines are few; they always reach the file
+            if matches!(e.column, "node" | "external") && !keep_in_file(&e.line) {
+                continue;
+            }
+            // held while deciding file vs pending, so `open` can't slip in between
+            let mut sessions = SESSIONS.lock().await;
+            match sessions.get_mut(&e.chain) {
+                Some(s) => {
+                    let _ = writeln!(s.file, "{line}");
+                }
+                None => {
+                    push_capped(
+                        PENDING.lock().await.entry(e.chain).or_default(),
+                        PENDING_CAPACITY,
+                        line,
+                    );
+                }
+            }
+        }
+    });
+    tx
+}
+
+fn queue(chain: &str, column: &'static str, line: String) {
+    let line = crate::redact::string(line);
+    let _ = WRITER.send(Entry {
+        chain: chain.to_string(),
+        column,
+        line,
+        ts: time::OffsetDateTime::now_utc(),
+    });
+}
+
+/// Record a node log line: into the chain's ring buffer and session file (if open).
+pub fn record(chain: &str, line: &str) {
+    queue(chain, "node", line.to_string());
+}
+
+/// Emit a `miner:log` line (`source` as the UI knows it: "ui" | "stdout" | "stderr")
+/// and record it in `chain`'s session log under `column`.
+pub fn emit(
+    app: &AppHandle,
+    chain: &str,
+    source: &'static str,
+    column: &'static str,
+    line: String,
+) {
+    queue(chain, column, line.clone());
+    events::emit_log(app, source, line);
+}
+
+/// A GUI message for `chain`: shown in the log view and recorded in the session log.
+pub fn ui(app: &AppHandle, chain: &str, line: impl Into<String>) {
+    emit(app, chain, "ui", "ui", line.into());
+}
+
+/// Note a command invocation in `chain`'s session log (not shown in the UI).
+pub fn command(chain: &str, name: &str, detail: &str) {
+    let line = if detail.is_empty() {
+        name.to_string()
+    } else {
+        format!("{name} {detail}")
+    };
+    queue(chain, "command", line);
+}
+
+/// Append a security-relevant event to `{logs_dir}/audit.log` ("<unix secs> <chain>
+/// <event> <detail>").
+pub fn audit(chain: &str, event: &str, detail: &str) {
+    let dir = logs_dir();
+    if fs::create_dir_all(&dir).is_err() {
+        return;
+    }
+    let ts = timeutil::now_secs();
+    if let Ok(mut f) = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(dir.join("audit.log"))
+    {
+        let detail = crate::redact::text(detail);
+        let _ = writeln!(f, "{ts} {chain} {event} {detail}");
+    }
+}
+
+/// Make `line` the first line of `chain`'s next session file (a `ui` line, timestamped now).
+pub fn set_start_reason(chain: &str, line: String) {
+    if let Ok(mut reasons) = START_REASONS.lock() {
+        reasons.insert(
+            chain.to_string(),
+            Entry {
+                chain: chain.to_string(),
+                column: "ui",
+                line,
+                ts: time::OffsetDateTime::now_utc(),
+            },
+        );
+    }
+}
+
+/// Forget a chain's buffered lines (used when that chain is restarted fresh). Lines
+/// waiting for the next file are kept: they belong to the start that is under way.
+pub async fn clear_ring(chain: &str) {
+    RINGS.lock().await.remove(chain);
+}
+
+/// The chain's buffered lines (formatted as in session files), oldest first, with the
+/// number of the first one. Numbers count from the chain's last fresh start.
+pub async fn ring_lines(chain: &str) -> (u64, Vec<String>) {
+    match RINGS.lock().await.get(chain) {
+        Some(r) => (r.dropped + 1, r.lines.iter().cloned().collect()),
+        None => (1, Vec::new()),
+    }
+}
+
+/// Path of the chain's current session log file, if file logging is active.
+pub async fn current_path(chain: &str) -> Option<PathBuf> {
+    SESSIONS.lock().await.get(chain).map(|s| s.path.clone())
+}
+
+/// Open a new session file for the chain's node with the given pid, replacing any open one.
+/// After the header (`header_line`) comes the start reason (`set_start_reason`), then lines produced while no file was
+/// open; when `seed` is true the whole ring buffer is written instead, so recent context
+/// isn't lost.
+pub async fn open(app: &AppHandle, chain: &str, pid: u32, seed: bool) -> Result<PathBuf> {
+    let (path, mut file) = create_log_file(chain, "quantus-node", pid)?;
+    let _ = writeln!(file, "{}", header_line(chain, "quantus-node", pid));
+    let reason = START_REASONS.lock().ok().and_then(|mut r| r.remove(chain));
+    if let Some(e) = reason {
+        let _ = writeln!(file, "{}", format_entry(&e));
+    }
+    let mut sessions = SESSIONS.lock().await;
+    let pending = PENDING.lock().await.remove(chain).unwrap_or_default();
+    let backlog = if seed {
+        RINGS
+            .lock()
+            .await
+            .get(chain)
+            .map(|r| r.lines.clone())
+            .unwrap_or_default()
+    } else {
+        pending
+    };
+    for line in backlog.iter() {
+        let _ = writeln!(file, "{}", line);
+    }
+    sessions.insert(
+        chain.to_string(),
+        SessionFile {
+            path: path.clone(),
+            file,
+        },
+    );
+    drop(sessions);
+
+    ui(
+        app,
+        chain,
+        format!("Node logging to file: {}", path.display()),
+    );
+    events::emit(
+        app,
+        events::MINER_LOGFILE,
+        &serde_json::json!({ "path": path.display().to_string(), "kind": "node", "chain": chain }),
+    );
+    Ok(path)
+}
+
+/// Close the chain's session file (if any) and notify the UI.
+pub async fn close(app: &AppHandle, chain: &str) {
+    let closed = SESSIONS.lock().await.remove(chain);
+    if let Some(mut s) = closed {
+        let _ = s.file.flush();
+        ui(
+            app,
+            chain,
+            format!("Stopped logging to file: {}", s.path.display()),
+        );
+        events::emit(
+            app,
+            events::MINER_LOGFILE,
+            &serde_json::json!({ "path": null, "kind": "node", "chain": chain }),
+        );
+    }
+}
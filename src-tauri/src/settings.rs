@@ -0,0 +1,383 @@
+use anyhow::{anyhow, Result};
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use std::{
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+use tauri::AppHandle;
+
+use crate::events;
+
+/// Changes are written this long after the last one, so typing in a field doesn't write
+/// once per keystroke.
+const FLUSH_DELAY: Duration = Duration::from_millis(500);
+
+/// GUI preferences (`{app_data_dir}/settings.json`). Feature settings with their own
+/// validation (throttle, shutdown, ...) keep their own files.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub chain: String,
+    /// The extra-args field as typed; parsed with `normalize_extra_args` on start.
+    pub extra_args: String,
+    pub external_miner_enabled: bool,
+    pub external_miner_cores: Option<u32>,
+    pub external_miner_port: Option<u16>,
+    /// Threads for the node's built-in miner (None: the node's default).
+    pub internal_mining_threads: Option<u32>,
+    pub log_to_file: bool,
+    pub auto_start: bool,
+    pub resume_session: bool,
+    /// "system" | "light" | "dark"
+    pub theme: String,
+    pub line_limit: u32,
+    /// POST target for `miner:authored` events (`outbox.rs`); empty means off.
+    pub webhook_url: String,
+    /// How long `query_balance` answers from its cache (`balance_cache.rs`); 0 = never.
+    pub balance_ttl_secs: u64,
+    /// Opt-in anonymous daily ping (`telemetry.rs`): version, OS, chain, synced or not.
+    pub usage_ping: bool,
+    /// Where the ping goes (https only); nothing is sent while empty.
+    pub usage_ping_url: String,
+    /// How long an automatic safe sync restart waits for `confirm_safe_mode`
+    /// (`safe_mode_countdown.rs`; at most 300); 0 applies it at once, for headless use.
+    pub safe_mode_countdown_secs: u64,
+    /// Argon2 hash of the app PIN (`app_pin.rs`); only `set_app_pin`/`clear_app_pin`
+    /// change it and the UI only sees `pin_required`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub app_pin_hash: Option<String>,
+    /// Address the node mines to instead of the account's (`set_rewards_address`);
+    /// empty means the account's.
+    pub rewards_address: String,
+    /// Earlier rewards addresses whose balances the UI keeps showing.
+    pub watched_addresses: Vec<String>,
+}
+
+impl crate::persist::Versioned for Settings {
+    const VERSION: u32 = 1;
+    const MIGRATIONS: &'static [crate::persist::Migration] = &[crate::persist::unchanged];
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            chain: "resonance".to_string(),
+            extra_args: String::new(),
+            external_miner_enabled: false,
+            external_miner_cores: None,
+            external_miner_port: None,
+            internal_mining_threads: None,
+            log_to_file: false,
+            auto_start: false,
+            resume_session: false,
+            theme: "system".to_string(),
+            line_limit: 400,
+            webhook_url: String::new(),
+            balance_ttl_secs: 60,
+            usage_ping: false,
+            usage_ping_url: String::new(),
+            safe_mode_countdown_secs: 30,
+            app_pin_hash: None,
+            rewards_address: String::new(),
+            watched_addresses: Vec::new(),
+        }
+    }
+}
+
+/// Settings as the UI gets them: the PIN hash replaced by whether there is one.
+#[derive(Debug, Clone, Serialize)]
+pub struct SettingsView {
+    #[serde(flatten)]
+    pub settings: Settings,
+    /// Destructive commands need the app PIN.
+    pub pin_required: bool,
+}
+
+impl From<Settings> for SettingsView {
+    fn from(mut settings: Settings) -> Self {
+        let pin_required = settings.app_pin_hash.take().is_some();
+        SettingsView {
+            settings,
+            pin_required,
+        }
+    }
+}
+
+/// `settings:changed` payload: the fields that changed, with their new values.
+#[derive(Debug, Clone, Serialize)]
+struct SettingsChanged {
+    changed: serde_json::Map<String, serde_json::Value>,
+    ts: i64,
+}
+
+/// `settings:flush-failed` payload; the change stays in memory and is written again with
+/// the next change or on exit.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct FlushFailed {
+    path: PathBuf,
+    error: String,
+    ts: i64,
+}
+
+struct Store {
+    // authoritative copy; None until first loaded
+    settings: Option<Settings>,
+    // changed since the last successful write
+    dirty: bool,
+    // why settings.json couldn't be read: it is then never overwritten and the
+    // PIN-gated commands refuse, since the file may hold a PIN
+    damaged: Option<String>,
+}
+
+lazy_static! {
+    // held for a whole set (merge, event) and for writes, which serializes them
+    static ref STORE: std::sync::Mutex<Store> = std::sync::Mutex::new(Store {
+        settings: None,
+        dirty: false,
+        damaged: None,
+    });
+}
+
+// bumped by every change; a pending flush only runs if no later change came in
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+fn settings_path(app: &AppHandle) -> PathBuf {
+    crate::paths::app_data_dir(app).join("settings.json")
+}
+
+fn loaded<'a>(app: &AppHandle, store: &'a mut Store) -> &'a mut Settings {
+    if store.settings.is_none() {
+        let path = settings_path(app);
+        let (settings, damaged) = read(&path);
+        store.settings = Some(settings);
+        store.damaged = damaged;
+    }
+    store.settings.get_or_insert_with(Settings::default)
+}
+
+// settings.json, or defaults and why it couldn't be used. A file that doesn't parse is
+// quarantined only when its `.bak` reads; otherwise it stays as it is, for the user to
+// fix or remove.
+fn read(path: &Path) -> (Settings, Option<String>) {
+    use crate::persist::{backup_path, quarantine, read_versioned, ReadError};
+    let err = match read_versioned::<Settings>(path) {
+        Ok(s) => return (s.unwrap_or_default(), None),
+        Err(e) => e,
+    };
+    if let ReadError::Invalid(_) = err {
+        if let Ok(Some(backup)) = read_versioned::<Settings>(&backup_path(path)) {
+            match quarantine(path) {
+                Ok(to) => eprintln!(
+                    "settings: {err}; moved to {}, using the backup",
+                    to.display()
+                ),
+                Err(qe) => eprintln!("settings: {err}; using the backup (not moved: {qe})"),
+            }
+            return (backup, None);
+        }
+    }
+    let why = format!("{} is {err}", path.display());
+    eprintln!("settings: {why}; using defaults and leaving it alone");
+    (Settings::default(), Some(why))
+}
+
+/// Why settings.json couldn't be read, if it couldn't: nothing is written over it and
+/// PIN-gated commands refuse until it's fixed (and the app restarted).
+pub fn damaged(app: &AppHandle) -> Option<String> {
+    let mut store = STORE.lock().unwrap_or_else(|e| e.into_inner());
+    loaded(app, &mut store);
+    store.damaged.clone()
+}
+
+/// Current settings (the in-memory copy, which may be ahead of the file).
+pub fn get(app: &AppHandle) -> Settings {
+    let mut store = STORE.lock().unwrap_or_else(|e| e.into_inner());
+    loaded(app, &mut store).clone()
+}
+
+/// Apply `patch` (any subset of the fields) and broadcast `settings:changed` with what
+/// actually changed; the write follows `FLUSH_DELAY` after the last change.
+pub fn set(app: &AppHandle, patch: serde_json::Map<String, serde_json::Value>) -> Result<Settings> {
+    let mut store = STORE.lock().unwrap_or_else(|e| e.into_inner());
+    let current = loaded(app, &mut store);
+    let serde_json::Value::Object(old) = serde_json::to_value(&*current)? else {
+        return Err(anyhow!("settings are not an object"));
+    };
+    if patch.contains_key("app_pin_hash") {
+        return Err(anyhow!(
+            "the app PIN is changed with set_app_pin/clear_app_pin"
+        ));
+    }
+    if let Some(key) = patch.keys().find(|k| !old.contains_key(*k)) {
+        return Err(anyhow!("unknown setting {key:?}"));
+    }
+    let mut merged = old.clone();
+    merged.extend(patch);
+    let next: Settings = serde_json::from_value(serde_json::Value::Object(merged.clone()))
+        .map_err(|e| anyhow!("invalid settings: {e}"))?;
+    let changed: serde_json::Map<String, serde_json::Value> = merged
+        .into_iter()
+        .filter(|(k, v)| old.get(k) != Some(v))
+        .collect();
+    if changed.is_empty() {
+        return Ok(next);
+    }
+    *current = next.clone();
+    store.dirty = true;
+    events::emit(
+        app,
+        events::SETTINGS_CHANGED,
+        &SettingsChanged {
+            changed,
+            ts: crate::timeutil::now_ms(),
+        },
+    );
+    drop(store);
+    schedule_flush(app);
+    Ok(next)
+}
+
+/// Store a new app PIN hash (None removes it), written at once; `settings:changed`
+/// carries `pin_required` instead of the hash.
+pub fn set_pin_hash(app: &AppHandle, hash: Option<String>) -> Result<()> {
+    let mut store = STORE.lock().unwrap_or_else(|e| e.into_inner());
+    let current = loaded(app, &mut store);
+    let pin_required = hash.is_some();
+    current.app_pin_hash = hash;
+    store.dirty = true;
+    drop(store);
+    flush_now(app);
+    let mut changed = serde_json::Map::new();
+    changed.insert("pin_required".to_string(), pin_required.into());
+    events::emit(
+        app,
+        events::SETTINGS_CHANGED,
+        &SettingsChanged {
+            changed,
+            ts: crate::timeutil::now_ms(),
+        },
+    );
+    if STORE.lock().map_or(true, |s| s.dirty) {
+        return Err(anyhow!(
+            "the app PIN change could not be written; it only lasts until the app restarts"
+        ));
+    }
+    Ok(())
+}
+
+fn schedule_flush(app: &AppHandle) {
+    let generation = GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(FLUSH_DELAY).await;
+        if GENERATION.load(Ordering::SeqCst) == generation {
+            flush_now(&app);
+        }
+    });
+}
+
+/// Write pending changes now: on exit, and before anything reads the file. A failed
+/// write is reported as `settings:flush-failed` and retried with the next flush.
+pub fn flush_now(app: &AppHandle) {
+    let mut store = STORE.lock().unwrap_or_else(|e| e.into_inner());
+    if !store.dirty {
+        return;
+    }
+    let Some(settings) = store.settings.clone() else {
+        return;
+    };
+    let path = settings_path(app);
+    let res = match &store.damaged {
+        Some(why) => Err(anyhow!("{why}; not overwriting it")),
+        None => crate::persist::store_versioned(&path, &settings),
+    };
+    match res {
+        Ok(()) => store.dirty = false,
+        Err(e) => {
+            eprintln!("settings: {e}");
+            events::emit(
+                app,
+                events::SETTINGS_FLUSH_FAILED,
+                &FlushFailed {
+                    path,
+                    error: e.to_string(),
+                    ts: crate::timeutil::now_ms(),
+                },
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    const PIN: &str = "$argon2id$v=19$m=19456,t=2,p=1$c2FsdHNhbHQ$aGFzaGhhc2hoYXNo";
+
+    fn with_pin() -> Settings {
+        Settings {
+            app_pin_hash: Some(PIN.to_string()),
+            ..Settings::default()
+        }
+    }
+
+    #[test]
+    fn reads_stored_and_legacy_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("settings.json");
+        assert_eq!(read(&path), (Settings::default(), None));
+
+        crate::persist::store_versioned(&path, &with_pin()).unwrap();
+        assert_eq!(read(&path), (with_pin(), None));
+
+        fs::write(&path, serde_json::to_vec(&with_pin()).unwrap()).unwrap();
+        assert_eq!(read(&path), (with_pin(), None));
+    }
+
+    #[test]
+    fn corrupt_file_falls_back_to_its_backup() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("settings.json");
+        crate::persist::store_versioned(&path, &with_pin()).unwrap();
+        crate::persist::store_versioned(&path, &with_pin()).unwrap();
+        fs::write(&path, b"{\"version\":1,\"data\":{\"chain\":\"reso").unwrap();
+        assert_eq!(read(&path), (with_pin(), None));
+        assert!(!path.exists());
+        let quarantined = fs::read_dir(dir.path()).unwrap().flatten().any(|e| {
+            e.file_name()
+                .to_string_lossy()
+                .starts_with("settings.json.corrupt-")
+        });
+        assert!(quarantined);
+    }
+
+    // Without a usable backup the file may be the only copy of the PIN: it stays put and
+    // the store is marked damaged.
+    #[test]
+    fn corrupt_file_without_backup_is_left_alone() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("settings.json");
+        let torn = b"{\"version\":1,\"data\":{\"app_pin_hash\":\"$argon2id$v=19";
+        fs::write(&path, torn).unwrap();
+        let (settings, damaged) = read(&path);
+        assert_eq!(settings, Settings::default());
+        assert!(damaged.is_some_and(|why| why.contains("unreadable")));
+        assert_eq!(fs::read(&path).unwrap(), torn);
+    }
+
+    #[test]
+    fn newer_file_is_left_alone() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("settings.json");
+        let newer = br#"{"version":9,"data":{"app_pin_hash":"x"}}"#;
+        fs::write(&path, newer).unwrap();
+        let (settings, damaged) = read(&path);
+        assert_eq!(settings, Settings::default());
+        assert!(damaged.is_some_and(|why| why.contains("newer version")));
+        assert_eq!(fs::read(&path).unwrap(), newer);
+    }
+}
@@ -0,0 +1,67 @@
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, path::PathBuf};
+use tauri::AppHandle;
+
+/// A step of first-run setup (`ensure_miner_and_account`), in order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SetupStep {
+    NodeInstalled,
+    ExternalMinerInstalled,
+    AccountReady,
+}
+
+/// Progress of setup (`{app_data_dir}/setup_state.json`), so a run cut short by a crash
+/// or the app closing continues from its last finished step.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SetupState {
+    /// Finished steps with what they produced (a path) and when (unix seconds).
+    pub completed: BTreeMap<SetupStep, (String, i64)>,
+    /// Unix seconds the last run finished every step; None while one is incomplete.
+    pub finished_at: Option<i64>,
+}
+
+impl crate::persist::Versioned for SetupState {
+    const VERSION: u32 = 1;
+    const MIGRATIONS: &'static [crate::persist::Migration] = &[crate::persist::unchanged];
+}
+
+impl SetupState {
+    /// The last finished step of an incomplete run.
+    pub fn resuming_after(&self) -> Option<SetupStep> {
+        if self.finished_at.is_some() {
+            return None;
+        }
+        self.completed.keys().next_back().copied()
+    }
+}
+
+fn state_path(app: &AppHandle) -> PathBuf {
+    crate::paths::app_data_dir(app).join("setup_state.json")
+}
+
+pub fn load(app: &AppHandle) -> SetupState {
+    crate::persist::load_versioned(&state_path(app)).unwrap_or_default()
+}
+
+/// Record `step` as finished with `path`; a failed write only costs redoing the step.
+pub fn complete(app: &AppHandle, state: &mut SetupState, step: SetupStep, path: &std::path::Path) {
+    state.completed.insert(
+        step,
+        (path.display().to_string(), crate::timeutil::now_secs()),
+    );
+    save(app, state);
+}
+
+/// Every step is done; the next run is a fresh one, not a resume.
+pub fn finish(app: &AppHandle, state: &mut SetupState) {
+    state.finished_at = Some(crate::timeutil::now_secs());
+    save(app, state);
+}
+
+fn save(app: &AppHandle, state: &SetupState) {
+    if let Err(e) = crate::persist::store_versioned(&state_path(app), state) {
+        eprintln!("setup state: {e}");
+    }
+}
@@ -0,0 +1,287 @@
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tauri::AppHandle;
+
+/// Longest grace period `save_settings` accepts.
+const MAX_GRACE_SECS: u64 = 600;
+
+/// How nodes are stopped (`{app_data_dir}/shutdown.json`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ShutdownSettings {
+    /// Time a node gets to exit after SIGINT before it is killed. RocksDB on a slow
+    /// disk can take several seconds to flush.
+    pub grace_secs: u64,
+}
+
+impl crate::persist::Versioned for ShutdownSettings {
+    const VERSION: u32 = 1;
+    const MIGRATIONS: &'static [crate::persist::Migration] = &[crate::persist::unchanged];
+}
+
+impl Default for ShutdownSettings {
+    fn default() -> Self {
+        ShutdownSettings { grace_secs: 15 }
+    }
+}
+
+impl ShutdownSettings {
+    pub fn grace(&self) -> Duration {
+        Duration::from_secs(self.grace_secs)
+    }
+}
+
+/// How a stopped node actually went away; sent in `miner:exited` and kept in the
+/// chain's snapshot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum NodeExit {
+    /// Exited within the grace period after SIGINT. `code` is None when the OS reports
+    /// no exit code (ended by a signal, or an adopted node we only know by pid).
+    ExitedGracefully { code: Option<i32> },
+    /// Still running when the grace period ran out (or no graceful stop exists on this
+    /// platform); killed.
+    Killed,
+    /// Had already exited before the stop began.
+    AlreadyDead,
+}
+
+impl NodeExit {
+    /// For the log view: "exited gracefully (code 0)", "killed", ...
+    pub fn describe(&self) -> String {
+        match self {
+            NodeExit::ExitedGracefully { code: Some(c) } => format!("exited gracefully (code {c})"),
+            NodeExit::ExitedGracefully { code: None } => "exited gracefully".to_string(),
+            NodeExit::Killed => "killed".to_string(),
+            NodeExit::AlreadyDead => "had already exited".to_string(),
+        }
+    }
+}
+
+static SETTINGS: crate::persist::Cached<ShutdownSettings> =
+    crate::persist::Cached::new("shutdown.json");
+
+/// Current settings (read from disk once, then cached).
+pub fn load_settings(app: &AppHandle) -> ShutdownSettings {
+    SETTINGS.load(app)
+}
+
+pub fn save_settings(app: &AppHandle, s: &ShutdownSettings) -> Result<()> {
+    if s.grace_secs == 0 || s.grace_secs > MAX_GRACE_SECS {
+        return Err(anyhow!(
+            "the grace period must be between 1 and {MAX_GRACE_SECS} seconds"
+        ));
+    }
+    SETTINGS.store(app, s)
+}
+
+/// How often a stopping node is checked for having exited (the grace period itself is
+/// `ShutdownSettings`).
+const STOP_POLL: Duration = Duration::from_millis(100);
+/// How long to wait for the process to be reaped after a kill.
+const KILL_WAIT: Duration = Duration::from_secs(5);
+
+/// A node or external miner was still running after the stop sequence.
+#[derive(Debug, thiserror::Error)]
+#[error("{what} (pid {pid:?}) is still running after stop: {reason}")]
+pub struct StopFailed {
+    pub what: &'static str,
+    pub pid: Option<u32>,
+    pub reason: String,
+}
+
+/// Kill `child` and wait (bounded) until it has been reaped.
+pub(crate) async fn kill_and_reap(
+    child: &mut tokio::process::Child,
+    what: &'static str,
+) -> Result<()> {
+    let pid = child.id();
+    if let Err(e) = child.start_kill() {
+        // already exited and reaped: nothing left to stop
+        if matches!(child.try_wait(), Ok(Some(_))) {
+            return Ok(());
+        }
+        return Err(StopFailed {
+            what,
+            pid,
+            reason: e.to_string(),
+        }
+        .into());
+    }
+    match tokio::time::timeout(KILL_WAIT, child.wait()).await {
+        Ok(Ok(_)) => Ok(()),
+        Ok(Err(e)) => Err(StopFailed {
+            what,
+            pid,
+            reason: e.to_string(),
+        }
+        .into()),
+        Err(_) => Err(StopFailed {
+            what,
+            pid,
+            reason: format!("not reaped {}s after kill", KILL_WAIT.as_secs()),
+        }
+        .into()),
+    }
+}
+
+/// Stop our node `child`: SIGINT, poll for its exit until `grace` has passed, and only
+/// then kill it (right away where there is no SIGINT).
+pub(crate) async fn stop_child(
+    child: &mut tokio::process::Child,
+    grace: Duration,
+) -> Result<NodeExit> {
+    if let Ok(Some(_)) = child.try_wait() {
+        return Ok(NodeExit::AlreadyDead);
+    }
+    #[cfg(target_family = "unix")]
+    if let Some(pid) = child.id() {
+        use nix::sys::signal::{kill, Signal::SIGINT};
+        use nix::unistd::Pid;
+        let _ = kill(Pid::from_raw(pid as i32), SIGINT);
+        let deadline = tokio::time::Instant::now() + grace;
+        loop {
+            if let Ok(Some(status)) = child.try_wait() {
+                return Ok(NodeExit::ExitedGracefully {
+                    code: status.code(),
+                });
+            }
+            if tokio::time::Instant::now() >= deadline {
+                break;
+            }
+            tokio::time::sleep(STOP_POLL).await;
+        }
+    }
+    #[cfg(not(target_family = "unix"))]
+    let _ = grace;
+    kill_and_reap(child, "node").await?;
+    Ok(NodeExit::Killed)
+}
+
+/// Stop an adopted node by pid: SIGINT, then SIGKILL once `grace` has passed, polling
+/// until the process is gone. Its exit code is unknown (it isn't our child).
+#[cfg(target_family = "unix")]
+pub(crate) async fn stop_adopted(pid: u32, grace: Duration) -> Result<NodeExit> {
+    use nix::sys::signal::{kill, Signal};
+    use nix::unistd::Pid;
+    let gone = || crate::pidfile::process_identity(pid).is_none();
+    if gone() {
+        return Ok(NodeExit::AlreadyDead);
+    }
+    for (signal, wait, exit) in [
+        (
+            Signal::SIGINT,
+            grace,
+            NodeExit::ExitedGracefully { code: None },
+        ),
+        (Signal::SIGKILL, KILL_WAIT, NodeExit::Killed),
+    ] {
+        let _ = kill(Pid::from_raw(pid as i32), signal);
+        let deadline = tokio::time::Instant::now() + wait;
+        while tokio::time::Instant::now() < deadline {
+            if gone() {
+                return Ok(exit);
+            }
+            tokio::time::sleep(STOP_POLL).await;
+        }
+    }
+    Err(StopFailed {
+        what: "adopted node",
+        pid: Some(pid),
+        reason: "still running after SIGKILL".into(),
+    }
+    .into())
+}
+
+/// Stop an adopted node by pid with taskkill, polling until the process is gone.
+#[cfg(windows)]
+pub(crate) async fn stop_adopted(pid: u32, _grace: Duration) -> Result<NodeExit> {
+    if crate::pidfile::process_identity(pid).is_none() {
+        return Ok(NodeExit::AlreadyDead);
+    }
+    let _ = tokio::process::Command::new("taskkill")
+        .args(["/PID", &pid.to_string(), "/F"])
+        .output()
+        .await;
+    let deadline = tokio::time::Instant::now() + KILL_WAIT;
+    while tokio::time::Instant::now() < deadline {
+        if crate::pidfile::process_identity(pid).is_none() {
+            return Ok(NodeExit::Killed);
+        }
+        tokio::time::sleep(STOP_POLL).await;
+    }
+    Err(StopFailed {
+        what: "adopted node",
+        pid: Some(pid),
+        reason: "still running after taskkill".into(),
+    }
+    .into())
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    // A stand-in node: `sh -c script`, stdin closed.
+    fn fake_node(script: &str) -> tokio::process::Child {
+        tokio::process::Command::new("sh")
+            .args(["-c", script])
+            .stdin(std::process::Stdio::null())
+            .kill_on_drop(true)
+            .spawn()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn ignoring_sigint_escalates_to_kill() {
+        // exec keeps the ignored disposition, so there is one process to stop
+        let mut child = fake_node("trap '' INT; exec sleep 30");
+        // let the trap be installed before the signal is sent
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        let grace = Duration::from_millis(600);
+        let started = Instant::now();
+        let exit = stop_child(&mut child, grace).await.unwrap();
+        assert_eq!(exit, NodeExit::Killed);
+        assert!(
+            started.elapsed() >= grace,
+            "killed before the grace period ended"
+        );
+        assert!(started.elapsed() < grace + KILL_WAIT);
+        // reaped, not left as a zombie
+        assert!(matches!(child.try_wait(), Ok(Some(_))));
+    }
+
+    #[tokio::test]
+    async fn exiting_on_sigint_is_graceful() {
+        let mut child = fake_node("trap 'exit 0' INT; while :; do sleep 0.05; done");
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        let started = Instant::now();
+        let exit = stop_child(&mut child, Duration::from_secs(10))
+            .await
+            .unwrap();
+        assert_eq!(exit, NodeExit::ExitedGracefully { code: Some(0) });
+        // polled, not waited out
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn an_exited_node_is_already_dead() {
+        let mut child = fake_node("exit 3");
+        child.wait().await.unwrap();
+        let exit = stop_child(&mut child, Duration::from_secs(1))
+            .await
+            .unwrap();
+        assert_eq!(exit, NodeExit::AlreadyDead);
+    }
+
+    #[test]
+    fn describe() {
+        assert_eq!(
+            NodeExit::ExitedGracefully { code: Some(0) }.describe(),
+            "exited gracefully (code 0)"
+        );
+        assert_eq!(NodeExit::Killed.describe(), "killed");
+    }
+}
@@ -0,0 +1,131 @@
+use anyhow::{anyhow, Result};
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, fs, path::PathBuf, time::Duration};
+use tauri::AppHandle;
+
+/// How often a changed snapshot is written to disk.
+const SAVE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Last known values for one chain. Only what the UI already receives as events
+/// (status, meta, balance, hashrate, authored count, last exit); no keys or seeds ever go in here.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ChainSnapshot {
+    pub status: Option<serde_json::Value>,
+    pub meta: Option<serde_json::Value>,
+    pub balance: Option<serde_json::Value>,
+    pub hashrate: Option<f64>,
+    #[serde(default)]
+    pub authored: u64,
+    /// How the node went the last time it was stopped.
+    #[serde(default)]
+    pub last_exit: Option<crate::shutdown::NodeExit>,
+    /// Dead forks `fork.rs` found the node on.
+    #[serde(default)]
+    pub forks_detected: u32,
+}
+
+/// Contents of `{app_data_dir}/last_state.json`, painted (greyed out) on cold start.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Snapshot {
+    /// Unix seconds when this was written.
+    pub saved_at: i64,
+    pub phase: Option<serde_json::Value>,
+    pub active_chain: Option<String>,
+    pub chains: HashMap<String, ChainSnapshot>,
+}
+
+lazy_static! {
+    // (changed since the last save, values per chain)
+    static ref CURRENT: std::sync::Mutex<(bool, HashMap<String, ChainSnapshot>)> =
+        std::sync::Mutex::new((false, HashMap::new()));
+}
+
+/// Change the in-memory snapshot of `chain`; written out by the periodic task.
+pub fn update(chain: &str, f: impl FnOnce(&mut ChainSnapshot)) {
+    if let Ok(mut cur) = CURRENT.lock() {
+        f(cur.1.entry(chain.to_string()).or_default());
+        cur.0 = true;
+    }
+}
+
+/// `chain`'s values from this session, if it has any yet.
+pub fn chain(chain: &str) -> Option<ChainSnapshot> {
+    CURRENT.lock().ok()?.1.get(chain).cloned()
+}
+
+/// Merge a `miner:meta` payload (non-null fields only; the log reader sends partial ones).
+pub fn merge_meta(chain: &str, meta: &impl Serialize) {
+    let Ok(serde_json::Value::Object(new)) = serde_json::to_value(meta) else {
+        return;
+    };
+    update(chain, |c| {
+        let mut merged = match c.meta.take() {
+            Some(serde_json::Value::Object(m)) => m,
+            _ => serde_json::Map::new(),
+        };
+        for (k, v) in new {
+            if !v.is_null() {
+                merged.insert(k, v);
+            }
+        }
+        c.meta = Some(serde_json::Value::Object(merged));
+    });
+}
+
+fn snapshot_path(app: &AppHandle) -> PathBuf {
+    crate::paths::app_data_dir(app).join("last_state.json")
+}
+
+/// Write the snapshot now (atomically) and clear the changed flag.
+pub async fn save(app: &AppHandle) -> Result<()> {
+    let chains = match CURRENT.lock() {
+        Ok(mut cur) => {
+            cur.0 = false;
+            cur.1.clone()
+        }
+        Err(_) => return Err(anyhow!("snapshot state poisoned")),
+    };
+    let snap = Snapshot {
+        saved_at: crate::timeutil::now_secs(),
+        phase: serde_json::to_value(crate::phase::current().await).ok(),
+        active_chain: crate::miner::active_chain().await,
+        chains,
+    };
+    let path = snapshot_path(app);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    crate::persist::write_atomic(&path, &serde_json::to_vec_pretty(&snap)?)
+}
+
+/// The last written snapshot; None if missing or unreadable. Payloads saved with
+/// snake_case field names come back in camelCase, as they are sent now.
+pub fn load(app: &AppHandle) -> Option<Snapshot> {
+    let bytes = fs::read(snapshot_path(app)).ok()?;
+    let mut snap: Snapshot = serde_json::from_slice(&bytes).ok()?;
+    for c in snap.chains.values_mut() {
+        for v in [&mut c.status, &mut c.meta, &mut c.balance]
+            .into_iter()
+            .flatten()
+        {
+            crate::events::camel_case_keys(v);
+        }
+    }
+    Some(snap)
+}
+
+/// Background task: save every `SAVE_INTERVAL` if anything changed.
+pub fn spawn_periodic_save(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(SAVE_INTERVAL).await;
+            let dirty = CURRENT.lock().map(|c| c.0).unwrap_or(false);
+            if dirty {
+                if let Err(e) = save(&app).await {
+                    eprintln!("state snapshot: {e}");
+                }
+            }
+        }
+    });
+}
@@ -0,0 +1,389 @@
+use lazy_static::lazy_static;
+use serde::Serialize;
+use std::{
+    collections::{HashMap, VecDeque},
+    time::{Duration, Instant},
+};
+use tauri::AppHandle;
+
+use crate::{events, parse::MinerEvent};
+
+/// This many slow imports / backlog warnings...
+const PRESSURE_EVENTS: usize = 3;
+/// ...within this long is import pressure (`miner:import-pressure`, at most once per window).
+const PRESSURE_WINDOW: Duration = Duration::from_secs(120);
+/// Import rates are measured over this much recent history.
+const RATE_WINDOW: Duration = Duration::from_secs(600);
+/// Imports further apart than this mean the node wasn't running in between; the gap
+/// isn't counted as time in safe mode.
+const MAX_IMPORT_GAP: Duration = Duration::from_secs(60);
+/// Past all heavy ranges with safe mode still on for this long: recommend turning it off.
+const RECOMMEND_AFTER: Duration = Duration::from_secs(600);
+
+/// Counters for the current node session of a chain (`get_session_stats`); they start
+/// over with every node start.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionStats {
+    /// Unix seconds the node session started.
+    pub started_at: Option<i64>,
+    /// Block imports that took `parse::SLOW_IMPORT_SECS` or longer.
+    pub slow_imports: u32,
+    pub max_import_secs: Option<f64>,
+    /// Import queue backlog as last reported (null when the line had no count).
+    pub import_backlog: Option<u64>,
+    pub max_import_backlog: Option<u64>,
+    /// Times `miner:import-pressure` was sent.
+    pub pressure_episodes: u32,
+    /// Time spent importing in safe mode since the app started (safe mode restarts the
+    /// node, so this and the rates below span node sessions).
+    pub safe_mode_secs: u64,
+    /// Blocks imported per minute before safe mode was last enabled...
+    pub blocks_per_min_before_safe_mode: Option<f64>,
+    /// ...and while it is (or was last) on.
+    pub blocks_per_min_in_safe_mode: Option<f64>,
+    /// `blocks_per_min_before_safe_mode / blocks_per_min_in_safe_mode`.
+    pub safe_mode_slowdown: Option<f64>,
+    /// Authored blocks the bootnode had within `propagation::WINDOW`...
+    pub propagated_blocks: u32,
+    /// ...and those it didn't (likely orphaned).
+    pub unpropagated_blocks: u32,
+    /// Over `propagated_blocks`, from our import until the bootnode had the block.
+    pub avg_propagation_ms: Option<u64>,
+    pub max_propagation_ms: Option<u64>,
+}
+
+/// `miner:safe-mode-recommendation` payload: safe mode was set by hand and the node has
+/// been past all heavy ranges for a while. Only a suggestion; a manual choice is never
+/// changed by the app.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SafeModeRecommendation {
+    chain: String,
+    best_block: u64,
+    safe_mode_secs: u64,
+    blocks_per_min_before: Option<f64>,
+    blocks_per_min_safe: Option<f64>,
+    slowdown: Option<f64>,
+    ts: i64,
+}
+
+/// `miner:import-pressure` payload: several slow imports or backlog warnings in a short
+/// window. Heavy blocks are expected to do this; the node isn't frozen.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ImportPressure {
+    chain: String,
+    events: usize,
+    window_secs: u64,
+    max_import_secs: Option<f64>,
+    import_backlog: Option<u64>,
+    ts: i64,
+}
+
+#[derive(Default)]
+struct Tracker {
+    stats: SessionStats,
+    // slow imports / backlog warnings inside the window
+    recent: VecDeque<Instant>,
+    last_pressure: Option<Instant>,
+    propagation_total_ms: u64,
+}
+
+#[derive(Default)]
+struct SafeModeTracker {
+    // recent (when, height) imports without / with safe mode
+    normal: VecDeque<(Instant, u64)>,
+    safe: VecDeque<(Instant, u64)>,
+    // previous import and whether safe mode was on for it
+    last: Option<(Instant, bool)>,
+    secs: f64,
+    past_ranges_since: Option<Instant>,
+    recommended: bool,
+}
+
+impl SafeModeTracker {
+    fn fill(&self, stats: &mut SessionStats) {
+        stats.safe_mode_secs = self.secs as u64;
+        stats.blocks_per_min_before_safe_mode = rate(&self.normal);
+        stats.blocks_per_min_in_safe_mode = rate(&self.safe);
+        stats.safe_mode_slowdown = match (
+            stats.blocks_per_min_before_safe_mode,
+            stats.blocks_per_min_in_safe_mode,
+        ) {
+            (Some(before), Some(safe)) if safe > 0.0 => Some(before / safe),
+            _ => None,
+        };
+    }
+}
+
+lazy_static! {
+    static ref TRACKERS: std::sync::Mutex<HashMap<String, Tracker>> =
+        std::sync::Mutex::new(HashMap::new());
+    // per app session, unlike `TRACKERS`: toggling safe mode restarts the node
+    static ref SAFE_MODE: std::sync::Mutex<HashMap<String, SafeModeTracker>> =
+        std::sync::Mutex::new(HashMap::new());
+}
+
+// Blocks per minute over `samples`; None until they span a minute.
+fn rate(samples: &VecDeque<(Instant, u64)>) -> Option<f64> {
+    let (&(t0, h0), &(t1, h1)) = (samples.front()?, samples.back()?);
+    let mins = t1.duration_since(t0).as_secs_f64() / 60.0;
+    (mins >= 1.0).then(|| h1.saturating_sub(h0) as f64 / mins)
+}
+
+fn push_sample(q: &mut VecDeque<(Instant, u64)>, now: Instant, height: u64) {
+    // a lower height (repair, reorg) starts the measurement over
+    if q.back().is_some_and(|&(_, h)| height < h) {
+        q.clear();
+    }
+    q.push_back((now, height));
+    while q
+        .front()
+        .is_some_and(|&(at, _)| now.duration_since(at) > RATE_WINDOW)
+    {
+        q.pop_front();
+    }
+}
+
+/// A node for `chain` is starting: a new session.
+pub fn reset_session(chain: &str) {
+    if let Ok(mut t) = TRACKERS.lock() {
+        t.insert(
+            chain.to_string(),
+            Tracker {
+                stats: SessionStats {
+                    started_at: Some(crate::timeutil::now_secs()),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        );
+    }
+}
+
+/// `chain`'s stats so far (all empty before its first start).
+pub fn get(chain: &str) -> SessionStats {
+    let mut stats = TRACKERS
+        .lock()
+        .ok()
+        .and_then(|t| t.get(chain).map(|t| t.stats.clone()))
+        .unwrap_or_default();
+    if let Some(t) = SAFE_MODE.lock().ok().as_ref().and_then(|m| m.get(chain)) {
+        t.fill(&mut stats);
+    }
+    stats
+}
+
+/// A block import at `height` on a known chain, with safe mode on (`safe`, whether set
+/// by the app or by hand: `manual`) or off. Measures the import rate with and without it
+/// and, once a manual safe mode has been past every heavy range (`past_ranges`) for
+/// `RECOMMEND_AFTER`, sends `miner:safe-mode-recommendation` (once per stretch of safe
+/// mode). Safe mode the app turned on is turned off by the app; this never does.
+pub fn note_block(
+    app: &AppHandle,
+    chain: &str,
+    height: u64,
+    safe: bool,
+    manual: bool,
+    past_ranges: bool,
+) {
+    let now = Instant::now();
+    let recommendation = {
+        let Ok(mut all) = SAFE_MODE.lock() else {
+            return;
+        };
+        let t = all.entry(chain.to_string()).or_default();
+        match t.last {
+            Some((at, was_safe)) if was_safe && safe => {
+                let gap = now.duration_since(at);
+                if gap <= MAX_IMPORT_GAP {
+                    t.secs += gap.as_secs_f64();
+                }
+            }
+            // a new stretch of safe mode gets its own rate
+            _ if safe => t.safe.clear(),
+            _ => {}
+        }
+        t.last = Some((now, safe));
+        if safe {
+            push_sample(&mut t.safe, now, height);
+        } else {
+            // frozen while safe mode is on: that is the rate "before"
+            push_sample(&mut t.normal, now, height);
+            t.recommended = false;
+        }
+        if !(safe && past_ranges) {
+            t.past_ranges_since = None;
+        }
+        let since = *t.past_ranges_since.get_or_insert(now);
+        let due = safe
+            && manual
+            && past_ranges
+            && !t.recommended
+            && now.duration_since(since) >= RECOMMEND_AFTER;
+        if !due {
+            return;
+        }
+        t.recommended = true;
+        let mut stats = SessionStats::default();
+        t.fill(&mut stats);
+        SafeModeRecommendation {
+            chain: chain.to_string(),
+            best_block: height,
+            safe_mode_secs: stats.safe_mode_secs,
+            blocks_per_min_before: stats.blocks_per_min_before_safe_mode,
+            blocks_per_min_safe: stats.blocks_per_min_in_safe_mode,
+            slowdown: stats.safe_mode_slowdown,
+            ts: crate::timeutil::now_ms(),
+        }
+    };
+    let slowdown = recommendation
+        .slowdown
+        .map(|x| format!(", about {x:.1}x slower than without it"))
+        .unwrap_or_default();
+    crate::session_log::ui(
+        app,
+        chain,
+        format!(
+            "Past all heavy block ranges at #{height}, but safe sync (--max-blocks-per-request) is still set in your extra args{slowdown}. Consider removing it; the app won't change it for you."
+        ),
+    );
+    events::emit(app, events::MINER_SAFE_MODE_RECOMMENDATION, &recommendation);
+}
+
+/// An authored block reached the bootnode after `propagation_ms`, or didn't (None).
+pub fn note_propagation(chain: &str, propagation_ms: Option<u64>) {
+    let Ok(mut trackers) = TRACKERS.lock() else {
+        return;
+    };
+    let t = trackers.entry(chain.to_string()).or_default();
+    let Some(ms) = propagation_ms else {
+        t.stats.unpropagated_blocks += 1;
+        return;
+    };
+    t.stats.propagated_blocks += 1;
+    t.propagation_total_ms += ms;
+    t.stats.avg_propagation_ms =
+        Some(t.propagation_total_ms / u64::from(t.stats.propagated_blocks));
+    t.stats.max_propagation_ms = Some(t.stats.max_propagation_ms.unwrap_or(0).max(ms));
+}
+
+/// Count a `SlowImport`/`ImportBacklog` event (others are ignored) and announce import
+/// pressure when they cluster.
+pub fn note_import_event(app: &AppHandle, chain: &str, ev: &MinerEvent) {
+    let now = Instant::now();
+    let pressure = {
+        let Ok(mut trackers) = TRACKERS.lock() else {
+            return;
+        };
+        let t = trackers.entry(chain.to_string()).or_default();
+        match ev {
+            MinerEvent::SlowImport { seconds, .. } => {
+                t.stats.slow_imports += 1;
+                t.stats.max_import_secs =
+                    Some(t.stats.max_import_secs.unwrap_or(0.0).max(*seconds));
+            }
+            MinerEvent::ImportBacklog { queued } => {
+                t.stats.import_backlog = *queued;
+                if let Some(q) = queued {
+                    t.stats.max_import_backlog =
+                        Some(t.stats.max_import_backlog.unwrap_or(0).max(*q));
+                }
+            }
+            _ => return,
+        }
+        t.recent.push_back(now);
+        while t
+            .recent
+            .front()
+            .is_some_and(|at| now.duration_since(*at) > PRESSURE_WINDOW)
+        {
+            t.recent.pop_front();
+        }
+        let quiet = t
+            .last_pressure
+            .is_none_or(|at| now.duration_since(at) > PRESSURE_WINDOW);
+        if t.recent.len() < PRESSURE_EVENTS || !quiet {
+            return;
+        }
+        t.last_pressure = Some(now);
+        t.stats.pressure_episodes += 1;
+        ImportPressure {
+            chain: chain.to_string(),
+            events: t.recent.len(),
+            window_secs: PRESSURE_WINDOW.as_secs(),
+            max_import_secs: t.stats.max_import_secs,
+            import_backlog: t.stats.import_backlog,
+            ts: crate::timeutil::now_ms(),
+        }
+    };
+    crate::session_log::ui(
+        app,
+        chain,
+        format!(
+            "Working through heavy blocks ({} slow imports/backlog warnings in {}s); this is expected and can take a while.",
+            pressure.events, pressure.window_secs
+        ),
+    );
+    events::emit(app, events::MINER_IMPORT_PRESSURE, &pressure);
+    crate::remediation::offer(
+        app,
+        chain,
+        crate::remediation::ErrorKind::ImportPressure,
+        &format!(
+            "{} slow imports/backlog warnings in {}s",
+            pressure.events, pressure.window_secs
+        ),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn payloads_are_camel_case() {
+        let rec = SafeModeRecommendation {
+            chain: "resonance".into(),
+            best_block: 1000,
+            safe_mode_secs: 600,
+            blocks_per_min_before: Some(30.0),
+            blocks_per_min_safe: Some(20.0),
+            slowdown: Some(1.5),
+            ts: 1,
+        };
+        assert_eq!(
+            serde_json::to_value(&rec).unwrap(),
+            json!({
+                "chain": "resonance",
+                "bestBlock": 1000,
+                "safeModeSecs": 600,
+                "blocksPerMinBefore": 30.0,
+                "blocksPerMinSafe": 20.0,
+                "slowdown": 1.5,
+                "ts": 1,
+            })
+        );
+        let pressure = ImportPressure {
+            chain: "resonance".into(),
+            events: 4,
+            window_secs: 300,
+            max_import_secs: Some(45.0),
+            import_backlog: None,
+            ts: 2,
+        };
+        assert_eq!(
+            serde_json::to_value(&pressure).unwrap(),
+            json!({
+                "chain": "resonance",
+                "events": 4,
+                "windowSecs": 300,
+                "maxImportSecs": 45.0,
+                "importBacklog": null,
+                "ts": 2,
+            })
+        );
+    }
+}
@@ -0,0 +1,497 @@
+use lazy_static::lazy_static;
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+
+use crate::parse::{MinerEvent, SyncStage};
+
+/// Samples older than this are dropped; also the longest "stuck on block" time measured
+/// exactly (beyond it the elapsed time is a lower bound).
+const WINDOW_MS: i64 = 30 * 60_000;
+const MAX_SAMPLES: usize = 1024;
+/// Rates and slow imports are judged over this much recent history.
+const RECENT_MS: i64 = 60_000;
+/// The best block standing still this long while behind the target...
+const STALL_MS: i64 = 30_000;
+/// ...with the import rate below this (blocks/s) or slow imports reported, is execution.
+const LOW_BPS: f64 = 0.5;
+/// Load per CPU below this says the node isn't busy executing: it's waiting for blocks.
+const IDLE_LOAD: f64 = 0.3;
+/// A warp/state stage line is current for this long after it was logged.
+const STAGE_MS: i64 = 60_000;
+
+/// What a syncing node spends its time on (`sync_phase` in `miner:status`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncPhase {
+    /// Importing blocks as fast as they arrive (or waiting for peers to send them).
+    Downloading,
+    /// Stuck executing a heavy block; the progress bar doesn't move meanwhile.
+    Executing,
+    /// Warp sync: downloading finality proofs.
+    WarpSync,
+    /// Downloading or importing state.
+    StateSync,
+}
+
+/// The block an execution-bound node is working on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HeavyBlock {
+    pub number: u64,
+    /// Since the block before it was imported.
+    pub elapsed_secs: u64,
+}
+
+/// One observation from the node's log.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Signal {
+    /// An informant line; `syncing` is false for idle lines.
+    Status {
+        syncing: bool,
+        best: Option<u64>,
+        target: Option<u64>,
+        bps: Option<f64>,
+    },
+    /// An import at or above `parse::SLOW_IMPORT_SECS`.
+    SlowImport {
+        block: Option<u64>,
+    },
+    Stage(SyncStage),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sample {
+    /// UTC milliseconds.
+    pub at_ms: i64,
+    pub signal: Signal,
+}
+
+// An informant line out of the window.
+struct Status {
+    at_ms: i64,
+    syncing: bool,
+    best: Option<u64>,
+    target: Option<u64>,
+    bps: Option<f64>,
+}
+
+/// Classification of `samples` (oldest first) at `now_ms`; None when the node isn't
+/// syncing or hasn't said enough yet. `load_per_cpu` is None where unknown.
+pub fn classify(
+    samples: &[Sample],
+    now_ms: i64,
+    load_per_cpu: Option<f64>,
+) -> Option<(SyncPhase, Option<HeavyBlock>)> {
+    let statuses: Vec<Status> = samples
+        .iter()
+        .filter_map(|s| match s.signal {
+            Signal::Status {
+                syncing,
+                best,
+                target,
+                bps,
+            } => Some(Status {
+                at_ms: s.at_ms,
+                syncing,
+                best,
+                target,
+                bps,
+            }),
+            _ => None,
+        })
+        .collect();
+    let stage = samples.iter().rev().find_map(|s| match s.signal {
+        Signal::Stage(stage) => Some((s.at_ms, stage)),
+        _ => None,
+    });
+    // a warp/state line newer than the last informant line wins while it's fresh
+    let last = statuses.last();
+    if let Some((at, stage)) = stage {
+        let fresh = now_ms - at <= STAGE_MS && last.is_none_or(|s| s.at_ms <= at);
+        match stage {
+            SyncStage::Warp if fresh => return Some((SyncPhase::WarpSync, None)),
+            SyncStage::State if fresh => return Some((SyncPhase::StateSync, None)),
+            _ => {}
+        }
+    }
+    let last = last?;
+    if !last.syncing {
+        return None;
+    }
+    let (best, target) = (last.best?, last.target);
+    // when the best block last changed: the first status of the current run of `best`
+    let since = statuses
+        .iter()
+        .rev()
+        .take_while(|s| s.best.is_none_or(|b| b == best))
+        .last()
+        .map_or(last.at_ms, |s| s.at_ms);
+    let stalled_ms = now_ms - since;
+    let recent = |at: i64| now_ms - at <= RECENT_MS;
+    let rates: Vec<f64> = statuses
+        .iter()
+        .filter(|s| recent(s.at_ms))
+        .filter_map(|s| s.bps)
+        .collect();
+    let avg_bps = (!rates.is_empty()).then(|| rates.iter().sum::<f64>() / rates.len() as f64);
+    let slow_imports = samples
+        .iter()
+        .rev()
+        .take_while(|s| recent(s.at_ms))
+        .any(|s| matches!(s.signal, Signal::SlowImport { .. }));
+    let behind = target.is_none_or(|t| t > best);
+    let slow = slow_imports || avg_bps.is_some_and(|b| b < LOW_BPS);
+    let busy = load_per_cpu.is_none_or(|l| l >= IDLE_LOAD);
+    if behind && stalled_ms >= STALL_MS && slow && busy {
+        let heavy = HeavyBlock {
+            number: best + 1,
+            elapsed_secs: (stalled_ms / 1000) as u64,
+        };
+        return Some((SyncPhase::Executing, Some(heavy)));
+    }
+    Some((SyncPhase::Downloading, None))
+}
+
+lazy_static! {
+    static ref SAMPLES: std::sync::Mutex<HashMap<String, VecDeque<Sample>>> =
+        std::sync::Mutex::new(HashMap::new());
+    // (read at, value): the load average is read at most every 10s
+    static ref LOAD: std::sync::Mutex<Option<(i64, Option<f64>)>> = std::sync::Mutex::new(None);
+}
+
+fn push(chain: &str, signal: Signal) {
+    let now = crate::timeutil::now_ms();
+    let Ok(mut all) = SAMPLES.lock() else {
+        return;
+    };
+    let q = all.entry(chain.to_string()).or_default();
+    q.push_back(Sample { at_ms: now, signal });
+    while q.len() > MAX_SAMPLES || q.front().is_some_and(|s| now - s.at_ms > WINDOW_MS) {
+        q.pop_front();
+    }
+}
+
+// The observation a parsed node event carries, if any.
+fn signal(ev: &MinerEvent) -> Option<Signal> {
+    match ev {
+        MinerEvent::NodeStatus {
+            state,
+            best,
+            target,
+            bps,
+            ..
+        } => Some(Signal::Status {
+            syncing: state != "idle",
+            best: *best,
+            target: *target,
+            bps: *bps,
+        }),
+        MinerEvent::SlowImport { block, .. } => Some(Signal::SlowImport { block: *block }),
+        _ => None,
+    }
+}
+
+/// Record an informant line or slow import from `chain`'s node.
+pub fn note_event(chain: &str, ev: &MinerEvent) {
+    if let Some(signal) = signal(ev) {
+        push(chain, signal);
+    }
+}
+
+/// Record a warp/state sync line from `chain`'s node.
+pub fn note_line(chain: &str, line: &str) {
+    if let Some(stage) = crate::parse::sync_stage(line) {
+        push(chain, Signal::Stage(stage));
+    }
+}
+
+/// A new node session: its samples start over.
+pub fn reset_session(chain: &str) {
+    if let Ok(mut all) = SAMPLES.lock() {
+        all.remove(chain);
+    }
+}
+
+fn load_per_cpu(now: i64) -> Option<f64> {
+    let Ok(mut cached) = LOAD.lock() else {
+        return None;
+    };
+    match *cached {
+        Some((at, load)) if now - at < 10_000 => load,
+        _ => {
+            let load = crate::throttle::load_per_cpu();
+            *cached = Some((now, load));
+            load
+        }
+    }
+}
+
+/// `chain`'s sync phase for `miner:status`; None unless `is_syncing` (when known).
+pub fn current(chain: &str, is_syncing: Option<bool>) -> Option<(SyncPhase, Option<HeavyBlock>)> {
+    if is_syncing == Some(false) {
+        return None;
+    }
+    let samples: Vec<Sample> = SAMPLES.lock().ok()?.get(chain)?.iter().copied().collect();
+    let now = crate::timeutil::now_ms();
+    classify(&samples, now, load_per_cpu(now))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SEC: i64 = 1000;
+    // load per CPU of a node executing a block, and of one waiting for peers
+    const BUSY: Option<f64> = Some(1.4);
+    const IDLE: Option<f64> = Some(0.05);
+
+    fn clock(secs: i64) -> String {
+        let t = 10 * 3600 + secs;
+        format!(
+            "2025-06-12 {:02}:{:02}:{:02}",
+            t / 3600,
+            t / 60 % 60,
+            t % 60
+        )
+    }
+
+    fn syncing(secs: i64, best: u64, bps: f64) -> (i64, String) {
+        let line = format!(
+            "{} ⚙️  Syncing {bps:.1} bps, target=#24810 (8 peers), best: #{best} (0x4e1c…9a1f), \
+             finalized #{} (0x4e19…77c0), ⬇ 341.2kiB/s ⬆ 0.4kiB/s",
+            clock(secs),
+            best - 3
+        );
+        (secs, line)
+    }
+
+    fn idle(secs: i64, best: u64) -> (i64, String) {
+        let line = format!(
+            "{} 💤 Idle (9 peers), best: #{best} (0x4736…e2d0), finalized #{} (0x4733…41bb), \
+             ⬇ 3.8kiB/s ⬆ 2.2kiB/s",
+            clock(secs),
+            best - 3
+        );
+        (secs, line)
+    }
+
+    fn slow_import(secs: i64, block: u64, took: u64) -> (i64, String) {
+        let line = format!(
+            "{} WARN tokio-runtime-worker sc_service: Block import took {took}s for #{block}",
+            clock(secs)
+        );
+        (secs, line)
+    }
+
+    // "Warping, Downloading state" and the like, with the informant's usual tail.
+    fn stage(secs: i64, what: &str) -> (i64, String) {
+        let line = format!(
+            "{} ⏩ {what}, 12.40 MiB (6 peers), best: #0 (0x8e61…0c2e), finalized #0 \
+             (0x8e61…0c2e), ⬇ 812.3kiB/s ⬆ 1.2kiB/s",
+            clock(secs)
+        );
+        (secs, line)
+    }
+
+    // The samples the node's stdout handler would record for these lines (seconds into
+    // the session, line): parsed events first, then warp/state lines, as in `miner`.
+    fn replay(lines: &[(i64, String)]) -> Vec<Sample> {
+        let mut samples = Vec::new();
+        for (secs, line) in lines {
+            let at_ms = secs * SEC;
+            if let Some(signal) = crate::parse::parse_event(line).as_ref().and_then(signal) {
+                samples.push(Sample { at_ms, signal });
+            }
+            if let Some(stage) = crate::parse::sync_stage(line) {
+                samples.push(Sample {
+                    at_ms,
+                    signal: Signal::Stage(stage),
+                });
+            }
+        }
+        samples
+    }
+
+    fn phase_at(
+        lines: &[(i64, String)],
+        now: i64,
+        load: Option<f64>,
+    ) -> Option<(SyncPhase, Option<HeavyBlock>)> {
+        classify(&replay(lines), now * SEC, load)
+    }
+
+    // 45 bps from #18200, an informant line every 5s for `secs`.
+    fn fast_sync(secs: i64) -> Vec<(i64, String)> {
+        (0..=secs / 5)
+            .map(|i| syncing(i * 5, 18200 + i as u64 * 225, 45.0))
+            .collect()
+    }
+
+    #[test]
+    fn download_bound() {
+        let lines = fast_sync(120);
+        assert_eq!(
+            phase_at(&lines, 121, BUSY),
+            Some((SyncPhase::Downloading, None))
+        );
+        assert_eq!(
+            phase_at(&lines, 121, None),
+            Some((SyncPhase::Downloading, None))
+        );
+    }
+
+    // Best stuck one block short of a heavy one for ten minutes, bps at 0.0, the CPU busy.
+    #[test]
+    fn execution_bound_on_a_heavy_block() {
+        let mut lines: Vec<_> = (0..=4)
+            .map(|i| syncing(i * 5, 18290 + i as u64 * 2, 40.0))
+            .collect();
+        lines.extend((5..=125).map(|i| syncing(i * 5, 18299, 0.0)));
+        let heavy = HeavyBlock {
+            number: 18300,
+            elapsed_secs: 600,
+        };
+        assert_eq!(
+            phase_at(&lines, 625, BUSY),
+            Some((SyncPhase::Executing, Some(heavy)))
+        );
+        // the elapsed time keeps counting between informant lines
+        let (_, Some(later)) = phase_at(&lines, 628, None).unwrap() else {
+            panic!("no heavy block");
+        };
+        assert_eq!(later.elapsed_secs, 603);
+    }
+
+    // A run of heavy blocks: the informant's averaged rate still looks healthy, but the
+    // node just reported a slow import and the best block hasn't moved since.
+    #[test]
+    fn execution_bound_by_slow_imports() {
+        let mut lines: Vec<_> = (0..=4)
+            .map(|i| syncing(i * 5, 18269 + i as u64 * 5, 40.0))
+            .collect();
+        lines.push(slow_import(24, 18290, 36));
+        lines.extend((5..=12).map(|i| syncing(i * 5, 18290, 2.0)));
+        let heavy = HeavyBlock {
+            number: 18291,
+            elapsed_secs: 35,
+        };
+        assert_eq!(
+            phase_at(&lines, 60, None),
+            Some((SyncPhase::Executing, Some(heavy)))
+        );
+        // the same stall without a slow import and at that rate is just slow downloading
+        let no_slow_import: Vec<_> = lines.iter().filter(|(s, _)| *s != 24).cloned().collect();
+        assert_eq!(
+            phase_at(&no_slow_import, 60, None),
+            Some((SyncPhase::Downloading, None))
+        );
+    }
+
+    // Once the heavy block is imported the best block moves and the rate recovers.
+    #[test]
+    fn heavy_block_imported() {
+        let mut lines: Vec<_> = (0..=20).map(|i| syncing(i * 5, 18299, 0.0)).collect();
+        lines.push(slow_import(102, 18300, 101));
+        lines.extend((21..=24).map(|i| syncing(i * 5, 18300 + (i as u64 - 20) * 150, 30.0)));
+        assert_eq!(
+            phase_at(&lines[..21], 100, BUSY).map(|p| p.0),
+            Some(SyncPhase::Executing)
+        );
+        assert_eq!(
+            phase_at(&lines, 121, BUSY),
+            Some((SyncPhase::Downloading, None))
+        );
+    }
+
+    // Stuck with bps at 0.0 but the machine idle: waiting for peers to send blocks.
+    #[test]
+    fn stalled_but_idle_is_downloading() {
+        let lines: Vec<_> = (0..=24).map(|i| syncing(i * 5, 18299, 0.0)).collect();
+        assert_eq!(
+            phase_at(&lines, 121, IDLE),
+            Some((SyncPhase::Downloading, None))
+        );
+    }
+
+    #[test]
+    fn short_stall_is_downloading() {
+        let mut lines = fast_sync(60);
+        lines.extend((13..=16).map(|i| syncing(i * 5, 20900, 0.0)));
+        assert_eq!(
+            phase_at(&lines, 81, BUSY),
+            Some((SyncPhase::Downloading, None))
+        );
+    }
+
+    #[test]
+    fn warp_then_state_then_blocks() {
+        let mut lines = vec![
+            syncing(0, 3, 0.0),
+            stage(2, "Warping, Downloading finality proofs"),
+            stage(7, "Warping, Downloading finality proofs"),
+        ];
+        assert_eq!(phase_at(&lines, 8, BUSY), Some((SyncPhase::WarpSync, None)));
+
+        lines.push(stage(12, "Warping, Downloading state"));
+        assert_eq!(
+            phase_at(&lines, 13, BUSY),
+            Some((SyncPhase::StateSync, None))
+        );
+        lines.push(stage(17, "Warping, Importing state"));
+        assert_eq!(
+            phase_at(&lines, 18, BUSY),
+            Some((SyncPhase::StateSync, None))
+        );
+
+        lines.push((
+            22,
+            format!(
+                "{} Warp sync is complete (96 MiB), restarting block sync.",
+                clock(22)
+            ),
+        ));
+        lines.extend((5..=8).map(|i| syncing(i * 5 + 1, 24100 + i as u64 * 150, 30.0)));
+        assert_eq!(
+            phase_at(&lines, 42, BUSY),
+            Some((SyncPhase::Downloading, None))
+        );
+    }
+
+    #[test]
+    fn fast_sync_state_download() {
+        let lines = vec![
+            syncing(0, 3, 0.0),
+            stage(3, "State sync, Downloading state"),
+        ];
+        assert_eq!(
+            phase_at(&lines, 5, BUSY),
+            Some((SyncPhase::StateSync, None))
+        );
+    }
+
+    // A warp/state line stops counting once it's stale or an informant line follows it.
+    #[test]
+    fn stage_lines_expire() {
+        let lines = [
+            stage(0, "Warping, Downloading finality proofs"),
+            syncing(5, 120, 24.0),
+        ];
+        assert_eq!(
+            phase_at(&lines[..1], 60, BUSY),
+            Some((SyncPhase::WarpSync, None))
+        );
+        assert_eq!(phase_at(&lines[..1], 61, BUSY), None);
+        assert_eq!(
+            phase_at(&lines, 6, BUSY),
+            Some((SyncPhase::Downloading, None))
+        );
+    }
+
+    #[test]
+    fn idle_node_has_no_phase() {
+        let mut lines = fast_sync(30);
+        lines.extend((7..=10).map(|i| idle(i * 5, 24810 + i as u64)));
+        assert_eq!(phase_at(&lines, 51, BUSY), None);
+        assert_eq!(phase_at(&[], 0, BUSY), None);
+    }
+}
@@ -0,0 +1,157 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tauri::AppHandle;
+
+/// At most one ping per this many seconds.
+const PING_INTERVAL_SECS: i64 = 24 * 3600;
+
+/// The anonymous usage ping (`usage_ping` in the settings, off by default). Nothing in it
+/// identifies the user or the machine: no addresses, peer ids or hardware details. Keep
+/// it that way when adding fields.
+#[derive(Debug, Clone, Serialize)]
+struct UsagePing {
+    version: &'static str,
+    os: &'static str,
+    arch: &'static str,
+    /// A known chain's UI name; anything else (a hand-edited setting) is sent as "other".
+    chain: &'static str,
+    /// This installation has fully synced `chain` at least once.
+    synced: bool,
+}
+
+impl UsagePing {
+    fn new(chain: &str, synced: bool) -> Self {
+        UsagePing {
+            version: env!("CARGO_PKG_VERSION"),
+            os: std::env::consts::OS,
+            arch: std::env::consts::ARCH,
+            chain: crate::chains::chain_info(chain).map_or("other", |c| c.ui_name),
+            synced,
+        }
+    }
+}
+
+/// `{app_data_dir}/usage_ping.json`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct PingState {
+    /// Unix seconds of the last ping that was accepted.
+    last_sent: Option<i64>,
+}
+
+impl crate::persist::Versioned for PingState {
+    const VERSION: u32 = 1;
+    const MIGRATIONS: &'static [crate::persist::Migration] = &[crate::persist::unchanged];
+}
+
+static STATE: crate::persist::Cached<PingState> = crate::persist::Cached::new("usage_ping.json");
+
+async fn send(url: &str, ping: &UsagePing) -> Result<()> {
+    crate::net::client()
+        .post(url)
+        .json(ping)
+        .send()
+        .await?
+        .error_for_status()?;
+    Ok(())
+}
+
+/// Background task: once a day, if the user opted in (`usage_ping`) and an endpoint is
+/// set (`usage_ping_url`), POST a `UsagePing` for the selected chain.
+pub fn spawn_daily_ping(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(Duration::from_secs(60)).await;
+        loop {
+            let settings = crate::settings::get(&app);
+            let url = settings.usage_ping_url.trim().to_string();
+            let mut state = STATE.load(&app);
+            let now = crate::timeutil::now_secs();
+            let due = state
+                .last_sent
+                .is_none_or(|t| now - t >= PING_INTERVAL_SECS);
+            if settings.usage_ping && url.starts_with("https://") && due {
+                let synced = crate::milestones::get(&app, &settings.chain)
+                    .first_synced_at
+                    .is_some();
+                let ping = UsagePing::new(&settings.chain, synced);
+                match send(&url, &ping).await {
+                    Ok(()) => {
+                        state.last_sent = Some(now);
+                        if let Err(e) = STATE.store(&app, &state) {
+                            eprintln!("usage ping: {e}");
+                        }
+                    }
+                    Err(e) => eprintln!("usage ping failed: {e}"),
+                }
+            }
+            // re-evaluated hourly so opting in or out takes effect without a restart
+            tokio::time::sleep(Duration::from_secs(60 * 60)).await;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use regex::Regex;
+
+    // What a ping must never carry: an SS58 address or other long base58 run, a libp2p
+    // peer id, a filesystem path, or the user's name.
+    fn assert_anonymous(body: &str) {
+        let base58 = Regex::new(r"[1-9A-HJ-NP-Za-km-z]{32,}").unwrap();
+        assert!(!base58.is_match(body), "address-like value in {body}");
+        assert!(
+            !body.contains("12D3KooW") && !body.contains("Qm"),
+            "peer id in {body}"
+        );
+        assert!(
+            !body.contains('/') && !body.contains('\\'),
+            "path in {body}"
+        );
+        for var in ["USER", "USERNAME", "HOSTNAME", "COMPUTERNAME"] {
+            if let Ok(v) = std::env::var(var) {
+                if v.len() > 2 {
+                    assert!(!body.contains(&v), "${var} in {body}");
+                }
+            }
+        }
+    }
+
+    fn body(ping: &UsagePing) -> String {
+        serde_json::to_string(ping).unwrap()
+    }
+
+    #[test]
+    fn ping_has_only_the_documented_fields() {
+        let v = serde_json::to_value(UsagePing::new("resonance", true)).unwrap();
+        let mut keys: Vec<&str> = v.as_object().unwrap().keys().map(|k| k.as_str()).collect();
+        keys.sort();
+        assert_eq!(keys, ["arch", "chain", "os", "synced", "version"]);
+        assert_eq!(v["chain"], "resonance");
+        assert_eq!(v["synced"], true);
+    }
+
+    #[test]
+    fn ping_carries_no_identifiers() {
+        for c in crate::chains::CHAINS {
+            for synced in [false, true] {
+                assert_anonymous(&body(&UsagePing::new(c.ui_name, synced)));
+            }
+        }
+    }
+
+    #[test]
+    fn hand_edited_chain_is_not_sent() {
+        for chain in [
+            "qzpFUhjXpZnRCbNr9LW5sAkWmTus52Ffcf31MBpig82u1JArX",
+            "12D3KooWLmqsJ8nvLp1GxDUJQiyqJbG1hsmGNUNsntfKoB4aB4dd",
+            "/home/alice/.config/quantus/chain-spec.json",
+            r"C:\Users\alice\chain-spec.json",
+        ] {
+            let ping = UsagePing::new(chain, false);
+            assert_eq!(ping.chain, "other");
+            assert_anonymous(&body(&ping));
+        }
+    }
+}
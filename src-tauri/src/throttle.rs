@@ -0,0 +1,297 @@
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashSet, fs, path::PathBuf, time::Duration};
+use tauri::AppHandle;
+
+use crate::events;
+
+/// Thermal/load throttle for the external miner (`{app_data_dir}/throttle.json`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ThrottleSettings {
+    /// Off by default.
+    pub enabled: bool,
+    /// Step cores down above this CPU temperature (°C)...
+    pub max_temp_c: f64,
+    /// ...and back up below this one.
+    pub resume_temp_c: f64,
+    /// Without a temperature sensor: 1-minute load average per CPU to step down above...
+    pub max_load_per_cpu: f64,
+    /// ...and to step back up below.
+    pub resume_load_per_cpu: f64,
+    /// Seconds between samples; at most one adjustment per sample.
+    pub interval_secs: u64,
+    /// Never go below this many cores.
+    pub min_cores: usize,
+    /// Still too hot at `min_cores`: pause mining (external miner only) until it cools.
+    pub pause_at_min_cores: bool,
+}
+
+impl crate::persist::Versioned for ThrottleSettings {
+    const VERSION: u32 = 1;
+    const MIGRATIONS: &'static [crate::persist::Migration] = &[crate::persist::unchanged];
+}
+
+impl Default for ThrottleSettings {
+    fn default() -> Self {
+        ThrottleSettings {
+            enabled: false,
+            max_temp_c: 85.0,
+            resume_temp_c: 75.0,
+            max_load_per_cpu: 1.0,
+            resume_load_per_cpu: 0.8,
+            interval_secs: 15,
+            min_cores: 1,
+            pause_at_min_cores: true,
+        }
+    }
+}
+
+/// What this machine lets the throttle measure.
+#[derive(Debug, Clone, Serialize)]
+pub struct ThrottleCapability {
+    pub temperature: bool,
+    pub load: bool,
+    /// "temperature" | "load" | "unavailable"
+    pub policy: &'static str,
+}
+
+static SETTINGS: crate::persist::Cached<ThrottleSettings> =
+    crate::persist::Cached::new("throttle.json");
+
+pub fn load_settings(app: &AppHandle) -> ThrottleSettings {
+    SETTINGS.load(app)
+}
+
+pub fn save_settings(app: &AppHandle, s: &ThrottleSettings) -> Result<()> {
+    if s.resume_temp_c >= s.max_temp_c || s.resume_load_per_cpu >= s.max_load_per_cpu {
+        return Err(anyhow!("resume thresholds must be below the ceilings"));
+    }
+    if s.interval_secs == 0 || s.min_cores == 0 {
+        return Err(anyhow!("interval and minimum cores must be at least 1"));
+    }
+    SETTINGS.store(app, s)
+}
+
+/// Hottest CPU sensor in °C (hwmon coretemp/k10temp/..., else a CPU thermal zone).
+#[cfg(target_os = "linux")]
+pub fn cpu_temp_c() -> Option<f64> {
+    const CPU_HWMON: &[&str] = &[
+        "coretemp",
+        "k10temp",
+        "zenpower",
+        "cpu_thermal",
+        "soc_thermal",
+    ];
+    let read_milli = |p: PathBuf| -> Option<f64> {
+        let v: f64 = fs::read_to_string(p).ok()?.trim().parse().ok()?;
+        Some(v / 1000.0)
+    };
+    let mut hottest: Option<f64> = None;
+    for dir in fs::read_dir("/sys/class/hwmon").ok()?.flatten() {
+        let dir = dir.path();
+        let name = fs::read_to_string(dir.join("name")).unwrap_or_default();
+        if !CPU_HWMON.contains(&name.trim()) {
+            continue;
+        }
+        for f in fs::read_dir(&dir).into_iter().flatten().flatten() {
+            let fname = f.file_name().to_string_lossy().to_string();
+            if fname.starts_with("temp") && fname.ends_with("_input") {
+                if let Some(t) = read_milli(f.path()) {
+                    hottest = Some(hottest.map_or(t, |h: f64| h.max(t)));
+                }
+            }
+        }
+    }
+    if hottest.is_some() {
+        return hottest;
+    }
+    for zone in fs::read_dir("/sys/class/thermal").ok()?.flatten() {
+        let kind = fs::read_to_string(zone.path().join("type")).unwrap_or_default();
+        let kind = kind.trim().to_ascii_lowercase();
+        if kind.contains("cpu") || kind.contains("x86_pkg_temp") {
+            if let Some(t) = read_milli(zone.path().join("temp")) {
+                hottest = Some(hottest.map_or(t, |h: f64| h.max(t)));
+            }
+        }
+    }
+    hottest
+}
+
+/// CPU temperature isn't readable here without extra privileges or drivers.
+#[cfg(not(target_os = "linux"))]
+pub fn cpu_temp_c() -> Option<f64> {
+    None
+}
+
+/// 1-minute load average divided by the CPU count.
+#[cfg(target_os = "linux")]
+pub fn load_per_cpu() -> Option<f64> {
+    let s = fs::read_to_string("/proc/loadavg").ok()?;
+    let one: f64 = s.split_whitespace().next()?.parse().ok()?;
+    Some(one / num_cpus::get().max(1) as f64)
+}
+
+/// 1-minute load average divided by the CPU count.
+#[cfg(target_os = "macos")]
+pub fn load_per_cpu() -> Option<f64> {
+    // "{ 1.23 1.10 1.00 }"
+    let out = std::process::Command::new("sysctl")
+        .args(["-n", "vm.loadavg"])
+        .output()
+        .ok()?;
+    let text = String::from_utf8_lossy(&out.stdout);
+    let one: f64 = text.split_whitespace().find(|t| *t != "{")?.parse().ok()?;
+    Some(one / num_cpus::get().max(1) as f64)
+}
+
+/// Windows has no load average.
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+pub fn load_per_cpu() -> Option<f64> {
+    None
+}
+
+pub fn capability() -> ThrottleCapability {
+    let temperature = cpu_temp_c().is_some();
+    let load = load_per_cpu().is_some();
+    ThrottleCapability {
+        temperature,
+        load,
+        policy: if temperature {
+            "temperature"
+        } else if load {
+            "load"
+        } else {
+            "unavailable"
+        },
+    }
+}
+
+// A user start/stop/restart isn't in flight (pausing would queue behind it).
+fn restart_idle() -> bool {
+    crate::restart::in_flight().is_none()
+}
+
+// Too hot/loaded (Some(true)), cool enough to step up (Some(false)), or in between.
+fn verdict(s: &ThrottleSettings, temp: Option<f64>, load: Option<f64>) -> Option<bool> {
+    match (temp, load) {
+        (Some(t), _) if t >= s.max_temp_c => Some(true),
+        (Some(t), _) if t <= s.resume_temp_c => Some(false),
+        (Some(_), _) => None,
+        (None, Some(l)) if l >= s.max_load_per_cpu => Some(true),
+        (None, Some(l)) if l <= s.resume_load_per_cpu => Some(false),
+        _ => None,
+    }
+}
+
+/// Background task: every `interval_secs`, step each running external miner one core
+/// down when too hot (or loaded) and one back up, to its configured count, once cool.
+/// At `min_cores` and still hot it pauses mining (`pause_at_min_cores`) and resumes it
+/// once cool; pauses it didn't make are left alone. Each change is emitted as
+/// `miner:throttle`.
+pub fn spawn(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        // chains this task paused
+        let mut paused_here: HashSet<String> = HashSet::new();
+        loop {
+            let settings = load_settings(&app);
+            tokio::time::sleep(Duration::from_secs(settings.interval_secs.max(1))).await;
+            if !settings.enabled {
+                continue;
+            }
+            let temp = cpu_temp_c();
+            let load = load_per_cpu();
+            let Some(hot) = verdict(&settings, temp, load) else {
+                continue;
+            };
+            for chain in crate::miner::running_chains().await {
+                if paused_here.contains(&chain) {
+                    if crate::miner::paused(&chain).await.is_none() {
+                        // resumed or restarted by the user meanwhile
+                        paused_here.remove(&chain);
+                    } else if !hot {
+                        let res =
+                            crate::miner::resume_mining(app.clone(), chain.clone(), "throttle")
+                                .await;
+                        if res.is_ok() {
+                            paused_here.remove(&chain);
+                        }
+                        events::emit(
+                            &app,
+                            events::MINER_THROTTLE,
+                            &serde_json::json!({
+                                "chain": chain,
+                                "from": 0,
+                                "to": settings.min_cores,
+                                "reason": "cooled",
+                                "resumed": true,
+                                "policy": if temp.is_some() { "temperature" } else { "load" },
+                                "tempC": temp,
+                                "loadPerCpu": load,
+                                "error": res.err().map(|e| e.to_string()),
+                            }),
+                        );
+                    }
+                    continue;
+                }
+                let Some((cores, _)) = crate::miner::external_miner(&chain).await else {
+                    continue;
+                };
+                // as clamped at start (1..available-1)
+                let configured = crate::miner::last_cfg(&chain)
+                    .await
+                    .and_then(|c| c.external_num_cores)
+                    .unwrap_or(cores)
+                    .min(num_cpus::get().saturating_sub(1).max(1));
+                let target = if hot {
+                    cores.saturating_sub(1).max(settings.min_cores)
+                } else {
+                    (cores + 1).min(configured)
+                };
+                if target == cores {
+                    if hot && settings.pause_at_min_cores && restart_idle() {
+                        let res =
+                            crate::miner::pause_mining(app.clone(), chain.clone(), "throttle")
+                                .await;
+                        if res.is_ok() {
+                            paused_here.insert(chain.clone());
+                        }
+                        events::emit(
+                            &app,
+                            events::MINER_THROTTLE,
+                            &serde_json::json!({
+                                "chain": chain,
+                                "from": cores,
+                                "to": 0,
+                                "configured": configured,
+                                "reason": "hot",
+                                "paused": true,
+                                "policy": if temp.is_some() { "temperature" } else { "load" },
+                                "tempC": temp,
+                                "loadPerCpu": load,
+                                "error": res.err().map(|e| e.to_string()),
+                            }),
+                        );
+                    }
+                    continue;
+                }
+                let res = crate::miner::set_external_cores(&app, &chain, target).await;
+                events::emit(
+                    &app,
+                    events::MINER_THROTTLE,
+                    &serde_json::json!({
+                        "chain": chain,
+                        "from": cores,
+                        "to": target,
+                        "configured": configured,
+                        "reason": if hot { "hot" } else { "cooled" },
+                        "policy": if temp.is_some() { "temperature" } else { "load" },
+                        "tempC": temp,
+                        "loadPerCpu": load,
+                        "error": res.err().map(|e| e.to_string()),
+                    }),
+                );
+            }
+        }
+    });
+}
@@ -0,0 +1,232 @@
+use anyhow::{anyhow, Result};
+use lazy_static::lazy_static;
+use serde::Serialize;
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+    time::Duration,
+};
+use tauri::AppHandle;
+
+use crate::events;
+
+/// Longest `stop_after_secs` accepted.
+const MAX_SECS: u64 = 7 * 24 * 3600;
+/// The timer re-reads the clock at least this often, so a suspended machine or a clock
+/// change stops the node on time rather than a full sleep later.
+const RECHECK: Duration = Duration::from_secs(30);
+/// A stop that found another operation (a safe-mode restart, say) on the chain is
+/// retried this often...
+const STOP_RETRY: Duration = Duration::from_secs(5);
+/// ...this many times.
+const STOP_ATTEMPTS: u32 = 12;
+
+/// A one-shot "stop at" armed by `start_miner` with `stop_after_secs` (see
+/// `get_miner_state`). Wall-clock based: restarts of the node (safe mode, adaptive,
+/// fork recovery) don't move it.
+#[derive(Debug, Clone, Serialize)]
+pub struct TimedStop {
+    pub chain: String,
+    /// UTC milliseconds it was armed...
+    pub armed_at: i64,
+    /// ...and when the node is stopped.
+    pub stop_at: i64,
+    pub remaining_secs: u64,
+}
+
+/// `miner:timed-stop` payload: the timer fired and the node was stopped (or `error`
+/// says why not), with the session's numbers as they were right before.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TimedStopped {
+    chain: String,
+    armed_at: i64,
+    stop_at: i64,
+    /// Blocks authored this node session.
+    authored_blocks: u64,
+    stats: crate::stats::SessionStats,
+    stopped: bool,
+    error: Option<String>,
+    ts: i64,
+}
+
+struct Armed {
+    armed_at: i64,
+    stop_at: i64,
+    id: u64,
+}
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+lazy_static! {
+    static ref ARMED: Mutex<HashMap<String, Armed>> = Mutex::new(HashMap::new());
+}
+
+/// Checked by `start_miner` before it starts anything.
+pub fn validate(secs: u64) -> Result<()> {
+    if secs == 0 || secs > MAX_SECS {
+        return Err(anyhow!(
+            "stop_after_secs must be between 1 and {MAX_SECS} (7 days)"
+        ));
+    }
+    Ok(())
+}
+
+/// Stop `chain`'s node `secs` from now, replacing a timer already armed for it.
+pub fn arm(app: &AppHandle, chain: &str, secs: u64) -> Result<TimedStop> {
+    validate(secs)?;
+    let armed_at = crate::timeutil::now_ms();
+    let stop_at = armed_at + (secs * 1000) as i64;
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    let armed = Armed {
+        armed_at,
+        stop_at,
+        id,
+    };
+    let timer = view(chain, &armed);
+    ARMED
+        .lock()
+        .map_err(|_| anyhow!("timed stop state poisoned"))?
+        .insert(chain.to_string(), armed);
+    crate::session_log::audit(chain, "timed-stop-armed", &format!("in {secs} s"));
+    let (app, chain) = (app.clone(), chain.to_string());
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let left = stop_at - crate::timeutil::now_ms();
+            if left <= 0 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(left as u64).min(RECHECK)).await;
+            if !is_current(&chain, id) {
+                return;
+            }
+        }
+        fire(&app, &chain, id).await;
+    });
+    Ok(timer)
+}
+
+fn is_current(chain: &str, id: u64) -> bool {
+    ARMED
+        .lock()
+        .is_ok_and(|a| a.get(chain).is_some_and(|a| a.id == id))
+}
+
+/// Disarm `chain`'s timer; false when none was armed.
+pub fn cancel(chain: &str) -> bool {
+    let removed = ARMED.lock().ok().and_then(|mut a| a.remove(chain));
+    if removed.is_some() {
+        crate::session_log::audit(chain, "timed-stop-cancelled", "");
+    }
+    removed.is_some()
+}
+
+/// Every armed timer, by chain.
+pub fn all() -> Vec<TimedStop> {
+    let Ok(armed) = ARMED.lock() else {
+        return Vec::new();
+    };
+    let mut out: Vec<TimedStop> = armed.iter().map(|(c, a)| view(c, a)).collect();
+    out.sort_by(|a, b| a.chain.cmp(&b.chain));
+    out
+}
+
+fn view(chain: &str, a: &Armed) -> TimedStop {
+    let left = (a.stop_at - crate::timeutil::now_ms()).max(0) as u64;
+    TimedStop {
+        chain: chain.to_string(),
+        armed_at: a.armed_at,
+        stop_at: a.stop_at,
+        remaining_secs: left.div_ceil(1000),
+    }
+}
+
+async fn fire(app: &AppHandle, chain: &str, id: u64) {
+    let armed = {
+        let Ok(mut armed) = ARMED.lock() else {
+            return;
+        };
+        if armed.get(chain).is_none_or(|a| a.id != id) {
+            return;
+        }
+        armed.remove(chain)
+    };
+    let Some(armed) = armed else {
+        return;
+    };
+    let authored_blocks = crate::authorship::session_count(chain).await;
+    let stats = crate::stats::get(chain);
+    let (stopped, error) = if crate::miner::is_chain_alive(chain).await {
+        crate::session_log::ui(app, chain, "Timed stop: stopping the node");
+        stop(app, chain).await
+    } else {
+        (false, Some("the node wasn't running".to_string()))
+    };
+    crate::session_log::audit(
+        chain,
+        "timed-stop-fired",
+        &match &error {
+            None => "node stopped".to_string(),
+            Some(e) => e.clone(),
+        },
+    );
+    events::emit(
+        app,
+        events::MINER_TIMED_STOP,
+        &TimedStopped {
+            chain: chain.to_string(),
+            armed_at: armed.armed_at,
+            stop_at: armed.stop_at,
+            authored_blocks,
+            stats,
+            stopped,
+            error,
+            ts: crate::timeutil::now_ms(),
+        },
+    );
+}
+
+// `stop_miner` returns early while another operation holds the chain; wait that out.
+async fn stop(app: &AppHandle, chain: &str) -> (bool, Option<String>) {
+    for _ in 0..STOP_ATTEMPTS {
+        if let Err(e) = crate::commands::stop_miner(app.clone(), Some(chain.to_string())).await {
+            return (false, Some(e));
+        }
+        if !crate::miner::is_chain_alive(chain).await {
+            return (true, None);
+        }
+        tokio::time::sleep(STOP_RETRY).await;
+    }
+    (
+        false,
+        Some("another operation kept the node running".to_string()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn payload_is_camel_case() {
+        let t = TimedStopped {
+            chain: "resonance".into(),
+            armed_at: 1,
+            stop_at: 2,
+            authored_blocks: 3,
+            stats: crate::stats::SessionStats::default(),
+            stopped: true,
+            error: None,
+            ts: 4,
+        };
+        let v = serde_json::to_value(&t).unwrap();
+        assert_eq!(v["armedAt"], 1);
+        assert_eq!(v["stopAt"], 2);
+        assert_eq!(v["authoredBlocks"], 3);
+        assert_eq!(v["stats"]["slowImports"], 0);
+        assert!(v.get("armed_at").is_none());
+    }
+}
@@ -0,0 +1,77 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use time::{format_description::well_known::Rfc3339, OffsetDateTime};
+
+// Per-session counter for file names, so two files named in the same second differ.
+static FILE_SEQ: AtomicU64 = AtomicU64::new(0);
+
+/// Timestamp for events sent to the UI: UTC milliseconds since the Unix epoch.
+pub fn now_ms() -> i64 {
+    (OffsetDateTime::now_utc().unix_timestamp_nanos() / 1_000_000) as i64
+}
+
+/// Unix seconds, for files we persist (histories, markers, audit log).
+pub fn now_secs() -> i64 {
+    OffsetDateTime::now_utc().unix_timestamp()
+}
+
+/// RFC 3339 UTC, for lines in log files.
+pub fn rfc3339(ts: OffsetDateTime) -> String {
+    ts.format(&Rfc3339).unwrap_or_default()
+}
+
+/// `<yyyymmdd>T<hhmmss>Z-<seq>` for file names: digits only (no ':' or locale), and
+/// unique within this app run even when called twice in the same second.
+pub fn file_stamp() -> String {
+    let t = OffsetDateTime::now_utc();
+    let seq = FILE_SEQ.fetch_add(1, Ordering::Relaxed);
+    format!(
+        "{:04}{:02}{:02}T{:02}{:02}{:02}Z-{seq:04}",
+        t.year(),
+        u8::from(t.month()),
+        t.day(),
+        t.hour(),
+        t.minute(),
+        t.second()
+    )
+}
+
+/// UTC milliseconds of a `rfc3339` timestamp ("2025-01-01T12:00:00.123Z" or with an
+/// offset); None when it isn't one.
+pub fn parse_rfc3339_ms(s: &str) -> Option<i64> {
+    let num = |r: std::ops::Range<usize>| s.get(r)?.parse::<u32>().ok();
+    let b = s.as_bytes();
+    if b.len() < 20 || b[4] != b'-' || b[7] != b'-' || b[10] != b'T' || b[13] != b':' {
+        return None;
+    }
+    let month = time::Month::try_from(num(5..7)? as u8).ok()?;
+    let date = time::Date::from_calendar_date(num(0..4)? as i32, month, num(8..10)? as u8).ok()?;
+    let mut rest = &s[19..];
+    let mut nanos = 0u32;
+    if let Some(frac) = rest.strip_prefix('.') {
+        let digits = frac.len() - frac.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+        let padded = format!("{:0<9}", &frac[..digits.min(9)]);
+        nanos = padded.parse().ok()?;
+        rest = &frac[digits..];
+    }
+    let t = time::Time::from_hms_nano(
+        num(11..13)? as u8,
+        num(14..16)? as u8,
+        num(17..19)? as u8,
+        nanos,
+    )
+    .ok()?;
+    let offset_secs = match rest {
+        "Z" | "z" => 0,
+        _ => {
+            let sign = match rest.as_bytes().first()? {
+                b'+' => 1,
+                b'-' => -1,
+                _ => return None,
+            };
+            let (h, m) = rest[1..].split_once(':')?;
+            sign * (h.parse::<i64>().ok()? * 3600 + m.parse::<i64>().ok()? * 60)
+        }
+    };
+    let utc = time::PrimitiveDateTime::new(date, t).assume_utc();
+    Some((utc.unix_timestamp_nanos() / 1_000_000) as i64 - offset_secs * 1000)
+}
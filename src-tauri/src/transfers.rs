@@ -0,0 +1,100 @@
+use lazy_static::lazy_static;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet, VecDeque};
+use tauri::AppHandle;
+
+use crate::{events, rpc::Transfer};
+
+/// Newest transfers read after a balance change; more than this many between two
+/// balance refreshes only show up in `query_transfers`.
+const DELTA_PAGE: u32 = 25;
+/// Transfers remembered per address so a later poll doesn't announce them again.
+const SEEN_CAPACITY: usize = 500;
+/// Indexed timestamps come from block time; a transfer this much older than the previous
+/// balance read still counts as new (indexer lag, clock skew).
+const SLACK_MS: i64 = 120_000;
+
+/// `miner:transfer` payload: a transfer the balance watcher hadn't seen before.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TransferSeen {
+    chain: String,
+    address: String,
+    transfer: Transfer,
+    ts: i64,
+}
+
+#[derive(Default)]
+struct Seen {
+    keys: HashSet<String>,
+    order: VecDeque<String>,
+}
+
+impl Seen {
+    // True when `key` is new.
+    fn insert(&mut self, key: String) -> bool {
+        if !self.keys.insert(key.clone()) {
+            return false;
+        }
+        self.order.push_back(key);
+        if self.order.len() > SEEN_CAPACITY {
+            if let Some(old) = self.order.pop_front() {
+                self.keys.remove(&old);
+            }
+        }
+        true
+    }
+}
+
+lazy_static! {
+    // keyed by (chain, address)
+    static ref SEEN: std::sync::Mutex<HashMap<(String, String), Seen>> =
+        std::sync::Mutex::new(HashMap::new());
+}
+
+// The extrinsic hash, or the indexer's id for transfers without one.
+fn dedup_key(t: &Transfer) -> String {
+    t.extrinsic_hash.clone().unwrap_or_else(|| t.id.clone())
+}
+
+/// The balance cache saw `address`'s balance change since it last read it at
+/// `previous_ms`: announce the transfers behind it with `miner:transfer`, oldest first.
+/// Chains without an indexer have no transfers to show; the balance event is all.
+pub fn on_balance_change(app: &AppHandle, chain: &str, address: &str, previous_ms: i64) {
+    let (app, chain, address) = (app.clone(), chain.to_string(), address.to_string());
+    tauri::async_runtime::spawn(async move {
+        let page = match crate::rpc::fetch_transfers(&chain, &address, None, DELTA_PAGE).await {
+            Ok(p) if p.indexed => p,
+            Ok(_) => return,
+            Err(e) => {
+                eprintln!("transfers: {e}");
+                return;
+            }
+        };
+        let fresh: Vec<Transfer> = {
+            let Ok(mut seen) = SEEN.lock() else {
+                return;
+            };
+            let seen = seen.entry((chain.clone(), address.clone())).or_default();
+            page.transfers
+                .into_iter()
+                .rev()
+                .filter(|t| seen.insert(dedup_key(t)))
+                // history from before the previous read was already reflected in it
+                .filter(|t| t.timestamp.is_none_or(|ts| ts >= previous_ms - SLACK_MS))
+                .collect()
+        };
+        for transfer in fresh {
+            events::emit(
+                &app,
+                events::MINER_TRANSFER,
+                &TransferSeen {
+                    chain: chain.clone(),
+                    address: address.clone(),
+                    transfer,
+                    ts: crate::timeutil::now_ms(),
+                },
+            );
+        }
+    });
+}
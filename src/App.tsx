@@ -10,14 +10,117 @@ import {
   onMinerMeta,
   onMinerState,
   onMinerLogFile,
+  onDeepLink,
+  frontendReady,
+  setFileLogging,
+  onMinerPhase,
+  pauseMining,
+  resumeMining,
+  getMinerState,
+  setEventSeverity,
+  getInstallDir,
+  setInstallDir,
+  onMinerQuarantined,
+  onMinerStartFailed,
+  onMinerStartDegraded,
+  onChainMismatch,
+  onExternalCrashloop,
+  resumeLastSession,
+  onSetupProgress,
+  onSecurityWarning,
+  onMinerAuthored,
+  onRuntimeUpgraded,
+  getAuthoredBlocks,
+  getLastSnapshot,
+  getNodeStatusNow,
+  openPath,
+  type PathKind,
+  restartWithSafeRpc,
+  type SecurityWarning,
+  onDuplicateIdentity,
+  onRepairProgress,
+  onImportProgress,
+  importChainData,
+  type ImportProgress,
+  runBenchmark,
+  cancelBenchmark,
+  onBenchmarkProgress,
+  type BenchResult,
+  type BenchmarkProgress,
+  getOrphanedNodes,
+  adoptOrphanedNode,
+  dismissOrphanedNode,
+  type OrphanedNode,
+  type RepairProgress,
+  regenerateNodeKey,
+  type DuplicateIdentity,
+  onAccountCorrupt,
+  confirmAccountReset,
+  retryAfterUnquarantine,
+  type QuarantineInfo,
+  type SetupError,
+  setupErrorMessage,
+  checkAppUpdate,
+  getAppUpdateSettings,
+  getThrottleSettings,
+  setThrottleSettings,
+  getAdaptiveSafeMode,
+  setAdaptiveSafeMode,
+  type AdaptiveSafeModeSettings,
+  getSystemRequirements,
+  onThrottle,
+  type ThrottleSettings,
+  setAppUpdateAutoCheck,
+  onAppUpdateAvailable,
+  type AppUpdateInfo,
+  type BalanceView,
+  type EventSeverity,
+  type MinerPhase,
   type MinerStatus,
   type MinerMeta,
+  planRepair,
+  repairMiner,
+  cancelOperation,
+  onOperationCancelled,
+  onAccountNotBackedUp,
+  markAccountBackedUp,
+  type AccountNotBackedUp,
+  onSyncComplete,
+  getNodeReportMarkdown,
+  onForkDetected,
+  getAccountAddress,
+  onImportPressure,
+  onSafeModeRecommendation,
+  onBalance,
+  onLogThrottled,
+  onLogMoveProgress,
+  onRemediation,
+  runRemediation,
+  type Remediation,
+  onBlockPropagated,
+  getPeerMetrics,
+  onTimedStop,
+  listChains,
+  onTransfer,
+  onMigratedData,
+  onSafeModeCountdown,
+  confirmSafeMode,
+  type SafeModeCountdown,
+  onBinaryModified,
+  withAppPin,
+  type GuardedError,
+  onRewardsAddressChanged,
+  getSettings,
 } from "./api";
-import { revealItemInDir } from "@tauri-apps/plugin-opener";
+import { openUrl, revealItemInDir } from "@tauri-apps/plugin-opener";
 import { celebrate } from "./celebrate";
 
 type Chain = "resonance" | "heisenberg" | "quantus";
 
+// For withAppPin: ask again after a wrong PIN, with the backend's message.
+const askPin = (e: GuardedError) =>
+  window.prompt(e.kind === "pin_invalid" ? `${e.message}. App PIN:` : "App PIN:");
+
 export default function App() {
   const [account, setAccount] = useState<any>(null);
   const [chain, setChain] = useState<Chain>(() => {
@@ -38,6 +141,8 @@ export default function App() {
   const [balance, setBalance] = useState<string>("—");
   const [balanceSymbol, setBalanceSymbol] = useState<string>("RES");
   const [balanceDecimals, setBalanceDecimals] = useState<number>(12);
+  // formatted by the backend (exact, rounded); formatBalanceRaw is the fallback
+  const [balanceFormatted, setBalanceFormatted] = useState<string | null>(null);
 
   // format on-chain integer balance into human-friendly string with symbol
   function formatBalanceRaw(
@@ -62,12 +167,52 @@ export default function App() {
   }
   const [minerPath, setMinerPath] = useState<string>("");
   const [plannedCmd, setPlannedCmd] = useState<string>("");
+  const [quarantine, setQuarantine] = useState<QuarantineInfo | null>(null);
+  const [setupError, setSetupError] = useState<SetupError | null>(null);
+  const [securityWarning, setSecurityWarning] =
+    useState<SecurityWarning | null>(null);
+  const [repairProgress, setRepairProgress] = useState<RepairProgress | null>(
+    null,
+  );
+  const [backupWarning, setBackupWarning] = useState<AccountNotBackedUp | null>(
+    null,
+  );
+  // id of the running repair, for its Cancel button
+  const [repairOp, setRepairOp] = useState<string | null>(null);
+  const [importProgress, setImportProgress] = useState<ImportProgress | null>(
+    null,
+  );
+  const [importSource, setImportSource] = useState<string>("");
+  const [importOverwrite, setImportOverwrite] = useState(false);
+  const [importing, setImporting] = useState(false);
+  const [orphans, setOrphans] = useState<OrphanedNode[]>([]);
+  const [benchRunning, setBenchRunning] = useState(false);
+  const [benchProgress, setBenchProgress] = useState<BenchmarkProgress | null>(
+    null,
+  );
+  const [benchResults, setBenchResults] = useState<BenchResult[] | null>(null);
+  const [duplicateIdentity, setDuplicateIdentity] =
+    useState<DuplicateIdentity | null>(null);
+  // bumped by Retry to re-run the install
+  const [setupAttempt, setSetupAttempt] = useState(0);
+  const [appUpdate, setAppUpdate] = useState<AppUpdateInfo | null>(null);
+  const [updateAutoCheck, setUpdateAutoCheck] = useState<boolean>(true);
+  const [throttle, setThrottle] = useState<ThrottleSettings | null>(null);
+  const [adaptive, setAdaptive] = useState<AdaptiveSafeModeSettings | null>(
+    null,
+  );
+  // "temperature" | "load" | "unavailable"
+  const [throttlePolicy, setThrottlePolicy] = useState<string>("unavailable");
+  const [installDir, setInstallDirState] = useState<string>("");
+  const [installDirDraft, setInstallDirDraft] = useState<string>("");
   const [accountJsonPath, setAccountJsonPath] = useState<string>("");
   const [toast, setToast] = useState<string>("");
+  const [remediation, setRemediation] = useState<Remediation | null>(null);
+  const [safeModeCountdown, setSafeModeCountdown] = useState<SafeModeCountdown | null>(null);
   // log file paths (prefer external miner when present)
 
   const [status, setStatus] = useState<
-    "Idle" | "Starting" | "Syncing" | "Mining" | "Repairing" | "Error"
+    "Idle" | "Starting" | "Syncing" | "Mining" | "Paused" | "Repairing" | "Error"
   >("Idle");
   // External miner controls (persisted)
   const [useExternalMiner, setUseExternalMiner] = useState<boolean>(
@@ -86,7 +231,25 @@ export default function App() {
   const [peers, setPeers] = useState<number | null>(null);
   const [best, setBest] = useState<number | null>(null);
   const [highest, setHighest] = useState<number | null>(null);
+  const [syncPct, setSyncPct] = useState<number | null>(null);
+  // earlier rewards addresses (settings.watched_addresses) and their balances
+  const [watched, setWatched] = useState<BalanceView[]>([]);
+  const [watchedRev, setWatchedRev] = useState(0);
+  const [syncPhase, setSyncPhase] = useState<Pick<MinerStatus, "syncPhase" | "heavyBlock">>(
+    {},
+  );
+  const [lastRpcTs, setLastRpcTs] = useState<number | null>(null);
+  const [foreignNode, setForeignNode] = useState<boolean>(false);
+  // verified blocks authored this node session
+  const [authored, setAuthored] = useState<number>(0);
+  const lastCelebrateRef = useRef<number>(0);
+  // unix seconds of the cold-start snapshot on screen; null once live data arrives
+  const [snapshotAt, setSnapshotAt] = useState<number | null>(null);
+  const liveStatusRef = useRef(false);
+  // natSuspected of the last status, so the port forwarding hint shows once per episode
+  const natSuspectedRef = useRef(false);
   const [safeMode, setSafeMode] = useState<boolean>(false);
+  const [safeModeReason, setSafeModeReason] = useState<string | null>(null);
   const [bootnodeConnected, setBootnodeConnected] = useState<boolean | null>(
     null,
   );
@@ -104,14 +267,47 @@ export default function App() {
     const v = parseInt(localStorage.getItem("qm.lineLimit") || "", 10);
     return Number.isFinite(v) && v > 0 ? v : 400;
   });
+  const [eventSeverity, setEventSeverityState] = useState<EventSeverity>(
+    () => (localStorage.getItem("qm.eventSeverity") as EventSeverity) || "info",
+  );
+  // why a chain can't be picked yet (null: it can); filled in from listChains
+  const [chainUnavailable, setChainUnavailable] = useState<
+    Record<string, string | null>
+  >({ heisenberg: "not available yet", quantus: "not available yet" });
+  useEffect(() => {
+    listChains()
+      .then((list) =>
+        setChainUnavailable(
+          Object.fromEntries(
+            list.map((c) => [
+              c.name,
+              c.enabled ? null : (c.unavailable_reason ?? "not available yet"),
+            ]),
+          ),
+        ),
+      )
+      .catch(() => {});
+  }, []);
+  useEffect(() => {
+    try {
+      localStorage.setItem("qm.eventSeverity", eventSeverity);
+    } catch {}
+    setEventSeverity(eventSeverity).catch(() => {});
+  }, [eventSeverity]);
   // simple derived display for balance pill (formatted)
   const balanceDisplay =
     balance && balance !== "—"
-      ? formatBalanceRaw(balance, balanceDecimals, balanceSymbol)
+      ? balanceFormatted
+        ? `${balanceFormatted} ${balanceSymbol}`
+        : formatBalanceRaw(balance, balanceDecimals, balanceSymbol)
       : null;
   const [autoStart, setAutoStart] = useState<boolean>(
     () => localStorage.getItem("qm.autoStart") === "1",
   );
+  // auto-start resumes the last session as it ran (safe mode, ports) instead of settings
+  const [resumeSession, setResumeSession] = useState<boolean>(
+    () => localStorage.getItem("qm.resumeSession") === "1",
+  );
   const [logToFile, setLogToFile] = useState<boolean>(
     () => localStorage.getItem("qm.logToFile") === "1",
   );
@@ -123,6 +319,39 @@ export default function App() {
     () => (localStorage.getItem("qm.theme") as ThemeMode) || "system",
   );
   const lineLimitRef = useRef(lineLimit);
+  // chain shown in the UI; other chains may be running alongside it
+  const activeChainRef = useRef<string>(chain);
+  // last meta seen per chain, so switching the view back restores it
+  const metaByChainRef = useRef<Record<string, Partial<MinerMeta>>>({});
+  useEffect(() => {
+    if (activeChainRef.current === chain) return;
+    activeChainRef.current = chain;
+    setBest(null);
+    setHighest(null);
+    setSyncPct(null);
+    setLastRpcTs(null);
+    setForeignNode(false);
+    setSecurityWarning(null);
+    setDuplicateIdentity(null);
+    setRepairProgress(null);
+    setImportProgress(null);
+    setPeers(null);
+    getAuthoredBlocks(chain)
+      .then((a) => setAuthored(a.session))
+      .catch(() => setAuthored(0));
+    const m = metaByChainRef.current[chain] || {};
+    setMeta(m);
+    try {
+      localStorage.setItem("qm.meta", JSON.stringify(m));
+    } catch {}
+    getMinerState()
+      .then((s) => {
+        const running = s.running_chains.includes(chain);
+        setMining(running);
+        setStatus(running ? "Syncing" : "Idle");
+      })
+      .catch(() => {});
+  }, [chain]);
   useEffect(() => {
     const apply = (mode: ThemeMode) => {
       const root = document.documentElement;
@@ -160,16 +389,109 @@ export default function App() {
   }
 
   useEffect(() => {
-    ensureMinerAndAccount().then(
-      async ({ minerPath, accountJsonPath, account }) => {
+    getInstallDir()
+      .then((d) => {
+        setInstallDirState(d);
+        setInstallDirDraft(d);
+      })
+      .catch(() => {});
+  }, []);
+
+  useEffect(() => {
+    getAppUpdateSettings()
+      .then((s) => setUpdateAutoCheck(s.auto_check))
+      .catch(() => {});
+    getThrottleSettings()
+      .then(setThrottle)
+      .catch(() => {});
+    getAdaptiveSafeMode()
+      .then(setAdaptive)
+      .catch(() => {});
+    getSystemRequirements()
+      .then((r) => setThrottlePolicy(r.throttle.policy))
+      .catch(() => {});
+    const un = onAppUpdateAvailable((info) => setAppUpdate(info));
+    const unThrottle = onThrottle((t) => {
+      const why =
+        t.policy === "temperature"
+          ? `${t.temp_c?.toFixed(0)} °C`
+          : `load ${t.load_per_cpu?.toFixed(2)}/CPU`;
+      showToast(
+        t.error
+          ? `Throttle couldn't change external miner cores: ${t.error}`
+          : t.paused
+            ? `Mining paused: still too hot at ${t.from} cores (${why})`
+            : t.resumed
+              ? `Mining resumed after cooling down (${why})`
+              : `External miner ${t.from} → ${t.to} cores (${why})`,
+      );
+    });
+    return () => {
+      unThrottle.then((u) => u());
+      un.then((u) => u());
+    };
+  }, []);
+
+  async function onCheckAppUpdate() {
+    try {
+      const info = await checkAppUpdate(true);
+      setAppUpdate(info);
+      if (!info.updateAvailable) {
+        showToast(`Up to date (v${info.current})`);
+      }
+    } catch (e) {
+      showToast(`Update check failed: ${String(e)}`);
+    }
+  }
+
+  async function applyInstallDir(dir: string | null) {
+    try {
+      const d = await setInstallDir(dir);
+      setInstallDirState(d);
+      setInstallDirDraft(d);
+      // reinstall into the new location if the move left nothing behind
+      const res = await ensureMinerAndAccount();
+      setMinerPath(res.minerPath);
+      showToast(`Install directory: ${d}`);
+    } catch (e) {
+      showToast(`Install directory unchanged: ${setupErrorMessage(e)}`);
+    }
+  }
+
+  useEffect(() => {
+    ensureMinerAndAccount(useExternalMiner).then(
+      async ({ minerPath, accountJsonPath, account, externalMinerError }) => {
+        if (externalMinerError)
+          showToast(`External miner not installed: ${externalMinerError.message}`);
+        setSetupError(null);
         setMinerPath(minerPath);
         setAccountJsonPath(accountJsonPath);
         setAccount(account); // shows ss58
 
+        // A node left running by a crashed GUI holds the DB; let the user decide first.
+        const orphaned = await getOrphanedNodes().catch(() => []);
+        setOrphans(orphaned);
+
         // Auto-start scaffold: resume mining if previously active and auto-start is enabled
         const wasMining = localStorage.getItem("qm.wasMining") === "1";
-        if (autoStart && wasMining && account && minerPath) {
-          const c = chain === "quantus" ? "resonance" : chain;
+        const c = chain === "quantus" ? "resonance" : chain;
+        if (
+          autoStart &&
+          wasMining &&
+          account &&
+          minerPath &&
+          !orphaned.some((o) => o.chain === c)
+        ) {
+          if (resumeSession) {
+            try {
+              setStatus("Starting");
+              await resumeLastSession(c);
+              setMining(true);
+              return;
+            } catch {
+              // nothing saved (or it failed): start from the settings below
+            }
+          }
           try {
             setStatus("Starting");
             const extraArgs: string[] = [];
@@ -202,8 +524,9 @@ export default function App() {
           }
         }
       },
+      (e) => setSetupError(e as SetupError),
     );
-  }, [autoStart, chain]);
+  }, [autoStart, resumeSession, chain, setupAttempt]);
   useEffect(() => {
     lineLimitRef.current = lineLimit;
     localStorage.setItem("qm.lineLimit", String(lineLimit));
@@ -225,25 +548,21 @@ export default function App() {
         }
       }
       if (ev.type === "FoundBlock") {
-        // A block was actually accepted (strong signal) – celebrate.
-        celebrate();
+        // A block was actually accepted (strong signal) – celebrate, once
+        // for the log line and its verified import.
+        if (Date.now() - lastCelebrateRef.current > 30_000) {
+          lastCelebrateRef.current = Date.now();
+          celebrate();
+        }
         setStatus("Mining");
         // Refresh balance after a found block (may reflect new rewards)
         if (account) {
           const c = chain === "quantus" ? "resonance" : chain;
-          queryBalance(c, account.address).then((res: any) => {
+          queryBalance(c, account.address).then((res) => {
             if (res && typeof res.free === "string") {
-              setBalance(res.free);
-              if (typeof res.symbol === "string") setBalanceSymbol(res.symbol);
-              if (typeof res.decimals === "number")
-                setBalanceDecimals(res.decimals);
+              applyBalance(res);
               // show formatted balance in a toast on reward detection
-              const formatted = formatBalanceRaw(
-                res.free,
-                res.decimals ?? balanceDecimals,
-                res.symbol ?? balanceSymbol,
-              );
-              showToast(`Balance updated: ${formatted}`);
+              showToast(`Balance updated: ${res.freeFormatted} ${res.symbol}`);
             }
           });
         }
@@ -306,34 +625,69 @@ export default function App() {
     const un3 = onMinerStatus(
       (
         s: MinerStatus & {
-          safe_mode?: boolean;
-          bootnode_connected?: boolean;
-          bootnode_host?: string;
+          safeMode?: boolean;
+          safeModeReason?: "range" | "adaptive" | null;
+          bootnodeConnected?: boolean;
+          bootnodeHost?: string;
         },
       ) => {
+        if (typeof s.chain === "string" && s.chain !== activeChainRef.current) {
+          return;
+        }
+        if (s.dbWiped) {
+          setBest(null);
+          setPeers(null);
+          setSyncPct(null);
+          setLastRpcTs(null);
+          return;
+        }
+        liveStatusRef.current = true;
+        setSnapshotAt(null);
+        setForeignNode(s.foreignNode === true);
+        if (typeof s.lastRpcSuccessTs === "number")
+          setLastRpcTs(s.lastRpcSuccessTs);
         if (typeof s.peers === "number") setPeers(s.peers);
-        if (typeof s.current_block === "number") {
-          setBest(s.current_block);
+        if (typeof s.currentBlock === "number") {
+          setBest(s.currentBlock);
           // In case RPC provides better signal, prefer RPC over log parsing.
           setStatus("Syncing");
         }
-        if (typeof s.highest_block === "number") setHighest(s.highest_block);
-        if (typeof s.is_syncing === "boolean" && !s.is_syncing) {
+        if (typeof s.highestBlock === "number") setHighest(s.highestBlock);
+        setSyncPct(typeof s.syncPercent === "number" ? s.syncPercent : null);
+        setSyncPhase({ syncPhase: s.syncPhase ?? null, heavyBlock: s.heavyBlock ?? null });
+        if (typeof s.isSyncing === "boolean" && !s.isSyncing) {
           // If RPC says not syncing and we have hashrate elsewhere, UI will move to Mining.
           // No-op: we no longer track syncBlock separately.
         }
-        if (typeof s.safe_mode === "boolean") {
-          setSafeMode(s.safe_mode);
+        if (typeof s.safeMode === "boolean") {
+          setSafeMode(s.safeMode);
+          setSafeModeReason(s.safeMode ? (s.safeModeReason ?? null) : null);
+        }
+        if (typeof s.bootnodeConnected === "boolean") {
+          setBootnodeConnected(s.bootnodeConnected);
         }
-        if (typeof s.bootnode_connected === "boolean") {
-          setBootnodeConnected(s.bootnode_connected);
+        if (typeof s.bootnodeHost === "string") {
+          setBootnodeHost(s.bootnodeHost);
         }
-        if (typeof s.bootnode_host === "string") {
-          setBootnodeHost(s.bootnode_host);
+        if (typeof s.natSuspected === "boolean") {
+          if (s.natSuspected && !natSuspectedRef.current) {
+            getPeerMetrics(s.chain ?? undefined)
+              .then((m) =>
+                showToast(
+                  `You appear unreachable from the internet (no inbound peers). Enable port forwarding for TCP ${m?.p2p_port ?? "P2P"} on your router, with a fixed --port in extra args.`,
+                ),
+              )
+              .catch(() => {});
+          }
+          natSuspectedRef.current = s.natSuspected;
         }
       },
     );
     const un4 = onMinerMeta((m: MinerMeta) => {
+      const key =
+        typeof m.chain === "string" ? m.chain : activeChainRef.current;
+      metaByChainRef.current[key] = { ...metaByChainRef.current[key], ...m };
+      if (key !== activeChainRef.current) return;
       setMeta((prev) => {
         const merged = { ...prev, ...m };
         try {
@@ -343,13 +697,17 @@ export default function App() {
       });
     });
     // subscribe to miner:logfile to capture file path when logging starts
-    const un5 = onMinerLogFile((path: string) => {
-      setLogFilePath(path);
+    const un5 = onMinerLogFile((path, _kind, c) => {
+      if (c && c !== activeChainRef.current) return;
+      setLogFilePath(path || "");
       try {
-        localStorage.setItem("qm.logFilePath", path);
+        localStorage.setItem("qm.logFilePath", path || "");
       } catch {}
     });
     const un6 = onMinerState((s) => {
+      if (typeof s.chain === "string" && s.chain !== activeChainRef.current) {
+        return;
+      }
       if (typeof s.running === "boolean") {
         setMining(s.running);
         try {
@@ -363,8 +721,255 @@ export default function App() {
         if (status === "Idle") setStatus("Syncing");
       } else if (s.phase === "stopped") {
         setStatus("Idle");
+      } else if (s.phase === "stop_failed") {
+        // onStop shows the error; the node may still be running
+        setStatus("Error");
+      }
+    });
+    // Backend phase is authoritative for buttons during restarts and repair.
+    const applyPhase = (p: MinerPhase) => {
+      switch (p.phase) {
+        case "starting_node":
+        case "waiting_for_rpc":
+        case "restarting":
+          setStatus("Starting");
+          setMining(true);
+          break;
+        case "syncing":
+          setStatus("Syncing");
+          setMining(true);
+          break;
+        case "mining":
+          setStatus("Mining");
+          setMining(true);
+          break;
+        case "paused":
+          setStatus("Paused");
+          setMining(true);
+          break;
+        case "repairing":
+          setStatus("Repairing");
+          setMining(true);
+          break;
+        case "errored":
+          setStatus("Error");
+          break;
+        case "idle":
+          setStatus("Idle");
+          setMining(false);
+          break;
+        default:
+          break;
+      }
+    };
+    const un8 = onMinerPhase(applyPhase);
+    const un9 = onMinerQuarantined((q) => setQuarantine(q));
+    // also covers restarts the backend does on its own (safe mode, repair)
+    const un11 = onAccountCorrupt(async (a) => {
+      if (!a.may_hold_key) {
+        showToast(
+          `Account file was unreadable (${a.reason}); moved to ${a.quarantined}`,
+        );
+        return;
+      }
+      const ok = confirm(
+        `The rewards account file ${a.path} is unreadable (${a.reason}) and may still contain your key. ` +
+          "Set it aside (it is kept as .corrupt-…) and create a new account?",
+      );
+      if (!ok) return;
+      try {
+        const res = await confirmAccountReset();
+        setMinerPath(res.minerPath);
+        setAccountJsonPath(res.accountJsonPath);
+        setAccount(res.account);
+        showToast("New rewards account created");
+      } catch (err) {
+        showToast(`Account reset failed: ${setupErrorMessage(err)}`);
       }
     });
+    const un10 = onMinerStartFailed((f) => {
+      if (f.chain !== activeChainRef.current) return;
+      showToast(`Node failed to start (${f.kind}): ${f.reason}`);
+    });
+    const un22 = onExternalCrashloop((c) => {
+      if (c.chain !== activeChainRef.current) return;
+      showToast(`External miner crashed ${c.crashes} times within ${c.window_secs}s: ${c.detail}.`);
+    });
+    const un23 = onOperationCancelled((o) => {
+      showToast(o.kind === "repair" ? "Repair cancelled." : "Install cancelled.");
+    });
+    const un24 = onAccountNotBackedUp(setBackupWarning);
+    const un25 = onSyncComplete((c) => {
+      if (c.chain !== activeChainRef.current) return;
+      const hours = ((c.syncWallSecs ?? 0) / 3600).toFixed(1);
+      celebrate();
+      showToast(`Fully synced ${c.chain} for the first time, after ${hours} h.`);
+    });
+    const un26 = onForkDetected((f) => {
+      if (f.chain !== activeChainRef.current) return;
+      const advice =
+        f.action === "restarting"
+          ? "Restarting the node."
+          : f.action === "suggest_repair"
+            ? "It persists after a restart; use Repair."
+            : "Stop and start the node to rejoin the network.";
+      showToast(`Node is on a fork at block #${f.height}. ${advice}`);
+    });
+    const un27 = onImportPressure((p) => {
+      if (p.chain !== activeChainRef.current) return;
+      const slowest = p.max_import_secs ? ` (slowest import ${Math.round(p.max_import_secs)}s)` : "";
+      showToast(`Working through heavy blocks${slowest}. This is expected; the node isn't frozen.`);
+    });
+    const un28 = onSafeModeRecommendation((r) => {
+      if (r.chain !== activeChainRef.current) return;
+      const slower = r.slowdown ? ` Syncing is about ${r.slowdown.toFixed(1)}x slower with it.` : "";
+      showToast(
+        `Past all heavy block ranges, but --max-blocks-per-request is still in your extra args.${slower} Consider removing it.`,
+      );
+    });
+    const un29 = onBalance((b) => {
+      if (b.chain !== activeChainRef.current) return;
+      applyBalance(b.balance);
+    });
+    const un30 = onLogThrottled((t) => {
+      const gib = ((t.freeBytes ?? 0) / 2 ** 30).toFixed(1);
+      showToast(
+        t.throttled
+          ? `Only ${gib} GiB free on the disk with the logs and the node database; log files keep warnings and errors only. Choose another log directory to keep full logs.`
+          : "Full session logging resumed.",
+      );
+    });
+    const un31 = onLogMoveProgress((p) => {
+      if (!p.done) return;
+      const left = p.skipped ? ` ${p.skipped} file(s) still in use stay in ${p.from}.` : "";
+      showToast(
+        p.error
+          ? `Moved ${p.moved} of ${p.total} log files to ${p.to}; last error: ${p.error}`
+          : `Moved ${p.moved} log files to ${p.to}.${left}`,
+      );
+    });
+    const un32 = onRemediation((r) => {
+      if (r.chain !== activeChainRef.current) return;
+      setRemediation(r);
+    });
+    const un33 = onBlockPropagated((p) => {
+      if (p.chain !== activeChainRef.current) return;
+      showToast(
+        p.propagationMs !== null
+          ? `Block #${p.height} propagated in ${(p.propagationMs / 1000).toFixed(1)} s`
+          : p.orphaned
+            ? `Block #${p.height} was orphaned`
+            : `Block #${p.height} hasn't reached the network after a minute; likely orphaned`,
+      );
+    });
+    const un34 = onTimedStop((t) => {
+      showToast(
+        t.stopped
+          ? `Timed stop: ${t.chain} node stopped (${t.authoredBlocks} blocks this session)`
+          : `Timed stop on ${t.chain} didn't stop the node: ${t.error}`,
+      );
+    });
+    const un35 = onTransfer((t) => {
+      if (t.chain !== activeChainRef.current) return;
+      const x = t.transfer;
+      const who = `${x.counterparty.slice(0, 6)}…${x.counterparty.slice(-4)}`;
+      showToast(
+        x.direction === "in"
+          ? `Received ${x.amountFormatted} ${x.symbol} from ${who}`
+          : `Sent ${x.amountFormatted} ${x.symbol} to ${who}`,
+      );
+    });
+    const un36 = onMigratedData((m) => {
+      showToast(
+        `Restored your account${m.files.length > 1 ? " and settings" : ""} from an earlier version's folder (${m.from}); the originals were left in place.`,
+      );
+    });
+    const un37 = onSafeModeCountdown((c) => {
+      if (c.chain !== activeChainRef.current) return;
+      setSafeModeCountdown(c.outcome ? null : c);
+    });
+    const un38 = onBinaryModified((m) => {
+      if (m.chain !== activeChainRef.current) return;
+      showToast(`${m.path} isn't the ${m.releaseTag} binary that was installed (its hash changed).`);
+    });
+    const un39 = onRewardsAddressChanged((c) => {
+      if (c.chain !== activeChainRef.current) return;
+      // balance refresh and explorer link follow the new address
+      setAccount((a: any) => (a ? { ...a, address: c.address } : a));
+      setWatchedRev((n) => n + 1);
+      showToast(
+        `Now mining to ${c.address}${c.restarted ? "; node restarted, sync kept" : ""}`,
+      );
+    });
+    const un21 = onChainMismatch((m) => {
+      if (m.chain !== activeChainRef.current) return;
+      showToast(
+        `${m.chain_dir} holds data for ${m.found}, not ${m.expected}. Use Repair to wipe it, or set another data folder (--base-path).`,
+      );
+    });
+    const un20 = onMinerStartDegraded((d) => {
+      if (d.chain !== activeChainRef.current) return;
+      showToast(
+        `Node still starting after ${Math.round(d.waited_ms / 1000)}s (waiting for ${d.missing.join(", ")})`,
+      );
+    });
+    const un12 = onSetupProgress((p) => {
+      if (p.stage === "reinstalling") {
+        showToast("quantus-node is missing; reinstalling…");
+      } else {
+        setMinerPath(p.path);
+        showToast(`quantus-node reinstalled at ${p.path}`);
+      }
+    });
+    const un14 = onMinerAuthored((a) => {
+      if (a.chain !== activeChainRef.current) return;
+      setAuthored(a.count);
+      if (a.change === "orphaned") {
+        showToast(`Block #${a.block.height} was orphaned by a reorg`);
+      }
+    });
+    const un15 = onRuntimeUpgraded((u) => {
+      if (u.chain !== activeChainRef.current) return;
+      showToast(
+        u.suggest_update
+          ? `Blocks fail to import after the runtime upgrade to v${u.to}; update quantus-node${
+              u.latest_node_release ? ` (latest ${u.latest_node_release})` : ""
+            }`
+          : `Runtime upgraded: spec_version ${u.from} → ${u.to}`,
+      );
+    });
+    const un13 = onSecurityWarning((w) => {
+      if (w.chain !== activeChainRef.current) return;
+      setSecurityWarning(w);
+    });
+    const un16 = onDuplicateIdentity((d) => {
+      if (d.chain !== activeChainRef.current) return;
+      setDuplicateIdentity(d);
+    });
+    const un18 = onBenchmarkProgress(setBenchProgress);
+    const un17 = onRepairProgress((p) => {
+      if (p.chain !== activeChainRef.current) return;
+      setRepairProgress(
+        p.stage === "done" || p.stage === "cancelled" ? null : p,
+      );
+    });
+    const un19 = onImportProgress((p) => {
+      if (p.chain !== activeChainRef.current) return;
+      setImportProgress(p.stage === "done" ? null : p);
+    });
+    getMinerState()
+      .then((s) => applyPhase(s.phase))
+      .catch(() => {});
+    // quantus:// links only preselect; the user still has to press Start.
+    const un7 = onDeepLink((intent) => {
+      if (intent.chain !== "quantus") setChain(intent.chain);
+      showToast(
+        intent.rewards_address
+          ? `Link received: mine on ${intent.chain} to ${intent.rewards_address}. Press Start to confirm.`
+          : `Link received: mine on ${intent.chain}. Press Start to confirm.`,
+      );
+    });
+    un7.then(() => frontendReady()).catch(() => {});
     return () => {
       un1.then((u) => u());
       un2.then((u) => u());
@@ -372,10 +977,43 @@ export default function App() {
       un4.then((u) => u());
       un5.then((u) => u());
       un6.then((u) => u());
+      un7.then((u) => u());
+      un8.then((u) => u());
+      un9.then((u) => u());
+      un10.then((u) => u());
+      un11.then((u) => u());
+      un12.then((u) => u());
+      un13.then((u) => u());
+      un14.then((u) => u());
+      un15.then((u) => u());
+      un16.then((u) => u());
+      un17.then((u) => u());
+      un18.then((u) => u());
+      un19.then((u) => u());
+      un20.then((u) => u());
+      un21.then((u) => u());
+      un22.then((u) => u());
+      un23.then((u) => u());
+      un24.then((u) => u());
+      un25.then((u) => u());
+      un26.then((u) => u());
+      un27.then((u) => u());
+      un28.then((u) => u());
+      un29.then((u) => u());
+      un30.then((u) => u());
+      un31.then((u) => u());
+      un32.then((u) => u());
+      un33.then((u) => u());
+      un34.then((u) => u());
+      un35.then((u) => u());
+      un36.then((u) => u());
+      un37.then((u) => u());
+      un38.then((u) => u());
+      un39.then((u) => u());
     };
   }, []);
 
-  async function onStart() {
+  async function onStart(unquarantine = false) {
     const c = chain === "quantus" ? "resonance" : chain;
     if (!account || !minerPath) {
       showToast("Miner not ready yet. Please wait for installer/account.");
@@ -383,6 +1021,9 @@ export default function App() {
     }
     try {
       setStatus("Starting");
+      setQuarantine(null);
+      // the backend starts a new authorship session with the node
+      setAuthored(0);
       const extraArgs: string[] = [];
       // If using external miner, add external miner URL arg to quantus-node
       if (useExternalMiner && externalPort) {
@@ -391,7 +1032,7 @@ export default function App() {
           `http://127.0.0.1:${externalPort}`,
         );
       }
-      await startMiner(
+      await (unquarantine ? retryAfterUnquarantine : startMiner)(
         c,
         account.address,
         minerPath,
@@ -413,16 +1054,21 @@ export default function App() {
         localStorage.setItem("qm.wasMining", "1");
       } catch {}
     } catch (err: any) {
-      showToast(
-        err?.message
-          ? `Start failed: ${err.message}`
-          : `Start failed: ${String(err)}`,
-      );
+      const msg = err?.message ?? String(err);
+      // double click / node already up: not a failure, the button was just stale
+      if (
+        msg.startsWith("already running") ||
+        msg.startsWith("already starting")
+      ) {
+        setMining(true);
+        return;
+      }
+      showToast(`Start failed: ${msg}`);
     }
   }
   async function onStop() {
     try {
-      await stopMiner();
+      await stopMiner(chain);
       setMining(false);
       try {
         localStorage.setItem("qm.wasMining", "0");
@@ -437,39 +1083,133 @@ export default function App() {
     }
   }
 
+  async function onBenchmark() {
+    setBenchRunning(true);
+    setBenchResults(null);
+    setBenchProgress(null);
+    try {
+      setBenchResults(await runBenchmark(30));
+    } catch (e) {
+      showToast(`Benchmark: ${String(e)}`);
+    } finally {
+      setBenchRunning(false);
+      setBenchProgress(null);
+    }
+  }
+
   async function onRepair() {
+    try {
+      const plan = await planRepair(chain);
+      const lines = plan.changes.map(
+        (c) =>
+          `${c.action} ${c.path} (${c.files} files, ${(c.bytes / 1e9).toFixed(2)} GB)`,
+      );
+      const ok = confirm(
+        [
+          "Repair will:",
+          ...lines,
+          plan.restarts_node
+            ? "and restart the node to resync."
+            : "The node will not be restarted.",
+          ...plan.notes,
+        ].join("\n"),
+      );
+      if (!ok) return;
+    } catch (err: any) {
+      showToast(`Repair: ${String(err?.message ?? err)}`);
+      return;
+    }
     setStatus("Repairing");
+    const opId = `repair-${Date.now()}`;
+    setRepairOp(opId);
     try {
-      const { invoke } = await import("@tauri-apps/api/core");
-      await invoke("repair_miner");
-      showToast("Repair initiated. Node will restart and resync.");
+      const restarted = await withAppPin((pin) => repairMiner(chain, opId, pin), askPin);
+      if (restarted) {
+        showToast("Repair initiated. Node will restart and resync.");
+      } else {
+        setStatus("Idle");
+        showToast("Database wiped. Press Start to resync from scratch.");
+      }
       // Status will transition to Syncing as logs come in; keep as Repairing for now.
     } catch (err: any) {
+      // operation:cancelled has its own toast
+      if (String(err?.message ?? err) === "cancelled") {
+        setStatus("Idle");
+        return;
+      }
       setStatus("Error");
       showToast(
         err?.message
           ? `Repair failed: ${err.message}`
           : `Repair failed: ${String(err)}`,
       );
+    } finally {
+      setRepairOp(null);
     }
   }
 
+  function applyBalance(res: BalanceView) {
+    setBalance(res.free);
+    setBalanceFormatted(res.freeFormatted ?? null);
+    if (typeof res.symbol === "string") setBalanceSymbol(res.symbol);
+    if (typeof res.decimals === "number") setBalanceDecimals(res.decimals);
+  }
+
+  // Paint the last session's values until the first live status arrives.
+  useEffect(() => {
+    getLastSnapshot()
+      .then((snap) => {
+        const c = snap?.chains[activeChainRef.current];
+        if (!snap || !c || liveStatusRef.current) return;
+        setSnapshotAt(snap.saved_at);
+        const s = c.status;
+        if (s) {
+          if (typeof s.peers === "number") setPeers(s.peers);
+          if (typeof s.currentBlock === "number") setBest(s.currentBlock);
+          if (typeof s.highestBlock === "number") setHighest(s.highestBlock);
+          if (typeof s.syncPercent === "number") setSyncPct(s.syncPercent);
+          // an old timestamp also greys out the RPC pill
+          if (typeof s.lastRpcSuccessTs === "number")
+            setLastRpcTs(s.lastRpcSuccessTs);
+        }
+        const m = c.meta;
+        if (m) setMeta((prev) => ({ ...m, ...prev }));
+        if (c.balance) applyBalance(c.balance);
+        if (typeof c.hashrate === "number") setHps(c.hashrate);
+        setAuthored(c.authored);
+      })
+      .catch(() => {});
+  }, []);
+
   async function refreshBalance() {
     if (!account) return;
     // mainnet disabled; if picked, fall back to resonance
     const c = chain === "quantus" ? "resonance" : chain;
-    const res: any = await queryBalance(c, account.address);
-    if (res && typeof res.free === "string") {
-      setBalance(res.free);
-      if (typeof res.symbol === "string") setBalanceSymbol(res.symbol);
-      if (typeof res.decimals === "number") setBalanceDecimals(res.decimals);
-    }
+    const res = await queryBalance(c, account.address, true);
+    if (res && typeof res.free === "string") applyBalance(res);
   }
 
   const progressPct =
-    typeof best === "number" && typeof highest === "number" && highest > 0
-      ? Math.max(0, Math.min(100, Math.floor((best / highest) * 100)))
-      : 0;
+    syncPct !== null
+      ? syncPct
+      : typeof best === "number" && typeof highest === "number" && highest > 0
+        ? Math.max(0, Math.min(100, Math.floor((best / highest) * 100)))
+        : 0;
+  // status events arrive ~1/s, so this is re-evaluated often enough
+  const rpcStale = lastRpcTs !== null && Date.now() - lastRpcTs > 10_000;
+
+  // balances of earlier rewards addresses, so rewards mined to them stay visible
+  useEffect(() => {
+    const c = chain === "quantus" ? "resonance" : chain;
+    getSettings()
+      .then((s) =>
+        Promise.all(
+          s.watched_addresses.map((a) => queryBalance(c, a).catch(() => null)),
+        ),
+      )
+      .then((views) => setWatched(views.filter((v): v is BalanceView => v !== null)))
+      .catch(() => setWatched([]));
+  }, [chain, watchedRev]);
 
   // auto-refresh balance while mining
   useEffect(() => {
@@ -477,13 +1217,8 @@ export default function App() {
     if (mining && account) {
       const c = chain === "quantus" ? "resonance" : chain;
       const tick = async () => {
-        const res: any = await queryBalance(c, account.address);
-        if (res && typeof res.free === "string") {
-          setBalance(res.free);
-          if (typeof res.symbol === "string") setBalanceSymbol(res.symbol);
-          if (typeof res.decimals === "number")
-            setBalanceDecimals(res.decimals);
-        }
+        const res = await queryBalance(c, account.address);
+        if (res && typeof res.free === "string") applyBalance(res);
       };
       // refresh immediately, then every 60s
       tick();
@@ -504,11 +1239,13 @@ export default function App() {
                 ? "bg-green-600 text-white"
                 : status === "Starting"
                   ? "bg-blue-600 text-white"
-                  : status === "Repairing"
-                    ? "bg-purple-600 text-white"
-                    : status === "Error"
-                      ? "bg-red-600 text-white"
-                      : "bg-gray-500 text-white"
+                  : status === "Paused"
+                    ? "bg-amber-500 text-black"
+                    : status === "Repairing"
+                      ? "bg-purple-600 text-white"
+                      : status === "Error"
+                        ? "bg-red-600 text-white"
+                        : "bg-gray-500 text-white"
             }`}
             title="Miner status"
           >
@@ -520,14 +1257,53 @@ export default function App() {
                   ? "Error"
                   : status === "Mining"
                     ? "Mining"
-                    : "Idle"}
+                    : status === "Paused"
+                      ? "Paused"
+                      : "Idle"}
           </div>
+          {repairProgress && (
+            <div
+              className="rounded-full px-3 py-1 text-xs font-semibold shadow bg-purple-600 text-white"
+              title={`${repairProgress.removed_files}/${repairProgress.total_files} files`}
+            >
+              {repairProgress.stage === "scanning"
+                ? "Measuring database…"
+                : `Deleting ${(repairProgress.removed_bytes / 1e9).toFixed(1)} / ${(
+                    repairProgress.total_bytes / 1e9
+                  ).toFixed(1)} GB`}
+              {repairProgress.resumed ? " (resuming)" : ""}
+              {repairOp && (
+                <button
+                  className="ml-2 underline"
+                  onClick={() => cancelOperation(repairOp)}
+                >
+                  Cancel
+                </button>
+              )}
+            </div>
+          )}
+          {importProgress && (
+            <div
+              className="rounded-full px-3 py-1 text-xs font-semibold shadow bg-purple-600 text-white"
+              title={`${importProgress.copied_files}/${importProgress.total_files} files`}
+            >
+              {importProgress.stage === "scanning"
+                ? "Measuring source database…"
+                : `Importing ${(importProgress.copied_bytes / 1e9).toFixed(1)} / ${(
+                    importProgress.total_bytes / 1e9
+                  ).toFixed(1)} GB`}
+            </div>
+          )}
           {safeMode && (
             <div
               className="rounded-full px-3 py-1 text-xs font-semibold shadow bg-purple-700 text-white"
-              title="Safe Sync mode (--max-blocks-per-request 1) is active"
+              title={
+                safeModeReason === "adaptive"
+                  ? "Safe Sync (--max-blocks-per-request 1) was enabled after repeated slow imports/sync timeouts; it turns off once sync is healthy"
+                  : "Safe Sync mode (--max-blocks-per-request 1) is active"
+              }
             >
-              Safe Sync
+              {safeModeReason === "adaptive" ? "Safe Sync (adaptive)" : "Safe Sync"}
             </div>
           )}
           {bootnodeHost ? (
@@ -570,13 +1346,57 @@ export default function App() {
                   : peers >= 1
                     ? "bg-amber-500 text-black"
                     : "bg-red-600 text-white"
-            }`}
-            title="Peers / Best / Highest (RPC)"
+            } ${rpcStale ? "opacity-50" : ""} cursor-pointer`}
+            title={
+              rpcStale
+                ? "Peers / Best / Highest (RPC) – no RPC reply for over 10s. Click to refresh"
+                : "Peers / Best / Highest (RPC). Click to refresh"
+            }
+            onClick={async () => {
+              const c = chain === "quantus" ? "resonance" : chain;
+              try {
+                const s = await getNodeStatusNow(c);
+                if (!s.reachable) {
+                  showToast(`Node RPC at ${s.rpc_url} is not answering`);
+                  return;
+                }
+                if (typeof s.peers === "number") setPeers(s.peers);
+                if (typeof s.current_block === "number") setBest(s.current_block);
+                setLastRpcTs(s.checked_at);
+              } catch (e) {
+                showToast(String(e));
+              }
+            }}
           >
             {typeof peers === "number" ? `${peers} peers` : "— peers"} ·{" "}
             {typeof best === "number" ? `#${best}` : "#—"} /{" "}
             {typeof highest === "number" ? `#${highest}` : "#—"}
           </div>
+          {snapshotAt !== null && (
+            <div
+              className="rounded-full px-3 py-1 text-xs font-semibold shadow bg-gray-600 text-white"
+              title="Values from the last session; live data replaces them"
+            >
+              Last session ·{" "}
+              {new Date(snapshotAt * 1000).toLocaleTimeString()}
+            </div>
+          )}
+          {authored > 0 && (
+            <div
+              className="rounded-full px-3 py-1 text-xs font-semibold shadow bg-green-600 text-white"
+              title="Imported blocks whose PoW author is your rewards account (this session)"
+            >
+              {authored} authored
+            </div>
+          )}
+          {foreignNode && (
+            <div
+              className="rounded-full px-3 py-1 text-xs font-semibold shadow bg-red-600 text-white"
+              title="Another node is answering on the local RPC port; its data is ignored"
+            >
+              Foreign node on RPC port
+            </div>
+          )}
           <label
             className="ml-2 flex items-center gap-1 text-xs font-semibold bg-black/80 text-white rounded-full px-3 py-1 shadow"
             title="Auto-start miner on launch if previously running"
@@ -595,6 +1415,26 @@ export default function App() {
             />
             Auto-start
           </label>
+          {autoStart && (
+            <label
+              className="ml-2 flex items-center gap-1 text-xs font-semibold bg-black/80 text-white rounded-full px-3 py-1 shadow"
+              title="Resume exactly as the last session ran (safe sync, ports) instead of current settings"
+            >
+              <input
+                type="checkbox"
+                className="accent-blue-600"
+                checked={resumeSession}
+                onChange={(e) => {
+                  const v = e.target.checked;
+                  setResumeSession(v);
+                  try {
+                    localStorage.setItem("qm.resumeSession", v ? "1" : "0");
+                  } catch {}
+                }}
+              />
+              Resume session
+            </label>
+          )}
           <select
             className="ml-2 border rounded px-2 py-1 text-xs"
             value={theme}
@@ -614,7 +1454,8 @@ export default function App() {
             title={`Balance (${balanceSymbol}, ${balanceDecimals}dp)`}
             onClick={async () => {
               if (account?.address && chain === "resonance") {
-                const url = `https://qsafe.af/chains/resonance/account/${account.address}`;
+                const address = await getAccountAddress("resonance").catch(() => account.address);
+                const url = `https://qsafe.af/chains/resonance/account/${address}`;
                 try {
                   const { openUrl } = await import("@tauri-apps/plugin-opener");
                   await openUrl(url);
@@ -628,6 +1469,11 @@ export default function App() {
             Balance: {balanceDisplay}
           </button>
         )}
+        {watched.map((w) => (
+          <span key={w.address} className="pill bg-black/60 text-white" title={w.address}>
+            Previous …{w.address.slice(-6)}: {w.freeFormatted} {w.symbol}
+          </span>
+        ))}
         <div className="ml-2 flex items-center gap-2">
           <label
             className="text-xs opacity-70 flex items-center gap-1"
@@ -643,6 +1489,12 @@ export default function App() {
                 try {
                   localStorage.setItem("qm.logToFile", v ? "1" : "0");
                 } catch {}
+                // switch file logging on the running node without a restart
+                if (mining) {
+                  setFileLogging(v, chain).catch((err) =>
+                    showToast(`Log file switch failed: ${String(err)}`),
+                  );
+                }
               }}
             />
             Log to file
@@ -670,6 +1522,20 @@ export default function App() {
             style={{ width: `${progressPct}%` }}
           />
         </div>
+        {syncPhase.syncPhase && (
+          <div className="text-xs opacity-70">
+            {syncPhase.syncPhase === "executing" && syncPhase.heavyBlock
+              ? `Executing heavy block #${syncPhase.heavyBlock.number} for ${Math.floor(
+                  syncPhase.heavyBlock.elapsedSecs / 60,
+                )}m ${syncPhase.heavyBlock.elapsedSecs % 60}s (progress pauses meanwhile)`
+              : {
+                  downloading: "Downloading blocks",
+                  executing: "Executing blocks",
+                  warp_sync: "Warp sync: downloading finality proofs",
+                  state_sync: "Syncing state",
+                }[syncPhase.syncPhase]}
+          </div>
+        )}
       </div>
       <h1 className="text-2xl font-bold mb-4">Quantus Miner (Demo)</h1>
 
@@ -711,16 +1577,27 @@ export default function App() {
                 <div className="font-mono break-all">{meta.version}</div>
               </div>
             )}
-            {meta?.chain_spec && (
+            {typeof meta?.specVersion === "number" && (
+              <div>
+                <span className="opacity-70">Runtime</span>
+                <div className="font-mono break-all">
+                  {meta.specName ?? "runtime"} v{meta.specVersion}
+                  {typeof meta.implVersion === "number"
+                    ? ` (impl ${meta.implVersion})`
+                    : ""}
+                </div>
+              </div>
+            )}
+            {meta?.chainSpec && (
               <div>
                 <span className="opacity-70">Chain Spec</span>
-                <div className="font-mono break-all">{meta.chain_spec}</div>
+                <div className="font-mono break-all">{meta.chainSpec}</div>
               </div>
             )}
-            {meta?.node_name && (
+            {meta?.nodeName && (
               <div>
                 <span className="opacity-70">Node name</span>
-                <div className="font-mono break-all">{meta.node_name}</div>
+                <div className="font-mono break-all">{meta.nodeName}</div>
               </div>
             )}
             {meta?.role && (
@@ -735,38 +1612,38 @@ export default function App() {
                 <div className="font-mono break-all">{meta.database}</div>
               </div>
             )}
-            {meta?.local_identity && (
+            {meta?.localIdentity && (
               <div className="col-span-2">
                 <span className="opacity-70">Local identity</span>
-                <div className="font-mono break-all">{meta.local_identity}</div>
+                <div className="font-mono break-all">{meta.localIdentity}</div>
               </div>
             )}
-            {meta?.jsonrpc_addr && (
+            {meta?.jsonrpcAddr && (
               <div>
                 <span className="opacity-70">JSON-RPC</span>
-                <div className="font-mono break-all">{meta.jsonrpc_addr}</div>
+                <div className="font-mono break-all">{meta.jsonrpcAddr}</div>
               </div>
             )}
-            {meta?.prometheus_addr && (
+            {meta?.prometheusAddr && (
               <div>
                 <span className="opacity-70">Prometheus</span>
                 <div className="font-mono break-all">
-                  {meta.prometheus_addr}
+                  {meta.prometheusAddr}
                 </div>
               </div>
             )}
-            {typeof meta?.highest_known_block === "number" && (
+            {typeof meta?.highestKnownBlock === "number" && (
               <div>
                 <span className="opacity-70">Highest known</span>
                 <div className="font-mono break-all">
-                  #{meta.highest_known_block}
+                  #{meta.highestKnownBlock}
                 </div>
               </div>
             )}
             <div className="col-span-2">
               <span className="opacity-70">Rewards address</span>
               <div className="font-mono break-all">
-                {meta.rewards_address ?? account?.address ?? "…"}
+                {meta.rewardsAddress ?? account?.address ?? "…"}
               </div>
             </div>
           </div>
@@ -801,10 +1678,10 @@ export default function App() {
                   <div className="font-mono break-all">{meta.cpu}</div>
                 </div>
               )}
-              {typeof meta?.cpu_cores === "number" && (
+              {typeof meta?.cpuCores === "number" && (
                 <div>
                   <span className="opacity-70">CPU cores</span>
-                  <div className="font-mono break-all">{meta.cpu_cores}</div>
+                  <div className="font-mono break-all">{meta.cpuCores}</div>
                 </div>
               )}
               {meta?.memory && (
@@ -849,13 +1726,28 @@ export default function App() {
             }}
           >
             <option value="resonance">Resonance (testnet)</option>
-            <option value="heisenberg" disabled>
-              Heisenberg (testnet – disabled)
+            <option
+              value="heisenberg"
+              disabled={!!chainUnavailable.heisenberg}
+              title={chainUnavailable.heisenberg ?? undefined}
+            >
+              Heisenberg (testnet
+              {chainUnavailable.heisenberg ? " – not available yet" : ""})
             </option>
-            <option value="quantus" disabled>
-              Quantus (mainnet – disabled)
+            <option
+              value="quantus"
+              disabled={!!chainUnavailable.quantus}
+              title={chainUnavailable.quantus ?? undefined}
+            >
+              Quantus (mainnet
+              {chainUnavailable.quantus ? " – not available yet" : ""})
             </option>
           </select>
+          {chainUnavailable[chain] && (
+            <span className="text-sm opacity-70">
+              {chain} is not available yet: {chainUnavailable[chain]}
+            </span>
+          )}
 
           <div className="basis-full text-sm">
             <div className="opacity-70 flex items-center gap-2">
@@ -876,6 +1768,184 @@ export default function App() {
               {minerPath || "installing…"}
             </div>
           </div>
+          <div className="basis-full text-sm flex items-center gap-2">
+            <span className="opacity-70">App updates</span>
+            <button
+              className="rounded px-2 py-0.5 border text-xs"
+              onClick={onCheckAppUpdate}
+            >
+              Check now
+            </button>
+            <label className="flex items-center gap-1 text-xs">
+              <input
+                type="checkbox"
+                checked={updateAutoCheck}
+                onChange={(e) => {
+                  const v = e.target.checked;
+                  setUpdateAutoCheck(v);
+                  setAppUpdateAutoCheck(v).catch(() => {});
+                }}
+              />
+              Check daily
+            </label>
+            {throttle && (
+              <label
+                className="flex items-center gap-1 text-xs"
+                title={
+                  throttlePolicy === "unavailable"
+                    ? "Neither CPU temperature nor load is readable on this system"
+                    : `Steps external miner cores down above ${
+                        throttlePolicy === "temperature"
+                          ? `${throttle.max_temp_c} °C`
+                          : `load ${throttle.max_load_per_cpu}/CPU`
+                      } and back up when it cools`
+                }
+              >
+                <input
+                  type="checkbox"
+                  checked={throttle.enabled}
+                  disabled={throttlePolicy === "unavailable"}
+                  onChange={(e) => {
+                    const next = { ...throttle, enabled: e.target.checked };
+                    setThrottle(next);
+                    setThrottleSettings(next).catch((err) =>
+                      showToast(`Throttle: ${String(err)}`),
+                    );
+                  }}
+                />
+                Throttle when hot
+              </label>
+            )}
+            {adaptive && (
+              <label
+                className="flex items-center gap-1 text-xs"
+                title={`Enables Safe Sync after more than ${adaptive.max_trouble_events} slow imports/sync timeouts within ${adaptive.window_secs}s, and disables it after ${adaptive.healthy_secs}s without trouble`}
+              >
+                <input
+                  type="checkbox"
+                  checked={adaptive.enabled}
+                  onChange={(e) => {
+                    const next = { ...adaptive, enabled: e.target.checked };
+                    setAdaptive(next);
+                    setAdaptiveSafeMode(next).catch((err) =>
+                      showToast(`Adaptive Safe Sync: ${String(err)}`),
+                    );
+                  }}
+                />
+                Adaptive Safe Sync
+              </label>
+            )}
+          </div>
+          <div className="basis-full text-sm">
+            <div className="opacity-70">Folders</div>
+            <div className="flex flex-wrap items-center gap-2">
+              {(
+                [
+                  ["data_dir", "Node data"],
+                  ["chain_db", "Chain database"],
+                  ["network_key_dir", "Node key"],
+                  ["logs_dir", "Logs"],
+                  ["app_data_dir", "App data"],
+                  ["account_dir", "Account"],
+                ] as [PathKind, string][]
+              ).map(([kind, label]) => (
+                <button
+                  key={kind}
+                  className="rounded px-2 py-0.5 border text-xs"
+                  onClick={async () => {
+                    const c = chain === "quantus" ? "resonance" : chain;
+                    try {
+                      showToast(`Opened ${await openPath(kind, c)}`);
+                    } catch (e) {
+                      showToast(String(e));
+                    }
+                  }}
+                >
+                  {label}
+                </button>
+              ))}
+            </div>
+          </div>
+          <div className="basis-full text-sm">
+            <div className="opacity-70">Install directory</div>
+            <div className="flex items-center gap-2">
+              <input
+                className="border rounded px-2 py-1 font-mono text-xs flex-1"
+                value={installDirDraft}
+                onChange={(e) => setInstallDirDraft(e.target.value)}
+              />
+              <button
+                className="rounded px-2 py-0.5 border text-xs"
+                disabled={
+                  status !== "Idle" ||
+                  !installDirDraft.trim() ||
+                  installDirDraft.trim() === installDir
+                }
+                onClick={() => applyInstallDir(installDirDraft.trim())}
+              >
+                Move
+              </button>
+              <button
+                className="rounded px-2 py-0.5 border text-xs"
+                disabled={status !== "Idle"}
+                onClick={() => applyInstallDir(null)}
+              >
+                Default
+              </button>
+            </div>
+          </div>
+          <div className="basis-full text-sm">
+            <div
+              className="opacity-70"
+              title="A node data folder copied from a synced machine (with chains/<id>), or its chains/<id> folder. The node key is not copied."
+            >
+              Import chain data
+            </div>
+            <div className="flex items-center gap-2">
+              <input
+                className="border rounded px-2 py-1 font-mono text-xs flex-1"
+                placeholder="~/quantus-node-copy"
+                value={importSource}
+                onChange={(e) => setImportSource(e.target.value)}
+              />
+              <label className="flex items-center gap-1 text-xs">
+                <input
+                  type="checkbox"
+                  checked={importOverwrite}
+                  onChange={(e) => setImportOverwrite(e.target.checked)}
+                />
+                Replace existing
+              </label>
+              <button
+                className="rounded px-2 py-0.5 border text-xs"
+                disabled={importing || !importSource.trim()}
+                onClick={async () => {
+                  const c = chain === "quantus" ? "resonance" : chain;
+                  setImporting(true);
+                  try {
+                    const r = await withAppPin(
+                      (pin) =>
+                        importChainData(importSource.trim(), c, importOverwrite, false, pin),
+                      askPin,
+                    );
+                    setImportSource("");
+                    showToast(
+                      `Imported ${(r.bytes / 1e9).toFixed(1)} GB into ${r.destination}${
+                        r.restarted ? "; node restarted" : ""
+                      }`,
+                    );
+                  } catch (e: any) {
+                    showToast(String(e?.message ?? e));
+                  } finally {
+                    setImporting(false);
+                    setImportProgress(null);
+                  }
+                }}
+              >
+                {importing ? "Importing…" : "Import"}
+              </button>
+            </div>
+          </div>
           <div className="basis-full text-sm">
             <div className="opacity-70 flex items-center gap-2">
               <span>Account JSON</span>
@@ -977,7 +2047,37 @@ export default function App() {
                   disabled={!useExternalMiner}
                 />
               </label>
+              {benchRunning ? (
+                <button
+                  className="rounded px-2 py-0.5 border text-xs"
+                  onClick={() => cancelBenchmark().catch(() => {})}
+                >
+                  Cancel benchmark
+                  {benchProgress
+                    ? ` (${benchProgress.cores} cores, ${benchProgress.hps.toFixed(0)} H/s)`
+                    : ""}
+                </button>
+              ) : (
+                <button
+                  className="rounded px-2 py-0.5 border text-xs"
+                  onClick={onBenchmark}
+                  disabled={mining}
+                  title="Run the external miner for 30 s per core count (stop mining first)"
+                >
+                  Benchmark
+                </button>
+              )}
             </div>
+            {benchResults && (
+              <div className="opacity-70 text-xs mt-1">
+                {benchResults.map((r) => (
+                  <div key={r.cores}>
+                    {r.cores} cores: avg {r.avg_hps.toFixed(0)} H/s (min{" "}
+                    {r.min_hps.toFixed(0)}, max {r.max_hps.toFixed(0)})
+                  </div>
+                ))}
+              </div>
+            )}
             <div className="opacity-70 flex items-center gap-2 mt-2">
               <span>Planned command</span>
               <button
@@ -1022,13 +2122,42 @@ export default function App() {
           </div>
 
           {!mining ? (
-            <button className="rounded-xl px-3 py-2 border" onClick={onStart}>
+            <button
+              className="rounded-xl px-3 py-2 border"
+              onClick={() => onStart()}
+            >
               Start
             </button>
           ) : (
-            <button className="rounded-xl px-3 py-2 border" onClick={onStop}>
-              Stop
-            </button>
+            <>
+              <button className="rounded-xl px-3 py-2 border" onClick={onStop}>
+                Stop
+              </button>
+              <button
+                className="rounded-xl px-3 py-2 border"
+                title={
+                  status === "Paused"
+                    ? "Resume mining"
+                    : useExternalMiner
+                      ? "Stop the external miner; the node keeps syncing"
+                      : "Restart the node without --validator (it reconnects and catches up); use an external miner for instant pause"
+                }
+                onClick={async () => {
+                  const c = chain === "quantus" ? "resonance" : chain;
+                  try {
+                    if (status === "Paused") {
+                      await resumeMining(c);
+                    } else {
+                      await pauseMining(c);
+                    }
+                  } catch (e) {
+                    showToast(String(e));
+                  }
+                }}
+              >
+                {status === "Paused" ? "Resume" : "Pause"}
+              </button>
+            </>
           )}
 
           <button
@@ -1046,6 +2175,22 @@ export default function App() {
             Resync
           </button>
 
+          <button
+            className="rounded-xl px-3 py-2 border"
+            onClick={async () => {
+              try {
+                const c = chain === "quantus" ? "resonance" : chain;
+                await navigator.clipboard.writeText(await getNodeReportMarkdown(c));
+                showToast("Report copied");
+              } catch (e) {
+                showToast(`Report: ${String(e)}`);
+              }
+            }}
+            title="Copy versions, paths, endpoints and status for a support request"
+          >
+            Copy report
+          </button>
+
           <button
             className="rounded-xl px-3 py-2 border"
             onClick={async () => {
@@ -1056,7 +2201,12 @@ export default function App() {
                 try {
                   // invoke backend unlock command
                   const { invoke } = await import("@tauri-apps/api/core");
-                  await invoke("unlock_miner");
+                  const restarted = await invoke<boolean>("unlock_miner", {
+                    chain,
+                  });
+                  if (!restarted) {
+                    showToast("LOCK file cleared. Press Start to run the node.");
+                  }
                 } catch (e) {
                   console.error("unlock_miner failed", e);
                 }
@@ -1090,6 +2240,20 @@ export default function App() {
               value={lineLimit}
               onChange={(e) => setLineLimit(Number(e.target.value) || 0)}
             />
+            <span className="text-xs opacity-70">Events</span>
+            <select
+              className="border rounded px-2 py-1 text-xs"
+              value={eventSeverity}
+              onChange={(e) =>
+                setEventSeverityState(e.target.value as EventSeverity)
+              }
+              title="Minimum severity of parsed node events (hashrate, blocks, errors)"
+            >
+              <option value="debug">Debug</option>
+              <option value="info">Info</option>
+              <option value="warn">Warn</option>
+              <option value="error">Error</option>
+            </select>
             <button
               className="rounded px-2 py-1 border text-xs"
               title="Clear console and forget stored lines"
@@ -1131,6 +2295,345 @@ export default function App() {
             {logs.join("\n")}
           </pre>
         </div>
+        {appUpdate?.updateAvailable && (
+          <div className="rounded-2xl shadow p-4 mb-4 border border-blue-500 text-sm">
+            <div className="font-medium">
+              Quantus Miner v{appUpdate.latest} is available (you have v
+              {appUpdate.current})
+            </div>
+            {appUpdate.notes && (
+              <pre className="mt-1 max-h-40 overflow-auto whitespace-pre-wrap text-xs opacity-80">
+                {appUpdate.notes}
+              </pre>
+            )}
+            <div className="mt-2 flex gap-2">
+              {appUpdate.downloadUrl && (
+                <button
+                  className="rounded px-2 py-0.5 border text-xs"
+                  onClick={() => openUrl(appUpdate.downloadUrl!)}
+                >
+                  Download
+                </button>
+              )}
+              <button
+                className="rounded px-2 py-0.5 border text-xs"
+                onClick={() => setAppUpdate(null)}
+              >
+                Dismiss
+              </button>
+            </div>
+          </div>
+        )}
+        {backupWarning && (
+          <div className="rounded-2xl shadow p-4 mb-4 border border-amber-500 text-sm">
+            <div className="font-medium">Your rewards account is not backed up</div>
+            <div className="mt-1">
+              If this machine is lost, so are the rewards mined to{" "}
+              <span className="font-mono break-all">{backupWarning.address}</span>.
+              Write down the secret phrase from the account file:
+            </div>
+            <div className="font-mono break-all">{backupWarning.path}</div>
+            <div className="mt-2 flex gap-2">
+              <button
+                className="rounded px-2 py-0.5 border text-xs"
+                onClick={() => revealItemInDir(backupWarning.path)}
+              >
+                Open account file
+              </button>
+              <button
+                className="rounded px-2 py-0.5 border text-xs"
+                onClick={async () => {
+                  if (!confirm("Have you written down the secret phrase and stored it safely?"))
+                    return;
+                  try {
+                    await markAccountBackedUp();
+                    setBackupWarning(null);
+                  } catch (e) {
+                    showToast(`Backup: ${String(e)}`);
+                  }
+                }}
+              >
+                I wrote it down
+              </button>
+              <button
+                className="rounded px-2 py-0.5 border text-xs"
+                onClick={() => setBackupWarning(null)}
+              >
+                Later
+              </button>
+            </div>
+          </div>
+        )}
+        {securityWarning && (
+          <div className="rounded-2xl shadow p-4 mb-4 border border-red-500 text-sm">
+            <div className="font-medium">
+              Node RPC is reachable from other machines
+            </div>
+            {securityWarning.addrs.length > 0 && (
+              <div className="font-mono break-all">
+                {securityWarning.addrs.join(", ")}
+              </div>
+            )}
+            {securityWarning.flags.length > 0 && (
+              <div className="opacity-70">
+                Flags: {securityWarning.flags.join(" ")}
+              </div>
+            )}
+            <div className="mt-1">
+              Anyone who can reach it can use {securityWarning.exposes}.
+            </div>
+            <div className="mt-2 flex gap-2">
+              <button
+                className="rounded px-2 py-0.5 border text-xs"
+                onClick={async () => {
+                  const c = securityWarning.chain;
+                  setSecurityWarning(null);
+                  try {
+                    await restartWithSafeRpc(c);
+                  } catch (e) {
+                    showToast(`Restart failed: ${String(e)}`);
+                  }
+                }}
+              >
+                Restart with local-only RPC
+              </button>
+              <button
+                className="rounded px-2 py-0.5 border text-xs"
+                onClick={() => setSecurityWarning(null)}
+              >
+                Dismiss
+              </button>
+            </div>
+          </div>
+        )}
+        {orphans.map((o) => (
+          <div
+            key={o.chain}
+            className="rounded-2xl shadow p-4 mb-4 border border-amber-500 text-sm"
+          >
+            <div className="font-medium">
+              A {o.chain} node from a previous session is still running
+            </div>
+            <div className="opacity-70">
+              pid {o.pid}, RPC {o.rpc_url}, started{" "}
+              {new Date(o.started_at * 1000).toLocaleString()}
+            </div>
+            <div className="mt-1">
+              Taking over lets you stop, restart and repair it again; its log
+              output isn't available until it is restarted.
+            </div>
+            <div className="mt-2 flex gap-2">
+              <button
+                className="rounded px-2 py-0.5 border text-xs"
+                onClick={async () => {
+                  setOrphans((l) => l.filter((x) => x.chain !== o.chain));
+                  try {
+                    await adoptOrphanedNode(o.chain);
+                    if (o.chain !== chain) setChain(o.chain as Chain);
+                  } catch (e) {
+                    showToast(`Take over failed: ${String(e)}`);
+                  }
+                }}
+              >
+                Take over management
+              </button>
+              <button
+                className="rounded px-2 py-0.5 border text-xs"
+                onClick={async () => {
+                  setOrphans((l) => l.filter((x) => x.chain !== o.chain));
+                  try {
+                    await adoptOrphanedNode(o.chain);
+                    await stopMiner(o.chain);
+                  } catch (e) {
+                    showToast(`Stop failed: ${String(e)}`);
+                  }
+                }}
+              >
+                Stop it
+              </button>
+              <button
+                className="rounded px-2 py-0.5 border text-xs"
+                onClick={() => {
+                  setOrphans((l) => l.filter((x) => x.chain !== o.chain));
+                  dismissOrphanedNode(o.chain).catch(() => {});
+                }}
+              >
+                Leave running
+              </button>
+            </div>
+          </div>
+        ))}
+        {duplicateIdentity && (
+          <div className="rounded-2xl shadow p-4 mb-4 border border-red-500 text-sm">
+            <div className="font-medium">
+              Another node is using this node's identity
+            </div>
+            {duplicateIdentity.peer_id && (
+              <div className="font-mono break-all">
+                {duplicateIdentity.peer_id}
+              </div>
+            )}
+            <div className="mt-1">{duplicateIdentity.explanation}</div>
+            <div className="mt-2 flex gap-2">
+              <button
+                className="rounded px-2 py-0.5 border text-xs"
+                onClick={async () => {
+                  const c = duplicateIdentity.chain;
+                  setDuplicateIdentity(null);
+                  try {
+                    const backup = await withAppPin((pin) => regenerateNodeKey(c, pin), askPin);
+                    showToast(`New node key generated; old key kept at ${backup}`);
+                  } catch (e: any) {
+                    showToast(`Regenerating node key failed: ${String(e?.message ?? e)}`);
+                  }
+                }}
+              >
+                Regenerate node key
+              </button>
+              <button
+                className="rounded px-2 py-0.5 border text-xs"
+                onClick={() => setDuplicateIdentity(null)}
+              >
+                Dismiss
+              </button>
+            </div>
+          </div>
+        )}
+        {setupError && (
+          <div className="rounded-2xl shadow p-4 mb-4 border border-red-500 text-sm">
+            <div className="font-medium">
+              {setupError.kind === "no_asset_for_platform"
+                ? "No quantus-node build for this platform"
+                : setupError.kind === "rate_limited"
+                  ? "GitHub rate limit reached"
+                  : "Couldn't install quantus-node"}
+            </div>
+            <div className="opacity-70 break-all">{setupError.message}</div>
+            {setupError.kind === "no_asset_for_platform" &&
+              setupError.installed_version && (
+                <div className="mt-1">
+                  Keep using the installed {setupError.installed_version}, or
+                  check back once a build for this platform is released.
+                </div>
+              )}
+            {setupError.kind === "rate_limited" && setupError.reset_at && (
+              <div className="mt-1">
+                Try again after{" "}
+                {new Date(setupError.reset_at * 1000).toLocaleTimeString()}.
+              </div>
+            )}
+            <div className="mt-2 flex gap-2">
+              {((setupError.kind === "network" && setupError.retryable) ||
+                setupError.kind === "rate_limited" ||
+                setupError.kind === "extraction_failed") && (
+                <button
+                  className="rounded px-2 py-0.5 border text-xs"
+                  onClick={() => {
+                    setSetupError(null);
+                    setSetupAttempt((n) => n + 1);
+                  }}
+                >
+                  Retry
+                </button>
+              )}
+              <button
+                className="rounded px-2 py-0.5 border text-xs"
+                onClick={() => setSetupError(null)}
+              >
+                Dismiss
+              </button>
+            </div>
+          </div>
+        )}
+        {quarantine && (
+          <div className="rounded-2xl shadow p-4 mb-4 border border-red-500 text-sm">
+            <div className="font-medium">Binary blocked by the OS</div>
+            <div className="font-mono break-all">{quarantine.path}</div>
+            <div className="opacity-70">{quarantine.detail}</div>
+            <div className="mt-1">{quarantine.remediation}</div>
+            <div className="mt-2 flex gap-2">
+              {quarantine.fixable && (
+                <button
+                  className="rounded px-2 py-0.5 border text-xs"
+                  onClick={() => onStart(true)}
+                >
+                  Unquarantine and retry
+                </button>
+              )}
+              <button
+                className="rounded px-2 py-0.5 border text-xs"
+                onClick={() => setQuarantine(null)}
+              >
+                Dismiss
+              </button>
+            </div>
+          </div>
+        )}
+        {remediation && (
+          <div className="fixed bottom-16 right-4 z-50 max-w-md rounded px-3 py-2 bg-gray-800 text-white shadow">
+            <div className="text-sm mb-2">{remediation.detail}</div>
+            <div className="flex flex-wrap gap-2">
+              {remediation.actions.map((a) => (
+                <button
+                  key={a.action}
+                  className="px-2 py-1 rounded bg-blue-600 disabled:opacity-50"
+                  disabled={!a.available}
+                  title={a.blockedBy ?? undefined}
+                  onClick={async () => {
+                    setRemediation(null);
+                    try {
+                      // Repair goes through its confirmation and progress handling
+                      if (a.action === "repair") await onRepair();
+                      else if (a.url) await openUrl(a.url);
+                      else await withAppPin((pin) => runRemediation(a, pin), askPin);
+                    } catch (err: any) {
+                      showToast(`${a.label}: ${String(err?.message ?? err)}`);
+                    }
+                  }}
+                >
+                  {a.label}
+                </button>
+              ))}
+              <button className="px-2 py-1 rounded bg-gray-600" onClick={() => setRemediation(null)}>
+                Dismiss
+              </button>
+            </div>
+          </div>
+        )}
+        {safeModeCountdown && (
+          <div className="fixed bottom-28 right-4 z-50 max-w-md rounded px-3 py-2 bg-gray-800 text-white shadow">
+            <div className="text-sm mb-2">
+              {safeModeCountdown.enable
+                ? `Restarting the node with safe sync (--max-blocks-per-request 1)${
+                    safeModeCountdown.rangeStart !== null
+                      ? ` for heavy blocks #${safeModeCountdown.rangeStart}-#${safeModeCountdown.rangeEnd}`
+                      : " because sync keeps stalling"
+                  }`
+                : "Restarting the node without safe sync"}{" "}
+              at {new Date(safeModeCountdown.deadline).toLocaleTimeString()}.
+            </div>
+            <div className="flex flex-wrap gap-2">
+              <button
+                className="px-2 py-1 rounded bg-blue-600"
+                onClick={() => {
+                  setSafeModeCountdown(null);
+                  confirmSafeMode(true, safeModeCountdown.chain).catch((e) => showToast(String(e)));
+                }}
+              >
+                Restart now
+              </button>
+              <button
+                className="px-2 py-1 rounded bg-gray-600"
+                onClick={() => {
+                  setSafeModeCountdown(null);
+                  confirmSafeMode(false, safeModeCountdown.chain).catch((e) => showToast(String(e)));
+                }}
+              >
+                Cancel
+              </button>
+            </div>
+          </div>
+        )}
         {toast && (
           <div className="fixed bottom-4 right-4 z-50 rounded px-3 py-2 bg-red-600 text-white shadow">
             {toast}
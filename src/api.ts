@@ -1,68 +1,951 @@
 import { invoke } from "@tauri-apps/api/core";
 import { listen } from "@tauri-apps/api/event";
 
-export async function ensureMinerAndAccount(): Promise<{
+/** Why installing quantus-node failed (error of ensureMinerAndAccount). */
+export type SetupError = { message: string } & (
+  | {
+      kind: "no_asset_for_platform";
+      available: string[];
+      // `--version` of a copy already on disk, if any
+      installed_version: string | null;
+    }
+  | { kind: "network"; retryable: boolean }
+  | { kind: "rate_limited"; reset_at: number | null }
+  | { kind: "extraction_failed" }
+  | { kind: "verification_failed" }
+  // stopped through cancelOperation
+  | { kind: "cancelled" }
+  | { kind: "other" }
+);
+export function setupErrorMessage(e: unknown): string {
+  return (e as SetupError)?.message ?? String(e);
+}
+
+/**
+ * Install quantus-node (and with `includeExternal` the external miner, concurrently)
+ * and load the rewards account. Only the node failing rejects; an external miner
+ * failure comes back as `externalMinerError`. Runs as an "install" operation; pass
+ * `opId` to cancel it with `cancelOperation` without waiting for `operation:started`.
+ */
+export async function ensureMinerAndAccount(
+  includeExternal = false,
+  opId?: string,
+): Promise<{
+  minerPath: string;
+  accountJsonPath: string;
+  // address as created; per-chain form via getAccountAddress
+  account: { address: string; backed_up?: boolean; account_id?: string | null };
+  externalMinerPath: string | null;
+  externalMinerError: SetupError | null;
+  /** Set when an earlier run was cut short: the last step it finished. */
+  resumedAfter: "node_installed" | "external_miner_installed" | "account_ready" | null;
+}> {
+  return await invoke("ensure_miner_and_account", { includeExternal, opId });
+}
+
+/** The rewards account file was empty or unreadable. */
+export type AccountCorrupt = {
+  path: string;
+  reason: string;
+  // where it was moved; null while waiting for confirmAccountReset
+  quarantined: string | null;
+  may_hold_key: boolean;
+};
+export function onAccountCorrupt(cb: (a: AccountCorrupt) => void) {
+  return listen<AccountCorrupt>("account:corrupt", (e) => cb(e.payload));
+}
+/** Sent on a start (at most daily) while the account was never backed up. */
+export type AccountNotBackedUp = {
+  chain: string;
+  address: string;
+  // the account file holding the secret phrase
+  path: string;
+  ts: number;
+};
+export function onAccountNotBackedUp(cb: (a: AccountNotBackedUp) => void) {
+  return listen<AccountNotBackedUp>("account:not-backed-up", (e) =>
+    cb(e.payload),
+  );
+}
+/** The user confirmed writing the secret phrase down; stops the reminders. */
+export async function markAccountBackedUp(): Promise<void> {
+  return await invoke("mark_account_backed_up");
+}
+/** Set an unreadable (possibly key-holding) account file aside and create a new one. */
+export async function confirmAccountReset(): Promise<{
   minerPath: string;
   accountJsonPath: string;
   account: { address: string };
 }> {
-  return await invoke("ensure_miner_and_account");
+  return await invoke("confirm_account_reset");
 }
 
 export type MinerEvent =
   | { type: "Connected" }
   | { type: "Hashrate"; hps: number }
   | { type: "ShareAccepted" }
-  | { type: "FoundBlock"; height?: number; hash?: string }
-  | { type: "Error"; message: string };
+  | {
+      type: "FoundBlock";
+      height?: number;
+      hash?: string;
+      // seen imported with our account as PoW author (not just a log line)
+      verified: boolean;
+    }
+  | { type: "Error"; message: string; severity: "warn" | "error" }
+  | { type: "SlowImport"; block?: number | null; seconds: number }
+  | { type: "ImportBacklog"; queued?: number | null }
+  | {
+      type: "NodeStatus";
+      state: "idle" | "syncing" | "preparing";
+      peers?: number | null;
+      best?: number | null;
+      finalized?: number | null;
+      target?: number | null;
+      // blocks per second; idle lines have none
+      bps?: number | null;
+    };
+
+export type EventSeverity = "debug" | "info" | "warn" | "error";
+/** Events below `level` are no longer sent as miner:event (default "info"). */
+export async function setEventSeverity(level: EventSeverity) {
+  return await invoke("set_event_severity", { level });
+}
 
 export function onMinerEvent(cb: (ev: MinerEvent) => void) {
   return listen<MinerEvent>("miner:event", (e) => cb(e.payload));
 }
-export function onMinerLog(cb: (line: string) => void) {
-  return listen<{ source: string; line: string }>("miner:log", (e) =>
-    cb(e.payload.line),
-  );
+/** Event timestamps (`ts`, `checked_at`, ...) are UTC milliseconds since the epoch. */
+export type LogMsg = { source: string; line: string; ts: number };
+export function onMinerLog(cb: (line: string, ts: number) => void) {
+  return listen<LogMsg>("miner:log", (e) => cb(e.payload.line, e.payload.ts));
 }
 
 export type MinerStatus = {
   peers?: number | null;
-  current_block?: number | null;
-  highest_block?: number | null;
-  is_syncing?: boolean | null;
+  currentBlock?: number | null;
+  highestBlock?: number | null;
+  isSyncing?: boolean | null;
+  chain?: string | null;
+  finalizedBlock?: number | null;
+  // where peers/best/is_syncing came from; node log lines fill in until RPC answers
+  source?: "rpc" | "log" | "mixed" | null;
+  // best / network height, clamped to 100 by the backend
+  syncPercent?: number | null;
+  // UTC ms of the last successful local RPC reply
+  lastRpcSuccessTs?: number | null;
+  // one-off after Repair wiped the database: drop cached heights/peers
+  dbWiped?: boolean;
+  // the local RPC port answers as a different node; its values are ignored
+  foreignNode?: boolean;
+  // producing blocks / paused by pauseMining
+  mining?: boolean;
+  paused?: boolean;
+  // open libp2p connections by direction, from the node's Prometheus exporter
+  inboundPeers?: number | null;
+  outboundPeers?: number | null;
+  // no inbound connection for 10 min while outbound ones work: likely behind NAT
+  natSuspected?: boolean;
+  // what syncing is bound by right now; null when not syncing or not known yet
+  syncPhase?: SyncPhase | null;
+  // the block being executed, while syncPhase is "executing"
+  heavyBlock?: { number: number; elapsedSecs: number } | null;
+};
+export type SyncPhase = "downloading" | "executing" | "warp_sync" | "state_sync";
+/** Dashboard state persisted by the backend (last_state.json), per chain. */
+export type LastSnapshot = {
+  // unix seconds
+  saved_at: number;
+  phase: { phase: string } | null;
+  active_chain: string | null;
+  chains: Record<
+    string,
+    {
+      status: MinerStatus | null;
+      meta: Partial<MinerMeta> | null;
+      balance: BalanceView | null;
+      hashrate: number | null;
+      authored: number;
+      last_exit?: NodeExit | null;
+      // dead forks found (fork.rs)
+      forks_detected?: number;
+    }
+  >;
 };
+/** Last persisted state, to paint (greyed out) before live events arrive. */
+export async function getLastSnapshot(): Promise<LastSnapshot | null> {
+  return await invoke("get_last_snapshot");
+}
 export type MinerState = {
   running?: boolean;
-  phase?: "starting" | "running" | "stopped";
+  phase?: "starting" | "running" | "stopping" | "stopped" | "stop_failed";
+  chain?: string | null;
+  // with "running": a node taken over from a previous GUI instance
+  adopted?: boolean;
+  // with "stop_failed": why the node couldn't be stopped
+  error?: string;
 };
 export function onMinerState(cb: (s: MinerState) => void) {
   return listen<MinerState>("miner:state", (e) => cb(e.payload));
 }
 
+/** The node exited during its startup grace period (start_miner also fails). */
+export type StartFailure = {
+  chain: string;
+  exit_code: number | null;
+  kind:
+    | "port_in_use"
+    | "db_corrupt"
+    | "db_locked"
+    | "disk_full"
+    | "bad_argument"
+    | "missing_library"
+    | "panic"
+    | "exited";
+  reason: string;
+  lines: string[]; // last stderr lines
+};
+export function onMinerStartFailed(cb: (f: StartFailure) => void) {
+  return listen<StartFailure>("miner:start-failed", (e) => cb(e.payload));
+}
+
+/**
+ * The chain folder holds another network's data (start_miner also fails with the same
+ * message). Offer Repair (wipe) or a different data folder (`--base-path`).
+ */
+export type ChainDataMismatch = {
+  chain: string;
+  chain_dir: string;
+  expected: string;
+  found: string;
+  expected_genesis: string | null;
+  found_genesis: string;
+  remediations: ("repair" | "data_dir")[];
+};
+export function onChainMismatch(cb: (m: ChainDataMismatch) => void) {
+  return listen<ChainDataMismatch>("miner:chain-mismatch", (e) => cb(e.payload));
+}
+/** The node logged its whole startup banner (version, chain spec, identity, RPC addr). */
+export type NodeStarted = { chain: string; meta: MinerMeta; took_ms: number };
+export function onMinerStarted(cb: (s: NodeStarted) => void) {
+  return listen<NodeStarted>("miner:started", (e) => cb(e.payload));
+}
+/** The banner was still incomplete after the timeout; `missing` names the fields. */
+export type StartDegraded = {
+  chain: string;
+  missing: ("version" | "chain_spec" | "local_identity" | "jsonrpc_addr")[];
+  waited_ms: number;
+};
+export function onMinerStartDegraded(cb: (d: StartDegraded) => void) {
+  return listen<StartDegraded>("miner:start-degraded", (e) => cb(e.payload));
+}
+
+/** Poll of the external miner's HTTP status endpoint (every 10 s while it runs). */
+export type ExternalWorker = { id: string; hashrate: number | null; accepted: number | null };
+export type ExternalStatus = {
+  chain: string;
+  port: number;
+  healthy: boolean;
+  version: string | null;
+  hashrate: number | null;
+  accepted: number | null;
+  workers: ExternalWorker[];
+  error: string | null;
+  ts: number;
+};
+export function onExternalStatus(cb: (s: ExternalStatus) => void) {
+  return listen<ExternalStatus>("miner:external-status", (e) => cb(e.payload));
+}
+
+/** How a stopped node went away. */
+export type NodeExit =
+  | { kind: "exited_gracefully"; code: number | null }
+  | { kind: "killed" }
+  | { kind: "already_dead" };
+export type NodeExited = {
+  chain: string;
+  exit: NodeExit;
+  took_ms: number;
+  grace_secs: number;
+  ts: number;
+};
+export function onMinerExited(cb: (e: NodeExited) => void) {
+  return listen<NodeExited>("miner:exited", (e) => cb(e.payload));
+}
+/** Seconds a node gets to exit after SIGINT before it is killed (default 15). */
+export type ShutdownSettings = { grace_secs: number };
+export async function getShutdownSettings(): Promise<ShutdownSettings> {
+  return await invoke("get_shutdown_settings");
+}
+export async function setShutdownSettings(settings: ShutdownSettings) {
+  return await invoke("set_shutdown_settings", { settings });
+}
+
+/** A cancellable long-running command (install, repair) that is running. */
+export type Operation = {
+  id: string;
+  kind: "install" | "repair";
+  chain: string | null;
+  startedAt: number;
+};
+/** Sent when an operation begins; `id` is what `cancelOperation` takes. */
+export function onOperationStarted(cb: (o: Operation) => void) {
+  return listen<Operation>("operation:started", (e) => cb(e.payload));
+}
+/** Terminal event of a cancelled operation, once its partial state is cleaned up. */
+export function onOperationCancelled(cb: (o: Operation) => void) {
+  return listen<Operation>("operation:cancelled", (e) => cb(e.payload));
+}
+/** Stop an operation at its next chunk or step; false if it isn't running. */
+export async function cancelOperation(id: string): Promise<boolean> {
+  return await invoke("cancel_operation", { id });
+}
+export async function listOperations(): Promise<Operation[]> {
+  return await invoke("list_operations");
+}
+
+/** First start and first full sync of a chain on this installation (unix seconds). */
+export type ChainMilestones = {
+  firstStartAt: number | null;
+  firstSyncedAt: number | null;
+  // firstSyncedAt - firstStartAt
+  syncWallSecs: number | null;
+  // node runtime until then
+  syncRuntimeSecs: number;
+  repairsBeforeSync: number;
+};
+export async function getChainMilestones(
+  chain?: string,
+): Promise<ChainMilestones> {
+  return await invoke("get_chain_milestones", { chain });
+}
+/** The first full sync of a chain on this installation; sent once. */
+export type SyncComplete = ChainMilestones & {
+  chain: string;
+  bestBlock: number | null;
+  ts: number;
+};
+export function onSyncComplete(cb: (s: SyncComplete) => void) {
+  return listen<SyncComplete>("miner:sync-complete", (e) => cb(e.payload));
+}
+
+/** GUI preferences kept by the backend (settings.json). */
+export type Settings = {
+  chain: string;
+  // the extra-args field as typed
+  extra_args: string;
+  external_miner_enabled: boolean;
+  external_miner_cores: number | null;
+  external_miner_port: number | null;
+  /** Threads for the node's built-in miner (null: the node's default). */
+  internal_mining_threads: number | null;
+  log_to_file: boolean;
+  auto_start: boolean;
+  resume_session: boolean;
+  theme: "system" | "light" | "dark";
+  line_limit: number;
+  // POST target for miner:authored events; empty = off
+  webhook_url: string;
+  /** Seconds `queryBalance` answers from its cache; 0 = never. */
+  balance_ttl_secs: number;
+  /** Opt-in anonymous daily ping (version, OS, chain, synced yes/no); off by default. */
+  usage_ping: boolean;
+  /** https endpoint for the ping; nothing is sent while empty. */
+  usage_ping_url: string;
+  /** Seconds an automatic safe sync restart can be vetoed for (max 300); 0 = apply at once. */
+  safe_mode_countdown_secs: number;
+  /** Mined to instead of the account's address (setRewardsAddress); "" = the account's. */
+  rewards_address: string;
+  /** Earlier rewards addresses whose balances stay visible. */
+  watched_addresses: string[];
+  /** An app PIN is set (setAppPin); read-only here. */
+  pin_required: boolean;
+};
+export async function getSettings(): Promise<Settings> {
+  return await invoke("get_settings");
+}
+/** Change some fields; fine per keystroke (writes are debounced). */
+export async function setSettings(
+  patch: Partial<Omit<Settings, "pin_required">>,
+): Promise<Settings> {
+  return await invoke("set_settings", { patch });
+}
+/** Error of a command behind the app PIN (repair, node key, overwriting import). */
+export type GuardedError = { message: string } & (
+  | { kind: "pin_required" }
+  // wrong PIN; with retry_after_secs, attempts are refused until then
+  | { kind: "pin_invalid"; retry_after_secs: number | null }
+  | { kind: "other" }
+);
+/**
+ * Run a PIN-gated call, first without a PIN, then with what `ask` returns while the PIN
+ * is missing or wrong. `ask` returning null (or a delay) throws the last error.
+ */
+export async function withAppPin<T>(
+  run: (pin?: string) => Promise<T>,
+  ask: (e: GuardedError) => string | null,
+): Promise<T> {
+  let pin: string | undefined;
+  for (;;) {
+    try {
+      return await run(pin);
+    } catch (e) {
+      const g = e as GuardedError;
+      const retry =
+        g?.kind === "pin_required" || (g?.kind === "pin_invalid" && g.retry_after_secs == null);
+      const answer = retry ? ask(g) : null;
+      if (answer == null) throw e;
+      pin = answer;
+    }
+  }
+}
+/** Set the app PIN (4-128 characters); replacing one needs `currentPin`. */
+export async function setAppPin(pin: string, currentPin?: string): Promise<void> {
+  return await invoke("set_app_pin", { pin, currentPin });
+}
+/** Remove the app PIN; needs the current one. */
+export async function clearAppPin(pin: string): Promise<void> {
+  return await invoke("clear_app_pin", { pin });
+}
+/** The fields a setSettings call changed, for every view to pick up. */
+export type SettingsChanged = { changed: Partial<Settings>; ts: number };
+export function onSettingsChanged(cb: (c: SettingsChanged) => void) {
+  return listen<SettingsChanged>("settings:changed", (e) => cb(e.payload));
+}
+/** Writing settings.json failed (e.g. disk full); changes are kept in memory. */
+export type SettingsFlushFailed = { path: string; error: string; ts: number };
+export function onSettingsFlushFailed(cb: (f: SettingsFlushFailed) => void) {
+  return listen<SettingsFlushFailed>("settings:flush-failed", (e) =>
+    cb(e.payload),
+  );
+}
+
+/** What support needs about a chain's node in one document; secrets redacted. */
+/** Where an installed binary came from, checked against the file (`unknown`: no record, e.g. user-provided). */
+export type ProvenanceCheck = {
+  status: "verified" | "modified" | "unknown";
+  sha256: string;
+  record: {
+    sourceUrl: string;
+    releaseTag: string;
+    asset: string;
+    sha256: string;
+    installedAt: number;
+    guiVersion: string;
+  } | null;
+};
+/** Portable mode (`--portable` or `portable.marker` beside the exe) and standard directories the OS didn't provide. */
+export type PathsReport = {
+  portable: boolean;
+  base: string;
+  fallbacks: {
+    dir: "data_dir" | "data_local_dir" | "cache_dir" | "home_dir" | "app_data_dir";
+    used: string;
+  }[];
+};
+export type NodeReport = {
+  generated_at: string;
+  gui_version: string;
+  os: string;
+  arch: string;
+  chain: string;
+  chain_spec_path: string | null;
+  running: boolean;
+  adopted: boolean;
+  paused: boolean;
+  node: { path: string | null; version: string | null; provenance: ProvenanceCheck | null };
+  external_miner: {
+    path: string | null;
+    version: string | null;
+    provenance: ProvenanceCheck | null;
+    running: boolean;
+    cores: number | null;
+    port: number | null;
+  };
+  peer_id: string | null;
+  rpc_addr: string | null;
+  prometheus_addr: string | null;
+  data_dir: string | null;
+  paths: PathsReport;
+  db_files: number | null;
+  db_bytes: number | null;
+  status: MinerStatus | null;
+  safe_mode: "range" | "adaptive" | null;
+  manual_safe_mode: boolean;
+  restarts: RestartRecord[];
+  // node events dropped as stdout/stderr mirrors this session
+  duplicate_events_suppressed: number;
+  extra_args: string[];
+  settings: Settings;
+};
+export async function getNodeReport(chain?: string): Promise<NodeReport> {
+  return await invoke("get_node_report", { chain });
+}
+/** The same report as Markdown, for "Copy report". */
+export async function getNodeReportMarkdown(chain?: string): Promise<string> {
+  return await invoke("get_node_report_markdown", { chain });
+}
+
+/** Counters for the current node session of a chain (reset on every node start). */
+export type SessionStats = {
+  startedAt: number | null;
+  slowImports: number;
+  maxImportSecs: number | null;
+  importBacklog: number | null;
+  maxImportBacklog: number | null;
+  pressureEpisodes: number;
+  /** Seconds importing in safe mode since the app started (spans node restarts). */
+  safeModeSecs: number;
+  blocksPerMinBeforeSafeMode: number | null;
+  blocksPerMinInSafeMode: number | null;
+  safeModeSlowdown: number | null;
+  /** Authored blocks the bootnode had within a minute, and those it didn't. */
+  propagatedBlocks: number;
+  unpropagatedBlocks: number;
+  avgPropagationMs: number | null;
+  maxPropagationMs: number | null;
+};
+export async function getSessionStats(chain?: string): Promise<SessionStats> {
+  return await invoke("get_session_stats", { chain });
+}
+/** Several slow imports / import queue backlog warnings in a short window: heavy blocks. */
+export type ImportPressure = {
+  chain: string;
+  events: number;
+  window_secs: number;
+  max_import_secs: number | null;
+  import_backlog: number | null;
+  ts: number;
+};
+export function onImportPressure(cb: (p: ImportPressure) => void) {
+  return listen<ImportPressure>("miner:import-pressure", (e) => cb(e.payload));
+}
+/** An automatic safe sync change waiting for `confirmSafeMode` (`outcome` null), or how it ended. */
+export type SafeModeCountdown = {
+  chain: string;
+  enable: boolean;
+  reason: "range" | "adaptive";
+  seconds: number;
+  rangeStart: number | null;
+  rangeEnd: number | null;
+  // UTC ms the change is applied at unless vetoed
+  deadline: number;
+  outcome: "applied" | "vetoed" | "expired" | null;
+  ts: number;
+};
+export function onSafeModeCountdown(cb: (c: SafeModeCountdown) => void) {
+  return listen<SafeModeCountdown>("miner:safe-mode-countdown", (e) => cb(e.payload));
+}
+/** Apply the counting-down safe sync change now (`apply`) or veto it until the node restarts. */
+export async function confirmSafeMode(apply: boolean, chain?: string): Promise<void> {
+  return await invoke("confirm_safe_mode", { chain, apply });
+}
+/** Safe mode set by hand is still on well past all heavy ranges; only a suggestion. */
+export type SafeModeRecommendation = {
+  chain: string;
+  best_block: number;
+  safe_mode_secs: number;
+  blocks_per_min_before: number | null;
+  blocks_per_min_safe: number | null;
+  slowdown: number | null;
+  ts: number;
+};
+export function onSafeModeRecommendation(cb: (r: SafeModeRecommendation) => void) {
+  return listen<SafeModeRecommendation>("miner:safe-mode-recommendation", (e) => cb(e.payload));
+}
+/** Ports a running node listens on (picked by the app, from extra args, or defaults). */
+export type PortAssignments = {
+  chain: string;
+  rpc: number;
+  prometheus: number;
+  p2p: number;
+};
+export async function getPortAssignments(): Promise<PortAssignments[]> {
+  return await invoke("get_port_assignments");
+}
+/** libp2p figures from the node's Prometheus exporter; counters are since the node started. */
+export type PeerMetrics = {
+  inbound_peers: number | null;
+  outbound_peers: number | null;
+  pending_dials: number | null;
+  failed_dials: number | null;
+  handshake_errors: number | null;
+  notification_streams_closed: number | null;
+  nat_suspected: boolean;
+  no_inbound_secs: number | null;
+  // the port to forward when nat_suspected
+  p2p_port: number | null;
+  scraped_at: number | null;
+};
+/** Null until the first read (every 15 s while the node runs). */
+export async function getPeerMetrics(
+  chain?: string,
+): Promise<PeerMetrics | null> {
+  return await invoke("get_peer_metrics", { chain });
+}
+/** Flags a quantus-node binary accepts (from its --help, cached by its hash). */
+export type NodeCapabilities = {
+  binary: string;
+  hash: string;
+  version: string | null;
+  flags: string[];
+  subcommands: Record<string, string[]>;
+  probed_at: number;
+};
+/** Capabilities of `binaryPath` (default: the installed node); hide toggles it can't do. */
+export async function getNodeCapabilities(binaryPath?: string): Promise<NodeCapabilities> {
+  return await invoke("get_node_capabilities", { binaryPath });
+}
+/** Session logs and a running node's database share a nearly full disk: files keep warnings/errors only. */
+export type LogThrottled = {
+  throttled: boolean;
+  logsDir: string;
+  dbDir: string | null;
+  freeBytes: number | null;
+  thresholdBytes: number;
+  ts: number;
+};
+export function onLogThrottled(cb: (t: LogThrottled) => void) {
+  return listen<LogThrottled>("miner:log-throttled", (e) => cb(e.payload));
+}
+/** Write session logs to `dir` (absolute; omitted = default); existing files move in the background. */
+export async function setLogDirectory(dir?: string): Promise<string> {
+  return await invoke("set_log_directory", { dir });
+}
+/** Progress of the move started by `setLogDirectory`; files still being written are skipped. */
+export type LogMoveProgress = {
+  from: string;
+  to: string;
+  moved: number;
+  total: number;
+  skipped: number;
+  bytes: number;
+  done: boolean;
+  error: string | null;
+};
+export function onLogMoveProgress(cb: (p: LogMoveProgress) => void) {
+  return listen<LogMoveProgress>("logs:move-progress", (e) => cb(e.payload));
+}
+/** A problem the backend recognized, with what can be done about it (`remediation.rs`). */
+export type RemediationAction = {
+  action:
+    | "repair"
+    | "unlock"
+    | "enable_safe_mode"
+    | "update_node"
+    | "free_disk_space"
+    | "regenerate_node_key";
+  label: string;
+  /** Invoke this with `args`; null when the action is a link (`url`). */
+  command: string | null;
+  args: Record<string, unknown> | null;
+  url: string | null;
+  available: boolean;
+  blockedBy: string | null;
+};
+export type Remediation = {
+  chain: string;
+  errorKind:
+    | "db_corrupt"
+    | "db_locked"
+    | "runtime_version"
+    | "unsupported_node"
+    | "chain_mismatch"
+    | "duplicate_identity"
+    | "import_pressure"
+    | "low_disk_space";
+  detail: string;
+  actions: RemediationAction[];
+  ts: number;
+};
+export function onRemediation(cb: (r: Remediation) => void) {
+  return listen<Remediation>("miner:remediation", (e) => cb(e.payload));
+}
+/** Run a remediation's command (link actions are opened by the caller). */
+export async function runRemediation(a: RemediationAction, pin?: string): Promise<unknown> {
+  if (!a.command) throw new Error(`${a.action} has no command`);
+  return await invoke(a.command, { ...(a.args ?? {}), pin });
+}
+/** Restart with safe sync on; adaptive safe mode turns it off once imports are healthy. */
+export async function enableSafeMode(chain?: string): Promise<void> {
+  return await invoke("enable_safe_mode", { chain });
+}
+/** Follows a verified FoundBlock: the bootnode had the block, or not within a minute (likely orphaned). */
+export type BlockPropagated = {
+  chain: string;
+  height: number;
+  hash: string;
+  propagated: boolean;
+  propagationMs: number | null;
+  /** The bootnode's block at `height` when it isn't ours. */
+  bootnodeHash: string | null;
+  /** Our node agrees the block was reorged out. */
+  orphaned: boolean;
+  ts: number;
+};
+export function onBlockPropagated(cb: (p: BlockPropagated) => void) {
+  return listen<BlockPropagated>("miner:block-propagated", (e) => cb(e.payload));
+}
+export type Notability = "none" | "info" | "warning" | "critical";
+export type NotableKind =
+  | "found_block"
+  | "orphaned_block"
+  | "unpropagated_block"
+  | "hashrate_drop"
+  | "no_peers"
+  | "node_error"
+  | "node_warning"
+  | "node_exited"
+  | "start_failed"
+  | "fork_detected"
+  | "duplicate_identity"
+  | "remediation"
+  | "sync_complete";
+/** An event the backend rules found notable; drive sounds/toasts from this, not the firehose. */
+export type Notable = {
+  kind: NotableKind;
+  notability: Notability;
+  /** The event it was classified from (which also carries `notability`). */
+  event: string;
+  chain: string | null;
+  message: string;
+  /** Sent by `testNotableEvent`. */
+  test: boolean;
+  ts: number;
+};
+export function onNotable(cb: (n: Notable) => void) {
+  return listen<Notable>("miner:notable", (e) => cb(e.payload));
+}
+export type NotableRules = {
+  levels: Partial<Record<NotableKind, Notability>>;
+  /** Percent below the 10 min peak that counts as a hashrate drop (1-99). */
+  hashrate_drop_pct: number;
+  /** Seconds at 0 peers before it's notable (30 or more). */
+  no_peers_secs: number;
+};
+export async function getNotableRules(): Promise<NotableRules> {
+  return await invoke("get_notable_rules");
+}
+export async function setNotableRules(rules: NotableRules) {
+  return await invoke("set_notable_rules", { rules });
+}
+/** Send a sample `miner:notable` of `kind` at its configured level, to preview it. */
+export async function testNotableEvent(kind: NotableKind, chain?: string): Promise<Notable> {
+  return await invoke("test_notable_event", { kind, chain });
+}
+/** Sent when a stopAfterSecs timer fired, with the session's numbers before the stop. */
+export type TimedStopped = {
+  chain: string;
+  armedAt: number;
+  stopAt: number;
+  authoredBlocks: number;
+  stats: Record<string, number | null>;
+  // false with `error` when the node wasn't running or couldn't be stopped
+  stopped: boolean;
+  error: string | null;
+  ts: number;
+};
+export function onTimedStop(cb: (t: TimedStopped) => void) {
+  return listen<TimedStopped>("miner:timed-stop", (e) => cb(e.payload));
+}
+
+/** With "debug" verbosity: what the parser made of a node output line. */
+export type ParseDebug = {
+  chain: string;
+  source: "stdout" | "stderr";
+  line: string;
+  // the parsed event (its `type` tag), null when the line isn't one
+  parsed: ({ type: string } & Record<string, unknown>) | null;
+  // false for a parsed event dropped as a duplicate
+  forwarded: boolean;
+  ts: number;
+};
+export function onParseDebug(cb: (d: ParseDebug) => void) {
+  return listen<ParseDebug>("miner:parse-debug", (e) => cb(e.payload));
+}
+
+/** A queued webhook POST (outbox.json); retried with backoff until delivered or 24 h old. */
+export type WebhookDelivery = {
+  id: number;
+  url: string;
+  event: string;
+  payload: unknown;
+  queued_at: number;
+  attempts: number;
+  next_attempt_at: number;
+  last_error: string | null;
+};
+export async function getPendingNotifications(): Promise<{
+  entries: WebhookDelivery[];
+  dropped_expired: number;
+  dropped_full: number;
+}> {
+  return await invoke("get_pending_notifications");
+}
+/** Reinstall of a missing quantus-node triggered by start_miner. */
+export type SetupProgress = {
+  stage: "reinstalling" | "installed";
+  path: string;
+};
+export function onSetupProgress(cb: (p: SetupProgress) => void) {
+  return listen<SetupProgress>("setup:progress", (e) => cb(e.payload));
+}
+
+/** Download/install progress of one binary; node and miner may interleave. */
+export type InstallerProgress = {
+  component: "node" | "miner";
+  stage: "fetching_release" | "downloading" | "installing" | "installed" | "failed";
+  downloaded: number;
+  total: number;
+  path: string | null;
+  error: string | null;
+};
+export function onInstallerProgress(cb: (p: InstallerProgress) => void) {
+  return listen<InstallerProgress>("installer:progress", (e) => cb(e.payload));
+}
+
+/** Backend lifecycle phase (see MinerPhase in src-tauri/src/phase.rs). */
+export type MinerPhase =
+  | { phase: "idle" }
+  | { phase: "installing" }
+  | { phase: "starting_node" }
+  | { phase: "waiting_for_rpc" }
+  | { phase: "syncing" }
+  | { phase: "mining" }
+  | { phase: "paused" }
+  | { phase: "restarting"; reason: string }
+  | { phase: "repairing" }
+  | { phase: "stopping" }
+  | { phase: "errored"; kind: string };
+/** How mining was paused: external miner stopped, or node restarted without --validator. */
+export type PauseMode =
+  | { mode: "external_miner"; cores: number; port: number }
+  | { mode: "no_validator" };
+export async function pauseMining(chain?: string): Promise<PauseMode> {
+  return await invoke("pause_mining", { chain });
+}
+export async function resumeMining(chain?: string) {
+  return await invoke("resume_mining", { chain });
+}
+export function onMinerPhase(cb: (p: MinerPhase) => void) {
+  return listen<MinerPhase>("miner:phase", (e) => cb(e.payload));
+}
+export async function getMinerState(): Promise<{
+  phase: MinerPhase;
+  running: boolean;
+  // most recently started chain; the phase follows this one
+  active_chain: string | null;
+  running_chains: string[];
+  // taken over from a previous GUI instance: no log output until restarted
+  adopted_chains: string[];
+  // armed stopAfterSecs timers (wall clock; node restarts don't move them)
+  timed_stops: TimedStop[];
+  // what this frontend is being sent; see setEventVerbosity
+  event_verbosity: EventVerbosity;
+}> {
+  return await invoke("get_miner_state");
+}
+/**
+ * "minimal": only miner:status/phase/state/notable (no log lines; the ring
+ * buffer and session files still get them). "debug": adds miner:parse-debug.
+ * Applies at once; back to "normal" on every app start.
+ */
+export type EventVerbosity = "minimal" | "normal" | "debug";
+export async function setEventVerbosity(level: EventVerbosity): Promise<void> {
+  return await invoke("set_event_verbosity", { level });
+}
+export type TimedStop = {
+  chain: string;
+  // UTC ms
+  armed_at: number;
+  stop_at: number;
+  remaining_secs: number;
+};
+/** Disarm the timed stop (the node keeps running); false when none was armed. */
+export async function cancelTimedStop(chain?: string): Promise<boolean> {
+  return await invoke("cancel_timed_stop", { chain });
+}
+
+/** Why a node was (re)started: `miner:restarting` payload and restart history entry. */
+export type RestartReason =
+  | "manual"
+  | "safe_mode_enable"
+  | "safe_mode_disable"
+  | "repair"
+  | "import"
+  | "unlock"
+  | "local_only_rpc"
+  | "new_node_key"
+  | "pause"
+  | "resume"
+  | "resume_session"
+  | "fork_detected"
+  | "rewards_address_change";
+export type RestartRecord = {
+  chain: string;
+  reason: RestartReason;
+  // trigger detail, e.g. "range" | "adaptive" | "user" | "throttle"; may be empty
+  details: string;
+  // UTC ms
+  ts: number;
+};
+export function onRestarting(cb: (r: RestartRecord) => void) {
+  return listen<RestartRecord>("miner:restarting", (e) => cb(e.payload));
+}
+export async function getRestartHistory(chain?: string): Promise<RestartRecord[]> {
+  return await invoke("get_restart_history", { chain });
+}
+
+/** A node left running by a previous (crashed) GUI instance, found via its pidfile. */
+export type OrphanedNode = {
+  pid: number;
+  start_time: string;
+  chain: string;
+  rpc_url: string;
+  started_at: number;
+};
+export async function getOrphanedNodes(): Promise<OrphanedNode[]> {
+  return await invoke("get_orphaned_nodes");
+}
+/** Manage an orphaned node again (stop/restart/repair); its logs stay unavailable. */
+export async function adoptOrphanedNode(chain: string) {
+  return await invoke("adopt_orphaned_node", { chain });
+}
+/** Leave an orphaned node running unmanaged. */
+export async function dismissOrphanedNode(chain: string) {
+  return await invoke("dismiss_orphaned_node", { chain });
+}
+
 export function onMinerStatus(cb: (s: MinerStatus) => void) {
   return listen<MinerStatus>("miner:status", (e) => cb(e.payload));
 }
 
 export type MinerMeta = {
   binary?: string | null;
+  binaryProvenance?: ProvenanceCheck | null;
   chain?: string | null;
-  rewards_address?: string | null;
+  rewardsAddress?: string | null;
+  paths?: PathsReport | null;
 
   version?: string | null;
-  chain_spec?: string | null;
-  node_name?: string | null;
+  chainSpec?: string | null;
+  nodeName?: string | null;
   role?: string | null;
   database?: string | null;
-  local_identity?: string | null;
-  jsonrpc_addr?: string | null;
-  prometheus_addr?: string | null;
-  highest_known_block?: number | null;
+  localIdentity?: string | null;
+  jsonrpcAddr?: string | null;
+  prometheusAddr?: string | null;
+  highestKnownBlock?: number | null;
+
+  // state_getRuntimeVersion, re-read every 100 blocks
+  specName?: string | null;
+  specVersion?: number | null;
+  implVersion?: number | null;
 
   os?: string | null;
   arch?: string | null;
   target?: string | null;
   cpu?: string | null;
-  cpu_cores?: number | null;
+  cpuCores?: number | null;
   memory?: string | null;
   kernel?: string | null;
   distro?: string | null;
@@ -79,48 +962,846 @@ export function onMinerMeta(cb: (m: MinerMeta) => void) {
  *  - "ext" for external miner
  *  - "node" for quantus-node
  * Prefer external miner path when available; otherwise fall back to node log path.
+ * A null path means file logging was switched off.
  */
 export function onMinerLogFile(
-  cb: (path: string, kind: "ext" | "node") => void,
+  cb: (
+    path: string | null,
+    kind: "ext" | "node",
+    chain: string | null,
+  ) => void,
 ) {
-  return listen<{ path: string; kind?: "ext" | "node" }>(
-    "miner:logfile",
-    (e) => {
-      const kind = e.payload.kind || "node";
-      cb(e.payload.path, kind);
-    },
-  );
+  return listen<{
+    path: string | null;
+    kind?: "ext" | "node";
+    chain?: string | null;
+  }>("miner:logfile", (e) => {
+    const kind = e.payload.kind || "node";
+    cb(e.payload.path, kind, e.payload.chain ?? null);
+  });
 }
 
-export async function startMiner(
+type StartParams = [
   chain: "resonance" | "heisenberg",
   rewardsAddress: string,
   binaryPath: string,
-  extraArgs: string[] = [],
-  logToFile: boolean = false,
+  extraArgs?: string[],
+  logToFile?: boolean,
   externalNumCores?: number,
   externalPort?: number,
+  chainSpecPath?: string,
+  // pass flags like --unsafe-rpc-external ("I know what I'm doing")
+  allowUnsafeArgs?: boolean,
+  // threads for the node's built-in miner; rejected together with the external miner
+  internalMiningThreads?: number,
+  // one-shot: stop the node this many seconds after it started (max 7 days)
+  stopAfterSecs?: number,
+];
+
+function startArgs(
+  ...[
+    chain,
+    rewardsAddress,
+    binaryPath,
+    extraArgs = [],
+    logToFile = false,
+    externalNumCores,
+    externalPort,
+    chainSpecPath,
+    allowUnsafeArgs = false,
+    internalMiningThreads,
+    stopAfterSecs,
+  ]: StartParams
 ) {
+  return {
+    chain,
+    rewards_address: rewardsAddress,
+    binary_path: binaryPath,
+    extra_args: extraArgs,
+    log_to_file: logToFile,
+    external_num_cores: externalNumCores,
+    external_port: externalPort,
+    // raw chain spec JSON for local devnets (overrides chain for the node)
+    chain_spec_path: chainSpecPath,
+    allow_unsafe_args: allowUnsafeArgs,
+    internal_mining_threads: internalMiningThreads,
+    stop_after_secs: stopAfterSecs,
+  };
+}
+
+/**
+ * Split a pasted extra-args line (quotes respected, leading binary name
+ * dropped) and validate it; resolves to the args start_miner would pass.
+ */
+export async function normalizeExtraArgs(
+  line: string,
+  allowUnsafe = false,
+): Promise<string[]> {
+  return await invoke("normalize_extra_args", { line, allowUnsafe });
+}
+
+export async function startMiner(...params: StartParams) {
   try {
-    return await invoke("start_miner", {
-      args: {
-        chain,
-        rewards_address: rewardsAddress,
-        binary_path: binaryPath,
-        extra_args: extraArgs,
-        log_to_file: logToFile,
-        external_num_cores: externalNumCores,
-        external_port: externalPort,
-      },
-    });
+    return await invoke("start_miner", { args: startArgs(...params) });
   } catch (err) {
     console.error("start_miner failed", err);
     throw err;
   }
 }
-export async function stopMiner() {
-  return await invoke("stop_miner");
+
+/** A start as it actually ran (injected safe-mode args, picked ports), per chain. */
+export type SessionCfg = {
+  cfg: {
+    chain: string;
+    rewards_address: string;
+    binary_path: string;
+    extra_args: string[];
+    log_to_file: boolean;
+    external_num_cores: number | null;
+    external_port: number | null;
+    chain_spec_path: string | null;
+  };
+  rpc_port: number | null;
+  prometheus_port: number | null;
+  // "range" | "adaptive" while safe mode was on
+  safe_mode: string | null;
+  manual_safe_mode: boolean;
+  reason: RestartReason;
+  started_at: number; // unix seconds
+};
+export async function getLastSession(chain?: string): Promise<SessionCfg | null> {
+  return await invoke("get_last_session", { chain });
+}
+/** Start `chain` (default: the last started) exactly as it last ran; returns the chain. */
+export async function resumeLastSession(chain?: string): Promise<string> {
+  return await invoke("resume_last_session", { chain });
+}
+
+/** Sent when the OS (antivirus/Gatekeeper) blocked a binary we tried to run. */
+export type QuarantineInfo = {
+  path: string;
+  detail: string;
+  remediation: string;
+  // true: retryAfterUnquarantine can clear it (macOS quarantine xattr)
+  fixable: boolean;
+};
+export function onMinerQuarantined(cb: (q: QuarantineInfo) => void) {
+  return listen<QuarantineInfo>("miner:quarantined", (e) => cb(e.payload));
+}
+/** Same as startMiner, after clearing the quarantine flag from the binaries. */
+export async function retryAfterUnquarantine(...params: StartParams) {
+  return await invoke("retry_after_unquarantine", {
+    args: startArgs(...params),
+  });
+}
+// `chain` defaults to the most recently started chain on the backend.
+export async function getLogfilePath(chain?: string): Promise<string | null> {
+  return await invoke("get_logfile_path", { chain });
+}
+/** A session log file on disk (`list_log_files`). */
+export type LogFile = {
+  path: string;
+  // null for older external miner files (named before logs were kept per chain)
+  chain: string | null;
+  kind: "node" | "ext";
+  size: number;
+  modified: number | null;
+};
+export async function listLogFiles(chain?: string): Promise<LogFile[]> {
+  return await invoke("list_log_files", { chain });
+}
+/** `search_logs` options; every field is optional. */
+export type LogSearchOptions = {
+  mode?: "substring" | "regex";
+  case_sensitive?: boolean;
+  sources?: ("node" | "external" | "ui" | "command")[];
+  // lines without a level of their own count as "info"
+  levels?: ("error" | "warn" | "info" | "debug" | "trace")[];
+  since?: number;
+  until?: number;
+  // a `LogFile.path` (or file name); omitted searches the in-memory buffer
+  file?: string;
+  limit?: number;
+  cursor?: number;
+};
+export type LogMatch = {
+  line_no: number;
+  ts: number | null;
+  source: string | null;
+  level: string | null;
+  text: string;
+};
+export type LogSearchResult = {
+  matches: LogMatch[];
+  // pass back as `cursor` for the next page; null at the end
+  next_cursor: number | null;
+  scanned: number;
+  file: string | null;
+};
+export async function searchLogs(
+  query: string,
+  options?: LogSearchOptions,
+  chain?: string
+): Promise<LogSearchResult> {
+  return await invoke("search_logs", { chain, query, options });
+}
+/** Start (seeded with recent lines) or stop writing the node session log mid-run. */
+export async function setFileLogging(
+  enabled: boolean,
+  chain?: string,
+): Promise<string | null> {
+  return await invoke("set_file_logging", { enabled, chain });
+}
+/** spec_version went up; suggest_update once block import then fails. */
+export type RuntimeUpgrade = {
+  chain: string;
+  spec_name: string | null;
+  from: number;
+  to: number;
+  suggest_update: boolean;
+  error: string | null;
+  latest_node_release: string | null;
+};
+export function onRuntimeUpgraded(cb: (u: RuntimeUpgrade) => void) {
+  return listen<RuntimeUpgrade>("miner:runtime-upgraded", (e) =>
+    cb(e.payload),
+  );
+}
+
+/** A block authored by our rewards account (see authorship.rs). */
+export type AuthoredBlock = {
+  height: number;
+  hash: string;
+  ts: number;
+  orphaned: boolean;
+  /** Milliseconds until the bootnode had it; null if it didn't (or not measured yet). */
+  propagationMs: number | null;
+  propagated: boolean | null;
+};
+export type AuthoredChange = {
+  chain: string;
+  change: "authored" | "orphaned" | "restored";
+  block: AuthoredBlock;
+  // non-orphaned blocks authored this node session
+  count: number;
+};
+export function onMinerAuthored(cb: (c: AuthoredChange) => void) {
+  return listen<AuthoredChange>("miner:authored", (e) => cb(e.payload));
+}
+export async function getAuthoredBlocks(
+  chain?: string,
+): Promise<{ session: number; history: AuthoredBlock[] }> {
+  return await invoke("get_authored_blocks", { chain });
+}
+
+/** Fresh `system_health` + `system_syncState` answer from the local node. */
+export type LocalNodeStatus = {
+  chain: string;
+  rpc_url: string;
+  // false when the node's RPC didn't answer at all
+  reachable: boolean;
+  peers: number | null;
+  is_syncing: boolean | null;
+  current_block: number | null;
+  // the node's own view of the network height
+  highest_block: number | null;
+  sync_percent: number | null;
+  // UTC ms
+  checked_at: number;
+};
+export async function getNodeStatusNow(chain?: string): Promise<LocalNodeStatus> {
+  return await invoke("get_node_status_now", { chain });
+}
+export type ChainConstants = {
+  chain: string;
+  // raw chain units (u128 as a decimal string)
+  existential_deposit: string | null;
+  block_time_ms: number | null;
+  // "TargetBlockTime" | "ExpectedBlockTime" | "MinimumPeriod" (doubled)
+  block_time_constant: string | null;
+  ss58_prefix: number | null;
+  token_symbol: string;
+  token_decimals: number;
+  source: "local" | "bootnode" | "static";
+};
+export async function getChainConstants(chain?: string): Promise<ChainConstants> {
+  return await invoke("get_chain_constants", { chain });
+}
+/** Debug state of the bootnode height probe (`system_syncState` / `chain_getHeader`). */
+export type BootnodeProbe = {
+  url: string;
+  method: "sync_state" | "best_header";
+  attempts: number;
+  successes: number;
+  success_rate: number;
+  connected: boolean | null;
+  last_height: number | null;
+  last_success_ts: number | null;
+  last_error: string | null;
+};
+export async function getBootnodeProbe(chain?: string): Promise<BootnodeProbe | null> {
+  return await invoke("get_bootnode_probe", { chain });
+}
+
+/** The node's RPC is reachable from other machines (--rpc-external etc). */
+export type SecurityWarning = {
+  chain: string;
+  kind: "rpc_exposed";
+  addrs: string[];
+  flags: string[];
+  unsafe_methods: boolean;
+  // what a remote caller can do
+  exposes: string;
+};
+export function onSecurityWarning(cb: (w: SecurityWarning) => void) {
+  return listen<SecurityWarning>("miner:security-warning", (e) =>
+    cb(e.payload),
+  );
+}
+/** Restart without the flags that expose the node's RPC. */
+export async function restartWithSafeRpc(chain?: string) {
+  return await invoke("restart_with_safe_rpc", { chain });
+}
+/** Database wipe during Repair (or finishing an interrupted one on start). */
+export type RepairProgress = {
+  chain: string;
+  stage: "scanning" | "deleting" | "done" | "cancelled";
+  total_files: number;
+  total_bytes: number;
+  removed_files: number;
+  removed_bytes: number;
+  resumed: boolean;
+};
+export function onRepairProgress(cb: (p: RepairProgress) => void) {
+  return listen<RepairProgress>("miner:repair-progress", (e) => cb(e.payload));
+}
+
+/** Copy progress of `importChainData`. */
+export type ImportProgress = {
+  chain: string;
+  stage: "scanning" | "copying" | "done";
+  total_files: number;
+  total_bytes: number;
+  copied_files: number;
+  copied_bytes: number;
+};
+export function onImportProgress(cb: (p: ImportProgress) => void) {
+  return listen<ImportProgress>("miner:import-progress", (e) => cb(e.payload));
+}
+export type ImportResult = {
+  source: string;
+  destination: string;
+  files: number;
+  bytes: number;
+  network_key_imported: boolean;
+  restarted: boolean;
+};
+/**
+ * Import a synced node database copied from another machine. `sourcePath` is a node
+ * data folder (with `chains/<id>`) or its `chains/<id>` folder. The network key is
+ * skipped unless `includeNetworkKey`; an existing database needs `overwrite`.
+ */
+export async function importChainData(
+  sourcePath: string,
+  chain?: string,
+  overwrite = false,
+  includeNetworkKey = false,
+  pin?: string,
+): Promise<ImportResult> {
+  return await invoke("import_chain_data", {
+    sourcePath,
+    chain,
+    overwrite,
+    includeNetworkKey,
+    pin,
+  });
+}
+
+/** One filesystem change a destructive command would make (see `Manifest`). */
+export type PlannedChange = {
+  action: "delete" | "create" | "move" | "copy";
+  path: string;
+  to: string | null;
+  files: number;
+  bytes: number;
+};
+/** What a destructive command would do; returned with `dryRun: true`, nothing touched. */
+export type Manifest = {
+  operation: "repair" | "import_chain_data" | "set_install_dir";
+  chain: string | null;
+  changes: PlannedChange[];
+  restarts_node: boolean;
+  notes: string[];
+};
+/** The plan for `importChainData` with the same arguments; validation errors are thrown. */
+export async function planImportChainData(
+  sourcePath: string,
+  chain?: string,
+  overwrite = false,
+  includeNetworkKey = false,
+): Promise<Manifest> {
+  return await invoke("import_chain_data", {
+    sourcePath,
+    chain,
+    overwrite,
+    includeNetworkKey,
+    dryRun: true,
+  });
+}
+/** The plan for Repair: the database it would delete and whether the node restarts. */
+export async function planRepair(chain?: string): Promise<Manifest> {
+  return await invoke("repair_miner", { chain, dryRun: true });
+}
+/** Wipe the chain's database and restart its node; false when only wiped. */
+export async function repairMiner(
+  chain?: string,
+  opId?: string,
+  pin?: string,
+): Promise<boolean> {
+  return await invoke("repair_miner", { chain, opId, pin });
+}
+
+/** Another node uses this node's network key (data folder copied to a second machine). */
+export type DuplicateIdentity = {
+  chain: string;
+  peer_id: string | null;
+  key_path: string | null;
+  count: number;
+  line: string;
+  explanation: string;
+};
+export function onDuplicateIdentity(cb: (d: DuplicateIdentity) => void) {
+  return listen<DuplicateIdentity>("miner:duplicate-identity", (e) =>
+    cb(e.payload),
+  );
+}
+/** Restart with a new node key; resolves to where the old key was moved. */
+export async function regenerateNodeKey(chain?: string, pin?: string): Promise<string> {
+  return await invoke("regenerate_node_key", { chain, pin });
+}
+export type CheckStatus = "skipped" | "pass" | "warn" | "fail";
+/** `verifyChainData` result; `status` is the worst of `checks`. */
+export type VerifyReport = {
+  chain: string;
+  db_path: string;
+  status: CheckStatus;
+  checks: {
+    name: "db_dir" | "repair_pending" | "lock" | "rocksdb_files" | "session_logs" | "node_check";
+    status: CheckStatus;
+    detail: string;
+    error_kind?: string;
+  }[];
+  remediations: RemediationAction[];
+  ts: number;
+};
+/** Check the chain database with the node stopped (rejects while it runs). */
+export async function verifyChainData(chain?: string): Promise<VerifyReport> {
+  return await invoke("verify_chain_data", { chain });
+}
+/** `compactDatabase` result; sizes in bytes. */
+export type CompactReport = {
+  chain: string;
+  // "rpc": on the running node; "subcommand": quantus-node compact-db, node stopped
+  method: "rpc" | "subcommand";
+  db_path: string;
+  bytes_before: number;
+  bytes_after: number;
+  reclaimed_bytes: number;
+  duration_secs: number;
+  ts: number;
+};
+/**
+ * Compact the chain database. Rejects when the running node has no compaction
+ * RPC (stop it first), the node has no compact-db subcommand, or the disk lacks
+ * room for the temporary copies. Node output arrives as miner:log lines.
+ */
+export async function compactDatabase(chain?: string): Promise<CompactReport> {
+  return await invoke("compact_database", { chain });
+}
+/** Hashrate of the external miner at one core count. */
+export type BenchResult = {
+  cores: number;
+  samples: number;
+  min_hps: number;
+  avg_hps: number;
+  max_hps: number;
+};
+export type BenchmarkProgress = {
+  cores: number;
+  run: number;
+  runs: number;
+  elapsed_secs: number;
+  duration_secs: number;
+  hps: number;
+};
+/** Benchmark the external miner (refused while mining); cores omitted tries several. */
+export async function runBenchmark(
+  durationSecs: number,
+  cores?: number,
+): Promise<BenchResult[]> {
+  return await invoke("run_benchmark", { cores, durationSecs });
+}
+export async function cancelBenchmark() {
+  return await invoke("cancel_benchmark");
+}
+export function onBenchmarkProgress(cb: (p: BenchmarkProgress) => void) {
+  return listen<BenchmarkProgress>("benchmark:progress", (e) => cb(e.payload));
+}
+export async function stopMiner(chain?: string) {
+  return await invoke("stop_miner", { chain });
+}
+/** Allow-listed folders `openPath` can show in the file manager. */
+export type PathKind =
+  | "data_dir"
+  | "chain_db"
+  | "network_key_dir"
+  | "logs_dir"
+  | "app_data_dir"
+  | "account_dir";
+/** Open a known folder (chain-specific kinds: `chain`, else the active one). */
+export async function openPath(
+  kind: PathKind,
+  chain?: string,
+): Promise<string> {
+  return await invoke("open_path", { kind, chain });
+}
+export async function getInstallDir(): Promise<string> {
+  return await invoke("get_install_dir");
+}
+/** Move the binaries to `dir` (null: back to the default) and install there. */
+export async function setInstallDir(dir: string | null): Promise<string> {
+  return await invoke("set_install_dir", { dir });
+}
+/** The plan for `setInstallDir(dir)`: which binaries would move where. */
+export async function planSetInstallDir(dir: string | null): Promise<Manifest> {
+  return await invoke("set_install_dir", { dir, dryRun: true });
+}
+export type BalanceView = {
+  address: string;
+  free: string; // raw plancks
+  reserved: string;
+  symbol: string;
+  decimals: number;
+  // whole tokens with separators, e.g. "1,234.5"
+  freeFormatted: string;
+  reservedFormatted: string;
+};
+/** Cached while fresh (refreshed in the background, see `onBalance`); `force` skips the cache. */
+export async function queryBalance(
+  chain: string,
+  address: string,
+  force = false,
+): Promise<BalanceView> {
+  return await invoke("query_balance", { chain, address, force });
+}
+/** A background refresh after a cached `queryBalance` found a different balance. */
+export type BalanceChanged = {
+  chain: string;
+  address: string;
+  balance: BalanceView;
+  ts: number;
+};
+export function onBalance(cb: (b: BalanceChanged) => void) {
+  return listen<BalanceChanged>("miner:balance", (e) => cb(e.payload));
+}
+/** A transfer to or from an address, from the chain's indexer. */
+export type Transfer = {
+  id: string;
+  direction: "in" | "out";
+  counterparty: string;
+  amount: string; // raw plancks
+  amountFormatted: string;
+  symbol: string;
+  block: number | null;
+  timestamp: number | null;
+  // null for transfers no extrinsic made (minted rewards)
+  extrinsicHash: string | null;
+};
+export type TransferPage = {
+  transfers: Transfer[];
+  // pass back for the next (older) page; null at the end
+  nextCursor: string | null;
+  // false on chains without an indexer (balance totals only)
+  indexed: boolean;
+};
+/** Transfers to and from `address`, newest first; `limit` defaults to 25 (max 100). */
+export async function queryTransfers(
+  chain: string,
+  address: string,
+  cursor?: string,
+  limit?: number,
+): Promise<TransferPage> {
+  return await invoke("query_transfers", { chain, address, cursor, limit });
+}
+/** A transfer behind a balance change the watcher saw, sent once per extrinsic. */
+export type TransferSeen = {
+  chain: string;
+  address: string;
+  transfer: {
+    id: string;
+    direction: "in" | "out";
+    counterparty: string;
+    amount: string;
+    amountFormatted: string;
+    symbol: string;
+    block: number | null;
+    timestamp: number | null;
+    extrinsicHash: string | null;
+  };
+  ts: number;
+};
+export function onTransfer(cb: (t: TransferSeen) => void) {
+  return listen<TransferSeen>("miner:transfer", (e) => cb(e.payload));
+}
+/** The rewards address on `chain` (its SS58 prefix), for QR codes and explorer links. */
+export async function getAccountAddress(chain?: string): Promise<string> {
+  return await invoke("get_account_address", { chain });
+}
+/**
+ * Mine to another address without resyncing: a running node restarts with only
+ * `--rewards-address` changed. Returns the address in the chain's SS58 prefix.
+ */
+export async function setRewardsAddress(
+  address: string,
+  chain?: string,
+  watchPrevious = false,
+): Promise<string> {
+  return await invoke("set_rewards_address", { address, chain, watchPrevious });
+}
+/** The node mines to `address` from now on; sent after its restart. */
+export type RewardsAddressChanged = {
+  chain: string;
+  address: string;
+  previous: string;
+  restarted: boolean;
+  ts: number;
+};
+export function onRewardsAddressChanged(cb: (c: RewardsAddressChanged) => void) {
+  return listen<RewardsAddressChanged>("miner:rewards-address-changed", (e) =>
+    cb(e.payload),
+  );
+}
+/** Raw amount -> whole tokens (exact; `precision` fraction digits, default 6). */
+export async function formatBalance(
+  raw: string,
+  decimals: number,
+  precision?: number,
+): Promise<string> {
+  return await invoke("format_balance", { raw, decimals, precision });
+}
+
+/** A known chain; disabled ones can't be selected or started yet. */
+export type ChainListing = {
+  name: "resonance" | "heisenberg" | "quantus";
+  enabled: boolean;
+  // completes "<name> is not available yet: …"
+  unavailable_reason: string | null;
+  token_symbol: string;
+  has_bootnode: boolean;
+};
+export async function listChains(): Promise<ChainListing[]> {
+  return await invoke("list_chains");
+}
+
+export type DeepLinkIntent = {
+  action: "mine";
+  chain: "resonance" | "heisenberg" | "quantus";
+  rewards_address?: string | null;
+  url: string;
+};
+export function onDeepLink(cb: (intent: DeepLinkIntent) => void) {
+  return listen<DeepLinkIntent>("app:deeplink", (e) => cb(e.payload));
+}
+/** Tell the backend listeners are registered so queued deep links are delivered. */
+export async function frontendReady() {
+  return await invoke("frontend_ready");
+}
+
+export type AppUpdateInfo = {
+  current: string;
+  latest: string;
+  notes: string | null;
+  // installer for this platform, else the release page
+  downloadUrl: string | null;
+  updateAvailable: boolean;
+};
+/** Compare with the latest GUI release (cached for an hour unless `force`). */
+export async function checkAppUpdate(force = false): Promise<AppUpdateInfo> {
+  return await invoke("check_app_update", { force });
+}
+export async function getAppUpdateSettings(): Promise<{
+  auto_check: boolean;
+  last_check: number | null;
+}> {
+  return await invoke("get_app_update_settings");
+}
+export async function setAppUpdateAutoCheck(enabled: boolean) {
+  return await invoke("set_app_update_auto_check", { enabled });
+}
+/** Thermal/load throttle for the external miner (off by default). */
+export type ThrottleSettings = {
+  enabled: boolean;
+  max_temp_c: number;
+  resume_temp_c: number;
+  // used when no CPU temperature is readable
+  max_load_per_cpu: number;
+  resume_load_per_cpu: number;
+  interval_secs: number;
+  min_cores: number;
+  // still too hot at min_cores: pause mining until it cools
+  pause_at_min_cores: boolean;
+};
+export async function getThrottleSettings(): Promise<ThrottleSettings> {
+  return await invoke("get_throttle_settings");
+}
+export async function setThrottleSettings(settings: ThrottleSettings) {
+  return await invoke("set_throttle_settings", { settings });
+}
+/** Adaptive safe sync: enable on repeated slow imports / sync timeouts (on by default). */
+export type AdaptiveSafeModeSettings = {
+  enabled: boolean;
+  // more than max_trouble_events within window_secs while syncing enables safe sync
+  max_trouble_events: number;
+  window_secs: number;
+  // disabled again after this long without trouble
+  healthy_secs: number;
+  // circuit breaker for automatic safe-sync restarts
+  max_auto_restarts: number;
+  restart_window_secs: number;
+};
+export async function getAdaptiveSafeMode(): Promise<AdaptiveSafeModeSettings> {
+  return await invoke("get_adaptive_safe_mode");
+}
+export async function setAdaptiveSafeMode(settings: AdaptiveSafeModeSettings) {
+  return await invoke("set_adaptive_safe_mode", { settings });
+}
+/** External miner crash handling: auto-restart, and what to do once it crash-loops. */
+export type ExternalCrashLoopSettings = {
+  auto_restart: boolean;
+  // max_crashes exits within window_secs is a crash loop
+  max_crashes: number;
+  window_secs: number;
+  // tried in order, one per detected loop
+  remediations: ("skip_work" | "restart_node")[];
+  skip_flag: string;
+};
+export async function getExternalCrashLoopSettings(): Promise<ExternalCrashLoopSettings> {
+  return await invoke("get_external_crashloop_settings");
+}
+export async function setExternalCrashLoopSettings(settings: ExternalCrashLoopSettings) {
+  return await invoke("set_external_crashloop_settings", { settings });
+}
+export type ExternalCrashLoop = {
+  chain: string;
+  crashes: number;
+  window_secs: number;
+  exit_status: string;
+  action: "skip_work" | "restart_node" | "gave_up";
+  detail: string;
+  ts: number;
+};
+export function onExternalCrashloop(cb: (c: ExternalCrashLoop) => void) {
+  return listen<ExternalCrashLoop>("miner:external-crashloop", (e) => cb(e.payload));
+}
+/** Dead-fork detection: local vs bootnode block hash once the node reports synced. */
+export type ForkSettings = {
+  // restart the node on a confirmed fork instead of only suggesting it
+  auto_restart: boolean;
+  // consecutive mismatching checks that confirm a fork
+  mismatches: number;
+  interval_secs: number;
+};
+export async function getForkSettings(): Promise<ForkSettings> {
+  return await invoke("get_fork_settings");
+}
+export async function setForkSettings(settings: ForkSettings) {
+  return await invoke("set_fork_settings", { settings });
+}
+/** Prometheus endpoint at http://<host>:<port>/gui-metrics (off by default). */
+export type GuiMetricsSettings = {
+  enabled: boolean;
+  port: number;
+  // listen on every interface (for a remote Prometheus); else 127.0.0.1 only
+  bind_all: boolean;
+  // export quantus_gui_balance_planck, labelled with a hash of the address
+  include_balance: boolean;
+};
+export async function getGuiMetricsSettings(): Promise<GuiMetricsSettings> {
+  return await invoke("get_gui_metrics_settings");
+}
+/** Rejects when the port is below 1024 or can't be bound. */
+export async function setGuiMetricsSettings(settings: GuiMetricsSettings) {
+  return await invoke("set_gui_metrics_settings", { settings });
+}
+export type ForkDetected = {
+  chain: string;
+  height: number;
+  local_hash: string;
+  network_hash: string;
+  network_height: number | null;
+  checks: number;
+  action: "suggest_restart" | "restarting" | "suggest_repair";
+  ts: number;
+};
+export function onForkDetected(cb: (f: ForkDetected) => void) {
+  return listen<ForkDetected>("miner:fork-detected", (e) => cb(e.payload));
+}
+/** One external miner core-count change by the throttle. */
+export type ThrottleChange = {
+  chain: string;
+  from: number;
+  to: number;
+  configured: number;
+  reason: "hot" | "cooled";
+  // mining was paused at min_cores / resumed after cooling (from/to 0)
+  paused?: boolean;
+  resumed?: boolean;
+  policy: "temperature" | "load";
+  temp_c: number | null;
+  load_per_cpu: number | null;
+  error: string | null;
+};
+export function onThrottle(cb: (t: ThrottleChange) => void) {
+  return listen<ThrottleChange>("miner:throttle", (e) => cb(e.payload));
+}
+export async function getSystemRequirements(): Promise<{
+  os: string;
+  arch: string;
+  cpus: number;
+  max_external_cores: number;
+  throttle: {
+    temperature: boolean;
+    load: boolean;
+    policy: "temperature" | "load" | "unavailable";
+  };
+  cpu_temp_c: number | null;
+  load_per_cpu: number | null;
+}> {
+  return await invoke("get_system_requirements");
+}
+/** The node binary differs from the one the installer recorded; a warning, the node still starts. */
+export type BinaryModified = {
+  chain: string;
+  path: string;
+  sha256: string;
+  expectedSha256: string;
+  releaseTag: string;
+  ts: number;
+};
+export function onBinaryModified(cb: (m: BinaryModified) => void) {
+  return listen<BinaryModified>("miner:binary-modified", (e) => cb(e.payload));
+}
+/** Files copied at startup from the app data directory of an older bundle identifier. */
+export type MigratedData = {
+  from: string;
+  to: string;
+  files: string[];
+  ts: number;
+};
+export function onMigratedData(cb: (m: MigratedData) => void) {
+  return listen<MigratedData>("app:migrated-data", (e) => cb(e.payload));
 }
-export async function queryBalance(chain: string, address: string) {
-  return await invoke("query_balance", { chain, address });
+/** Emitted by the daily background check when a newer GUI is released. */
+export function onAppUpdateAvailable(cb: (info: AppUpdateInfo) => void) {
+  return listen<AppUpdateInfo>("app:update-available", (e) => cb(e.payload));
 }